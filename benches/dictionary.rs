@@ -0,0 +1,122 @@
+//! Benchmarks of looking up guesses in a hash set and in a transducer, and of
+//! loading them from a text list and from a packed one
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wordle::{
+    dictionary::{packed, Dictionary},
+    wordlist,
+};
+
+/// How many words to make up, around the size of a full English word list
+const WORDS: usize = 400_000;
+
+/// Make up `WORDS` distinct five-letter words, spread over all of them
+fn words() -> Vec<String> {
+    let step = 26usize.pow(5) / WORDS;
+    (0..WORDS)
+        .map(|i| {
+            let mut n = i * step;
+            (0..5)
+                .map(|_| {
+                    let letter = (b'a' + (n % 26) as u8) as char;
+                    n /= 26;
+                    letter
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+    let words = words();
+    let list: Vec<&str> = words.iter().map(String::as_str).collect();
+    let hash = Dictionary::new(&list);
+    let fst = Dictionary::fst(&list);
+
+    // the words themselves are the same either way; only the index differs
+    let text: usize = words.iter().map(String::len).sum();
+    println!("{} words, {} bytes of text", list.len(), text);
+    println!("hash set index: {} bytes", hash.index_size());
+    println!("transducer index: {} bytes", fst.index_size());
+
+    // half of the lookups miss, like typos would
+    let queries: Vec<String> = list
+        .iter()
+        .step_by(97)
+        .flat_map(|word| [word.to_string(), format!("{}q", &word[..4])])
+        .collect();
+    let mut group = c.benchmark_group("contains_guess");
+    for (name, dictionary) in [("hash", &hash), ("fst", &fst)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                queries
+                    .iter()
+                    .filter(|word| dictionary.contains_guess(black_box(word)))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("build");
+    group.sample_size(10);
+    group.bench_function("hash", |b| b.iter(|| Dictionary::new(black_box(&list))));
+    group.bench_function("fst", |b| b.iter(|| Dictionary::fst(black_box(&list))));
+    group.finish();
+}
+
+/// Everything needed before the first guess can be checked, from a file
+fn startup(c: &mut Criterion) {
+    let words = words();
+    let list: Vec<&str> = words.iter().map(String::as_str).collect();
+    let dir = std::env::temp_dir().join(format!("wordle-bench-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let text = dir.join("guesses.txt");
+    fs::write(&text, words.join("\n")).unwrap();
+    let pack = dir.join("guesses.pack");
+    fs::write(&pack, packed::pack(&list, 5).unwrap()).unwrap();
+    let pack_fst = dir.join("guesses-fst.pack");
+    fs::write(&pack_fst, packed::pack_with_fst(&list, 5).unwrap()).unwrap();
+
+    let mut group = c.benchmark_group("startup");
+    group.sample_size(10);
+    group.bench_function("text", |b| {
+        b.iter(|| {
+            let words = wordlist::load(&text).unwrap();
+            let list = words.to_vec();
+            Dictionary::new(&list).contains_guess("crane")
+        })
+    });
+    for (name, path) in [("packed", &pack), ("packed-fst", &pack_fst)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let packed = Dictionary::open_packed(path).unwrap();
+                let words = packed.words();
+                let dictionary = packed.dictionary(&words).unwrap();
+                dictionary.contains_guess("crane")
+            })
+        });
+    }
+    group.finish();
+    fs::remove_dir_all(dir).unwrap();
+}
+
+criterion_group!(benches, lookup, startup);
+criterion_main!(benches);
@@ -0,0 +1,152 @@
+//! Generates `static` arrays of the word lists at build time, with the
+//! `codegen-wordlists` feature
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+// the same rules the game checks word lists with
+#[allow(dead_code)]
+#[path = "src/wordlist.rs"]
+mod wordlist;
+
+// what `wordlist` falls back on when a game's lists can't be read, which never
+// happens here: these are the lists being generated
+#[cfg(feature = "embedded-wordlists")]
+#[allow(dead_code)]
+mod embedded {
+    use std::{error::Error, fmt};
+
+    #[derive(Debug)]
+    pub struct EmbeddedError;
+
+    impl fmt::Display for EmbeddedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "the built-in word lists aren't available to the build script"
+            )
+        }
+    }
+
+    impl Error for EmbeddedError {}
+
+    pub fn lists() -> Result<(&'static [&'static str], &'static [&'static str]), EmbeddedError> {
+        Err(EmbeddedError)
+    }
+}
+
+/// The number of letters in each generated word
+const WORD_LENGTH: usize = 5;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_CODEGEN_WORDLISTS").is_none() {
+        return;
+    }
+
+    let manifest = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let guesses = list_path("WORDLE_GUESSES", &manifest.join("wordlists/guesses.txt"));
+    let answers = list_path("WORDLE_ANSWERS", &manifest.join("wordlists/answers.txt"));
+    let (guesses_text, answers_text) = (read(&guesses), read(&answers));
+    let guess_entries = wordlist::entries(&guesses_text);
+    let answer_entries = wordlist::entries(&answers_text);
+
+    let mut failed = false;
+    for (path, entries) in [(&guesses, &guess_entries), (&answers, &answer_entries)] {
+        for issue in wordlist::check(entries, Some(WORD_LENGTH)) {
+            eprintln!("error: {}: {}", path.display(), issue);
+            failed = true;
+        }
+        // letters outside ASCII don't fit in a byte each
+        for entry in entries {
+            if let Some(column) = entry.word.chars().position(|c| !c.is_ascii()) {
+                eprintln!(
+                    "error: {}: line {}, column {}: '{}' has letters that aren't ASCII",
+                    path.display(),
+                    entry.line,
+                    entry.column + column,
+                    entry.word
+                );
+                failed = true;
+            }
+        }
+    }
+    for issue in wordlist::check_subset(&answer_entries, &guess_entries) {
+        eprintln!("error: {}: {}", answers.display(), issue);
+        failed = true;
+    }
+    if failed {
+        process::exit(1);
+    }
+
+    let code = format!(
+        "{}\n{}",
+        array("GUESSES", "acceptable guesses", &guesses, &guess_entries),
+        array(
+            "ANSWERS",
+            "answers, in daily puzzle order",
+            &answers,
+            &answer_entries
+        ),
+    );
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("words_gen.rs");
+    fs::write(&out, code).unwrap_or_else(|e| {
+        eprintln!("error: could not write '{}': {}", out.display(), e);
+        process::exit(1);
+    });
+}
+
+/// The word list named by the environment variable `var`, or `default`
+fn list_path(var: &str, default: &Path) -> PathBuf {
+    println!("cargo:rerun-if-env-changed={}", var);
+    let path = env::var_os(var).map_or_else(|| default.to_owned(), PathBuf::from);
+    println!("cargo:rerun-if-changed={}", path.display());
+    path
+}
+
+/// Read a word list, or exit with an error naming it
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!(
+            "error: could not read word list '{}': {}",
+            path.display(),
+            e
+        );
+        process::exit(1);
+    })
+}
+
+/// The code for a `static` array of the words in a list
+fn array(name: &str, what: &str, path: &Path, entries: &[wordlist::Entry<'_>]) -> String {
+    let mut code = format!(
+        "/// The {}, generated from `{}`\npub static {}: [[u8; {}]; {}] = [\n",
+        what,
+        path.display(),
+        name,
+        WORD_LENGTH,
+        entries.len()
+    );
+    for entry in entries {
+        code.push_str(&format!("    *b\"{}\",\n", entry.word));
+    }
+    code.push_str("];\n");
+    code
+}
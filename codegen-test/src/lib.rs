@@ -0,0 +1,24 @@
+//! Plays with the word lists generated by the `codegen-wordlists` feature,
+//! which needs a crate of its own to turn the feature on
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::generated::{self, ANSWERS, GUESSES};
+
+/// The generated lists of acceptable guesses and answers, to play with
+pub fn lists() -> (Vec<&'static str>, Vec<&'static str>) {
+    (generated::words(&GUESSES), generated::words(&ANSWERS))
+}
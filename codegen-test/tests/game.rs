@@ -0,0 +1,43 @@
+//! Playing a game with the generated word lists
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{GameStatus, GuessError, Wordle};
+
+#[test]
+fn test_game() {
+    let (guesses, answers) = wordle_codegen_test::lists();
+    // without WORDLE_GUESSES and WORDLE_ANSWERS, the built-in lists are used
+    let builtin = include_str!("../../wordlists/guesses.txt");
+    assert_eq!(builtin.lines().collect::<Vec<_>>(), guesses);
+
+    let mut game = Wordle::builder()
+        .guesses(&guesses)
+        .answers(&answers)
+        .seed(7)
+        .build()
+        .unwrap()
+        .choose_word();
+    let answer = game.transcript().answer.unwrap();
+    assert!(answers.contains(&answer.as_str()));
+
+    assert_eq!(Err(GuessError::NotInWordList), game.guess("zzzzz"));
+    let other = guesses.iter().find(|&&word| word != answer).unwrap();
+    game.guess(other).unwrap();
+    game.guess(&answer).unwrap();
+    assert_eq!(GameStatus::Won { guesses: 2 }, game.status());
+    assert_eq!(answer, game.finish().unwrap().reveal());
+}
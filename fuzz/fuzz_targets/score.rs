@@ -0,0 +1,70 @@
+//! Fuzz scoring guesses: it never panics, and the feedback always makes sense
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![no_main]
+
+use std::collections::HashMap;
+
+use libfuzzer_sys::fuzz_target;
+use wordle::{try_score_guess, LetterStatus};
+
+/// The number of letters the words have in common, counting repeats
+fn shared_letters(guess: &str, answer: &str) -> usize {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for letter in answer.chars() {
+        *counts.entry(letter).or_default() += 1;
+    }
+    guess
+        .chars()
+        .filter(|letter| match counts.get_mut(letter) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .count()
+}
+
+// the guess and answer are separated by the first newline
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let (guess, answer) = text.split_once('\n').unwrap_or((&text, ""));
+
+    assert_eq!(
+        Ok(vec![LetterStatus::Correct; answer.chars().count()]),
+        try_score_guess(answer, answer),
+        "a word scored against itself is all green"
+    );
+    let Ok(feedback) = try_score_guess(guess, answer) else {
+        assert_ne!(guess.chars().count(), answer.chars().count());
+        return;
+    };
+    assert_eq!(answer.chars().count(), feedback.len());
+
+    let shared = shared_letters(guess, answer);
+    let greens = feedback
+        .iter()
+        .filter(|&&status| status == LetterStatus::Correct)
+        .count();
+    let colored = feedback
+        .iter()
+        .filter(|&&status| status != LetterStatus::NotInWord)
+        .count();
+    assert!(greens <= shared, "more greens than shared letters");
+    assert_eq!(shared, colored, "every shared letter is green or yellow");
+});
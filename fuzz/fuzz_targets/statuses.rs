@@ -0,0 +1,47 @@
+//! Fuzz reading feedback as letters and squares: it never panics, and what's read can be written back
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wordle::codec;
+
+fuzz_target!(|text: &str| {
+    if let Ok(statuses) = codec::statuses_from_string(text) {
+        assert_eq!(text.chars().count(), statuses.len());
+        let written = codec::statuses_to_string(&statuses);
+        assert_eq!(
+            Ok(&statuses),
+            codec::statuses_from_string(&written).as_ref()
+        );
+    }
+    if let Ok(statuses) = codec::statuses_from_emoji(text) {
+        let written = codec::statuses_to_emoji(&statuses);
+        assert_eq!(Ok(&statuses), codec::statuses_from_emoji(&written).as_ref());
+    }
+    if let Ok(statuses) = codec::parse_statuses(text) {
+        let written = codec::statuses_to_emoji(&statuses);
+        assert_eq!(Ok(&statuses), codec::parse_statuses(&written).as_ref());
+        if statuses.len() <= 20 {
+            let packed = codec::statuses_to_packed(&statuses);
+            assert_eq!(
+                Ok(&statuses),
+                codec::statuses_from_packed(packed, statuses.len()).as_ref()
+            );
+        }
+    }
+});
@@ -0,0 +1,65 @@
+//! Fuzz resuming saved games: a transcript that can be read never makes a game panic
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wordle::{
+    board::BoardStyle, messages::Messages, share, transcript::Transcript, GameStatus, Wordle,
+};
+
+/// The word lists from the integration tests, small enough that the fuzzer
+/// can find words in them
+const GUESSES: &str = include_str!("../../tests/data/guesses.txt");
+/// The answers from the integration tests
+const ANSWERS: &str = include_str!("../../tests/data/answers.txt");
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(transcript) = serde_json::from_slice::<Transcript>(data) else {
+        return;
+    };
+    let guesses: Vec<&str> = GUESSES.lines().collect();
+    let answers: Vec<&str> = ANSWERS.lines().collect();
+    let Ok(mut game) = Wordle::new(&guesses, &answers).resume(&transcript) else {
+        return;
+    };
+
+    // a resumed game is the one that was saved, apart from the times
+    let resumed = game.transcript();
+    assert_eq!(transcript.guesses.len(), resumed.guesses.len());
+    assert_eq!(transcript.status, resumed.status);
+    for style in [BoardStyle::Emoji, BoardStyle::Letters, BoardStyle::Grid] {
+        let _ = game.board(style).to_string();
+    }
+    let _ = share::share_text(
+        Messages::english(),
+        &game,
+        transcript.puzzle,
+        &share::TileSet::default(),
+    );
+    let _ = game.keyboard();
+
+    // and it carries on from where it was left off
+    if game.status() == GameStatus::InProgress {
+        let _ = game.hint();
+        for word in &guesses {
+            let _ = game.guess(word);
+            // saving again mustn't trip over the times it was resumed with
+            let _ = game.transcript();
+        }
+    }
+});
@@ -0,0 +1,41 @@
+//! Fuzz reading word lists from arbitrary file contents: it never panics
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wordle::wordlist::{self, Order};
+
+fuzz_target!(|text: &str| {
+    let entries = wordlist::entries(text);
+    for entry in &entries {
+        assert!(!entry.word.is_empty());
+        assert_eq!(entry.word, entry.word.trim());
+    }
+    let _ = wordlist::check(&entries, Some(5));
+    let _ = wordlist::check_subset(&entries, &entries[entries.len() / 2..]);
+    let sorted = wordlist::normalize(&entries, Order::Sorted);
+    assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+    let _ = wordlist::filter(&entries, 5, None, 0.0);
+    let _ = wordlist::frequencies(text);
+
+    if let Ok(words) = wordlist::parse_array(text, None) {
+        assert!(words
+            .iter()
+            .all(|word| word.chars().all(char::is_alphabetic)));
+    }
+});
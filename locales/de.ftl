@@ -0,0 +1,310 @@
+# German
+
+## Why a guess was rejected
+
+rejection = „{ $guess }“ ist ungültig: { $reason }
+rejection-too-short = zu kurz — { $expected } Buchstaben nötig, nicht { $actual }
+rejection-too-long = zu lang — { $expected } Buchstaben nötig, nicht { $actual }
+rejection-invalid-letter = „{ $letter }“ ist kein Buchstabe
+rejection-not-in-word-list = nicht in der Wortliste
+rejection-already-guessed = { $guess } wurde schon bei Versuch { $turn } probiert
+rejection-game-over = das Spiel ist vorbei
+rejection-no-word = es wurde noch kein Wort gewählt
+
+## Hard mode
+
+hard-mode-missing-correct = Der { $position }. Buchstabe muss { $letter } sein
+hard-mode-missing-present = Das Wort muss { $letter } enthalten
+
+## Hints
+
+hint-present = Das Wort enthält { $letter }
+hint-position = Der { $position }. Buchstabe ist { $letter }
+hint-no-hints-left = Keine Tipps mehr
+hint-nothing-to-reveal = Du kennst schon alle Buchstaben
+hint-game-over = Das Spiel ist vorbei
+
+## The end of a game
+
+praise = { $guesses ->
+        [1] Genial!
+        [2] Großartig!
+        [3] Beeindruckend!
+        [4] Prima!
+        [5] Gut gemacht!
+       *[other] Puh!
+    }
+consolation = Das war knifflig! Das Wort war { $answer }
+streak = Serie: { $current } { $current ->
+        [one] Sieg
+       *[other] Siege
+    } (Rekord: { $max })
+streak-ended = Damit endet deine Serie von { $streak } { $streak ->
+        [one] Sieg
+       *[other] Siegen
+    }
+
+## Descriptions for screen readers
+
+letter-correct = richtige Stelle
+letter-present = im Wort, falsche Stelle
+letter-no-more = nicht öfter im Wort
+letter-absent = nicht im Wort
+letter-described = { $letter } — { $meaning }.
+tally = { $correct } richtig, { $present } enthalten, { $absent } nicht enthalten
+guess-described = { $guess }: { $letters } { $tally }.
+spoken-guess = Versuch { $number }, { $description }
+guesses-left = { $count ->
+        [one] Noch 1 Versuch.
+       *[other] Noch { $count } Versuche.
+    }
+keyboard-correct = Richtig: { $letters }.
+keyboard-present = Im Wort: { $letters }.
+keyboard-absent = Nicht im Wort: { $letters }.
+keyboard-empty = Noch keine Buchstaben geraten.
+
+## Sharing
+
+share-hints = { $count ->
+        [one] 1 Tipp
+       *[other] { $count } Tipps
+    }
+share-assisted = mit Hilfe
+
+## Possible answers
+
+remaining = Noch mögliche Lösungen: { $remaining } (vorher { $before })
+narrowed-down = Mögliche Lösungen nach Versuch { $turn }: { $answers }
+
+## Starting and finishing games
+
+banner-hard-mode = schwerer Modus
+boards-started = Spiel { $number } (Seed { $seed }, { $boards } Felder) gestartet
+chain-started = Kette { $number } (Seed { $seed }, { $games } Spiele) gestartet
+reverse-started = Umgekehrtes Wordle Nr. { $puzzle } gestartet
+daily-started = Wordle Nr. { $puzzle } ({ $date }) gestartet
+daily-resumed = Wordle Nr. { $puzzle } fortgesetzt
+daily-over = Wordle Nr. { $puzzle } ist vorbei
+game-started = Spiel { $number } von Seed { $seed } gestartet
+game-resumed = Spiel { $number } von Seed { $seed } fortgesetzt
+seed-resumed = Spiel (Seed { $seed }) fortgesetzt
+practice-started = Übungsspiel gestartet
+practice-resumed = Übungsspiel fortgesetzt
+game-over = Dieses Spiel ist vorbei
+already-played = Du hast Wordle Nr. { $puzzle } heute schon gespielt; komm morgen wieder!
+opened-earlier = Eröffnet wurde mit { $guess }
+opened-with = Eröffnet mit { $guess }
+next-wordle = Nächstes Wordle in { $time }
+found = { $answer } in { $guesses }/{ $max } gefunden
+the-word-was = Das Wort war { $answer }
+won-games = { $won } von { $played } Spielen gewonnen
+won-chains = { $won } von { $played } Ketten gewonnen
+time = Zeit: { $time }
+time-per-guess = Zeit: { $time } (pro Versuch: { $splits })
+personal-best = Das ist eine neue persönliche Bestzeit!
+boards-no-stats = Spiele mit mehr als einem Feld zählen nicht zu deiner Statistik
+chain-no-stats = Ketten von Spielen zählen nicht zu deiner Statistik
+other-day-no-stats = Das war das Rätsel vom { $date }, also zählt es nicht zu deiner Statistik
+practice-word-no-stats = Das war ein Übungswort, also zählt es nicht zu deiner Statistik
+practice-game-no-stats = Das war ein Übungsspiel, also zählt es nicht zu deiner Statistik
+stats-hard-mode = Schwerer Modus:
+computing-openers = Die besten ersten Versuche werden berechnet...
+
+## Commands during a game
+
+time-up = Die Zeit ist um! Das Limit war { $time }
+daily-give-up = Rate mindestens einmal, bevor du das Tagesrätsel aufgibst
+saved-game = Spiel in „{ $path }“ gespeichert
+save-exists = „{ $path }“ gibt es schon; speichere das Spiel woanders
+save-failed = Das Spiel konnte nicht gespeichert werden: { $error }
+copy-later = Der Text zum Teilen kann kopiert werden, sobald das Spiel vorbei ist
+hint = Tipp: { $hint } (noch { $count })
+unknown-command = Unbekannter Befehl „{ $command }“
+game-saved-resume = Spiel gespeichert — starte mit --resume, um weiterzuspielen
+quit-without-saving = Beenden ohne zu speichern
+copied = Der Text zum Teilen wurde in die Zwischenablage kopiert
+copy-failed = Kopieren in die Zwischenablage fehlgeschlagen ({ $error }); kopiere den Text zum Teilen stattdessen von hier:
+transcripts-taken = zu viele Protokolle in „{ $path }“ heißen schon wie dieses
+
+## Prompts, which are followed by the player's answer
+
+prompt-guess = Versuch { $turn }/{ $max }:
+prompt-guess-timed = Versuch { $turn }/{ $max } [{ $time }]:
+prompt-chain-guess = Spiel { $number }/{ $games }, Versuch { $turn }/{ $max }:
+prompt-row = Zeile { $row }/{ $rows }:
+prompt-give-up = Aufgeben und das Wort zeigen? [y/N]
+prompt-give-up-boards = Aufgeben und die Wörter zeigen? [y/N]
+prompt-give-up-reverse = Aufgeben und passende Wörter zeigen? [y/N]
+prompt-play-again = Noch einmal spielen? [Y/n]
+
+## The full-screen view
+
+tui-help = Tippe einen Versuch und drücke Enter; Esc für das Menü
+tui-menu = q: beenden   g: aufgeben   Esc: zurück zum Spiel
+tui-too-short = Nicht genug Buchstaben
+tui-won = In { $guesses } geschafft! Drücke eine Taste, um weiterzumachen
+tui-lost = Das Wort war { $answer }. Drücke eine Taste, um weiterzumachen
+tui-guesses-left = Verbleibende Versuche: { $count }
+
+## Several boards at once
+
+boards-won = Alle { $boards } Felder in { $guesses }/{ $max } gelöst!
+boards-lost = { $solved } von { $boards } Feldern gelöst
+boards-solved-on = Feld { $number }: { $answer }, gelöst im { $turn }. Versuch
+boards-not-solved = Feld { $number }: { $answer }, nicht gelöst
+board = Feld { $number }
+board-solved = Feld { $number }: gelöst in { $guesses }
+board-not-solved = Feld { $number }: nicht gelöst
+board-keyboard = Feld { $number }:
+boards-no-hints = Tipps gibt es nicht bei mehr als einem Feld
+boards-no-save = Spiele mit mehr als einem Feld können nicht gespeichert werden
+
+## Chains of games
+
+chain-won = Alle { $games } Spiele mit { $guesses } eigenen Versuchen gelöst!
+chain-lost = { $solved } von { $games } Spielen gelöst
+chain-solved-in = Spiel { $number }: { $answer }, gelöst in { $guesses }/{ $max }
+chain-not-solved = Spiel { $number }: { $answer }, nicht gelöst
+chain-game = Spiel { $number } von { $games }
+chain-game-carried-over = Spiel { $number } von { $games }, beginnend mit der letzten Lösung, { $answer }
+chain-no-hints = Tipps gibt es nicht in einer Kette
+chain-copy-later = Der Text zum Teilen kann kopiert werden, sobald die Kette vorbei ist
+chain-no-save = Ketten von Spielen können nicht gespeichert werden
+
+## Reverse puzzles
+
+reverse-target = Finde Wörter, die gegen { $answer } diese Farben ergeben:
+reverse-mismatch = { $guess } ergibt stattdessen { $tiles }
+reverse-no-such-row = Es gibt nur { $rows } Zeilen
+reverse-hint = { $count ->
+        [one] Nur ein Wort passt in Zeile { $row }
+       *[other] { $count } Wörter passen in Zeile { $row }
+    }
+reverse-solved = Gelöst!
+reverse-would-fit = Wörter, die gepasst hätten:
+reverse-fits = { $others ->
+        [0] Zeile { $row }: { $word }
+        [one] Zeile { $row }: { $word } (oder 1 anderes)
+       *[other] Zeile { $row }: { $word } (oder { $others } andere)
+    }
+reverse-no-copy = Umgekehrte Rätsel können nicht geteilt werden
+reverse-no-save = Umgekehrte Rätsel können nicht gespeichert werden
+
+## Racing the bot
+
+bot-board = Das Feld des Bots:
+vs-bot-won = Du hast den Bot geschlagen!
+vs-bot-lost = Der Bot hat dich geschlagen.
+vs-bot-tied = Unentschieden gegen den Bot, beide in { $guesses }.
+vs-bot-neither = Keiner von euch hat das Wort gefunden.
+
+## Stats and the keyboard
+
+stats-summary = Gespielt: { $played }  Gewonnen: { $percentage } %  Aktuelle Serie: { $current }  Längste Serie: { $max }
+stats-assisted = Mit Hilfe: { $count }
+stats-best-time = Persönliche Bestzeit: { $time }
+guess-distribution = Verteilung der Versuche:
+keyboard-in-word = Im Wort: { $letters }
+keyboard-eliminated = Ausgeschlossen: { $letters }
+
+## Suggestions in assist mode
+
+suggestions = { $count ->
+        [one] Noch 1 mögliche Lösung; versuche { $words }
+       *[other] Noch { $count } mögliche Lösungen; versuche { $words }
+    }
+suggestion = { $word } ({ $bits } Bit)
+could-be-answer = könnte die Lösung sein
+cannot-be-answer = kann nicht die Lösung sein
+explanation = { $word }: { $verdict }; lässt im Schnitt { $average } Lösungen übrig, höchstens { $worst }
+likeliest-colors = wahrscheinlichste Farben: { $patterns }
+
+## Files and settings
+
+saved-config = Einstellungen in „{ $path }“ gespeichert
+saved-transcript = Protokoll in „{ $path }“ gespeichert
+no-languages = Keine Sprachen in „{ $path }“ gefunden
+exported = { $count } { $count ->
+        [one] Spiel
+       *[other] Spiele
+    } nach „{ $path }“ exportiert
+merged = { $added } { $added ->
+        [one] Spiel
+       *[other] Spiele
+    } aus „{ $path }“ hinzugefügt, { $skipped } schon erfasste übersprungen
+builtin-themes = Die eingebauten Themen sind: { $names }
+builtin-locales = Die eingebauten Sprachen sind: { $names }
+word-list-help = Gib mit --guesses und --answers die Pfade zu den Wortlisten an
+language-help = Mit --lang list siehst du die Sprachen dort, mit --dict-dir kannst du woanders suchen
+notice-builtin-word-lists = Hinweis: Wortliste „{ $path }“ konnte nicht gelesen werden ({ $error }); die eingebauten Wortlisten werden verwendet
+
+## The leaderboard
+
+leaderboard-rank = Du bist Nr. { $rank } auf der Bestenliste, als { $player }
+outbox-sent = { $count } { $count ->
+        [one] früher aufbewahrtes Ergebnis
+       *[other] früher aufbewahrte Ergebnisse
+    } an die Bestenliste gesendet
+leaderboard = Bestenliste für Wordle Nr. { $puzzle } ({ $date }):
+leaderboard-empty = Noch keine Ergebnisse
+leaderboard-entry = { $guesses } in { $time }
+api-rejected = der Server hat es abgelehnt: { $reason }
+api-unavailable = der Server ist nicht erreichbar: { $reason }
+
+## Errors
+
+error-no-config-dir = Fehler: kein Konfigurationsverzeichnis gefunden
+error-no-data-dir = Fehler: kein Datenverzeichnis gefunden
+error-no-stats-dir = Fehler: kein Ort für die Statistik gefunden; verwende --stats-dir
+error-write-config = Fehler: Konfigurationsdatei „{ $path }“ konnte nicht geschrieben werden: { $error }
+error-invalid-config = Fehler: ungültige Konfigurationsdatei „{ $path }“: { $error }
+error-read-theme = Fehler: Thema „{ $name }“ konnte nicht gelesen werden: { $error }
+error-read-locale = Fehler: Sprache „{ $name }“ konnte nicht gelesen werden: { $error }
+error-read-dict-dir = Fehler: Wörterbuchverzeichnis „{ $path }“ konnte nicht gelesen werden: { $error }
+error-no-language = Fehler: keine Wortlisten für die Sprache „{ $code }“ in „{ $path }“; erwartet werden guesses.{ $code }.txt und answers.{ $code }.txt
+error-read-word-list = Fehler: Wortliste „{ $path }“ konnte nicht gelesen werden: { $error }
+error-invalid-word-list = Fehler: ungültige Wortliste „{ $path }“: { $error }
+error-builtin-word-lists = Fehler: die eingebauten Wortlisten können nicht verwendet werden: { $error }
+error-word-list-length = Fehler: die Wortlisten passen nicht zu --length { $length }: { $error }
+error-invalid-word-lists = Fehler: ungültige Wortlisten: { $error }
+error-no-builtin-word-lists = Fehler: dieser Build enthält keine eingebauten Wortlisten
+error-invalid-first-guess = Fehler: der erste Versuch kann nicht gespielt werden: { $error }
+error-invalid-answer = Fehler: die Lösung kann nicht gespielt werden: { $error }
+error-read-guesses = Fehler: Versuche konnten nicht gelesen werden: { $error }
+error-input-ended = Fehler: die Eingabe endete, bevor das Spiel vorbei war
+error-no-puzzle-today = Fehler: heute gibt es kein Rätsel; das erste ist am { $date }
+error-no-puzzle = Fehler: für den { $date } gibt es kein Rätsel; das erste war am { $first }
+error-terminal = Fehler: das Terminal kann nicht verwendet werden: { $error }
+error-export-format = Fehler: unklar, in welchem Format „{ $path }“ exportiert werden soll; verwende --format csv oder --format json
+error-read-log = Fehler: Spielprotokoll „{ $path }“ konnte nicht gelesen werden: { $error }
+error-write = Fehler: „{ $path }“ konnte nicht geschrieben werden: { $error }
+error-read-stats = Fehler: Statistik aus „{ $path }“ konnte nicht gelesen werden: { $error }
+error-save-stats = Fehler: Statistik konnte nicht in „{ $path }“ gespeichert werden: { $error }
+error-no-saved-game = Fehler: es gibt kein gespeichertes Spiel zum Fortsetzen; Spiele werden bei Strg-C in „{ $path }“ gespeichert
+error-read-saved-game = Fehler: gespeichertes Spiel „{ $path }“ konnte nicht gelesen werden: { $error }
+error-invalid-saved-game = Fehler: ungültiges gespeichertes Spiel „{ $path }“: { $error }
+error-resume = Fehler: gespeichertes Spiel „{ $path }“ konnte nicht fortgesetzt werden: { $error }
+error-save-game = Fehler: das Spiel konnte nicht gespeichert werden: { $error }
+
+## Warnings
+
+warning = Warnung: { $error }
+warning-ctrl-c = Warnung: Strg-C kann nicht abgefangen werden: { $error }
+warning-unknown-setting = Warnung: unbekannte Einstellung „{ $key }“ in der Konfigurationsdatei „{ $path }“
+warning-read-definitions = Warnung: Definitionen aus „{ $path }“ konnten nicht gelesen werden: { $error }
+warning-corrupt-stats = Warnung: die Statistikdatei „{ $path }“ ist beschädigt ({ $error }); sie wurde nach „{ $backup }“ verschoben
+warning-move-stats = Warnung: beschädigte Statistikdatei „{ $path }“ konnte nicht verschoben werden: { $error }
+warning-read-stats = Warnung: Statistik aus „{ $path }“ konnte nicht gelesen werden: { $error }
+warning-save-stats = Warnung: Statistik konnte nicht in „{ $path }“ gespeichert werden: { $error }
+warning-log-game = Warnung: das Spiel konnte nicht in „{ $path }“ protokolliert werden: { $error }
+warning-save-transcript = Warnung: das Protokoll konnte nicht gespeichert werden: { $error }
+warning-remove-saved-game = Warnung: gespeichertes Spiel „{ $path }“ konnte nicht entfernt werden: { $error }
+warning-merge-conflict = Warnung: Wordle Nr. { $puzzle } ist in beiden unterschiedlich erfasst; der frühere Eintrag vom { $date } wurde behalten
+warning-outbox-rejected = Warnung: ein früher aufbewahrtes Ergebnis wurde nicht in die Bestenliste aufgenommen: { $error }
+warning-outbox-send = Warnung: die in „{ $path }“ aufbewahrten Ergebnisse konnten nicht gesendet werden: { $error }
+warning-result-rejected = Warnung: das Ergebnis konnte nicht in die Bestenliste aufgenommen werden: { $error }
+warning-result-kept = Warnung: { $error }; das Ergebnis wird beim nächsten Spiel mit --server gesendet
+warning-result-lost = Warnung: { $error }, und das Ergebnis konnte nicht für später aufbewahrt werden: { $reason }
+warning-leaderboard = Warnung: die Bestenliste konnte nicht abgerufen werden: { $error }
+warning-server-keyed = Warnung: { $path } wählt seine eigenen Tageswörter, also kann das hier gespielte Tagesrätsel nicht in seine Bestenliste, und Ergebnisse werden nicht an ihn gesendet
+warning-line-editing = Warnung: Zeilenbearbeitung ist nicht verfügbar: { $error }
@@ -0,0 +1,312 @@
+# English, the built-in default; every other locale falls back to these
+# messages for any it leaves out.
+
+## Why a guess was rejected
+
+rejection = '{ $guess }' is not valid: { $reason }
+rejection-too-short = too short — need { $expected } letters, got { $actual }
+rejection-too-long = too long — need { $expected } letters, got { $actual }
+rejection-invalid-letter = '{ $letter }' isn't a letter
+rejection-not-in-word-list = not in word list
+rejection-already-guessed = you already tried { $guess } on guess { $turn }
+rejection-game-over = the game is over
+rejection-no-word = no word has been chosen
+
+## Hard mode
+
+hard-mode-missing-correct = { NUMBER($position, type: "ordinal") ->
+        [one] { $position }st
+        [two] { $position }nd
+        [few] { $position }rd
+       *[other] { $position }th
+    } letter must be { $letter }
+hard-mode-missing-present = Guess must contain { $letter }
+
+## Hints
+
+hint-present = The word contains { $letter }
+hint-position = The { NUMBER($position, type: "ordinal") ->
+        [one] { $position }st
+        [two] { $position }nd
+        [few] { $position }rd
+       *[other] { $position }th
+    } letter is { $letter }
+hint-no-hints-left = No hints left
+hint-nothing-to-reveal = You already know every letter
+hint-game-over = The game is over
+
+## The end of a game
+
+praise = { $guesses ->
+        [1] Genius!
+        [2] Magnificent!
+        [3] Impressive!
+        [4] Splendid!
+        [5] Great!
+       *[other] Phew!
+    }
+consolation = Tough one! The word was { $answer }
+streak = Streak: { $current } { $current ->
+        [one] win
+       *[other] wins
+    } (best: { $max })
+streak-ended = That ends your streak of { $streak } { $streak ->
+        [one] win
+       *[other] wins
+    }
+
+## Descriptions for screen readers
+
+letter-correct = correct position
+letter-present = in the word, wrong position
+letter-no-more = no more in the word
+letter-absent = not in the word
+letter-described = { $letter } — { $meaning }.
+tally = { $correct } correct, { $present } present, { $absent } absent
+guess-described = { $guess }: { $letters } { $tally }.
+spoken-guess = Guess { $number }, { $description }
+guesses-left = { $count ->
+        [one] 1 guess left.
+       *[other] { $count } guesses left.
+    }
+keyboard-correct = Correct: { $letters }.
+keyboard-present = In the word: { $letters }.
+keyboard-absent = Not in the word: { $letters }.
+keyboard-empty = No letters guessed yet.
+
+## Sharing
+
+share-hints = { $count ->
+        [one] 1 hint
+       *[other] { $count } hints
+    }
+share-assisted = assisted
+
+## Possible answers
+
+remaining = Possible answers remaining: { $remaining } (was { $before })
+narrowed-down = Possible answers after guess { $turn }: { $answers }
+
+## Starting and finishing games
+
+banner-hard-mode = hard mode
+boards-started = Game { $number } (seed { $seed }, { $boards } boards) started
+chain-started = Chain { $number } (seed { $seed }, { $games } games) started
+reverse-started = Reverse Wordle #{ $puzzle } started
+daily-started = Wordle #{ $puzzle } ({ $date }) started
+daily-resumed = Wordle #{ $puzzle } resumed
+daily-over = Wordle #{ $puzzle } is over
+game-started = Game { $number } of seed { $seed } started
+game-resumed = Game { $number } of seed { $seed } resumed
+seed-resumed = Game (seed { $seed }) resumed
+practice-started = Practice game started
+practice-resumed = Practice game resumed
+game-over = This game is over
+already-played = You've already played Wordle #{ $puzzle } today; come back tomorrow!
+opened-earlier = It was opened with { $guess }
+opened-with = Opened with { $guess }
+next-wordle = Next Wordle in { $time }
+found = Found { $answer } in { $guesses }/{ $max }
+the-word-was = The word was { $answer }
+won-games = Won { $won } of { $played } games
+won-chains = Won { $won } of { $played } chains
+time = Time: { $time }
+time-per-guess = Time: { $time } (per guess: { $splits })
+personal-best = That's a new personal best!
+boards-no-stats = Games with more than one board don't count towards your stats
+chain-no-stats = Chains of games don't count towards your stats
+other-day-no-stats = This was the puzzle for { $date }, so it doesn't count towards your stats
+practice-word-no-stats = This was a practice word, so it doesn't count towards your stats
+practice-game-no-stats = This was a practice game, so it doesn't count towards your stats
+stats-hard-mode = Hard mode:
+computing-openers = Computing the best first guesses...
+
+## Commands during a game
+
+time-up = Time's up! The limit was { $time }
+daily-give-up = Make at least one guess before giving up on the daily puzzle
+saved-game = Saved the game to '{ $path }'
+save-exists = '{ $path }' already exists; save the game somewhere else
+save-failed = Could not save the game: { $error }
+copy-later = The share text can be copied once the game is over
+hint = Hint: { $hint } ({ $count } left)
+unknown-command = Unknown command '{ $command }'
+game-saved-resume = Game saved — run with --resume to continue
+quit-without-saving = Quitting without saving
+copied = Copied the share text to the clipboard
+copy-failed = Could not copy to the clipboard ({ $error }); copy the share text from here instead:
+transcripts-taken = too many transcripts in '{ $path }' are already named like this one
+
+## Prompts, which are followed by the player's answer
+
+prompt-guess = Guess { $turn }/{ $max }:
+prompt-guess-timed = Guess { $turn }/{ $max } [{ $time }]:
+prompt-chain-guess = Game { $number }/{ $games }, guess { $turn }/{ $max }:
+prompt-row = Row { $row }/{ $rows }:
+prompt-give-up = Give up and reveal the word? [y/N]
+prompt-give-up-boards = Give up and reveal the words? [y/N]
+prompt-give-up-reverse = Give up and see words that fit? [y/N]
+prompt-play-again = Play again? [Y/n]
+
+## The full-screen view
+
+tui-help = Type a guess and press Enter; Esc for the menu
+tui-menu = q: quit   g: give up   Esc: back to the game
+tui-too-short = Not enough letters
+tui-won = You got it in { $guesses }! Press any key to continue
+tui-lost = The word was { $answer }. Press any key to continue
+tui-guesses-left = Guesses left: { $count }
+
+## Several boards at once
+
+boards-won = Solved all { $boards } boards in { $guesses }/{ $max }!
+boards-lost = Solved { $solved } of { $boards } boards
+boards-solved-on = Board { $number }: { $answer }, solved on turn { $turn }
+boards-not-solved = Board { $number }: { $answer }, not solved
+board = Board { $number }
+board-solved = Board { $number }: solved in { $guesses }
+board-not-solved = Board { $number }: not solved
+board-keyboard = Board { $number }:
+boards-no-hints = Hints aren't available with more than one board
+boards-no-save = Games with more than one board can't be saved
+
+## Chains of games
+
+chain-won = Solved all { $games } games with { $guesses } guesses of your own!
+chain-lost = Solved { $solved } of { $games } games
+chain-solved-in = Game { $number }: { $answer }, solved in { $guesses }/{ $max }
+chain-not-solved = Game { $number }: { $answer }, not solved
+chain-game = Game { $number } of { $games }
+chain-game-carried-over = Game { $number } of { $games }, starting with the last answer, { $answer }
+chain-no-hints = Hints aren't available in a chain
+chain-copy-later = The share text can be copied once the chain is over
+chain-no-save = Chains of games can't be saved
+
+## Reverse puzzles
+
+reverse-target = Find words that give these colors against { $answer }:
+reverse-mismatch = { $guess } gives { $tiles } instead
+reverse-no-such-row = There are only { $rows } rows
+reverse-hint = { $count ->
+        [one] Only one word fits row { $row }
+       *[other] { $count } words fit row { $row }
+    }
+reverse-solved = Solved!
+reverse-would-fit = Words that would have fit:
+reverse-fits = { $others ->
+        [0] Row { $row }: { $word }
+        [one] Row { $row }: { $word } (or 1 other)
+       *[other] Row { $row }: { $word } (or { $others } others)
+    }
+reverse-no-copy = Reverse puzzles can't be shared
+reverse-no-save = Reverse puzzles can't be saved
+
+## Racing the bot
+
+bot-board = The bot's board:
+vs-bot-won = You beat the bot!
+vs-bot-lost = The bot beat you.
+vs-bot-tied = You and the bot tied, both in { $guesses }.
+vs-bot-neither = Neither of you found the word.
+
+## Stats and the keyboard
+
+stats-summary = Played: { $played }  Win %: { $percentage }  Current streak: { $current }  Max streak: { $max }
+stats-assisted = Assisted: { $count }
+stats-best-time = Personal best: { $time }
+guess-distribution = Guess distribution:
+keyboard-in-word = In word: { $letters }
+keyboard-eliminated = Eliminated: { $letters }
+
+## Suggestions in assist mode
+
+suggestions = { $count ->
+        [one] 1 possible answer left; try { $words }
+       *[other] { $count } possible answers left; try { $words }
+    }
+suggestion = { $word } ({ $bits } bits)
+could-be-answer = could be the answer
+cannot-be-answer = can't be the answer
+explanation = { $word }: { $verdict }; leaves { $average } answers on average, { $worst } at most
+likeliest-colors = likeliest colors: { $patterns }
+
+## Files and settings
+
+saved-config = Saved the settings to '{ $path }'
+saved-transcript = Saved the transcript to '{ $path }'
+no-languages = No languages found in '{ $path }'
+exported = Exported { $count } games to '{ $path }'
+merged = Added { $added } games from '{ $path }', and skipped { $skipped } already recorded
+builtin-themes = The built-in themes are: { $names }
+builtin-locales = The built-in locales are: { $names }
+word-list-help = Use --guesses and --answers to give the paths to the word lists
+language-help = Use --lang list to see the languages there, or --dict-dir to look elsewhere
+notice-builtin-word-lists = Notice: could not read word list '{ $path }' ({ $error }); using the built-in word lists
+
+## The leaderboard
+
+leaderboard-rank = You're #{ $rank } on the leaderboard, as { $player }
+outbox-sent = Sent { $count } results kept from before to the leaderboard
+leaderboard = Leaderboard for Wordle #{ $puzzle } ({ $date }):
+leaderboard-empty = No results yet
+leaderboard-entry = { $guesses } in { $time }
+api-rejected = the server refused it: { $reason }
+api-unavailable = could not reach the server: { $reason }
+
+## Errors
+
+error-no-config-dir = Error: could not find a config directory
+error-no-data-dir = Error: could not find a data directory
+error-no-stats-dir = Error: could not find where to keep stats; use --stats-dir
+error-write-config = Error: could not write config file '{ $path }': { $error }
+error-invalid-config = Error: invalid config file '{ $path }': { $error }
+error-read-theme = Error: could not read theme '{ $name }': { $error }
+error-read-locale = Error: could not read locale '{ $name }': { $error }
+error-read-dict-dir = Error: could not read dictionary directory '{ $path }': { $error }
+error-no-language = Error: no word lists for language '{ $code }' in '{ $path }'; expected guesses.{ $code }.txt and answers.{ $code }.txt
+error-read-word-list = Error: could not read word list '{ $path }': { $error }
+error-invalid-word-list = Error: invalid word list '{ $path }': { $error }
+error-builtin-word-lists = Error: could not use the built-in word lists: { $error }
+error-word-list-length = Error: the word lists don't match --length { $length }: { $error }
+error-invalid-word-lists = Error: invalid word lists: { $error }
+error-no-builtin-word-lists = Error: there are no built-in word lists in this build
+error-invalid-first-guess = Error: the first guess can't be played: { $error }
+error-invalid-answer = Error: the answer can't be played: { $error }
+error-read-guesses = Error: could not read guesses: { $error }
+error-input-ended = Error: the input ended before the game was over
+error-no-puzzle-today = Error: there is no puzzle today; the first is on { $date }
+error-no-puzzle = Error: there is no puzzle for { $date }; the first was on { $first }
+error-terminal = Error: could not use the terminal: { $error }
+error-export-format = Error: can't tell what format to export '{ $path }' in; use --format csv or --format json
+error-read-log = Error: could not read the game log '{ $path }': { $error }
+error-write = Error: could not write '{ $path }': { $error }
+error-read-stats = Error: could not read stats from '{ $path }': { $error }
+error-save-stats = Error: could not save stats to '{ $path }': { $error }
+error-no-saved-game = Error: there is no saved game to resume; games are saved to '{ $path }' on Ctrl-C
+error-read-saved-game = Error: could not read saved game '{ $path }': { $error }
+error-invalid-saved-game = Error: invalid saved game '{ $path }': { $error }
+error-resume = Error: could not resume the saved game '{ $path }': { $error }
+error-save-game = Error: could not save the game: { $error }
+
+## Warnings
+
+warning = Warning: { $error }
+warning-ctrl-c = Warning: could not handle Ctrl-C: { $error }
+warning-unknown-setting = Warning: unknown setting '{ $key }' in config file '{ $path }'
+warning-read-definitions = Warning: could not read definitions from '{ $path }': { $error }
+warning-corrupt-stats = Warning: the stats file '{ $path }' is corrupt ({ $error }); it was moved to '{ $backup }'
+warning-move-stats = Warning: could not move aside corrupt stats file '{ $path }': { $error }
+warning-read-stats = Warning: could not read stats from '{ $path }': { $error }
+warning-save-stats = Warning: could not save stats to '{ $path }': { $error }
+warning-log-game = Warning: could not log the game to '{ $path }': { $error }
+warning-save-transcript = Warning: could not save the transcript: { $error }
+warning-remove-saved-game = Warning: could not remove saved game '{ $path }': { $error }
+warning-merge-conflict = Warning: Wordle #{ $puzzle } was recorded differently in each; kept the earlier record, from { $date }
+warning-outbox-rejected = Warning: a result kept from before wasn't put on the leaderboard: { $error }
+warning-outbox-send = Warning: could not send the results kept in '{ $path }': { $error }
+warning-result-rejected = Warning: could not put the result on the leaderboard: { $error }
+warning-result-kept = Warning: { $error }; the result will be sent next time you play with --server
+warning-result-lost = Warning: { $error }, and could not keep the result to send later: { $reason }
+warning-leaderboard = Warning: could not get the leaderboard: { $error }
+warning-server-keyed = Warning: { $path } picks its own daily answers, so the daily puzzle played here can't go on its leaderboard, and results won't be sent to it
+warning-line-editing = Warning: line editing is not available: { $error }
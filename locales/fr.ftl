@@ -0,0 +1,325 @@
+# French
+
+## Why a guess was rejected
+
+rejection = « { $guess } » n'est pas valide : { $reason }
+rejection-too-short = trop court — il faut { $expected } lettres, pas { $actual }
+rejection-too-long = trop long — il faut { $expected } lettres, pas { $actual }
+rejection-invalid-letter = « { $letter } » n'est pas une lettre
+rejection-not-in-word-list = pas dans la liste de mots
+rejection-already-guessed = vous avez déjà essayé { $guess } à l'essai { $turn }
+rejection-game-over = la partie est terminée
+rejection-no-word = aucun mot n'a été choisi
+
+## Hard mode
+
+hard-mode-missing-correct = La { $position ->
+        [1] 1re
+       *[other] { $position }e
+    } lettre doit être { $letter }
+hard-mode-missing-present = Le mot doit contenir { $letter }
+
+## Hints
+
+hint-present = Le mot contient { $letter }
+hint-position = La { $position ->
+        [1] 1re
+       *[other] { $position }e
+    } lettre est { $letter }
+hint-no-hints-left = Plus d'indices
+hint-nothing-to-reveal = Vous connaissez déjà toutes les lettres
+hint-game-over = La partie est terminée
+
+## The end of a game
+
+praise = { $guesses ->
+        [1] Génial !
+        [2] Magnifique !
+        [3] Impressionnant !
+        [4] Splendide !
+        [5] Bien joué !
+       *[other] Ouf !
+    }
+consolation = Pas facile ! Le mot était { $answer }
+streak = Série : { $current } { $current ->
+        [one] victoire
+       *[other] victoires
+    } (record : { $max })
+streak-ended = Fin de votre série de { $streak } { $streak ->
+        [one] victoire
+       *[other] victoires
+    }
+
+## Descriptions for screen readers
+
+letter-correct = bien placée
+letter-present = dans le mot, mal placée
+letter-no-more = plus présente dans le mot
+letter-absent = pas dans le mot
+letter-described = { $letter } — { $meaning }.
+tally = { $correct } bien placées, { $present } mal placées, { $absent } absentes
+guess-described = { $guess } : { $letters } { $tally }.
+spoken-guess = Essai { $number }, { $description }
+guesses-left = { $count ->
+        [one] 1 essai restant.
+       *[other] { $count } essais restants.
+    }
+keyboard-correct = Bien placées : { $letters }.
+keyboard-present = Dans le mot : { $letters }.
+keyboard-absent = Pas dans le mot : { $letters }.
+keyboard-empty = Aucune lettre essayée pour l'instant.
+
+## Sharing
+
+share-hints = { $count ->
+        [one] 1 indice
+       *[other] { $count } indices
+    }
+share-assisted = assisté
+
+## Possible answers
+
+remaining = Réponses encore possibles : { $remaining } (avant : { $before })
+narrowed-down = Réponses possibles après l'essai { $turn } : { $answers }
+
+## Starting and finishing games
+
+banner-hard-mode = mode difficile
+boards-started = Partie { $number } (graine { $seed }, { $boards } grilles) commencée
+chain-started = Chaîne { $number } (graine { $seed }, { $games } parties) commencée
+reverse-started = Wordle inversé nº { $puzzle } commencé
+daily-started = Wordle nº { $puzzle } ({ $date }) commencé
+daily-resumed = Wordle nº { $puzzle } repris
+daily-over = Wordle nº { $puzzle } terminé
+game-started = Partie { $number } de la graine { $seed } commencée
+game-resumed = Partie { $number } de la graine { $seed } reprise
+seed-resumed = Partie (graine { $seed }) reprise
+practice-started = Partie d'entraînement commencée
+practice-resumed = Partie d'entraînement reprise
+game-over = Cette partie est terminée
+already-played = Vous avez déjà joué le Wordle nº { $puzzle } aujourd'hui ; revenez demain !
+opened-earlier = La partie avait commencé par { $guess }
+opened-with = Premier essai : { $guess }
+next-wordle = Prochain Wordle dans { $time }
+found = { $answer } trouvé en { $guesses }/{ $max }
+the-word-was = Le mot était { $answer }
+won-games = { $won } { $won ->
+        [one] partie gagnée
+       *[other] parties gagnées
+    } sur { $played }
+won-chains = { $won } { $won ->
+        [one] chaîne gagnée
+       *[other] chaînes gagnées
+    } sur { $played }
+time = Temps : { $time }
+time-per-guess = Temps : { $time } (par essai : { $splits })
+personal-best = C'est un nouveau record personnel !
+boards-no-stats = Les parties à plusieurs grilles ne comptent pas dans vos statistiques
+chain-no-stats = Les chaînes de parties ne comptent pas dans vos statistiques
+other-day-no-stats = C'était le puzzle du { $date }, il ne compte donc pas dans vos statistiques
+practice-word-no-stats = C'était un mot d'entraînement, il ne compte donc pas dans vos statistiques
+practice-game-no-stats = C'était une partie d'entraînement, elle ne compte donc pas dans vos statistiques
+stats-hard-mode = Mode difficile :
+computing-openers = Calcul des meilleurs premiers essais...
+
+## Commands during a game
+
+time-up = Temps écoulé ! La limite était de { $time }
+daily-give-up = Faites au moins un essai avant d'abandonner le puzzle du jour
+saved-game = Partie enregistrée dans « { $path } »
+save-exists = « { $path } » existe déjà ; enregistrez la partie ailleurs
+save-failed = Impossible d'enregistrer la partie : { $error }
+copy-later = Le texte à partager pourra être copié une fois la partie terminée
+hint = Indice : { $hint } (encore { $count })
+unknown-command = Commande inconnue « { $command } »
+game-saved-resume = Partie enregistrée — relancez avec --resume pour continuer
+quit-without-saving = Sortie sans enregistrer
+copied = Texte à partager copié dans le presse-papiers
+copy-failed = Impossible de copier dans le presse-papiers ({ $error }) ; copiez plutôt le texte à partager ici :
+transcripts-taken = trop de transcriptions dans « { $path } » portent déjà ce nom
+
+## Prompts, which are followed by the player's answer
+
+prompt-guess = Essai { $turn }/{ $max } :
+prompt-guess-timed = Essai { $turn }/{ $max } [{ $time }] :
+prompt-chain-guess = Partie { $number }/{ $games }, essai { $turn }/{ $max } :
+prompt-row = Ligne { $row }/{ $rows } :
+prompt-give-up = Abandonner et révéler le mot ? [y/N]
+prompt-give-up-boards = Abandonner et révéler les mots ? [y/N]
+prompt-give-up-reverse = Abandonner et voir les mots qui conviennent ? [y/N]
+prompt-play-again = Rejouer ? [Y/n]
+
+## The full-screen view
+
+tui-help = Tapez un essai et appuyez sur Entrée ; Échap pour le menu
+tui-menu = q : quitter   g : abandonner   Échap : revenir à la partie
+tui-too-short = Pas assez de lettres
+tui-won = Trouvé en { $guesses } ! Appuyez sur une touche pour continuer
+tui-lost = Le mot était { $answer }. Appuyez sur une touche pour continuer
+tui-guesses-left = Essais restants : { $count }
+
+## Several boards at once
+
+boards-won = Les { $boards } grilles résolues en { $guesses }/{ $max } !
+boards-lost = { $solved } grilles résolues sur { $boards }
+boards-solved-on = Grille { $number } : { $answer }, résolue à l'essai { $turn }
+boards-not-solved = Grille { $number } : { $answer }, non résolue
+board = Grille { $number }
+board-solved = Grille { $number } : résolue en { $guesses }
+board-not-solved = Grille { $number } : non résolue
+board-keyboard = Grille { $number } :
+boards-no-hints = Les indices ne sont pas disponibles avec plusieurs grilles
+boards-no-save = Les parties à plusieurs grilles ne peuvent pas être enregistrées
+
+## Chains of games
+
+chain-won = Les { $games } parties résolues avec { $guesses } essais de votre cru !
+chain-lost = { $solved } parties résolues sur { $games }
+chain-solved-in = Partie { $number } : { $answer }, résolue en { $guesses }/{ $max }
+chain-not-solved = Partie { $number } : { $answer }, non résolue
+chain-game = Partie { $number } sur { $games }
+chain-game-carried-over = Partie { $number } sur { $games }, qui commence par la réponse précédente, { $answer }
+chain-no-hints = Les indices ne sont pas disponibles dans une chaîne
+chain-copy-later = Le texte à partager pourra être copié une fois la chaîne terminée
+chain-no-save = Les chaînes de parties ne peuvent pas être enregistrées
+
+## Reverse puzzles
+
+reverse-target = Trouvez des mots qui donnent ces couleurs contre { $answer } :
+reverse-mismatch = { $guess } donne plutôt { $tiles }
+reverse-no-such-row = Il n'y a que { $rows } lignes
+reverse-hint = { $count ->
+        [one] Un seul mot convient à la ligne { $row }
+       *[other] { $count } mots conviennent à la ligne { $row }
+    }
+reverse-solved = Résolu !
+reverse-would-fit = Mots qui auraient convenu :
+reverse-fits = { $others ->
+        [0] Ligne { $row } : { $word }
+        [one] Ligne { $row } : { $word } (ou 1 autre)
+       *[other] Ligne { $row } : { $word } (ou { $others } autres)
+    }
+reverse-no-copy = Les puzzles inversés ne peuvent pas être partagés
+reverse-no-save = Les puzzles inversés ne peuvent pas être enregistrés
+
+## Racing the bot
+
+bot-board = La grille du robot :
+vs-bot-won = Vous avez battu le robot !
+vs-bot-lost = Le robot vous a battu.
+vs-bot-tied = Égalité avec le robot, en { $guesses } chacun.
+vs-bot-neither = Aucun de vous deux n'a trouvé le mot.
+
+## Stats and the keyboard
+
+stats-summary = Parties : { $played }  Victoires : { $percentage } %  Série actuelle : { $current }  Meilleure série : { $max }
+stats-assisted = Assistées : { $count }
+stats-best-time = Record personnel : { $time }
+guess-distribution = Répartition des essais :
+keyboard-in-word = Dans le mot : { $letters }
+keyboard-eliminated = Éliminées : { $letters }
+
+## Suggestions in assist mode
+
+suggestions = { $count ->
+        [one] 1 réponse encore possible ; essayez { $words }
+       *[other] { $count } réponses encore possibles ; essayez { $words }
+    }
+suggestion = { $word } ({ $bits } bits)
+could-be-answer = peut être la réponse
+cannot-be-answer = ne peut pas être la réponse
+explanation = { $word } : { $verdict } ; laisse { $average } réponses en moyenne, { $worst } au plus
+likeliest-colors = couleurs les plus probables : { $patterns }
+
+## Files and settings
+
+saved-config = Réglages enregistrés dans « { $path } »
+saved-transcript = Transcription enregistrée dans « { $path } »
+no-languages = Aucune langue trouvée dans « { $path } »
+exported = { $count } { $count ->
+        [one] partie exportée
+       *[other] parties exportées
+    } dans « { $path } »
+merged = { $added } { $added ->
+        [one] partie ajoutée
+       *[other] parties ajoutées
+    } depuis « { $path } », { $skipped } déjà { $skipped ->
+        [one] enregistrée ignorée
+       *[other] enregistrées ignorées
+    }
+builtin-themes = Les thèmes intégrés sont : { $names }
+builtin-locales = Les langues intégrées sont : { $names }
+word-list-help = Utilisez --guesses et --answers pour indiquer les chemins des listes de mots
+language-help = Utilisez --lang list pour voir les langues disponibles, ou --dict-dir pour chercher ailleurs
+notice-builtin-word-lists = Remarque : impossible de lire la liste de mots « { $path } » ({ $error }) ; les listes intégrées sont utilisées
+
+## The leaderboard
+
+leaderboard-rank = Vous êtes nº { $rank } du classement, sous le nom de { $player }
+outbox-sent = { $count } { $count ->
+        [one] résultat gardé auparavant a été envoyé
+       *[other] résultats gardés auparavant ont été envoyés
+    } au classement
+leaderboard = Classement du Wordle nº { $puzzle } ({ $date }) :
+leaderboard-empty = Aucun résultat pour l'instant
+leaderboard-entry = { $guesses } en { $time }
+api-rejected = le serveur l'a refusé : { $reason }
+api-unavailable = impossible de joindre le serveur : { $reason }
+
+## Errors
+
+error-no-config-dir = Erreur : impossible de trouver un dossier de configuration
+error-no-data-dir = Erreur : impossible de trouver un dossier de données
+error-no-stats-dir = Erreur : impossible de trouver où garder les statistiques ; utilisez --stats-dir
+error-write-config = Erreur : impossible d'écrire le fichier de configuration « { $path } » : { $error }
+error-invalid-config = Erreur : fichier de configuration « { $path } » invalide : { $error }
+error-read-theme = Erreur : impossible de lire le thème « { $name } » : { $error }
+error-read-locale = Erreur : impossible de lire la langue « { $name } » : { $error }
+error-read-dict-dir = Erreur : impossible de lire le dossier de dictionnaires « { $path } » : { $error }
+error-no-language = Erreur : aucune liste de mots pour la langue « { $code } » dans « { $path } » ; guesses.{ $code }.txt et answers.{ $code }.txt sont attendus
+error-read-word-list = Erreur : impossible de lire la liste de mots « { $path } » : { $error }
+error-invalid-word-list = Erreur : liste de mots « { $path } » invalide : { $error }
+error-builtin-word-lists = Erreur : impossible d'utiliser les listes de mots intégrées : { $error }
+error-word-list-length = Erreur : les listes de mots ne correspondent pas à --length { $length } : { $error }
+error-invalid-word-lists = Erreur : listes de mots invalides : { $error }
+error-no-builtin-word-lists = Erreur : cette version ne contient pas de listes de mots intégrées
+error-invalid-first-guess = Erreur : impossible de jouer le premier essai : { $error }
+error-invalid-answer = Erreur : impossible de jouer cette réponse : { $error }
+error-read-guesses = Erreur : impossible de lire les essais : { $error }
+error-input-ended = Erreur : l'entrée s'est terminée avant la fin de la partie
+error-no-puzzle-today = Erreur : il n'y a pas de puzzle aujourd'hui ; le premier est le { $date }
+error-no-puzzle = Erreur : il n'y a pas de puzzle pour le { $date } ; le premier était le { $first }
+error-terminal = Erreur : impossible d'utiliser le terminal : { $error }
+error-export-format = Erreur : impossible de savoir dans quel format exporter « { $path } » ; utilisez --format csv ou --format json
+error-read-log = Erreur : impossible de lire le journal des parties « { $path } » : { $error }
+error-write = Erreur : impossible d'écrire « { $path } » : { $error }
+error-read-stats = Erreur : impossible de lire les statistiques de « { $path } » : { $error }
+error-save-stats = Erreur : impossible d'enregistrer les statistiques dans « { $path } » : { $error }
+error-no-saved-game = Erreur : aucune partie enregistrée à reprendre ; les parties sont enregistrées dans « { $path } » avec Ctrl-C
+error-read-saved-game = Erreur : impossible de lire la partie enregistrée « { $path } » : { $error }
+error-invalid-saved-game = Erreur : partie enregistrée « { $path } » invalide : { $error }
+error-resume = Erreur : impossible de reprendre la partie enregistrée « { $path } » : { $error }
+error-save-game = Erreur : impossible d'enregistrer la partie : { $error }
+
+## Warnings
+
+warning = Attention : { $error }
+warning-ctrl-c = Attention : impossible de gérer Ctrl-C : { $error }
+warning-unknown-setting = Attention : réglage « { $key } » inconnu dans le fichier de configuration « { $path } »
+warning-read-definitions = Attention : impossible de lire les définitions de « { $path } » : { $error }
+warning-corrupt-stats = Attention : le fichier de statistiques « { $path } » est corrompu ({ $error }) ; il a été déplacé vers « { $backup } »
+warning-move-stats = Attention : impossible de mettre de côté le fichier de statistiques corrompu « { $path } » : { $error }
+warning-read-stats = Attention : impossible de lire les statistiques de « { $path } » : { $error }
+warning-save-stats = Attention : impossible d'enregistrer les statistiques dans « { $path } » : { $error }
+warning-log-game = Attention : impossible d'ajouter la partie au journal « { $path } » : { $error }
+warning-save-transcript = Attention : impossible d'enregistrer la transcription : { $error }
+warning-remove-saved-game = Attention : impossible de supprimer la partie enregistrée « { $path } » : { $error }
+warning-merge-conflict = Attention : le Wordle nº { $puzzle } est enregistré différemment dans chacun ; le plus ancien, du { $date }, a été gardé
+warning-outbox-rejected = Attention : un résultat gardé auparavant n'a pas été ajouté au classement : { $error }
+warning-outbox-send = Attention : impossible d'envoyer les résultats gardés dans « { $path } » : { $error }
+warning-result-rejected = Attention : impossible d'ajouter le résultat au classement : { $error }
+warning-result-kept = Attention : { $error } ; le résultat sera envoyé la prochaine fois que vous jouerez avec --server
+warning-result-lost = Attention : { $error }, et impossible de garder le résultat pour l'envoyer plus tard : { $reason }
+warning-leaderboard = Attention : impossible d'obtenir le classement : { $error }
+warning-server-keyed = Attention : { $path } choisit ses propres mots du jour, donc le puzzle du jour joué ici ne peut pas figurer dans son classement, et les résultats ne lui seront pas envoyés
+warning-line-editing = Attention : l'édition de ligne n'est pas disponible : { $error }
@@ -0,0 +1,822 @@
+//! The API: creating games, making guesses, pausing and looking at games, the daily
+//! puzzle, leaderboard and guess distribution, race rooms, metrics, and health checks,
+//! with API keys checked on the way in
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{net::SocketAddr, time::Instant};
+
+use axum::{
+    body::Bytes,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        ws::WebSocketUpgrade,
+        ConnectInfo, Extension, MatchedPath, Path, Query, Request, State,
+    },
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use wordle::{
+    daily,
+    definitions::Definitions,
+    render::{html::board_html, svg::board_svg, BoardOptions},
+    stats,
+    transcript::Transcript,
+    verify::VerifyError,
+    GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+use crate::{
+    auth::{self, AuthError, Identity},
+    daily::{DailyError, DailyResult},
+    race,
+    ratelimit::{self, Client},
+    state::{GameOptions, Server, ServerError},
+};
+
+/// The number of results on the leaderboard unless asked for more (or fewer)
+const DEFAULT_LEADERBOARD_LIMIT: usize = 10;
+/// The most results the leaderboard gives back at once
+const MAX_LEADERBOARD_LIMIT: usize = 100;
+/// The width of the guess distribution unless asked for another
+const DEFAULT_DISTRIBUTION_WIDTH: usize = 40;
+/// The widest the guess distribution can be asked to be
+const MAX_DISTRIBUTION_WIDTH: usize = 200;
+
+/// The routes of the API, serving games from `server`
+pub fn router(server: Server) -> Router {
+    // only starting games and guessing cost the server much, so only they're rate limited
+    let games = Router::new()
+        .route("/games", post(create_game))
+        .route("/games/{id}/guesses", post(make_guess))
+        .route_layer(middleware::from_fn_with_state(server.clone(), limit_rate))
+        .route_layer(middleware::from_fn_with_state(
+            server.clone(),
+            require_key_for_games,
+        ));
+    let results = Router::new()
+        .route("/daily/result", post(add_result))
+        .route_layer(middleware::from_fn_with_state(
+            server.clone(),
+            require_key_for_results,
+        ));
+    // each guess over a race socket is held to the limit too, as it's played
+    let races = Router::new()
+        .route("/ws/race/{room}", get(race_socket))
+        .route_layer(middleware::from_fn_with_state(server.clone(), limit_rate))
+        .route_layer(middleware::from_fn_with_state(server.clone(), authenticate));
+    Router::new()
+        .merge(games)
+        .merge(results)
+        .route("/games/{id}", get(get_game).delete(delete_game))
+        .route("/games/{id}/pause", post(pause_game))
+        .route("/games/{id}/resume", post(resume_game))
+        .route("/games/{id}/board.svg", get(get_board_svg))
+        .route("/games/{id}/board.html", get(get_board_html))
+        .route("/daily/meta", get(get_daily_meta))
+        .route("/daily/leaderboard", get(get_leaderboard))
+        .route("/daily/distribution", get(get_distribution))
+        .route("/metrics", get(get_metrics))
+        .route_layer(middleware::from_fn_with_state(server.clone(), authenticate))
+        .route_layer(middleware::from_fn_with_state(server.clone(), time_request))
+        // added after the timing layer, so probes and long-lived sockets don't
+        // crowd out the requests that matter
+        .merge(races)
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(server)
+}
+
+/// The body of `POST /games`; it can be left out for the defaults
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct NewGame {
+    /// Whether revealed hints must be used in subsequent guesses
+    pub hard_mode: bool,
+    /// Whether to play today's puzzle instead of a random answer
+    pub daily: bool,
+}
+
+/// The body of `POST /games/{id}/guesses`
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NewGuess {
+    /// The word to guess
+    pub word: String,
+}
+
+/// How a game stands
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The word hasn't been found, and there are guesses left
+    InProgress,
+    /// The word was found
+    Won,
+    /// The guesses ran out before the word was found
+    Lost,
+}
+
+impl From<GameStatus> for Status {
+    fn from(status: GameStatus) -> Self {
+        match status {
+            GameStatus::InProgress => Self::InProgress,
+            GameStatus::Won { .. } => Self::Won,
+            GameStatus::Lost => Self::Lost,
+        }
+    }
+}
+
+/// A guess made in a game
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GuessView {
+    /// The guessed word
+    pub word: String,
+    /// The feedback for each letter of the guess
+    pub statuses: Vec<LetterStatus>,
+}
+
+/// A game as a client may see it: the answer is only given once the game is over
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GameView {
+    /// The game's id
+    pub id: String,
+    /// The number of letters in the word
+    pub word_length: usize,
+    /// The number of guesses allowed
+    pub max_guesses: usize,
+    /// Whether revealed hints must be used in subsequent guesses
+    pub hard_mode: bool,
+    /// The guesses made so far, in order
+    pub guesses: Vec<GuessView>,
+    /// How the game stands
+    pub status: Status,
+    /// Whether the game's clock is paused
+    pub paused: bool,
+    /// How many seconds the game is kept for if it isn't used again; any use
+    /// starts this over, and it's longer while the game is paused
+    pub ttl_secs: u64,
+    /// The answer, once the game is over
+    pub answer: Option<String>,
+    /// The answer's definition, once the game is over, if the server has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+impl GameView {
+    /// Describe a game, hiding the answer and its definition while it's in progress
+    fn new(id: String, game: &Wordle<'_>, server: &Server) -> Self {
+        Self {
+            id,
+            word_length: game.word_length(),
+            max_guesses: game.max_guesses(),
+            hard_mode: game.hard_mode(),
+            guesses: game
+                .history()
+                .iter()
+                .map(|(word, statuses)| GuessView {
+                    word: word.to_string(),
+                    statuses: statuses.clone(),
+                })
+                .collect(),
+            status: game.status().into(),
+            paused: game.is_paused(),
+            ttl_secs: server.ttl(game).as_secs(),
+            answer: revealed_answer(game),
+            definition: revealed_definition(game, server.definitions()),
+        }
+    }
+}
+
+/// The result of a guess
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GuessResult {
+    /// The feedback for each letter of the guess
+    pub statuses: Vec<LetterStatus>,
+    /// The number of guesses made so far, including this one
+    pub turn: usize,
+    /// The number of guesses left
+    pub guesses_left: usize,
+    /// How the game stands after the guess
+    pub status: Status,
+    /// The answer, if the game is now over
+    pub answer: Option<String>,
+    /// The answer's definition, if the game is now over and the server has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+/// The body of `POST /daily/result`
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct NewResult {
+    /// The player's name, for results sent without an API key; if it's left
+    /// out, a made-up name is used. With a key, the name the key was issued
+    /// to is always used, and a different name here is turned away
+    #[serde(default)]
+    pub player: Option<String>,
+    /// The game of today's puzzle, which is played again to check it
+    pub transcript: Transcript,
+}
+
+/// Today's puzzle, without giving its answer away
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DailyMeta {
+    /// Today's date, in UTC
+    pub date: NaiveDate,
+    /// The number of today's puzzle
+    pub puzzle: u32,
+    /// When the next puzzle comes out
+    pub rollover: DateTime<Utc>,
+    /// A short keyed hash of the answer, the same on every server with the
+    /// same daily secret, if the server has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+/// The query of `GET /daily/leaderboard`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaderboardQuery {
+    /// The day to look at, or `None` for today
+    pub date: Option<NaiveDate>,
+    /// The number of results to give back, or `None` for the default
+    pub limit: Option<usize>,
+}
+
+/// The query of `GET /daily/distribution`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct DistributionQuery {
+    /// The day to look at, or `None` for today
+    pub date: Option<NaiveDate>,
+    /// The widest the histogram can be, in columns, or `None` for the default
+    pub width: Option<usize>,
+}
+
+/// The query of `GET /ws/race/{room}`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct RaceQuery {
+    /// Anything but 0 to watch the race instead of joining it
+    pub spectate: Option<u8>,
+}
+
+/// A result on the leaderboard
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LeaderboardEntry {
+    /// Where the result places, starting at 1
+    pub rank: usize,
+    /// The player's name, or the made-up one they were given
+    pub player: String,
+    /// The number of guesses it took to win
+    pub guesses: usize,
+    /// The time the client says the game took, in milliseconds; results
+    /// that tie on guesses are ranked by when they were sent in instead
+    pub time_ms: u64,
+    /// Whether the game was played in hard mode
+    pub hard_mode: bool,
+}
+
+impl LeaderboardEntry {
+    /// Describe a result that places `rank`
+    fn new(rank: usize, result: &DailyResult) -> Self {
+        Self {
+            rank,
+            player: result.player.clone(),
+            guesses: result.guesses,
+            time_ms: result.elapsed.as_millis().try_into().unwrap_or(u64::MAX),
+            hard_mode: result.hard_mode,
+        }
+    }
+}
+
+/// The best results for a day's puzzle
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Leaderboard {
+    /// The day
+    pub date: NaiveDate,
+    /// The number of the day's puzzle
+    pub puzzle: u32,
+    /// The best results, best first
+    pub results: Vec<LeaderboardEntry>,
+}
+
+/// An error response, with a message for the client
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ApiError {
+    /// The HTTP status to respond with
+    status: StatusCode,
+    /// What went wrong
+    message: String,
+    /// The kind of error, for errors a client may want to tell apart
+    code: Option<&'static str>,
+}
+
+impl ApiError {
+    /// The request body isn't valid
+    fn bad_request(message: String) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message,
+            code: None,
+        }
+    }
+}
+
+impl From<ServerError> for ApiError {
+    fn from(e: ServerError) -> Self {
+        Self {
+            status: match e {
+                ServerError::NotFound | ServerError::Expired => StatusCode::NOT_FOUND,
+                ServerError::GameOver => StatusCode::CONFLICT,
+                ServerError::TooManyGames { .. } => StatusCode::TOO_MANY_REQUESTS,
+                ServerError::Daily(DailyError::NoPuzzle) => StatusCode::NOT_FOUND,
+                ServerError::Daily(DailyError::AlreadySubmitted) => StatusCode::CONFLICT,
+                ServerError::Daily(
+                    DailyError::WrongPlayer { .. } | DailyError::ReservedName { .. },
+                ) => StatusCode::FORBIDDEN,
+                // the server's own word lists are to blame, not the client
+                ServerError::Daily(DailyError::Forged(VerifyError::InvalidWordList(_))) => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+                ServerError::Daily(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                ServerError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+                ServerError::Store(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            message: e.to_string(),
+            code: match &e {
+                ServerError::Expired => Some("game_expired"),
+                ServerError::Daily(e) => Some(e.code()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl From<GuessError> for ApiError {
+    fn from(e: GuessError) -> Self {
+        Self {
+            status: match e {
+                GuessError::GameOver => StatusCode::CONFLICT,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            message: e.to_string(),
+            code: None,
+        }
+    }
+}
+
+/// The body of an error response
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct ErrorBody<'a> {
+    /// What went wrong
+    error: &'a str,
+    /// The kind of error, for errors a client may want to tell apart, e.g.
+    /// `forged_feedback` for a result whose feedback doesn't match the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+}
+
+/// The schema for each request and response body, by a name for its file
+#[cfg(feature = "schema")]
+pub fn schemas() -> Vec<(&'static str, schemars::Schema)> {
+    use schemars::schema_for;
+    vec![
+        ("new-game", schema_for!(NewGame)),
+        ("new-guess", schema_for!(NewGuess)),
+        ("game", schema_for!(GameView)),
+        ("guess-result", schema_for!(GuessResult)),
+        ("new-result", schema_for!(NewResult)),
+        ("daily-meta", schema_for!(DailyMeta)),
+        ("leaderboard", schema_for!(Leaderboard)),
+        ("error", schema_for!(ErrorBody<'static>)),
+    ]
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: &self.message,
+            code: self.code,
+        };
+        let mut response = (self.status, Json(body)).into_response();
+        if self.status == StatusCode::UNAUTHORIZED {
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static("Bearer"),
+            );
+        }
+        response
+    }
+}
+
+/// The answer, but only if the game is over
+fn revealed_answer(game: &Wordle<'_>) -> Option<String> {
+    match game.status() {
+        GameStatus::InProgress => None,
+        _ => game.transcript().answer,
+    }
+}
+
+/// The answer's definition, but only if the game is over
+fn revealed_definition(game: &Wordle<'_>, definitions: &Definitions) -> Option<String> {
+    revealed_answer(game)
+        .and_then(|answer| definitions.get(&answer))
+        .map(str::to_owned)
+}
+
+/// `POST /games`: start a new game with a random answer
+async fn create_game(
+    State(server): State<Server>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<GameView>), ApiError> {
+    let options: NewGame = if body.is_empty() {
+        NewGame::default()
+    } else {
+        serde_json::from_slice(&body).map_err(|e| ApiError::bad_request(e.to_string()))?
+    };
+    let options = GameOptions {
+        hard_mode: options.hard_mode,
+        daily: options.daily,
+    };
+    let id = server.create(options, Some(client.ip()))?;
+    let view = server.with_game(&id, |game| GameView::new(id.clone(), game, &server))?;
+    Ok((StatusCode::CREATED, Json(view)))
+}
+
+/// `GET /games/{id}`: look at a game
+async fn get_game(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<Json<GameView>, ApiError> {
+    let view = server.with_game(&id, |game| GameView::new(id.clone(), game, &server))?;
+    Ok(Json(view))
+}
+
+/// How to draw a game's board: the letters are left off until the game is
+/// over, so it doesn't give the guesses away
+fn board_options(game: &Wordle<'_>) -> BoardOptions {
+    BoardOptions {
+        letters: game.status() != GameStatus::InProgress,
+        ..BoardOptions::default()
+    }
+}
+
+/// `GET /games/{id}/board.svg`: an image of a game's board
+async fn get_board_svg(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let svg = server.with_game(&id, |game| board_svg(game.history(), &board_options(game)))?;
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg))
+}
+
+/// `GET /games/{id}/board.html`: a game's board as a fragment of HTML
+async fn get_board_html(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let html = server.with_game(&id, |game| board_html(game.history(), &board_options(game)))?;
+    Ok(([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html))
+}
+
+/// `POST /games/{id}/pause`: stop a game's clock while the player is away;
+/// it's kept for longer while it's paused
+async fn pause_game(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<Json<GameView>, ApiError> {
+    server.pause(&id)?;
+    get_game(State(server), Path(id)).await
+}
+
+/// `POST /games/{id}/resume`: start a paused game's clock again
+async fn resume_game(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<Json<GameView>, ApiError> {
+    server.resume(&id)?;
+    get_game(State(server), Path(id)).await
+}
+
+/// `DELETE /games/{id}`: abandon a game
+async fn delete_game(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    server.delete(&id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /games/{id}/guesses`: make a guess
+async fn make_guess(
+    State(server): State<Server>,
+    Path(id): Path<String>,
+    guess: Result<Json<NewGuess>, JsonRejection>,
+) -> Result<Json<GuessResult>, ApiError> {
+    let Json(guess) = guess.map_err(|e| ApiError::bad_request(e.body_text()))?;
+    let word = guess.word.trim().to_lowercase();
+    server.with_session(&id, |session| {
+        let game = &mut session.game;
+        let result = game.guess(&word);
+        server.metrics().guessed(session.mode, game, &result);
+        let statuses = result?.into_feedback();
+        Ok(Json(GuessResult {
+            statuses,
+            turn: game.history().len(),
+            guesses_left: game.guesses_left(),
+            status: game.status().into(),
+            answer: revealed_answer(game),
+            definition: revealed_definition(game, server.definitions()),
+        }))
+    })?
+}
+
+/// `GET /daily/meta`: today's puzzle number, and when the next one comes out
+async fn get_daily_meta(State(server): State<Server>) -> Result<Json<DailyMeta>, ApiError> {
+    let date = server.today();
+    let (puzzle, _) = server.daily_answer(date)?;
+    let rollover = date
+        .succ_opt()
+        .expect("Dates this far in the future aren't supported")
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    Ok(Json(DailyMeta {
+        date,
+        puzzle,
+        rollover,
+        fingerprint: server.daily_fingerprint(date)?,
+    }))
+}
+
+/// `GET /daily/leaderboard`: the best results for today's puzzle, or another day's
+async fn get_leaderboard(
+    State(server): State<Server>,
+    query: Result<Query<LeaderboardQuery>, QueryRejection>,
+) -> Result<Json<Leaderboard>, ApiError> {
+    let Query(query) = query.map_err(|e| ApiError::bad_request(e.body_text()))?;
+    let date = query.date.unwrap_or_else(|| server.today());
+    let puzzle = daily::puzzle_number(date).ok_or(ServerError::Daily(DailyError::NoPuzzle))?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LEADERBOARD_LIMIT)
+        .min(MAX_LEADERBOARD_LIMIT);
+    let results = server
+        .leaderboard(date)?
+        .iter()
+        .take(limit)
+        .enumerate()
+        .map(|(i, result)| LeaderboardEntry::new(i + 1, result))
+        .collect();
+    Ok(Json(Leaderboard {
+        date,
+        puzzle,
+        results,
+    }))
+}
+
+/// `GET /daily/distribution`: how many guesses the results for today's puzzle,
+/// or another day's, took, as a plain text histogram for simple clients
+async fn get_distribution(
+    State(server): State<Server>,
+    query: Result<Query<DistributionQuery>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Query(query) = query.map_err(|e| ApiError::bad_request(e.body_text()))?;
+    let date = query.date.unwrap_or_else(|| server.today());
+    daily::puzzle_number(date).ok_or(ServerError::Daily(DailyError::NoPuzzle))?;
+    let width = query
+        .width
+        .unwrap_or(DEFAULT_DISTRIBUTION_WIDTH)
+        .min(MAX_DISTRIBUTION_WIDTH);
+    // only wins go on the leaderboard, so nothing is ever in the X row
+    let mut dist = [0; 7];
+    for result in server.leaderboard(date)? {
+        if let Some(count) = result.guesses.checked_sub(1).and_then(|i| dist.get_mut(i)) {
+            *count += 1;
+        }
+    }
+    let mut text = stats::render_histogram(&dist, width, None).join("\n");
+    text.push('\n');
+    Ok(([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], text))
+}
+
+/// `POST /daily/result`: put a game of today's puzzle on the leaderboard
+async fn add_result(
+    State(server): State<Server>,
+    identity: Option<Extension<Identity>>,
+    result: Result<Json<NewResult>, JsonRejection>,
+) -> Result<(StatusCode, Json<LeaderboardEntry>), ApiError> {
+    let Json(result) = result.map_err(|e| ApiError::bad_request(e.body_text()))?;
+    // a key speaks for the player it was issued to, and no one else does
+    let player = match &identity {
+        Some(Extension(identity)) => {
+            if let Some(player) = &result.player {
+                if player.trim() != identity.name {
+                    let key = identity.name.clone();
+                    return Err(ServerError::Daily(DailyError::WrongPlayer { key }).into());
+                }
+            }
+            Some(identity.name.as_str())
+        }
+        None => {
+            if let Some(player) = result.player.as_deref().map(str::trim) {
+                if server.has_key(player)? {
+                    let player = player.to_owned();
+                    return Err(ServerError::Daily(DailyError::ReservedName { player }).into());
+                }
+            }
+            result.player.as_deref()
+        }
+    };
+    let added = server.add_result(player, &result.transcript)?;
+    let rank = server
+        .leaderboard(added.date)?
+        .iter()
+        .position(|result| result.player == added.player)
+        .expect("The result was just added")
+        + 1;
+    Ok((
+        StatusCode::CREATED,
+        Json(LeaderboardEntry::new(rank, &added)),
+    ))
+}
+
+/// `GET /metrics`: metrics about the games being played, for Prometheus
+async fn get_metrics(State(server): State<Server>) -> Result<impl IntoResponse, ApiError> {
+    let text = server.render_metrics()?;
+    Ok(([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], text))
+}
+
+/// Check the API key in a request's `Authorization` header, if it has one,
+/// and note who the request is from for the routes after this.
+///
+/// A request without a key goes through anonymously, but one with a key
+/// that isn't accepted is turned away with `401 Unauthorized`.
+async fn authenticate(
+    State(server): State<Server>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(header) = request.headers().get(header::AUTHORIZATION) else {
+        return Ok(next.run(request).await);
+    };
+    let header = header
+        .to_str()
+        .map_err(|_| ServerError::from(AuthError::Malformed))?;
+    let identity = server.authenticate(auth::bearer(header).map_err(ServerError::from)?)?;
+    // keyed clients get a rate limit of their own, wherever they connect from
+    request
+        .extensions_mut()
+        .insert(Client::Key(identity.name.clone()));
+    request.extensions_mut().insert(identity);
+    Ok(next.run(request).await)
+}
+
+/// Turn a request away with `401 Unauthorized` if it has no API key, when
+/// starting games and guessing need one
+async fn require_key_for_games(
+    State(server): State<Server>,
+    request: Request,
+    next: Next,
+) -> Response {
+    require_key(server.required_keys().games, request, next).await
+}
+
+/// Turn a request away with `401 Unauthorized` if it has no API key, when
+/// putting results on the leaderboard needs one
+async fn require_key_for_results(
+    State(server): State<Server>,
+    request: Request,
+    next: Next,
+) -> Response {
+    require_key(server.required_keys().results, request, next).await
+}
+
+/// Turn a request away if it has no API key and `required` is set; its key
+/// was already checked by [`authenticate`] if it has one
+async fn require_key(required: bool, request: Request, next: Next) -> Response {
+    if required && request.extensions().get::<Identity>().is_none() {
+        return ApiError::from(ServerError::from(AuthError::Missing)).into_response();
+    }
+    next.run(request).await
+}
+
+/// Turn a request away with `429 Too Many Requests` if its client has gone
+/// over the rate limit, saying in `Retry-After` how many seconds until it can
+/// try again
+async fn limit_rate(
+    State(server): State<Server>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client = request
+        .extensions()
+        .get::<Client>()
+        .cloned()
+        .unwrap_or(Client::Ip(addr.ip()));
+    let Err(wait) = server.rate_limiter().check(&client, Instant::now()) else {
+        return next.run(request).await;
+    };
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("", MatchedPath::as_str);
+    server.metrics().rate_limited(route);
+    let secs = ratelimit::retry_after(wait);
+    let error = ApiError {
+        status: StatusCode::TOO_MANY_REQUESTS,
+        message: format!("Too many requests; try again in {} seconds", secs),
+        code: None,
+    };
+    ([(header::RETRY_AFTER, secs.to_string())], error).into_response()
+}
+
+/// Time every request, by its route rather than its path so ids don't each
+/// get a metric of their own
+async fn time_request(State(server): State<Server>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("", MatchedPath::as_str)
+        .to_owned();
+    let start = Instant::now();
+    let response = next.run(request).await;
+    server.metrics().request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        start.elapsed(),
+    );
+    response
+}
+
+/// `GET /ws/race/{room}`: race other players in a room, over a WebSocket, or
+/// with `?spectate=1`, watch them.
+///
+/// See [`wordle::protocol`] for the messages.
+async fn race_socket(
+    State(server): State<Server>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    client: Option<Extension<Client>>,
+    Path(room): Path<String>,
+    query: Result<Query<RaceQuery>, QueryRejection>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let Query(query) = query.map_err(|e| ApiError::bad_request(e.body_text()))?;
+    let client = client.map_or(Client::Ip(addr.ip()), |Extension(client)| client);
+    Ok(if query.spectate.is_some_and(|spectate| spectate != 0) {
+        upgrade.on_upgrade(move |socket| race::spectate(server, room, socket))
+    } else {
+        upgrade.on_upgrade(move |socket| race::play(server, room, client, socket))
+    })
+}
+
+/// `GET /healthz`: whether the server is up, which it is if it can answer
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// `GET /readyz`: whether the server can serve games
+async fn readyz(State(server): State<Server>) -> Result<&'static str, ApiError> {
+    server.check_ready().map_err(|e| ApiError {
+        status: StatusCode::SERVICE_UNAVAILABLE,
+        message: e.to_string(),
+        code: None,
+    })?;
+    Ok("ready")
+}
@@ -0,0 +1,241 @@
+//! API keys, so results and games can be put down to whoever made them
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::Path,
+    time::SystemTime,
+};
+
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::daily;
+
+/// The start of every key, so one is easy to recognize, e.g. in a leaked file
+const PREFIX: &str = "wk_";
+
+/// An API key, as the server keeps it: only a hash of the secret is kept, so
+/// the keys can't be read back out of the store
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ApiKey {
+    /// Who the key was issued to; requests made with it are put down to them
+    pub name: String,
+    /// The SHA-256 hash of the secret, in hex
+    pub hash: String,
+    /// When the key was issued
+    pub created: SystemTime,
+    /// When the key was revoked, or `None` if it can still be used
+    pub revoked: Option<SystemTime>,
+}
+
+impl ApiKey {
+    /// Make up a key for `name`, and get back the secret to give them along
+    /// with the key to keep; the secret can't be had again later
+    pub fn issue(name: &str) -> (String, Self) {
+        let secret = format!("{}{:032x}", PREFIX, rand::random::<u128>());
+        let key = Self::new(name, &secret);
+        (secret, key)
+    }
+
+    /// A key for `name` with the given secret
+    pub fn new(name: &str, secret: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            hash: hash(secret),
+            created: SystemTime::now(),
+            revoked: None,
+        }
+    }
+}
+
+/// Who made a request, once their key has been checked
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Identity {
+    /// The name the key was issued to
+    pub name: String,
+}
+
+/// Which routes need a key; the rest can be used without one
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Required {
+    /// Starting games and making guesses
+    pub games: bool,
+    /// Putting results on the daily leaderboard
+    pub results: bool,
+}
+
+/// Why a request's key wasn't accepted
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AuthError {
+    /// The route needs a key, and none was given
+    Missing,
+    /// The `Authorization` header isn't `Bearer` followed by a key
+    Malformed,
+    /// The key isn't one the server issued
+    Invalid,
+    /// The key was revoked
+    Revoked,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(
+                f,
+                "An API key is needed; send one in an 'Authorization: Bearer' header"
+            ),
+            Self::Malformed => write!(f, "The Authorization header should be 'Bearer KEY'"),
+            Self::Invalid => write!(f, "Unknown API key"),
+            Self::Revoked => write!(f, "The API key was revoked"),
+        }
+    }
+}
+
+impl Error for AuthError {}
+
+/// The SHA-256 hash of a secret, in hex
+pub fn hash(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Get the key out of an `Authorization` header's value
+pub fn bearer(header: &str) -> Result<&str, AuthError> {
+    let (scheme, secret) = header.trim().split_once(' ').ok_or(AuthError::Malformed)?;
+    let secret = secret.trim();
+    if !scheme.eq_ignore_ascii_case("bearer") || secret.is_empty() {
+        return Err(AuthError::Malformed);
+    }
+    Ok(secret)
+}
+
+/// Find the key whose secret is `secret`, if it hasn't been revoked.
+///
+/// Every key is compared in constant time, and all of them are compared
+/// whichever matches, so how long this takes gives away nothing about the keys.
+pub fn find<'a>(
+    keys: impl IntoIterator<Item = &'a ApiKey>,
+    secret: &str,
+) -> Result<&'a ApiKey, AuthError> {
+    let hash = hash(secret);
+    let mut found = None;
+    for key in keys {
+        if bool::from(key.hash.as_bytes().ct_eq(hash.as_bytes())) {
+            found = Some(key);
+        }
+    }
+    match found {
+        None => Err(AuthError::Invalid),
+        Some(key) if key.revoked.is_some() => Err(AuthError::Revoked),
+        Some(key) => Ok(key),
+    }
+}
+
+/// Read keys from `text`: a `name secret` line for each, with blank lines
+/// and lines starting with `#` skipped.
+///
+/// Names must be ones [`daily::check_name`] accepts, since results sent with
+/// a key go on the leaderboard under its name.
+pub fn parse(text: &str) -> Result<Vec<ApiKey>, String> {
+    let mut keys = Vec::new();
+    let mut names = HashSet::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(secret), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(format!("line {}: expected 'name secret'", i + 1));
+        };
+        daily::check_name(name).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        if !names.insert(name) {
+            return Err(format!(
+                "line {}: '{}' has another key already",
+                i + 1,
+                name
+            ));
+        }
+        keys.push(ApiKey::new(name, secret));
+    }
+    Ok(keys)
+}
+
+/// Read keys from a file in the format [`parse`] takes.
+///
+/// A file that isn't in that format is an [`ErrorKind::InvalidData`] error.
+pub fn load(path: &Path) -> io::Result<Vec<ApiKey>> {
+    parse(&fs::read_to_string(path)?).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let (secret, alice) = ApiKey::issue("alice");
+        assert!(secret.starts_with(PREFIX));
+        assert_ne!(secret, alice.hash);
+        let (revoked_secret, mut bob) = ApiKey::issue("bob");
+        bob.revoked = Some(SystemTime::now());
+        let keys = [alice.clone(), bob];
+
+        assert_eq!(Ok(&alice), find(&keys, &secret));
+        assert_eq!(Err(AuthError::Revoked), find(&keys, &revoked_secret));
+        assert_eq!(Err(AuthError::Invalid), find(&keys, "wk_nope"));
+        assert_eq!(Err(AuthError::Invalid), find(&keys, &alice.hash));
+        assert_eq!(Err(AuthError::Invalid), find(&[], &secret));
+    }
+
+    #[test]
+    fn test_bearer() {
+        assert_eq!(Ok("wk_123"), bearer("Bearer wk_123"));
+        assert_eq!(Ok("wk_123"), bearer("bearer  wk_123 "));
+        assert_eq!(Err(AuthError::Malformed), bearer("Bearer"));
+        assert_eq!(Err(AuthError::Malformed), bearer("Bearer "));
+        assert_eq!(Err(AuthError::Malformed), bearer("Basic YWxpY2U6cHc="));
+    }
+
+    #[test]
+    fn test_parse() {
+        let keys = parse("# the bots\nalice wk_1\n\n  bob   wk_2  \n").unwrap();
+        assert_eq!(2, keys.len());
+        assert_eq!("alice", keys[0].name);
+        assert_eq!(hash("wk_1"), keys[0].hash);
+        assert_eq!("bob", keys[1].name);
+        assert_eq!(None, keys[1].revoked);
+
+        assert!(parse("alice").is_err());
+        assert!(parse("alice wk_1 extra").is_err());
+        assert!(parse("alice wk_1\nalice wk_2").is_err());
+        assert_eq!(
+            Err(
+                "line 2: Player names must be 1 to 32 characters, with no control characters"
+                    .to_owned()
+            ),
+            parse(&format!("alice wk_1\n{} wk_2", "x".repeat(33)))
+        );
+        assert!(parse("al\u{7}ice wk_1").is_err());
+    }
+}
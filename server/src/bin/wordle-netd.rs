@@ -0,0 +1,83 @@
+//! Runner for `wordle-netd`, which serves games over a line-based TCP protocol
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{net::SocketAddr, path::PathBuf, process};
+
+use clap::Parser;
+use tokio::net::TcpListener;
+use wordle::{messages::Messages, startup, wordlist::Limits};
+use wordle_server::netd::{self, Settings};
+
+/// Serve Wordle games over a line-based TCP protocol, one game per connection.
+///
+/// Clients send `NEW`, `GUESS word`, `BOARD` and `QUIT`, and get back replies
+/// like `OK gy--g 2/6` or `ERR not-in-dictionary`.
+#[derive(Debug, PartialEq, Eq, Parser)]
+#[command(version, about)]
+struct Args {
+    /// The address to listen on
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:4242")]
+    listen: SocketAddr,
+
+    /// File with the list of acceptable guesses, one per line
+    #[arg(long, value_name = "PATH", default_value = "guesses.txt")]
+    guesses: PathBuf,
+
+    /// File with the list of answers, one per line
+    #[arg(long, value_name = "PATH", default_value = "answers.txt")]
+    answers: PathBuf,
+
+    /// Use the built-in word lists, even if the word list files exist
+    #[arg(long, conflicts_with_all = ["guesses", "answers"])]
+    builtin_words: bool,
+
+    /// The number of letters in each word; the word lists must match
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u8).range(4..=7))]
+    length: u8,
+
+    /// Make every game a hard mode game
+    #[arg(long)]
+    hard: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let (guesses, answers) = startup::load_word_lists(
+        Messages::english(),
+        &args.guesses,
+        &args.answers,
+        args.builtin_words,
+        &Limits::default(),
+    );
+    let settings =
+        Settings::new(guesses, answers, args.length.into(), args.hard).unwrap_or_else(|e| {
+            eprintln!("Error: invalid word lists: {}", e);
+            process::exit(1);
+        });
+
+    let listener = TcpListener::bind(args.listen).await.unwrap_or_else(|e| {
+        eprintln!("Error: could not listen on {}: {}", args.listen, e);
+        process::exit(1);
+    });
+    let addr = listener.local_addr().unwrap_or(args.listen);
+    eprintln!("Listening on {}", addr);
+    if let Err(e) = netd::serve(listener, settings).await {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,418 @@
+//! The daily puzzle as the server has it, and its leaderboard: results for the
+//! day's puzzle, checked by playing them again
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    error::Error,
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use chrono::NaiveDate;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use wordle::{
+    daily,
+    transcript::Transcript,
+    verify::{self, Verified, VerifyError},
+    GameStatus,
+};
+
+/// The longest a player's name can be
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// A result on the leaderboard
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DailyResult {
+    /// The day the puzzle was played
+    pub date: NaiveDate,
+    /// The number of the puzzle
+    pub puzzle: u32,
+    /// The player's name, or the anonymous token they were given
+    pub player: String,
+    /// The number of guesses it took to win
+    pub guesses: usize,
+    /// The time the client says the game took, which can't be checked
+    pub elapsed: Duration,
+    /// Whether the game was played in hard mode
+    pub hard_mode: bool,
+    /// When the server accepted the result
+    pub submitted: SystemTime,
+}
+
+/// Why a result wasn't put on the leaderboard
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DailyError {
+    /// There's no puzzle on that date
+    NoPuzzle,
+    /// The result isn't for today's puzzle
+    WrongPuzzle {
+        /// The number of today's puzzle
+        expected: u32,
+        /// The puzzle the result is for, if it says
+        actual: Option<u32>,
+    },
+    /// The player's name is empty, too long, or has control characters in it
+    InvalidName,
+    /// The result names a different player than the API key it was sent with
+    WrongPlayer {
+        /// The name the key was issued to
+        key: String,
+    },
+    /// The result was sent without an API key, but names a player who was
+    /// issued one
+    ReservedName {
+        /// The player's name
+        player: String,
+    },
+    /// Playing the transcript again doesn't give the same game
+    Forged(VerifyError),
+    /// The game was played with help from the solver, or with hints
+    Assisted,
+    /// The game wasn't won
+    NotWon,
+    /// The player already has a result for the day
+    AlreadySubmitted,
+}
+
+impl fmt::Display for DailyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPuzzle => write!(f, "There's no puzzle on that date"),
+            Self::WrongPuzzle {
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "Results can only be given for today's puzzle (#{}), not #{}",
+                expected, actual
+            ),
+            Self::WrongPuzzle {
+                expected,
+                actual: None,
+            } => write!(
+                f,
+                "Results can only be given for today's puzzle (#{}), and this game wasn't a daily puzzle",
+                expected
+            ),
+            Self::InvalidName => write!(
+                f,
+                "Player names must be 1 to {} characters, with no control characters",
+                MAX_NAME_LENGTH
+            ),
+            Self::WrongPlayer { key } => write!(
+                f,
+                "The API key was issued to {}, so results sent with it can't be for anyone else",
+                key
+            ),
+            Self::ReservedName { player } => write!(
+                f,
+                "An API key was issued to {}, so results for them must be sent with it",
+                player
+            ),
+            Self::Forged(e) => write!(f, "The transcript doesn't add up: {}", e),
+            Self::Assisted => write!(
+                f,
+                "Games played with help from the solver or hints don't count"
+            ),
+            Self::NotWon => write!(f, "Only won games go on the leaderboard"),
+            Self::AlreadySubmitted => write!(f, "That player already has a result for today"),
+        }
+    }
+}
+
+impl Error for DailyError {}
+
+impl DailyError {
+    /// A short name for the kind of error, for clients to tell them apart by
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoPuzzle => "no_puzzle",
+            Self::WrongPuzzle { .. } => "wrong_puzzle",
+            Self::InvalidName => "invalid_name",
+            Self::WrongPlayer { .. } => "wrong_player",
+            Self::ReservedName { .. } => "reserved_name",
+            Self::Forged(VerifyError::WrongAnswer { .. }) => "wrong_answer",
+            Self::Forged(VerifyError::IllegalGuess { .. }) => "illegal_guess",
+            Self::Forged(VerifyError::ForgedFeedback { .. }) => "forged_feedback",
+            Self::Forged(VerifyError::StatusMismatch { .. }) => "status_mismatch",
+            Self::Forged(VerifyError::InvalidWordList(_)) => "invalid_word_list",
+            Self::Forged(_) => "wrong_rules",
+            Self::Assisted => "assisted",
+            Self::NotWon => "not_won",
+            Self::AlreadySubmitted => "already_submitted",
+        }
+    }
+}
+
+/// Check a player's name, and get it back without surrounding whitespace
+pub fn check_name(name: &str) -> Result<&str, DailyError> {
+    let name = name.trim();
+    let length = name.chars().count();
+    if length == 0 || length > MAX_NAME_LENGTH || name.chars().any(char::is_control) {
+        return Err(DailyError::InvalidName);
+    }
+    Ok(name)
+}
+
+/// The HMAC-SHA256 of `message`, keyed by `secret`
+fn mac(secret: &[u8], message: &str) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().into()
+}
+
+/// The index in a list of `answers` answers of the answer for `date`, picked
+/// by a keyed hash of the date.
+///
+/// Without `secret`, which answer comes next can't be worked out, but every
+/// server with it picks the same one, without having to ask the others.
+pub fn keyed_index(secret: &[u8], date: NaiveDate, answers: usize) -> usize {
+    let hash = mac(secret, &date.to_string());
+    let n = u64::from_be_bytes(hash[..8].try_into().expect("The hash is 32 bytes"));
+    // the bias towards the start of the list is too small to matter
+    (n % answers as u64) as usize
+}
+
+/// A short keyed hash of the answer picked for `date`, so servers sharing
+/// `secret` can be checked to agree on it without giving it away
+pub fn fingerprint(secret: &[u8], date: NaiveDate, index: usize) -> String {
+    mac(secret, &format!("fingerprint {} {}", date, index))[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Check a transcript of the puzzle for `date` by playing it again against
+/// the day's answer, with `guesses` as the acceptable guesses, and get back
+/// how the game really went.
+///
+/// See [`verify::verify_transcript`] for what's checked.
+pub fn replay(
+    date: NaiveDate,
+    answer: &str,
+    guesses: &[&str],
+    transcript: &Transcript,
+) -> Result<Verified, DailyError> {
+    let expected = daily::puzzle_number(date).ok_or(DailyError::NoPuzzle)?;
+    if transcript.puzzle != Some(expected) {
+        return Err(DailyError::WrongPuzzle {
+            expected,
+            actual: transcript.puzzle,
+        });
+    }
+    if transcript.assist {
+        return Err(DailyError::Assisted);
+    }
+
+    let verified =
+        verify::verify_transcript(transcript, answer, guesses).map_err(DailyError::Forged)?;
+    // only known once the hints have been checked against the answer
+    if verified.hints > 0 {
+        return Err(DailyError::Assisted);
+    }
+    match verified.status {
+        GameStatus::Won { .. } => Ok(verified),
+        _ => Err(DailyError::NotWon),
+    }
+}
+
+/// Put results in leaderboard order: fewest guesses first, then the first
+/// to be sent in.
+///
+/// Ties aren't broken by the time the game took, since that's only what the
+/// client says it was. The sort is stable, so results that tie on both stay
+/// in the order they were given.
+pub fn rank(results: &mut [DailyResult]) {
+    results.sort_by_key(|result| (result.guesses, result.submitted));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 3] = ["crane", "slate", "irate"];
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()
+    }
+
+    /// A genuine transcript of the day's puzzle, guessing `words`
+    fn played(words: &[&str]) -> Transcript {
+        let (mut game, puzzle) = wordle::Wordle::new(&WORDS, &WORDS)
+            .choose_daily(date())
+            .unwrap();
+        for word in words {
+            game.guess(word).unwrap();
+        }
+        Transcript {
+            puzzle: Some(puzzle),
+            ..game.transcript().hide_answer()
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let answer = daily::answer(&WORDS, 196);
+        let transcript = played(&["crane", answer]);
+        let replayed = replay(date(), answer, &WORDS, &transcript).unwrap();
+        assert_eq!(GameStatus::Won { guesses: 2 }, replayed.status);
+
+        // a day later, it's no longer today's puzzle
+        assert_eq!(
+            Err(DailyError::WrongPuzzle {
+                expected: 197,
+                actual: Some(196)
+            }),
+            replay(date().succ_opt().unwrap(), answer, &WORDS, &transcript).map(|_| ())
+        );
+        assert_eq!(
+            Err(DailyError::NoPuzzle),
+            replay(
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                answer,
+                &WORDS,
+                &transcript
+            )
+            .map(|_| ())
+        );
+
+        let lost = Transcript {
+            status: GameStatus::Lost,
+            ..played(&["crane"])
+        };
+        assert_eq!(
+            Err(DailyError::NotWon),
+            replay(date(), answer, &WORDS, &lost).map(|_| ())
+        );
+        let assisted = Transcript {
+            assist: true,
+            ..transcript
+        };
+        assert_eq!(
+            Err(DailyError::Assisted),
+            replay(date(), answer, &WORDS, &assisted).map(|_| ())
+        );
+
+        // a letter given away by a hint is help too
+        let (mut game, _) = wordle::Wordle::new(&WORDS, &WORDS)
+            .choose_daily(date())
+            .unwrap();
+        game.hint().unwrap();
+        game.guess(answer).unwrap();
+        let hinted = Transcript {
+            puzzle: Some(196),
+            ..game.transcript().hide_answer()
+        };
+        assert_eq!(
+            Err(DailyError::Assisted),
+            replay(date(), answer, &WORDS, &hinted).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_forged() {
+        let answer = daily::answer(&WORDS, 196);
+
+        // claim a first-guess win with the wrong word
+        let mut forged = played(&["crane"]);
+        forged.guesses[0].feedback = vec![wordle::LetterStatus::Correct; 5];
+        forged.status = GameStatus::Won { guesses: 1 };
+        let e = replay(date(), answer, &WORDS, &forged).unwrap_err();
+        assert_eq!(
+            DailyError::Forged(VerifyError::ForgedFeedback {
+                turn: 1,
+                word: "crane".to_owned()
+            }),
+            e
+        );
+        assert_eq!("forged_feedback", e.code());
+
+        // and with an answer to match
+        forged.answer = Some("crane".to_owned());
+        let e = replay(date(), answer, &WORDS, &forged).unwrap_err();
+        assert_eq!("wrong_answer", e.code());
+
+        // a genuine game against a different answer doesn't count either
+        let other = WORDS.iter().find(|&&word| word != answer).unwrap();
+        let e = replay(date(), other, &WORDS, &played(&[answer])).unwrap_err();
+        assert_eq!("wrong_answer", e.code());
+    }
+
+    #[test]
+    fn test_keyed_index() {
+        let days: Vec<_> = date().iter_days().take(30).collect();
+        let indexes = |secret: &[u8]| -> Vec<_> {
+            days.iter()
+                .map(|&day| keyed_index(secret, day, 2315))
+                .collect()
+        };
+        assert_eq!(indexes(b"secret"), indexes(b"secret"));
+        assert_ne!(indexes(b"secret"), indexes(b"other secret"));
+        assert!(indexes(b"secret").iter().all(|&index| index < 2315));
+
+        let index = keyed_index(b"secret", date(), 2315);
+        assert_eq!(16, fingerprint(b"secret", date(), index).len());
+        assert_eq!(
+            fingerprint(b"secret", date(), index),
+            fingerprint(b"secret", date(), index)
+        );
+        assert_ne!(
+            fingerprint(b"secret", date(), index),
+            fingerprint(b"other secret", date(), index)
+        );
+    }
+
+    #[test]
+    fn test_check_name() {
+        assert_eq!(Ok("alice"), check_name("  alice "));
+        assert_eq!(Err(DailyError::InvalidName), check_name(" "));
+        assert_eq!(Err(DailyError::InvalidName), check_name("a\nb"));
+        assert_eq!(Err(DailyError::InvalidName), check_name(&"x".repeat(33)));
+        assert_eq!(Ok("é".repeat(32).as_str()), check_name(&"é".repeat(32)));
+    }
+
+    #[test]
+    fn test_rank() {
+        // `elapsed` is whatever the client said, so it doesn't break ties
+        let result = |player: &str, guesses, elapsed, submitted| DailyResult {
+            date: date(),
+            puzzle: 196,
+            player: player.to_owned(),
+            guesses,
+            elapsed: Duration::from_secs(elapsed),
+            hard_mode: false,
+            submitted: SystemTime::UNIX_EPOCH + Duration::from_secs(submitted),
+        };
+        let mut results = vec![
+            result("second", 3, 90, 20),
+            result("lucky", 1, 200, 40),
+            result("forged", 3, 0, 30),
+            result("first", 3, 90, 10),
+            result("also second", 3, 90, 20),
+        ];
+        rank(&mut results);
+        let players: Vec<_> = results
+            .iter()
+            .map(|result| result.player.as_str())
+            .collect();
+        assert_eq!(
+            vec!["lucky", "first", "second", "also second", "forged"],
+            players
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! An HTTP server for playing Wordle, so games can be played from any client
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![warn(
+    missing_docs,
+    rust_2018_idioms,
+    missing_debug_implementations,
+    rustdoc::broken_intra_doc_links
+)]
+
+use std::{future::Future, io, net::SocketAddr};
+
+use tokio::net::TcpListener;
+
+pub mod api;
+pub mod auth;
+pub mod daily;
+pub mod line;
+pub mod metrics;
+pub mod netd;
+pub mod race;
+pub mod ratelimit;
+pub mod sqlite;
+pub mod state;
+pub mod store;
+
+pub use api::router;
+pub use state::Server;
+
+/// Serve the API on `listener` until the server fails
+pub async fn serve(listener: TcpListener, server: Server) -> io::Result<()> {
+    serve_until(listener, server, std::future::pending()).await
+}
+
+/// Serve the API on `listener` until `shutdown` completes (or the server fails).
+///
+/// Once `shutdown` completes, no new connections are accepted, and this
+/// returns when the requests already being handled have been answered.
+pub async fn serve_until(
+    listener: TcpListener,
+    server: Server,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> io::Result<()> {
+    axum::serve(
+        listener,
+        router(server).into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown)
+    .await
+}
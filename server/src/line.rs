@@ -0,0 +1,449 @@
+//! The line-based protocol spoken by `wordle-netd`, simple enough to play over netcat
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! A client sends one command per line, and gets back one reply per line:
+//!
+//! ```text
+//! > NEW
+//! < OK new 0/6
+//! > GUESS slate
+//! < OK ---y- 1/6
+//! > GUESS crane
+//! < OK ggggg 2/6 won
+//! > BOARD
+//! < OK slate=---y- crane=ggggg 2/6
+//! > GUESS zzzzz
+//! < ERR game-over
+//! > QUIT
+//! < OK bye
+//! ```
+//!
+//! Feedback has a letter for each letter of the guess: `g` for a letter in the
+//! right place, `y` for one in the word but in the wrong place, and `-` for
+//! one not in the word. A lost game ends with `lost` and the answer.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use wordle::{GuessError, LetterStatus};
+
+/// The longest line either side sends, not counting the newline
+pub const MAX_LINE: usize = 256;
+
+/// A command from the client
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+    /// `NEW`: start a new game, abandoning the one in progress
+    New,
+    /// `GUESS word`: guess a word
+    Guess(String),
+    /// `BOARD`: show the guesses made so far
+    Board,
+    /// `QUIT`: close the connection
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = ErrorCode;
+
+    /// Read a command; the command itself can be in any case
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default().to_ascii_uppercase();
+        let command = match (command.as_str(), words.next()) {
+            ("NEW", None) => Self::New,
+            ("GUESS", Some(word)) => Self::Guess(word.to_owned()),
+            ("GUESS", None) => return Err(ErrorCode::MissingWord),
+            ("BOARD", None) => Self::Board,
+            ("QUIT", None) => Self::Quit,
+            _ => return Err(ErrorCode::UnknownCommand),
+        };
+        match words.next() {
+            None => Ok(command),
+            Some(_) => Err(ErrorCode::UnknownCommand),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::New => write!(f, "NEW"),
+            Self::Guess(word) => write!(f, "GUESS {}", word),
+            Self::Board => write!(f, "BOARD"),
+            Self::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+/// Why a command failed, as sent after `ERR`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorCode {
+    /// The line isn't a command
+    UnknownCommand,
+    /// `GUESS` without a word
+    MissingWord,
+    /// The line is longer than [`MAX_LINE`]
+    LineTooLong,
+    /// There's no game yet; send `NEW` first
+    NoGame,
+    /// The guess isn't in the list of acceptable guesses
+    NotInDictionary,
+    /// The guess has the wrong number of letters
+    WrongLength,
+    /// The guess has something in it that isn't a letter
+    InvalidLetter,
+    /// The word was already guessed
+    AlreadyGuessed,
+    /// The guess doesn't use a hint revealed by an earlier guess, in hard mode
+    HardMode,
+    /// The game is over; send `NEW` to play again
+    GameOver,
+}
+
+impl ErrorCode {
+    /// Every code, to look one up by name
+    const ALL: [Self; 10] = [
+        Self::UnknownCommand,
+        Self::MissingWord,
+        Self::LineTooLong,
+        Self::NoGame,
+        Self::NotInDictionary,
+        Self::WrongLength,
+        Self::InvalidLetter,
+        Self::AlreadyGuessed,
+        Self::HardMode,
+        Self::GameOver,
+    ];
+
+    /// The code as it's sent
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnknownCommand => "unknown-command",
+            Self::MissingWord => "missing-word",
+            Self::LineTooLong => "line-too-long",
+            Self::NoGame => "no-game",
+            Self::NotInDictionary => "not-in-dictionary",
+            Self::WrongLength => "wrong-length",
+            Self::InvalidLetter => "invalid-letter",
+            Self::AlreadyGuessed => "already-guessed",
+            Self::HardMode => "hard-mode",
+            Self::GameOver => "game-over",
+        }
+    }
+}
+
+impl From<&GuessError> for ErrorCode {
+    fn from(e: &GuessError) -> Self {
+        match e {
+            GuessError::WrongLength { .. } => Self::WrongLength,
+            GuessError::InvalidLetter { .. } => Self::InvalidLetter,
+            GuessError::NotInWordList => Self::NotInDictionary,
+            GuessError::AlreadyGuessed { .. } => Self::AlreadyGuessed,
+            GuessError::HardMode(_) => Self::HardMode,
+            GuessError::GameOver => Self::GameOver,
+            GuessError::NoWord => Self::NoGame,
+        }
+    }
+}
+
+/// How a game ended
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    /// The word was found
+    Won,
+    /// The guesses ran out
+    Lost {
+        /// The answer
+        answer: String,
+    },
+}
+
+/// A reply from the server
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Reply {
+    /// Sent once a client connects: `OK wordle-netd`
+    Hello,
+    /// A new game was started: `OK new 0/6`
+    Started {
+        /// The number of guesses allowed
+        max_guesses: usize,
+    },
+    /// The feedback for a guess: `OK gy--g 2/6`, then `won`, or `lost` and
+    /// the answer, if the game is over
+    Guessed {
+        /// The feedback for each letter
+        feedback: Vec<LetterStatus>,
+        /// The number of guesses made so far, including this one
+        turn: usize,
+        /// The number of guesses allowed
+        max_guesses: usize,
+        /// How the game ended, if it's over
+        outcome: Option<Outcome>,
+    },
+    /// The guesses made so far: `OK slate=---y- crane=ggggg 2/6`
+    Board {
+        /// Each guess, and its feedback
+        rows: Vec<(String, Vec<LetterStatus>)>,
+        /// The number of guesses allowed
+        max_guesses: usize,
+    },
+    /// The connection is closing: `OK bye`
+    Bye,
+    /// The command failed: `ERR not-in-dictionary`
+    Error(ErrorCode),
+}
+
+/// Feedback as it's sent, e.g. `gy--g`
+pub fn pattern(feedback: &[LetterStatus]) -> String {
+    feedback
+        .iter()
+        .map(|status| match status {
+            LetterStatus::Correct => 'g',
+            LetterStatus::InWord => 'y',
+            LetterStatus::NotInWord => '-',
+        })
+        .collect()
+}
+
+/// Read feedback as it's sent
+fn parse_pattern(text: &str) -> Result<Vec<LetterStatus>, ParseReplyError> {
+    text.chars()
+        .map(|c| match c {
+            'g' => Ok(LetterStatus::Correct),
+            'y' => Ok(LetterStatus::InWord),
+            '-' => Ok(LetterStatus::NotInWord),
+            _ => Err(ParseReplyError),
+        })
+        .collect()
+}
+
+/// Read a count of guesses as it's sent, e.g. `2/6`
+fn parse_turn(text: &str) -> Result<(usize, usize), ParseReplyError> {
+    let (turn, max) = text.split_once('/').ok_or(ParseReplyError)?;
+    Ok((
+        turn.parse().map_err(|_| ParseReplyError)?,
+        max.parse().map_err(|_| ParseReplyError)?,
+    ))
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hello => write!(f, "OK wordle-netd"),
+            Self::Started { max_guesses } => write!(f, "OK new 0/{}", max_guesses),
+            Self::Guessed {
+                feedback,
+                turn,
+                max_guesses,
+                outcome,
+            } => {
+                write!(f, "OK {} {}/{}", pattern(feedback), turn, max_guesses)?;
+                match outcome {
+                    None => Ok(()),
+                    Some(Outcome::Won) => write!(f, " won"),
+                    Some(Outcome::Lost { answer }) => write!(f, " lost {}", answer),
+                }
+            }
+            Self::Board { rows, max_guesses } => {
+                write!(f, "OK")?;
+                for (word, feedback) in rows {
+                    write!(f, " {}={}", word, pattern(feedback))?;
+                }
+                write!(f, " {}/{}", rows.len(), max_guesses)
+            }
+            Self::Bye => write!(f, "OK bye"),
+            Self::Error(code) => write!(f, "ERR {}", code.as_str()),
+        }
+    }
+}
+
+/// A line that isn't a [`Reply`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseReplyError;
+
+impl fmt::Display for ParseReplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a wordle-netd reply")
+    }
+}
+
+impl Error for ParseReplyError {}
+
+impl FromStr for Reply {
+    type Err = ParseReplyError;
+
+    /// Read a reply, e.g. for a bot playing over the protocol
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            ["OK", "wordle-netd"] => Ok(Self::Hello),
+            ["OK", "bye"] => Ok(Self::Bye),
+            ["OK", "new", turn] => match parse_turn(turn)? {
+                (0, max_guesses) => Ok(Self::Started { max_guesses }),
+                _ => Err(ParseReplyError),
+            },
+            ["ERR", code] => ErrorCode::ALL
+                .into_iter()
+                .find(|known| known.as_str() == code)
+                .map(Self::Error)
+                .ok_or(ParseReplyError),
+            ["OK", first, ..] if !first.contains('=') && !first.contains('/') => {
+                let (turn, max_guesses) = parse_turn(words.get(2).ok_or(ParseReplyError)?)?;
+                let outcome = match words[3..] {
+                    [] => None,
+                    ["won"] => Some(Outcome::Won),
+                    ["lost", answer] => Some(Outcome::Lost {
+                        answer: answer.to_owned(),
+                    }),
+                    _ => return Err(ParseReplyError),
+                };
+                Ok(Self::Guessed {
+                    feedback: parse_pattern(first)?,
+                    turn,
+                    max_guesses,
+                    outcome,
+                })
+            }
+            ["OK", ref rest @ .., turn] => {
+                let rows = rest
+                    .iter()
+                    .map(|row| {
+                        let (word, feedback) = row.split_once('=').ok_or(ParseReplyError)?;
+                        Ok((word.to_owned(), parse_pattern(feedback)?))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (count, max_guesses) = parse_turn(turn)?;
+                if count != rows.len() {
+                    return Err(ParseReplyError);
+                }
+                Ok(Self::Board { rows, max_guesses })
+            }
+            _ => Err(ParseReplyError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterStatus::{Correct as G, InWord as Y, NotInWord as N};
+
+    #[test]
+    fn test_command() {
+        assert_eq!(Ok(Command::New), "NEW".parse());
+        assert_eq!(Ok(Command::New), " new \r".parse());
+        assert_eq!(Ok(Command::Guess("crane".into())), "GUESS crane".parse());
+        assert_eq!(Ok(Command::Guess("CRANE".into())), "guess  CRANE".parse());
+        assert_eq!(Ok(Command::Board), "Board".parse());
+        assert_eq!(Ok(Command::Quit), "QUIT".parse());
+
+        assert_eq!(Err(ErrorCode::MissingWord), "GUESS".parse::<Command>());
+        assert_eq!(
+            Err(ErrorCode::UnknownCommand),
+            "GUESS a b".parse::<Command>()
+        );
+        assert_eq!(
+            Err(ErrorCode::UnknownCommand),
+            "NEW game".parse::<Command>()
+        );
+        assert_eq!(Err(ErrorCode::UnknownCommand), "HELLO".parse::<Command>());
+        assert_eq!(Err(ErrorCode::UnknownCommand), "".parse::<Command>());
+
+        for command in [
+            Command::New,
+            Command::Guess("crane".into()),
+            Command::Board,
+            Command::Quit,
+        ] {
+            assert_eq!(Ok(command.clone()), command.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_reply() {
+        let replies = [
+            (Reply::Hello, "OK wordle-netd"),
+            (Reply::Started { max_guesses: 6 }, "OK new 0/6"),
+            (
+                Reply::Guessed {
+                    feedback: vec![G, Y, N, N, G],
+                    turn: 2,
+                    max_guesses: 6,
+                    outcome: None,
+                },
+                "OK gy--g 2/6",
+            ),
+            (
+                Reply::Guessed {
+                    feedback: vec![G; 5],
+                    turn: 3,
+                    max_guesses: 6,
+                    outcome: Some(Outcome::Won),
+                },
+                "OK ggggg 3/6 won",
+            ),
+            (
+                Reply::Guessed {
+                    feedback: vec![N; 5],
+                    turn: 6,
+                    max_guesses: 6,
+                    outcome: Some(Outcome::Lost {
+                        answer: "crane".into(),
+                    }),
+                },
+                "OK ----- 6/6 lost crane",
+            ),
+            (
+                Reply::Board {
+                    rows: vec![
+                        ("slate".into(), vec![N, N, Y, N, N]),
+                        ("crane".into(), vec![G; 5]),
+                    ],
+                    max_guesses: 6,
+                },
+                "OK slate=--y-- crane=ggggg 2/6",
+            ),
+            (
+                Reply::Board {
+                    rows: Vec::new(),
+                    max_guesses: 6,
+                },
+                "OK 0/6",
+            ),
+            (Reply::Bye, "OK bye"),
+            (
+                Reply::Error(ErrorCode::NotInDictionary),
+                "ERR not-in-dictionary",
+            ),
+        ];
+        for (reply, line) in replies {
+            assert_eq!(line, reply.to_string());
+            assert_eq!(Ok(reply), line.parse());
+        }
+
+        for code in ErrorCode::ALL {
+            let reply = Reply::Error(code);
+            assert_eq!(Ok(reply.clone()), reply.to_string().parse());
+        }
+
+        assert_eq!(Err(ParseReplyError), "OK gx--g 2/6".parse::<Reply>());
+        assert_eq!(Err(ParseReplyError), "OK slate=--y-- 2/6".parse::<Reply>());
+        assert_eq!(Err(ParseReplyError), "ERR nope".parse::<Reply>());
+        assert_eq!(Err(ParseReplyError), "hello".parse::<Reply>());
+    }
+}
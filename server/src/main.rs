@@ -0,0 +1,480 @@
+//! Runner for the Wordle server
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#[cfg(feature = "schema")]
+use std::path::Path;
+use std::{
+    future::Future,
+    io,
+    net::SocketAddr,
+    path::PathBuf,
+    process,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use tokio::signal;
+use tokio_util::sync::CancellationToken;
+use wordle::{definitions::Definitions, messages::Messages, startup, wordlist};
+use wordle_server::{
+    auth::{self, ApiKey, Required},
+    daily,
+    ratelimit::RateLimit,
+    sqlite::SqliteStore,
+    state::{self, Limits},
+    store::{GameStore, MemoryStore},
+    Server,
+};
+
+/// Where to keep the games being played
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum StoreKind {
+    /// In memory, so they're lost when the server stops
+    Memory,
+    /// In a SQLite database at this path
+    Sqlite(PathBuf),
+}
+
+/// Parse a `--store` value: `memory`, or `sqlite:` followed by a path
+fn parse_store(value: &str) -> Result<StoreKind, String> {
+    match value.split_once(':') {
+        None if value == "memory" => Ok(StoreKind::Memory),
+        Some(("sqlite", path)) if !path.is_empty() => Ok(StoreKind::Sqlite(path.into())),
+        _ => Err("expected 'memory' or 'sqlite:PATH'".to_owned()),
+    }
+}
+
+/// A route that can be made to need an API key
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+enum KeyRoute {
+    /// Starting games and making guesses
+    Games,
+    /// Putting results on the daily leaderboard
+    Results,
+}
+
+/// Parse a name to issue a key to, which can't have spaces so it fits in a
+/// key file
+fn parse_key_name(value: &str) -> Result<String, String> {
+    if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("expected a name without spaces".to_owned());
+    }
+    Ok(value.to_owned())
+}
+
+/// Serve Wordle games over HTTP
+#[derive(Debug, PartialEq, Eq, Parser)]
+#[command(version, about)]
+struct Args {
+    /// The address to listen on
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+
+    /// File with the list of acceptable guesses, one per line
+    #[arg(long, value_name = "PATH", default_value = "guesses.txt")]
+    guesses: PathBuf,
+
+    /// File with the list of answers, one per line
+    #[arg(long, value_name = "PATH", default_value = "answers.txt")]
+    answers: PathBuf,
+
+    /// Use the built-in word lists, even if the word list files exist
+    #[arg(long, conflicts_with_all = ["guesses", "answers"])]
+    builtin_words: bool,
+
+    /// File with a `word<TAB>definition` line for each word, to give the
+    /// answer's definition once a game is over
+    #[arg(long, value_name = "PATH")]
+    definitions: Option<PathBuf>,
+
+    /// The number of letters in each word; the word lists must match
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u8).range(4..=7))]
+    length: u8,
+
+    /// The most words a word list can have, so a huge one can't use up all
+    /// the memory
+    #[arg(long, value_name = "N", default_value_t = 1_000_000)]
+    max_words: usize,
+
+    /// Where to keep games: `memory`, or `sqlite:PATH` to keep them in a
+    /// database so they survive a restart
+    #[arg(long, value_name = "STORE", default_value = "memory", value_parser = parse_store)]
+    store: StoreKind,
+
+    /// How long to keep a game after it was last played, in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 60 * 60)]
+    game_ttl: u64,
+
+    /// How long to keep a paused game after it was last looked at, in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 7 * 24 * 60 * 60)]
+    paused_game_ttl: u64,
+
+    /// The most games one client (by IP address) can have in progress at once
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    max_games_per_client: usize,
+
+    /// How often one client (by IP address) can start games, join races, and
+    /// make guesses, e.g. `30/min` or `500/h` (default: no limit)
+    #[arg(long, value_name = "N/WINDOW")]
+    rate_limit: Option<RateLimit>,
+
+    /// Pick each day's answer by a keyed hash of the date with this secret,
+    /// instead of going through the answer list in order; servers with the
+    /// same secret pick the same answers. The `wordle` binary's own daily
+    /// puzzle then has a different answer, so `wordle --server` doesn't send
+    /// its results; only daily games played through the API can be submitted
+    #[arg(
+        long,
+        value_name = "SECRET",
+        env = "WORDLE_DAILY_SECRET",
+        hide_env_values = true
+    )]
+    daily_secret: Option<String>,
+
+    /// File with a `name key` line for each API key to accept, along with
+    /// the keys issued to the store
+    #[arg(long, value_name = "PATH")]
+    api_keys: Option<PathBuf>,
+
+    /// Routes that need an API key, in an `Authorization: Bearer` header
+    /// (default: none, so anyone can play and submit results)
+    #[arg(long, value_name = "ROUTES", value_delimiter = ',')]
+    require_key: Vec<KeyRoute>,
+
+    /// The most players a race room can have
+    #[arg(long, value_name = "N", default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+    max_players: u16,
+
+    /// How long to wait for requests in progress to finish when shutting
+    /// down, in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 20)]
+    shutdown_timeout: u64,
+
+    /// Write a JSON Schema for each request and response body, and each type
+    /// that's saved or sent over a socket, to this directory, and exit
+    #[cfg(feature = "schema")]
+    #[arg(long, value_name = "DIR")]
+    dump_schemas: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Something to do instead of serving games
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+enum Command {
+    /// Manage the API keys kept in the store, which must be a database
+    #[command(subcommand)]
+    Keys(KeysCommand),
+}
+
+/// A `keys` subcommand
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+enum KeysCommand {
+    /// Issue a key to NAME, and print it; it can't be shown again later
+    Issue {
+        /// Who the key is for; results submitted with it are put down to them
+        #[arg(value_parser = parse_key_name)]
+        name: String,
+    },
+    /// Revoke NAME's key, so it's turned away from then on
+    Revoke {
+        /// Whose key to revoke
+        name: String,
+    },
+    /// List the keys, and whether each has been revoked
+    List,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    #[cfg(feature = "schema")]
+    if let Some(dir) = &args.dump_schemas {
+        dump_schemas(dir);
+        return;
+    }
+    let word_limits = wordlist::Limits {
+        max_words: Some(args.max_words),
+        ..wordlist::Limits::default()
+    };
+    let (guesses, answers) = startup::load_word_lists(
+        Messages::english(),
+        &args.guesses,
+        &args.answers,
+        args.builtin_words,
+        &word_limits,
+    );
+    if let Some(Command::Keys(command)) = &args.command {
+        manage_keys(&args.store, guesses, answers, args.length.into(), command);
+        return;
+    }
+    let limits = Limits {
+        ttl: Duration::from_secs(args.game_ttl),
+        paused_ttl: Duration::from_secs(args.paused_game_ttl),
+        max_games_per_client: args.max_games_per_client,
+        max_players_per_room: args.max_players.into(),
+        rate_limit: args.rate_limit,
+    };
+    let length = args.length.into();
+    let server = Server::new(guesses, answers, length).unwrap_or_else(|e| {
+        eprintln!("Error: invalid word lists: {}", e);
+        process::exit(1);
+    });
+    let store: Arc<dyn GameStore> = match &args.store {
+        StoreKind::Memory => Arc::new(MemoryStore::new()),
+        StoreKind::Sqlite(path) => Arc::new(
+            SqliteStore::open(path, guesses, answers, length).unwrap_or_else(|e| {
+                eprintln!("Error: could not open database '{}': {}", path.display(), e);
+                process::exit(1);
+            }),
+        ),
+    };
+    let keys = match &args.api_keys {
+        Some(path) => auth::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: could not read API keys '{}': {}", path.display(), e);
+            process::exit(1);
+        }),
+        None => Vec::new(),
+    };
+    let required = Required {
+        games: args.require_key.contains(&KeyRoute::Games),
+        results: args.require_key.contains(&KeyRoute::Results),
+    };
+    let mut server = server
+        .with_store(store)
+        .with_limits(limits)
+        .with_keys(keys, required);
+    if let Some(secret) = &args.daily_secret {
+        server = server.with_daily_secret(secret.as_bytes());
+    }
+    if let Some(path) = &args.definitions {
+        let definitions = Definitions::load(path).unwrap_or_else(|e| {
+            eprintln!(
+                "Error: could not read definitions '{}': {}",
+                path.display(),
+                e
+            );
+            process::exit(1);
+        });
+        server = server.with_definitions(definitions);
+    }
+
+    // listen for signals before anything else, so one can't kill the server
+    // before it's ready to shut down cleanly
+    let signalled = shutdown_signal().unwrap_or_else(|e| {
+        eprintln!("Error: could not listen for signals: {}", e);
+        process::exit(1);
+    });
+    let shutdown = CancellationToken::new();
+
+    // check for abandoned games often enough that none outstays the TTL by much
+    let interval = (limits.ttl / 10).clamp(Duration::from_secs(1), Duration::from_secs(60));
+    let sweeper = tokio::spawn(state::sweep(server.clone(), interval, shutdown.clone()));
+
+    let listener = tokio::net::TcpListener::bind(args.listen)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Error: could not listen on {}: {}", args.listen, e);
+            process::exit(1);
+        });
+    let addr = listener.local_addr().unwrap_or(args.listen);
+    eprintln!("Listening on {}", addr);
+    let mut serving = tokio::spawn(wordle_server::serve_until(
+        listener,
+        server.clone(),
+        shutdown.clone().cancelled_owned(),
+    ));
+
+    tokio::select! {
+        result = &mut serving => {
+            let e = match result {
+                Ok(Ok(())) => "the server stopped".to_owned(),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) => e.to_string(),
+            };
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        () = signalled => {}
+    }
+
+    eprintln!("Shutting down once the requests in progress are finished");
+    shutdown.cancel();
+    let deadline = Duration::from_secs(args.shutdown_timeout);
+    match tokio::time::timeout(deadline, serving).await {
+        Ok(Ok(Ok(()))) => {}
+        Ok(Ok(Err(e))) => eprintln!("Warning: the server failed while shutting down: {}", e),
+        Ok(Err(e)) => eprintln!("Warning: the server failed while shutting down: {}", e),
+        Err(_) => eprintln!(
+            "Warning: requests were still in progress after {} seconds; stopping anyway",
+            args.shutdown_timeout
+        ),
+    }
+    let _ = sweeper.await;
+    if let Err(e) = server.flush() {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+    eprintln!("Stopped");
+}
+
+/// Write the schemas for the API and the library's saved and sent types to `dir`
+#[cfg(feature = "schema")]
+fn dump_schemas(dir: &Path) {
+    let schemas: Vec<_> = wordle::schema::schemas()
+        .into_iter()
+        .chain(wordle_server::api::schemas())
+        .collect();
+    match wordle::schema::write(dir, &schemas) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!(
+                "Error: could not write schemas to '{}': {}",
+                dir.display(),
+                e
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Issue, revoke, or list the API keys in the database `store`, and exit if
+/// that fails
+fn manage_keys(
+    store: &StoreKind,
+    guesses: &'static [&'static str],
+    answers: &'static [&'static str],
+    length: usize,
+    command: &KeysCommand,
+) {
+    let StoreKind::Sqlite(path) = store else {
+        eprintln!(
+            "Error: keys can only be kept in a database; use --store sqlite:PATH, \
+             or put them in a file for --api-keys"
+        );
+        process::exit(1);
+    };
+    let fail = |e: &dyn std::fmt::Display| -> ! {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    };
+    let store = SqliteStore::open(path, guesses, answers, length).unwrap_or_else(|e| {
+        eprintln!("Error: could not open database '{}': {}", path.display(), e);
+        process::exit(1);
+    });
+    match command {
+        KeysCommand::Issue { name } => {
+            // results sent with the key go on the leaderboard under its name
+            let name = daily::check_name(name).unwrap_or_else(|e| fail(&e));
+            let (secret, key) = ApiKey::issue(name);
+            store.add_key(key).unwrap_or_else(|e| fail(&e));
+            eprintln!(
+                "Issued a key to {}; keep it safe, as it can't be shown again:",
+                name
+            );
+            println!("{}", secret);
+        }
+        KeysCommand::Revoke { name } => {
+            store
+                .revoke_key(name, SystemTime::now())
+                .unwrap_or_else(|e| fail(&e));
+            eprintln!("Revoked {}'s key", name);
+        }
+        KeysCommand::List => {
+            let format =
+                |time: SystemTime| DateTime::<Utc>::from(time).format("%Y-%m-%d %H:%M UTC");
+            for key in store.keys().unwrap_or_else(|e| fail(&e)) {
+                match key.revoked {
+                    Some(revoked) => println!(
+                        "{}\tissued {}\trevoked {}",
+                        key.name,
+                        format(key.created),
+                        format(revoked)
+                    ),
+                    None => println!("{}\tissued {}", key.name, format(key.created)),
+                }
+            }
+        }
+    }
+}
+
+/// Start listening for SIGTERM (as sent by e.g. Kubernetes) and Ctrl-C, and
+/// get back a future that completes when either arrives
+fn shutdown_signal() -> io::Result<impl Future<Output = ()>> {
+    #[cfg(unix)]
+    let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())?;
+    Ok(async move {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+        #[cfg(not(unix))]
+        let _ = signal::ctrl_c().await;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_store() {
+        assert_eq!(Ok(StoreKind::Memory), parse_store("memory"));
+        assert_eq!(
+            Ok(StoreKind::Sqlite("games.db".into())),
+            parse_store("sqlite:games.db")
+        );
+        assert_eq!(
+            Ok(StoreKind::Sqlite("/var/lib/wordle/games.db".into())),
+            parse_store("sqlite:/var/lib/wordle/games.db")
+        );
+        assert!(parse_store("sqlite:").is_err());
+        assert!(parse_store("postgres:games").is_err());
+        assert!(parse_store("disk").is_err());
+    }
+
+    #[test]
+    fn test_keys_command() {
+        let args = Args::parse_from([
+            "wordle-server",
+            "--store",
+            "sqlite:games.db",
+            "keys",
+            "issue",
+            "alice",
+        ]);
+        assert_eq!(
+            Some(Command::Keys(KeysCommand::Issue {
+                name: "alice".to_owned()
+            })),
+            args.command
+        );
+        assert!(Args::try_parse_from(["wordle-server", "keys", "issue", "bad name"]).is_err());
+
+        let args = Args::parse_from(["wordle-server", "--require-key", "games,results"]);
+        assert_eq!(vec![KeyRoute::Games, KeyRoute::Results], args.require_key);
+        assert_eq!(None, args.command);
+    }
+}
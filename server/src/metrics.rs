@@ -0,0 +1,285 @@
+//! Metrics about the games being played, for Prometheus to scrape
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use wordle::{GuessError, GuessOutcome, Wordle};
+
+use crate::daily::DailyResult;
+
+/// The labels on the metrics about games: how the game was chosen, and
+/// whether it was played in hard mode
+const GAME_LABELS: [&str; 2] = ["mode", "hard"];
+
+/// How a game was chosen, for the `mode` label
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    /// A day's puzzle, played on the server or by a client and then put on
+    /// the leaderboard
+    Daily,
+    /// A game with a random answer, played on the server
+    Endless,
+}
+
+impl Mode {
+    /// The value of the `mode` label
+    fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Endless => "endless",
+        }
+    }
+}
+
+/// The metrics, kept up to date by the request handlers as they go.
+///
+/// Each server has a registry of its own, so several can run in one process.
+#[derive(Debug)]
+pub struct Metrics {
+    /// Where the metrics are registered
+    registry: Registry,
+    /// Games started
+    games_created: IntCounterVec,
+    /// Games won
+    games_won: IntCounterVec,
+    /// Games lost
+    games_lost: IntCounterVec,
+    /// Valid guesses made
+    guesses: IntCounterVec,
+    /// Guesses that were rejected
+    invalid_guesses: IntCounterVec,
+    /// The number of guesses each win took
+    guesses_per_win: HistogramVec,
+    /// How long requests took to handle
+    request_duration: HistogramVec,
+    /// Games in progress, set just before the metrics are gathered
+    active_games: IntGauge,
+    /// Requests turned away for going over the rate limit
+    rate_limited: IntCounterVec,
+    /// Clients the rate limiter is keeping track of, set just before the
+    /// metrics are gathered
+    rate_limited_clients: IntGauge,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    /// Create the metrics, all starting at zero
+    pub fn new() -> Self {
+        let registry =
+            Registry::new_custom(Some("wordle".to_owned()), None).expect("The prefix is valid");
+        let counter = |name: &str, help: &str| {
+            let counter = IntCounterVec::new(Opts::new(name, help), &GAME_LABELS)
+                .expect("The counter is valid");
+            registry
+                .register(Box::new(counter.clone()))
+                .expect("Every metric has a name of its own");
+            counter
+        };
+        let games_created = counter("games_created_total", "Games started");
+        let games_won = counter("games_won_total", "Games won");
+        let games_lost = counter("games_lost_total", "Games lost");
+        let guesses = counter("guesses_total", "Valid guesses made");
+        let invalid_guesses = counter("invalid_guesses_total", "Guesses that were rejected");
+
+        let guesses_per_win = HistogramVec::new(
+            HistogramOpts::new("guesses_per_win", "The number of guesses each win took")
+                .buckets(prometheus::linear_buckets(1.0, 1.0, 10).expect("The buckets are valid")),
+            &GAME_LABELS,
+        )
+        .expect("The histogram is valid");
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "request_duration_seconds",
+                "How long requests took to handle",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("The histogram is valid");
+        let active_games =
+            IntGauge::new("active_games", "Games in progress").expect("The gauge is valid");
+        let rate_limited = IntCounterVec::new(
+            Opts::new(
+                "rate_limited_total",
+                "Requests turned away for going over the rate limit",
+            ),
+            &["route"],
+        )
+        .expect("The counter is valid");
+        let rate_limited_clients = IntGauge::new(
+            "rate_limited_clients",
+            "Clients the rate limiter is keeping track of",
+        )
+        .expect("The gauge is valid");
+        for metric in [
+            Box::new(guesses_per_win.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(request_duration.clone()),
+            Box::new(active_games.clone()),
+            Box::new(rate_limited.clone()),
+            Box::new(rate_limited_clients.clone()),
+        ] {
+            registry
+                .register(metric)
+                .expect("Every metric has a name of its own");
+        }
+
+        Self {
+            registry,
+            games_created,
+            games_won,
+            games_lost,
+            guesses,
+            invalid_guesses,
+            guesses_per_win,
+            request_duration,
+            active_games,
+            rate_limited,
+            rate_limited_clients,
+        }
+    }
+
+    /// Count a game being started
+    pub fn game_created(&self, mode: Mode, hard_mode: bool) {
+        self.games_created
+            .with_label_values(&labels(mode, hard_mode))
+            .inc();
+    }
+
+    /// Count a guess, and the end of the game if it was the last one
+    pub fn guessed(
+        &self,
+        mode: Mode,
+        game: &Wordle<'_>,
+        result: &Result<GuessOutcome, GuessError>,
+    ) {
+        let labels = labels(mode, game.hard_mode());
+        let Ok(outcome) = result else {
+            self.invalid_guesses.with_label_values(&labels).inc();
+            return;
+        };
+        self.guesses.with_label_values(&labels).inc();
+        match outcome.result() {
+            None => {}
+            Some(result) if result.won => self.won(&labels, result.turns_used),
+            Some(_) => self.games_lost.with_label_values(&labels).inc(),
+        }
+    }
+
+    /// Count a result put on the daily leaderboard, which is a game won
+    pub fn daily_result(&self, result: &DailyResult) {
+        self.won(&labels(Mode::Daily, result.hard_mode), result.guesses);
+    }
+
+    /// Count a game won in `guesses`
+    fn won(&self, labels: &[&str; 2], guesses: usize) {
+        self.games_won.with_label_values(labels).inc();
+        self.guesses_per_win
+            .with_label_values(labels)
+            .observe(guesses as f64);
+    }
+
+    /// Time a request
+    pub fn request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.request_duration
+            .with_label_values(&[method, route, &status.to_string()])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Count a request to `route` turned away for going over the rate limit
+    pub fn rate_limited(&self, route: &str) {
+        self.rate_limited.with_label_values(&[route]).inc();
+    }
+
+    /// The metrics in the Prometheus text format, with `active_games` games in
+    /// progress and the rate limiter keeping track of `clients` clients
+    pub fn render(&self, active_games: usize, clients: usize) -> String {
+        self.active_games
+            .set(active_games.try_into().unwrap_or(i64::MAX));
+        self.rate_limited_clients
+            .set(clients.try_into().unwrap_or(i64::MAX));
+        let mut text = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut text)
+            .expect("Metrics can always be written to memory");
+        String::from_utf8(text).expect("The text format is UTF-8")
+    }
+}
+
+/// The values of [`GAME_LABELS`]
+fn labels(mode: Mode, hard_mode: bool) -> [&'static str; 2] {
+    [mode.label(), if hard_mode { "true" } else { "false" }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guessed() {
+        let words = ["crane", "slate"];
+        let metrics = Metrics::new();
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        metrics.game_created(Mode::Endless, false);
+
+        let result = game.guess("xxxxx");
+        metrics.guessed(Mode::Endless, &game, &result);
+        let result = game.guess("slate");
+        metrics.guessed(Mode::Endless, &game, &result);
+        let result = game.guess("crane");
+        metrics.guessed(Mode::Endless, &game, &result);
+
+        metrics.daily_result(&DailyResult {
+            date: chrono::NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            puzzle: 196,
+            player: "alice".to_owned(),
+            guesses: 4,
+            elapsed: Duration::from_secs(60),
+            hard_mode: true,
+            submitted: std::time::SystemTime::UNIX_EPOCH,
+        });
+
+        metrics.rate_limited("/games");
+        let text = metrics.render(0, 3);
+        for line in [
+            r#"wordle_games_created_total{hard="false",mode="endless"} 1"#,
+            r#"wordle_games_won_total{hard="false",mode="endless"} 1"#,
+            r#"wordle_guesses_total{hard="false",mode="endless"} 2"#,
+            r#"wordle_invalid_guesses_total{hard="false",mode="endless"} 1"#,
+            r#"wordle_guesses_per_win_sum{hard="false",mode="endless"} 2"#,
+            r#"wordle_games_won_total{hard="true",mode="daily"} 1"#,
+            r#"wordle_guesses_per_win_sum{hard="true",mode="daily"} 4"#,
+            "wordle_active_games 0",
+            r#"wordle_rate_limited_total{route="/games"} 1"#,
+            "wordle_rate_limited_clients 3",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "{} not in:\n{}",
+                line,
+                text
+            );
+        }
+        assert!(!text.contains("wordle_games_lost_total{"));
+    }
+}
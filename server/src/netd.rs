@@ -0,0 +1,306 @@
+//! Playing games over the line-based protocol in [`line`](crate::line), one per connection
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{io, sync::Arc};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use wordle::{state::NoWord, BuildError, Wordle};
+
+use crate::line::{Command, ErrorCode, Outcome, Reply, MAX_LINE};
+
+/// How every game is set up
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// The list of acceptable guesses
+    guesses: &'static [&'static str],
+    /// The list of answers
+    answers: &'static [&'static str],
+    /// The number of letters in each word
+    word_length: usize,
+    /// Whether revealed hints have to be used in subsequent guesses
+    hard_mode: bool,
+}
+
+impl Settings {
+    /// Set up games with these word lists, checking that they're valid
+    pub fn new(
+        guesses: &'static [&'static str],
+        answers: &'static [&'static str],
+        word_length: usize,
+        hard_mode: bool,
+    ) -> Result<Self, BuildError> {
+        let settings = Self {
+            guesses,
+            answers,
+            word_length,
+            hard_mode,
+        };
+        settings.build()?;
+        Ok(settings)
+    }
+
+    /// A game with these settings, without an answer chosen yet
+    fn build(&self) -> Result<Wordle<'static, NoWord>, BuildError> {
+        Wordle::builder()
+            .guesses(self.guesses)
+            .answers(self.answers)
+            .word_length(self.word_length)
+            .hard_mode(self.hard_mode)
+            .build()
+    }
+
+    /// Start a new game
+    fn new_game(&self) -> Wordle<'static> {
+        self.build()
+            .expect("word lists were checked when the settings were made")
+            .choose_word()
+    }
+}
+
+/// Carry out a command on a connection's game, if it has one, and get the reply
+pub fn respond(settings: &Settings, game: &mut Option<Wordle<'static>>, command: Command) -> Reply {
+    match command {
+        Command::New => {
+            let new = game.insert(settings.new_game());
+            Reply::Started {
+                max_guesses: new.max_guesses(),
+            }
+        }
+        Command::Guess(word) => {
+            let Some(game) = game else {
+                return Reply::Error(ErrorCode::NoGame);
+            };
+            match game.guess(&word.to_lowercase()) {
+                Ok(outcome) => {
+                    let ended = outcome.result().map(|result| match result.won {
+                        true => Outcome::Won,
+                        false => Outcome::Lost {
+                            answer: result.answer.clone(),
+                        },
+                    });
+                    Reply::Guessed {
+                        feedback: outcome.into_feedback(),
+                        turn: game.history().len(),
+                        max_guesses: game.max_guesses(),
+                        outcome: ended,
+                    }
+                }
+                Err(e) => Reply::Error(ErrorCode::from(&e)),
+            }
+        }
+        Command::Board => match game {
+            Some(game) => Reply::Board {
+                rows: game
+                    .history()
+                    .iter()
+                    .map(|(word, feedback)| (word.to_string(), feedback.clone()))
+                    .collect(),
+                max_guesses: game.max_guesses(),
+            },
+            None => Reply::Error(ErrorCode::NoGame),
+        },
+        Command::Quit => Reply::Bye,
+    }
+}
+
+/// Play games with each client that connects to `listener`, until accepting a
+/// connection fails
+pub async fn serve(listener: TcpListener, settings: Settings) -> io::Result<()> {
+    let settings = Arc::new(settings);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let settings = Arc::clone(&settings);
+        tokio::spawn(async move {
+            // a client going away mid-game isn't worth reporting
+            let _ = handle(stream, &settings).await;
+        });
+    }
+}
+
+/// Play games with one client until they quit or disconnect
+async fn handle(mut stream: TcpStream, settings: &Settings) -> io::Result<()> {
+    let (reader, writer) = stream.split();
+    converse(reader, writer, settings).await
+}
+
+/// Answer the commands read from `reader` until the client quits or
+/// disconnects, or sends a line that's too long
+async fn converse(
+    reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    settings: &Settings,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut game = None;
+    let mut line = String::new();
+    send(&mut writer, &Reply::Hello).await?;
+    loop {
+        line.clear();
+        // leave room for a `\r\n` after the longest line allowed, so anything
+        // longer is caught
+        let read = (&mut reader)
+            .take(MAX_LINE as u64 + 2)
+            .read_line(&mut line)
+            .await?;
+        if read == 0 {
+            return Ok(());
+        }
+        let text = line.strip_suffix('\n').unwrap_or(&line);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        if text.len() > MAX_LINE {
+            return send(&mut writer, &Reply::Error(ErrorCode::LineTooLong)).await;
+        }
+
+        let reply = match text.parse() {
+            Ok(command) => respond(settings, &mut game, command),
+            Err(code) => Reply::Error(code),
+        };
+        send(&mut writer, &reply).await?;
+        if reply == Reply::Bye {
+            return Ok(());
+        }
+    }
+}
+
+/// Send a reply on its own line
+async fn send(writer: &mut (impl AsyncWrite + Unpin), reply: &Reply) -> io::Result<()> {
+    writer.write_all(format!("{}\n", reply).as_bytes()).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::LetterStatus::{Correct as G, InWord as Y, NotInWord as N};
+
+    const GUESSES: &[&str] = &["crane", "slate", "trace", "irate", "stare"];
+    const ANSWERS: &[&str] = &["crane"];
+
+    fn settings() -> Settings {
+        Settings::new(GUESSES, ANSWERS, 5, false).unwrap()
+    }
+
+    #[test]
+    fn test_settings() {
+        assert_eq!(
+            Err(BuildError::EmptyAnswers),
+            Settings::new(GUESSES, &[], 5, false).map(|_| ())
+        );
+        assert!(Settings::new(GUESSES, ANSWERS, 6, false).is_err());
+    }
+
+    #[test]
+    fn test_respond() {
+        let settings = settings();
+        let mut game = None;
+        let mut respond = |command| respond(&settings, &mut game, command);
+
+        assert_eq!(
+            Reply::Error(ErrorCode::NoGame),
+            respond(Command::Guess("crane".into()))
+        );
+        assert_eq!(Reply::Error(ErrorCode::NoGame), respond(Command::Board));
+        assert_eq!(Reply::Started { max_guesses: 6 }, respond(Command::New));
+        assert_eq!(
+            Reply::Guessed {
+                feedback: vec![N, N, G, N, G],
+                turn: 1,
+                max_guesses: 6,
+                outcome: None,
+            },
+            respond(Command::Guess("SLATE".into()))
+        );
+        assert_eq!(
+            Reply::Error(ErrorCode::NotInDictionary),
+            respond(Command::Guess("zzzzz".into()))
+        );
+        assert_eq!(
+            Reply::Error(ErrorCode::WrongLength),
+            respond(Command::Guess("cranes".into()))
+        );
+        assert_eq!(
+            Reply::Guessed {
+                feedback: vec![N, G, G, Y, G],
+                turn: 2,
+                max_guesses: 6,
+                outcome: None,
+            },
+            respond(Command::Guess("trace".into()))
+        );
+        assert_eq!(
+            Reply::Guessed {
+                feedback: vec![G; 5],
+                turn: 3,
+                max_guesses: 6,
+                outcome: Some(Outcome::Won),
+            },
+            respond(Command::Guess("crane".into()))
+        );
+        assert_eq!(
+            Reply::Error(ErrorCode::GameOver),
+            respond(Command::Guess("stare".into()))
+        );
+        assert_eq!(
+            Reply::Board {
+                rows: vec![
+                    ("slate".into(), vec![N, N, G, N, G]),
+                    ("trace".into(), vec![N, G, G, Y, G]),
+                    ("crane".into(), vec![G; 5]),
+                ],
+                max_guesses: 6,
+            },
+            respond(Command::Board)
+        );
+
+        // a new game starts over
+        assert_eq!(Reply::Started { max_guesses: 6 }, respond(Command::New));
+        assert_eq!(
+            Reply::Board {
+                rows: Vec::new(),
+                max_guesses: 6,
+            },
+            respond(Command::Board)
+        );
+        assert_eq!(Reply::Bye, respond(Command::Quit));
+    }
+
+    #[tokio::test]
+    async fn test_line_too_long() {
+        let input = format!("NEW\n{}\nBOARD\n", "x".repeat(MAX_LINE + 1));
+        let mut output = Vec::new();
+        converse(input.as_bytes(), &mut output, &settings())
+            .await
+            .unwrap();
+        assert_eq!(
+            "OK wordle-netd\nOK new 0/6\nERR line-too-long\n",
+            String::from_utf8(output).unwrap()
+        );
+
+        let input = format!("GUESS {}\r\nquit\r\nNEW\n", "x".repeat(MAX_LINE - 6));
+        let mut output = Vec::new();
+        converse(input.as_bytes(), &mut output, &settings())
+            .await
+            .unwrap();
+        assert_eq!(
+            "OK wordle-netd\nERR no-game\nOK bye\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
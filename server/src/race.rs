@@ -0,0 +1,726 @@
+//! Race rooms: players racing over WebSockets to find the same word first,
+//! with spectators watching
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Instant, SystemTime},
+};
+
+use axum::extract::ws::{Message, WebSocket};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+use wordle::{
+    codec,
+    protocol::{ClientMessage, Opponent, Scored, ServerMessage, Standing},
+    race::{PlayerStatus, Race, RaceError},
+    GuessError,
+};
+
+use crate::{
+    daily,
+    ratelimit::{self, Client},
+    state::Server,
+};
+
+/// Why a player couldn't join a room, or make a guess in one
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoomError {
+    /// The room already has as many players as it can
+    Full {
+        /// The most players a room can have
+        limit: usize,
+    },
+    /// The race in the room is over, so no one new can join
+    Finished,
+    /// The token doesn't belong to anyone in the room
+    UnknownToken,
+    /// The player's name is empty, too long, or has control characters in it
+    InvalidName,
+    /// The room doesn't exist any more
+    NotFound,
+    /// The player has already found the word or run out of guesses
+    PlayerFinished,
+    /// The guess itself was invalid
+    Guess(GuessError),
+    /// Spectators can only watch
+    Spectator,
+    /// The player has guessed more often than the rate limit allows
+    RateLimited {
+        /// The seconds until they can guess again
+        secs: u64,
+    },
+}
+
+impl fmt::Display for RoomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full { limit } => write!(f, "The room is full (the limit is {})", limit),
+            Self::Finished => write!(f, "The race in this room is over"),
+            Self::UnknownToken => write!(f, "No one in this room has that token"),
+            Self::InvalidName => write!(
+                f,
+                "Player names must be 1 to {} characters, with no control characters",
+                daily::MAX_NAME_LENGTH
+            ),
+            Self::NotFound => write!(f, "No such room"),
+            Self::PlayerFinished => write!(f, "You've already finished"),
+            Self::Guess(e) => write!(f, "{}", e),
+            Self::Spectator => write!(f, "Spectators can't send messages"),
+            Self::RateLimited { secs } => {
+                write!(f, "Too many requests; try again in {} seconds", secs)
+            }
+        }
+    }
+}
+
+impl Error for RoomError {}
+
+impl From<RaceError> for RoomError {
+    fn from(e: RaceError) -> Self {
+        match e {
+            // players only ever guess on their own boards, which exist
+            RaceError::UnknownPlayer(_) | RaceError::PlayerFinished(_) => Self::PlayerFinished,
+            RaceError::Guess(e) => Self::Guess(e),
+        }
+    }
+}
+
+/// The number of events each room keeps for anyone who hasn't caught up
+/// yet; anyone further behind is sent the state of the room instead
+const EVENT_BUFFER: usize = 64;
+
+/// A player's current connection
+#[derive(Debug)]
+struct Connection {
+    /// Tells this connection apart from the player's earlier ones
+    id: u64,
+    /// Cancelled when the player connects again elsewhere
+    replaced: CancellationToken,
+}
+
+/// Someone who has joined a room
+#[derive(Debug)]
+struct Player {
+    /// Their name
+    name: String,
+    /// The token to rejoin with
+    token: String,
+    /// Their connection, or `None` while they're disconnected
+    connection: Option<Connection>,
+}
+
+/// A race, and the players in it
+#[derive(Debug)]
+struct Room {
+    /// The race; there's a board for every place in the room, whether it's
+    /// been taken yet or not
+    race: Race<'static>,
+    /// The players who have joined, in order, numbered from 0
+    players: Vec<Player>,
+    /// How the race ended, once it's over
+    finished: Option<ServerMessage>,
+    /// When someone last joined, left, or guessed
+    last_used: SystemTime,
+    /// What happens in the room, for everyone in it and watching it; the
+    /// buffer is bounded, so no one falling behind holds anyone else up
+    events: broadcast::Sender<ServerMessage>,
+}
+
+impl Room {
+    /// Let everyone know about something that happened
+    fn publish(&self, event: ServerMessage) {
+        // no one may be listening, which is fine
+        let _ = self.events.send(event);
+    }
+
+    /// Everyone in the room, as the other players see them
+    fn opponents(&self) -> Vec<Opponent> {
+        self.players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| Opponent {
+                player: id,
+                name: player.name.clone(),
+                connected: player.connection.is_some(),
+                patterns: self
+                    .race
+                    .board(id)
+                    .map(|board| {
+                        board
+                            .history()
+                            .iter()
+                            .map(|(_, statuses)| codec::statuses_to_emoji(statuses))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// The messages that catch a player up with the room, or a spectator if
+    /// there's no player
+    fn catch_up(&self, player: Option<usize>) -> Vec<ServerMessage> {
+        let board = self
+            .race
+            .board(player.unwrap_or(0))
+            .expect("Every room has a board");
+        let state = match player {
+            Some(player) => ServerMessage::Welcome {
+                player,
+                token: self.players[player].token.clone(),
+                word_length: board.word_length(),
+                max_guesses: board.max_guesses(),
+                guesses: board
+                    .history()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (word, statuses))| Scored {
+                        word: word.to_string(),
+                        statuses: statuses.clone(),
+                        turn: i + 1,
+                    })
+                    .collect(),
+                players: self.opponents(),
+            },
+            None => ServerMessage::Spectating {
+                word_length: board.word_length(),
+                max_guesses: board.max_guesses(),
+                players: self.opponents(),
+            },
+        };
+        std::iter::once(state)
+            .chain(self.finished.clone())
+            .collect()
+    }
+
+    /// End the race, with `winner` having found the word first (if anyone did)
+    fn finish(&mut self, winner: Option<usize>) {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| Standing {
+                player: id,
+                name: player.name.clone(),
+                guesses: self.race.board(id).map_or(0, |board| board.history().len()),
+                status: self.race.status(id).unwrap_or(PlayerStatus::Playing),
+            })
+            .collect();
+        // the winner, then anyone else who found the word by how quickly, then the rest
+        standings.sort_by_key(|standing| {
+            let time = match standing.status {
+                PlayerStatus::Solved { time, .. } => Some(time),
+                _ => None,
+            };
+            (Some(standing.player) != winner, time.is_none(), time)
+        });
+        let finished = ServerMessage::Finished {
+            winner,
+            answer: self.race.snapshot().answer,
+            standings,
+        };
+        self.publish(finished.clone());
+        self.finished = Some(finished);
+    }
+
+    /// Whether any players are connected
+    fn is_empty(&self) -> bool {
+        self.players
+            .iter()
+            .all(|player| player.connection.is_none())
+    }
+}
+
+/// A player's place in a room, from joining until they're disconnected
+#[derive(Debug)]
+pub struct Seat {
+    /// The player's number in the room
+    pub player: usize,
+    /// Tells this connection apart from the player's others
+    connection: u64,
+    /// What happens in the room
+    pub events: broadcast::Receiver<ServerMessage>,
+    /// Cancelled when the player connects again elsewhere
+    pub replaced: CancellationToken,
+}
+
+impl Seat {
+    /// Whether the player should hear about an event; they already know
+    /// about their own guesses, and that they've joined
+    pub fn should_hear(&self, event: &ServerMessage) -> bool {
+        match event {
+            ServerMessage::Joined { player, .. } | ServerMessage::Progress { player, .. } => {
+                *player != self.player
+            }
+            _ => true,
+        }
+    }
+}
+
+/// The race rooms, by name.
+///
+/// Each room is locked separately, so a guess in one room never holds up
+/// another.
+#[derive(Debug)]
+pub struct Rooms {
+    /// The rooms; the map is only locked long enough to find a room
+    rooms: Mutex<HashMap<String, Arc<Mutex<Room>>>>,
+    /// The most players a room can have
+    max_players: usize,
+    /// The number of events each room keeps
+    buffer: usize,
+    /// The id of the next connection
+    next_connection: AtomicU64,
+}
+
+/// Lock a room; a panic while it was locked doesn't stop the race
+fn lock(room: &Mutex<Room>) -> MutexGuard<'_, Room> {
+    room.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Make up a token for a player to rejoin with, which can't be guessed
+fn random_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+impl Rooms {
+    /// Create rooms that hold up to `max_players` players each
+    pub fn new(max_players: usize) -> Self {
+        Self {
+            rooms: Mutex::default(),
+            max_players,
+            buffer: EVENT_BUFFER,
+            next_connection: AtomicU64::new(0),
+        }
+    }
+
+    /// Lock the map of rooms
+    fn rooms(&self) -> MutexGuard<'_, HashMap<String, Arc<Mutex<Room>>>> {
+        self.rooms.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Find a room
+    fn get(&self, room: &str) -> Result<Arc<Mutex<Room>>, RoomError> {
+        self.rooms().get(room).cloned().ok_or(RoomError::NotFound)
+    }
+
+    /// Join a room as `name`, or rejoin it with a token, creating it with a
+    /// race from `new_race` if it doesn't exist yet.
+    ///
+    /// Along with the player's seat, get back the messages that catch them
+    /// up. If the player was already connected, their old connection is
+    /// replaced.
+    pub fn join(
+        &self,
+        room: &str,
+        name: &str,
+        token: Option<&str>,
+        new_race: impl FnOnce(usize) -> Race<'static>,
+    ) -> Result<(Seat, Vec<ServerMessage>), RoomError> {
+        let room = Arc::clone(self.rooms().entry(room.to_owned()).or_insert_with(|| {
+            Arc::new(Mutex::new(Room {
+                race: new_race(self.max_players),
+                players: Vec::new(),
+                finished: None,
+                last_used: SystemTime::now(),
+                events: broadcast::channel(self.buffer).0,
+            }))
+        }));
+        let mut room = lock(&room);
+
+        let player = match token {
+            Some(token) => room
+                .players
+                .iter()
+                .position(|player| player.token == token)
+                .ok_or(RoomError::UnknownToken)?,
+            None => {
+                let name = daily::check_name(name).map_err(|_| RoomError::InvalidName)?;
+                if room.finished.is_some() {
+                    return Err(RoomError::Finished);
+                }
+                if room.players.len() >= room.race.players() {
+                    return Err(RoomError::Full {
+                        limit: room.race.players(),
+                    });
+                }
+                room.players.push(Player {
+                    name: name.to_owned(),
+                    token: random_token(),
+                    connection: None,
+                });
+                room.players.len() - 1
+            }
+        };
+
+        let connection = Connection {
+            id: self.next_connection.fetch_add(1, Ordering::Relaxed),
+            replaced: CancellationToken::new(),
+        };
+        let seat = Seat {
+            player,
+            connection: connection.id,
+            events: room.events.subscribe(),
+            replaced: connection.replaced.clone(),
+        };
+        if let Some(old) = room.players[player].connection.replace(connection) {
+            old.replaced.cancel();
+        }
+        room.last_used = SystemTime::now();
+        room.publish(ServerMessage::Joined {
+            player,
+            name: room.players[player].name.clone(),
+        });
+        Ok((seat, room.catch_up(Some(player))))
+    }
+
+    /// Watch a room, and get back what happens in it along with the messages
+    /// that catch the spectator up
+    pub fn spectate(
+        &self,
+        room: &str,
+    ) -> Result<(broadcast::Receiver<ServerMessage>, Vec<ServerMessage>), RoomError> {
+        let room = self.get(room)?;
+        let room = lock(&room);
+        Ok((room.events.subscribe(), room.catch_up(None)))
+    }
+
+    /// The messages that catch a player (or a spectator, if there's no
+    /// player) up with a room, after they've fallen behind
+    pub fn catch_up(
+        &self,
+        room: &str,
+        player: Option<usize>,
+    ) -> Result<Vec<ServerMessage>, RoomError> {
+        let room = self.get(room)?;
+        let room = lock(&room);
+        Ok(room.catch_up(player))
+    }
+
+    /// Make a guess for a player, and get back its feedback; everyone else
+    /// only hears the pattern
+    pub fn guess(&self, room: &str, seat: &Seat, word: &str) -> Result<Scored, RoomError> {
+        let room = self.get(room)?;
+        let mut room = lock(&room);
+        if room.finished.is_some() {
+            return Err(RoomError::Finished);
+        }
+
+        let player = seat.player;
+        let statuses = room.race.guess(player, word)?;
+        let turn = room
+            .race
+            .board(player)
+            .expect("The player just guessed")
+            .history()
+            .len();
+        room.last_used = SystemTime::now();
+        room.publish(ServerMessage::Progress {
+            player,
+            pattern: codec::statuses_to_emoji(&statuses),
+            turn,
+        });
+
+        let joined = room.players.len();
+        let finished = (0..joined)
+            .filter_map(|id| room.race.status(id))
+            .all(|status| status != PlayerStatus::Playing);
+        if let Some(PlayerStatus::Solved { .. }) = room.race.status(player) {
+            room.finish(Some(player));
+        } else if finished {
+            room.finish(None);
+        }
+        Ok(Scored {
+            word: word.to_owned(),
+            statuses,
+            turn,
+        })
+    }
+
+    /// Disconnect a player, unless they've already reconnected elsewhere.
+    ///
+    /// A finished race is forgotten once every player has left it.
+    pub fn leave(&self, name: &str, seat: Seat) {
+        let Ok(room) = self.get(name) else {
+            return;
+        };
+        let mut locked = lock(&room);
+        let player = &mut locked.players[seat.player];
+        if player.connection.as_ref().map(|c| c.id) != Some(seat.connection) {
+            return;
+        }
+        player.connection = None;
+        locked.last_used = SystemTime::now();
+        locked.publish(ServerMessage::Left {
+            player: seat.player,
+        });
+
+        if locked.is_empty() && locked.finished.is_some() {
+            drop(locked);
+            let mut rooms = self.rooms();
+            // unless someone has rejoined in the meantime
+            if rooms
+                .get(name)
+                .is_some_and(|current| Arc::ptr_eq(current, &room) && lock(current).is_empty())
+            {
+                rooms.remove(name);
+            }
+        }
+    }
+
+    /// Remove the rooms no players are connected to that were last used
+    /// before `cutoff`, and get back how many there were
+    pub fn expire(&self, cutoff: SystemTime) -> usize {
+        let mut rooms = self.rooms();
+        let before = rooms.len();
+        rooms.retain(|_, room| {
+            let room = lock(room);
+            !room.is_empty() || room.last_used >= cutoff
+        });
+        before - rooms.len()
+    }
+}
+
+/// The route race sockets are served on, for the rate limiting metrics
+const ROUTE: &str = "/ws/race/{room}";
+
+/// Play in a race over a WebSocket, from joining until the player
+/// disconnects; each guess `client` makes counts towards their rate limit
+pub async fn play(server: Server, room: String, client: Client, mut socket: WebSocket) {
+    let Some(mut seat) = join(&server, &room, &mut socket).await else {
+        return;
+    };
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let reply = match serde_json::from_str(&text) {
+                    Ok(ClientMessage::Guess { word }) => limit_rate(&server, &client)
+                        .and_then(|()| {
+                            server
+                                .rooms()
+                                .guess(&room, &seat, &word.trim().to_lowercase())
+                        })
+                        .map_or_else(|e| error(e.to_string()), ServerMessage::Scored),
+                    Ok(ClientMessage::Join { .. }) => error("You've already joined".to_owned()),
+                    Err(e) => error(e.to_string()),
+                };
+                if send(&mut socket, &reply).await.is_err() {
+                    break;
+                }
+            }
+            event = seat.events.recv() => {
+                let messages = match event {
+                    Ok(event) if seat.should_hear(&event) => vec![event],
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => match server.rooms().catch_up(&room, Some(seat.player)) {
+                        Ok(messages) => messages,
+                        Err(_) => break,
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+                if send_all(&mut socket, &messages).await.is_err() {
+                    break;
+                }
+            }
+            () = seat.replaced.cancelled() => break,
+        }
+    }
+    server.rooms().leave(&room, seat);
+}
+
+/// Watch a race over a WebSocket, until the spectator disconnects or the
+/// room is forgotten
+pub async fn spectate(server: Server, room: String, mut socket: WebSocket) {
+    let (mut events, messages) = match server.rooms().spectate(&room) {
+        Ok(spectating) => spectating,
+        Err(e) => return refuse(&mut socket, e.to_string()).await,
+    };
+    if send_all(&mut socket, &messages).await.is_err() {
+        return;
+    }
+    loop {
+        tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(_) | Message::Binary(_))) => {
+                    let reply = error(RoomError::Spectator.to_string());
+                    if send(&mut socket, &reply).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            event = events.recv() => {
+                let messages = match event {
+                    Ok(event) => vec![event],
+                    Err(RecvError::Lagged(_)) => match server.rooms().catch_up(&room, None) {
+                        Ok(messages) => messages,
+                        Err(_) => break,
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+                if send_all(&mut socket, &messages).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Wait for a player to join the room, telling them why if they can't
+async fn join(server: &Server, room: &str, socket: &mut WebSocket) -> Option<Seat> {
+    let error = loop {
+        let text = match socket.recv().await? {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return None,
+            Ok(_) => continue,
+        };
+        match serde_json::from_str(&text) {
+            Ok(ClientMessage::Join { name, token }) => {
+                match server.join_race(room, &name, token.as_deref()) {
+                    Ok((seat, messages)) => {
+                        return send_all(socket, &messages).await.ok().map(|()| seat);
+                    }
+                    Err(e) => break e.to_string(),
+                }
+            }
+            Ok(ClientMessage::Guess { .. }) => break "Join the room first".to_owned(),
+            Err(e) => break e.to_string(),
+        }
+    };
+    refuse(socket, error).await;
+    None
+}
+
+/// Take a guess from `client`'s rate limit, or turn it away if they've
+/// run out
+fn limit_rate(server: &Server, client: &Client) -> Result<(), RoomError> {
+    server
+        .rate_limiter()
+        .check(client, Instant::now())
+        .map_err(|wait| {
+            server.metrics().rate_limited(ROUTE);
+            RoomError::RateLimited {
+                secs: ratelimit::retry_after(wait),
+            }
+        })
+}
+
+/// An error message
+fn error(message: String) -> ServerMessage {
+    ServerMessage::Error { message }
+}
+
+/// Tell the client why they can't be let in, and close the connection
+async fn refuse(socket: &mut WebSocket, message: String) {
+    let _ = send(socket, &error(message)).await;
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+/// Send a message over a WebSocket, as JSON
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).expect("Messages can always be serialized");
+    socket.send(Message::Text(text.into())).await
+}
+
+/// Send messages over a WebSocket, in order
+async fn send_all(socket: &mut WebSocket, messages: &[ServerMessage]) -> Result<(), axum::Error> {
+    for message in messages {
+        send(socket, message).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::Wordle;
+
+    const WORDS: [&str; 3] = ["crane", "slate", "trace"];
+
+    fn new_race(players: usize) -> Race<'static> {
+        let game = Wordle::new(&WORDS, &WORDS).set_answer("crane").unwrap();
+        Race::new(&game, players)
+    }
+
+    #[test]
+    fn test_slow_spectator() {
+        let mut rooms = Rooms::new(4);
+        rooms.buffer = 2;
+        let (alice, _) = rooms.join("room", "alice", None, new_race).unwrap();
+        let (mut watching, messages) = rooms.spectate("room").unwrap();
+        assert!(matches!(messages[..], [ServerMessage::Spectating { .. }]));
+
+        // the spectator isn't reading, but guessing carries on regardless
+        for word in ["slate", "trace", "slate", "trace"] {
+            rooms.guess("room", &alice, word).unwrap();
+        }
+        assert_eq!(
+            Err(broadcast::error::TryRecvError::Lagged(2)),
+            watching.try_recv()
+        );
+
+        // and catching up shows every guess, without giving any letters away
+        let messages = rooms.catch_up("room", None).unwrap();
+        let [ServerMessage::Spectating { players, .. }] = &messages[..] else {
+            panic!("unexpected messages {:?}", messages);
+        };
+        assert_eq!(4, players[0].patterns.len());
+        assert!(!format!("{:?}", messages).contains("slate"));
+    }
+
+    #[test]
+    fn test_spectate_missing_room() {
+        let rooms = Rooms::new(4);
+        assert_eq!(RoomError::NotFound, rooms.spectate("nowhere").unwrap_err());
+    }
+
+    #[test]
+    fn test_rejoin_replaces_connection() {
+        let rooms = Rooms::new(2);
+        let (first, _) = rooms.join("room", "alice", None, new_race).unwrap();
+        let token = match &rooms.catch_up("room", Some(0)).unwrap()[0] {
+            ServerMessage::Welcome { token, .. } => token.clone(),
+            message => panic!("unexpected message {:?}", message),
+        };
+        let (second, messages) = rooms.join("room", "", Some(&token), new_race).unwrap();
+        assert!(matches!(
+            messages[..],
+            [ServerMessage::Welcome { player: 0, .. }]
+        ));
+        assert!(first.replaced.is_cancelled());
+
+        // the old connection going away doesn't disconnect the new one
+        rooms.leave("room", first);
+        let room = rooms.get("room").unwrap();
+        assert!(!lock(&room).is_empty());
+        rooms.leave("room", second);
+        assert!(lock(&room).is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+//! Limiting how often each client can start games and make guesses
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    net::IpAddr,
+    str::FromStr,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// How many requests a client can make in a window of time, e.g. `30/min`.
+///
+/// A client can make that many requests at once, and then gets them back
+/// steadily over the window, like tokens refilling a bucket.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RateLimit {
+    /// The most requests in a window, and the most that can be made at once
+    pub requests: u32,
+    /// How long it takes to get all of them back
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// How long it takes to get one request back
+    fn interval(&self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+/// Why a [`RateLimit`] couldn't be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseRateLimitError {
+    /// There's no `/` between the requests and the window
+    MissingSlash,
+    /// The number of requests isn't a whole number above zero
+    InvalidRequests(String),
+    /// The window isn't `s`, `min` or `h`, or a number of one of them
+    InvalidWindow(String),
+}
+
+impl fmt::Display for ParseRateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSlash => write!(f, "expected requests per window, e.g. '30/min'"),
+            Self::InvalidRequests(requests) => {
+                write!(f, "'{}' isn't a number of requests above zero", requests)
+            }
+            Self::InvalidWindow(window) => write!(
+                f,
+                "'{}' isn't a window of time; use s, min or h, e.g. '30/min' or '100/10min'",
+                window
+            ),
+        }
+    }
+}
+
+impl Error for ParseRateLimitError {}
+
+impl FromStr for RateLimit {
+    type Err = ParseRateLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (requests, window) = s.split_once('/').ok_or(ParseRateLimitError::MissingSlash)?;
+        let requests = requests
+            .trim()
+            .parse()
+            .ok()
+            .filter(|&requests| requests > 0)
+            .ok_or_else(|| ParseRateLimitError::InvalidRequests(requests.to_owned()))?;
+
+        let invalid = || ParseRateLimitError::InvalidWindow(window.to_owned());
+        let window = window.trim();
+        let digits = window.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+        let (count, unit) = window.split_at(digits);
+        let count: u64 = match count {
+            "" => 1,
+            count => count.parse().map_err(|_| invalid())?,
+        };
+        let unit = match unit {
+            "s" | "sec" | "second" => 1,
+            "m" | "min" | "minute" => 60,
+            "h" | "hour" => 60 * 60,
+            _ => return Err(invalid()),
+        };
+        let per = count
+            .checked_mul(unit)
+            .filter(|&secs| secs > 0)
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            requests,
+            per: Duration::from_secs(per),
+        })
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}s", self.requests, self.per.as_secs())
+    }
+}
+
+/// The whole seconds to tell a client to wait for `wait`, rounded up so a
+/// client that waits as long as it's told is let through
+pub fn retry_after(wait: Duration) -> u64 {
+    wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+}
+
+/// Who a request is from, to give each client a bucket of its own
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Client {
+    /// A client known only by its IP address
+    Ip(IpAddr),
+    /// A client that's shown an API key, wherever it connects from
+    Key(String),
+}
+
+/// Keeps a bucket for each client that's made a request recently, to hold
+/// them to a [`RateLimit`]
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// The limit, or `None` for no limit
+    limit: Option<RateLimit>,
+    /// When each client's bucket will be full again, were it to make no more
+    /// requests; every request pushes this back by a share of the window
+    buckets: Mutex<HashMap<Client, Instant>>,
+}
+
+impl RateLimiter {
+    /// Hold each client to `limit`, or let every request through if there isn't one
+    pub fn new(limit: Option<RateLimit>) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// The limit, if there is one
+    pub fn limit(&self) -> Option<RateLimit> {
+        self.limit
+    }
+
+    /// Lock the buckets; a panic while they were locked doesn't lose them
+    fn buckets(&self) -> MutexGuard<'_, HashMap<Client, Instant>> {
+        self.buckets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Take a request from `client`'s bucket at `now`, or if it's empty, get
+    /// back how long until there's one to take
+    pub fn check(&self, client: &Client, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let interval = limit.interval();
+        let mut buckets = self.buckets();
+        let full = buckets.entry(client.clone()).or_insert(now);
+        let full_at = (*full).max(now);
+        // the bucket has a request in it as long as it's at most the rest of
+        // the window from being full
+        let wait = full_at - now;
+        let allowed = limit.per.saturating_sub(interval);
+        if wait > allowed {
+            return Err(wait - allowed);
+        }
+        *full = full_at + interval;
+        Ok(())
+    }
+
+    /// Forget the clients whose buckets would be full again by `now`, since
+    /// they're no different from clients that have never made a request, and
+    /// get back how many there were
+    pub fn evict_idle(&self, now: Instant) -> usize {
+        let mut buckets = self.buckets();
+        let before = buckets.len();
+        buckets.retain(|_, full| *full > now);
+        before - buckets.len()
+    }
+
+    /// The number of clients with a bucket
+    pub fn clients(&self) -> usize {
+        self.buckets().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_parse() {
+        let limit = |requests, secs| {
+            Ok(RateLimit {
+                requests,
+                per: Duration::from_secs(secs),
+            })
+        };
+        assert_eq!(limit(30, 60), "30/min".parse());
+        assert_eq!(limit(5, 1), "5/s".parse());
+        assert_eq!(limit(1000, 3600), "1000/hour".parse());
+        assert_eq!(limit(100, 600), "100/10min".parse());
+        assert_eq!(limit(2, 30), " 2 / 30s ".parse());
+
+        assert_eq!(
+            Err(ParseRateLimitError::MissingSlash),
+            "30".parse::<RateLimit>()
+        );
+        assert_eq!(
+            Err(ParseRateLimitError::InvalidRequests("0".to_owned())),
+            "0/min".parse::<RateLimit>()
+        );
+        assert_eq!(
+            Err(ParseRateLimitError::InvalidWindow("fortnight".to_owned())),
+            "30/fortnight".parse::<RateLimit>()
+        );
+        assert!("30/0min".parse::<RateLimit>().is_err());
+        assert_eq!("30/60s", "30/min".parse::<RateLimit>().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_check() {
+        let limiter = RateLimiter::new(Some("3/min".parse().unwrap()));
+        let client = Client::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let other = Client::Key("key".to_owned());
+        let start = Instant::now();
+
+        // a client can use up the whole bucket at once
+        for _ in 0..3 {
+            assert_eq!(Ok(()), limiter.check(&client, start));
+        }
+        assert_eq!(Err(Duration::from_secs(20)), limiter.check(&client, start));
+        // but doesn't use up anyone else's
+        assert_eq!(Ok(()), limiter.check(&other, start));
+
+        // and gets a request back every 20 seconds
+        let later = start + Duration::from_secs(15);
+        assert_eq!(Err(Duration::from_secs(5)), limiter.check(&client, later));
+        let later = start + Duration::from_secs(20);
+        assert_eq!(Ok(()), limiter.check(&client, later));
+        assert!(limiter.check(&client, later).is_err());
+
+        // with no limit, anything goes
+        let limiter = RateLimiter::new(None);
+        for _ in 0..100 {
+            assert_eq!(Ok(()), limiter.check(&client, start));
+        }
+        assert_eq!(0, limiter.clients());
+    }
+
+    #[test]
+    fn test_evict_idle() {
+        let limiter = RateLimiter::new(Some("3/min".parse().unwrap()));
+        let start = Instant::now();
+        let client = Client::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let other = Client::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        limiter.check(&client, start).unwrap();
+        for _ in 0..3 {
+            limiter.check(&other, start).unwrap();
+        }
+        assert_eq!(2, limiter.clients());
+
+        // one request comes back in 20 seconds, and all three in a minute
+        assert_eq!(0, limiter.evict_idle(start + Duration::from_secs(19)));
+        assert_eq!(1, limiter.evict_idle(start + Duration::from_secs(20)));
+        assert_eq!(1, limiter.clients());
+        assert_eq!(1, limiter.evict_idle(start + Duration::from_secs(60)));
+        assert_eq!(0, limiter.clients());
+    }
+}
@@ -0,0 +1,717 @@
+//! A game store backed by a SQLite database, so games survive a restart
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    net::IpAddr,
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use wordle::{transcript::Transcript, GameStatus, Wordle};
+
+use crate::{
+    auth::ApiKey,
+    daily::DailyResult,
+    metrics::Mode,
+    store::{GameStore, MemoryStore, Session, StoreError},
+};
+
+/// The schema, one migration per version; new migrations go on the end, and
+/// old ones are never changed
+const MIGRATIONS: [&str; 7] = [
+    r#"
+    CREATE TABLE games (
+        id TEXT PRIMARY KEY,
+        client TEXT,
+        last_used INTEGER NOT NULL,
+        transcript TEXT NOT NULL
+    );
+    CREATE TABLE stats (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        finished INTEGER NOT NULL,
+        answer TEXT NOT NULL,
+        hard_mode INTEGER NOT NULL,
+        guesses INTEGER,
+        seconds REAL NOT NULL
+    );
+"#,
+    r#"
+    CREATE TABLE daily_results (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        date TEXT NOT NULL,
+        puzzle INTEGER NOT NULL,
+        player TEXT NOT NULL,
+        guesses INTEGER NOT NULL,
+        seconds REAL NOT NULL,
+        hard_mode INTEGER NOT NULL,
+        UNIQUE (date, player)
+    );
+"#,
+    r#"
+    CREATE TABLE api_keys (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        hash TEXT NOT NULL,
+        created INTEGER NOT NULL,
+        revoked INTEGER
+    );
+"#,
+    r#"
+    CREATE TABLE daily_answers (
+        date TEXT PRIMARY KEY,
+        answer_index INTEGER NOT NULL
+    );
+"#,
+    r#"
+    ALTER TABLE games ADD COLUMN paused INTEGER NOT NULL DEFAULT 0;
+"#,
+    r#"
+    ALTER TABLE games ADD COLUMN daily INTEGER NOT NULL DEFAULT 0;
+"#,
+    // results from before this was kept were all sent in before any after it
+    r#"
+    ALTER TABLE daily_results ADD COLUMN submitted_ms INTEGER NOT NULL DEFAULT 0;
+"#,
+];
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Backend(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Backend(e.to_string())
+    }
+}
+
+/// How a finished game went, as kept in the `stats` table
+#[derive(Debug, PartialEq, Clone)]
+pub struct Summary {
+    /// When the game finished
+    pub finished: SystemTime,
+    /// The answer
+    pub answer: String,
+    /// Whether the game was played in hard mode
+    pub hard_mode: bool,
+    /// The number of guesses it took to win, or `None` if the game was lost
+    pub guesses: Option<usize>,
+    /// The time spent on the game
+    pub elapsed: Duration,
+}
+
+/// A [`GameStore`] that keeps games in a SQLite database.
+///
+/// Games are also kept in memory, so looking at a game doesn't need the
+/// database; but every change is written to the database before it's kept.
+/// When a game finishes, a summary of it is added to the `stats` table, and
+/// the game itself is removed once it expires like any other. The daily
+/// puzzles, leaderboard and API keys are only kept in the database, so keys
+/// issued or revoked by another process count straight away.
+#[derive(Debug)]
+pub struct SqliteStore {
+    /// The games, as they are in the database
+    games: MemoryStore,
+    /// The database
+    db: Mutex<Connection>,
+    /// Acceptable guesses, to pick games back up with
+    guesses: &'static [&'static str],
+    /// Answer list, to pick games back up with
+    answers: &'static [&'static str],
+    /// The number of letters in each word
+    word_length: usize,
+}
+
+/// Seconds since the Unix epoch, as kept in the database
+fn to_secs(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64)
+}
+
+/// A time kept in the database
+fn from_secs(secs: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+/// Milliseconds since the Unix epoch, for times that have to be told apart
+/// more finely than [`to_secs`]
+fn to_millis(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis().try_into().unwrap_or(i64::MAX))
+}
+
+/// A time kept in the database in milliseconds
+fn from_millis(millis: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(millis.max(0) as u64)
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`, bring its schema up to date,
+    /// and pick up the games in it
+    pub fn open(
+        path: &Path,
+        guesses: &'static [&'static str],
+        answers: &'static [&'static str],
+        word_length: usize,
+    ) -> Result<Self, StoreError> {
+        let mut db = Connection::open(path)?;
+        migrate(&mut db)?;
+
+        let store = Self {
+            games: MemoryStore::new(),
+            db: Mutex::new(db),
+            guesses,
+            answers,
+            word_length,
+        };
+        store.load()?;
+        Ok(store)
+    }
+
+    /// Pick up the games in the database
+    fn load(&self) -> Result<(), StoreError> {
+        let db = self.db();
+        let mut rows =
+            db.prepare("SELECT id, client, last_used, transcript, paused, daily FROM games")?;
+        let rows = rows.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, bool>(4)?,
+                row.get::<_, bool>(5)?,
+            ))
+        })?;
+        for row in rows {
+            let (id, client, last_used, transcript, paused, daily) = row?;
+            let transcript: Transcript = serde_json::from_str(&transcript)?;
+            let session = Session {
+                game: self.resume(&transcript, paused)?,
+                client: client.and_then(|client| client.parse().ok()),
+                last_used: from_secs(last_used),
+                mode: match daily {
+                    true => Mode::Daily,
+                    false => Mode::Endless,
+                },
+            };
+            self.games.insert(&id, session)?;
+        }
+        Ok(())
+    }
+
+    /// Lock the database
+    fn db(&self) -> MutexGuard<'_, Connection> {
+        self.db.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Pick up a game from its transcript, with its clock paused if it was
+    fn resume(&self, transcript: &Transcript, paused: bool) -> Result<Wordle<'static>, StoreError> {
+        let mut game = Wordle::builder()
+            .guesses(self.guesses)
+            .answers(self.answers)
+            .word_length(self.word_length)
+            .build()
+            .map_err(|e| StoreError::Backend(e.to_string()))?
+            .resume(transcript)
+            .map_err(|e| StoreError::Backend(format!("could not resume a game: {}", e)))?;
+        if paused {
+            game.pause();
+        }
+        Ok(game)
+    }
+
+    /// The summaries of the finished games, oldest first
+    pub fn summaries(&self) -> Result<Vec<Summary>, StoreError> {
+        let db = self.db();
+        let mut rows = db.prepare(
+            "SELECT finished, answer, hard_mode, guesses, seconds FROM stats ORDER BY id",
+        )?;
+        let summaries = rows
+            .query_map([], |row| {
+                Ok(Summary {
+                    finished: from_secs(row.get(0)?),
+                    answer: row.get(1)?,
+                    hard_mode: row.get(2)?,
+                    guesses: row.get(3)?,
+                    elapsed: Duration::from_secs_f64(row.get::<_, f64>(4)?.max(0.0)),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(summaries)
+    }
+}
+
+/// Bring the schema up to date, in one transaction
+fn migrate(db: &mut Connection) -> Result<(), StoreError> {
+    let tx = db.transaction()?;
+    let version: usize = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(StoreError::Backend(format!(
+            "the database is from a newer version of the server (schema version {})",
+            version
+        )));
+    }
+    for migration in &MIGRATIONS[version..] {
+        tx.execute_batch(migration)?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Write a game to the database, summarizing it if it just finished
+fn save(
+    tx: &Transaction<'_>,
+    id: &str,
+    session: &Session,
+    was_in_progress: bool,
+) -> Result<(), StoreError> {
+    let transcript = session.game.transcript();
+    tx.execute(
+        "INSERT INTO games (id, client, last_used, transcript, paused, daily)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (id) DO UPDATE SET last_used = ?3, transcript = ?4, paused = ?5",
+        params![
+            id,
+            session.client.map(|client| client.to_string()),
+            to_secs(session.last_used),
+            serde_json::to_string(&transcript)?,
+            session.game.is_paused(),
+            session.mode == Mode::Daily,
+        ],
+    )?;
+
+    if was_in_progress && transcript.status != GameStatus::InProgress {
+        let guesses = match transcript.status {
+            GameStatus::Won { guesses } => Some(guesses),
+            _ => None,
+        };
+        tx.execute(
+            "INSERT INTO stats (finished, answer, hard_mode, guesses, seconds)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                to_secs(SystemTime::now()),
+                transcript.answer,
+                transcript.hard_mode,
+                guesses,
+                transcript.elapsed.as_secs_f64(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+impl GameStore for SqliteStore {
+    fn insert(&self, id: &str, session: Session) -> Result<(), StoreError> {
+        let mut db = self.db();
+        let tx = db.transaction()?;
+        let exists = tx
+            .query_row("SELECT 1 FROM games WHERE id = ?1", [id], |_| Ok(()))
+            .optional()?
+            .is_some();
+        if exists {
+            return Err(StoreError::Exists);
+        }
+        save(&tx, id, &session, false)?;
+        tx.commit()?;
+        self.games.insert(id, session)
+    }
+
+    fn update(&self, id: &str, f: &mut dyn FnMut(&mut Session)) -> Result<(), StoreError> {
+        let mut result = Ok(());
+        self.games.update(id, &mut |session| {
+            let before = session.game.transcript();
+            let paused = session.game.is_paused();
+            let last_used = session.last_used;
+            f(session);
+
+            let saved = {
+                let mut db = self.db();
+                db.transaction().map_err(StoreError::from).and_then(|tx| {
+                    save(&tx, id, session, before.status == GameStatus::InProgress)?;
+                    Ok(tx.commit()?)
+                })
+            };
+            // if the change couldn't be saved, it didn't happen
+            if let Err(e) = saved {
+                if let Ok(game) = self.resume(&before, paused) {
+                    session.game = game;
+                    session.last_used = last_used;
+                }
+                result = Err(e);
+            }
+        })?;
+        result
+    }
+
+    fn remove(&self, id: &str) -> Result<(), StoreError> {
+        if self.db().execute("DELETE FROM games WHERE id = ?1", [id])? == 0 {
+            return Err(StoreError::NotFound);
+        }
+        self.games.remove(id)
+    }
+
+    fn expire(&self, cutoff: SystemTime, paused_cutoff: SystemTime) -> Result<usize, StoreError> {
+        // the games are unlocked again before the database is locked, since
+        // `update` locks a game and then the database
+        let expired = self.games.expire_ids(cutoff, paused_cutoff);
+        let mut db = self.db();
+        let tx = db.transaction()?;
+        for id in &expired {
+            tx.execute("DELETE FROM games WHERE id = ?1", [id])?;
+        }
+        tx.commit()?;
+        Ok(expired.len())
+    }
+
+    fn active_games(&self, client: IpAddr) -> Result<usize, StoreError> {
+        self.games.active_games(client)
+    }
+
+    fn in_progress(&self) -> Result<usize, StoreError> {
+        self.games.in_progress()
+    }
+
+    fn add_result(&self, result: DailyResult) -> Result<(), StoreError> {
+        let mut db = self.db();
+        let tx = db.transaction()?;
+        let exists = tx
+            .query_row(
+                "SELECT 1 FROM daily_results WHERE date = ?1 AND player = ?2",
+                params![result.date, result.player],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if exists {
+            return Err(StoreError::DuplicateResult);
+        }
+        tx.execute(
+            "INSERT INTO daily_results
+                 (date, puzzle, player, guesses, seconds, hard_mode, submitted_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                result.date,
+                result.puzzle,
+                result.player,
+                result.guesses,
+                result.elapsed.as_secs_f64(),
+                result.hard_mode,
+                to_millis(result.submitted),
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn results(&self, date: NaiveDate) -> Result<Vec<DailyResult>, StoreError> {
+        let db = self.db();
+        let mut rows = db.prepare(
+            "SELECT puzzle, player, guesses, seconds, hard_mode, submitted_ms
+             FROM daily_results WHERE date = ?1 ORDER BY id",
+        )?;
+        let results = rows
+            .query_map([date], |row| {
+                Ok(DailyResult {
+                    date,
+                    puzzle: row.get(0)?,
+                    player: row.get(1)?,
+                    guesses: row.get(2)?,
+                    elapsed: Duration::from_secs_f64(row.get::<_, f64>(3)?.max(0.0)),
+                    hard_mode: row.get(4)?,
+                    submitted: from_millis(row.get(5)?),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(results)
+    }
+
+    fn pin_daily_index(&self, date: NaiveDate, index: usize) -> Result<usize, StoreError> {
+        let mut db = self.db();
+        let tx = db.transaction()?;
+        tx.execute(
+            "INSERT OR IGNORE INTO daily_answers (date, answer_index) VALUES (?1, ?2)",
+            params![date, index],
+        )?;
+        let index = tx.query_row(
+            "SELECT answer_index FROM daily_answers WHERE date = ?1",
+            [date],
+            |row| row.get(0),
+        )?;
+        tx.commit()?;
+        Ok(index)
+    }
+
+    fn add_key(&self, key: ApiKey) -> Result<(), StoreError> {
+        let mut db = self.db();
+        let tx = db.transaction()?;
+        let exists = tx
+            .query_row(
+                "SELECT 1 FROM api_keys WHERE name = ?1",
+                [&key.name],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if exists {
+            return Err(StoreError::DuplicateKey);
+        }
+        tx.execute(
+            "INSERT INTO api_keys (name, hash, created, revoked) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                key.name,
+                key.hash,
+                to_secs(key.created),
+                key.revoked.map(to_secs),
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn revoke_key(&self, name: &str, when: SystemTime) -> Result<(), StoreError> {
+        let changed = self.db().execute(
+            "UPDATE api_keys SET revoked = COALESCE(revoked, ?2) WHERE name = ?1",
+            params![name, to_secs(when)],
+        )?;
+        if changed == 0 {
+            return Err(StoreError::UnknownKey);
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<ApiKey>, StoreError> {
+        let db = self.db();
+        let mut rows =
+            db.prepare("SELECT name, hash, created, revoked FROM api_keys ORDER BY id")?;
+        let keys = rows
+            .query_map([], |row| {
+                Ok(ApiKey {
+                    name: row.get(0)?,
+                    hash: row.get(1)?,
+                    created: from_secs(row.get(2)?),
+                    revoked: row.get::<_, Option<i64>>(3)?.map(from_secs),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(keys)
+    }
+
+    fn ping(&self) -> Result<(), StoreError> {
+        self.db().query_row("SELECT 1", [], |_| Ok(()))?;
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), StoreError> {
+        // every change is committed as it's made, so there are only pages
+        // held in the connection's cache left to write
+        Ok(self.db().cache_flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 3] = ["crane", "slate", "irate"];
+
+    /// A path for a test's database, removing any left over from before
+    fn db_path(test: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("wordle-server-{}-{}.db", test, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn session(answer: &str) -> Session {
+        let game = Wordle::new(&WORDS, &WORDS).set_answer(answer).unwrap();
+        Session {
+            game,
+            client: Some("127.0.0.1".parse().unwrap()),
+            last_used: SystemTime::now(),
+            mode: Mode::Endless,
+        }
+    }
+
+    #[test]
+    fn test_reopen() {
+        let path = db_path("reopen");
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        store.insert("a", session("crane")).unwrap();
+        assert_eq!(Err(StoreError::Exists), store.insert("a", session("slate")));
+        let daily = Session {
+            mode: Mode::Daily,
+            ..session("slate")
+        };
+        store.insert("b", daily).unwrap();
+        store
+            .update("a", &mut |session| {
+                session.game.guess("slate").unwrap();
+            })
+            .unwrap();
+        drop(store);
+
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        let mut history = Vec::new();
+        store
+            .update("a", &mut |session| {
+                history = session.game.history().to_vec();
+                assert_eq!(Some("crane".into()), session.game.transcript().answer);
+                assert_eq!(Mode::Endless, session.mode);
+            })
+            .unwrap();
+        assert_eq!(1, history.len());
+        assert_eq!("slate", history[0].0);
+        store
+            .update("b", &mut |session| assert_eq!(Mode::Daily, session.mode))
+            .unwrap();
+        assert_eq!(Ok(2), store.active_games("127.0.0.1".parse().unwrap()));
+
+        store.remove("a").unwrap();
+        drop(store);
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        assert_eq!(Err(StoreError::NotFound), store.update("a", &mut |_| {}));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_summaries() {
+        let path = db_path("summaries");
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        store.insert("won", session("crane")).unwrap();
+        store.insert("lost", session("crane")).unwrap();
+        store
+            .update("won", &mut |session| {
+                session.game.guess("slate").unwrap();
+                session.game.guess("crane").unwrap();
+            })
+            .unwrap();
+        store
+            .update("lost", &mut |session| session.game.give_up())
+            .unwrap();
+        // looking at a finished game doesn't summarize it again
+        store.update("won", &mut |_| {}).unwrap();
+
+        let summaries = store.summaries().unwrap();
+        assert_eq!(2, summaries.len());
+        assert_eq!(Some(2), summaries[0].guesses);
+        assert_eq!("crane", summaries[0].answer);
+        assert_eq!(None, summaries[1].guesses);
+
+        // expired games are gone, but their summaries stay
+        let later = SystemTime::now() + Duration::from_secs(60);
+        assert_eq!(Ok(2), store.expire(later, later));
+        drop(store);
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        assert_eq!(Err(StoreError::NotFound), store.update("won", &mut |_| {}));
+        assert_eq!(2, store.summaries().unwrap().len());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_results() {
+        let path = db_path("results");
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        let day = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let result = |player: &str| DailyResult {
+            date: day,
+            puzzle: 196,
+            player: player.to_owned(),
+            guesses: 4,
+            elapsed: Duration::from_millis(61_500),
+            hard_mode: true,
+            submitted: SystemTime::UNIX_EPOCH + Duration::from_millis(1_641_000_000_250),
+        };
+        store.add_result(result("bob")).unwrap();
+        store.add_result(result("alice")).unwrap();
+        assert_eq!(
+            Err(StoreError::DuplicateResult),
+            store.add_result(result("bob"))
+        );
+        drop(store);
+
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        assert_eq!(Ok(vec![result("bob"), result("alice")]), store.results(day));
+        assert_eq!(Ok(Vec::new()), store.results(day.succ_opt().unwrap()));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_pin_daily_index() {
+        let path = db_path("daily-index");
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        let day = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        assert_eq!(Ok(2), store.pin_daily_index(day, 2));
+        drop(store);
+
+        // a day's answer stays the same across restarts
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        assert_eq!(Ok(2), store.pin_daily_index(day, 0));
+        assert_eq!(Ok(0), store.pin_daily_index(day.succ_opt().unwrap(), 0));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keys() {
+        let path = db_path("keys");
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        store.add_key(ApiKey::issue("alice").1).unwrap();
+        store.add_key(ApiKey::issue("bob").1).unwrap();
+        assert_eq!(
+            Err(StoreError::DuplicateKey),
+            store.add_key(ApiKey::issue("bob").1)
+        );
+        let revoked = from_secs(to_secs(SystemTime::now()));
+        store.revoke_key("alice", revoked).unwrap();
+        store
+            .revoke_key("alice", revoked + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            Err(StoreError::UnknownKey),
+            store.revoke_key("carol", revoked)
+        );
+        let keys = store.keys().unwrap();
+        drop(store);
+
+        let store = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap();
+        assert_eq!(Ok(keys.clone()), store.keys());
+        assert_eq!("alice", keys[0].name);
+        assert_eq!(Some(revoked), keys[0].revoked);
+        assert_eq!(None, keys[1].revoked);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_migrations() {
+        let path = db_path("migrations");
+        drop(SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap());
+        // migrating an up-to-date database changes nothing
+        drop(SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap());
+
+        let db = Connection::open(&path).unwrap();
+        db.pragma_update(None, "user_version", 99).unwrap();
+        drop(db);
+        let e = SqliteStore::open(&path, &WORDS, &WORDS, 5).unwrap_err();
+        assert!(e.to_string().contains("newer version"), "{}", e);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
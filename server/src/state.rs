@@ -0,0 +1,831 @@
+//! The games being played on the server
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    error::Error,
+    fmt,
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::{NaiveDate, Utc};
+use tokio_util::sync::CancellationToken;
+use wordle::{
+    definitions::Definitions, protocol::ServerMessage, race::Race, state::NoWord,
+    transcript::Transcript, BuildError, GameStatus, Wordle,
+};
+
+use crate::{
+    auth::{self, ApiKey, AuthError, Identity, Required},
+    daily::{self, DailyError, DailyResult},
+    metrics::{Metrics, Mode},
+    race::{RoomError, Rooms, Seat},
+    ratelimit::{RateLimit, RateLimiter},
+    store::{GameStore, MemoryStore, Session, StoreError},
+};
+
+/// The settings for a new game
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct GameOptions {
+    /// Whether revealed hints must be used in subsequent guesses
+    pub hard_mode: bool,
+    /// Whether to play today's puzzle instead of a random answer
+    pub daily: bool,
+}
+
+/// Limits on the games being played, so abandoned games don't pile up
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    /// How long a game is kept after it was last looked at or played
+    pub ttl: Duration,
+    /// How long a paused game is kept after it was last looked at, which is
+    /// longer, so a player can come back to it
+    pub paused_ttl: Duration,
+    /// The most games one client can have in progress at once
+    pub max_games_per_client: usize,
+    /// The most players a race room can have
+    pub max_players_per_room: usize,
+    /// How often each client can start games and make guesses, or `None` for
+    /// as often as they like
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(60 * 60),
+            paused_ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            max_games_per_client: 10,
+            max_players_per_room: 8,
+            rate_limit: None,
+        }
+    }
+}
+
+/// Why the server couldn't do something with a game
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ServerError {
+    /// There's no game with that id; it may have expired
+    NotFound,
+    /// The game being resumed isn't kept any more, since it went unused for
+    /// longer than the TTL (or it was deleted)
+    Expired,
+    /// The game is over, so it has no clock to pause
+    GameOver,
+    /// The client already has as many games in progress as it's allowed
+    TooManyGames {
+        /// The most games one client can have in progress at once
+        limit: usize,
+    },
+    /// A result wasn't put on the daily leaderboard
+    Daily(DailyError),
+    /// The request's API key wasn't accepted
+    Unauthorized(AuthError),
+    /// The store failed
+    Store(StoreError),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "No such game; it may have expired"),
+            Self::Expired => write!(f, "The game has expired; start a new one"),
+            Self::GameOver => write!(f, "The game is over, so it can't be paused"),
+            Self::TooManyGames { limit } => write!(
+                f,
+                "Too many games in progress; finish or delete one first (the limit is {})",
+                limit
+            ),
+            Self::Daily(e) => write!(f, "{}", e),
+            Self::Unauthorized(e) => write!(f, "{}", e),
+            Self::Store(e) => write!(f, "Could not store the game: {}", e),
+        }
+    }
+}
+
+impl Error for ServerError {}
+
+impl From<StoreError> for ServerError {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::NotFound => Self::NotFound,
+            StoreError::DuplicateResult => Self::Daily(DailyError::AlreadySubmitted),
+            e => Self::Store(e),
+        }
+    }
+}
+
+impl From<AuthError> for ServerError {
+    fn from(e: AuthError) -> Self {
+        Self::Unauthorized(e)
+    }
+}
+
+impl From<DailyError> for ServerError {
+    fn from(e: DailyError) -> Self {
+        Self::Daily(e)
+    }
+}
+
+/// Everything the server knows: the word lists, and where the games being
+/// played are kept.
+///
+/// This is cheap to clone; every clone shares the same games.
+#[derive(Debug, Clone)]
+pub struct Server {
+    /// Acceptable guesses
+    guesses: &'static [&'static str],
+    /// Answer list
+    answers: &'static [&'static str],
+    /// The number of letters in each word
+    word_length: usize,
+    /// Where the games are kept
+    store: Arc<dyn GameStore>,
+    /// Limits on the games being played
+    limits: Limits,
+    /// Makes up ids for new games
+    new_id: fn() -> String,
+    /// Today's date, which picks the daily puzzle
+    today: fn() -> NaiveDate,
+    /// Metrics about the games being played
+    metrics: Arc<Metrics>,
+    /// The race rooms
+    rooms: Arc<Rooms>,
+    /// Holds clients to the rate limit
+    rate_limiter: Arc<RateLimiter>,
+    /// Definitions of the answers, to give once a game is over
+    definitions: Arc<Definitions>,
+    /// API keys from a file, accepted along with the ones in the store
+    keys: Arc<[ApiKey]>,
+    /// Which routes need an API key
+    required_keys: Required,
+    /// The secret that picks each day's answer, or `None` to go through the
+    /// answer list in order
+    daily_secret: Option<Arc<[u8]>>,
+}
+
+/// Make up an id for a game, which can't be guessed from the ids of other games
+fn random_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// Make up a name for a player who didn't give one
+fn anonymous_name() -> String {
+    format!("anonymous-{:08x}", rand::random::<u32>())
+}
+
+/// Today's date in UTC, so the daily puzzle changes at the same time for everyone
+fn today_utc() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+impl Server {
+    /// Serve games with the given word lists, checking them first.
+    ///
+    /// Games are kept in memory with the default limits, until changed with
+    /// [`Server::with_store`] and [`Server::with_limits`].
+    pub fn new(
+        guesses: &'static [&'static str],
+        answers: &'static [&'static str],
+        word_length: usize,
+    ) -> Result<Self, BuildError> {
+        let server = Self {
+            guesses,
+            answers,
+            word_length,
+            store: Arc::new(MemoryStore::new()),
+            limits: Limits::default(),
+            new_id: random_id,
+            today: today_utc,
+            metrics: Arc::new(Metrics::new()),
+            rooms: Arc::new(Rooms::new(Limits::default().max_players_per_room)),
+            rate_limiter: Arc::new(RateLimiter::new(Limits::default().rate_limit)),
+            definitions: Arc::default(),
+            keys: Arc::new([]),
+            required_keys: Required::default(),
+            daily_secret: None,
+        };
+        server.new_game(GameOptions::default())?;
+        Ok(server)
+    }
+
+    /// Keep games in `store`
+    pub fn with_store(mut self, store: Arc<dyn GameStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Limit the games being played.
+    ///
+    /// This starts over with no race rooms, and forgets the requests clients
+    /// have made, so it's for setting up the server before it starts serving.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self.rooms = Arc::new(Rooms::new(limits.max_players_per_room));
+        self.rate_limiter = Arc::new(RateLimiter::new(limits.rate_limit));
+        self
+    }
+
+    /// Give the answer's definition, if it has one, once a game is over
+    pub fn with_definitions(mut self, definitions: Definitions) -> Self {
+        self.definitions = Arc::new(definitions);
+        self
+    }
+
+    /// Accept `keys` as well as the ones in the store, and need a key for the
+    /// routes in `required`
+    pub fn with_keys(mut self, keys: Vec<ApiKey>, required: Required) -> Self {
+        self.keys = keys.into();
+        self.required_keys = required;
+        self
+    }
+
+    /// Which routes need an API key
+    pub fn required_keys(&self) -> Required {
+        self.required_keys
+    }
+
+    /// Find out who a request is from by its API key
+    pub fn authenticate(&self, secret: &str) -> Result<Identity, ServerError> {
+        let stored = self.store.keys()?;
+        let key = auth::find(self.keys.iter().chain(&stored), secret)?;
+        Ok(Identity {
+            name: key.name.clone(),
+        })
+    }
+
+    /// Whether an API key was issued to `name`, from the file or the store,
+    /// even if it's since been revoked
+    pub fn has_key(&self, name: &str) -> Result<bool, ServerError> {
+        let stored = self.store.keys()?;
+        Ok(self.keys.iter().chain(&stored).any(|key| key.name == name))
+    }
+
+    /// Pick each day's answer by a keyed hash of the date, so it can't be
+    /// worked out without `secret`, but every server with it picks the same one.
+    ///
+    /// The `wordle` binary plays the daily puzzle from the answer list in
+    /// order, so it can't play this one; `GET /daily/meta` gives a fingerprint
+    /// of the answer with a secret, which `wordle --server` checks for so it
+    /// doesn't send results the server would refuse as `wrong_answer`.
+    pub fn with_daily_secret(mut self, secret: &[u8]) -> Self {
+        self.daily_secret = Some(secret.into());
+        self
+    }
+
+    /// The definitions of the answers
+    pub fn definitions(&self) -> &Definitions {
+        &self.definitions
+    }
+
+    /// The limits on the games being played
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// The race rooms
+    pub fn rooms(&self) -> &Rooms {
+        &self.rooms
+    }
+
+    /// Holds clients to the rate limit
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// Join a race room as `name`, or rejoin it with a token, starting a race
+    /// with a random answer if the room is new.
+    ///
+    /// Along with the player's seat, get back the messages that catch them up.
+    pub fn join_race(
+        &self,
+        room: &str,
+        name: &str,
+        token: Option<&str>,
+    ) -> Result<(Seat, Vec<ServerMessage>), RoomError> {
+        self.rooms.join(room, name, token, |players| {
+            let game = self
+                .new_game(GameOptions::default())
+                .expect("Word lists were checked when the server started")
+                .choose_word();
+            Race::new(&game, players)
+        })
+    }
+
+    /// Metrics about the games being played
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// The metrics in the Prometheus text format
+    pub fn render_metrics(&self) -> Result<String, ServerError> {
+        Ok(self
+            .metrics
+            .render(self.store.in_progress()?, self.rate_limiter.clients()))
+    }
+
+    /// Set up a game without an answer chosen yet, or keeping track of it
+    fn new_game(&self, options: GameOptions) -> Result<Wordle<'static, NoWord>, BuildError> {
+        Wordle::builder()
+            .guesses(self.guesses)
+            .answers(self.answers)
+            .word_length(self.word_length)
+            .hard_mode(options.hard_mode)
+            .build()
+    }
+
+    /// The number of `date`'s puzzle, and the index of its answer in the answer list
+    fn daily_index(&self, date: NaiveDate) -> Result<(u32, usize), ServerError> {
+        let puzzle = wordle::daily::puzzle_number(date).ok_or(DailyError::NoPuzzle)?;
+        let index = match &self.daily_secret {
+            Some(secret) => daily::keyed_index(secret, date, self.answers.len()),
+            None => wordle::daily::index(puzzle, self.answers.len()),
+        };
+        // the first answer picked for a day sticks, so changing the secret
+        // (or adding one) doesn't change a day that's already started
+        let index = self.store.pin_daily_index(date, index)?;
+        Ok((puzzle, index % self.answers.len()))
+    }
+
+    /// The number of `date`'s puzzle, and its answer
+    pub fn daily_answer(&self, date: NaiveDate) -> Result<(u32, &'static str), ServerError> {
+        let (puzzle, index) = self.daily_index(date)?;
+        Ok((puzzle, self.answers[index]))
+    }
+
+    /// A short keyed hash of `date`'s answer, the same on every server with
+    /// the same secret, or `None` if there's no secret
+    pub fn daily_fingerprint(&self, date: NaiveDate) -> Result<Option<String>, ServerError> {
+        let (_, index) = self.daily_index(date)?;
+        Ok(self
+            .daily_secret
+            .as_ref()
+            .map(|secret| daily::fingerprint(secret, date, index)))
+    }
+
+    /// Start a new game for `client`, and get back its id
+    pub fn create(
+        &self,
+        options: GameOptions,
+        client: Option<IpAddr>,
+    ) -> Result<String, ServerError> {
+        if let Some(client) = client {
+            let limit = self.limits.max_games_per_client;
+            if self.store.active_games(client)? >= limit {
+                return Err(ServerError::TooManyGames { limit });
+            }
+        }
+
+        let daily = match options.daily {
+            true => Some(self.daily_answer(self.today())?.1),
+            false => None,
+        };
+        let mode = match daily {
+            Some(_) => Mode::Daily,
+            None => Mode::Endless,
+        };
+        let new_game = || {
+            let game = self
+                .new_game(options)
+                .expect("Word lists were checked when the server started");
+            match daily {
+                Some(answer) => game
+                    .set_answer(answer)
+                    .expect("Answers are checked to be acceptable guesses"),
+                None => game.choose_word(),
+            }
+        };
+        let mut session = Session {
+            game: new_game(),
+            client,
+            last_used: SystemTime::now(),
+            mode,
+        };
+        // ids are random, so they should never collide; but if one does, try another
+        loop {
+            let id = (self.new_id)();
+            match self.store.insert(&id, session) {
+                Ok(()) => {
+                    self.metrics.game_created(mode, options.hard_mode);
+                    return Ok(id);
+                }
+                Err(StoreError::Exists) => {
+                    session = Session {
+                        game: new_game(),
+                        client,
+                        last_used: SystemTime::now(),
+                        mode,
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Do something with a game, with only that game locked
+    pub fn with_game<T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut Wordle<'static>) -> T,
+    ) -> Result<T, ServerError> {
+        self.with_session(id, |session| f(&mut session.game))
+    }
+
+    /// Do something with a game and what's known about it, with only that
+    /// game locked
+    pub fn with_session<T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut Session) -> T,
+    ) -> Result<T, ServerError> {
+        let mut f = Some(f);
+        let mut result = None;
+        self.store.update(id, &mut |session| {
+            session.last_used = SystemTime::now();
+            if let Some(f) = f.take() {
+                result = Some(f(session));
+            }
+        })?;
+        Ok(result.expect("The game was updated"))
+    }
+
+    /// Stop a game's clock, e.g. while the player's client is offline, and
+    /// keep it for the longer paused TTL; the next guess starts it again
+    pub fn pause(&self, id: &str) -> Result<(), ServerError> {
+        self.with_game(id, |game| match game.status() {
+            GameStatus::InProgress => {
+                game.pause();
+                Ok(())
+            }
+            _ => Err(ServerError::GameOver),
+        })?
+    }
+
+    /// Start a paused game's clock again.
+    ///
+    /// The client had the game, so if it's gone, it's [`ServerError::Expired`]
+    /// rather than not found.
+    pub fn resume(&self, id: &str) -> Result<(), ServerError> {
+        self.with_game(id, Wordle::unpause).map_err(|e| match e {
+            ServerError::NotFound => ServerError::Expired,
+            e => e,
+        })
+    }
+
+    /// How long a game is kept from now if it isn't used again, which is
+    /// longer while it's paused
+    pub fn ttl(&self, game: &Wordle<'_>) -> Duration {
+        match game.is_paused() {
+            true => self.limits.paused_ttl,
+            false => self.limits.ttl,
+        }
+    }
+
+    /// Abandon a game
+    pub fn delete(&self, id: &str) -> Result<(), ServerError> {
+        Ok(self.store.remove(id)?)
+    }
+
+    /// Remove the games that haven't been used for longer than the TTL, or
+    /// the paused TTL for paused games, and get back how many there were.
+    ///
+    /// Race rooms no one is connected to are removed after the TTL too, and
+    /// clients are forgotten by the rate limiter once they've been idle long
+    /// enough to be back to their full allowance.
+    pub fn expire(&self) -> Result<usize, ServerError> {
+        self.rate_limiter.evict_idle(Instant::now());
+        let now = SystemTime::now();
+        let cutoff = |ttl| now.checked_sub(ttl).unwrap_or(SystemTime::UNIX_EPOCH);
+        self.rooms.expire(cutoff(self.limits.ttl));
+        Ok(self
+            .store
+            .expire(cutoff(self.limits.ttl), cutoff(self.limits.paused_ttl))?)
+    }
+
+    /// Check that the server can serve games: the word lists are loaded, and
+    /// the store can be reached
+    pub fn check_ready(&self) -> Result<(), ServerError> {
+        if self.guesses.is_empty() || self.answers.is_empty() {
+            return Err(ServerError::Store(StoreError::Backend(
+                "the word lists are empty".to_owned(),
+            )));
+        }
+        Ok(self.store.ping()?)
+    }
+
+    /// Write anything the store is holding on to, before the server stops
+    pub fn flush(&self) -> Result<(), ServerError> {
+        Ok(self.store.flush()?)
+    }
+
+    /// Today's date, which picks the daily puzzle
+    pub fn today(&self) -> NaiveDate {
+        (self.today)()
+    }
+
+    /// Check a game of today's puzzle by playing it again, and put it on the
+    /// leaderboard for `player`, or a made-up name if there isn't one
+    pub fn add_result(
+        &self,
+        player: Option<&str>,
+        transcript: &Transcript,
+    ) -> Result<DailyResult, ServerError> {
+        let player = match player {
+            Some(name) => daily::check_name(name)?.to_owned(),
+            None => anonymous_name(),
+        };
+        let date = self.today();
+        let (_, answer) = self.daily_answer(date)?;
+        let verified = daily::replay(date, answer, self.guesses, transcript)?;
+        let result = DailyResult {
+            date,
+            puzzle: transcript.puzzle.expect("The puzzle was checked"),
+            player,
+            guesses: verified.guesses,
+            elapsed: verified.elapsed,
+            hard_mode: verified.hard_mode,
+            submitted: SystemTime::now(),
+        };
+        self.store.add_result(result.clone())?;
+        self.metrics.daily_result(&result);
+        Ok(result)
+    }
+
+    /// The leaderboard for a day, best first
+    pub fn leaderboard(&self, date: NaiveDate) -> Result<Vec<DailyResult>, ServerError> {
+        let mut results = self.store.results(date)?;
+        daily::rank(&mut results);
+        Ok(results)
+    }
+}
+
+/// Expire abandoned games every `interval`, until `shutdown` is cancelled
+pub async fn sweep(server: Server, interval: Duration, shutdown: CancellationToken) {
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {}
+            _ = shutdown.cancelled() => return,
+        }
+        if let Err(e) = server.expire() {
+            eprintln!("Warning: could not expire games: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        net::Ipv4Addr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const WORDS: [&str; 2] = ["crane", "slate"];
+
+    #[test]
+    fn test_create() {
+        let server = Server::new(&WORDS, &WORDS, 5).unwrap();
+        let first = server.create(GameOptions::default(), None).unwrap();
+        let second = server
+            .create(
+                GameOptions {
+                    hard_mode: true,
+                    ..GameOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+        assert_ne!(first, second);
+        assert_eq!(32, first.len());
+
+        assert_eq!(Ok(false), server.with_game(&first, |game| game.hard_mode()));
+        assert_eq!(Ok(true), server.with_game(&second, |game| game.hard_mode()));
+        assert_eq!(
+            Err(ServerError::NotFound),
+            server.with_game("nope", |game| game.hard_mode())
+        );
+
+        server.delete(&first).unwrap();
+        assert_eq!(Err(ServerError::NotFound), server.delete(&first));
+
+        assert!(Server::new(&WORDS, &WORDS, 6).is_err());
+    }
+
+    #[test]
+    fn test_id_collisions() {
+        /// Hands out "a", "a", "b", "c", ...
+        fn colliding_id() -> String {
+            static CALLS: AtomicUsize = AtomicUsize::new(0);
+            let n = CALLS.fetch_add(1, Ordering::SeqCst).saturating_sub(1);
+            ((b'a' + n as u8) as char).to_string()
+        }
+
+        let mut server = Server::new(&WORDS, &WORDS, 5).unwrap();
+        server.new_id = colliding_id;
+        assert_eq!(Ok("a".into()), server.create(GameOptions::default(), None));
+        assert_eq!(Ok("b".into()), server.create(GameOptions::default(), None));
+    }
+
+    #[test]
+    fn test_add_result() {
+        let mut server = Server::new(&WORDS, &WORDS, 5).unwrap();
+        server.today = || NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let (mut game, puzzle) = Wordle::new(&WORDS, &WORDS)
+            .choose_daily(server.today())
+            .unwrap();
+        let answer = game.transcript().answer.unwrap();
+        game.guess(&answer).unwrap();
+        let transcript = Transcript {
+            puzzle: Some(puzzle),
+            ..game.transcript().hide_answer()
+        };
+
+        let result = server.add_result(Some(" alice "), &transcript).unwrap();
+        assert_eq!("alice", result.player);
+        assert_eq!(1, result.guesses);
+        assert_eq!(
+            Err(ServerError::Daily(DailyError::AlreadySubmitted)),
+            server.add_result(Some("alice"), &transcript)
+        );
+        let anonymous = server.add_result(None, &transcript).unwrap();
+        assert!(anonymous.player.starts_with("anonymous-"));
+        assert_eq!(
+            Err(ServerError::Daily(DailyError::InvalidName)),
+            server.add_result(Some(""), &transcript)
+        );
+
+        let players: Vec<_> = server
+            .leaderboard(server.today())
+            .unwrap()
+            .into_iter()
+            .map(|result| result.player)
+            .collect();
+        assert_eq!(vec!["alice".to_owned(), anonymous.player], players);
+    }
+
+    #[test]
+    fn test_daily_secret() {
+        const ANSWERS: [&str; 7] = [
+            "crane", "slate", "trace", "irate", "stare", "snare", "share",
+        ];
+        let today = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+        let mut server = Server::new(&ANSWERS, &ANSWERS, 5)
+            .unwrap()
+            .with_daily_secret(b"one");
+        server.today = || NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+
+        let (puzzle, answer) = server.daily_answer(today).unwrap();
+        assert_eq!(196, puzzle);
+        assert_eq!(ANSWERS[daily::keyed_index(b"one", today, 7)], answer);
+        let options = GameOptions {
+            daily: true,
+            ..GameOptions::default()
+        };
+        let id = server.create(options, None).unwrap();
+        assert_eq!(
+            Ok(Some(answer.to_owned())),
+            server.with_game(&id, |game| game.transcript().answer)
+        );
+
+        // changing the secret doesn't change a day that's already started
+        let server = server.with_daily_secret(b"two");
+        assert_eq!(Ok((puzzle, answer)), server.daily_answer(today));
+        assert_eq!(
+            ANSWERS[daily::keyed_index(b"two", tomorrow, 7)],
+            server.daily_answer(tomorrow).unwrap().1
+        );
+
+        // the CLI's daily puzzle goes through the list in order, so its
+        // results are refused when the secret picks another answer
+        let (mut game, puzzle) = Wordle::new(&ANSWERS, &ANSWERS).choose_daily(today).unwrap();
+        let cli_answer = game.transcript().answer.unwrap();
+        assert_ne!(answer, cli_answer);
+        game.guess(&cli_answer).unwrap();
+        let transcript = Transcript {
+            puzzle: Some(puzzle),
+            ..game.transcript().hide_answer()
+        };
+        let e = server.add_result(None, &transcript).unwrap_err();
+        assert!(
+            matches!(&e, ServerError::Daily(e) if e.code() == "wrong_answer"),
+            "{:?}",
+            e
+        );
+    }
+
+    #[test]
+    fn test_daily_answer() {
+        // without a secret, the answers are the same as the CLI's
+        let mut server = Server::new(&WORDS, &WORDS, 5).unwrap();
+        server.today = || NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        for date in [server.today(), server.today().succ_opt().unwrap()] {
+            let puzzle = wordle::daily::puzzle_number(date).unwrap();
+            assert_eq!(
+                Ok((puzzle, wordle::daily::answer(&WORDS, puzzle))),
+                server.daily_answer(date)
+            );
+        }
+        assert_eq!(Ok(None), server.daily_fingerprint(server.today()));
+    }
+
+    #[test]
+    fn test_limits() {
+        let server = Server::new(&WORDS, &WORDS, 5).unwrap().with_limits(Limits {
+            ttl: Duration::ZERO,
+            max_games_per_client: 2,
+            ..Limits::default()
+        });
+        let client = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let other = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        server.create(GameOptions::default(), client).unwrap();
+        let id = server.create(GameOptions::default(), client).unwrap();
+        assert_eq!(
+            Err(ServerError::TooManyGames { limit: 2 }),
+            server.create(GameOptions::default(), client)
+        );
+        assert!(server.create(GameOptions::default(), other).is_ok());
+
+        // finishing a game frees up a slot
+        server
+            .with_game(&id, |game| {
+                let word = game.transcript().answer.unwrap();
+                game.guess(&word).unwrap();
+            })
+            .unwrap();
+        assert!(server.create(GameOptions::default(), client).is_ok());
+
+        // with no TTL, every game has expired by now
+        assert_eq!(Ok(4), server.expire());
+        assert_eq!(Err(ServerError::NotFound), server.with_game(&id, |_| ()));
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let (file_secret, file_key) = ApiKey::issue("alice");
+        let (stored_secret, stored_key) = ApiKey::issue("bob");
+        let store = Arc::new(MemoryStore::new());
+        store.add_key(stored_key).unwrap();
+        let server = Server::new(&WORDS, &WORDS, 5)
+            .unwrap()
+            .with_store(store.clone())
+            .with_keys(vec![file_key], Required::default());
+
+        // keys from a file and from the store are both accepted
+        assert_eq!(
+            Ok("alice".to_owned()),
+            server.authenticate(&file_secret).map(|id| id.name)
+        );
+        assert_eq!(
+            Ok("bob".to_owned()),
+            server.authenticate(&stored_secret).map(|id| id.name)
+        );
+        assert_eq!(
+            Err(ServerError::Unauthorized(AuthError::Invalid)),
+            server.authenticate("wk_nope")
+        );
+
+        // and revoking one in the store counts straight away
+        store.revoke_key("bob", SystemTime::now()).unwrap();
+        assert_eq!(
+            Err(ServerError::Unauthorized(AuthError::Revoked)),
+            server.authenticate(&stored_secret)
+        );
+
+        // but the name stays taken
+        assert_eq!(Ok(true), server.has_key("alice"));
+        assert_eq!(Ok(true), server.has_key("bob"));
+        assert_eq!(Ok(false), server.has_key("carol"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sweep_shutdown() {
+        let server = Server::new(&WORDS, &WORDS, 5).unwrap();
+        let shutdown = CancellationToken::new();
+        let sweeper = tokio::spawn(sweep(server, Duration::from_secs(1), shutdown.clone()));
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert!(!sweeper.is_finished());
+
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), sweeper)
+            .await
+            .expect("The sweeper stops when asked")
+            .unwrap();
+    }
+}
@@ -0,0 +1,454 @@
+//! Where the games being played are kept
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    net::IpAddr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock},
+    time::SystemTime,
+};
+
+use chrono::NaiveDate;
+use wordle::{GameStatus, Wordle};
+
+use crate::{auth::ApiKey, daily::DailyResult, metrics::Mode};
+
+/// A game being played, along with who's playing it
+#[derive(Debug)]
+pub struct Session {
+    /// The game
+    pub game: Wordle<'static>,
+    /// The address of the client that created the game, if it's known
+    pub client: Option<IpAddr>,
+    /// When the game was last looked at or played
+    pub last_used: SystemTime,
+    /// Whether the game is the day's puzzle or has a random answer
+    pub mode: Mode,
+}
+
+/// Why a [`GameStore`] couldn't do something
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StoreError {
+    /// There's already a game with that id
+    Exists,
+    /// There's no game with that id
+    NotFound,
+    /// The player already has a result on the leaderboard for the day
+    DuplicateResult,
+    /// There's already a key for that name
+    DuplicateKey,
+    /// There's no key for that name
+    UnknownKey,
+    /// The storage behind the store failed, e.g. a database
+    Backend(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists => write!(f, "a game with that id already exists"),
+            Self::NotFound => write!(f, "no game with that id"),
+            Self::DuplicateResult => write!(f, "the player already has a result for the day"),
+            Self::DuplicateKey => write!(f, "there's already a key for that name"),
+            Self::UnknownKey => write!(f, "no key for that name"),
+            Self::Backend(e) => write!(f, "storage failed: {}", e),
+        }
+    }
+}
+
+impl Error for StoreError {}
+
+/// Somewhere to keep games by id, the daily puzzles and leaderboard, and API keys.
+///
+/// Stores lock each game separately, so playing one game never holds up
+/// playing another.
+pub trait GameStore: fmt::Debug + Send + Sync {
+    /// Add a new game; it's an error if the id is taken
+    fn insert(&self, id: &str, session: Session) -> Result<(), StoreError>;
+
+    /// Change a game, with only that game locked
+    fn update(&self, id: &str, f: &mut dyn FnMut(&mut Session)) -> Result<(), StoreError>;
+
+    /// Remove a game
+    fn remove(&self, id: &str) -> Result<(), StoreError>;
+
+    /// Remove the games last used before `cutoff`, or before `paused_cutoff`
+    /// for paused games, and get back how many there were
+    fn expire(&self, cutoff: SystemTime, paused_cutoff: SystemTime) -> Result<usize, StoreError>;
+
+    /// The number of games in progress that were created by `client`
+    fn active_games(&self, client: IpAddr) -> Result<usize, StoreError>;
+
+    /// The number of games in progress, by anyone
+    fn in_progress(&self) -> Result<usize, StoreError>;
+
+    /// Add a result to the leaderboard for its day; it's an error if the
+    /// player already has one
+    fn add_result(&self, result: DailyResult) -> Result<(), StoreError>;
+
+    /// The results on the leaderboard for a day, in the order they were added
+    fn results(&self, date: NaiveDate) -> Result<Vec<DailyResult>, StoreError>;
+
+    /// The index of `date`'s answer in the answer list: the one already kept
+    /// for it, or else `index`, which is kept for it from then on
+    fn pin_daily_index(&self, date: NaiveDate, index: usize) -> Result<usize, StoreError>;
+
+    /// Add an API key; it's an error if its name already has one
+    fn add_key(&self, key: ApiKey) -> Result<(), StoreError>;
+
+    /// Revoke the API key for `name` as of `when`; revoking a key twice keeps
+    /// the time it was first revoked
+    fn revoke_key(&self, name: &str, when: SystemTime) -> Result<(), StoreError>;
+
+    /// Every API key, revoked or not, in the order they were added
+    fn keys(&self) -> Result<Vec<ApiKey>, StoreError>;
+
+    /// Check that the storage behind the store can be reached
+    fn ping(&self) -> Result<(), StoreError>;
+
+    /// Write anything the store is holding on to, before the server stops
+    fn flush(&self) -> Result<(), StoreError>;
+}
+
+/// A [`GameStore`] that keeps games in memory, so they're lost when the server stops
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    /// The games, each behind a lock of its own; the map is only locked long
+    /// enough to find a game
+    games: RwLock<HashMap<String, Arc<Mutex<Session>>>>,
+    /// The leaderboard, by day
+    results: Mutex<BTreeMap<NaiveDate, Vec<DailyResult>>>,
+    /// The index of each day's answer, once it's been picked
+    daily_indexes: Mutex<BTreeMap<NaiveDate, usize>>,
+    /// The API keys, in the order they were added
+    keys: Mutex<Vec<ApiKey>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove the games last used before `cutoff`, or before `paused_cutoff`
+    /// for paused games, and get back their ids
+    pub fn expire_ids(&self, cutoff: SystemTime, paused_cutoff: SystemTime) -> Vec<String> {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        let expired: Vec<String> = games
+            .iter()
+            .filter(|(_, session)| {
+                let session = lock(session);
+                match session.game.is_paused() {
+                    true => session.last_used < paused_cutoff,
+                    false => session.last_used < cutoff,
+                }
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &expired {
+            games.remove(id);
+        }
+        expired
+    }
+
+    /// Find a game, without keeping the map locked
+    fn get(&self, id: &str) -> Result<Arc<Mutex<Session>>, StoreError> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        games.get(id).cloned().ok_or(StoreError::NotFound)
+    }
+}
+
+/// Lock a game; a panic while it was locked doesn't stop it being played
+fn lock(session: &Mutex<Session>) -> MutexGuard<'_, Session> {
+    session.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl GameStore for MemoryStore {
+    fn insert(&self, id: &str, session: Session) -> Result<(), StoreError> {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        if games.contains_key(id) {
+            return Err(StoreError::Exists);
+        }
+        games.insert(id.to_owned(), Arc::new(Mutex::new(session)));
+        Ok(())
+    }
+
+    fn update(&self, id: &str, f: &mut dyn FnMut(&mut Session)) -> Result<(), StoreError> {
+        let session = self.get(id)?;
+        f(&mut lock(&session));
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> Result<(), StoreError> {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        games.remove(id).map(|_| ()).ok_or(StoreError::NotFound)
+    }
+
+    fn expire(&self, cutoff: SystemTime, paused_cutoff: SystemTime) -> Result<usize, StoreError> {
+        Ok(self.expire_ids(cutoff, paused_cutoff).len())
+    }
+
+    fn active_games(&self, client: IpAddr) -> Result<usize, StoreError> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(games
+            .values()
+            .filter(|session| {
+                let session = lock(session);
+                session.client == Some(client) && session.game.status() == GameStatus::InProgress
+            })
+            .count())
+    }
+
+    fn in_progress(&self) -> Result<usize, StoreError> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(games
+            .values()
+            .filter(|session| lock(session).game.status() == GameStatus::InProgress)
+            .count())
+    }
+
+    fn add_result(&self, result: DailyResult) -> Result<(), StoreError> {
+        let mut results = self.results.lock().unwrap_or_else(PoisonError::into_inner);
+        let day = results.entry(result.date).or_default();
+        if day.iter().any(|other| other.player == result.player) {
+            return Err(StoreError::DuplicateResult);
+        }
+        day.push(result);
+        Ok(())
+    }
+
+    fn results(&self, date: NaiveDate) -> Result<Vec<DailyResult>, StoreError> {
+        let results = self.results.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(results.get(&date).cloned().unwrap_or_default())
+    }
+
+    fn pin_daily_index(&self, date: NaiveDate, index: usize) -> Result<usize, StoreError> {
+        let mut indexes = self
+            .daily_indexes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Ok(*indexes.entry(date).or_insert(index))
+    }
+
+    fn add_key(&self, key: ApiKey) -> Result<(), StoreError> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        if keys.iter().any(|other| other.name == key.name) {
+            return Err(StoreError::DuplicateKey);
+        }
+        keys.push(key);
+        Ok(())
+    }
+
+    fn revoke_key(&self, name: &str, when: SystemTime) -> Result<(), StoreError> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        let key = keys
+            .iter_mut()
+            .find(|key| key.name == name)
+            .ok_or(StoreError::UnknownKey)?;
+        key.revoked.get_or_insert(when);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<ApiKey>, StoreError> {
+        Ok(self
+            .keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone())
+    }
+
+    fn ping(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::Ipv4Addr, thread, time::Duration};
+
+    const WORDS: [&str; 2] = ["crane", "slate"];
+
+    fn session(client: Option<IpAddr>, last_used: SystemTime) -> Session {
+        let game = Wordle::new(&WORDS, &WORDS).set_answer("crane").unwrap();
+        Session {
+            game,
+            client,
+            last_used,
+            mode: Mode::Endless,
+        }
+    }
+
+    #[test]
+    fn test_insert() {
+        let store = MemoryStore::new();
+        let now = SystemTime::now();
+        store.insert("a", session(None, now)).unwrap();
+        assert_eq!(
+            Err(StoreError::Exists),
+            store.insert("a", session(None, now))
+        );
+
+        store
+            .update("a", &mut |session| {
+                session.game.guess("slate").unwrap();
+            })
+            .unwrap();
+        let mut guesses = 0;
+        store
+            .update("a", &mut |session| guesses = session.game.history().len())
+            .unwrap();
+        assert_eq!(1, guesses);
+
+        assert_eq!(Err(StoreError::NotFound), store.update("b", &mut |_| {}));
+        store.remove("a").unwrap();
+        assert_eq!(Err(StoreError::NotFound), store.remove("a"));
+    }
+
+    #[test]
+    fn test_expire() {
+        let store = MemoryStore::new();
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        store.insert("old", session(None, now - 2 * hour)).unwrap();
+        store.insert("new", session(None, now)).unwrap();
+        let mut paused = session(None, now - 2 * hour);
+        paused.game.pause();
+        store.insert("paused", paused).unwrap();
+
+        // paused games are kept for longer
+        assert_eq!(Ok(1), store.expire(now - hour, now - 3 * hour));
+        assert_eq!(Err(StoreError::NotFound), store.update("old", &mut |_| {}));
+        assert_eq!(Ok(()), store.update("new", &mut |_| {}));
+        assert_eq!(Ok(()), store.update("paused", &mut |_| {}));
+        assert_eq!(Ok(0), store.expire(now - hour, now - 3 * hour));
+        assert_eq!(Ok(1), store.expire(now - hour, now - hour));
+        assert_eq!(
+            Err(StoreError::NotFound),
+            store.update("paused", &mut |_| {})
+        );
+    }
+
+    #[test]
+    fn test_active_games() {
+        let store = MemoryStore::new();
+        let now = SystemTime::now();
+        let client = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        store.insert("a", session(Some(client), now)).unwrap();
+        store.insert("b", session(Some(client), now)).unwrap();
+        store.insert("c", session(None, now)).unwrap();
+        assert_eq!(Ok(2), store.active_games(client));
+        assert_eq!(Ok(3), store.in_progress());
+
+        // finished games don't count
+        store
+            .update("a", &mut |session| {
+                session.game.guess("crane").unwrap();
+            })
+            .unwrap();
+        assert_eq!(Ok(1), store.active_games(client));
+        assert_eq!(Ok(2), store.in_progress());
+    }
+
+    #[test]
+    fn test_results() {
+        let store = MemoryStore::new();
+        let day = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let result = |date: NaiveDate, player: &str| DailyResult {
+            date,
+            puzzle: 196,
+            player: player.to_owned(),
+            guesses: 3,
+            elapsed: Duration::from_secs(60),
+            hard_mode: false,
+            submitted: SystemTime::UNIX_EPOCH,
+        };
+        store.add_result(result(day, "alice")).unwrap();
+        store.add_result(result(day, "bob")).unwrap();
+        assert_eq!(
+            Err(StoreError::DuplicateResult),
+            store.add_result(result(day, "alice"))
+        );
+        // each day has a leaderboard of its own
+        let next = day.succ_opt().unwrap();
+        store.add_result(result(next, "alice")).unwrap();
+
+        assert_eq!(
+            Ok(vec![result(day, "alice"), result(day, "bob")]),
+            store.results(day)
+        );
+        assert_eq!(Ok(vec![result(next, "alice")]), store.results(next));
+        assert_eq!(Ok(Vec::new()), store.results(next.succ_opt().unwrap()));
+    }
+
+    #[test]
+    fn test_pin_daily_index() {
+        let store = MemoryStore::new();
+        let day = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        assert_eq!(Ok(7), store.pin_daily_index(day, 7));
+        // once picked, a day's answer stays the same
+        assert_eq!(Ok(7), store.pin_daily_index(day, 3));
+        assert_eq!(Ok(3), store.pin_daily_index(day.succ_opt().unwrap(), 3));
+    }
+
+    #[test]
+    fn test_keys() {
+        let store = MemoryStore::new();
+        let (_, alice) = ApiKey::issue("alice");
+        store.add_key(alice.clone()).unwrap();
+        store.add_key(ApiKey::issue("bob").1).unwrap();
+        assert_eq!(
+            Err(StoreError::DuplicateKey),
+            store.add_key(ApiKey::issue("alice").1)
+        );
+
+        let now = SystemTime::now();
+        store.revoke_key("bob", now).unwrap();
+        store
+            .revoke_key("bob", now + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(Err(StoreError::UnknownKey), store.revoke_key("carol", now));
+        let keys = store.keys().unwrap();
+        assert_eq!(alice, keys[0]);
+        assert_eq!(Some(now), keys[1].revoked);
+    }
+
+    #[test]
+    fn test_unrelated_games() {
+        let store = Arc::new(MemoryStore::new());
+        let now = SystemTime::now();
+        store.insert("a", session(None, now)).unwrap();
+        store.insert("b", session(None, now)).unwrap();
+
+        // a game stays playable while another is locked
+        store
+            .update("a", &mut |_| {
+                let store = Arc::clone(&store);
+                thread::spawn(move || store.update("b", &mut |_| {}))
+                    .join()
+                    .unwrap()
+                    .unwrap();
+            })
+            .unwrap();
+    }
+}
@@ -0,0 +1,415 @@
+//! End-to-end tests of the REST API, over HTTP
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use wordle::definitions::Definitions;
+use wordle_server::{ratelimit::RateLimit, state::Limits, Server};
+
+use common::{create, guess, serve, start, ANSWERS, GUESSES};
+
+/// Start a server with the given limits on a free port, and get back its base URL
+async fn start_with(limits: Limits) -> String {
+    serve(
+        Server::new(&GUESSES, &ANSWERS, 5)
+            .unwrap()
+            .with_limits(limits),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_full_game() {
+    let url = start().await;
+    let client = Client::new();
+
+    let response = client.post(format!("{}/games", url)).send().await.unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let game: Value = response.json().await.unwrap();
+    let id = game["id"].as_str().unwrap().to_owned();
+    assert_eq!(5, game["word_length"]);
+    assert_eq!(6, game["max_guesses"]);
+    assert_eq!("in_progress", game["status"]);
+    assert_eq!(Value::Null, game["answer"]);
+
+    let (status, result) = guess(&client, &url, &id, "trace").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(
+        json!({
+            "statuses": ["not_in_word", "correct", "correct", "in_word", "correct"],
+            "turn": 1,
+            "guesses_left": 5,
+            "status": "in_progress",
+            "answer": null,
+        }),
+        result
+    );
+
+    // the answer stays on the server while the game is in progress
+    let text = client
+        .get(format!("{}/games/{}", url, id))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(!text.contains("crane"), "answer leaked: {}", text);
+    let game: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!("trace", game["guesses"][0]["word"]);
+
+    let (status, result) = guess(&client, &url, &id, "CRANE").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(2, result["turn"]);
+    assert_eq!("won", result["status"]);
+    assert_eq!("crane", result["answer"]);
+
+    let game: Value = client
+        .get(format!("{}/games/{}", url, id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!("won", game["status"]);
+    assert_eq!("crane", game["answer"]);
+    assert_eq!(2, game["guesses"].as_array().unwrap().len());
+
+    let (status, result) = guess(&client, &url, &id, "slate").await;
+    assert_eq!(StatusCode::CONFLICT, status);
+    assert_eq!("The game is over", result["error"]);
+}
+
+/// Get a game's board as an SVG image, and get back the response status and body
+async fn board_svg(client: &Client, url: &str, id: &str) -> (StatusCode, String) {
+    let response = client
+        .get(format!("{}/games/{}/board.svg", url, id))
+        .send()
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert_eq!("image/svg+xml", response.headers()["content-type"]);
+    }
+    (status, response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_board_svg() {
+    let url = start().await;
+    let client = Client::new();
+    let (_, game) = create(&client, &url).await;
+    let id = game["id"].as_str().unwrap();
+
+    guess(&client, &url, id, "trace").await;
+    let (status, svg) = board_svg(&client, &url, id).await;
+    assert_eq!(StatusCode::OK, status);
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(5, svg.matches("<rect x=").count());
+    assert!(!svg.contains("<text"), "letters shown mid-game: {}", svg);
+
+    guess(&client, &url, id, "crane").await;
+    let (_, svg) = board_svg(&client, &url, id).await;
+    assert_eq!(10, svg.matches("<rect x=").count());
+    assert_eq!(10, svg.matches("<text").count());
+
+    let (status, _) = board_svg(&client, &url, "nope").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+}
+
+#[tokio::test]
+async fn test_board_html() {
+    let url = start().await;
+    let client = Client::new();
+    let (_, game) = create(&client, &url).await;
+    let id = game["id"].as_str().unwrap();
+    let board = || async {
+        let response = client
+            .get(format!("{}/games/{}/board.html", url, id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            "text/html; charset=utf-8",
+            response.headers()["content-type"]
+        );
+        response.text().await.unwrap()
+    };
+
+    guess(&client, &url, id, "trace").await;
+    let html = board().await;
+    assert!(html.contains(r#"aria-label="in the word, wrong position""#));
+    assert!(!html.contains("T,"), "letters shown mid-game: {}", html);
+
+    guess(&client, &url, id, "crane").await;
+    let html = board().await;
+    assert!(html.contains(r#"aria-label="T, not in the word""#));
+    assert!(html.contains(r#"aria-label="C, correct position""#));
+}
+
+#[tokio::test]
+async fn test_definitions() {
+    let definitions = Definitions::parse("crane\ta large wading bird\n");
+    let url = serve(
+        Server::new(&GUESSES, &ANSWERS, 5)
+            .unwrap()
+            .with_definitions(definitions),
+    )
+    .await;
+    let client = Client::new();
+
+    let (_, game) = create(&client, &url).await;
+    let id = game["id"].as_str().unwrap().to_owned();
+    assert_eq!(Value::Null, game["definition"]);
+    let (_, result) = guess(&client, &url, &id, "slate").await;
+    assert_eq!(Value::Null, result["definition"]);
+
+    let (_, result) = guess(&client, &url, &id, "crane").await;
+    assert_eq!("a large wading bird", result["definition"]);
+    let game: Value = client
+        .get(format!("{}/games/{}", url, id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!("a large wading bird", game["definition"]);
+}
+
+#[tokio::test]
+async fn test_errors() {
+    let url = start().await;
+    let client = Client::new();
+
+    let game: Value = client
+        .post(format!("{}/games", url))
+        .json(&json!({ "hard_mode": true }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = game["id"].as_str().unwrap();
+    assert_eq!(true, game["hard_mode"]);
+
+    let (status, result) = guess(&client, &url, id, "cran").await;
+    assert_eq!(StatusCode::BAD_REQUEST, status);
+    assert_eq!("Guess must have exactly 5 letters, not 4", result["error"]);
+
+    let (status, result) = guess(&client, &url, id, "xyzzy").await;
+    assert_eq!(StatusCode::BAD_REQUEST, status);
+    assert_eq!("Not in word list", result["error"]);
+
+    // hard mode: the R and A from TRACE must be used again
+    guess(&client, &url, id, "trace").await;
+    let (status, _) = guess(&client, &url, id, "slate").await;
+    assert_eq!(StatusCode::BAD_REQUEST, status);
+
+    let response = client
+        .post(format!("{}/games/{}/guesses", url, id))
+        .json(&json!({ "guess": "crane" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let (status, result) = guess(&client, &url, "no-such-game", "crane").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!("No such game; it may have expired", result["error"]);
+    let response = client
+        .get(format!("{}/games/no-such-game", url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn test_delete() {
+    let url = start().await;
+    let client = Client::new();
+    let (_, game) = create(&client, &url).await;
+    let game_url = format!("{}/games/{}", url, game["id"].as_str().unwrap());
+
+    let response = client.delete(&game_url).send().await.unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    let response = client.get(&game_url).send().await.unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    let response = client.delete(&game_url).send().await.unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn test_ids() {
+    let url = start().await;
+    let client = Client::new();
+    let (_, first) = create(&client, &url).await;
+    let (_, second) = create(&client, &url).await;
+    let (first, second) = (
+        first["id"].as_str().unwrap(),
+        second["id"].as_str().unwrap(),
+    );
+    assert_ne!(first, second);
+    assert!(
+        first.len() >= 32,
+        "id is too short to be unguessable: {}",
+        first
+    );
+}
+
+#[tokio::test]
+async fn test_games_per_client() {
+    let url = start_with(Limits {
+        max_games_per_client: 2,
+        ..Limits::default()
+    })
+    .await;
+    let client = Client::new();
+
+    let (_, game) = create(&client, &url).await;
+    create(&client, &url).await;
+    let (status, result) = create(&client, &url).await;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, status);
+    assert!(result["error"].as_str().unwrap().contains("limit is 2"));
+
+    // abandoning a game makes room for another
+    client
+        .delete(format!("{}/games/{}", url, game["id"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    let (status, _) = create(&client, &url).await;
+    assert_eq!(StatusCode::CREATED, status);
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let url = start_with(Limits {
+        rate_limit: Some(RateLimit {
+            requests: 3,
+            per: Duration::from_secs(1),
+        }),
+        ..Limits::default()
+    })
+    .await;
+    let client = Client::new();
+
+    // starting games and guessing share the allowance
+    let (status, game) = create(&client, &url).await;
+    assert_eq!(StatusCode::CREATED, status);
+    let id = game["id"].as_str().unwrap().to_owned();
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "slate").await.0);
+    assert_eq!(StatusCode::CREATED, create(&client, &url).await.0);
+
+    let response = client.post(format!("{}/games", url)).send().await.unwrap();
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    let retry_after: u64 = response.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(1, retry_after);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Too many requests"));
+    assert_eq!(
+        StatusCode::TOO_MANY_REQUESTS,
+        guess(&client, &url, &id, "trace").await.0
+    );
+
+    // looking at a game isn't limited
+    let response = client
+        .get(format!("{}/games/{}", url, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let metrics = client
+        .get(format!("{}/metrics", url))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    for line in [
+        r#"wordle_rate_limited_total{route="/games"} 1"#,
+        r#"wordle_rate_limited_total{route="/games/{id}/guesses"} 1"#,
+        "wordle_rate_limited_clients 1",
+    ] {
+        assert!(
+            metrics.lines().any(|l| l == line),
+            "{} not in:\n{}",
+            line,
+            metrics
+        );
+    }
+
+    // waiting as long as it's told lets the client back in
+    tokio::time::sleep(Duration::from_secs(retry_after)).await;
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "trace").await.0);
+}
+
+#[tokio::test]
+async fn test_concurrent_guesses() {
+    let url = start().await;
+    let client = Client::new();
+    let (_, game) = create(&client, &url).await;
+    let id = game["id"].as_str().unwrap().to_owned();
+
+    // every guess is played exactly once, however they're interleaved
+    let guesses: Vec<_> = (0..10)
+        .map(|_| {
+            let (client, url, id) = (client.clone(), url.clone(), id.clone());
+            tokio::spawn(async move { guess(&client, &url, &id, "slate").await })
+        })
+        .collect();
+    let mut turns = Vec::new();
+    let mut rejected = 0;
+    for guess in guesses {
+        match guess.await.unwrap() {
+            (StatusCode::OK, result) => turns.push(result["turn"].as_u64().unwrap()),
+            (StatusCode::CONFLICT, _) => rejected += 1,
+            (status, result) => panic!("unexpected response {}: {}", status, result),
+        }
+    }
+    turns.sort_unstable();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6], turns);
+    assert_eq!(4, rejected);
+}
+
+#[tokio::test]
+async fn test_health_checks() {
+    let url = start().await;
+    let client = Client::new();
+    for (path, body) in [("/healthz", "ok"), ("/readyz", "ready")] {
+        let response = client.get(format!("{}{}", url, path)).send().await.unwrap();
+        assert_eq!(StatusCode::OK, response.status(), "{}", path);
+        assert_eq!(body, response.text().await.unwrap());
+    }
+}
@@ -0,0 +1,188 @@
+//! Tests for API keys
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Utc;
+use reqwest::{header, Client, RequestBuilder, StatusCode};
+use serde_json::{json, Value};
+use wordle::{transcript::Transcript, Wordle};
+use wordle_server::{
+    auth::{ApiKey, Required},
+    store::{GameStore, MemoryStore},
+    Server,
+};
+
+use common::{serve, ANSWERS, GUESSES};
+
+/// Start a server needing keys for `required`, with a key for alice and a
+/// revoked one for bob in its store, and get back its base URL and their keys
+async fn start(required: Required) -> (String, String, String) {
+    let store = Arc::new(MemoryStore::new());
+    let (alice, key) = ApiKey::issue("alice");
+    store.add_key(key).unwrap();
+    let (bob, key) = ApiKey::issue("bob");
+    store.add_key(key).unwrap();
+    store.revoke_key("bob", SystemTime::now()).unwrap();
+
+    let server = Server::new(&GUESSES, &ANSWERS, 5)
+        .unwrap()
+        .with_store(store)
+        .with_keys(Vec::new(), required);
+    (serve(server).await, alice, bob)
+}
+
+/// A genuine transcript of today's puzzle
+fn played() -> Transcript {
+    let (mut game, puzzle) = Wordle::new(&GUESSES, &ANSWERS)
+        .choose_daily(Utc::now().date_naive())
+        .unwrap();
+    game.guess("crane").unwrap();
+    Transcript {
+        puzzle: Some(puzzle),
+        elapsed: Duration::from_secs(30),
+        ..game.transcript().hide_answer()
+    }
+}
+
+/// Send a request, and get back the response status and body
+async fn send(request: RequestBuilder) -> (StatusCode, Value) {
+    let response = request.send().await.unwrap();
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        assert_eq!("Bearer", response.headers()[header::WWW_AUTHENTICATE]);
+    }
+    (status, response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_required_for_results() {
+    let required = Required {
+        results: true,
+        ..Required::default()
+    };
+    let (url, alice, bob) = start(required).await;
+    let client = Client::new();
+    let submit = || {
+        client
+            .post(format!("{}/daily/result", url))
+            .json(&json!({ "transcript": played() }))
+    };
+
+    // missing
+    let (status, body) = send(submit()).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    assert!(
+        body["error"].as_str().unwrap().contains("API key"),
+        "{}",
+        body
+    );
+
+    // invalid
+    let (status, _) = send(submit().bearer_auth("wk_0123456789abcdef")).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    let (status, _) = send(submit().header(header::AUTHORIZATION, alice.as_str())).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+
+    // revoked
+    let (status, body) = send(submit().bearer_auth(&bob)).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    assert_eq!("The API key was revoked", body["error"]);
+
+    // a good key can't put its result down to someone else
+    let (status, body) = send(
+        client
+            .post(format!("{}/daily/result", url))
+            .bearer_auth(&alice)
+            .json(&json!({ "player": "mallory", "transcript": played() })),
+    )
+    .await;
+    assert_eq!(StatusCode::FORBIDDEN, status);
+    assert_eq!("wrong_player", body["code"]);
+
+    // and its result is put down to whoever it was issued to
+    let (status, body) = send(submit().bearer_auth(&alice)).await;
+    assert_eq!(StatusCode::CREATED, status);
+    assert_eq!("alice", body["player"]);
+
+    // anyone can still play
+    let (status, _) = send(client.post(format!("{}/games", url))).await;
+    assert_eq!(StatusCode::CREATED, status);
+}
+
+#[tokio::test]
+async fn test_required_for_games() {
+    let required = Required {
+        games: true,
+        ..Required::default()
+    };
+    let (url, alice, bob) = start(required).await;
+    let client = Client::new();
+    let create = || client.post(format!("{}/games", url));
+
+    let (status, _) = send(create()).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    let (status, _) = send(create().bearer_auth("nope")).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    let (status, _) = send(create().bearer_auth(&bob)).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    let (status, _) = send(create().bearer_auth(&alice)).await;
+    assert_eq!(StatusCode::CREATED, status);
+
+    // the rest of the API stays open
+    let (status, _) = send(
+        client
+            .post(format!("{}/daily/result", url))
+            .json(&json!({ "player": "carol", "transcript": played() })),
+    )
+    .await;
+    assert_eq!(StatusCode::CREATED, status);
+}
+
+#[tokio::test]
+async fn test_not_required() {
+    let (url, alice, bob) = start(Required::default()).await;
+    let client = Client::new();
+
+    let (status, _) = send(client.post(format!("{}/games", url))).await;
+    assert_eq!(StatusCode::CREATED, status);
+    let (status, _) = send(client.post(format!("{}/games", url)).bearer_auth(&alice)).await;
+    assert_eq!(StatusCode::CREATED, status);
+    // a key that's given is still checked, even where none is needed
+    let (status, _) = send(client.post(format!("{}/games", url)).bearer_auth(&bob)).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+
+    // a result without a key can't be put down to someone who has one
+    let submit = |player: &str| {
+        client
+            .post(format!("{}/daily/result", url))
+            .json(&json!({ "player": player, "transcript": played() }))
+    };
+    for player in ["alice", " alice ", "bob"] {
+        let (status, body) = send(submit(player)).await;
+        assert_eq!(StatusCode::FORBIDDEN, status);
+        assert_eq!("reserved_name", body["code"]);
+    }
+    let (status, body) = send(submit("carol")).await;
+    assert_eq!(StatusCode::CREATED, status);
+    assert_eq!("carol", body["player"]);
+}
@@ -0,0 +1,75 @@
+//! Word lists and requests shared by the end-to-end tests of the REST API
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// each test file uses only some of these
+#![allow(dead_code)]
+
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use wordle_server::Server;
+
+pub const GUESSES: [&str; 5] = ["crane", "slate", "trace", "irate", "stare"];
+/// Only one answer, so every game, and every day's puzzle, is against the same word
+pub const ANSWERS: [&str; 1] = ["crane"];
+
+/// Start a server on a free port, and get back its base URL
+pub async fn start() -> String {
+    serve(Server::new(&GUESSES, &ANSWERS, 5).unwrap()).await
+}
+
+/// Serve `server` on a free port, and get back its base URL
+pub async fn serve(server: Server) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(wordle_server::serve(listener, server));
+    format!("http://{}", addr)
+}
+
+/// Create a game, and get back the response status and body
+pub async fn create(client: &Client, url: &str) -> (StatusCode, Value) {
+    let response = client.post(format!("{}/games", url)).send().await.unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+/// Create a game, and get back its id
+pub async fn new_game(client: &Client, url: &str) -> String {
+    let (_, game) = create(client, url).await;
+    game["id"].as_str().unwrap().to_owned()
+}
+
+/// Create a game with the given options, e.g. `{ "hard_mode": true }`, and
+/// get back the response status and body
+pub async fn create_with(client: &Client, url: &str, options: Value) -> (StatusCode, Value) {
+    let response = client
+        .post(format!("{}/games", url))
+        .json(&options)
+        .send()
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+/// Make a guess, and get back the response status and body
+pub async fn guess(client: &Client, url: &str, id: &str, word: &str) -> (StatusCode, Value) {
+    let response = client
+        .post(format!("{}/games/{}/guesses", url, id))
+        .json(&json!({ "word": word }))
+        .send()
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
@@ -0,0 +1,337 @@
+//! End-to-end tests of the daily leaderboard, over HTTP
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use wordle::{transcript::Transcript, GameStatus, LetterStatus, Wordle};
+use wordle_server::Server;
+
+use common::{serve, start, ANSWERS, GUESSES};
+
+/// A genuine transcript of today's puzzle, guessing `words`, that took `secs`
+fn played(words: &[&str], secs: u64) -> Transcript {
+    let (mut game, puzzle) = Wordle::new(&GUESSES, &ANSWERS)
+        .choose_daily(Utc::now().date_naive())
+        .unwrap();
+    for word in words {
+        game.guess(word).unwrap();
+    }
+    Transcript {
+        puzzle: Some(puzzle),
+        elapsed: Duration::from_secs(secs),
+        ..game.transcript().hide_answer()
+    }
+}
+
+/// Submit a result, and get back the response status and body
+async fn submit(client: &Client, url: &str, body: Value) -> (StatusCode, Value) {
+    let response = client
+        .post(format!("{}/daily/result", url))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+/// Look at the leaderboard, and get back the response status and body
+async fn leaderboard(client: &Client, url: &str, query: &str) -> (StatusCode, Value) {
+    let response = client
+        .get(format!("{}/daily/leaderboard{}", url, query))
+        .send()
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_leaderboard() {
+    let url = start().await;
+    let client = Client::new();
+
+    for (player, words, secs) in [
+        ("alice", &["slate", "crane"][..], 30),
+        ("bob", &["crane"][..], 90),
+        ("carol", &["slate", "crane"][..], 20),
+    ] {
+        let body = json!({ "player": player, "transcript": played(words, secs) });
+        let (status, _) = submit(&client, &url, body).await;
+        assert_eq!(StatusCode::CREATED, status);
+    }
+    let (status, result) = submit(
+        &client,
+        &url,
+        json!({ "transcript": played(&["trace", "crane"], 25) }),
+    )
+    .await;
+    assert_eq!(StatusCode::CREATED, status);
+    assert_eq!(4, result["rank"]);
+    let anonymous = result["player"].as_str().unwrap().to_owned();
+    assert!(anonymous.starts_with("anonymous-"));
+
+    let (status, board) = leaderboard(&client, &url, "").await;
+    assert_eq!(StatusCode::OK, status);
+    let today = Utc::now().date_naive();
+    assert_eq!(json!(today), board["date"]);
+    assert_eq!(json!(wordle::daily::puzzle_number(today)), board["puzzle"]);
+    assert_eq!(
+        json!([
+            { "rank": 1, "player": "bob", "guesses": 1, "time_ms": 90_000, "hard_mode": false },
+            { "rank": 2, "player": "alice", "guesses": 2, "time_ms": 30_000, "hard_mode": false },
+            { "rank": 3, "player": "carol", "guesses": 2, "time_ms": 20_000, "hard_mode": false },
+            { "rank": 4, "player": anonymous, "guesses": 2, "time_ms": 25_000, "hard_mode": false },
+        ]),
+        board["results"]
+    );
+
+    let (_, board) = leaderboard(&client, &url, "?limit=2").await;
+    assert_eq!(2, board["results"].as_array().unwrap().len());
+
+    // each day has a leaderboard of its own
+    let (status, board) = leaderboard(&client, &url, "?date=2022-01-01").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(
+        json!({ "date": "2022-01-01", "puzzle": 196, "results": [] }),
+        board
+    );
+
+    let (status, _) = leaderboard(&client, &url, "?date=2021-01-01").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    let (status, _) = leaderboard(&client, &url, "?date=yesterday").await;
+    assert_eq!(StatusCode::BAD_REQUEST, status);
+}
+
+#[tokio::test]
+async fn test_forged_time() {
+    let url = start().await;
+    let client = Client::new();
+
+    let body = json!({ "player": "alice", "transcript": played(&["slate", "crane"], 60) });
+    assert_eq!(StatusCode::CREATED, submit(&client, &url, body).await.0);
+
+    // claiming the game took no time at all doesn't get ahead of a result sent in first
+    let body = json!({ "player": "mallory", "transcript": played(&["trace", "crane"], 0) });
+    let (status, result) = submit(&client, &url, body).await;
+    assert_eq!(StatusCode::CREATED, status);
+    assert_eq!(2, result["rank"]);
+
+    let (_, board) = leaderboard(&client, &url, "").await;
+    let players: Vec<&str> = board["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["player"].as_str().unwrap())
+        .collect();
+    assert_eq!(vec!["alice", "mallory"], players);
+}
+
+#[tokio::test]
+async fn test_forged_result() {
+    let url = start().await;
+    let client = Client::new();
+
+    // a first-guess win with the wrong word
+    let mut forged = played(&["slate"], 5);
+    forged.guesses[0].feedback = vec![LetterStatus::Correct; 5];
+    forged.status = GameStatus::Won { guesses: 1 };
+    let (status, error) = submit(
+        &client,
+        &url,
+        json!({ "player": "mallory", "transcript": forged }),
+    )
+    .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert_eq!(
+        json!({
+            "error": "The transcript doesn't add up: the feedback for guess 1 ('slate') doesn't match the answer",
+            "code": "forged_feedback",
+        }),
+        error
+    );
+
+    // and with an answer made up to match it
+    forged.answer = Some("slate".to_owned());
+    let (status, error) = submit(
+        &client,
+        &url,
+        json!({ "player": "mallory", "transcript": forged }),
+    )
+    .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert_eq!("wrong_answer", error["code"]);
+
+    // a word the server doesn't accept
+    let mut illegal = played(&["slate", "crane"], 5);
+    illegal.guesses[0].word = "sla7e".to_owned();
+    let (status, error) = submit(
+        &client,
+        &url,
+        json!({ "player": "mallory", "transcript": illegal }),
+    )
+    .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert_eq!("illegal_guess", error["code"]);
+
+    // an old puzzle, won or not
+    let old = Transcript {
+        puzzle: Some(196),
+        ..played(&["crane"], 5)
+    };
+    let (status, error) = submit(
+        &client,
+        &url,
+        json!({ "player": "mallory", "transcript": old }),
+    )
+    .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert_eq!("wrong_puzzle", error["code"]);
+
+    // a lost game passed off as a win
+    let lost = Transcript {
+        status: GameStatus::Won { guesses: 1 },
+        ..played(&["slate"], 5)
+    };
+    let (status, error) = submit(
+        &client,
+        &url,
+        json!({ "player": "mallory", "transcript": lost }),
+    )
+    .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert_eq!("status_mismatch", error["code"]);
+
+    let (_, board) = leaderboard(&client, &url, "").await;
+    assert_eq!(json!([]), board["results"]);
+
+    // a genuine result only counts once
+    let genuine = json!({ "player": "mallory", "transcript": played(&["crane"], 5) });
+    let (status, _) = submit(&client, &url, genuine.clone()).await;
+    assert_eq!(StatusCode::CREATED, status);
+    let (status, _) = submit(&client, &url, genuine).await;
+    assert_eq!(StatusCode::CONFLICT, status);
+}
+
+#[tokio::test]
+async fn test_distribution() {
+    let url = start().await;
+    let client = Client::new();
+    for (player, words) in [
+        ("alice", &["slate", "crane"][..]),
+        ("bob", &["crane"][..]),
+        ("carol", &["slate", "trace", "crane"][..]),
+        ("dave", &["trace", "crane"][..]),
+    ] {
+        let body = json!({ "player": player, "transcript": played(words, 30) });
+        submit(&client, &url, body).await;
+    }
+
+    let distribution = |query: &'static str| {
+        let client = client.clone();
+        let url = url.clone();
+        async move {
+            let response = client
+                .get(format!("{}/daily/distribution{}", url, query))
+                .send()
+                .await
+                .unwrap();
+            (response.status(), response.text().await.unwrap())
+        }
+    };
+    let (status, text) = distribution("?width=12").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(
+        concat!(
+            "1: ████    1\n",
+            "2: ███████ 2\n",
+            "3: ████    1\n",
+            "4:         0\n",
+            "5:         0\n",
+            "6:         0\n",
+            "X:         0\n",
+        ),
+        text
+    );
+
+    let (status, text) = distribution("?date=2022-01-01").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(
+        Some("1: ".to_owned() + &" ".repeat(35) + " 0"),
+        text.lines().next().map(str::to_owned)
+    );
+    let (status, _) = distribution("?date=2021-01-01").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    let (status, _) = distribution("?width=wide").await;
+    assert_eq!(StatusCode::BAD_REQUEST, status);
+}
+
+/// Look at today's puzzle on a server with `secret`
+async fn meta(client: &Client, secret: &str) -> Value {
+    // every answer can be picked, so different secrets can pick different ones
+    let server = Server::new(&GUESSES, &GUESSES, 5)
+        .unwrap()
+        .with_daily_secret(secret.as_bytes());
+    let url = serve(server).await;
+    let response = client
+        .get(format!("{}/daily/meta", url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_meta() {
+    let client = Client::new();
+    let today = Utc::now().date_naive();
+    let first = meta(&client, "replicas").await;
+    assert_eq!(json!(today), first["date"]);
+    assert_eq!(
+        json!(wordle::daily::puzzle_number(today).unwrap()),
+        first["puzzle"]
+    );
+    let rollover: chrono::DateTime<Utc> =
+        serde_json::from_value(first["rollover"].clone()).unwrap();
+    assert_eq!(today.succ_opt().unwrap(), rollover.date_naive());
+    assert!(!first.to_string().contains("\"answer\""));
+
+    // replicas sharing a secret agree on the puzzle, without talking to each other
+    assert_eq!(first, meta(&client, "replicas").await);
+
+    // but a server with another secret has another answer
+    let other = meta(&client, "another secret").await;
+    assert_eq!(first["puzzle"], other["puzzle"]);
+    assert_ne!(first["fingerprint"], other["fingerprint"]);
+
+    // without a secret, the answer list is just gone through in order
+    let url = start().await;
+    let plain: Value = client
+        .get(format!("{}/daily/meta", url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(first["puzzle"], plain["puzzle"]);
+    assert_eq!(Value::Null, plain["fingerprint"]);
+}
@@ -0,0 +1,107 @@
+//! End-to-end tests of the metrics, scraped after playing over HTTP
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+
+use common::{create_with, guess, start};
+
+/// Create a game, and get back its id
+async fn create(client: &Client, url: &str, hard_mode: bool, daily: bool) -> String {
+    let options = json!({ "hard_mode": hard_mode, "daily": daily });
+    let (_, game) = create_with(client, url, options).await;
+    game["id"].as_str().unwrap().to_owned()
+}
+
+/// Scrape the metrics
+async fn scrape(client: &Client, url: &str) -> String {
+    let response = client.get(format!("{}/metrics", url)).send().await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    response.text().await.unwrap()
+}
+
+/// The value of a metric, or 0 if it hasn't been set yet
+fn value(metrics: &str, name: &str) -> f64 {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+        .map_or(0.0, |value| value.parse().unwrap())
+}
+
+#[tokio::test]
+async fn test_scripted_game() {
+    let url = start().await;
+    let client = Client::new();
+    let before = scrape(&client, &url).await;
+    let won = r#"wordle_games_won_total{hard="true",mode="endless"}"#;
+    assert_eq!(0.0, value(&before, won));
+
+    // win a game in hard mode, with a typo along the way
+    let id = create(&client, &url, true, false).await;
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "slate").await.0);
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        guess(&client, &url, &id, "zzzzz").await.0
+    );
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "crane").await.0);
+    assert_eq!(
+        StatusCode::CONFLICT,
+        guess(&client, &url, &id, "crane").await.0
+    );
+    // and leave another going
+    create(&client, &url, false, false).await;
+    // then win today's puzzle
+    let id = create(&client, &url, false, true).await;
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "trace").await.0);
+    assert_eq!(StatusCode::OK, guess(&client, &url, &id, "crane").await.0);
+
+    let metrics = scrape(&client, &url).await;
+    let hard = r#"{hard="true",mode="endless"}"#;
+    let easy = r#"{hard="false",mode="endless"}"#;
+    let daily = r#"{hard="false",mode="daily"}"#;
+    for (name, expected) in [
+        (format!("wordle_games_created_total{}", hard), 1.0),
+        (format!("wordle_games_created_total{}", easy), 1.0),
+        (format!("wordle_games_won_total{}", hard), 1.0),
+        (format!("wordle_games_lost_total{}", hard), 0.0),
+        (format!("wordle_guesses_total{}", hard), 2.0),
+        (format!("wordle_invalid_guesses_total{}", hard), 2.0),
+        (format!("wordle_guesses_per_win_count{}", hard), 1.0),
+        (format!("wordle_guesses_per_win_sum{}", hard), 2.0),
+        (format!("wordle_games_created_total{}", daily), 1.0),
+        (format!("wordle_games_won_total{}", daily), 1.0),
+        (format!("wordle_guesses_total{}", daily), 2.0),
+        (format!("wordle_guesses_per_win_sum{}", daily), 2.0),
+        (format!("wordle_games_won_total{}", easy), 0.0),
+        (format!("wordle_guesses_total{}", easy), 0.0),
+        ("wordle_active_games".to_owned(), 1.0),
+    ] {
+        assert_eq!(expected, value(&metrics, &name), "{}", name);
+    }
+
+    let guesses = value(
+        &metrics,
+        r#"wordle_request_duration_seconds_count{method="POST",route="/games/{id}/guesses",status="200"}"#,
+    );
+    assert_eq!(4.0, guesses);
+}
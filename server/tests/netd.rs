@@ -0,0 +1,199 @@
+//! End-to-end tests of `wordle-netd`, over a real socket
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use wordle::LetterStatus::{Correct as G, InWord as Y, NotInWord as N};
+use wordle_server::{
+    line::{Command, ErrorCode, Outcome, Reply},
+    netd::{self, Settings},
+};
+
+use common::{ANSWERS, GUESSES};
+
+async fn start() -> TcpStream {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let settings = Settings::new(&GUESSES, &ANSWERS, 5, false).unwrap();
+    tokio::spawn(netd::serve(listener, settings));
+    TcpStream::connect(addr).await.unwrap()
+}
+
+/// A client connected to the server, reading its replies line by line
+struct Client {
+    reader: BufReader<TcpStream>,
+}
+
+impl Client {
+    async fn connect() -> Self {
+        let mut client = Self {
+            reader: BufReader::new(start().await),
+        };
+        assert_eq!(Some(Reply::Hello), client.reply().await);
+        client
+    }
+
+    async fn reply(&mut self) -> Option<Reply> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line).await.unwrap() {
+            0 => None,
+            _ => Some(line.trim_end().parse().unwrap()),
+        }
+    }
+
+    async fn send(&mut self, command: Command) -> Option<Reply> {
+        let line = format!("{}\n", command);
+        self.reader
+            .get_mut()
+            .write_all(line.as_bytes())
+            .await
+            .unwrap();
+        self.reply().await
+    }
+}
+
+#[tokio::test]
+async fn test_game() {
+    let mut client = Client::connect().await;
+    assert_eq!(
+        Some(Reply::Started { max_guesses: 6 }),
+        client.send(Command::New).await
+    );
+    assert_eq!(
+        Some(Reply::Error(ErrorCode::NotInDictionary)),
+        client.send(Command::Guess("zzzzz".into())).await
+    );
+    assert_eq!(
+        Some(Reply::Guessed {
+            feedback: vec![N, N, G, N, G],
+            turn: 1,
+            max_guesses: 6,
+            outcome: None,
+        }),
+        client.send(Command::Guess("slate".into())).await
+    );
+    assert_eq!(
+        Some(Reply::Guessed {
+            feedback: vec![N, G, G, Y, G],
+            turn: 2,
+            max_guesses: 6,
+            outcome: None,
+        }),
+        client.send(Command::Guess("trace".into())).await
+    );
+    assert_eq!(
+        Some(Reply::Guessed {
+            feedback: vec![G; 5],
+            turn: 3,
+            max_guesses: 6,
+            outcome: Some(Outcome::Won),
+        }),
+        client.send(Command::Guess("crane".into())).await
+    );
+    assert_eq!(
+        Some(Reply::Board {
+            rows: vec![
+                ("slate".into(), vec![N, N, G, N, G]),
+                ("trace".into(), vec![N, G, G, Y, G]),
+                ("crane".into(), vec![G; 5]),
+            ],
+            max_guesses: 6,
+        }),
+        client.send(Command::Board).await
+    );
+    assert_eq!(Some(Reply::Bye), client.send(Command::Quit).await);
+    assert_eq!(None, client.reply().await, "the connection is closed");
+}
+
+#[tokio::test]
+async fn test_lost() {
+    let mut client = Client::connect().await;
+    client.send(Command::New).await;
+    for _ in 0..5 {
+        client.send(Command::Guess("slate".into())).await;
+    }
+    // repeated guesses are allowed by default, so the game can be lost
+    assert_eq!(
+        Some(Reply::Guessed {
+            feedback: vec![N, N, G, N, G],
+            turn: 6,
+            max_guesses: 6,
+            outcome: Some(Outcome::Lost {
+                answer: "crane".into()
+            }),
+        }),
+        client.send(Command::Guess("slate".into())).await
+    );
+}
+
+#[tokio::test]
+async fn test_raw_lines() {
+    // the protocol is plain enough to speak by hand
+    let mut stream = BufReader::new(start().await);
+    stream
+        .get_mut()
+        .write_all(b"new\r\nguess crane\r\nfly away\r\n")
+        .await
+        .unwrap();
+    let mut lines = Vec::new();
+    for _ in 0..4 {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.unwrap();
+        lines.push(line);
+    }
+    assert_eq!(
+        vec![
+            "OK wordle-netd\n",
+            "OK new 0/6\n",
+            "OK ggggg 1/6 won\n",
+            "ERR unknown-command\n"
+        ],
+        lines
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent() {
+    // each connection has its own game
+    let mut first = Client::connect().await;
+    let mut second = Client::connect().await;
+    first.send(Command::New).await;
+    first.send(Command::Guess("slate".into())).await;
+    assert_eq!(
+        Some(Reply::Error(ErrorCode::NoGame)),
+        second.send(Command::Board).await
+    );
+    second.send(Command::New).await;
+    assert_eq!(
+        Some(Reply::Board {
+            rows: Vec::new(),
+            max_guesses: 6,
+        }),
+        second.send(Command::Board).await
+    );
+    assert_eq!(
+        Some(Reply::Board {
+            rows: vec![("slate".into(), vec![N, N, G, N, G])],
+            max_guesses: 6,
+        }),
+        first.send(Command::Board).await
+    );
+}
@@ -0,0 +1,229 @@
+//! End-to-end tests of pausing and resuming games, and of games expiring, with
+//! games kept in memory and in a SQLite database
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{fs, path::Path, sync::Arc, time::Duration};
+
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use wordle_server::{
+    sqlite::SqliteStore,
+    state::Limits,
+    store::{GameStore, MemoryStore},
+    Server,
+};
+
+use common::{guess, new_game, serve, ANSWERS, GUESSES};
+
+/// Open a database for a test, removing any left over from before
+fn open_db(name: &str) -> Arc<SqliteStore> {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.db", name));
+    let _ = fs::remove_file(&path);
+    Arc::new(SqliteStore::open(&path, &GUESSES, &ANSWERS, 5).unwrap())
+}
+
+/// Start a server keeping games in `store`, and get back its base URL along
+/// with the server, to expire games with
+async fn start(store: Arc<dyn GameStore>, limits: Limits) -> (String, Server) {
+    let server = Server::new(&GUESSES, &ANSWERS, 5)
+        .unwrap()
+        .with_store(store)
+        .with_limits(limits);
+    (serve(server.clone()).await, server)
+}
+
+/// Send a request about a game with no body, e.g. `POST .../pause`, and get
+/// back the response status and body
+async fn send(client: &Client, method: &str, url: &str, path: &str) -> (StatusCode, Value) {
+    let response = client
+        .request(method.parse().unwrap(), format!("{}{}", url, path))
+        .send()
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+/// Pause a game, resume it, and finish it, checking what each step gives back
+async fn pause_and_resume(store: Arc<dyn GameStore>) {
+    let limits = Limits {
+        ttl: Duration::from_secs(60),
+        paused_ttl: Duration::from_secs(600),
+        ..Limits::default()
+    };
+    let (url, _) = start(store, limits).await;
+    let client = Client::new();
+    let id = new_game(&client, &url).await;
+
+    let (status, game) = send(&client, "GET", &url, &format!("/games/{}", id)).await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(false, game["paused"]);
+    assert_eq!(60, game["ttl_secs"]);
+
+    // a paused game is kept for longer, and pausing it again changes nothing
+    for _ in 0..2 {
+        let (status, game) = send(&client, "POST", &url, &format!("/games/{}/pause", id)).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(true, game["paused"]);
+        assert_eq!(600, game["ttl_secs"]);
+    }
+    let (_, game) = send(&client, "GET", &url, &format!("/games/{}", id)).await;
+    assert_eq!(true, game["paused"]);
+
+    let (status, game) = send(&client, "POST", &url, &format!("/games/{}/resume", id)).await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(false, game["paused"]);
+    assert_eq!(60, game["ttl_secs"]);
+
+    // a guess starts a paused game's clock again
+    send(&client, "POST", &url, &format!("/games/{}/pause", id)).await;
+    let (status, _) = guess(&client, &url, &id, "crane").await;
+    assert_eq!(StatusCode::OK, status);
+    let (_, game) = send(&client, "GET", &url, &format!("/games/{}", id)).await;
+    assert_eq!(false, game["paused"]);
+    assert_eq!("won", game["status"]);
+
+    // and once it's over, there's no clock to pause
+    let (status, error) = send(&client, "POST", &url, &format!("/games/{}/pause", id)).await;
+    assert_eq!(StatusCode::CONFLICT, status);
+    assert!(error["error"].as_str().unwrap().contains("over"));
+}
+
+/// Expire games with `limits`, one paused and one not, and get back which
+/// are left, by whether they were paused
+async fn expire(store: Arc<dyn GameStore>, limits: Limits) -> (bool, bool) {
+    let (url, server) = start(store, limits).await;
+    let client = Client::new();
+    let active = new_game(&client, &url).await;
+    let paused = new_game(&client, &url).await;
+    send(&client, "POST", &url, &format!("/games/{}/pause", paused)).await;
+
+    // give the games time to go past a TTL of zero
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    server.expire().unwrap();
+    let kept = |id: String| {
+        let client = client.clone();
+        let url = url.clone();
+        async move {
+            let (status, _) = send(&client, "GET", &url, &format!("/games/{}", id)).await;
+            status == StatusCode::OK
+        }
+    };
+    let active_kept = kept(active.clone()).await;
+    let paused_kept = kept(paused.clone()).await;
+
+    // resuming a game that expired says so, rather than just not finding it
+    for (id, kept) in [(active, active_kept), (paused, paused_kept)] {
+        if kept {
+            continue;
+        }
+        let (status, error) = send(&client, "POST", &url, &format!("/games/{}/resume", id)).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+        assert_eq!("game_expired", error["code"]);
+    }
+    (active_kept, paused_kept)
+}
+
+/// Games being played expire before paused ones, and paused ones expire too
+async fn expiry(store: impl Fn() -> Arc<dyn GameStore>) {
+    let active_expires = Limits {
+        ttl: Duration::ZERO,
+        ..Limits::default()
+    };
+    assert_eq!((false, true), expire(store(), active_expires).await);
+    let paused_expires = Limits {
+        paused_ttl: Duration::ZERO,
+        ..Limits::default()
+    };
+    assert_eq!((true, false), expire(store(), paused_expires).await);
+}
+
+#[tokio::test]
+async fn test_pause_and_resume() {
+    pause_and_resume(Arc::new(MemoryStore::new())).await;
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_sqlite() {
+    pause_and_resume(open_db("pause")).await;
+}
+
+#[tokio::test]
+async fn test_expiry() {
+    expiry(|| Arc::new(MemoryStore::new())).await;
+}
+
+#[tokio::test]
+async fn test_expiry_sqlite() {
+    expiry(|| open_db("expiry")).await;
+}
+
+#[tokio::test]
+async fn test_resume_unknown() {
+    let (url, _) = start(Arc::new(MemoryStore::new()), Limits::default()).await;
+    let client = Client::new();
+    let (status, error) = send(&client, "POST", &url, "/games/nope/resume").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!("game_expired", error["code"]);
+    // looking at a game that isn't there is a plain 404
+    let (status, error) = send(&client, "GET", &url, "/games/nope").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(Value::Null, error["code"]);
+}
+
+#[tokio::test]
+async fn test_activity_refreshes_ttl() {
+    let limits = Limits {
+        ttl: Duration::from_millis(500),
+        ..Limits::default()
+    };
+    let (url, server) = start(Arc::new(MemoryStore::new()), limits).await;
+    let client = Client::new();
+    let id = new_game(&client, &url).await;
+
+    // looked at more often than the TTL, the game is kept for longer than it
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        send(&client, "GET", &url, &format!("/games/{}", id)).await;
+        assert_eq!(Ok(0), server.expire());
+    }
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(Ok(1), server.expire());
+}
+
+#[tokio::test]
+async fn test_paused_across_restart() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("paused-restart.db");
+    let _ = fs::remove_file(&path);
+    let store = Arc::new(SqliteStore::open(&path, &GUESSES, &ANSWERS, 5).unwrap());
+    let (url, _) = start(store, Limits::default()).await;
+    let client = Client::new();
+    let id = new_game(&client, &url).await;
+    send(&client, "POST", &url, &format!("/games/{}/pause", id)).await;
+
+    // a new server picks the game up from the database still paused
+    let store = Arc::new(SqliteStore::open(&path, &GUESSES, &ANSWERS, 5).unwrap());
+    let (url, _) = start(store, Limits::default()).await;
+    let (status, game) = send(&client, "GET", &url, &format!("/games/{}", id)).await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(true, game["paused"]);
+    assert_eq!(
+        Limits::default().paused_ttl.as_secs(),
+        game["ttl_secs"].as_u64().unwrap()
+    );
+}
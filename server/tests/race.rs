@@ -0,0 +1,351 @@
+//! End-to-end tests of racing in rooms, over WebSockets
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{http::StatusCode, Error, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use wordle::protocol::{ClientMessage, ServerMessage};
+use wordle_server::{ratelimit::RateLimit, state::Limits, Server};
+
+use common::{serve, ANSWERS, GUESSES};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Start a server on a free port, and get back its base URL for WebSockets;
+/// there's only one answer, so every race is for the same word
+async fn start() -> String {
+    start_with(Limits::default()).await
+}
+
+/// Start a server with `limits`, apart from the two players a room holds
+async fn start_with(limits: Limits) -> String {
+    let server = Server::new(&GUESSES, &ANSWERS, 5)
+        .unwrap()
+        .with_limits(Limits {
+            max_players_per_room: 2,
+            ..limits
+        });
+    serve(server).await.replacen("http", "ws", 1)
+}
+
+/// Connect to a room
+async fn connect(url: &str, room: &str) -> Socket {
+    let url = format!("{}/ws/race/{}", url, room);
+    tokio_tungstenite::connect_async(url).await.unwrap().0
+}
+
+/// Watch a room
+async fn connect_spectator(url: &str, room: &str) -> Socket {
+    let url = format!("{}/ws/race/{}?spectate=1", url, room);
+    tokio_tungstenite::connect_async(url).await.unwrap().0
+}
+
+/// Send a message to the server
+async fn send(socket: &mut Socket, message: ClientMessage) {
+    let text = serde_json::to_string(&message).unwrap();
+    socket.send(Message::text(text)).await.unwrap();
+}
+
+/// Wait for the next message from the server, as it was sent
+async fn recv_text(socket: &mut Socket) -> String {
+    loop {
+        match socket.next().await.unwrap().unwrap() {
+            Message::Text(text) => return text.to_string(),
+            Message::Close(_) => panic!("the connection closed"),
+            _ => continue,
+        }
+    }
+}
+
+/// Wait for the next message from the server
+async fn recv(socket: &mut Socket) -> ServerMessage {
+    serde_json::from_str(&recv_text(socket).await).unwrap()
+}
+
+/// Join a room as `name`, and get back the socket, player number, and token
+async fn join(url: &str, room: &str, name: &str) -> (Socket, usize, String) {
+    let mut socket = connect(url, room).await;
+    send(
+        &mut socket,
+        ClientMessage::Join {
+            name: name.to_owned(),
+            token: None,
+        },
+    )
+    .await;
+    match recv(&mut socket).await {
+        ServerMessage::Welcome { player, token, .. } => (socket, player, token),
+        message => panic!("unexpected message {:?}", message),
+    }
+}
+
+fn guess(word: &str) -> ClientMessage {
+    ClientMessage::Guess {
+        word: word.to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn test_race() {
+    let url = start().await;
+    let (mut alice, first, _) = join(&url, "lobby", "alice").await;
+    let (mut bob, second, _) = join(&url, "lobby", "bob").await;
+    assert_eq!((0, 1), (first, second));
+    assert_eq!(
+        ServerMessage::Joined {
+            player: 1,
+            name: "bob".to_owned()
+        },
+        recv(&mut alice).await
+    );
+
+    // alice sees her own letters...
+    send(&mut alice, guess("slate")).await;
+    match recv(&mut alice).await {
+        ServerMessage::Scored(scored) => {
+            assert_eq!(("slate", 1), (scored.word.as_str(), scored.turn));
+        }
+        message => panic!("unexpected message {:?}", message),
+    }
+    // ...but bob only sees the pattern
+    let progress = recv_text(&mut bob).await;
+    assert!(!progress.contains("slate"), "{}", progress);
+    assert_eq!(
+        ServerMessage::Progress {
+            player: 0,
+            pattern: "⬛⬛🟩⬛🟩".to_owned(),
+            turn: 1
+        },
+        serde_json::from_str(&progress).unwrap()
+    );
+
+    // an invalid guess only goes back to the player who made it
+    send(&mut bob, guess("zzzzz")).await;
+    assert!(matches!(recv(&mut bob).await, ServerMessage::Error { .. }));
+
+    // the first to find the word wins, and everyone hears about it
+    send(&mut bob, guess("crane")).await;
+    assert!(matches!(recv(&mut bob).await, ServerMessage::Scored(_)));
+    assert!(matches!(
+        recv(&mut alice).await,
+        ServerMessage::Progress { player: 1, .. }
+    ));
+    for socket in [&mut alice, &mut bob] {
+        let finished: Value = serde_json::from_str(&recv_text(socket).await).unwrap();
+        assert_eq!("finished", finished["type"]);
+        assert_eq!(1, finished["winner"]);
+        assert_eq!("crane", finished["answer"]);
+        assert_eq!("bob", finished["standings"][0]["name"]);
+        assert_eq!("alice", finished["standings"][1]["name"]);
+    }
+
+    send(&mut alice, guess("crane")).await;
+    assert_eq!(
+        ServerMessage::Error {
+            message: "The race in this room is over".to_owned()
+        },
+        recv(&mut alice).await
+    );
+}
+
+#[tokio::test]
+async fn test_reconnect() {
+    let url = start().await;
+    let (mut alice, _, token) = join(&url, "reconnect", "alice").await;
+    let (mut bob, _, _) = join(&url, "reconnect", "bob").await;
+    recv(&mut alice).await;
+    send(&mut alice, guess("slate")).await;
+    recv(&mut alice).await;
+    recv(&mut bob).await;
+
+    alice.close(None).await.unwrap();
+    assert_eq!(ServerMessage::Left { player: 0 }, recv(&mut bob).await);
+
+    // the room is full, so only alice can take her place back
+    let mut carol = connect(&url, "reconnect").await;
+    send(
+        &mut carol,
+        ClientMessage::Join {
+            name: "carol".to_owned(),
+            token: None,
+        },
+    )
+    .await;
+    assert_eq!(
+        ServerMessage::Error {
+            message: "The room is full (the limit is 2)".to_owned()
+        },
+        recv(&mut carol).await
+    );
+
+    let mut alice = connect(&url, "reconnect").await;
+    send(
+        &mut alice,
+        ClientMessage::Join {
+            name: String::new(),
+            token: Some(token),
+        },
+    )
+    .await;
+    match recv(&mut alice).await {
+        ServerMessage::Welcome {
+            player,
+            guesses,
+            players,
+            ..
+        } => {
+            assert_eq!(0, player);
+            assert_eq!(1, guesses.len());
+            assert_eq!("slate", guesses[0].word);
+            assert_eq!(2, players.len());
+        }
+        message => panic!("unexpected message {:?}", message),
+    }
+    assert_eq!(
+        ServerMessage::Joined {
+            player: 0,
+            name: "alice".to_owned()
+        },
+        recv(&mut bob).await
+    );
+
+    // her next guess carries on from where she left off
+    send(&mut alice, guess("trace")).await;
+    match recv(&mut alice).await {
+        ServerMessage::Scored(scored) => assert_eq!(2, scored.turn),
+        message => panic!("unexpected message {:?}", message),
+    }
+}
+
+#[tokio::test]
+async fn test_spectator() {
+    let url = start().await;
+    let mut nobody = connect_spectator(&url, "watched").await;
+    assert_eq!(
+        ServerMessage::Error {
+            message: "No such room".to_owned()
+        },
+        recv(&mut nobody).await
+    );
+
+    let (mut alice, _, _) = join(&url, "watched", "alice").await;
+    send(&mut alice, guess("slate")).await;
+    recv(&mut alice).await;
+
+    // a late spectator catches up on the boards so far
+    let mut spectator = connect_spectator(&url, "watched").await;
+    match recv(&mut spectator).await {
+        ServerMessage::Spectating { players, .. } => {
+            assert_eq!(1, players.len());
+            assert_eq!(vec!["⬛⬛🟩⬛🟩".to_owned()], players[0].patterns);
+        }
+        message => panic!("unexpected message {:?}", message),
+    }
+
+    let (mut bob, _, _) = join(&url, "watched", "bob").await;
+    assert_eq!(
+        ServerMessage::Joined {
+            player: 1,
+            name: "bob".to_owned()
+        },
+        recv(&mut spectator).await
+    );
+
+    // spectators can only watch
+    send(&mut spectator, guess("crane")).await;
+    assert_eq!(
+        ServerMessage::Error {
+            message: "Spectators can't send messages".to_owned()
+        },
+        recv(&mut spectator).await
+    );
+
+    // and they see every guess, but not its letters
+    send(&mut bob, guess("trace")).await;
+    let progress = recv_text(&mut spectator).await;
+    assert!(!progress.contains("trace"), "{}", progress);
+    assert!(matches!(
+        serde_json::from_str(&progress).unwrap(),
+        ServerMessage::Progress {
+            player: 1,
+            turn: 1,
+            ..
+        }
+    ));
+
+    bob.close(None).await.unwrap();
+    assert_eq!(
+        ServerMessage::Left { player: 1 },
+        recv(&mut spectator).await
+    );
+
+    // until the race is over, when the answer is given away
+    send(&mut alice, guess("crane")).await;
+    assert!(matches!(
+        recv(&mut spectator).await,
+        ServerMessage::Progress { player: 0, .. }
+    ));
+    match recv(&mut spectator).await {
+        ServerMessage::Finished { winner, answer, .. } => {
+            assert_eq!((Some(0), "crane"), (winner, answer.as_str()));
+        }
+        message => panic!("unexpected message {:?}", message),
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let url = start_with(Limits {
+        rate_limit: Some(RateLimit {
+            requests: 3,
+            per: Duration::from_secs(60),
+        }),
+        ..Limits::default()
+    })
+    .await;
+
+    // connecting and each guess share the allowance
+    let (mut alice, _, _) = join(&url, "lobby", "alice").await;
+    for word in ["slate", "trace"] {
+        send(&mut alice, guess(word)).await;
+        assert!(matches!(recv(&mut alice).await, ServerMessage::Scored(_)));
+    }
+    send(&mut alice, guess("irate")).await;
+    match recv(&mut alice).await {
+        ServerMessage::Error { message } => {
+            assert!(message.starts_with("Too many requests"), "{}", message);
+        }
+        message => panic!("unexpected message {:?}", message),
+    }
+
+    // and once it's used up, the client can't connect again either
+    let url = format!("{}/ws/race/lobby", url);
+    match tokio_tungstenite::connect_async(url).await {
+        Err(Error::Http(response)) => {
+            assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+        }
+        result => panic!("unexpected result {:?}", result.map(|_| ())),
+    }
+}
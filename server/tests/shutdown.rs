@@ -0,0 +1,114 @@
+//! End-to-end tests of shutting the server down cleanly
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(unix)]
+
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use reqwest::Client;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Write the word lists for the server, and get back their paths
+fn word_lists() -> (PathBuf, PathBuf) {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("shutdown");
+    fs::create_dir_all(&dir).unwrap();
+    let (guesses, answers) = (dir.join("guesses.txt"), dir.join("answers.txt"));
+    fs::write(&guesses, "crane\nslate\ntrace\n").unwrap();
+    fs::write(&answers, "crane\n").unwrap();
+    (guesses, answers)
+}
+
+#[tokio::test]
+async fn test_sigterm_mid_request() {
+    let (guesses, answers) = word_lists();
+    let mut server = Command::new(env!("CARGO_BIN_EXE_wordle-server"))
+        .args(["--listen", "127.0.0.1:0", "--guesses"])
+        .arg(&guesses)
+        .arg("--answers")
+        .arg(&answers)
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = BufReader::new(server.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    let addr = line
+        .trim()
+        .strip_prefix("Listening on ")
+        .unwrap_or_else(|| panic!("unexpected output: {}", line))
+        .to_owned();
+
+    let game: Value = Client::new()
+        .post(format!("http://{}/games", addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = game["id"].as_str().unwrap();
+
+    // start a guess, but hold back the end of its body until after the signal
+    let body = r#"{"word": "slate"}"#;
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let head = format!(
+        "POST /games/{}/guesses HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+        id,
+        addr,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await.unwrap();
+    stream.write_all(&body.as_bytes()[..5]).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let killed = Command::new("kill")
+        .args(["-TERM", &server.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(killed.success());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // no new connections are accepted...
+    assert!(TcpStream::connect(&addr).await.is_err());
+
+    // ...but the guess in progress is still answered
+    stream.write_all(&body.as_bytes()[5..]).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+    assert!(response.contains(r#""turn":1"#), "{}", response);
+
+    let status = server.wait().unwrap();
+    assert!(status.success(), "{}", status);
+    let rest: Vec<String> = stderr.lines().map(Result::unwrap).collect();
+    assert_eq!(
+        Some("Stopped"),
+        rest.last().map(String::as_str),
+        "{:?}",
+        rest
+    );
+}
@@ -0,0 +1,73 @@
+//! End-to-end tests of keeping games in a SQLite database across restarts
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{fs, path::Path, sync::Arc};
+
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use wordle_server::{sqlite::SqliteStore, Server};
+
+use common::{create_with, guess, serve, ANSWERS, GUESSES};
+
+/// Start a server keeping games in the database at `path`, and get back its
+/// base URL along with the store
+async fn start(path: &Path) -> (String, Arc<SqliteStore>) {
+    let store = Arc::new(SqliteStore::open(path, &GUESSES, &ANSWERS, 5).unwrap());
+    let server = Server::new(&GUESSES, &ANSWERS, 5)
+        .unwrap()
+        .with_store(store.clone());
+    (serve(server).await, store)
+}
+
+#[tokio::test]
+async fn test_restart_mid_game() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("restart.db");
+    let _ = fs::remove_file(&path);
+    let client = Client::new();
+
+    let (url, _) = start(&path).await;
+    let (_, game) = create_with(&client, &url, json!({ "hard_mode": true })).await;
+    let id = game["id"].as_str().unwrap().to_owned();
+    let (status, _) = guess(&client, &url, &id, "slate").await;
+    assert_eq!(StatusCode::OK, status);
+
+    // a new server, with a store of its own, picks the game up from the database
+    let (url, store) = start(&path).await;
+    let game: Value = client
+        .get(format!("{}/games/{}", url, id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!("slate", game["guesses"][0]["word"]);
+    assert_eq!(true, game["hard_mode"]);
+    assert_eq!("in_progress", game["status"]);
+
+    let (status, result) = guess(&client, &url, &id, "crane").await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(2, result["turn"]);
+    assert_eq!("won", result["status"]);
+
+    let summaries = store.summaries().unwrap();
+    assert_eq!(1, summaries.len());
+    assert_eq!(Some(2), summaries[0].guesses);
+    assert!(summaries[0].hard_mode);
+}
@@ -0,0 +1,146 @@
+//! The letters words can be made of, and how they're laid out on a keyboard
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+
+use crate::keyboard::QWERTY;
+
+/// The letters words can be made of, along with the keyboard to show them on
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Alphabet {
+    /// Every letter in the alphabet
+    letters: BTreeSet<char>,
+    /// The rows of the keyboard, top to bottom; every letter is on one of them
+    rows: Vec<String>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Alphabet {
+    /// Build an alphabet from its keyboard rows
+    fn from_rows(rows: &[&str]) -> Self {
+        Self {
+            letters: rows.iter().flat_map(|row| row.chars()).collect(),
+            rows: rows.iter().map(|row| row.to_string()).collect(),
+        }
+    }
+
+    /// The letters `a` to `z`, on a QWERTY keyboard
+    pub fn english() -> Self {
+        Self::from_rows(&QWERTY)
+    }
+
+    /// The letters `a` to `z` and `ñ`, on a Spanish QWERTY keyboard
+    pub fn spanish() -> Self {
+        Self::from_rows(&["qwertyuiop", "asdfghjklñ", "zxcvbnm"])
+    }
+
+    /// The letters `a` to `z`, the umlauts, and `ß`, on a German QWERTZ keyboard
+    pub fn german() -> Self {
+        Self::from_rows(&["qwertzuiopü", "asdfghjklöä", "yxcvbnmß"])
+    }
+
+    /// The alphabet for a language code like `en` or `de`, if it's a known one
+    pub fn for_language(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "en" => Some(Self::english()),
+            "es" => Some(Self::spanish()),
+            "de" => Some(Self::german()),
+            _ => None,
+        }
+    }
+
+    /// The English alphabet plus any other letters used in `words`, which get
+    /// an extra keyboard row of their own
+    pub fn from_words(words: &[&str]) -> Self {
+        let mut alphabet = Self::english();
+        let extra: BTreeSet<char> = words
+            .iter()
+            .flat_map(|word| word.chars())
+            .filter(|letter| !alphabet.contains(*letter))
+            .collect();
+        if !extra.is_empty() {
+            alphabet.rows.push(extra.iter().collect());
+            alphabet.letters.extend(extra);
+        }
+        alphabet
+    }
+
+    /// Whether a letter is in the alphabet
+    pub fn contains(&self, letter: char) -> bool {
+        self.letters.contains(&letter)
+    }
+
+    /// The first letter of `word` that isn't in the alphabet, if there is one
+    pub fn invalid_letter(&self, word: &str) -> Option<char> {
+        word.chars().find(|letter| !self.contains(*letter))
+    }
+
+    /// Every letter in the alphabet, in order
+    pub fn letters(&self) -> impl Iterator<Item = char> + '_ {
+        self.letters.iter().copied()
+    }
+
+    /// The rows of the keyboard, top to bottom
+    pub fn keyboard(&self) -> &[String] {
+        &self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages() {
+        let english = Alphabet::english();
+        assert_eq!(26, english.letters().count());
+        assert!(english.letters().eq('a'..='z'));
+        assert!(!english.contains('ñ'));
+
+        let spanish = Alphabet::for_language("es").unwrap();
+        assert_eq!(27, spanish.letters().count());
+        assert!(spanish.contains('ñ'));
+        assert_eq!(None, spanish.invalid_letter("niños"));
+
+        let german = Alphabet::for_language("DE").unwrap();
+        assert_eq!(30, german.letters().count());
+        assert_eq!(Some('ñ'), german.invalid_letter("año"));
+        assert_eq!(None, german.invalid_letter("größe"));
+
+        assert_eq!(Some(english), Alphabet::for_language("en"));
+        assert_eq!(None, Alphabet::for_language("xx"));
+    }
+
+    #[test]
+    fn test_from_words() {
+        assert_eq!(
+            Alphabet::english(),
+            Alphabet::from_words(&["crane", "slate"])
+        );
+
+        let alphabet = Alphabet::from_words(&["crème", "façon", "crane"]);
+        assert_eq!(28, alphabet.letters().count());
+        assert_eq!(None, alphabet.invalid_letter("façon"));
+        assert_eq!(4, alphabet.keyboard().len());
+        assert_eq!("çè", alphabet.keyboard()[3]);
+    }
+}
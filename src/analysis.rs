@@ -0,0 +1,178 @@
+//! Statistics for comparing strategies, answer by answer
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The average of some values, or `None` if there aren't any
+pub fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// The result of a [`sign_test`]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SignTest {
+    /// The number of differences above zero
+    pub positive: usize,
+    /// The number of differences below zero
+    pub negative: usize,
+    /// The number of differences of zero, which the test leaves out
+    pub ties: usize,
+    /// The chance of a split at least this uneven if neither sign were more
+    /// likely than the other (two-sided)
+    pub p_value: f64,
+}
+
+/// Test whether paired differences lean one way more than chance would
+/// explain, counting only their signs.
+///
+/// The p-value is exact, from the binomial distribution.
+pub fn sign_test(differences: &[f64]) -> SignTest {
+    let positive = differences.iter().filter(|&&d| d > 0.0).count();
+    let negative = differences.iter().filter(|&&d| d < 0.0).count();
+    let ties = differences.len() - positive - negative;
+
+    // P(X <= k) for X ~ Binomial(n, 1/2), working with logarithms so that
+    // 2^-n doesn't underflow for big answer lists
+    let n = positive + negative;
+    let k = positive.min(negative);
+    let mut ln_p = -(n as f64) * std::f64::consts::LN_2;
+    let mut tail = 0.0;
+    for i in 0..=k {
+        tail += ln_p.exp();
+        ln_p += ((n - i) as f64).ln() - ((i + 1) as f64).ln();
+    }
+
+    SignTest {
+        positive,
+        negative,
+        ties,
+        p_value: (2.0 * tail).min(1.0),
+    }
+}
+
+/// A range of values, from `low` to `high` inclusive
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Interval {
+    /// The lowest value in the range
+    pub low: f64,
+    /// The highest value in the range
+    pub high: f64,
+}
+
+impl Interval {
+    /// Whether `value` is in the range
+    pub fn contains(&self, value: f64) -> bool {
+        (self.low..=self.high).contains(&value)
+    }
+}
+
+/// A bootstrap confidence interval for the mean of paired differences, or
+/// `None` if there aren't any.
+///
+/// The differences are resampled with replacement `resamples` times, and the
+/// interval holds the middle `confidence` (e.g. 0.95) of the resampled means.
+/// The same `seed` always gives the same interval.
+pub fn bootstrap_mean(
+    differences: &[f64],
+    confidence: f64,
+    resamples: usize,
+    seed: u64,
+) -> Option<Interval> {
+    if differences.is_empty() || resamples == 0 {
+        return None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut means: Vec<f64> = (0..resamples)
+        .map(|_| {
+            let total: f64 = (0..differences.len())
+                .map(|_| differences[rng.gen_range(0..differences.len())])
+                .sum();
+            total / differences.len() as f64
+        })
+        .collect();
+    means.sort_unstable_by(f64::total_cmp);
+
+    let outside = ((1.0 - confidence.clamp(0.0, 1.0)) / 2.0 * resamples as f64) as usize;
+    let outside = outside.min((resamples - 1) / 2);
+    Some(Interval {
+        low: means[outside],
+        high: means[resamples - 1 - outside],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean() {
+        assert_eq!(None, mean(&[]));
+        assert_eq!(Some(2.5), mean(&[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(Some(-0.5), mean(&[-1.0, 0.0]));
+    }
+
+    #[test]
+    fn test_sign_test() {
+        // 8 up, 1 down: P(X <= 1) = (1 + 9) / 2^9, doubled
+        let test = sign_test(&[1.0, 2.0, 1.0, 1.0, 3.0, 1.0, 1.0, 1.0, 0.0, -1.0]);
+        assert_eq!(8, test.positive);
+        assert_eq!(1, test.negative);
+        assert_eq!(1, test.ties);
+        assert!((20.0 / 512.0 - test.p_value).abs() < 1e-12);
+
+        // an even split isn't evidence of anything
+        assert_eq!(1.0, sign_test(&[1.0, -1.0, 2.0, -2.0]).p_value);
+        assert_eq!(1.0, sign_test(&[0.0, 0.0]).p_value);
+        assert_eq!(1.0, sign_test(&[]).p_value);
+
+        // 10 of 10 the same way: 2 / 2^10
+        let test = sign_test(&[-1.0; 10]);
+        assert!((2.0 / 1024.0 - test.p_value).abs() < 1e-12);
+
+        // a big list doesn't underflow
+        let mut differences = vec![1.0; 1200];
+        differences.extend([-1.0; 1100]);
+        let test = sign_test(&differences);
+        assert!(
+            test.p_value > 0.03 && test.p_value < 0.04,
+            "{}",
+            test.p_value
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_mean() {
+        assert_eq!(None, bootstrap_mean(&[], 0.95, 1000, 1));
+        assert_eq!(
+            Some(Interval {
+                low: 2.0,
+                high: 2.0
+            }),
+            bootstrap_mean(&[2.0, 2.0, 2.0], 0.95, 1000, 1)
+        );
+
+        let differences = [1.0, 0.0, 2.0, 1.0, 1.0, -1.0, 1.0, 0.0, 2.0, 1.0];
+        let interval = bootstrap_mean(&differences, 0.95, 2000, 7).unwrap();
+        assert_eq!(Some(interval), bootstrap_mean(&differences, 0.95, 2000, 7));
+        assert!(interval.contains(0.8));
+        assert!(interval.low > 0.0 && interval.high < 1.6, "{:?}", interval);
+
+        // a wider interval for more confidence
+        let wider = bootstrap_mean(&differences, 0.999, 2000, 7).unwrap();
+        assert!(wider.low <= interval.low && wider.high >= interval.high);
+    }
+}
@@ -0,0 +1,351 @@
+//! Tool for building and checking word lists
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Parser, Subcommand};
+#[cfg(feature = "mmap")]
+use wordle::dictionary::packed;
+use wordle::wordlist::{self, Entry, Order};
+
+/// The exit code when `check` finds problems
+const PROBLEMS_FOUND: i32 = 1;
+/// The exit code when a file can't be read or written
+const FAILED: i32 = 2;
+
+/// Build and check word lists for Wordle
+#[derive(Debug, PartialEq, Parser)]
+#[command(version, about)]
+struct Args {
+    /// What to do
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// A `wordle-dict` subcommand
+#[derive(Debug, PartialEq, Subcommand)]
+enum Command {
+    /// Report duplicates, words of the wrong length, words with characters that
+    /// aren't letters, and answers that aren't in the guess list; exits with 1
+    /// if there are any
+    Check {
+        /// File with the list of acceptable guesses, one per line
+        #[arg(long, value_name = "PATH", default_value = "guesses.txt")]
+        guesses: PathBuf,
+
+        /// File with the list of answers, one per line
+        #[arg(long, value_name = "PATH", default_value = "answers.txt")]
+        answers: PathBuf,
+
+        /// The number of letters in each word
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        length: usize,
+    },
+    /// Lowercase a word list, remove duplicates, and sort it; a byte order mark,
+    /// `\r\n` line endings, and blank lines are removed too
+    Normalize {
+        /// The word list to normalize
+        input: PathBuf,
+
+        /// Where to write the normalized list (default: standard output)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Keep the words in the order they first appear, e.g. for an answer list
+        /// in daily puzzle order
+        #[arg(long)]
+        keep_order: bool,
+    },
+    /// Cut a big raw word list down to a playable one, keeping only the words with
+    /// the right number of letters (and used often enough, with --frequencies)
+    Filter {
+        /// The raw word list
+        input: PathBuf,
+
+        /// The number of letters in each word
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        length: usize,
+
+        /// File with a word and how often it's used on each line, e.g. "crane 1234";
+        /// words that aren't in it are dropped
+        #[arg(long, value_name = "PATH")]
+        frequencies: Option<PathBuf>,
+
+        /// Drop words used less often than this
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0.0,
+            requires = "frequencies"
+        )]
+        min_frequency: f64,
+
+        /// Where to write the filtered list (default: standard output)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Convert a word list written as a JavaScript or JSON array, e.g.
+    /// `["cigar","rebut"]`, into one with a word per line; words with anything
+    /// but letters in them, or the wrong number of letters, are errors
+    ImportJs {
+        /// The array, e.g. as scraped from the official site
+        input: PathBuf,
+
+        /// The number of letters in each word
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        length: usize,
+
+        /// Where to write the word list (default: standard output)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Keep the words in the order they appear, e.g. for an answer list in
+        /// daily puzzle order
+        #[arg(long)]
+        keep_order: bool,
+    },
+    /// Compile a word list into a finite state transducer, for a game to look
+    /// guesses up in without hashing them all; the list must be loaded in the
+    /// same order to use it
+    #[cfg(feature = "fst")]
+    BuildFst {
+        /// The word list to compile
+        input: PathBuf,
+
+        /// Where to write the transducer
+        #[arg(long, short, value_name = "PATH")]
+        output: PathBuf,
+    },
+    /// Pack a word list into one file, which a game maps into memory to open
+    /// rather than reading and parsing the list
+    #[cfg(feature = "mmap")]
+    Pack {
+        /// The word list to pack
+        input: PathBuf,
+
+        /// Where to write the packed list
+        #[arg(long, short, value_name = "PATH")]
+        output: PathBuf,
+
+        /// The number of letters in each word
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        length: usize,
+
+        /// Pack a transducer to look the words up in along with them
+        #[cfg(feature = "fst")]
+        #[arg(long)]
+        fst: bool,
+    },
+}
+
+fn main() {
+    match Args::parse().command {
+        Command::Check {
+            guesses,
+            answers,
+            length,
+        } => {
+            if !check(&guesses, &answers, length) {
+                process::exit(PROBLEMS_FOUND);
+            }
+        }
+        Command::Normalize {
+            input,
+            output,
+            keep_order,
+        } => {
+            let text = read(&input);
+            let order = if keep_order {
+                Order::Original
+            } else {
+                Order::Sorted
+            };
+            let words = wordlist::normalize(&wordlist::entries(&text), order);
+            write(output.as_deref(), &words);
+        }
+        Command::Filter {
+            input,
+            length,
+            frequencies,
+            min_frequency,
+            output,
+        } => {
+            let text = read(&input);
+            let frequencies = frequencies.map(|path| {
+                wordlist::frequencies(&read(&path)).unwrap_or_else(|e| {
+                    eprintln!("Error: invalid frequency list '{}': {}", path.display(), e);
+                    process::exit(FAILED);
+                })
+            });
+            let words = wordlist::filter(
+                &wordlist::entries(&text),
+                length,
+                frequencies.as_ref(),
+                min_frequency,
+            );
+            write(output.as_deref(), &words);
+        }
+        Command::ImportJs {
+            input,
+            length,
+            output,
+            keep_order,
+        } => {
+            let words = wordlist::parse_array(&read(&input), Some(length)).unwrap_or_else(|e| {
+                eprintln!("Error: invalid word list '{}': {}", input.display(), e);
+                process::exit(FAILED);
+            });
+            let entries: Vec<_> = words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| Entry {
+                    line: i + 1,
+                    column: 1,
+                    word,
+                })
+                .collect();
+            let order = if keep_order {
+                Order::Original
+            } else {
+                Order::Sorted
+            };
+            write(output.as_deref(), &wordlist::normalize(&entries, order));
+        }
+        #[cfg(feature = "fst")]
+        Command::BuildFst { input, output } => {
+            let text = read(&input);
+            let words: Vec<&str> = wordlist::entries(&text)
+                .iter()
+                .map(|entry| entry.word)
+                .collect();
+            let bytes = wordle::dictionary::build_fst(&words);
+            if let Err(e) = fs::write(&output, &bytes) {
+                eprintln!("Error: could not write '{}': {}", output.display(), e);
+                process::exit(FAILED);
+            }
+            eprintln!(
+                "Wrote {} words to '{}' ({} bytes)",
+                words.len(),
+                output.display(),
+                bytes.len()
+            );
+        }
+        #[cfg(feature = "mmap")]
+        Command::Pack {
+            input,
+            output,
+            length,
+            #[cfg(feature = "fst")]
+            fst,
+        } => {
+            let words = wordlist::load(&input).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                process::exit(FAILED);
+            });
+            let words = words.to_vec();
+            #[cfg(feature = "fst")]
+            let packed = if fst {
+                packed::pack_with_fst(&words, length)
+            } else {
+                packed::pack(&words, length)
+            };
+            #[cfg(not(feature = "fst"))]
+            let packed = packed::pack(&words, length);
+            let bytes = packed.unwrap_or_else(|e| {
+                eprintln!("Error: could not pack '{}': {}", input.display(), e);
+                process::exit(FAILED);
+            });
+            if let Err(e) = fs::write(&output, &bytes) {
+                eprintln!("Error: could not write '{}': {}", output.display(), e);
+                process::exit(FAILED);
+            }
+            eprintln!(
+                "Wrote {} words to '{}' ({} bytes)",
+                words.len(),
+                output.display(),
+                bytes.len()
+            );
+        }
+    }
+}
+
+/// Check both word lists, printing any problems, and get back whether there
+/// weren't any
+fn check(guesses_path: &Path, answers_path: &Path, length: usize) -> bool {
+    let (guesses_text, answers_text) = (read(guesses_path), read(answers_path));
+    let guesses = wordlist::entries(&guesses_text);
+    let answers = wordlist::entries(&answers_text);
+
+    let mut problems = 0;
+    for (path, issues) in [
+        (guesses_path, wordlist::check(&guesses, Some(length))),
+        (answers_path, wordlist::check(&answers, Some(length))),
+        (answers_path, wordlist::check_subset(&answers, &guesses)),
+    ] {
+        for issue in &issues {
+            println!("{}: {}", path.display(), issue);
+        }
+        problems += issues.len();
+    }
+
+    let summary = format!("{} guesses and {} answers", guesses.len(), answers.len());
+    match problems {
+        0 => println!("{}, no problems found", summary),
+        1 => println!("{}, 1 problem found", summary),
+        n => println!("{}, {} problems found", summary, n),
+    }
+    problems == 0
+}
+
+/// Read a word list, or exit with an error message
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Error: could not read '{}': {}", path.display(), e);
+        process::exit(FAILED);
+    })
+}
+
+/// Write a word list to a file, or to standard output if there's no file,
+/// exiting with an error message if it can't be written
+fn write(path: Option<&Path>, words: &[String]) {
+    let result = match path {
+        Some(path) => fs::File::create(path).and_then(|file| write_words(file, words)),
+        None => write_words(io::stdout().lock(), words),
+    };
+    if let Err(e) = result {
+        let path = path.map_or("standard output".into(), |path| path.display().to_string());
+        eprintln!("Error: could not write '{}': {}", path, e);
+        process::exit(FAILED);
+    }
+    if let Some(path) = path {
+        eprintln!("Wrote {} words to '{}'", words.len(), path.display());
+    }
+}
+
+/// Write words one per line
+fn write_words<W: Write>(out: W, words: &[String]) -> io::Result<()> {
+    let mut out = io::BufWriter::new(out);
+    for word in words {
+        writeln!(out, "{}", word)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,645 @@
+//! Benchmark for solving strategies, playing them against every answer
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use directories::ProjectDirs;
+use wordle::{
+    analysis::{self, Interval, SignTest},
+    messages::Messages,
+    sim::{self, HeadToHead, Outcome, Report, Summary, REPORT_VERSION, SLOW_GUESSES},
+    solver::{
+        self, EntropyStrategy, LookaheadStrategy, PositionalFrequencyStrategy, RandomStrategy,
+    },
+    startup,
+    state::NoWord,
+    wordlist::Limits,
+    Wordle,
+};
+
+/// The exit code when `compare` finds strategy B significantly worse than A
+const WORSE: i32 = 2;
+
+/// A strategy to benchmark
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+enum StrategyName {
+    /// Guess the word that gives the most information
+    Entropy,
+    /// Guess a random word that could still be the answer
+    Random,
+    /// Guess the word with the most common letters in each position
+    Frequency,
+    /// Guess the word expected to find the answer soonest, looking two guesses ahead
+    Lookahead,
+}
+
+impl StrategyName {
+    /// The name of the strategy, as given on the command line
+    fn name(self) -> &'static str {
+        match self {
+            Self::Entropy => "entropy",
+            Self::Random => "random",
+            Self::Frequency => "frequency",
+            Self::Lookahead => "lookahead",
+        }
+    }
+}
+
+/// Play solving strategies against every word in the answer list, and report
+/// how well they did
+#[derive(Debug, PartialEq, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Something to do instead of reporting on each strategy
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// A strategy to play; give this more than once to compare strategies
+    #[arg(long = "strategy", value_name = "NAME", default_value = "entropy")]
+    strategies: Vec<StrategyName>,
+
+    /// File with the list of acceptable guesses, one per line
+    #[arg(
+        long,
+        value_name = "PATH",
+        default_value = "guesses.txt",
+        global = true
+    )]
+    guesses: PathBuf,
+
+    /// File with the list of answers, one per line
+    #[arg(
+        long,
+        value_name = "PATH",
+        default_value = "answers.txt",
+        global = true
+    )]
+    answers: PathBuf,
+
+    /// Use the built-in word lists, even if the word list files exist
+    #[arg(long, conflicts_with_all = ["guesses", "answers"], global = true)]
+    builtin_words: bool,
+
+    /// The number of letters in each word; the word lists must match
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u8).range(4..=7), global = true)]
+    length: u8,
+
+    /// Seed the random strategy, so the same seed makes the same choices (default: random)
+    #[arg(long, value_name = "N", global = true)]
+    seed: Option<u64>,
+
+    /// The number of games to play at once (default: the number of CPUs)
+    #[arg(long, value_name = "N", global = true)]
+    threads: Option<NonZeroUsize>,
+
+    /// Also write the report as JSON to this file, e.g. report.json
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+
+    /// Save every game played to this directory, as a transcript in
+    /// <DIR>/<STRATEGY>/<ANSWER>.json
+    #[arg(long, value_name = "DIR")]
+    save_transcripts: Option<PathBuf>,
+}
+
+/// A `wordle-sim` subcommand
+#[derive(Debug, PartialEq, Subcommand)]
+enum Command {
+    /// Play two strategies against the same answers, and test whether B finds
+    /// them in more or fewer guesses than A; exits with 2 if B is significantly
+    /// worse, so it can gate changes to a strategy
+    Compare {
+        /// The strategy to compare against
+        #[arg(long, value_name = "NAME")]
+        a: StrategyName,
+
+        /// The strategy being tested
+        #[arg(long, value_name = "NAME")]
+        b: StrategyName,
+
+        /// How sure to be before calling a difference significant
+        #[arg(long, value_name = "P", default_value_t = 0.95, value_parser = parse_confidence)]
+        confidence: f64,
+
+        /// The number of times to resample the answers for the confidence interval
+        #[arg(long, value_name = "N", default_value_t = 10_000)]
+        resamples: usize,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+    let (guess_list, answer_list) = startup::load_word_lists(
+        Messages::english(),
+        &args.guesses,
+        &args.answers,
+        args.builtin_words,
+        &Limits::default(),
+    );
+    let game = Wordle::builder()
+        .guesses(guess_list)
+        .answers(answer_list)
+        .word_length(args.length.into())
+        .build()
+        .unwrap_or_else(|e| {
+            eprintln!("Error: invalid word lists: {}", e);
+            process::exit(1);
+        });
+
+    // the seed is always known, so any run can be repeated
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let threads = args
+        .threads
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+
+    println!(
+        "{} answers, {} guesses, seed {}\n",
+        answer_list.len(),
+        guess_list.len(),
+        seed
+    );
+    let run = |strategy| simulate(strategy, &game, guess_list, answer_list, seed, threads);
+
+    if let Some(Command::Compare {
+        a,
+        b,
+        confidence,
+        resamples,
+    }) = args.command
+    {
+        let (a_outcomes, a_elapsed) = run(a);
+        let (b_outcomes, b_elapsed) = run(b);
+        let max_guesses = game.max_guesses();
+        let reports = [
+            Report::new(a.name(), &a_outcomes, max_guesses, a_elapsed),
+            Report::new(b.name(), &b_outcomes, max_guesses, b_elapsed),
+        ];
+        print!("{}", table(&reports));
+
+        let differences: Vec<f64> = a_outcomes
+            .iter()
+            .zip(&b_outcomes)
+            .map(|(a, b)| b.cost(max_guesses) as f64 - a.cost(max_guesses) as f64)
+            .collect();
+        let interval = analysis::bootstrap_mean(&differences, confidence, resamples, seed)
+            .expect("The answer list isn't empty");
+        let pair = HeadToHead::new(b.name(), &b_outcomes, a.name(), &a_outcomes, max_guesses);
+        print!(
+            "\n{}",
+            verdict(
+                &pair,
+                &analysis::sign_test(&differences),
+                interval,
+                confidence
+            )
+        );
+        if interval.low > 0.0 {
+            process::exit(WORSE);
+        }
+        return;
+    }
+
+    let mut runs = Vec::new();
+    for &strategy in &args.strategies {
+        let (outcomes, elapsed) = run(strategy);
+        if let Some(dir) = &args.save_transcripts {
+            if let Err(e) = save_transcripts(&dir.join(strategy.name()), &outcomes) {
+                eprintln!(
+                    "Error: could not save transcripts to '{}': {}",
+                    dir.display(),
+                    e
+                );
+                process::exit(1);
+            }
+        }
+        runs.push((strategy.name(), outcomes, elapsed));
+    }
+
+    let reports: Vec<Report> = runs
+        .iter()
+        .map(|(name, outcomes, elapsed)| Report::new(name, outcomes, game.max_guesses(), *elapsed))
+        .collect();
+    let mut head_to_head = Vec::new();
+    for (i, (a, a_outcomes, _)) in runs.iter().enumerate() {
+        for (b, b_outcomes, _) in &runs[i + 1..] {
+            head_to_head.push(HeadToHead::new(
+                a,
+                a_outcomes,
+                b,
+                b_outcomes,
+                game.max_guesses(),
+            ));
+        }
+    }
+
+    print!("{}", table(&reports));
+    if !head_to_head.is_empty() {
+        print!("\n{}", comparison(&head_to_head));
+    }
+
+    if let Some(path) = &args.out {
+        let summary = Summary {
+            version: REPORT_VERSION,
+            seed,
+            guesses: guess_list.len(),
+            answers: answer_list.len(),
+            max_guesses: game.max_guesses(),
+            reports,
+            head_to_head,
+        };
+        if let Err(e) = write_json(path, &summary) {
+            eprintln!(
+                "Error: could not write report to '{}': {}",
+                path.display(),
+                e
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Play `strategy` against every answer, and get back the outcomes and how
+/// long they took to play
+fn simulate<'a>(
+    strategy: StrategyName,
+    game: &Wordle<'a, NoWord>,
+    guess_list: &[&str],
+    answer_list: &[&str],
+    seed: u64,
+    threads: usize,
+) -> (Vec<Outcome<'a>>, Duration) {
+    // working out the opener isn't counted in the time the games took
+    let opener = match strategy {
+        StrategyName::Entropy | StrategyName::Lookahead => best_opener(guess_list, answer_list),
+        StrategyName::Random | StrategyName::Frequency => String::new(),
+    };
+    let start = Instant::now();
+    let outcomes = match strategy {
+        StrategyName::Entropy => sim::simulate(
+            game,
+            |_| EntropyStrategy::with_opener(opener.as_str()),
+            threads,
+        ),
+        StrategyName::Random => sim::simulate(
+            game,
+            |i| RandomStrategy::seeded(seed.wrapping_add(i as u64)),
+            threads,
+        ),
+        StrategyName::Frequency => {
+            sim::simulate(game, |_| PositionalFrequencyStrategy::new(), threads)
+        }
+        StrategyName::Lookahead => sim::simulate(
+            game,
+            |_| LookaheadStrategy::default().with_opener(opener.as_str()),
+            threads,
+        ),
+    };
+    (outcomes, start.elapsed())
+}
+
+/// What `compare` found: how often strategy B (the first of the pair) did
+/// better or worse than A, how much by on average, and whether that's significant
+fn verdict(pair: &HeadToHead, sign: &SignTest, interval: Interval, confidence: f64) -> String {
+    let conclusion = if interval.low > 0.0 {
+        format!("{} is significantly worse than {}", pair.a, pair.b)
+    } else if interval.high < 0.0 {
+        format!("{} is significantly better than {}", pair.a, pair.b)
+    } else {
+        format!("{} is not significantly different from {}", pair.a, pair.b)
+    };
+    let p = match sign.p_value {
+        p if p < 0.001 => String::from("p < 0.001"),
+        p => format!("p = {:.3}", p),
+    };
+    format!(
+        "{} vs {}: better on {} of {} answers, worse on {}, the same on {}\n\
+         Mean difference: {:+.3} guesses ({}% interval {:+.3} to {:+.3})\n\
+         Sign test: {}\n\
+         {}\n",
+        pair.a,
+        pair.b,
+        pair.a_better,
+        pair.a_better + pair.b_better + pair.ties,
+        pair.b_better,
+        pair.ties,
+        pair.difference,
+        (confidence * 1000.0).round() / 10.0,
+        interval.low,
+        interval.high,
+        p,
+        conclusion
+    )
+}
+
+/// Parse a confidence level, which must be between 0 and 1
+fn parse_confidence(arg: &str) -> Result<f64, String> {
+    match arg.parse() {
+        Ok(confidence) if 0.0 < confidence && confidence < 1.0 => Ok(confidence),
+        Ok(_) => Err(String::from("must be between 0 and 1")),
+        Err(e) => Err(format!("{}", e)),
+    }
+}
+
+/// A table of how each strategy did, with a column for each number of
+/// guesses, followed by the words each strategy failed on or found slowly
+fn table(reports: &[Report]) -> String {
+    let width = reports
+        .iter()
+        .map(|report| report.strategy.len())
+        .chain(["strategy".len()])
+        .max()
+        .unwrap_or_default();
+    let max_guesses = reports
+        .iter()
+        .map(|report| report.distribution.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut header = format!(
+        "{:<width$}  {:>5}  {:>5}  {:>8}  {:>7}",
+        "strategy", "games", "wins", "failures", "average"
+    );
+    for guesses in 1..=max_guesses {
+        header += &format!("  {:>4}", guesses);
+    }
+    header += &format!("  {:>8}  {:>8}\n", "time", "per game");
+
+    let mut table = header;
+    for report in reports {
+        let average = report
+            .average_guesses
+            .map_or("-".to_owned(), |average| format!("{:.3}", average));
+        table += &format!(
+            "{:<width$}  {:>5}  {:>5}  {:>8}  {:>7}",
+            report.strategy,
+            report.games,
+            report.wins,
+            report.failures.len(),
+            average
+        );
+        for count in &report.distribution {
+            table += &format!("  {:>4}", count);
+        }
+        table += &format!(
+            "  {:>7.2}s  {:>6.1}ms\n",
+            report.seconds,
+            report.seconds_per_game * 1000.0
+        );
+    }
+
+    for report in reports {
+        if !report.slow.is_empty() {
+            table += &format!(
+                "\n{} took {}+ guesses on: {}\n",
+                report.strategy,
+                SLOW_GUESSES,
+                report.slow.join(", ")
+            );
+        }
+        if !report.failures.is_empty() {
+            table += &format!(
+                "\n{} failed on: {}\n",
+                report.strategy,
+                report.failures.join(", ")
+            );
+        }
+    }
+    table
+}
+
+/// A table of how each pair of strategies did against each other: how many
+/// answers each found sooner, and how many more guesses the first took on average
+fn comparison(head_to_head: &[HeadToHead]) -> String {
+    let names: Vec<String> = head_to_head
+        .iter()
+        .map(|pair| format!("{} vs {}", pair.a, pair.b))
+        .collect();
+    let width = names
+        .iter()
+        .map(String::len)
+        .chain(["head to head".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "{:<width$}  {:>6}  {:>6}  {:>5}  {:>10}\n",
+        "head to head", "better", "worse", "same", "difference"
+    );
+    for (name, pair) in names.iter().zip(head_to_head) {
+        table += &format!(
+            "{:<width$}  {:>6}  {:>6}  {:>5}  {:>+10.3}\n",
+            name, pair.a_better, pair.b_better, pair.ties, pair.difference
+        );
+    }
+    table
+}
+
+/// Write the JSON report to a file
+fn write_json(path: &Path, summary: &Summary) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    serde_json::to_writer_pretty(&mut file, summary)?;
+    writeln!(file)?;
+    file.flush()
+}
+
+/// Save the transcript of each game to `dir`, named after its answer,
+/// creating the directory if needed
+fn save_transcripts(dir: &Path, outcomes: &[Outcome<'_>]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for outcome in outcomes {
+        let path = dir.join(format!("{}.json", outcome.answer));
+        fs::write(path, serde_json::to_string_pretty(&outcome.transcript)?)?;
+    }
+    Ok(())
+}
+
+/// Find the best first guess for the entropy strategy, from the cache if
+/// possible; working it out takes a while, so the user is told when that's needed
+fn best_opener(guesses: &[&str], answers: &[&str]) -> String {
+    let cache =
+        ProjectDirs::from("", "", "wordle").map(|dirs| dirs.cache_dir().join("openers.txt"));
+    let openers = match cache {
+        Some(cache) => solver::cached_openers(guesses, answers, 1, &cache).unwrap_or_else(|| {
+            eprintln!("Computing the best first guess...");
+            solver::best_openers_cached(guesses, answers, 1, &cache)
+        }),
+        None => {
+            eprintln!("Computing the best first guess...");
+            solver::best_openers(guesses, answers, 1)
+        }
+    };
+    openers
+        .into_iter()
+        .next()
+        .map(|opener| opener.word)
+        .expect("The guess list isn't empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let reports = [
+            Report {
+                strategy: "entropy".into(),
+                games: 3,
+                wins: 3,
+                average_guesses: Some(2.0),
+                distribution: vec![1, 1, 1, 0, 0, 0],
+                failures: vec![],
+                slow: vec![],
+                seconds: 0.5,
+                seconds_per_game: 0.0021,
+            },
+            Report {
+                strategy: "random".into(),
+                games: 3,
+                wins: 1,
+                average_guesses: Some(6.0),
+                distribution: vec![0, 0, 0, 0, 0, 1],
+                failures: vec!["slate".into(), "irate".into()],
+                slow: vec!["crane".into()],
+                seconds: 12.345,
+                seconds_per_game: 4.1,
+            },
+        ];
+        assert_eq!(
+            "\
+strategy  games   wins  failures  average     1     2     3     4     5     6      time  per game
+entropy       3      3         0    2.000     1     1     1     0     0     0     0.50s     2.1ms
+random        3      1         2    6.000     0     0     0     0     0     1    12.35s  4100.0ms
+
+random took 6+ guesses on: crane
+
+random failed on: slate, irate
+",
+            table(&reports)
+        );
+    }
+
+    #[test]
+    fn test_verdict() {
+        let pair = HeadToHead {
+            a: "lookahead".into(),
+            b: "entropy".into(),
+            a_better: 40,
+            b_better: 30,
+            ties: 2245,
+            difference: -0.0196,
+        };
+        let sign = SignTest {
+            positive: 30,
+            negative: 40,
+            ties: 2245,
+            p_value: 0.28199,
+        };
+        let interval = Interval {
+            low: -0.041,
+            high: 0.0012,
+        };
+        assert_eq!(
+            "\
+lookahead vs entropy: better on 40 of 2315 answers, worse on 30, the same on 2245
+Mean difference: -0.020 guesses (95% interval -0.041 to +0.001)
+Sign test: p = 0.282
+lookahead is not significantly different from entropy
+",
+            verdict(&pair, &sign, interval, 0.95)
+        );
+
+        let sign = SignTest {
+            p_value: 0.0001,
+            ..sign
+        };
+        let worse = verdict(
+            &pair,
+            &sign,
+            Interval {
+                low: 0.01,
+                high: 0.2,
+            },
+            0.9,
+        );
+        assert!(
+            worse.contains("(90% interval +0.010 to +0.200)"),
+            "{}",
+            worse
+        );
+        assert!(worse.contains("Sign test: p < 0.001\n"), "{}", worse);
+        assert!(worse.ends_with("lookahead is significantly worse than entropy\n"));
+        let better = verdict(
+            &pair,
+            &sign,
+            Interval {
+                low: -0.2,
+                high: -0.01,
+            },
+            0.999,
+        );
+        assert!(better.contains("(99.9% interval"), "{}", better);
+        assert!(better.ends_with("lookahead is significantly better than entropy\n"));
+    }
+
+    #[test]
+    fn test_parse_confidence() {
+        assert_eq!(Ok(0.95), parse_confidence("0.95"));
+        assert!(parse_confidence("1").is_err());
+        assert!(parse_confidence("0").is_err());
+        assert!(parse_confidence("95%").is_err());
+    }
+
+    #[test]
+    fn test_comparison() {
+        let pairs = [
+            HeadToHead {
+                a: "entropy".into(),
+                b: "random".into(),
+                a_better: 120,
+                b_better: 3,
+                ties: 200,
+                difference: -0.4567,
+            },
+            HeadToHead {
+                a: "entropy".into(),
+                b: "lookahead".into(),
+                a_better: 10,
+                b_better: 12,
+                ties: 301,
+                difference: 0.01,
+            },
+        ];
+        assert_eq!(
+            "\
+head to head          better   worse   same  difference
+entropy vs random        120       3    200      -0.457
+entropy vs lookahead      10      12    301      +0.010
+",
+            comparison(&pairs)
+        );
+    }
+}
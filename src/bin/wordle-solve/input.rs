@@ -0,0 +1,106 @@
+//! Parsing what the player types at the solver prompt
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{codec, LetterStatus};
+
+/// A line typed at the solver prompt
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Input {
+    /// The feedback for the suggested guess
+    Feedback(Vec<LetterStatus>),
+    /// Another word was played instead of the suggestion, and got this feedback
+    Played(String, Vec<LetterStatus>),
+    /// Take back the last guess
+    Undo,
+    /// Show how to use the solver
+    Help,
+    /// Stop solving
+    Quit,
+}
+
+/// Parse a line typed at the solver prompt, or get back why it makes no sense.
+///
+/// The feedback can be letters or colored squares, as understood by
+/// [`codec::parse_statuses`]. Commands can be given with or without a leading
+/// `:`, and surrounding whitespace is ignored.
+pub fn parse(line: &str) -> Result<Input, String> {
+    let line = line.trim();
+    match line.trim_start_matches(':').to_lowercase().as_str() {
+        "undo" | "u" => return Ok(Input::Undo),
+        "help" | "h" | "?" => return Ok(Input::Help),
+        "quit" | "q" | "exit" => return Ok(Input::Quit),
+        _ => {}
+    }
+
+    // the feedback alone, possibly with spaces between the colors
+    if let Ok(feedback) = codec::parse_statuses(line) {
+        return Ok(Input::Feedback(feedback));
+    }
+
+    let (word, feedback) = line.split_once(char::is_whitespace).ok_or_else(|| {
+        format!(
+            "'{}' isn't feedback; type the colors, e.g. gy--- or 🟩🟨⬛⬛⬛",
+            line
+        )
+    })?;
+    if !word.chars().all(char::is_alphabetic) {
+        return Err(format!("'{}' isn't a word", word));
+    }
+    let feedback = codec::parse_statuses(feedback)
+        .map_err(|e| format!("'{}' isn't feedback: {}", feedback.trim(), e))?;
+    Ok(Input::Played(word.to_lowercase(), feedback))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterStatus::*;
+
+    #[test]
+    fn test_parse() {
+        let feedback = vec![Correct, InWord, NotInWord, NotInWord, NotInWord];
+        assert_eq!(Ok(Input::Feedback(feedback.clone())), parse("gy---\n"));
+        assert_eq!(Ok(Input::Feedback(feedback.clone())), parse(" G Y b b b "));
+        assert_eq!(Ok(Input::Feedback(feedback.clone())), parse("🟩🟨⬛⬛⬛"));
+        assert_eq!(
+            Ok(Input::Played("crane".into(), feedback.clone())),
+            parse("CRANE gy---")
+        );
+        assert_eq!(
+            Ok(Input::Played("crane".into(), feedback)),
+            parse("crane 🟩 🟨 ⬜ ⬜ ⬜")
+        );
+
+        assert_eq!(Ok(Input::Undo), parse("undo"));
+        assert_eq!(Ok(Input::Undo), parse(":UNDO"));
+        assert_eq!(Ok(Input::Help), parse("?"));
+        assert_eq!(Ok(Input::Quit), parse("q"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err("'crane' isn't feedback; type the colors, e.g. gy--- or 🟩🟨⬛⬛⬛".into()),
+            parse("crane")
+        );
+        assert_eq!(Err("'cr4ne' isn't a word".into()), parse("cr4ne gy---"));
+        assert_eq!(
+            Err("'gyr--' isn't feedback: invalid status 'r' at position 3".into()),
+            parse("crane gyr--")
+        );
+    }
+}
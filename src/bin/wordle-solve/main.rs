@@ -0,0 +1,405 @@
+//! Solver for Wordle games played elsewhere, like the official one
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    process,
+};
+
+use clap::Parser;
+use directories::ProjectDirs;
+use wordle::{
+    codec,
+    messages::Messages,
+    solver::{
+        self,
+        cache::{CacheStats, SolverCache},
+        Explanation, Session, Suggestion,
+    },
+    startup,
+    wordlist::Limits,
+    LetterStatus,
+};
+
+use input::Input;
+
+mod input;
+
+/// The number of guesses to suggest at a time
+const SUGGESTIONS: usize = 3;
+
+/// What to type at the prompt
+const HELP: &str = "\
+Play the suggested word, then type the colors you got back: g for green, y for yellow,
+and - (or b) for gray, e.g. gy--- (or paste the squares, e.g. 🟩🟨⬛⬛⬛).
+If you played another word, type it before the colors, e.g. crane gy---.
+Type undo to take back a guess, or quit to stop.";
+
+/// Solve a Wordle game played elsewhere, by suggesting guesses and narrowing
+/// down the answer from the colors they get
+#[derive(Debug, PartialEq, Eq, Parser)]
+#[command(version, about)]
+struct Args {
+    /// File with the list of acceptable guesses, one per line
+    #[arg(long, value_name = "PATH", default_value = "guesses.txt")]
+    guesses: PathBuf,
+
+    /// File with the list of answers, one per line
+    #[arg(long, value_name = "PATH", default_value = "answers.txt")]
+    answers: PathBuf,
+
+    /// Use the built-in word lists, even if the word list files exist
+    #[arg(long, conflicts_with_all = ["guesses", "answers"])]
+    builtin_words: bool,
+
+    /// The number of letters in each word
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u8).range(4..=7))]
+    length: u8,
+
+    /// Where to keep the searches for the best guesses, so they don't have to
+    /// be done again (default: the platform's cache directory)
+    #[arg(long, value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+
+    /// Search for the best guesses again, rather than reusing earlier searches
+    #[arg(long, conflicts_with = "cache_dir")]
+    no_cache: bool,
+
+    /// When done, report how much use the cache of searches was
+    #[arg(long, conflicts_with = "no_cache")]
+    cache_stats: bool,
+
+    /// Explain each suggestion: how many answers it's likely to leave, and the
+    /// colors it's likeliest to get
+    #[arg(long)]
+    explain: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+    let (guesses, answers) = startup::load_word_lists(
+        Messages::english(),
+        &args.guesses,
+        &args.answers,
+        args.builtin_words,
+        &Limits::default(),
+    );
+    let length = args.length.into();
+    let guesses: Vec<&str> = guesses
+        .iter()
+        .copied()
+        .filter(|word| word.chars().count() == length)
+        .collect();
+    let answers: Vec<&str> = answers
+        .iter()
+        .copied()
+        .filter(|word| word.chars().count() == length)
+        .collect();
+    if answers.is_empty() {
+        eprintln!("Error: the answer list has no {}-letter words", length);
+        process::exit(1);
+    }
+
+    // working them out takes a while, so the player is told when that's needed
+    let openers = solver::best_openers_default_cache(&guesses, &answers, SUGGESTIONS, || {
+        println!("Computing the best first guesses...")
+    });
+    let mut cache = if args.no_cache {
+        None
+    } else {
+        args.cache_dir
+            .clone()
+            .or_else(|| ProjectDirs::from("", "", "wordle").map(|dirs| dirs.cache_dir().into()))
+            .map(|dir| SolverCache::open(dir.join("solver.txt")))
+    };
+    let mut session = Session::new(&guesses, &answers, length);
+    println!("{}\n", HELP);
+
+    loop {
+        if session.is_solved() {
+            let count = session.deductions().count();
+            println!(
+                "Solved in {} guess{}!",
+                count,
+                if count == 1 { "" } else { "es" }
+            );
+            break;
+        }
+
+        let suggestions = if session.deductions().next().is_none() {
+            let mut openers = openers.clone();
+            if args.explain {
+                solver::explain_all(&mut openers, session.candidates());
+            }
+            openers
+        } else {
+            session.suggest_lookahead(SUGGESTIONS, args.explain, cache.as_mut())
+        };
+        let suggested = match session.candidates() {
+            [] => {
+                println!("No words fit that feedback; undo any typos, or type the word you played and its colors");
+                None
+            }
+            [answer] => {
+                println!("The answer must be {}", answer.to_uppercase());
+                Some(answer.to_string())
+            }
+            candidates => {
+                println!("{}", describe(candidates.len(), &suggestions));
+                suggestions.first().map(|s| s.word.clone())
+            }
+        };
+
+        let line = match prompt("> ") {
+            Some(line) => line,
+            None => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (word, feedback) = match input::parse(&line) {
+            Ok(Input::Feedback(feedback)) => match &suggested {
+                Some(word) => (word.clone(), feedback),
+                None => {
+                    println!("Type the word you played before its colors, e.g. crane gy---");
+                    continue;
+                }
+            },
+            Ok(Input::Played(word, feedback)) => (word, feedback),
+            Ok(Input::Undo) => {
+                match session.undo() {
+                    Some(deduction) => println!("Took back {}", deduction.guess.to_uppercase()),
+                    None => println!("There's nothing to undo"),
+                }
+                continue;
+            }
+            Ok(Input::Help) => {
+                println!("{}", HELP);
+                continue;
+            }
+            Ok(Input::Quit) => break,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        if let Err(e) = session.apply(&word, feedback) {
+            println!("{}", e);
+        }
+    }
+
+    if session.deductions().next().is_some() {
+        println!("\n{}", summary(&session));
+    }
+
+    if let Some(mut cache) = cache {
+        if let Err(e) = cache.flush() {
+            eprintln!(
+                "Warning: could not save the solver cache to '{}': {}",
+                cache.path().display(),
+                e
+            );
+        }
+        if args.cache_stats {
+            println!(
+                "\n{} in {}",
+                cache_stats(&cache.stats()),
+                cache.path().display()
+            );
+        }
+    }
+}
+
+/// How much use the cache was, e.g. "Solver cache: 3 hits, 1 miss, 12 entries (1.2 kB)"
+fn cache_stats(stats: &CacheStats) -> String {
+    let plural =
+        |n: u64, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+    let size = if stats.bytes < 1000 {
+        format!("{} bytes", stats.bytes)
+    } else {
+        format!("{:.1} kB", stats.bytes as f64 / 1000.0)
+    };
+    format!(
+        "Solver cache: {}, {}, {} ({})",
+        plural(stats.hits, "hit", "hits"),
+        plural(stats.misses, "miss", "misses"),
+        plural(stats.entries as u64, "entry", "entries"),
+        size
+    )
+}
+
+/// Describe where the solve stands, e.g. "12 possible answers left; try SLATE
+/// (2.91 bits), CRATE (2.75 bits)"; followed by the explanation of each
+/// suggestion, if they have them
+fn describe(remaining: usize, suggestions: &[Suggestion]) -> String {
+    let words: Vec<String> = suggestions
+        .iter()
+        .map(|s| format!("{} ({:.2} bits)", s.word.to_uppercase(), s.score))
+        .collect();
+    let mut text = format!(
+        "{} possible answers left; try {}",
+        remaining,
+        words.join(", ")
+    );
+    for suggestion in suggestions {
+        if let Some(explanation) = &suggestion.explanation {
+            text.push('\n');
+            text.push_str(&explain(&suggestion.word, explanation));
+        }
+    }
+    text
+}
+
+/// Explain a suggestion in an indented block, e.g.
+///
+/// ```text
+///   SLATE: could be the answer; leaves 2.17 answers on average, 5 at most
+///     likeliest colors: ⬛⬛🟩⬛🟩 5, ⬛⬛🟩🟩🟩 3, 🟩⬛🟩⬛🟩 2
+/// ```
+fn explain(word: &str, explanation: &Explanation) -> String {
+    let patterns: Vec<String> = explanation
+        .patterns
+        .iter()
+        .map(|(pattern, n)| format!("{} {}", codec::statuses_to_emoji(pattern), n))
+        .collect();
+    format!(
+        "  {}: {}; leaves {:.2} answers on average, {} at most\n    likeliest colors: {}",
+        word.to_uppercase(),
+        if explanation.possible_answers > 0 {
+            "could be the answer"
+        } else {
+            "can't be the answer"
+        },
+        explanation.expected_remaining,
+        explanation.worst_case,
+        patterns.join(", ")
+    )
+}
+
+/// The chain of guesses that led to the answer, one per line, with how far
+/// each narrowed down the possible answers
+fn summary(session: &Session<'_>) -> String {
+    let mut lines = vec!["How it went:".to_owned()];
+    for (i, deduction) in session.deductions().enumerate() {
+        let solved = deduction
+            .feedback
+            .iter()
+            .all(|status| *status == LetterStatus::Correct);
+        lines.push(format!(
+            "{}. {} {}  {}",
+            i + 1,
+            deduction.guess.to_uppercase(),
+            codec::statuses_to_emoji(&deduction.feedback),
+            if solved {
+                "solved".to_owned()
+            } else {
+                format!(
+                    "narrowed {} possible answers to {}",
+                    deduction.before, deduction.after
+                )
+            }
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Print a prompt and read a line of input, or `None` at the end of input
+fn prompt(text: &str) -> Option<String> {
+    print!("{}", text);
+    io::stdout().flush().expect("Could not flush stdout");
+
+    let mut line = String::new();
+    let read = io::stdin()
+        .read_line(&mut line)
+        .expect("Failed to read line");
+    if read == 0 {
+        // end the prompt's line, so the shell prompt doesn't start after it
+        println!();
+        return None;
+    }
+    Some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterStatus::*;
+
+    #[test]
+    fn test_describe() {
+        let candidates = ["crane", "slate", "irate"];
+        let mut suggestions = vec![Suggestion {
+            word: "slate".into(),
+            score: 1.585,
+            explanation: None,
+        }];
+        assert_eq!(
+            "3 possible answers left; try SLATE (1.58 bits)",
+            describe(3, &suggestions)
+        );
+
+        solver::explain_all(&mut suggestions, &candidates);
+        assert_eq!(
+            "3 possible answers left; try SLATE (1.58 bits)\n  \
+             SLATE: could be the answer; leaves 0.67 answers on average, 1 at most\n    \
+             likeliest colors: ⬛⬛🟩⬛🟩 1, ⬛⬛🟩🟩🟩 1, 🟩🟩🟩🟩🟩 1",
+            describe(3, &suggestions)
+        );
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            entries: 12,
+            bytes: 1234,
+        };
+        assert_eq!(
+            "Solver cache: 3 hits, 1 miss, 12 entries (1.2 kB)",
+            cache_stats(&stats)
+        );
+        assert_eq!(
+            "Solver cache: 0 hits, 0 misses, 1 entry (24 bytes)",
+            cache_stats(&CacheStats {
+                entries: 1,
+                bytes: 24,
+                ..CacheStats::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let words = ["crane", "slate", "irate"];
+        let mut session = Session::new(&words, &words, 5);
+        session
+            .apply(
+                "slate",
+                vec![NotInWord, NotInWord, Correct, Correct, Correct],
+            )
+            .unwrap();
+        session.apply("crane", vec![Correct; 5]).unwrap();
+        session.undo();
+        session.apply("irate", vec![Correct; 5]).unwrap();
+        assert_eq!(
+            "How it went:\n1. SLATE ⬛⬛🟩🟩🟩  narrowed 3 possible answers to 1\n2. IRATE 🟩🟩🟩🟩🟩  solved",
+            summary(&session)
+        );
+    }
+}
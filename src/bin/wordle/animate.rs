@@ -0,0 +1,212 @@
+//! Animating a guess being revealed in the full-screen view, like the web game
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! The tiles of a guess flip over one by one, left to right, and are colored
+//! once they're halfway over; on a win, the row then bounces, one tile after
+//! another. Nothing here sleeps: the view [ticks](Reveal::tick) the animation
+//! along between drawing frames, so input is never held up.
+
+use std::time::Duration;
+
+/// How long after one tile starts flipping the next one does
+const STAGGER: Duration = Duration::from_millis(250);
+/// How long a tile takes to flip over
+const FLIP: Duration = Duration::from_millis(300);
+/// How long after one tile jumps the next one does, in the bounce after a win
+const BOUNCE_STAGGER: Duration = Duration::from_millis(80);
+/// How long each tile stays up, in the bounce after a win
+const BOUNCE: Duration = Duration::from_millis(160);
+
+/// How long to wait between frames while something is being animated
+pub const FRAME: Duration = Duration::from_millis(30);
+
+/// How a tile of the guess being revealed is drawn
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+    /// Not flipped yet: the letter, without its color
+    Hidden,
+    /// Edge-on, halfway through flipping over
+    Turning,
+    /// Flipped over: the letter on its color
+    Shown,
+    /// Jumping up, in the bounce after a win
+    Lifted,
+}
+
+/// The reveal of a guess's feedback, stepped along by [`Reveal::tick`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Reveal {
+    /// The number of tiles in the row
+    tiles: u32,
+    /// Whether the guess won the game, so the row bounces
+    won: bool,
+    /// The time since the reveal started
+    elapsed: Duration,
+}
+
+impl Reveal {
+    /// Start revealing a row of `tiles` tiles
+    pub fn new(tiles: usize, won: bool) -> Self {
+        Self {
+            tiles: tiles as u32,
+            won,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Move the animation on by `elapsed`
+    pub fn tick(&mut self, elapsed: Duration) {
+        self.elapsed = (self.elapsed + elapsed).min(self.end());
+    }
+
+    /// Jump to the end of the animation
+    pub fn finish(&mut self) {
+        self.elapsed = self.end();
+    }
+
+    /// When the last tile has landed
+    fn landed_at(&self) -> Duration {
+        match self.tiles {
+            0 => Duration::ZERO,
+            tiles => STAGGER * (tiles - 1) + FLIP,
+        }
+    }
+
+    /// When the whole animation is over
+    fn end(&self) -> Duration {
+        match self.tiles {
+            tiles if self.won && tiles > 0 => {
+                self.landed_at() + BOUNCE_STAGGER * (tiles - 1) + BOUNCE
+            }
+            _ => self.landed_at(),
+        }
+    }
+
+    /// How the `i`th tile of the row is drawn right now
+    pub fn tile(&self, i: usize) -> Tile {
+        let i = i as u32;
+        let flip = STAGGER * i;
+        let bounce = self.landed_at() + BOUNCE_STAGGER * i;
+        match self.elapsed {
+            t if t < flip => Tile::Hidden,
+            t if t < flip + FLIP / 2 => Tile::Turning,
+            t if self.won && t >= bounce && t < bounce + BOUNCE => Tile::Lifted,
+            _ => Tile::Shown,
+        }
+    }
+
+    /// Whether every tile has landed, so the keyboard can show the guess's feedback
+    pub fn landed(&self) -> bool {
+        self.elapsed >= self.landed_at()
+    }
+
+    /// Whether the animation is over
+    pub fn done(&self) -> bool {
+        self.elapsed >= self.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Step `reveal` along in ticks of `step` until `until`, and get back how
+    /// it looked after each tick
+    fn frames(reveal: &mut Reveal, step: u64, until: u64) -> Vec<Vec<Tile>> {
+        (0..until / step)
+            .map(|_| {
+                reveal.tick(Duration::from_millis(step));
+                (0..reveal.tiles as usize).map(|i| reveal.tile(i)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flip() {
+        use Tile::*;
+
+        let mut reveal = Reveal::new(3, false);
+        assert_eq!(
+            vec![Turning, Hidden, Hidden],
+            [0, 1, 2].map(|i| reveal.tile(i))
+        );
+        assert!(!reveal.landed());
+
+        let frames = frames(&mut reveal, 100, 900);
+        assert_eq!(
+            vec![
+                vec![Turning, Hidden, Hidden], // 100ms
+                vec![Shown, Hidden, Hidden],   // 200ms
+                vec![Shown, Turning, Hidden],  // 300ms
+                vec![Shown, Shown, Hidden],    // 400ms
+                vec![Shown, Shown, Turning],   // 500ms
+                vec![Shown, Shown, Turning],   // 600ms
+                vec![Shown, Shown, Shown],     // 700ms
+                vec![Shown, Shown, Shown],     // 800ms: the last tile lands
+                vec![Shown, Shown, Shown],     // 900ms
+            ],
+            frames
+        );
+        assert!(reveal.landed());
+        assert!(reveal.done(), "there's no bounce without a win");
+    }
+
+    #[test]
+    fn test_landed() {
+        let mut reveal = Reveal::new(5, false);
+        reveal.tick(Duration::from_millis(1299));
+        assert_eq!(Tile::Shown, reveal.tile(4));
+        assert!(!reveal.landed(), "the last tile is still flipping");
+        reveal.tick(Duration::from_millis(1));
+        assert!(reveal.landed());
+        assert!(reveal.done());
+    }
+
+    #[test]
+    fn test_bounce() {
+        use Tile::*;
+
+        let mut reveal = Reveal::new(3, true);
+        reveal.tick(Duration::from_millis(800));
+        assert!(reveal.landed());
+        assert!(!reveal.done());
+
+        // the tiles jump one after another, each staying up a while
+        let frames = frames(&mut reveal, 40, 400);
+        assert_eq!(vec![Lifted, Shown, Shown], frames[0]); // 840ms
+        assert_eq!(vec![Lifted, Lifted, Shown], frames[1]); // 880ms
+        assert_eq!(vec![Shown, Lifted, Lifted], frames[3]); // 960ms
+        assert_eq!(vec![Shown, Shown, Lifted], frames[5]); // 1040ms
+        assert_eq!(vec![Shown, Shown, Shown], frames[9]); // 1200ms
+        assert!(reveal.done());
+    }
+
+    #[test]
+    fn test_finish() {
+        let mut reveal = Reveal::new(5, true);
+        reveal.tick(Duration::from_millis(10));
+        reveal.finish();
+        assert!(reveal.done());
+        assert!((0..5).all(|i| reveal.tile(i) == Tile::Shown));
+
+        // ticking past the end doesn't overshoot
+        let mut reveal = Reveal::new(5, false);
+        reveal.tick(Duration::from_secs(60));
+        assert_eq!(Reveal::new(5, false).end(), reveal.elapsed);
+    }
+}
@@ -0,0 +1,176 @@
+//! Non-interactive play, for scripts and tests: guesses in, one result per line out
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::io::{self, BufRead, Write};
+
+use wordle::{codec, messages::Messages, GameStatus, Wordle};
+
+use crate::render;
+
+/// How a batch game ended
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// The word was found
+    Won,
+    /// The guesses ran out
+    Lost,
+    /// The input ended while the game was still in progress
+    InputEnded,
+}
+
+impl Outcome {
+    /// The exit code for the outcome: 0 for a win, 1 for a loss, and 2 for an input error
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Won => 0,
+            Self::Lost => 1,
+            Self::InputEnded => 2,
+        }
+    }
+}
+
+/// Play the current word with one guess per line of `input`, until the game is over.
+///
+/// Each guess gets a line in `output` with the guess, its feedback in the
+/// [letter encoding](codec::statuses_to_string), the state of the game, and the
+/// number of turns taken; e.g. `crane ggggg win 3`. Guesses are lowercased,
+/// and blank lines are ignored.
+///
+/// Invalid guesses are reported to `errors` and don't take a turn, unless
+/// `strict` is set; then they do, and get the feedback `invalid` in `output`.
+pub fn run(
+    game: &mut Wordle<'_>,
+    input: impl BufRead,
+    mut output: impl Write,
+    mut errors: impl Write,
+    messages: &Messages,
+    strict: bool,
+) -> io::Result<Outcome> {
+    // turns taken by invalid guesses, in strict mode
+    let mut wasted = 0;
+
+    for line in input.lines() {
+        let line = line?;
+        let guess = line.trim().to_lowercase();
+        if guess.is_empty() {
+            continue;
+        }
+
+        let feedback = match game.guess(&guess) {
+            Ok(outcome) => codec::statuses_to_string(outcome.feedback()),
+            Err(e) => {
+                writeln!(errors, "{}", render::rejection(messages, &guess, &e))?;
+                if !strict {
+                    continue;
+                }
+                wasted += 1;
+                if game.history().len() + wasted >= game.max_guesses() {
+                    game.give_up();
+                }
+                "invalid".to_owned()
+            }
+        };
+
+        let state = match game.status() {
+            GameStatus::InProgress => "playing",
+            GameStatus::Won { .. } => "win",
+            GameStatus::Lost => "loss",
+        };
+        let turn = game.history().len() + wasted;
+        writeln!(output, "{} {} {} {}", guess, feedback, state, turn)?;
+
+        match game.status() {
+            GameStatus::InProgress => {}
+            GameStatus::Won { .. } => return Ok(Outcome::Won),
+            GameStatus::Lost => return Ok(Outcome::Lost),
+        }
+    }
+    Ok(Outcome::InputEnded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUESSES: [&str; 6] = ["crane", "trace", "slate", "stare", "snare", "share"];
+
+    /// Play a batch game against "crane", returning the outcome, output, and errors
+    fn play(input: &str, strict: bool) -> (Outcome, String, String) {
+        let mut game = Wordle::builder()
+            .guesses(&GUESSES)
+            .answers(&GUESSES)
+            .build()
+            .unwrap()
+            .set_answer("crane")
+            .unwrap();
+
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let outcome = run(
+            &mut game,
+            input.as_bytes(),
+            &mut output,
+            &mut errors,
+            Messages::english(),
+            strict,
+        )
+        .unwrap();
+        (
+            outcome,
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(errors).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_win() {
+        let (outcome, output, errors) = play("trace\n\n  slate\ncrane\nshare\n", false);
+        assert_eq!(Outcome::Won, outcome);
+        assert_eq!(
+            "trace -ggyg playing 1\nslate --g-g playing 2\ncrane ggggg win 3\n",
+            output
+        );
+        assert_eq!("", errors);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let (outcome, output, errors) = play("xxxxx\ncrane\n", false);
+        assert_eq!(Outcome::Won, outcome);
+        assert_eq!("crane ggggg win 1\n", output);
+        assert_eq!("'xxxxx' is not valid: not in word list\n", errors);
+
+        let (outcome, output, _) = play("xxxxx\ncrane\n", true);
+        assert_eq!(Outcome::Won, outcome);
+        assert_eq!("xxxxx invalid playing 1\ncrane ggggg win 2\n", output);
+
+        // invalid guesses can use up the last turn, too
+        let (outcome, output, _) = play("trace\nslate\nstare\nsnare\nshare\nxxxxx\n", true);
+        assert_eq!(Outcome::Lost, outcome);
+        assert!(output.ends_with("share --gyg playing 5\nxxxxx invalid loss 6\n"));
+    }
+
+    #[test]
+    fn test_input_ended() {
+        let (outcome, output, _) = play("trace\n", false);
+        assert_eq!(Outcome::InputEnded, outcome);
+        assert_eq!("trace -ggyg playing 1\n", output);
+
+        assert_eq!(0, Outcome::Won.exit_code());
+        assert_eq!(1, Outcome::Lost.exit_code());
+        assert_eq!(2, Outcome::InputEnded.exit_code());
+    }
+}
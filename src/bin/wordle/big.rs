@@ -0,0 +1,160 @@
+//! Drawing the board as big tiles, each a box three lines high, for streaming and screenshots
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use owo_colors::OwoColorize;
+use wordle::{state::Started, LetterStatus, Wordle};
+
+use crate::render::{self, Style};
+
+/// The width of a tile, in columns
+const TILE_WIDTH: usize = 5;
+
+/// The number of columns between tiles
+const GAP: usize = 1;
+
+/// The width of a row of `word_length` tiles, in columns
+pub fn width(word_length: usize) -> usize {
+    (word_length * (TILE_WIDTH + GAP)).saturating_sub(GAP)
+}
+
+/// Draw the game's board as big tiles: a row of tiles for each guess so far,
+/// then an empty row for each guess left, with a blank line between rows.
+///
+/// With colors, each tile is filled with its feedback's color; without, tiles
+/// are boxed in ASCII and their letters marked like [`Style::guess`] does.
+/// Returns `None` if a row wouldn't fit in `width` columns.
+pub fn board<S: Started>(style: &Style, game: &Wordle<'_, S>, width: usize) -> Option<Vec<String>> {
+    if self::width(game.word_length()) > width {
+        return None;
+    }
+
+    let guesses = game.history().iter().map(|(guess, statuses)| {
+        guess
+            .chars()
+            .zip(statuses)
+            .map(|(letter, status)| tile(style, Some((letter, status))))
+            .collect::<Vec<[String; 3]>>()
+    });
+    let empty = vec![tile(style, None); game.word_length()];
+    let rows = guesses.chain(std::iter::repeat_n(
+        empty,
+        game.max_guesses().saturating_sub(game.history().len()),
+    ));
+
+    let mut lines = Vec::new();
+    for (i, row) in rows.enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        for line in 0..3 {
+            let parts: Vec<&str> = row.iter().map(|tile| tile[line].as_str()).collect();
+            lines.push(parts.join(&" ".repeat(GAP)));
+        }
+    }
+    Some(lines)
+}
+
+/// The three lines of a tile, for a letter and its feedback or an empty tile
+fn tile(style: &Style, letter: Option<(char, &LetterStatus)>) -> [String; 3] {
+    let blank = " ".repeat(TILE_WIDTH);
+    if style.color() {
+        let (middle, ansi) = match letter {
+            Some((letter, status)) => (
+                format!("  {}  ", letter.to_uppercase()),
+                render::ansi(status, style.theme()),
+            ),
+            None => (String::from("  ·  "), style.empty()),
+        };
+        return [&blank, &middle, &blank].map(|line| line.style(ansi).to_string());
+    }
+
+    let border = format!("+{}+", "-".repeat(TILE_WIDTH - 2));
+    let middle = match letter {
+        Some((letter, LetterStatus::Correct)) => format!("[{}]", letter.to_uppercase()),
+        Some((letter, LetterStatus::InWord)) => format!("({})", letter.to_uppercase()),
+        Some((letter, LetterStatus::NotInWord)) => format!(" {} ", letter.to_uppercase()),
+        None => String::from("   "),
+    };
+    [border.clone(), format!("|{}|", middle), border]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use owo_colors::Style as AnsiStyle;
+
+    /// A game of two guesses against the first word, after guessing the second
+    fn game<'a>(words: &'a [&'a str]) -> Wordle<'a> {
+        let mut game = Wordle::builder()
+            .guesses(words)
+            .answers(&words[..1])
+            .word_length(words[0].len())
+            .max_guesses(2)
+            .build()
+            .unwrap()
+            .choose_word();
+        game.guess(words[1]).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_plain() {
+        let game = game(&["crane", "trace"]);
+        assert_eq!(
+            vec![
+                "+---+ +---+ +---+ +---+ +---+",
+                "| T | |[R]| |[A]| |(C)| |[E]|",
+                "+---+ +---+ +---+ +---+ +---+",
+                "",
+                "+---+ +---+ +---+ +---+ +---+",
+                "|   | |   | |   | |   | |   |",
+                "+---+ +---+ +---+ +---+ +---+",
+            ],
+            board(&Style::new(false), &game, 80).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_color() {
+        let lines = board(&Style::new(true), &game(&["crane", "trace"]), 80).unwrap();
+        assert_eq!(7, lines.len());
+        let correct = render::ansi(&LetterStatus::Correct, &wordle::theme::CLASSIC);
+        assert!(lines[1].contains(&"  R  ".style(correct).to_string()));
+        assert!(lines[5].contains(&"  ·  ".style(AnsiStyle::new().dimmed()).to_string()));
+    }
+
+    #[test]
+    fn test_word_length() {
+        let game = game(&["cane", "acne"]);
+        assert_eq!(
+            vec![
+                "+---+ +---+ +---+ +---+",
+                "|(A)| |(C)| |[N]| |[E]|",
+                "+---+ +---+ +---+ +---+",
+            ],
+            board(&Style::new(false), &game, 80).unwrap()[..3]
+        );
+        assert_eq!(23, width(4));
+    }
+
+    #[test]
+    fn test_too_narrow() {
+        let game = game(&["crane", "trace"]);
+        assert!(board(&Style::new(false), &game, 29).is_some());
+        assert_eq!(None, board(&Style::new(false), &game, 28));
+    }
+}
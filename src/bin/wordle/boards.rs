@@ -0,0 +1,219 @@
+//! Playing several boards at once with `--boards`, like Quordle
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{messages::Messages, multi::MultiGame, GameStatus, Wordle};
+
+use crate::{
+    command::{self, Command, Input},
+    complete::Completions,
+    confirm,
+    prompt::{Prompt, Stop},
+    render::{self, Style},
+    Flow,
+};
+
+/// The columns between boards drawn side by side
+const GAP: usize = 3;
+
+/// The number of boards `--boards` can play
+pub const CHOICES: [usize; 2] = [2, 4];
+
+/// Play every board until they're all solved, the guesses run out, or the player skips them.
+///
+/// The boards are drawn side by side at the start and after every guess,
+/// stacked if the terminal is too narrow for them, each with its own keyboard.
+///
+/// Returns [`Flow::Quit`] if the player quit or closed stdin partway through.
+pub fn play(game: &mut MultiGame<'_>, prompt: &mut dyn Prompt, style: &Style) -> Flow {
+    // every board takes the same guesses, so any of them has the guess list
+    let guesses = game.boards().first().map_or(&[][..], Wordle::guess_list);
+    let messages = style.messages();
+    prompt.set_completions(Completions::new(guesses.iter().copied()));
+    // the number of guesses the boards were last drawn for
+    let mut drawn = None;
+
+    loop {
+        if drawn != Some(game.turns()) {
+            draw(game, style);
+            drawn = Some(game.turns());
+        }
+        if game.status() != GameStatus::InProgress {
+            break;
+        }
+
+        let text = messages.format(
+            "prompt-guess",
+            &[
+                ("turn", (game.turns() + 1).into()),
+                ("max", game.max_guesses().into()),
+            ],
+        );
+        let text = format!("{} ", text);
+        let line = match prompt.read(&text) {
+            Ok(line) => line,
+            Err(Stop::End) => return Flow::Quit,
+            Err(Stop::Interrupted) => return Flow::Interrupted,
+        };
+        match command::parse(&line) {
+            Input::Guess(guess) => {
+                let guess = guess.to_lowercase();
+                if !guess.is_empty() {
+                    prompt.remember(&guess);
+                }
+                if let Err(e) = game.guess(&guess) {
+                    println!("{}", render::rejection(messages, &guess, &e));
+                }
+            }
+            Input::Command(Command::Quit) => return Flow::Quit,
+            Input::Command(Command::Skip) => game.give_up(),
+            Input::Command(Command::GiveUp) => {
+                match confirm(prompt, &messages.format("prompt-give-up-boards", &[])) {
+                    Ok(true) => game.give_up(),
+                    Ok(false) | Err(Stop::End) => {}
+                    Err(Stop::Interrupted) => return Flow::Interrupted,
+                }
+            }
+            Input::Command(Command::Hint) => {
+                println!("{}", messages.format("boards-no-hints", &[]))
+            }
+            Input::Command(Command::Copy) => println!("{}", messages.format("copy-later", &[])),
+            Input::Command(Command::Save(_)) => {
+                println!("{}", messages.format("boards-no-save", &[]))
+            }
+            Input::Unknown(command) => println!(
+                "{}",
+                messages.format("unknown-command", &[("command", command.into())])
+            ),
+        }
+    }
+    Flow::Continue
+}
+
+/// How each board ended, and which turn the solved ones were solved on
+pub fn summary(messages: &Messages, game: &MultiGame<'_>) -> String {
+    let solved_on = game.solved_on();
+    let solved = solved_on.iter().flatten().count();
+    let boards = ("boards", game.boards().len().into());
+    let mut lines = vec![match game.status() {
+        GameStatus::Won { guesses } => messages.format(
+            "boards-won",
+            &[
+                boards,
+                ("guesses", guesses.into()),
+                ("max", game.max_guesses().into()),
+            ],
+        ),
+        _ => messages.format("boards-lost", &[("solved", solved.into()), boards]),
+    }];
+    for (i, (answer, turn)) in game.answers().into_iter().zip(solved_on).enumerate() {
+        let number = ("number", (i + 1).into());
+        let answer = ("answer", answer.to_uppercase().into());
+        lines.push(match turn {
+            Some(turn) => {
+                messages.format("boards-solved-on", &[number, answer, ("turn", turn.into())])
+            }
+            None => messages.format("boards-not-solved", &[number, answer]),
+        });
+    }
+    lines.join("\n")
+}
+
+/// Draw every board with how it's going, and then every unsolved board's keyboard
+fn draw(game: &MultiGame<'_>, style: &Style) {
+    let width = render::terminal_width();
+    if style.clear() {
+        print!("{}", render::CLEAR_SCREEN);
+    }
+
+    let boards: Vec<String> = game
+        .boards()
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            format!(
+                "{}\n{}",
+                status(style.messages(), i, board),
+                style.turn(board)
+            )
+        })
+        .collect();
+    println!("{}", render::side_by_side(&boards, width, GAP));
+
+    if game.turns() > 0 {
+        let keyboards: Vec<String> = game
+            .boards()
+            .iter()
+            .enumerate()
+            .filter(|(_, board)| board.status() == GameStatus::InProgress)
+            .map(|(i, board)| {
+                let keyboard = style.keyboard(&board.keyboard(), board.alphabet());
+                let label = style
+                    .messages()
+                    .format("board-keyboard", &[("number", (i + 1).into())]);
+                format!("{}\n{}", label, keyboard)
+            })
+            .collect();
+        println!("{}", render::side_by_side(&keyboards, width, GAP));
+    }
+}
+
+/// The line above a board, saying how it's going
+fn status(messages: &Messages, i: usize, board: &Wordle<'_>) -> String {
+    let number = ("number", (i + 1).into());
+    match board.status() {
+        GameStatus::InProgress => messages.format("board", &[number]),
+        GameStatus::Won { guesses } => {
+            messages.format("board-solved", &[number, ("guesses", guesses.into())])
+        }
+        GameStatus::Lost => messages.format("board-not-solved", &[number]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 4] = ["crane", "slate", "trace", "share"];
+
+    #[test]
+    fn test_summary() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let mut game = MultiGame::new(&template, &["crane", "slate"]).unwrap();
+        game.guess("slate").unwrap();
+        assert_eq!(
+            "Solved 1 of 2 boards\nBoard 1: CRANE, not solved\nBoard 2: SLATE, solved on turn 1",
+            summary(Messages::english(), &game)
+        );
+        game.guess("crane").unwrap();
+        assert_eq!(
+            "Solved all 2 boards in 2/7!\nBoard 1: CRANE, solved on turn 2\nBoard 2: SLATE, solved on turn 1",
+            summary(Messages::english(), &game)
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let mut game = MultiGame::new(&template, &["crane", "slate"]).unwrap();
+        let mut prompt = crate::prompt::Lines::new("crane\n:hint\nSlate\n".as_bytes(), Vec::new());
+        assert_eq!(
+            Flow::Continue,
+            play(&mut game, &mut prompt, &Style::new(false))
+        );
+        assert_eq!(GameStatus::Won { guesses: 2 }, game.status());
+    }
+}
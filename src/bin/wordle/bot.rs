@@ -0,0 +1,92 @@
+//! Racing a computer player with `--vs-bot`
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{
+    messages::Messages, opponent::Opponent, share::TileSet, state::Finished, GameStatus, Wordle,
+};
+
+/// The bot's board so far, without any letters
+pub fn progress(messages: &Messages, opponent: &Opponent<'_>, tiles: &TileSet<'_>) -> String {
+    format!(
+        "{}\n{}",
+        messages.format("bot-board", &[]),
+        opponent.progress_with(tiles)
+    )
+}
+
+/// Who won, once the bot has played out its board too: whoever found the
+/// word in fewer guesses
+pub fn verdict(
+    messages: &Messages,
+    game: &Wordle<'_, Finished>,
+    opponent: &Opponent<'_>,
+) -> String {
+    let bot = opponent.is_solved().then(|| opponent.turns());
+    let id = match (game.status(), bot) {
+        (GameStatus::Won { guesses }, Some(bot)) if guesses == bot => {
+            return messages.format("vs-bot-tied", &[("guesses", guesses.into())])
+        }
+        (GameStatus::Won { guesses }, Some(bot)) if guesses > bot => "vs-bot-lost",
+        (GameStatus::Won { .. }, _) => "vs-bot-won",
+        (_, Some(_)) => "vs-bot-lost",
+        (_, None) => "vs-bot-neither",
+    };
+    messages.format(id, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::solver::EntropyStrategy;
+
+    const WORDS: [&str; 4] = ["crane", "slate", "toast", "trace"];
+
+    /// The verdict when the player guesses `guesses` against "crane", and the
+    /// bot opens with `opener`
+    fn race(guesses: &[&str], opener: &str) -> String {
+        let mut game = Wordle::new(&WORDS, &WORDS).set_answer("crane").unwrap();
+        let mut opponent = Opponent::new(&game, Box::new(EntropyStrategy::with_opener(opener)));
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game.give_up();
+        while opponent.take_turn().is_some() {}
+        verdict(Messages::english(), &game.finish().unwrap(), &opponent)
+    }
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!("You beat the bot!", race(&["crane"], "slate"));
+        assert_eq!(
+            "You and the bot tied, both in 1.",
+            race(&["crane"], "crane")
+        );
+        assert_eq!("The bot beat you.", race(&["slate", "crane"], "crane"));
+        assert_eq!("The bot beat you.", race(&["slate"], "crane"));
+    }
+
+    #[test]
+    fn test_progress() {
+        let game = Wordle::new(&WORDS, &WORDS).set_answer("crane").unwrap();
+        let mut opponent = Opponent::new(&game, Box::new(EntropyStrategy::with_opener("toast")));
+        opponent.take_turn();
+        assert_eq!(
+            "The bot's board:\n..#..",
+            progress(Messages::english(), &opponent, &TileSet::ascii())
+        );
+    }
+}
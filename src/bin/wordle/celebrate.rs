@@ -0,0 +1,79 @@
+//! What's said at the end of a game, kept together so it can be translated
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::messages::Messages;
+
+/// The praise for a win in `guesses` guesses, like the official game's; any
+/// more than six is a close call too
+pub fn praise(messages: &Messages, guesses: usize) -> String {
+    messages.format("praise", &[("guesses", guesses.into())])
+}
+
+/// What's said after a loss
+pub fn consolation(messages: &Messages, answer: &str) -> String {
+    messages.format("consolation", &[("answer", answer.to_uppercase().into())])
+}
+
+/// The player's streaks after a win
+pub fn streak(messages: &Messages, current: u32, max: u32) -> String {
+    messages.format(
+        "streak",
+        &[("current", current.into()), ("max", max.into())],
+    )
+}
+
+/// The notice that a loss ended a streak
+pub fn streak_ended(messages: &Messages, streak: u32) -> String {
+    messages.format("streak-ended", &[("streak", streak.into())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_praise() {
+        let english = Messages::english();
+        let praise: Vec<String> = (1..=6).map(|guesses| praise(english, guesses)).collect();
+        assert_eq!(
+            vec![
+                "Genius!",
+                "Magnificent!",
+                "Impressive!",
+                "Splendid!",
+                "Great!",
+                "Phew!"
+            ],
+            praise
+        );
+        // with more guesses than usual, the last ones are all close calls
+        assert_eq!("Phew!", super::praise(english, 8));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let english = Messages::english();
+        assert_eq!(
+            "Tough one! The word was CRANE",
+            consolation(english, "crane")
+        );
+        assert_eq!("Streak: 1 win (best: 4)", streak(english, 1, 4));
+        assert_eq!("Streak: 5 wins (best: 5)", streak(english, 5, 5));
+        assert_eq!("That ends your streak of 1 win", streak_ended(english, 1));
+        assert_eq!("That ends your streak of 3 wins", streak_ended(english, 3));
+    }
+}
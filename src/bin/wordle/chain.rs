@@ -0,0 +1,201 @@
+//! Playing a chain of games, each starting with the last one's answer
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{chain::ChainedWordle, messages::Messages, GameStatus};
+
+use crate::{
+    command::{self, Command, Input},
+    complete::Completions,
+    confirm,
+    prompt::{Prompt, Stop},
+    render::{self, Style},
+    Flow,
+};
+
+/// Play the chain until every game is won, one is lost, or the player skips it.
+///
+/// The game being played is drawn at the start and after every guess, with
+/// its keyboard; once a game is won, the next one is drawn with the answer
+/// already guessed.
+///
+/// Returns [`Flow::Quit`] if the player quit or closed stdin partway through.
+pub fn play(chain: &mut ChainedWordle<'_>, prompt: &mut dyn Prompt, style: &Style) -> Flow {
+    prompt.set_completions(Completions::new(
+        chain.current().guess_list().iter().copied(),
+    ));
+    let messages = style.messages();
+    // the game and number of guesses the board was last drawn for
+    let mut drawn = None;
+
+    loop {
+        let turn = (chain.number(), chain.current().history().len());
+        if drawn != Some(turn) {
+            draw(chain, style);
+            drawn = Some(turn);
+        }
+        if chain.status() != GameStatus::InProgress {
+            break;
+        }
+
+        let game = chain.current();
+        let text = messages.format(
+            "prompt-chain-guess",
+            &[
+                ("number", chain.number().into()),
+                ("games", chain.length().into()),
+                ("turn", (game.history().len() + 1).into()),
+                ("max", game.max_guesses().into()),
+            ],
+        );
+        let text = format!("{} ", text);
+        let line = match prompt.read(&text) {
+            Ok(line) => line,
+            Err(Stop::End) => return Flow::Quit,
+            Err(Stop::Interrupted) => return Flow::Interrupted,
+        };
+        match command::parse(&line) {
+            Input::Guess(guess) => {
+                let guess = guess.to_lowercase();
+                if !guess.is_empty() {
+                    prompt.remember(&guess);
+                }
+                if let Err(e) = chain.guess(&guess) {
+                    println!("{}", render::rejection(messages, &guess, &e));
+                }
+            }
+            Input::Command(Command::Quit) => return Flow::Quit,
+            Input::Command(Command::Skip) => chain.give_up(),
+            Input::Command(Command::GiveUp) => {
+                match confirm(prompt, &messages.format("prompt-give-up", &[])) {
+                    Ok(true) => chain.give_up(),
+                    Ok(false) | Err(Stop::End) => {}
+                    Err(Stop::Interrupted) => return Flow::Interrupted,
+                }
+            }
+            Input::Command(Command::Hint) => println!("{}", messages.format("chain-no-hints", &[])),
+            Input::Command(Command::Copy) => {
+                println!("{}", messages.format("chain-copy-later", &[]))
+            }
+            Input::Command(Command::Save(_)) => {
+                println!("{}", messages.format("chain-no-save", &[]))
+            }
+            Input::Unknown(command) => println!(
+                "{}",
+                messages.format("unknown-command", &[("command", command.into())])
+            ),
+        }
+    }
+    Flow::Continue
+}
+
+/// How the chain ended, and how each game played went
+pub fn summary(messages: &Messages, chain: &ChainedWordle<'_>) -> String {
+    let games = ("games", chain.length().into());
+    let mut lines = vec![match chain.status() {
+        GameStatus::Won { guesses } => {
+            messages.format("chain-won", &[games, ("guesses", guesses.into())])
+        }
+        _ => messages.format("chain-lost", &[("solved", chain.solved().into()), games]),
+    }];
+    for (i, (game, answer)) in chain.games().iter().zip(chain.answers()).enumerate() {
+        let number = ("number", (i + 1).into());
+        let answer = ("answer", answer.to_uppercase().into());
+        lines.push(match game.status() {
+            GameStatus::Won { guesses } => messages.format(
+                "chain-solved-in",
+                &[
+                    number,
+                    answer,
+                    ("guesses", guesses.into()),
+                    ("max", game.max_guesses().into()),
+                ],
+            ),
+            _ => messages.format("chain-not-solved", &[number, answer]),
+        });
+    }
+    lines.join("\n")
+}
+
+/// Draw the game being played, with how far along the chain it is, and its keyboard
+fn draw(chain: &ChainedWordle<'_>, style: &Style) {
+    if style.clear() {
+        print!("{}", render::CLEAR_SCREEN);
+    }
+    let game = chain.current();
+    println!("{}\n{}", status(style.messages(), chain), style.turn(game));
+    if !game.history().is_empty() && game.status() == GameStatus::InProgress {
+        println!("{}", style.keyboard(&game.keyboard(), game.alphabet()));
+    }
+}
+
+/// The line above the board, saying which game it is and what it started with
+fn status(messages: &Messages, chain: &ChainedWordle<'_>) -> String {
+    let number = ("number", chain.number().into());
+    let games = ("games", chain.length().into());
+    match chain.carried_over() {
+        Some(word) => messages.format(
+            "chain-game-carried-over",
+            &[number, games, ("answer", word.to_uppercase().into())],
+        ),
+        None => messages.format("chain-game", &[number, games]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::Wordle;
+
+    const WORDS: [&str; 4] = ["crane", "slate", "trace", "share"];
+
+    #[test]
+    fn test_summary() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let mut chain = ChainedWordle::new(&template, &["crane", "slate", "trace"]).unwrap();
+        chain.guess("slate").unwrap();
+        chain.guess("crane").unwrap();
+        assert_eq!(
+            "Game 2 of 3, starting with the last answer, CRANE",
+            status(Messages::english(), &chain)
+        );
+        chain.give_up();
+        assert_eq!(
+            "Solved 1 of 3 games\nGame 1: CRANE, solved in 2/6\nGame 2: SLATE, not solved",
+            summary(Messages::english(), &chain)
+        );
+
+        let mut chain = ChainedWordle::new(&template, &["crane", "slate"]).unwrap();
+        chain.guess("crane").unwrap();
+        chain.guess("slate").unwrap();
+        assert_eq!(
+            "Solved all 2 games with 2 guesses of your own!\nGame 1: CRANE, solved in 1/6\nGame 2: SLATE, solved in 2/6",
+            summary(Messages::english(), &chain)
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let mut chain = ChainedWordle::new(&template, &["crane", "slate"]).unwrap();
+        let mut prompt = crate::prompt::Lines::new("crane\n:hint\nSlate\n".as_bytes(), Vec::new());
+        assert_eq!(
+            Flow::Continue,
+            play(&mut chain, &mut prompt, &Style::new(false))
+        );
+        assert_eq!(GameStatus::Won { guesses: 2 }, chain.status());
+    }
+}
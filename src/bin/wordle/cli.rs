@@ -0,0 +1,915 @@
+//! Command-line arguments for the Wordle game
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use wordle::{opponent::Difficulty, share::TileSet};
+
+use crate::{boards, lang};
+
+/// Play Wordle in the terminal
+#[derive(Debug, PartialEq, Eq, Parser)]
+#[command(version, about)]
+pub struct Args {
+    /// File with the list of acceptable guesses, one per line [default: guesses.txt]
+    #[arg(long, value_name = "PATH")]
+    pub guesses: Option<PathBuf>,
+
+    /// File with the list of answers, one per line [default: answers.txt]
+    #[arg(long, value_name = "PATH")]
+    pub answers: Option<PathBuf>,
+
+    /// Use the built-in word lists, even if the word list files exist
+    #[arg(long, conflicts_with_all = ["guesses", "answers", "lang"])]
+    pub builtin_words: bool,
+
+    /// Play in another language, with the word lists guesses.CODE.txt and answers.CODE.txt
+    /// from the dictionary directory; `--lang list` lists the languages there
+    #[arg(long, value_name = "CODE", value_parser = parse_lang, conflicts_with_all = ["guesses", "answers"])]
+    pub lang: Option<String>,
+
+    /// Where to look for the word lists for --lang [default: the directory of the guess list]
+    #[arg(long, value_name = "PATH")]
+    pub dict_dir: Option<PathBuf>,
+
+    /// The number of letters in each word; the word lists must match [default: 5]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(4..=7))]
+    pub length: Option<u8>,
+
+    /// Play this many games, then exit (default: keep playing)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub games: Option<u32>,
+
+    /// Play today's puzzle, the same for everyone with the same answer list
+    #[arg(long, conflicts_with = "games")]
+    pub daily: bool,
+
+    /// Seed the choice of words, so the same seed plays the same words (default: random)
+    #[arg(long, value_name = "N", conflicts_with = "daily")]
+    pub seed: Option<u64>,
+
+    /// Start from this game of the seed's session, e.g. `--seed 42 --game 7`
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with_all = ["daily", "answer", "resume", "boards"])]
+    pub game: Option<u32>,
+
+    /// Play the daily puzzle for another day; it doesn't count towards your stats
+    #[arg(long, value_name = "YYYY-MM-DD", requires = "daily")]
+    pub date: Option<NaiveDate>,
+
+    /// Use the date in UTC, rather than in the local time zone, to pick today's puzzle
+    #[arg(long, requires = "daily", conflicts_with = "date")]
+    pub utc: bool,
+
+    /// Print how long it is until the next daily puzzle, and exit
+    #[arg(long, requires = "daily", conflicts_with = "date")]
+    pub when: bool,
+
+    /// Play in hard mode: revealed hints must be used in subsequent guesses
+    #[arg(long)]
+    pub hard: bool,
+
+    /// Open every game with this guess, played automatically
+    #[arg(long, value_name = "WORD", conflicts_with_all = ["batch", "boards"])]
+    pub first_guess: Option<String>,
+
+    /// Let a word be guessed again, using up a turn, rather than asking for another guess
+    #[arg(long)]
+    pub strict_repeats: bool,
+
+    /// Time each game, showing the time so far at each prompt
+    #[arg(long, conflicts_with_all = ["tui", "batch"])]
+    pub timed: bool,
+
+    /// Forfeit a timed game if a guess is made after this many seconds
+    #[arg(long, value_name = "SECONDS", requires = "timed", value_parser = clap::value_parser!(u64).range(1..))]
+    pub time_limit: Option<u64>,
+
+    /// Use orange and blue instead of green and yellow, including in the share
+    /// text; the same as `--theme high-contrast`
+    #[arg(long)]
+    pub colorblind: bool,
+
+    /// The colors of the tiles, and the share tiles to match: `classic`,
+    /// `high-contrast`, or a TOML theme file
+    #[arg(long, value_name = "NAME|PATH")]
+    pub theme: Option<String>,
+
+    /// The language to describe guesses and explain rejections in: `en`, `fr`,
+    /// `de`, or a Fluent `.ftl` file; by default, the one `LANG` names
+    #[arg(long, value_name = "CODE|PATH")]
+    pub locale: Option<String>,
+
+    /// Show how many answers are left and the best guesses to try; these games are marked as assisted
+    #[arg(long, conflicts_with_all = ["tui", "batch"])]
+    pub assist: bool,
+
+    /// In assist mode, search for the best guesses again rather than reusing
+    /// the searches kept from earlier games
+    #[arg(long, requires = "assist")]
+    pub no_cache: bool,
+
+    /// In assist mode, explain each suggestion: how many answers it's likely
+    /// to leave, and the colors it's likeliest to get
+    #[arg(long, requires = "assist")]
+    pub explain: bool,
+
+    /// Show how many answers are still possible after each guess; `always` shows it
+    /// for the daily puzzle too, where it's left out by default
+    #[arg(long, value_enum, value_name = "WHEN", num_args = 0..=1, default_missing_value = "auto", conflicts_with_all = ["tui", "batch"])]
+    pub show_remaining: Option<Remaining>,
+
+    /// Clear the screen before drawing the board, so it stays in place; only when stdout is a terminal
+    #[arg(long, conflicts_with_all = ["tui", "batch"])]
+    pub clear: bool,
+
+    /// Play 2 or 4 boards at once with the same guesses, like Quordle; these
+    /// games don't count towards your stats
+    #[arg(long, value_name = "N", value_parser = parse_boards, conflicts_with_all = ["daily", "answer", "tui", "batch", "resume", "assist", "timed"])]
+    pub boards: Option<usize>,
+
+    /// Play a chain of N games, like Hurdle, where each game after the first
+    /// starts with the last one's answer already guessed; these games don't
+    /// count towards your stats
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(2..), conflicts_with_all = ["boards", "daily", "answer", "tui", "batch", "resume", "assist", "timed", "game"])]
+    pub chain: Option<u32>,
+
+    /// The number of guesses in the last game of a chain, counting the one
+    /// played for you (default: as many as the other games)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(2..), requires = "chain")]
+    pub final_guesses: Option<u8>,
+
+    /// Play a reverse puzzle made from today's answer: the answer and the
+    /// colors of each guess are shown, and you find words that give those colors
+    #[arg(long, conflicts_with_all = ["boards", "chain", "daily", "answer", "games", "tui", "batch", "resume", "assist", "timed", "game"])]
+    pub reverse: bool,
+
+    /// Race a computer player against the same word; it makes a guess after
+    /// each of yours, and only its colors are shown. `easy` guesses words that
+    /// could be the answer, and `hard` the most informative ones
+    #[arg(long, value_enum, value_name = "LEVEL", num_args = 0..=1, default_missing_value = "hard", conflicts_with_all = ["boards", "chain", "reverse", "tui", "batch", "resume"])]
+    pub vs_bot: Option<Bot>,
+
+    /// Describe each guess and the keyboard in sentences for screen readers,
+    /// rather than drawing them; this turns off colors too
+    #[arg(long, conflicts_with = "tui")]
+    pub accessible: bool,
+
+    /// Draw each tile as a big box, three lines high, e.g. for streaming; the
+    /// usual board is drawn if the terminal is too narrow
+    #[arg(long, conflicts_with_all = ["accessible", "tui", "batch"])]
+    pub big: bool,
+
+    /// Play full-screen, typing guesses straight into the board
+    #[arg(long)]
+    pub tui: bool,
+
+    /// In the full-screen view, show each guess's colors straight away rather
+    /// than flipping its tiles over one by one
+    #[arg(long)]
+    pub no_animations: bool,
+
+    /// Play a single game against this word, e.g. to practice; it doesn't count towards your stats
+    #[arg(long, value_name = "WORD", conflicts_with_all = ["daily", "games"])]
+    pub answer: Option<String>,
+
+    /// Allow an --answer that isn't in the guess list
+    #[arg(long, requires = "answer")]
+    pub allow_any_answer: bool,
+
+    /// Play one game without prompts, reading guesses from stdin and printing one result per line
+    #[arg(long, conflicts_with_all = ["tui", "games", "daily"])]
+    pub batch: bool,
+
+    /// Count invalid guesses as turns in batch mode
+    #[arg(long, requires = "batch")]
+    pub strict: bool,
+
+    /// Continue a saved game: the one saved when Ctrl-C was last pressed, or a
+    /// transcript saved with `:save`; a game that's over is just summed up
+    #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with_all = ["daily", "answer", "batch"])]
+    pub resume: Option<Option<PathBuf>>,
+
+    /// Save a transcript of every game to this directory once it's over; `:save` saves one there too
+    #[arg(long, value_name = "DIR", conflicts_with = "batch")]
+    pub save_transcripts: Option<PathBuf>,
+
+    /// Put your daily puzzle results on the leaderboard of the wordle-server at
+    /// this URL, and show the leaderboard after; results that can't be sent
+    /// are kept, and sent next time. A server run with --daily-secret picks
+    /// its own answers, so nothing is sent to it
+    #[arg(long, value_name = "URL", value_parser = parse_server, conflicts_with = "batch")]
+    pub server: Option<String>,
+
+    /// Don't praise wins, commiserate over losses, or show streaks after each game
+    #[arg(long)]
+    pub quiet: bool,
+
+    /// File with a `word<TAB>definition` line for each word, to show the
+    /// answer's definition once a game is over
+    #[arg(long, value_name = "PATH")]
+    pub definitions: Option<PathBuf>,
+
+    /// Copy the share text to the clipboard after each game; `:copy` copies it
+    /// when asked whether to play again
+    #[arg(long, conflicts_with = "batch")]
+    pub copy_share: bool,
+
+    /// Don't print the share text after each game
+    #[arg(long)]
+    pub no_share: bool,
+
+    /// The squares to use in the share text (default: classic, or ascii without colors)
+    #[arg(long, value_enum)]
+    pub tiles: Option<Tiles>,
+
+    /// Don't use colors or emoji; this is also the default if NO_COLOR is set or stdout isn't a terminal
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Print your statistics and exit without playing
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Print your statistics as JSON and exit without playing
+    #[arg(long, conflicts_with = "stats")]
+    pub stats_json: bool,
+
+    /// Write a row for every game you've finished to a file and exit without
+    /// playing; the format is chosen by the file's extension, .csv or .json
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stats", "stats_json"])]
+    pub export_stats: Option<PathBuf>,
+
+    /// The format for --export-stats, whatever the file's extension
+    #[arg(long, value_enum, requires = "export_stats")]
+    pub format: Option<ExportFormat>,
+
+    /// Fold the stats in another stats file, e.g. from another computer, into
+    /// yours (for hard mode with --hard) and exit without playing; games in both
+    /// are only counted once
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["stats", "stats_json", "export_stats"]
+    )]
+    pub merge_stats: Option<PathBuf>,
+
+    /// Save the settings in effect, including these flags, to the config file and exit
+    #[arg(long)]
+    pub write_config: bool,
+}
+
+/// Check a `--lang` code, so it can only name files in the dictionary directory
+fn parse_lang(code: &str) -> Result<String, String> {
+    if lang::is_code(code) {
+        Ok(code.to_owned())
+    } else {
+        Err(String::from(
+            "language codes can only have letters, digits, '-' and '_'",
+        ))
+    }
+}
+
+/// Check a `--server` URL, which must be HTTP or HTTPS
+fn parse_server(url: &str) -> Result<String, String> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(url.to_owned())
+    } else {
+        Err(String::from(
+            "the server's URL must start with http:// or https://",
+        ))
+    }
+}
+
+/// Check a `--boards` count, which must be one of [`boards::CHOICES`]
+fn parse_boards(count: &str) -> Result<usize, String> {
+    match count.parse() {
+        Ok(count) if boards::CHOICES.contains(&count) => Ok(count),
+        _ => Err(String::from("only 2 or 4 boards can be played at once")),
+    }
+}
+
+/// The choices of [`TileSet`] for the share text
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tiles {
+    /// Green, yellow, and black squares
+    Classic,
+    /// Green, yellow, and white squares
+    Light,
+    /// Orange, blue, and black squares
+    HighContrast,
+    /// Plain ASCII characters
+    Ascii,
+}
+
+/// How well the computer player plays with `--vs-bot`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Bot {
+    /// Guess random words that could still be the answer
+    Easy,
+    /// Guess the most informative word every turn
+    Hard,
+}
+
+impl Bot {
+    /// The opponent's difficulty level
+    pub fn difficulty(self) -> Difficulty {
+        match self {
+            Self::Easy => Difficulty::Easy,
+            Self::Hard => Difficulty::Hard,
+        }
+    }
+}
+
+/// When to show how many answers are still possible
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Remaining {
+    /// In every game but the daily puzzle
+    Auto,
+    /// In every game
+    Always,
+}
+
+/// The formats --export-stats can write
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// Comma-separated values, with a header row
+    Csv,
+    /// A JSON array, with an object for each game
+    Json,
+}
+
+impl ExportFormat {
+    /// The format a file's extension stands for, if it's one of them
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("csv") {
+            Some(Self::Csv)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+}
+
+impl Remaining {
+    /// Whether to show it in a game, which may be the daily puzzle
+    pub fn shown(self, daily: bool) -> bool {
+        self == Self::Always || !daily
+    }
+}
+
+impl Tiles {
+    /// The tile set to use; in colorblind mode, green and yellow become orange and blue
+    pub fn tile_set(self, colorblind: bool) -> TileSet<'static> {
+        match (self, colorblind) {
+            (Self::Classic, false) => TileSet::classic(),
+            (Self::Light, false) => TileSet::light(),
+            (Self::Light, true) => TileSet {
+                not_in_word: TileSet::light().not_in_word,
+                ..TileSet::high_contrast()
+            },
+            (Self::Classic | Self::HighContrast, _) => TileSet::high_contrast(),
+            (Self::Ascii, _) => TileSet::ascii(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(std::iter::once("wordle").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(
+            Args {
+                guesses: None,
+                answers: None,
+                builtin_words: false,
+                lang: None,
+                dict_dir: None,
+                length: None,
+                games: None,
+                daily: false,
+                seed: None,
+                game: None,
+                date: None,
+                utc: false,
+                when: false,
+                hard: false,
+                first_guess: None,
+                strict_repeats: false,
+                timed: false,
+                time_limit: None,
+                colorblind: false,
+                theme: None,
+                locale: None,
+                assist: false,
+                no_cache: false,
+                explain: false,
+                show_remaining: None,
+                clear: false,
+                boards: None,
+                chain: None,
+                final_guesses: None,
+                reverse: false,
+                vs_bot: None,
+                accessible: false,
+                big: false,
+                tui: false,
+                no_animations: false,
+                answer: None,
+                allow_any_answer: false,
+                batch: false,
+                strict: false,
+                resume: None,
+                save_transcripts: None,
+                server: None,
+                quiet: false,
+                definitions: None,
+                copy_share: false,
+                no_share: false,
+                tiles: None,
+                no_color: false,
+                stats: false,
+                stats_json: false,
+                export_stats: None,
+                format: None,
+                merge_stats: None,
+                write_config: false,
+            },
+            parse(&[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_flags() {
+        let args = parse(&[
+            "--guesses",
+            "lists/all.txt",
+            "--answers=lists/answers.txt",
+            "--length",
+            "6",
+            "--games",
+            "3",
+            "--seed",
+            "1337",
+            "--hard",
+            "--first-guess",
+            "CRANE",
+            "--strict-repeats",
+            "--colorblind",
+            "--theme",
+            "themes/solarized.toml",
+            "--locale",
+            "fr",
+            "--tui",
+            "--save-transcripts",
+            "games",
+            "--quiet",
+            "--definitions",
+            "defs.tsv",
+            "--copy-share",
+            "--no-share",
+            "--tiles",
+            "high-contrast",
+            "--no-color",
+            "--stats",
+            "--write-config",
+        ])
+        .unwrap();
+        assert_eq!(Some(PathBuf::from("lists/all.txt")), args.guesses);
+        assert_eq!(Some(PathBuf::from("lists/answers.txt")), args.answers);
+        assert_eq!(Some(6), args.length);
+        assert_eq!(Some(3), args.games);
+        assert_eq!(Some(1337), args.seed);
+        assert!(args.hard);
+        assert_eq!(Some("CRANE".to_owned()), args.first_guess);
+        assert!(args.strict_repeats);
+        assert!(args.colorblind);
+        assert_eq!(Some("themes/solarized.toml".to_owned()), args.theme);
+        assert_eq!(Some("fr".to_owned()), args.locale);
+        assert!(args.tui);
+        assert_eq!(Some(PathBuf::from("games")), args.save_transcripts);
+        assert!(args.quiet);
+        assert_eq!(Some(PathBuf::from("defs.tsv")), args.definitions);
+        assert!(args.copy_share);
+        assert!(args.no_share);
+        assert_eq!(Some(Tiles::HighContrast), args.tiles);
+        assert!(args.no_color);
+        assert!(args.stats);
+        assert!(args.write_config);
+    }
+
+    #[test]
+    fn test_daily() {
+        let args = parse(&["--daily", "--date", "2022-01-01"]).unwrap();
+        assert!(args.daily);
+        assert_eq!(NaiveDate::from_ymd_opt(2022, 1, 1), args.date);
+        assert!(parse(&["--daily", "--utc"]).unwrap().utc);
+        assert!(parse(&["--daily", "--when"]).unwrap().when);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::MissingRequiredArgument,
+            kind(&["--date", "2022-01-01"])
+        );
+        assert_eq!(ErrorKind::MissingRequiredArgument, kind(&["--utc"]));
+        assert_eq!(ErrorKind::MissingRequiredArgument, kind(&["--when"]));
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--daily", "--utc", "--date", "2022-01-01"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--daily", "--games", "2"])
+        );
+        assert_eq!(
+            ErrorKind::ValueValidation,
+            kind(&["--daily", "--date", "1/1/2022"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--daily", "--seed", "1"])
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let args = parse(&["--batch", "--answer", "crane", "--strict"]).unwrap();
+        assert!(args.batch);
+        assert_eq!(Some("crane".to_owned()), args.answer);
+        assert!(args.strict);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::MissingRequiredArgument, kind(&["--strict"]));
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--batch", "--tui"]));
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--batch", "--daily"]));
+    }
+
+    #[test]
+    fn test_answer() {
+        let args = parse(&["--answer", "xylyl", "--allow-any-answer"]).unwrap();
+        assert_eq!(Some("xylyl".to_owned()), args.answer);
+        assert!(args.allow_any_answer);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::MissingRequiredArgument,
+            kind(&["--allow-any-answer"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--answer", "crane", "--daily"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--answer", "crane", "--games", "2"])
+        );
+    }
+
+    #[test]
+    fn test_timed() {
+        let args = parse(&["--timed", "--time-limit", "120"]).unwrap();
+        assert!(args.timed);
+        assert_eq!(Some(120), args.time_limit);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::MissingRequiredArgument,
+            kind(&["--time-limit", "60"])
+        );
+        assert_eq!(
+            ErrorKind::ValueValidation,
+            kind(&["--timed", "--time-limit", "0"])
+        );
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--timed", "--tui"]));
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--timed", "--batch"]));
+    }
+
+    #[test]
+    fn test_game() {
+        let args = parse(&["--seed", "42", "--game", "7"]).unwrap();
+        assert_eq!(Some(42), args.seed);
+        assert_eq!(Some(7), args.game);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--game", "0"]));
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--game", "2", "--daily"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--game", "2", "--answer", "crane"])
+        );
+    }
+
+    #[test]
+    fn test_assist() {
+        assert!(parse(&["--assist", "--hard"]).unwrap().assist);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--assist", "--tui"]));
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--assist", "--batch"]));
+
+        assert!(parse(&["--assist", "--no-cache"]).unwrap().no_cache);
+        assert!(parse(&["--assist", "--explain"]).unwrap().explain);
+        assert_eq!(ErrorKind::MissingRequiredArgument, kind(&["--explain"]));
+        assert_eq!(ErrorKind::MissingRequiredArgument, kind(&["--no-cache"]));
+    }
+
+    #[test]
+    fn test_resume() {
+        assert_eq!(None, parse(&[]).unwrap().resume);
+        assert_eq!(Some(None), parse(&["--resume"]).unwrap().resume);
+        assert_eq!(
+            Some(Some(PathBuf::from("games/saved.json"))),
+            parse(&["--resume", "games/saved.json", "--hard"])
+                .unwrap()
+                .resume
+        );
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--resume", "--daily"]));
+    }
+
+    #[test]
+    fn test_accessible() {
+        assert!(parse(&["--accessible", "--clear"]).unwrap().accessible);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--accessible", "--tui"])
+        );
+    }
+
+    #[test]
+    fn test_no_animations() {
+        assert!(!parse(&["--tui"]).unwrap().no_animations);
+        assert!(parse(&["--tui", "--no-animations"]).unwrap().no_animations);
+    }
+
+    #[test]
+    fn test_big() {
+        assert!(parse(&["--big", "--clear"]).unwrap().big);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--big", "--accessible"])
+        );
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--big", "--tui"]));
+    }
+
+    #[test]
+    fn test_clear() {
+        assert!(parse(&["--clear", "--hard"]).unwrap().clear);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--clear", "--tui"]));
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--clear", "--batch"]));
+    }
+
+    #[test]
+    fn test_boards() {
+        assert_eq!(None, parse(&[]).unwrap().boards);
+        assert_eq!(Some(4), parse(&["--boards", "4"]).unwrap().boards);
+        assert_eq!(
+            Some(2),
+            parse(&["--boards=2", "--seed", "7"]).unwrap().boards
+        );
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--boards", "3"]));
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--boards", "many"]));
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--boards", "4", "--daily"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--boards", "4", "--tui"])
+        );
+    }
+
+    #[test]
+    fn test_chain() {
+        assert_eq!(None, parse(&[]).unwrap().chain);
+        let args = parse(&["--chain", "5", "--final-guesses", "4"]).unwrap();
+        assert_eq!(Some(5), args.chain);
+        assert_eq!(Some(4), args.final_guesses);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--chain", "1"]));
+        assert_eq!(
+            ErrorKind::MissingRequiredArgument,
+            kind(&["--final-guesses", "4"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--chain", "3", "--boards", "2"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--chain", "3", "--daily"])
+        );
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert!(!parse(&[]).unwrap().reverse);
+        assert!(parse(&["--reverse", "--utc"]).unwrap().reverse);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--reverse", "--chain", "3"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--reverse", "--answer", "crane"])
+        );
+    }
+
+    #[test]
+    fn test_vs_bot() {
+        assert_eq!(None, parse(&[]).unwrap().vs_bot);
+        assert_eq!(Some(Bot::Hard), parse(&["--vs-bot"]).unwrap().vs_bot);
+        assert_eq!(
+            Some(Bot::Easy),
+            parse(&["--vs-bot=easy", "--hard"]).unwrap().vs_bot
+        );
+        assert_eq!(Difficulty::Easy, Bot::Easy.difficulty());
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::InvalidValue, kind(&["--vs-bot=genius"]));
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--vs-bot", "--boards", "2"])
+        );
+        assert_eq!(ErrorKind::ArgumentConflict, kind(&["--vs-bot", "--tui"]));
+    }
+
+    #[test]
+    fn test_show_remaining() {
+        assert_eq!(None, parse(&[]).unwrap().show_remaining);
+        let args = parse(&["--show-remaining"]).unwrap();
+        assert_eq!(Some(Remaining::Auto), args.show_remaining);
+        let args = parse(&["--show-remaining=always", "--daily"]).unwrap();
+        assert_eq!(Some(Remaining::Always), args.show_remaining);
+
+        assert!(Remaining::Auto.shown(false));
+        assert!(!Remaining::Auto.shown(true));
+        assert!(Remaining::Always.shown(true));
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::InvalidValue,
+            kind(&["--show-remaining=sometimes"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--show-remaining", "--batch"])
+        );
+    }
+
+    #[test]
+    fn test_lang() {
+        let args = parse(&["--lang", "es", "--dict-dir", "dicts"]).unwrap();
+        assert_eq!(Some("es".to_owned()), args.lang);
+        assert_eq!(Some(PathBuf::from("dicts")), args.dict_dir);
+        assert_eq!(
+            Some("list".to_owned()),
+            parse(&["--lang", "list"]).unwrap().lang
+        );
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--lang", "../es"]));
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--lang", "es", "--guesses", "guesses.txt"])
+        );
+    }
+
+    #[test]
+    fn test_server() {
+        let args = parse(&["--daily", "--server", "https://wordle.example.com"]).unwrap();
+        assert_eq!(Some("https://wordle.example.com".to_owned()), args.server);
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::ValueValidation,
+            kind(&["--server", "wordle.example.com"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--server", "http://localhost", "--batch"])
+        );
+    }
+
+    #[test]
+    fn test_tile_set() {
+        assert_eq!(TileSet::classic(), Tiles::Classic.tile_set(false));
+        assert_eq!(TileSet::high_contrast(), Tiles::Classic.tile_set(true));
+        assert_eq!("⬜", Tiles::Light.tile_set(true).not_in_word);
+        assert_eq!("🟧", Tiles::Light.tile_set(true).correct);
+        assert_eq!(TileSet::ascii(), Tiles::Ascii.tile_set(true));
+    }
+
+    #[test]
+    fn test_export_stats() {
+        let args = parse(&["--export-stats", "games.txt", "--format", "csv"]).unwrap();
+        assert_eq!(Some(PathBuf::from("games.txt")), args.export_stats);
+        assert_eq!(Some(ExportFormat::Csv), args.format);
+
+        assert_eq!(
+            Some(ExportFormat::Csv),
+            ExportFormat::from_path(Path::new("games.CSV"))
+        );
+        assert_eq!(
+            Some(ExportFormat::Json),
+            ExportFormat::from_path(Path::new("stats/games.json"))
+        );
+        assert_eq!(None, ExportFormat::from_path(Path::new("games.txt")));
+        assert_eq!(None, ExportFormat::from_path(Path::new("games")));
+
+        use clap::error::ErrorKind;
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(
+            ErrorKind::MissingRequiredArgument,
+            kind(&["--format", "json"])
+        );
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--export-stats", "games.csv", "--stats"])
+        );
+
+        let args = parse(&["--merge-stats", "desktop/stats.json", "--hard"]).unwrap();
+        assert_eq!(Some(PathBuf::from("desktop/stats.json")), args.merge_stats);
+        assert!(args.hard);
+        assert_eq!(
+            ErrorKind::ArgumentConflict,
+            kind(&["--merge-stats", "stats.json", "--export-stats", "games.csv"])
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        use clap::error::ErrorKind;
+
+        let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--games", "0"]));
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--games", "many"]));
+        assert_eq!(ErrorKind::InvalidValue, kind(&["--guesses"]));
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--seed", "many"]));
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--length", "3"]));
+
+        assert_eq!(ErrorKind::ValueValidation, kind(&["--length", "8"]));
+        assert_eq!(ErrorKind::InvalidValue, kind(&["--tiles", "rainbow"]));
+        assert_eq!(ErrorKind::UnknownArgument, kind(&["--easy"]));
+        assert_eq!(ErrorKind::DisplayHelp, kind(&["--help"]));
+        assert_eq!(ErrorKind::DisplayVersion, kind(&["--version"]));
+    }
+}
@@ -0,0 +1,127 @@
+//! Copying the share text to the system clipboard
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::messages::Messages;
+
+/// Somewhere to put text for the player to paste
+pub trait Clipboard {
+    /// Replace the clipboard's contents with `text`
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+}
+
+/// The system clipboard, through whatever the platform provides.
+///
+/// On Linux, the text is only on the clipboard while this is around (unless a
+/// clipboard manager takes it over), so it's kept for the whole session.
+#[cfg(feature = "clipboard")]
+struct System(arboard::Clipboard);
+
+#[cfg(feature = "clipboard")]
+impl Clipboard for System {
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.0.set_text(text).map_err(|e| e.to_string())
+    }
+}
+
+/// A clipboard that can't be used, and why
+#[derive(Debug)]
+struct Unavailable(String);
+
+impl Clipboard for Unavailable {
+    fn set_text(&mut self, _text: &str) -> Result<(), String> {
+        Err(self.0.clone())
+    }
+}
+
+/// The system clipboard; if there isn't one, e.g. without a display, every
+/// copy fails with the reason why
+pub fn system() -> Box<dyn Clipboard> {
+    #[cfg(feature = "clipboard")]
+    match arboard::Clipboard::new() {
+        Ok(clipboard) => Box::new(System(clipboard)),
+        Err(e) => Box::new(Unavailable(e.to_string())),
+    }
+    #[cfg(not(feature = "clipboard"))]
+    Box::new(Unavailable(String::from(
+        "this build doesn't support the clipboard",
+    )))
+}
+
+/// Copy the share text, and get back what to tell the player: that it was
+/// copied, or why it wasn't, along with the text to copy by hand
+pub fn copy(messages: &Messages, clipboard: &mut dyn Clipboard, text: &str) -> String {
+    match clipboard.set_text(text) {
+        Ok(()) => messages.format("copied", &[]),
+        // blank lines around the share text make it easy to select
+        Err(e) => format!(
+            "{}\n\n{}\n",
+            messages.format("copy-failed", &[("error", e.into())]),
+            text
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A clipboard that keeps what's copied, or fails if it's broken
+    #[derive(Debug, Default)]
+    struct Mock {
+        contents: Option<String>,
+        broken: bool,
+    }
+
+    impl Clipboard for Mock {
+        fn set_text(&mut self, text: &str) -> Result<(), String> {
+            if self.broken {
+                return Err(String::from("no display"));
+            }
+            self.contents = Some(text.to_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_copy() {
+        let mut clipboard = Mock::default();
+        assert_eq!(
+            "Copied the share text to the clipboard",
+            copy(Messages::english(), &mut clipboard, "Wordle 3/6")
+        );
+        assert_eq!(Some("Wordle 3/6"), clipboard.contents.as_deref());
+
+        let mut clipboard = Mock {
+            broken: true,
+            ..Mock::default()
+        };
+        assert_eq!(
+            "Could not copy to the clipboard (no display); copy the share text from here instead:\n\nWordle 3/6\n",
+            copy(Messages::english(), &mut clipboard, "Wordle 3/6")
+        );
+        assert_eq!(None, clipboard.contents);
+
+        assert_eq!(
+            "Could not copy to the clipboard (gone); copy the share text from here instead:\n\nWordle 3/6\n",
+            copy(
+                Messages::english(),
+                &mut Unavailable(String::from("gone")),
+                "Wordle 3/6"
+            )
+        );
+    }
+}
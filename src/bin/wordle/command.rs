@@ -0,0 +1,105 @@
+//! Commands that can be typed at the guess prompt instead of a guess
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+/// A command recognized at the guess prompt
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Command<'a> {
+    /// Exit the program
+    Quit,
+    /// Abandon the current word, counting it as a loss
+    Skip,
+    /// Like [`Command::Skip`], but only after the player confirms it
+    GiveUp,
+    /// Reveal something about the word, without using up a guess
+    Hint,
+    /// Save the game's transcript, to this path if one is given
+    Save(Option<&'a str>),
+    /// Copy the share text to the clipboard, once the game is over
+    Copy,
+}
+
+/// A line typed at the guess prompt
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Input<'a> {
+    /// A guess
+    Guess(&'a str),
+    /// A recognized command
+    Command(Command<'a>),
+    /// Something that looks like a command, but isn't one
+    Unknown(&'a str),
+}
+
+/// Parse a line typed at the guess prompt.
+///
+/// Commands start with a `:`; anything else is a guess. Surrounding whitespace
+/// is ignored. Only `:save` takes an argument, separated from it by whitespace.
+pub fn parse(line: &str) -> Input<'_> {
+    let line = line.trim();
+    let command = match line.strip_prefix(':') {
+        Some(command) => command,
+        None => return Input::Guess(line),
+    };
+    let (name, argument) = match command.split_once(char::is_whitespace) {
+        Some((name, argument)) => (name, Some(argument.trim_start())),
+        None => (command, None),
+    };
+
+    match (name.to_lowercase().as_str(), argument) {
+        ("quit" | "q", None) => Input::Command(Command::Quit),
+        ("skip", None) => Input::Command(Command::Skip),
+        ("giveup" | "give-up", None) => Input::Command(Command::GiveUp),
+        ("hint", None) => Input::Command(Command::Hint),
+        ("copy", None) => Input::Command(Command::Copy),
+        ("save", path) => Input::Command(Command::Save(path)),
+        _ => Input::Unknown(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Input::Guess("crane"), parse("crane\n"));
+        assert_eq!(Input::Guess("crane"), parse("  crane\r\n"));
+        assert_eq!(Input::Guess(""), parse("\n"));
+
+        assert_eq!(Input::Command(Command::Quit), parse(":quit"));
+        assert_eq!(Input::Command(Command::Quit), parse(":q\n"));
+        assert_eq!(Input::Command(Command::Quit), parse(" :Q "));
+        assert_eq!(Input::Command(Command::Skip), parse(":skip"));
+        assert_eq!(Input::Command(Command::Hint), parse(":hint"));
+        assert_eq!(Input::Command(Command::Copy), parse(":copy"));
+        assert_eq!(Input::Command(Command::GiveUp), parse(":giveup"));
+        assert_eq!(Input::Command(Command::GiveUp), parse(":give-up"));
+
+        assert_eq!(Input::Unknown(":quiet"), parse(":quiet"));
+        assert_eq!(Input::Unknown(":"), parse(":"));
+        assert_eq!(Input::Unknown(": q"), parse(": q"));
+        assert_eq!(Input::Unknown(":quit now"), parse(":quit now"));
+    }
+
+    #[test]
+    fn test_save() {
+        assert_eq!(Input::Command(Command::Save(None)), parse(":save\n"));
+        assert_eq!(
+            Input::Command(Command::Save(Some("Games/today.json"))),
+            parse(":SAVE   Games/today.json \n")
+        );
+    }
+}
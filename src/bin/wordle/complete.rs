@@ -0,0 +1,149 @@
+//! Completing guesses with Tab at the prompt
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use rustyline::{
+    completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator, Context,
+    Helper,
+};
+
+/// The words a guess can be completed to, kept sorted so the ones starting
+/// with a prefix can be found with a binary search
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Completions {
+    /// The words, in order and without duplicates
+    words: Vec<String>,
+}
+
+impl Completions {
+    /// Complete guesses to any of `words`
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = S>) -> Self {
+        let mut words: Vec<String> = words.into_iter().map(Into::into).collect();
+        words.sort_unstable();
+        words.dedup();
+        Self { words }
+    }
+
+    /// The words starting with `prefix`, in order.
+    ///
+    /// A guess has to be started before it can be completed, so there are none
+    /// for an empty prefix.
+    pub fn starting_with(&self, prefix: &str) -> &[String] {
+        if prefix.is_empty() {
+            return &[];
+        }
+        // the words starting with the prefix come straight after the ones before it
+        let start = self.words.partition_point(|word| word.as_str() < prefix);
+        let count = self.words[start..].partition_point(|word| word.starts_with(prefix));
+        &self.words[start..start + count]
+    }
+}
+
+impl Completer for Completions {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let typed = &line[..pos];
+        let prefix = typed.trim_start();
+        let start = typed.len() - prefix.len();
+        // commands aren't guesses, so they aren't completed
+        if prefix.starts_with(':') {
+            return Ok((start, Vec::new()));
+        }
+        Ok((start, self.starting_with(&prefix.to_lowercase()).to_vec()))
+    }
+}
+
+impl Hinter for Completions {
+    type Hint = String;
+}
+
+impl Highlighter for Completions {}
+
+impl Validator for Completions {}
+
+impl Helper for Completions {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::MemHistory;
+
+    const WORDS: [&str; 7] = ["slate", "crane", "crate", "cramp", "crane", "trace", "cr"];
+
+    #[test]
+    fn test_starting_with() {
+        let completions = Completions::new(WORDS);
+        assert_eq!(
+            ["cr", "cramp", "crane", "crate"],
+            completions.starting_with("cr")
+        );
+        assert_eq!(
+            ["cramp", "crane", "crate"],
+            completions.starting_with("cra")
+        );
+        assert_eq!(["crane"], completions.starting_with("cran"));
+        assert_eq!(["crane"], completions.starting_with("crane"));
+        assert_eq!(["trace"], completions.starting_with("t"));
+        assert!(completions.starting_with("x").is_empty());
+        assert!(completions.starting_with("cranes").is_empty());
+        assert!(completions.starting_with("").is_empty());
+        assert!(Completions::default().starting_with("c").is_empty());
+    }
+
+    #[test]
+    fn test_complete() {
+        let completions = Completions::new(WORDS);
+        let history = MemHistory::new();
+        let ctx = Context::new(&history);
+        assert_eq!(
+            (2, vec!["crate".to_owned()]),
+            completions.complete("  CRAT", 6, &ctx).unwrap()
+        );
+        // only the part before the cursor is completed
+        assert_eq!(
+            (0, vec!["slate".to_owned()]),
+            completions.complete("slxyz", 2, &ctx).unwrap()
+        );
+        assert_eq!(
+            (0, Vec::<String>::new()),
+            completions.complete(":q", 2, &ctx).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let words = ["crane", "crate", "cramp", "slate", "trace"];
+        let mut game = wordle::Wordle::builder()
+            .guesses(&words)
+            .answers(&words)
+            .hard_mode(true)
+            .build()
+            .unwrap()
+            .set_answer("crate")
+            .unwrap();
+        game.guess("crane").unwrap();
+
+        // "cramp" doesn't keep the green "e", and "crane" has been played
+        let completions = Completions::new(game.playable_guesses());
+        assert_eq!(["crate"], completions.starting_with("cr"));
+    }
+}
@@ -0,0 +1,352 @@
+//! Settings kept in a config file, so they don't need to be passed every time
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{Args, Remaining, Tiles};
+
+/// The keys a config file can have; any others are ignored with a warning
+const KEYS: [&str; 14] = [
+    "guesses",
+    "answers",
+    "dict-dir",
+    "hard",
+    "colorblind",
+    "theme",
+    "locale",
+    "tiles",
+    "length",
+    "utc",
+    "stats-dir",
+    "show-remaining",
+    "definitions",
+    "first-guess",
+];
+
+/// The settings for the game.
+///
+/// These start as the built-in defaults, are overridden by the config file,
+/// and then by the command-line flags.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// File with the list of acceptable guesses
+    pub guesses: PathBuf,
+    /// File with the list of answers
+    pub answers: PathBuf,
+    /// Where to look for the word lists for `--lang`, or `None` for the
+    /// directory of the guess list
+    pub dict_dir: Option<PathBuf>,
+    /// Whether to play in hard mode
+    pub hard: bool,
+    /// Whether to use orange and blue instead of green and yellow
+    pub colorblind: bool,
+    /// The name of a built-in theme, or the path to a theme file, or `None`
+    /// for the classic theme (or the high contrast one, if colorblind)
+    pub theme: Option<String>,
+    /// The code of a built-in locale, or the path to a `.ftl` file, or `None`
+    /// for the one `LANG` names
+    pub locale: Option<String>,
+    /// The squares to use in the share text, or `None` to choose based on the terminal
+    pub tiles: Option<Tiles>,
+    /// The number of letters in each word
+    pub length: u8,
+    /// Whether to pick the daily puzzle by the date in UTC
+    pub utc: bool,
+    /// Where to keep stats, or `None` for the platform's data directory
+    pub stats_dir: Option<PathBuf>,
+    /// When to show how many answers are still possible, or `None` to never show it
+    pub show_remaining: Option<Remaining>,
+    /// File with a definition for each word, to show the answer's once a game is over
+    pub definitions: Option<PathBuf>,
+    /// The word to open every game with, played automatically
+    pub first_guess: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            guesses: "guesses.txt".into(),
+            answers: "answers.txt".into(),
+            dict_dir: None,
+            hard: false,
+            colorblind: false,
+            theme: None,
+            locale: None,
+            tiles: None,
+            length: 5,
+            utc: false,
+            stats_dir: None,
+            show_remaining: None,
+            definitions: None,
+            first_guess: None,
+        }
+    }
+}
+
+impl Config {
+    /// Read settings from TOML, getting back the unknown keys too.
+    ///
+    /// Settings that aren't given keep their defaults.
+    pub fn parse(text: &str) -> Result<(Self, Vec<String>), String> {
+        let table: toml::Table = toml::from_str(text).map_err(|e| e.message().to_owned())?;
+        let unknown = table
+            .keys()
+            .filter(|key| !KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+
+        let config: Self = table
+            .try_into()
+            .map_err(|e: toml::de::Error| e.message().to_owned())?;
+        if !(4..=7).contains(&config.length) {
+            return Err(format!(
+                "length must be between 4 and 7, not {}",
+                config.length
+            ));
+        }
+        Ok((config, unknown))
+    }
+
+    /// Read settings from a file; if it doesn't exist, everything is the default.
+    ///
+    /// A file that isn't a valid config is an [`ErrorKind::InvalidData`] error.
+    pub fn load(path: &Path) -> io::Result<(Self, Vec<String>)> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok((Self::default(), Vec::new())),
+            Err(e) => return Err(e),
+        };
+        Self::parse(&text).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Write the settings to a file, creating its directory if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+    /// Override the settings with the ones given on the command line.
+    ///
+    /// Flags can only turn settings on; e.g. hard mode can't be turned off
+    /// with a flag if the config file turns it on.
+    pub fn merge(mut self, args: &Args) -> Self {
+        if let Some(guesses) = &args.guesses {
+            self.guesses = guesses.clone();
+        }
+        if let Some(answers) = &args.answers {
+            self.answers = answers.clone();
+        }
+        if let Some(dict_dir) = &args.dict_dir {
+            self.dict_dir = Some(dict_dir.clone());
+        }
+        self.hard |= args.hard;
+        self.colorblind |= args.colorblind;
+        if let Some(theme) = &args.theme {
+            self.theme = Some(theme.clone());
+        }
+        if let Some(locale) = &args.locale {
+            self.locale = Some(locale.clone());
+        }
+        self.tiles = args.tiles.or(self.tiles);
+        self.length = args.length.unwrap_or(self.length);
+        self.utc |= args.utc;
+        self.show_remaining = args.show_remaining.or(self.show_remaining);
+        if let Some(definitions) = &args.definitions {
+            self.definitions = Some(definitions.clone());
+        }
+        if let Some(first_guess) = &args.first_guess {
+            self.first_guess = Some(first_guess.clone());
+        }
+        self
+    }
+
+    /// Where to look for the word lists for `--lang`
+    pub fn dict_dir(&self) -> &Path {
+        match (&self.dict_dir, self.guesses.parent()) {
+            (Some(dir), _) => dir,
+            (None, Some(dir)) if dir != Path::new("") => dir,
+            (None, _) => Path::new("."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse_from(std::iter::once("wordle").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!((Config::default(), Vec::new()), Config::parse("").unwrap());
+
+        let (config, unknown) = Config::parse(
+            r#"
+            guesses = "/usr/share/wordle/guesses.txt"
+            hard = true
+            tiles = "high-contrast"
+            stats-dir = "/tmp/stats"
+            font = "mono"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            Config {
+                guesses: "/usr/share/wordle/guesses.txt".into(),
+                hard: true,
+                tiles: Some(Tiles::HighContrast),
+                stats_dir: Some("/tmp/stats".into()),
+                ..Config::default()
+            },
+            config
+        );
+        assert_eq!(vec!["font".to_owned()], unknown);
+
+        assert!(Config::parse("hard = \"yes\"").is_err());
+        assert!(Config::parse("length = 9").is_err());
+        assert!(Config::parse("not toml").is_err());
+    }
+
+    #[test]
+    fn test_keys() {
+        // every setting is a known key, so saved configs load without warnings
+        let config = Config {
+            dict_dir: Some("dicts".into()),
+            theme: Some("high-contrast".into()),
+            locale: Some("fr".into()),
+            tiles: Some(Tiles::Ascii),
+            stats_dir: Some("stats".into()),
+            show_remaining: Some(Remaining::Auto),
+            definitions: Some("defs.tsv".into()),
+            first_guess: Some("crane".into()),
+            ..Config::default()
+        };
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!((config, Vec::new()), Config::parse(&text).unwrap());
+        assert_eq!(KEYS.len(), text.lines().count());
+    }
+
+    #[test]
+    fn test_precedence() {
+        let (file, _) = Config::parse(
+            r#"
+            guesses = "config-guesses.txt"
+            length = 6
+            tiles = "light"
+            colorblind = true
+            theme = "mine.toml"
+            locale = "de"
+            show-remaining = "auto"
+            first-guess = "slate"
+            "#,
+        )
+        .unwrap();
+
+        // the built-in defaults are used when nothing else is given
+        assert_eq!(Config::default(), Config::default().merge(&args(&[])));
+
+        // the config file overrides the defaults
+        let config = file.clone().merge(&args(&[]));
+        assert_eq!(PathBuf::from("config-guesses.txt"), config.guesses);
+        assert_eq!(PathBuf::from("answers.txt"), config.answers);
+        assert_eq!(6, config.length);
+        assert_eq!(Some(Tiles::Light), config.tiles);
+        assert!(config.colorblind);
+        assert_eq!(Some("mine.toml".to_owned()), config.theme);
+        assert_eq!(Some("de".to_owned()), config.locale);
+        assert!(!config.hard);
+        assert_eq!(Some(Remaining::Auto), config.show_remaining);
+        assert_eq!(Some("slate".to_owned()), config.first_guess);
+
+        // and flags override the config file
+        let config = file.merge(&args(&[
+            "--guesses",
+            "flag-guesses.txt",
+            "--length",
+            "7",
+            "--tiles",
+            "ascii",
+            "--hard",
+            "--theme",
+            "classic",
+            "--locale",
+            "fr",
+            "--show-remaining=always",
+            "--first-guess",
+            "crane",
+        ]));
+        assert_eq!(PathBuf::from("flag-guesses.txt"), config.guesses);
+        assert_eq!(7, config.length);
+        assert_eq!(Some(Tiles::Ascii), config.tiles);
+        assert!(config.colorblind, "flags can't turn settings off");
+        assert_eq!(Some("classic".to_owned()), config.theme);
+        assert_eq!(Some("fr".to_owned()), config.locale);
+        assert!(config.hard);
+        assert_eq!(Some(Remaining::Always), config.show_remaining);
+        assert_eq!(Some("crane".to_owned()), config.first_guess);
+    }
+
+    #[test]
+    fn test_dict_dir() {
+        assert_eq!(Path::new("."), Config::default().dict_dir());
+
+        let (config, _) = Config::parse("guesses = \"/usr/share/wordle/guesses.txt\"").unwrap();
+        assert_eq!(Path::new("/usr/share/wordle"), config.dict_dir());
+        let config = config.merge(&args(&["--dict-dir", "dicts"]));
+        assert_eq!(Path::new("dicts"), config.dict_dir());
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir().join(format!("wordle-config-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("config.toml");
+
+        assert_eq!(
+            (Config::default(), Vec::new()),
+            Config::load(&path).unwrap()
+        );
+        let config = Config {
+            hard: true,
+            length: 6,
+            ..Config::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!((config, Vec::new()), Config::load(&path).unwrap());
+
+        fs::write(&path, "length = \"six\"").unwrap();
+        assert_eq!(
+            ErrorKind::InvalidData,
+            Config::load(&path).unwrap_err().kind()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+//! Saving transcripts of games, to look back on or pick up again later
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, File},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use wordle::transcript::Transcript;
+
+/// The most numbers tried after a transcript's name before giving up
+const MAX_SUFFIX: u32 = 1000;
+
+/// The name a transcript is saved under, e.g. `wordle-2022-01-31-226.json`
+/// for a daily puzzle, or `wordle-2022-01-31-1337.json` for the seed of a random game.
+///
+/// A game with neither is a practice game, e.g. `wordle-2022-01-31-practice.json`.
+pub fn file_name(date: NaiveDate, transcript: &Transcript) -> String {
+    let game = match (transcript.puzzle, transcript.seed) {
+        (Some(puzzle), _) => puzzle.to_string(),
+        (None, Some(seed)) => seed.to_string(),
+        (None, None) => String::from("practice"),
+    };
+    format!("wordle-{}-{}.json", date, game)
+}
+
+/// Write a transcript to `path`, in the same format `--resume` reads.
+///
+/// An existing file is never overwritten; that's an [`ErrorKind::AlreadyExists`] error.
+pub fn write(path: &Path, transcript: &Transcript) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(transcript)?;
+    let mut file = File::create_new(path)?;
+    file.write_all(json.as_bytes())
+}
+
+/// Write a transcript to `dir` under its [`file_name`], creating the directory
+/// if needed, and get back where it was written.
+///
+/// If a file already has that name, a number is added to it, e.g.
+/// `wordle-2022-01-31-226-2.json`; if too many do, that's a bare
+/// [`ErrorKind::AlreadyExists`] error, for the caller to explain in the
+/// player's language.
+pub fn write_in(dir: &Path, date: NaiveDate, transcript: &Transcript) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let name = file_name(date, transcript);
+    let stem = name.trim_end_matches(".json");
+    for n in 1..=MAX_SUFFIX {
+        let path = match n {
+            1 => dir.join(&name),
+            n => dir.join(format!("{}-{}.json", stem, n)),
+        };
+        match write(&path, transcript) {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            result => return result.map(|_| path),
+        }
+    }
+    Err(ErrorKind::AlreadyExists.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::Wordle;
+
+    fn transcript(puzzle: Option<u32>, seed: Option<u64>) -> Transcript {
+        let words = ["crane", "slate"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        game.guess("slate").unwrap();
+        Transcript {
+            puzzle,
+            seed,
+            ..game.transcript()
+        }
+    }
+
+    #[test]
+    fn test_file_name() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        assert_eq!(
+            "wordle-2022-01-31-226.json",
+            file_name(date, &transcript(Some(226), None))
+        );
+        assert_eq!(
+            "wordle-2022-01-31-1337.json",
+            file_name(date, &transcript(None, Some(1337)))
+        );
+        assert_eq!(
+            "wordle-2022-01-31-practice.json",
+            file_name(date, &transcript(None, None))
+        );
+    }
+
+    #[test]
+    fn test_write_in() {
+        let dir = std::env::temp_dir().join(format!("wordle-export-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let saved = transcript(None, Some(7)).hide_answer();
+
+        let first = write_in(&dir, date, &saved).unwrap();
+        assert_eq!(dir.join("wordle-2022-01-31-7.json"), first);
+        // the first isn't overwritten
+        let second = write_in(&dir, date, &saved).unwrap();
+        assert_eq!(dir.join("wordle-2022-01-31-7-2.json"), second);
+        assert_eq!(
+            ErrorKind::AlreadyExists,
+            write(&first, &saved).unwrap_err().kind()
+        );
+
+        // it's read back just as it was written, with the answer still hidden
+        let read: Transcript = serde_json::from_str(&fs::read_to_string(&second).unwrap()).unwrap();
+        assert_eq!(saved, read);
+        assert_eq!(None, read.answer);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
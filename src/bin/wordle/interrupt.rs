@@ -0,0 +1,219 @@
+//! Saving the game in progress when the player presses Ctrl-C
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+};
+
+use chrono::Local;
+use wordle::{messages::Messages, state::Started, transcript::Transcript, GameStatus, Wordle};
+
+use crate::export;
+
+/// The exit code after an interrupt, the same as a shell gives a process killed by SIGINT
+pub const EXIT_CODE: i32 = 130;
+
+/// What's known about the game in progress
+#[derive(Debug, Default)]
+struct Current {
+    /// The number of the daily puzzle, if the game is one
+    puzzle: Option<u32>,
+    /// The seed the answer was chosen with, if it was chosen at random
+    seed: Option<u64>,
+    /// Whether the game is just for practice
+    practice: bool,
+    /// The game so far, while it's in progress
+    transcript: Option<Transcript>,
+}
+
+/// The game in progress, kept up to date so it can be saved if the player is interrupted
+#[derive(Debug)]
+pub struct Checkpoint {
+    /// Where to save the game, or `None` to never save it (e.g. in batch mode)
+    path: Option<PathBuf>,
+    /// The game in progress
+    current: Mutex<Current>,
+    /// Where transcripts are saved when no path is given for them
+    transcripts: PathBuf,
+}
+
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Checkpoint {
+    /// Keep track of games, to save them to `path` if there is one
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            current: Mutex::default(),
+            transcripts: PathBuf::from("."),
+        }
+    }
+
+    /// Save transcripts to `dir` when no path is given for them, rather than
+    /// the current directory
+    pub fn with_transcripts(mut self, dir: PathBuf) -> Self {
+        self.transcripts = dir;
+        self
+    }
+
+    /// Lock the current game; a panic while it was locked doesn't stop it being saved
+    fn current(&self) -> MutexGuard<'_, Current> {
+        self.current.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Start keeping track of a new game
+    pub fn start(&self, game: &Wordle<'_>, puzzle: Option<u32>, seed: Option<u64>, practice: bool) {
+        *self.current() = Current {
+            puzzle,
+            seed,
+            practice,
+            transcript: None,
+        };
+        self.update(game);
+    }
+
+    /// Catch up with the game; once it's over, there's nothing to save
+    pub fn update(&self, game: &Wordle<'_>) {
+        let transcript = (game.status() == GameStatus::InProgress).then(|| self.transcript(game));
+        self.current().transcript = transcript;
+    }
+
+    /// The transcript of the game, including what's known about it besides its guesses
+    pub fn transcript<S: Started>(&self, game: &Wordle<'_, S>) -> Transcript {
+        let current = self.current();
+        Transcript {
+            puzzle: current.puzzle,
+            seed: current.seed,
+            practice: current.practice,
+            ..game.transcript()
+        }
+    }
+
+    /// Where transcripts are saved when no path is given for them
+    pub fn transcripts(&self) -> &Path {
+        &self.transcripts
+    }
+
+    /// Save the game's transcript to `path`, or into the transcripts directory
+    /// if there isn't one, and get back where it was saved.
+    ///
+    /// The answer is hidden until the game is over. Existing files are never
+    /// overwritten.
+    pub fn export(&self, game: &Wordle<'_>, path: Option<&Path>) -> io::Result<PathBuf> {
+        let transcript = self.transcript(game).hide_answer();
+        let today = Local::now().date_naive();
+        match path {
+            Some(dir) if dir.is_dir() => export::write_in(dir, today, &transcript),
+            Some(path) => export::write(path, &transcript).map(|_| path.to_owned()),
+            None => export::write_in(&self.transcripts, today, &transcript),
+        }
+    }
+
+    /// Save the game in progress, and get back where it was saved, or `None`
+    /// if there was nothing to save
+    pub fn save(&self) -> io::Result<Option<&Path>> {
+        let current = self.current();
+        let (Some(path), Some(transcript)) = (&self.path, &current.transcript) else {
+            return Ok(None);
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(transcript)?)?;
+        Ok(Some(path))
+    }
+}
+
+/// Save the game in progress, if there is one, and exit.
+///
+/// Stats are saved as soon as each game ends, so they're already up to date.
+pub fn save_and_exit(messages: &Messages, checkpoint: &Checkpoint) -> ! {
+    match checkpoint.save() {
+        Ok(Some(_)) => eprintln!("\n{}", messages.format("game-saved-resume", &[])),
+        Ok(None) => eprintln!(),
+        Err(e) => eprintln!(
+            "\n{}",
+            messages.format("error-save-game", &[("error", e.to_string().into())])
+        ),
+    }
+    process::exit(EXIT_CODE);
+}
+
+/// Save the game in progress and exit when Ctrl-C is pressed.
+///
+/// The game is saved on another thread, so pressing Ctrl-C again while it's
+/// being saved quits straight away.
+pub fn install(
+    messages: &'static Messages,
+    checkpoint: Arc<Checkpoint>,
+) -> Result<(), ctrlc::Error> {
+    let interrupted = AtomicBool::new(false);
+    ctrlc::set_handler(move || {
+        if interrupted.swap(true, Ordering::SeqCst) {
+            eprintln!("\n{}", messages.format("quit-without-saving", &[]));
+            process::exit(EXIT_CODE);
+        }
+        let checkpoint = Arc::clone(&checkpoint);
+        thread::spawn(move || save_and_exit(messages, &checkpoint));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint() {
+        let path =
+            std::env::temp_dir().join(format!("wordle-checkpoint-test-{}.json", process::id()));
+        let words = ["crane", "slate"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+
+        // nothing is saved without a path
+        let checkpoint = Checkpoint::new(None);
+        checkpoint.start(&game, None, Some(42), false);
+        assert_eq!(None, checkpoint.save().unwrap());
+
+        let checkpoint = Checkpoint::new(Some(path.clone()));
+        checkpoint.start(&game, Some(7), None, true);
+        game.guess("slate").unwrap();
+        checkpoint.update(&game);
+        assert_eq!(Some(path.as_path()), checkpoint.save().unwrap());
+        let saved: Transcript = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(Some(7), saved.puzzle);
+        assert!(saved.practice);
+        assert_eq!(1, saved.guesses.len());
+        fs::remove_file(&path).unwrap();
+
+        // a finished game isn't saved
+        game.guess("crane").unwrap();
+        checkpoint.update(&game);
+        assert_eq!(None, checkpoint.save().unwrap());
+        assert!(!path.exists());
+    }
+}
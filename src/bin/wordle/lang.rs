@@ -0,0 +1,106 @@
+//! Word lists for other languages, kept side by side in a dictionary directory
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The `--lang` value that lists the available languages instead of picking one
+pub const LIST: &str = "list";
+
+/// Whether `code` can name a language, e.g. `es` or `pt-br`.
+///
+/// Only letters, digits, `-` and `_` are allowed, so a code can't name a file
+/// outside the dictionary directory.
+pub fn is_code(code: &str) -> bool {
+    !code.is_empty()
+        && code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The guess and answer lists for a language: `guesses.<code>.txt` and
+/// `answers.<code>.txt` in `dir`
+pub fn paths(dir: &Path, code: &str) -> (PathBuf, PathBuf) {
+    (
+        dir.join(format!("guesses.{}.txt", code)),
+        dir.join(format!("answers.{}.txt", code)),
+    )
+}
+
+/// The languages in `dir` that have both word lists, in alphabetical order
+pub fn available(dir: &Path) -> io::Result<Vec<String>> {
+    let mut codes = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let code = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("guesses."))
+            .and_then(|name| name.strip_suffix(".txt"));
+        if let Some(code) = code.filter(|code| is_code(code)) {
+            if paths(dir, code).1.is_file() {
+                codes.push(code.to_owned());
+            }
+        }
+    }
+    codes.sort_unstable();
+    Ok(codes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_code() {
+        assert!(is_code("es"));
+        assert!(is_code("pt-br"));
+        assert!(!is_code(""));
+        assert!(!is_code("../es"));
+        assert!(!is_code("e s"));
+    }
+
+    #[test]
+    fn test_available() {
+        let dir = std::env::temp_dir().join(format!("wordle-lang-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "guesses.txt",
+            "answers.txt",
+            "guesses.es.txt",
+            "answers.es.txt",
+            "guesses.de.txt",
+            "answers.de.txt",
+            // no answer list, so it can't be played
+            "guesses.fr.txt",
+            "answers.it.txt",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        assert_eq!(vec!["de", "es"], available(&dir).unwrap());
+        assert_eq!(
+            (dir.join("guesses.es.txt"), dir.join("answers.es.txt")),
+            paths(&dir, "es")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(available(&dir).is_err());
+    }
+}
@@ -0,0 +1,2224 @@
+//! Runner for the Wordle game
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, fs,
+    io::{self, ErrorKind, IsTerminal},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{Local, NaiveDate, Utc};
+use clap::Parser;
+use directories::ProjectDirs;
+use rand::{rngs::StdRng, SeedableRng};
+use wordle::{
+    alphabet::Alphabet,
+    chain::ChainedWordle,
+    daily,
+    definitions::Definitions,
+    filter,
+    messages::{self, Messages},
+    multi::MultiGame,
+    opponent::Opponent,
+    reverse::ReverseWordle,
+    share,
+    solver::{self, cache::SolverCache, EntropyStrategy, Suggestion},
+    startup,
+    state::{Finished, NoWord, Started},
+    stats::{self, DailyResult, GameRecord, Stats, StatsStore},
+    theme::{self, Theme},
+    transcript::Transcript,
+    wordlist, BuildError, GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+use batch::Outcome;
+use cli::{Args, Bot, ExportFormat};
+use clipboard::Clipboard;
+use command::{Command, Input};
+use complete::Completions;
+use config::Config;
+use interrupt::Checkpoint;
+use prompt::{Prompt, Stop};
+use remote::{ApiClient, ApiError, HttpClient, Outbox, Submission};
+use render::Style;
+
+mod animate;
+mod batch;
+mod big;
+mod boards;
+mod bot;
+mod celebrate;
+mod chain;
+mod cli;
+mod clipboard;
+mod command;
+mod complete;
+mod config;
+mod export;
+mod interrupt;
+mod lang;
+mod prompt;
+mod remote;
+mod render;
+mod reverse;
+mod tui;
+
+/// The number of guesses suggested in assist mode
+const SUGGESTIONS: usize = 3;
+
+/// The most possible answers that are listed once a game is over
+const HANDFUL: usize = 5;
+
+/// How a timed game is played
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Timed {
+    /// A guess made after this long forfeits the game
+    limit: Option<Duration>,
+}
+
+/// The optional parts of a game played at the prompt
+#[derive(Debug, Default)]
+struct Extras<'g, 'a> {
+    /// How the game is timed, if it is
+    timed: Option<Timed>,
+    /// The suggestions to show, in assist mode
+    assist: Option<&'g mut Assist>,
+    /// The computer player racing against the player, with `--vs-bot`
+    opponent: Option<&'g mut Opponent<'a>>,
+}
+
+/// What to do after a game
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Flow {
+    /// Move on to the next game
+    Continue,
+    /// Exit the program
+    Quit,
+    /// Save the game in progress and exit the program, as on Ctrl-C
+    Interrupted,
+}
+
+/// What every mode of a session shares, once the arguments have been read
+struct Session<'s> {
+    /// The command line arguments
+    args: &'s Args,
+    /// The settings, from the config file overridden by the arguments
+    config: &'s Config,
+    /// The messages, in the player's language
+    messages: &'static Messages,
+    /// How everything is drawn
+    style: Style,
+    /// The colors of the board
+    theme: &'static Theme,
+    /// The number of games to play, or `None` to keep going until the player stops
+    games: Option<u32>,
+    /// The seed the words are chosen with; it's always known, so any session can be replayed
+    seed: u64,
+    /// Where the game in progress is kept, in case the player presses Ctrl-C
+    checkpoint: Arc<Checkpoint>,
+    /// Where guesses are read from; the history of guesses is kept for the
+    /// whole session, across games
+    prompt: Box<dyn Prompt>,
+    /// Set up when the share text is first copied, and then kept, since on
+    /// some platforms what's copied is gone once it is
+    clipboard: Option<Box<dyn Clipboard>>,
+}
+
+/// How the games of a normal session are chosen and played
+struct Standard {
+    /// The date of the daily puzzle to play, and whether it's from another day
+    daily: Option<(NaiveDate, bool)>,
+    /// A practice word, kept for the lifetime of the program like the word lists
+    answer: Option<&'static str>,
+    /// How the games are timed, if they are
+    timed: Option<Timed>,
+    /// Whether to show how many answers are left after each guess
+    show_remaining: bool,
+    /// The first guess to make in every game, already checked
+    opener: Option<String>,
+    /// The acceptable guesses
+    guesses: &'static [&'static str],
+    /// The possible answers
+    answers: &'static [&'static str],
+}
+
+fn main() {
+    let args = Args::parse();
+    let config_path = config_path();
+    let config = match &config_path {
+        Some(path) => load_config(path, environment_messages()),
+        None => Config::default(),
+    }
+    .merge(&args);
+    let messages = load_messages(&config);
+
+    if args.write_config {
+        write_config(messages, &config, config_path);
+        return;
+    }
+
+    let theme = load_theme(&config, messages);
+    let style = Style::detect(args.no_color)
+        .with_accessible(args.accessible)
+        .with_big(args.big)
+        .with_theme(theme)
+        .with_messages(messages)
+        .with_clear(args.clear && io::stdout().is_terminal());
+    let style = match config.tiles {
+        Some(tiles) => style.with_tiles(tiles.tile_set(config.colorblind)),
+        None if style.color() => style.with_tiles(theme.tile_set()),
+        None => style,
+    };
+    let stats_dir = config.stats_dir.as_deref();
+    let language = args.lang.as_deref();
+
+    if language == Some(lang::LIST) {
+        list_languages(messages, config.dict_dir());
+        return;
+    }
+
+    if let Some(path) = &args.export_stats {
+        export_stats(messages, path, args.format, stats_dir, language);
+        return;
+    }
+
+    if let Some(path) = &args.merge_stats {
+        merge_stats(
+            messages,
+            path,
+            stats_store(stats_dir, language, config.hard),
+        );
+        return;
+    }
+
+    if args.stats || args.stats_json {
+        show_stats(&style, stats_dir, language, args.stats_json);
+        return;
+    }
+
+    if args.when {
+        println!("{}", next_wordle(messages, config.utc));
+        return;
+    }
+
+    // the date of the daily puzzle to play, and whether it's from another day
+    let daily = args.daily.then(|| {
+        let today = today(config.utc);
+        let date = args.date.unwrap_or(today);
+        (date, date != today)
+    });
+    // a practice word, kept for the lifetime of the program like the word lists
+    let answer: Option<&'static str> = args
+        .answer
+        .as_deref()
+        .map(|answer| &*Box::leak(answer.to_lowercase().into_boxed_str()));
+    let games = if daily.is_some() || answer.is_some() {
+        Some(1)
+    } else {
+        args.games
+    };
+
+    // load the word lists
+    let (guess_list, answer_list) = match language {
+        Some(code) => load_language(messages, config.dict_dir(), code),
+        None => startup::load_word_lists(
+            messages,
+            &config.guesses,
+            &config.answers,
+            args.builtin_words,
+            &wordlist::Limits::default(),
+        ),
+    };
+
+    // the count is left out of the daily puzzle unless it's asked for, since it helps a little
+    let show_remaining = config
+        .show_remaining
+        .is_some_and(|when| when.shown(daily.is_some()));
+
+    // the seed is always known, so any session can be replayed
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let setup = new_setup(
+        messages,
+        &args,
+        &config,
+        (guess_list, answer_list),
+        show_remaining,
+        seed,
+    );
+    // the opener is checked up front, rather than at the start of every game
+    let opener = config
+        .first_guess
+        .as_deref()
+        .map(|word| check_opener(messages, &setup, word));
+
+    // batch games are never saved, but Ctrl-C still exits cleanly
+    let checkpoint = Checkpoint::new(if args.batch {
+        None
+    } else {
+        resume_path(stats_dir)
+    });
+    let checkpoint = Arc::new(match &args.save_transcripts {
+        Some(dir) => checkpoint.with_transcripts(dir.clone()),
+        None => checkpoint,
+    });
+    if let Err(e) = interrupt::install(messages, Arc::clone(&checkpoint)) {
+        eprintln!(
+            "{}",
+            messages.format("warning-ctrl-c", &[("error", e.to_string().into())])
+        );
+    }
+
+    if args.batch {
+        play_batch(&args, &style, setup, answer);
+    }
+
+    let session = Session {
+        args: &args,
+        config: &config,
+        messages,
+        style,
+        theme,
+        games,
+        seed,
+        checkpoint,
+        prompt: prompt::stdin(messages),
+        clipboard: None,
+    };
+    if let Some(boards) = args.boards {
+        play_boards(session, &setup, boards);
+    } else if let Some(length) = args.chain {
+        play_chains(session, &setup, length);
+    } else if args.reverse {
+        play_reverse(session, &setup);
+    } else {
+        let timed = args.timed.then(|| Timed {
+            limit: args.time_limit.map(Duration::from_secs),
+        });
+        let standard = Standard {
+            daily,
+            answer,
+            timed,
+            show_remaining,
+            opener,
+            guesses: guess_list,
+            answers: answer_list,
+        };
+        play_games(session, setup, standard);
+    }
+}
+
+/// Save the settings to the config file, for `--write-config`
+fn write_config(messages: &Messages, config: &Config, path: Option<PathBuf>) {
+    let path = path.unwrap_or_else(|| {
+        eprintln!("{}", messages.format("error-no-config-dir", &[]));
+        process::exit(1);
+    });
+    if let Err(e) = config.save(&path) {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-write-config",
+                &[
+                    ("path", path.display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+        process::exit(1);
+    }
+    println!(
+        "{}",
+        messages.format(
+            "saved-config",
+            &[("path", path.display().to_string().into())]
+        )
+    );
+}
+
+/// List the languages with word lists in `dir`, for `--lang list`
+fn list_languages(messages: &Messages, dir: &Path) {
+    match lang::available(dir) {
+        Ok(codes) if codes.is_empty() => println!(
+            "{}",
+            messages.format(
+                "no-languages",
+                &[("path", startup::resolve(dir).display().to_string().into())]
+            )
+        ),
+        Ok(codes) => println!("{}", codes.join("\n")),
+        Err(e) => {
+            eprintln!(
+                "{}",
+                messages.format(
+                    "error-read-dict-dir",
+                    &[
+                        ("path", startup::resolve(dir).display().to_string().into()),
+                        ("error", e.to_string().into()),
+                    ],
+                )
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Print the stats for both modes, for `--stats`, or as JSON for `--stats-json`
+fn show_stats(style: &Style, stats_dir: Option<&Path>, language: Option<&str>, json: bool) {
+    let messages = style.messages();
+    let stats = stats_store(stats_dir, language, false)
+        .as_ref()
+        .and_then(|store| load_stats(messages, store))
+        .unwrap_or_default();
+    let hard = stats_store(stats_dir, language, true)
+        .as_ref()
+        .and_then(|store| load_stats(messages, store))
+        .filter(|stats| stats.played() > 0);
+    if json {
+        let json = serde_json::json!({
+            "stats": render::stats_json(&stats, wordle::MAX_GUESSES),
+            "hard_mode": hard.map(|hard| render::stats_json(&hard, wordle::MAX_GUESSES)),
+        });
+        println!("{:#}", json);
+        return;
+    }
+    let width = render::terminal_width();
+    println!("{}", style.stats(&stats, width));
+    if let Some(hard) = hard {
+        println!(
+            "\n{}\n{}",
+            messages.format("stats-hard-mode", &[]),
+            style.stats(&hard, width)
+        );
+    }
+}
+
+/// The game every word of the session is chosen from, with the word lists
+/// and rules the arguments ask for
+fn new_setup(
+    messages: &Messages,
+    args: &Args,
+    config: &Config,
+    (guess_list, answer_list): (&'static [&'static str], &'static [&'static str]),
+    show_remaining: bool,
+    seed: u64,
+) -> Wordle<'static, NoWord> {
+    let alphabet = args
+        .lang
+        .as_deref()
+        .and_then(Alphabet::for_language)
+        .unwrap_or_else(|| Alphabet::from_words(guess_list));
+    Wordle::builder()
+        .guesses(guess_list)
+        .answers(answer_list)
+        .word_length(config.length.into())
+        .alphabet(alphabet)
+        .hard_mode(config.hard)
+        .reject_repeats(!args.strict_repeats)
+        .assist(args.assist)
+        .track_candidates(show_remaining)
+        .seed(seed)
+        .build()
+        .unwrap_or_else(|e| {
+            match e {
+                BuildError::WrongLength { .. } => eprintln!(
+                    "{}",
+                    messages.format(
+                        "error-word-list-length",
+                        &[
+                            ("length", config.length.into()),
+                            ("error", e.to_string().into()),
+                        ],
+                    )
+                ),
+                _ => eprintln!(
+                    "{}",
+                    messages.format(
+                        "error-invalid-word-lists",
+                        &[("error", e.to_string().into())]
+                    )
+                ),
+            }
+            process::exit(1);
+        })
+}
+
+/// The `--first-guess` word, in lowercase, or exit if it can't be guessed
+fn check_opener(messages: &Messages, setup: &Wordle<'_, NoWord>, word: &str) -> String {
+    let word = word.to_lowercase();
+    if let Err(e) = setup.check_guess(&word) {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-invalid-first-guess",
+                &[("error", render::rejection(messages, &word, &e).into())],
+            )
+        );
+        process::exit(1);
+    }
+    word
+}
+
+/// Play one game with `--batch`, and exit with its outcome
+fn play_batch(
+    args: &Args,
+    style: &Style,
+    setup: Wordle<'static, NoWord>,
+    answer: Option<&'static str>,
+) -> ! {
+    let messages = style.messages();
+    let mut game = match answer {
+        Some(answer) => set_answer(setup, answer, args.allow_any_answer).unwrap_or_else(|e| {
+            eprintln!("{}", invalid_answer(messages, answer, &e));
+            process::exit(2);
+        }),
+        None => choose_word(setup, args.game),
+    };
+    let outcome = batch::run(
+        &mut game,
+        io::stdin().lock(),
+        io::stdout().lock(),
+        io::stderr().lock(),
+        style.messages(),
+        args.strict,
+    )
+    .unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            messages.format("error-read-guesses", &[("error", e.to_string().into())])
+        );
+        process::exit(2);
+    });
+    if outcome == Outcome::InputEnded {
+        eprintln!("{}", messages.format("error-input-ended", &[]));
+    }
+    process::exit(outcome.exit_code());
+}
+
+/// Play games on several boards at once, with `--boards`
+fn play_boards(session: Session<'_>, setup: &Wordle<'_, NoWord>, boards: usize) {
+    let Session {
+        args,
+        messages,
+        style,
+        games,
+        seed,
+        checkpoint,
+        mut prompt,
+        mut clipboard,
+        ..
+    } = session;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counter = 0;
+    let mut wins = 0;
+    while games.is_none_or(|games| counter < games) {
+        counter += 1;
+        let mut multi = MultiGame::random(setup, boards, &mut rng);
+        println!(
+            "--- {} ---",
+            messages.format(
+                "boards-started",
+                &[
+                    ("number", counter.into()),
+                    ("seed", seed.to_string().into()),
+                    ("boards", boards.into()),
+                ],
+            )
+        );
+        match boards::play(&mut multi, &mut *prompt, &style) {
+            Flow::Continue => {}
+            Flow::Quit => break,
+            // there's nothing to save, since these games can't be resumed
+            Flow::Interrupted => interrupt::save_and_exit(messages, &checkpoint),
+        }
+
+        if matches!(multi.status(), GameStatus::Won { .. }) {
+            wins += 1;
+        }
+        println!("{}", boards::summary(messages, &multi));
+        let share_text = multi.share_text(style.tiles());
+        if !args.no_share {
+            println!("\n{}\n", share_text);
+        }
+        if args.copy_share {
+            copy_share(messages, &mut clipboard, &share_text);
+        }
+        println!("{}", won(messages, "won-games", wins, counter));
+        println!("{}", messages.format("boards-no-stats", &[]));
+
+        if games != Some(counter) {
+            match play_again(messages, &mut *prompt, &mut || {
+                copy_share(messages, &mut clipboard, &share_text)
+            }) {
+                Ok(true) => {}
+                Ok(false) | Err(Stop::End) => break,
+                Err(Stop::Interrupted) => interrupt::save_and_exit(messages, &checkpoint),
+            }
+        }
+    }
+}
+
+/// Play chains of words, each starting with the last one's answer, with `--chain`
+fn play_chains(session: Session<'_>, setup: &Wordle<'_, NoWord>, length: u32) {
+    let Session {
+        args,
+        messages,
+        style,
+        games,
+        seed,
+        checkpoint,
+        mut prompt,
+        mut clipboard,
+        ..
+    } = session;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counter = 0;
+    let mut wins = 0;
+    while games.is_none_or(|games| counter < games) {
+        counter += 1;
+        let mut chain = ChainedWordle::random(setup, length as usize, &mut rng);
+        if let Some(guesses) = args.final_guesses {
+            chain = chain.with_final_guesses(guesses.into());
+        }
+        println!(
+            "--- {} ---",
+            messages.format(
+                "chain-started",
+                &[
+                    ("number", counter.into()),
+                    ("seed", seed.to_string().into()),
+                    ("games", length.into()),
+                ],
+            )
+        );
+        match chain::play(&mut chain, &mut *prompt, &style) {
+            Flow::Continue => {}
+            Flow::Quit => break,
+            // there's nothing to save, since chains can't be resumed
+            Flow::Interrupted => interrupt::save_and_exit(messages, &checkpoint),
+        }
+
+        if matches!(chain.status(), GameStatus::Won { .. }) {
+            wins += 1;
+        }
+        println!("{}", chain::summary(messages, &chain));
+        let share_text = chain.share_text(style.tiles());
+        if !args.no_share {
+            println!("\n{}\n", share_text);
+        }
+        if args.copy_share {
+            copy_share(messages, &mut clipboard, &share_text);
+        }
+        println!("{}", won(messages, "won-chains", wins, counter));
+        println!("{}", messages.format("chain-no-stats", &[]));
+
+        if games != Some(counter) {
+            match play_again(messages, &mut *prompt, &mut || {
+                copy_share(messages, &mut clipboard, &share_text)
+            }) {
+                Ok(true) => {}
+                Ok(false) | Err(Stop::End) => break,
+                Err(Stop::Interrupted) => interrupt::save_and_exit(messages, &checkpoint),
+            }
+        }
+    }
+}
+
+/// Play today's reverse puzzle, finding guesses that give its colors, with `--reverse`
+fn play_reverse(session: Session<'_>, setup: &Wordle<'_, NoWord>) {
+    let Session {
+        config,
+        messages,
+        style,
+        checkpoint,
+        mut prompt,
+        ..
+    } = session;
+    let puzzle = daily::puzzle_number(today(config.utc)).unwrap_or_else(|| {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-no-puzzle-today",
+                &[("date", daily::epoch().to_string().into())]
+            )
+        );
+        process::exit(1);
+    });
+    let answer = daily::answer(setup.answers(), puzzle);
+    // everyone gets the same colors for the same day
+    let mut rng = StdRng::seed_from_u64(puzzle.into());
+    let mut reverse = ReverseWordle::generate(setup, answer, &mut rng)
+        .expect("The daily answer is an acceptable guess");
+    println!(
+        "--- {} ---",
+        messages.format("reverse-started", &[("puzzle", puzzle.into())])
+    );
+    match reverse::play(&mut reverse, &mut *prompt, &style) {
+        Flow::Continue => println!("{}", reverse::summary(messages, &reverse)),
+        Flow::Quit => {}
+        // there's nothing to save, since reverse puzzles can't be resumed
+        Flow::Interrupted => interrupt::save_and_exit(messages, &checkpoint),
+    }
+}
+
+/// Play a session of ordinary games, or the daily puzzle, starting with a
+/// saved game if one is being resumed; with `--vs-bot`, a computer player
+/// races the player on every word
+fn play_games(session: Session<'_>, setup: Wordle<'static, NoWord>, standard: Standard) {
+    let Session {
+        args,
+        config,
+        messages,
+        style,
+        theme,
+        games,
+        checkpoint,
+        mut prompt,
+        mut clipboard,
+        ..
+    } = session;
+    let Standard {
+        daily,
+        answer,
+        timed,
+        show_remaining,
+        opener,
+        guesses: guess_list,
+        answers: answer_list,
+    } = standard;
+    let stats_dir = config.stats_dir.as_deref();
+    let language = args.lang.as_deref();
+    // screen readers would read out every frame
+    let animate = !args.no_animations && !args.accessible;
+
+    // stats are only recorded if they could be read; otherwise they'd be overwritten
+    let store = stats_store(stats_dir, language, config.hard);
+    let mut stats = store.as_ref().and_then(|store| load_stats(messages, store));
+
+    // a resumed game is played first, and then words are chosen with the
+    // setup it leaves behind once it's over
+    let (mut setup, mut resumed) = match &args.resume {
+        Some(path) => {
+            let (game, transcript) = resume(messages, setup, stats_dir, path.as_deref());
+            match game.finish() {
+                Ok(game) => {
+                    summarize(&game, &style, transcript.puzzle);
+                    return;
+                }
+                Err(game) => (None, Some((game, transcript))),
+            }
+        }
+        None => (Some(setup), None),
+    };
+    let mut assist = args.assist.then(|| Assist {
+        // working them out takes a while, so the player is told when that's needed
+        openers: solver::best_openers_default_cache(guess_list, answer_list, SUGGESTIONS, || {
+            println!("{}", messages.format("computing-openers", &[]))
+        }),
+        cache: if args.no_cache { None } else { solver_cache() },
+        explain: args.explain,
+    });
+
+    // the hard bot opens with the best first guess, which is slow to work out, so it's cached
+    let bot_opener = (args.vs_bot == Some(Bot::Hard))
+        .then(|| {
+            solver::best_openers_default_cache(guess_list, answer_list, SUGGESTIONS, || {
+                println!("{}", messages.format("computing-openers", &[]))
+            })
+        })
+        .and_then(|openers| openers.into_iter().next())
+        .map(|opener| opener.word);
+
+    // results kept from when the server couldn't be reached go first, so
+    // they're sent in the order they were played
+    let server = args.server.as_deref().and_then(|url| {
+        let client = HttpClient::new(url);
+        // a server with a daily secret refuses every daily result played
+        // here, so they aren't sent, rather than kept to try again forever;
+        // if it can't be asked, they're sent and kept as usual
+        if client.meta().is_ok_and(|meta| meta.keyed()) {
+            eprintln!(
+                "{}",
+                messages.format("warning-server-keyed", &[("path", url.into())])
+            );
+            return None;
+        }
+        let outbox = outbox_path(stats_dir).map(Outbox::new);
+        if let Some(outbox) = &outbox {
+            send_waiting(messages, &client, outbox);
+        }
+        Some((client, outbox))
+    });
+
+    // the definitions are only read once the first game is over, so a big file doesn't slow down starting
+    let mut definitions = None;
+    // the game to jump to, for the first game only
+    let mut jump = args.game;
+    let mut counter = 0;
+    let mut wins = 0;
+    while games.is_none_or(|games| counter < games) {
+        // whether the game doesn't count towards stats; only resumed games can be, besides these
+        let mut practice = answer.is_some() || matches!(daily, Some((_, true)));
+        let mut game_seed = setup.as_ref().map(Wordle::seed);
+        let resuming = resumed.is_some();
+        let (mut game, puzzle) = if let Some((game, transcript)) = resumed.take() {
+            practice = transcript.practice;
+            game_seed = transcript.seed;
+            (game, transcript.puzzle)
+        } else {
+            let setup = setup
+                .take()
+                .expect("The last game was cleared for the next");
+            match daily {
+                Some((date, archive)) => {
+                    let (game, puzzle) = setup.choose_daily(date).unwrap_or_else(|_| {
+                        eprintln!(
+                            "{}",
+                            messages.format(
+                                "error-no-puzzle",
+                                &[
+                                    ("date", date.to_string().into()),
+                                    ("first", daily::epoch().to_string().into()),
+                                ],
+                            )
+                        );
+                        process::exit(1);
+                    });
+                    if !archive {
+                        if let Some(result) = played_daily(
+                            messages,
+                            stats.as_ref(),
+                            stats_dir,
+                            language,
+                            config.hard,
+                            puzzle,
+                        ) {
+                            show_played_daily(&style, config.utc, puzzle, result);
+                            return;
+                        }
+                    }
+                    (game, Some(puzzle))
+                }
+                None => {
+                    let game = match answer {
+                        Some(answer) => set_answer(setup, answer, args.allow_any_answer)
+                            .unwrap_or_else(|e| {
+                                eprintln!("{}", invalid_answer(messages, answer, &e));
+                                process::exit(1);
+                            }),
+                        None => choose_word(setup, jump.take()),
+                    };
+                    (game, None)
+                }
+            }
+        };
+        counter += 1;
+        if practice || puzzle.is_some() {
+            game_seed = None;
+        }
+        checkpoint.start(&game, puzzle, game_seed, practice);
+
+        let mode = match game.hard_mode() {
+            true => format!(" ({})", messages.format("banner-hard-mode", &[])),
+            false => String::new(),
+        };
+        let banner = match (puzzle, daily) {
+            _ if resuming => match (puzzle, game_seed, game.game_number()) {
+                (Some(puzzle), _, _) => {
+                    messages.format("daily-resumed", &[("puzzle", puzzle.into())])
+                }
+                (None, Some(seed), Some(number)) => messages.format(
+                    "game-resumed",
+                    &[("number", number.into()), ("seed", seed.to_string().into())],
+                ),
+                (None, Some(seed), None) => {
+                    messages.format("seed-resumed", &[("seed", seed.to_string().into())])
+                }
+                (None, None, _) => messages.format("practice-resumed", &[]),
+            },
+            (Some(puzzle), Some((date, _))) => messages.format(
+                "daily-started",
+                &[("puzzle", puzzle.into()), ("date", date.to_string().into())],
+            ),
+            // the seed doesn't matter for a practice word, which is kept secret
+            _ if answer.is_some() => messages.format("practice-started", &[]),
+            _ => messages.format(
+                "game-started",
+                &[
+                    ("number", game.game_number().unwrap_or(counter).into()),
+                    ("seed", game.seed().to_string().into()),
+                ],
+            ),
+        };
+        println!("--- {}{} ---", banner, mode);
+
+        let mut opponent = args.vs_bot.map(|bot| match &bot_opener {
+            Some(bot_opener) => Opponent::new(
+                &game,
+                Box::new(EntropyStrategy::with_opener(bot_opener.as_str())),
+            ),
+            None => Opponent::with_difficulty(&game, bot.difficulty()),
+        });
+
+        // a resumed game may already have its first guess
+        let opened = opener.as_deref().filter(|_| game.history().is_empty());
+        if let Some(opener) = opened {
+            game.guess(opener).expect("The first guess was checked");
+            if let Some(opponent) = &mut opponent {
+                opponent.take_turn();
+            }
+            println!(
+                "{}",
+                messages.format("opened-with", &[("guess", opener.to_uppercase().into())])
+            );
+            checkpoint.update(&game);
+        }
+
+        let flow = if args.tui {
+            tui::play(
+                &mut game,
+                puzzle.is_some(),
+                theme,
+                style.messages(),
+                animate,
+            )
+            .unwrap_or_else(|e| {
+                eprintln!(
+                    "{}",
+                    messages.format("error-terminal", &[("error", e.to_string().into())])
+                );
+                process::exit(1);
+            })
+        } else {
+            play(
+                &mut game,
+                &mut *prompt,
+                &style,
+                puzzle.is_some(),
+                Extras {
+                    timed,
+                    assist: assist.as_mut(),
+                    opponent: opponent.as_mut(),
+                },
+                &checkpoint,
+            )
+        };
+        match flow {
+            Flow::Continue => checkpoint.update(&game),
+            Flow::Quit => break,
+            Flow::Interrupted => {
+                checkpoint.update(&game);
+                interrupt::save_and_exit(messages, &checkpoint);
+            }
+        }
+
+        if args.save_transcripts.is_some() {
+            match checkpoint.export(&game, None) {
+                Ok(path) => println!(
+                    "{}",
+                    messages.format(
+                        "saved-transcript",
+                        &[("path", path.display().to_string().into())]
+                    )
+                ),
+                Err(e) => eprintln!(
+                    "{}",
+                    messages.format(
+                        "warning-save-transcript",
+                        &[(
+                            "error",
+                            export_error(messages, checkpoint.transcripts(), &e).into()
+                        )]
+                    )
+                ),
+            }
+        }
+
+        let game = game.finish().expect("The game is over");
+        let answer_word = game.reveal();
+        match game.status() {
+            GameStatus::Won { guesses } => {
+                wins += 1;
+                if !args.quiet {
+                    println!("{}", celebrate::praise(style.messages(), guesses));
+                }
+            }
+            GameStatus::Lost if args.quiet => println!(
+                "{}",
+                messages.format(
+                    "the-word-was",
+                    &[("answer", answer_word.to_uppercase().into())]
+                )
+            ),
+            GameStatus::Lost => {
+                println!("{}", celebrate::consolation(style.messages(), answer_word))
+            }
+            GameStatus::InProgress => unreachable!("The game is over"),
+        }
+        if let Some(opponent) = &mut opponent {
+            while opponent.take_turn().is_some() {}
+            println!("{}", bot::progress(messages, opponent, style.tiles()));
+            println!("{}", bot::verdict(messages, &game, opponent));
+        }
+        if let Some(path) = &config.definitions {
+            let definitions = definitions.get_or_insert_with(|| load_definitions(messages, path));
+            if let Some(line) = definitions.line(answer_word) {
+                println!("{}", line);
+            }
+        }
+        let share_text = share::share_text(messages, &game, puzzle, style.tiles());
+        if !args.no_share {
+            // blank lines around the share text make it easy to select
+            println!("\n{}\n", share_text);
+        }
+        if args.copy_share {
+            copy_share(messages, &mut clipboard, &share_text);
+        }
+        println!("{}", won(messages, "won-games", wins, counter));
+        if timed.is_some() {
+            show_times(messages, &game);
+        }
+
+        if show_remaining {
+            if let Some((guess, answers)) = narrowed_down(&game) {
+                println!(
+                    "{}",
+                    render::narrowed_down(style.messages(), guess, &answers)
+                );
+            }
+        }
+
+        if let Some((date, true)) = daily {
+            println!(
+                "{}",
+                messages.format("other-day-no-stats", &[("date", date.to_string().into())])
+            );
+        } else if answer.is_some() {
+            println!("{}", messages.format("practice-word-no-stats", &[]));
+        } else if practice {
+            println!("{}", messages.format("practice-game-no-stats", &[]));
+        } else if let (Some(store), Some(stats)) = (&store, &mut stats) {
+            let result = puzzle.map(|puzzle| DailyResult {
+                puzzle,
+                status: game.status(),
+                max_guesses: game.max_guesses(),
+                hard_mode: game.hard_mode(),
+                hints: game.hints().len(),
+                assisted: game.assist(),
+                feedback: game.history().iter().map(|(_, f)| f.clone()).collect(),
+                opener: opened.map(str::to_owned),
+            });
+            let date = daily.map_or_else(|| today(config.utc), |(date, _)| date);
+            record_game(&style, args, store, stats, &game, result, date);
+        }
+        if let (Some((client, outbox)), Some(_), false) = (&server, puzzle, practice) {
+            submit_result(
+                messages,
+                client,
+                outbox.as_ref(),
+                checkpoint.transcript(&game),
+            );
+        }
+
+        setup = Some(game.clear());
+        let last_game = games == Some(counter);
+        if !last_game {
+            match play_again(messages, &mut *prompt, &mut || {
+                copy_share(messages, &mut clipboard, &share_text)
+            }) {
+                Ok(true) => {}
+                Ok(false) | Err(Stop::End) => break,
+                Err(Stop::Interrupted) => interrupt::save_and_exit(messages, &checkpoint),
+            }
+        }
+    }
+}
+
+/// Show the result of today's puzzle, which has already been played
+fn show_played_daily(style: &Style, utc: bool, puzzle: u32, result: DailyResult) {
+    let messages = style.messages();
+    println!(
+        "{}",
+        messages.format("already-played", &[("puzzle", puzzle.into())])
+    );
+    if let Some(opener) = &result.opener {
+        println!(
+            "{}",
+            messages.format("opened-earlier", &[("guess", opener.to_uppercase().into())])
+        );
+    }
+    let rows: Vec<(&str, Vec<LetterStatus>)> =
+        result.feedback.into_iter().map(|f| ("", f)).collect();
+    println!(
+        "\n{}\n{}\n",
+        share::share_header(
+            messages,
+            Some(puzzle),
+            result.status,
+            result.max_guesses,
+            result.hard_mode,
+            result.hints,
+            result.assisted
+        ),
+        share::share_grid(&rows, style.tiles())
+    );
+    println!("{}", next_wordle(messages, utc));
+}
+
+/// Show how long a timed game took, and each guess along the way
+fn show_times(messages: &Messages, game: &Wordle<'_, Finished>) {
+    let splits: Vec<String> = game
+        .guess_times()
+        .into_iter()
+        .map(render::duration)
+        .collect();
+    let time = render::duration(game.elapsed());
+    if splits.is_empty() {
+        println!("{}", messages.format("time", &[("time", time.into())]));
+    } else {
+        println!(
+            "{}",
+            messages.format(
+                "time-per-guess",
+                &[("time", time.into()), ("splits", splits.join(", ").into())],
+            )
+        );
+    }
+}
+
+/// Record a finished game, played on `date`, in the stats and the log of
+/// games, and show the stats; `daily` is its result, if it was the daily puzzle
+fn record_game(
+    style: &Style,
+    args: &Args,
+    store: &StatsStore,
+    stats: &mut Stats,
+    game: &Wordle<'_, Finished>,
+    daily: Option<DailyResult>,
+    date: NaiveDate,
+) {
+    let messages = style.messages();
+    let streak = stats.current_streak();
+    let result = game.result();
+    let puzzle = daily.as_ref().map(|daily| daily.puzzle);
+    match daily {
+        Some(daily) => stats.record_daily(daily),
+        None => stats.record(&result),
+    }
+    let won = result.won;
+    if !args.quiet {
+        if won {
+            println!(
+                "{}",
+                celebrate::streak(messages, stats.current_streak(), stats.max_streak())
+            );
+        } else if streak > 0 && stats.current_streak() == 0 {
+            println!("{}", celebrate::streak_ended(messages, streak));
+        }
+    }
+    if args.timed && won && stats.record_time(result.duration) {
+        println!("{}", messages.format("personal-best", &[]));
+    }
+    if let Err(e) = store.save(stats) {
+        eprintln!(
+            "{}",
+            messages.format(
+                "warning-save-stats",
+                &[
+                    ("path", store.path().display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+    }
+    let record = GameRecord {
+        puzzle,
+        seed: puzzle.is_none().then_some(game.seed()),
+        game: game.game_number(),
+        ..GameRecord::new(&result, date)
+    };
+    if let Err(e) = store.append(&record) {
+        eprintln!(
+            "{}",
+            messages.format(
+                "warning-log-game",
+                &[
+                    ("path", store.log_path().display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+    }
+    println!("{}", style.stats(stats, render::terminal_width()));
+}
+
+/// Play against the `--answer` word, which must be an acceptable guess unless any word is allowed
+fn set_answer<'a>(
+    game: Wordle<'a, NoWord>,
+    answer: &'a str,
+    allow_any: bool,
+) -> Result<Wordle<'a>, GuessError> {
+    if allow_any {
+        game.set_custom_answer(answer)
+    } else {
+        game.set_answer(answer)
+    }
+}
+
+/// Play the next game of the session, or jump to game `number` of it
+fn choose_word(game: Wordle<'_, NoWord>, number: Option<u32>) -> Wordle<'_> {
+    match number {
+        Some(number) => game.choose_game(number),
+        None => game.choose_word(),
+    }
+}
+
+/// How long until the next daily puzzle, by the date in UTC or in the local time zone
+fn until_next_puzzle(utc: bool) -> Duration {
+    let until = if utc {
+        let now = Utc::now();
+        daily::next_puzzle(&now) - now
+    } else {
+        let now = Local::now();
+        daily::next_puzzle(&now) - now
+    };
+    until.to_std().unwrap_or_default()
+}
+
+/// Find the result of a daily puzzle, if it's been played in either mode
+fn played_daily(
+    messages: &Messages,
+    stats: Option<&Stats>,
+    stats_dir: Option<&Path>,
+    language: Option<&str>,
+    hard_mode: bool,
+    puzzle: u32,
+) -> Option<DailyResult> {
+    if let Some(result) = stats.and_then(|stats| stats.daily_result(puzzle)) {
+        return Some(result.clone());
+    }
+    let other = stats_store(stats_dir, language, !hard_mode)
+        .as_ref()
+        .and_then(|store| load_stats(messages, store))?;
+    other.daily_result(puzzle).cloned()
+}
+
+/// Play the current word until it's found, the guesses run out, or the player skips it.
+///
+/// The whole board is drawn at the start, and again after every guess.
+///
+/// The daily puzzle can't be given up before making a guess, so it can't be
+/// skipped to see the answer without it counting towards the player's streak.
+///
+/// In a timed game, the time so far is shown at each prompt, and a guess made
+/// after the time limit forfeits the game instead.
+///
+/// In assist mode, the number of possible answers and the best guesses are
+/// shown before each guess.
+///
+/// When the game keeps track of the possible answers, how many are left is
+/// shown after each guess, unless assist mode already shows it.
+///
+/// The game is kept up to date in `checkpoint`, in case the player presses Ctrl-C.
+///
+/// Guesses are read from `prompt`, and remembered there so they can be
+/// brought back and edited. They can be completed to any word that's still
+/// playable, which in hard mode means using every revealed hint.
+///
+/// Returns [`Flow::Quit`] if the player quit or closed stdin partway through.
+fn play<'a>(
+    game: &mut Wordle<'a>,
+    prompt: &mut dyn Prompt,
+    style: &Style,
+    daily: bool,
+    extras: Extras<'_, 'a>,
+    checkpoint: &Checkpoint,
+) -> Flow {
+    let Extras {
+        timed,
+        mut assist,
+        mut opponent,
+    } = extras;
+    // the number of guesses the suggestions were last shown for
+    let mut suggested = None;
+    // the number of guesses the board was last drawn for
+    let mut drawn = None;
+    // how many answers were possible before and after the last guess, shown under the board
+    let mut narrowed = None;
+
+    loop {
+        if drawn != Some(game.history().len()) {
+            draw(game, style);
+            if let Some((before, after)) = narrowed.take() {
+                println!("{}", render::remaining(style.messages(), after, before));
+            }
+            if let Some(opponent) = opponent.as_deref().filter(|o| o.turns() > 0) {
+                println!(
+                    "{}",
+                    bot::progress(style.messages(), opponent, style.tiles())
+                );
+            }
+            drawn = Some(game.history().len());
+        }
+        if game.status() != GameStatus::InProgress {
+            break;
+        }
+        checkpoint.update(game);
+        prompt.set_completions(Completions::new(game.playable_guesses()));
+
+        if let Some(assist) = assist
+            .as_deref_mut()
+            .filter(|_| suggested != Some(game.history().len()))
+        {
+            let suggestions = if game.history().is_empty() {
+                let mut openers = assist.openers.clone();
+                if assist.explain {
+                    let candidates = game.remaining_candidates().unwrap_or(game.answers());
+                    solver::explain_all(&mut openers, candidates);
+                }
+                openers
+            } else {
+                solver::suggest_next_lookahead(
+                    game,
+                    SUGGESTIONS,
+                    assist.explain,
+                    assist.cache.as_mut(),
+                )
+            };
+            let remaining = game
+                .remaining_candidates_count()
+                .unwrap_or(game.answers().len());
+            println!(
+                "{}",
+                render::suggestions(style.messages(), remaining, &suggestions, style.tiles())
+            );
+            suggested = Some(game.history().len());
+        }
+
+        // the number of the guess being made; rejected guesses don't use one up
+        let i = game.history().len() + 1;
+
+        let turn = ("turn", i.into());
+        let max = ("max", game.max_guesses().into());
+        let text = match timed {
+            Some(_) => {
+                let time = ("time", render::duration(game.elapsed()).into());
+                style
+                    .messages()
+                    .format("prompt-guess-timed", &[turn, max, time])
+            }
+            None => style.messages().format("prompt-guess", &[turn, max]),
+        };
+        let line = match prompt.read(&format!("{} ", text)) {
+            Ok(line) => line,
+            Err(Stop::End) => return Flow::Quit,
+            Err(Stop::Interrupted) => return Flow::Interrupted,
+        };
+
+        let input = command::parse(&line);
+        // the time limit is only checked when a guess is made
+        let limit = timed.and_then(|timed| timed.limit);
+        if let (Input::Guess(_), Some(limit)) = (input, limit) {
+            if game.elapsed() > limit {
+                println!(
+                    "{}",
+                    style
+                        .messages()
+                        .format("time-up", &[("time", render::duration(limit).into())])
+                );
+                game.give_up();
+                continue;
+            }
+        }
+
+        match input {
+            Input::Guess(guess) => {
+                let guess = guess.to_lowercase();
+                if !guess.is_empty() {
+                    // invalid guesses are remembered too, so typos can be fixed
+                    prompt.remember(&guess);
+                }
+                let before = game.remaining_candidates_count();
+                match game.guess(&guess) {
+                    Ok(_) => {
+                        // assist mode shows how many are left before each guess anyway
+                        if !game.assist() {
+                            narrowed = before.zip(game.remaining_candidates_count());
+                        }
+                        // the bot makes a guess for each one of the player's
+                        if let Some(opponent) = opponent.as_deref_mut() {
+                            opponent.take_turn();
+                        }
+                    }
+                    Err(e) => println!("{}", render::rejection(style.messages(), &guess, &e)),
+                }
+            }
+            Input::Command(Command::Quit) => return Flow::Quit,
+            Input::Command(Command::Skip | Command::GiveUp)
+                if daily && game.history().is_empty() =>
+            {
+                println!("{}", style.messages().format("daily-give-up", &[]))
+            }
+            Input::Command(Command::Skip) => game.give_up(),
+            Input::Command(Command::GiveUp) => {
+                match confirm(prompt, &style.messages().format("prompt-give-up", &[])) {
+                    Ok(true) => game.give_up(),
+                    Ok(false) | Err(Stop::End) => {}
+                    Err(Stop::Interrupted) => return Flow::Interrupted,
+                }
+            }
+            Input::Command(Command::Save(path)) => {
+                let target = path.map(Path::new);
+                match checkpoint.export(game, target) {
+                    Ok(path) => println!(
+                        "{}",
+                        style
+                            .messages()
+                            .format("saved-game", &[("path", path.display().to_string().into())])
+                    ),
+                    Err(e)
+                        if e.kind() == ErrorKind::AlreadyExists
+                            && target.is_some_and(|target| !target.is_dir()) =>
+                    {
+                        println!(
+                            "{}",
+                            style.messages().format(
+                                "save-exists",
+                                &[("path", path.unwrap_or_default().into())]
+                            )
+                        )
+                    }
+                    Err(e) => {
+                        let dir = target.unwrap_or(checkpoint.transcripts());
+                        let error = export_error(style.messages(), dir, &e);
+                        println!(
+                            "{}",
+                            style
+                                .messages()
+                                .format("save-failed", &[("error", error.into())])
+                        )
+                    }
+                }
+            }
+            Input::Command(Command::Copy) => {
+                println!("{}", style.messages().format("copy-later", &[]))
+            }
+            Input::Command(Command::Hint) => match game.hint() {
+                Ok(hint) => println!(
+                    "{}",
+                    style.messages().format(
+                        "hint",
+                        &[
+                            ("hint", style.messages().hint(&hint).into()),
+                            ("count", game.hints_left().into()),
+                        ],
+                    )
+                ),
+                Err(e) => println!("{}", style.messages().hint_error(&e)),
+            },
+            Input::Unknown(command) => println!(
+                "{}",
+                style
+                    .messages()
+                    .format("unknown-command", &[("command", command.into())])
+            ),
+        }
+    }
+    Flow::Continue
+}
+
+/// The first guess that left only a handful of possible answers, and what
+/// they were, unless it found the word anyway
+fn narrowed_down<'a, S: Started>(game: &Wordle<'a, S>) -> Option<(usize, Vec<&'a str>)> {
+    let mut candidates = game.answers().to_vec();
+    for (i, (guess, statuses)) in game.history().iter().enumerate() {
+        candidates = filter::filter_candidates(&candidates, guess, statuses);
+        if candidates.len() <= HANDFUL {
+            let found = statuses.iter().all(|s| *s == LetterStatus::Correct);
+            // an answer that isn't in the list leaves none at all
+            return (!found && !candidates.is_empty()).then_some((i + 1, candidates));
+        }
+    }
+    None
+}
+
+/// Draw the board so far and, once a guess has been made, the keyboard.
+///
+/// With `--clear`, the screen is cleared first, so the board stays in place.
+fn draw<S: Started>(game: &Wordle<'_, S>, style: &Style) {
+    if style.clear() {
+        print!("{}", render::CLEAR_SCREEN);
+    }
+    println!("{}", style.turn(game));
+    if !game.history().is_empty() {
+        println!("{}", style.keyboard(&game.keyboard(), game.alphabet()));
+    }
+}
+
+/// Ask whether to play another game; anything but "n" means yes.
+///
+/// `:copy` copies the share text of the game just played, and then asks again.
+fn play_again(
+    messages: &Messages,
+    prompt: &mut dyn Prompt,
+    copy: &mut dyn FnMut(),
+) -> Result<bool, Stop> {
+    let question = format!("{} ", messages.format("prompt-play-again", &[]));
+    loop {
+        let answer = prompt.read(&question)?;
+        if command::parse(&answer) == Input::Command(Command::Copy) {
+            copy();
+            continue;
+        }
+        return Ok(!answer.trim().eq_ignore_ascii_case("n"));
+    }
+}
+
+/// Copy the share text to the clipboard, which is only set up the first time
+/// it's needed, and say whether it worked
+fn copy_share(messages: &Messages, clipboard: &mut Option<Box<dyn Clipboard>>, text: &str) {
+    let clipboard = clipboard.get_or_insert_with(clipboard::system);
+    println!("{}", clipboard::copy(messages, clipboard.as_mut(), text));
+}
+
+/// Ask a yes or no question; anything but "y" means no
+fn confirm(prompt: &mut dyn Prompt, question: &str) -> Result<bool, Stop> {
+    let answer = prompt.read(&format!("{} ", question))?;
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Where stats are kept: `stats.json` in `dir` if there is one, or else in the
+/// platform's data directory, e.g. `~/.local/share/wordle/stats.json` on Linux.
+///
+/// Hard mode games are kept separately, in `stats-hard.json`, and so are games
+/// in other languages, e.g. in `stats.es.json`, so each has its own streaks.
+fn stats_store(dir: Option<&Path>, language: Option<&str>, hard_mode: bool) -> Option<StatsStore> {
+    let name = if hard_mode { "stats-hard" } else { "stats" };
+    let file = match language {
+        Some(code) => format!("{}.{}.json", name, code),
+        None => format!("{}.json", name),
+    };
+    match dir {
+        Some(dir) => Some(StatsStore::new(dir.join(file))),
+        None => ProjectDirs::from("", "", "wordle")
+            .map(|dirs| StatsStore::new(dirs.data_dir().join(file))),
+    }
+}
+
+/// Today's date, where the player is or in UTC
+fn today(utc: bool) -> NaiveDate {
+    if utc {
+        Utc::now().date_naive()
+    } else {
+        Local::now().date_naive()
+    }
+}
+
+/// Write every game in the game logs, in hard mode or not, to `path`, in
+/// `format` or the one its extension stands for; exits if it can't
+fn export_stats(
+    messages: &Messages,
+    path: &Path,
+    format: Option<ExportFormat>,
+    stats_dir: Option<&Path>,
+    language: Option<&str>,
+) {
+    let Some(format) = format.or_else(|| ExportFormat::from_path(path)) else {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-export-format",
+                &[("path", path.display().to_string().into())]
+            )
+        );
+        process::exit(1);
+    };
+
+    let mut games = Vec::new();
+    for store in [false, true]
+        .into_iter()
+        .filter_map(|hard_mode| stats_store(stats_dir, language, hard_mode))
+    {
+        match store.games() {
+            Ok(logged) => games.extend(logged),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "error-read-log",
+                        &[
+                            ("path", store.log_path().display().to_string().into()),
+                            ("error", e.to_string().into()),
+                        ],
+                    )
+                );
+                process::exit(1);
+            }
+        }
+    }
+    // the sort is stable, so games on the same day stay in the order they were played
+    games.sort_by_key(|game| game.date);
+
+    let written = fs::File::create(path).and_then(|file| {
+        let out = io::BufWriter::new(file);
+        match format {
+            ExportFormat::Csv => stats::write_csv(out, &games),
+            ExportFormat::Json => stats::write_json(out, &games),
+        }
+    });
+    if let Err(e) = written {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-write",
+                &[
+                    ("path", path.display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+        process::exit(1);
+    }
+    println!(
+        "{}",
+        messages.format(
+            "exported",
+            &[
+                ("count", games.len().into()),
+                ("path", path.display().to_string().into()),
+            ],
+        )
+    );
+}
+
+/// Fold the stats in the file at `path`, and its game log, into the stats in
+/// `store`; exits if it can't
+fn merge_stats(messages: &Messages, path: &Path, store: Option<StatsStore>) {
+    let Some(store) = store else {
+        eprintln!("{}", messages.format("error-no-stats-dir", &[]));
+        process::exit(1);
+    };
+    // a missing file would load as no games played, which is surely a mistake
+    let other = if path.is_file() {
+        StatsStore::new(path).load()
+    } else {
+        Err(io::Error::from(ErrorKind::NotFound))
+    };
+    let other = other.unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-read-stats",
+                &[
+                    ("path", path.display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+        process::exit(1);
+    });
+    let Some(mut stats) = load_stats(messages, &store) else {
+        process::exit(1);
+    };
+
+    let summary = stats.merge(&other);
+    for conflict in &summary.conflicts {
+        eprintln!(
+            "{}",
+            messages.format(
+                "warning-merge-conflict",
+                &[
+                    ("puzzle", conflict.kept.puzzle.unwrap_or_default().into()),
+                    ("date", conflict.kept.date.to_string().into()),
+                ],
+            )
+        );
+    }
+    if let Err(e) = store.save(&stats).and_then(|()| store.save_games(&stats)) {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-save-stats",
+                &[
+                    ("path", store.path().display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+        process::exit(1);
+    }
+    println!(
+        "{}",
+        messages.format(
+            "merged",
+            &[
+                ("added", summary.added.into()),
+                ("path", path.display().to_string().into()),
+                (
+                    "skipped",
+                    (summary.duplicates + summary.conflicts.len() as u32).into(),
+                ),
+            ],
+        )
+    );
+}
+
+/// Where a game interrupted by Ctrl-C is saved: `resume.json` alongside the stats
+fn resume_path(stats_dir: Option<&Path>) -> Option<PathBuf> {
+    match stats_dir {
+        Some(dir) => Some(dir.join("resume.json")),
+        None => ProjectDirs::from("", "", "wordle").map(|dirs| dirs.data_dir().join("resume.json")),
+    }
+}
+
+/// Where results that couldn't be sent to the server are kept until they can
+/// be: `outbox.json` alongside the stats
+fn outbox_path(stats_dir: Option<&Path>) -> Option<PathBuf> {
+    match stats_dir {
+        Some(dir) => Some(dir.join("outbox.json")),
+        None => ProjectDirs::from("", "", "wordle").map(|dirs| dirs.data_dir().join("outbox.json")),
+    }
+}
+
+/// Send the results kept from when the server couldn't be reached; any it
+/// still can't be reached for are kept for next time
+fn send_waiting(messages: &Messages, client: &dyn ApiClient, outbox: &Outbox) {
+    match outbox.send(client) {
+        Ok(sent) => {
+            if !sent.accepted.is_empty() {
+                println!(
+                    "{}",
+                    messages.format("outbox-sent", &[("count", sent.accepted.len().into())])
+                );
+            }
+            for reason in sent.rejected {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "warning-outbox-rejected",
+                        &[("error", reason.to_string().into())]
+                    )
+                );
+            }
+        }
+        Err(e) => eprintln!(
+            "{}",
+            messages.format(
+                "warning-outbox-send",
+                &[
+                    ("path", outbox.path().display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        ),
+    }
+}
+
+/// Put a daily puzzle's result on the server's leaderboard, and show the
+/// leaderboard; if the server can't be reached, the result is kept in
+/// `outbox` to send next time.
+///
+/// Only wins played without the solver can go on the leaderboard, so other
+/// results aren't sent.
+fn submit_result(
+    messages: &Messages,
+    client: &dyn ApiClient,
+    outbox: Option<&Outbox>,
+    transcript: Transcript,
+) {
+    // the server won't put games with help on the leaderboard
+    if matches!(transcript.status, GameStatus::Won { .. })
+        && !transcript.assist
+        && transcript.hints.is_empty()
+    {
+        let submission = Submission {
+            player: None,
+            transcript,
+        };
+        match client.submit(&submission) {
+            Ok(entry) => println!(
+                "{}",
+                messages.format(
+                    "leaderboard-rank",
+                    &[("rank", entry.rank.into()), ("player", entry.player.into()),],
+                )
+            ),
+            Err(e @ ApiError::Rejected(_)) => eprintln!(
+                "{}",
+                messages.format(
+                    "warning-result-rejected",
+                    &[("error", render::api_error(messages, &e).into())]
+                )
+            ),
+            Err(e) => {
+                let error = ("error", render::api_error(messages, &e).into());
+                match outbox.map(|outbox| outbox.push(submission)) {
+                    Some(Ok(())) => {
+                        eprintln!("{}", messages.format("warning-result-kept", &[error]))
+                    }
+                    Some(Err(saving)) => eprintln!(
+                        "{}",
+                        messages.format(
+                            "warning-result-lost",
+                            &[error, ("reason", saving.to_string().into())],
+                        )
+                    ),
+                    None => eprintln!("{}", messages.format("warning", &[error])),
+                }
+                // the leaderboard surely can't be reached either
+                return;
+            }
+        }
+    }
+    match client.leaderboard() {
+        Ok(board) => println!("{}", render::leaderboard(messages, &board)),
+        Err(e) => eprintln!(
+            "{}",
+            messages.format(
+                "warning-leaderboard",
+                &[("error", render::api_error(messages, &e).into())]
+            )
+        ),
+    }
+}
+
+/// Why a transcript couldn't be saved into `dir`; running out of names for it
+/// there is an error with no text of its own
+fn export_error(messages: &Messages, dir: &Path, e: &io::Error) -> String {
+    if e.kind() == ErrorKind::AlreadyExists {
+        messages.format(
+            "transcripts-taken",
+            &[("path", dir.display().to_string().into())],
+        )
+    } else {
+        e.to_string()
+    }
+}
+
+/// Pick up a saved game from `path`, or else the one saved when the player
+/// last pressed Ctrl-C, or exit with an error message if it can't be.
+///
+/// The game is played again from the start to check it, so a save that's
+/// been tampered with or that doesn't fit the word lists is refused.
+///
+/// The Ctrl-C save is removed once it's been resumed; it's saved again if the
+/// player presses Ctrl-C again. Saves from elsewhere are left alone.
+fn resume<'a>(
+    messages: &Messages,
+    game: Wordle<'a, NoWord>,
+    stats_dir: Option<&Path>,
+    path: Option<&Path>,
+) -> (Wordle<'a>, Transcript) {
+    let (path, autosave) = match path {
+        Some(path) => (path.to_owned(), false),
+        None => (
+            resume_path(stats_dir).unwrap_or_else(|| {
+                eprintln!("{}", messages.format("error-no-data-dir", &[]));
+                process::exit(1);
+            }),
+            true,
+        ),
+    };
+    // every message about the save names it
+    let failed = |id: &str, e: &dyn std::fmt::Display| {
+        messages.format(
+            id,
+            &[
+                ("path", path.display().to_string().into()),
+                ("error", e.to_string().into()),
+            ],
+        )
+    };
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| {
+        match e.kind() {
+            ErrorKind::NotFound if autosave => eprintln!("{}", failed("error-no-saved-game", &e)),
+            _ => eprintln!("{}", failed("error-read-saved-game", &e)),
+        }
+        process::exit(1);
+    });
+    let transcript: Transcript = serde_json::from_str(&text).unwrap_or_else(|e| {
+        eprintln!("{}", failed("error-invalid-saved-game", &e));
+        process::exit(1);
+    });
+    let game = game.resume(&transcript).unwrap_or_else(|e| {
+        eprintln!("{}", failed("error-resume", &e));
+        process::exit(1);
+    });
+
+    if autosave {
+        if let Err(e) = fs::remove_file(&path) {
+            eprintln!("{}", failed("warning-remove-saved-game", &e));
+        }
+    }
+    (game, transcript)
+}
+
+/// Sum up a resumed game that was already over: its board, how it ended, and its share text
+fn summarize(game: &Wordle<'_, Finished>, style: &Style, puzzle: Option<u32>) {
+    let messages = style.messages();
+    let banner = match puzzle {
+        Some(puzzle) => messages.format("daily-over", &[("puzzle", puzzle.into())]),
+        None => messages.format("game-over", &[]),
+    };
+    println!("--- {} ---", banner);
+    draw(game, style);
+    let answer = game.reveal().to_uppercase();
+    let ending = match game.status() {
+        GameStatus::Won { guesses } => messages.format(
+            "found",
+            &[
+                ("answer", answer.into()),
+                ("guesses", guesses.into()),
+                ("max", game.max_guesses().into()),
+            ],
+        ),
+        GameStatus::Lost => messages.format("the-word-was", &[("answer", answer.into())]),
+        GameStatus::InProgress => unreachable!("The game is over"),
+    };
+    println!("{}", ending);
+    println!(
+        "{}",
+        messages.format("time", &[("time", render::duration(game.elapsed()).into())])
+    );
+    println!(
+        "\n{}\n",
+        share::share_text(messages, game, puzzle, style.tiles())
+    );
+}
+
+/// When the next daily puzzle comes out, e.g. `Next Wordle in 3h 12m`
+fn next_wordle(messages: &Messages, utc: bool) -> String {
+    let time = render::countdown(until_next_puzzle(utc));
+    messages.format("next-wordle", &[("time", time.into())])
+}
+
+/// How many of the games or chains played so far were won, with `id` saying which
+fn won(messages: &Messages, id: &str, won: u32, played: u32) -> String {
+    messages.format(id, &[("won", won.into()), ("played", played.into())])
+}
+
+/// Why the `--answer` word can't be played
+fn invalid_answer(messages: &Messages, answer: &str, e: &GuessError) -> String {
+    messages.format(
+        "error-invalid-answer",
+        &[("error", render::rejection(messages, answer, e).into())],
+    )
+}
+
+/// What assist mode needs to suggest guesses
+#[derive(Debug)]
+struct Assist {
+    /// The best first guesses
+    openers: Vec<Suggestion>,
+    /// The searches for the best guesses after that, kept between runs; or
+    /// `None` to search again every time
+    cache: Option<SolverCache>,
+    /// Whether to explain each suggestion
+    explain: bool,
+}
+
+/// The cache of the solver's searches for assist mode, or `None` if there's
+/// nowhere to keep it
+fn solver_cache() -> Option<SolverCache> {
+    ProjectDirs::from("", "", "wordle")
+        .map(|dirs| SolverCache::open(dirs.cache_dir().join("solver.txt")))
+}
+
+/// Where the config file is kept, e.g. `~/.config/wordle/config.toml` on Linux
+fn config_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "wordle").map(|dirs| dirs.config_dir().join("config.toml"))
+}
+
+/// Load the config file, warning about any settings that aren't recognized.
+///
+/// Exits with an error message if the file can't be read or isn't valid.
+fn load_config(path: &Path, messages: &Messages) -> Config {
+    match Config::load(path) {
+        Ok((config, unknown)) => {
+            for key in unknown {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "warning-unknown-setting",
+                        &[
+                            ("key", key.into()),
+                            ("path", path.display().to_string().into()),
+                        ],
+                    )
+                );
+            }
+            config
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                messages.format(
+                    "error-invalid-config",
+                    &[
+                        ("path", path.display().to_string().into()),
+                        ("error", e.to_string().into()),
+                    ],
+                )
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Load the theme to draw with for the lifetime of the program: a built-in one
+/// by name, or one from a file, or the classic theme (or the high contrast
+/// one, if colorblind) if none is given.
+///
+/// Exits with an error message if the theme file can't be read or isn't valid.
+fn load_theme(config: &Config, messages: &Messages) -> &'static Theme {
+    let Some(name) = &config.theme else {
+        return match config.colorblind {
+            true => &theme::HIGH_CONTRAST,
+            false => &theme::CLASSIC,
+        };
+    };
+    if let Some(theme) = Theme::builtin(name) {
+        return theme;
+    }
+    match Theme::load(Path::new(name)) {
+        Ok(theme) => Box::leak(Box::new(theme)),
+        Err(e) => {
+            eprintln!(
+                "{}",
+                messages.format(
+                    "error-read-theme",
+                    &[("name", name.into()), ("error", e.to_string().into())],
+                )
+            );
+            eprintln!(
+                "{}",
+                messages.format(
+                    "builtin-themes",
+                    &[("names", Theme::BUILTIN.join(", ").into())]
+                )
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Load the catalog of messages for the configured locale.
+///
+/// Without one, the locale comes from the environment, as for
+/// [`environment_messages`].
+fn load_messages(config: &Config) -> &'static Messages {
+    let Some(locale) = &config.locale else {
+        return environment_messages();
+    };
+    if let Some(messages) = Messages::builtin(locale) {
+        return messages;
+    }
+    match Messages::load(Path::new(locale)) {
+        Ok(messages) => Box::leak(Box::new(messages)),
+        Err(e) => {
+            let messages = environment_messages();
+            eprintln!(
+                "{}",
+                messages.format(
+                    "error-read-locale",
+                    &[("name", locale.into()), ("error", e.to_string().into())],
+                )
+            );
+            eprintln!(
+                "{}",
+                messages.format(
+                    "builtin-locales",
+                    &[("names", Messages::BUILTIN.join(", ").into())]
+                )
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// The catalog of messages for the environment's locale, like `LANG`'s, for
+/// before the config file is read; if that names a language there's no
+/// catalog for, it's English.
+fn environment_messages() -> &'static Messages {
+    let language = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+    language
+        .as_deref()
+        .and_then(messages::language)
+        .and_then(Messages::builtin)
+        .unwrap_or(Messages::english())
+}
+
+/// Load the definitions of words; if they can't be read, there are none, after a warning
+fn load_definitions(messages: &Messages, path: &Path) -> Definitions {
+    Definitions::load(path).unwrap_or_else(|e| {
+        eprintln!(
+            "{}",
+            messages.format(
+                "warning-read-definitions",
+                &[
+                    ("path", path.display().to_string().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        );
+        Definitions::default()
+    })
+}
+
+/// Load the stats, moving a corrupt stats file aside and starting afresh.
+///
+/// Returns `None`, after printing a warning, if the stats couldn't be read.
+fn load_stats(messages: &Messages, store: &StatsStore) -> Option<Stats> {
+    let path = ("path", store.path().display().to_string().into());
+    match store.load() {
+        Ok(stats) => Some(stats),
+        Err(e) if e.kind() == ErrorKind::InvalidData => match store.recover() {
+            Ok(backup) => {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "warning-corrupt-stats",
+                        &[
+                            path,
+                            ("error", e.to_string().into()),
+                            ("backup", backup.display().to_string().into()),
+                        ],
+                    )
+                );
+                Some(Stats::new())
+            }
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "warning-move-stats",
+                        &[path, ("error", e.to_string().into())]
+                    )
+                );
+                None
+            }
+        },
+        Err(e) => {
+            eprintln!(
+                "{}",
+                messages.format(
+                    "warning-read-stats",
+                    &[path, ("error", e.to_string().into())]
+                )
+            );
+            None
+        }
+    }
+}
+
+/// Load the word lists for a `--lang` language for the lifetime of the program,
+/// or exit with an error message naming the directory searched.
+fn load_language(
+    messages: &Messages,
+    dir: &Path,
+    code: &str,
+) -> (&'static [&'static str], &'static [&'static str]) {
+    let (guesses, answers) = lang::paths(dir, code);
+    if !guesses.is_file() || !answers.is_file() {
+        eprintln!(
+            "{}",
+            messages.format(
+                "error-no-language",
+                &[
+                    ("code", code.into()),
+                    ("path", startup::resolve(dir).display().to_string().into()),
+                ],
+            )
+        );
+        eprintln!("{}", messages.format("language-help", &[]));
+        process::exit(1);
+    }
+
+    let lists = wordlist::load(&guesses)
+        .and_then(|guesses| Ok((guesses, wordlist::load(&answers)?)))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", startup::word_list_error(messages, &e));
+            process::exit(1);
+        });
+    (lists.0.leak(), lists.1.leak())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prompt::Lines;
+
+    #[test]
+    fn test_play() {
+        let words = ["crane", "slate"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+
+        // guesses are trimmed and lowercased before they're checked
+        let mut output = Vec::new();
+        let mut prompt = Lines::new(" Slate\nCRANE  \n".as_bytes(), &mut output);
+        let flow = play(
+            &mut game,
+            &mut prompt,
+            &Style::new(false),
+            false,
+            Extras::default(),
+            &Checkpoint::default(),
+        );
+        assert_eq!(Flow::Continue, flow);
+        let guessed: Vec<&str> = game.history().iter().map(|(guess, _)| *guess).collect();
+        assert_eq!(vec!["slate", "crane"], guessed);
+        assert!(matches!(game.status(), GameStatus::Won { .. }));
+
+        // a game can be quit partway through
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        let mut prompt = Lines::new("slate\n".as_bytes(), Vec::new());
+        let flow = play(
+            &mut game,
+            &mut prompt,
+            &Style::new(false),
+            false,
+            Extras::default(),
+            &Checkpoint::default(),
+        );
+        assert_eq!(Flow::Quit, flow);
+        assert_eq!(1, game.history().len());
+    }
+
+    #[test]
+    fn test_narrowed_down() {
+        let words = [
+            "abate", "agate", "crane", "elate", "plate", "skate", "slate", "state",
+        ];
+        let mut game = Wordle::new(&words, &words).set_answer("skate").unwrap();
+
+        // "crane" leaves the seven words ending in "a?e" without a C, R or N
+        game.guess("crane").unwrap();
+        assert_eq!(None, narrowed_down(&game));
+        game.guess("slate").unwrap();
+        assert_eq!(Some((2, vec!["skate", "state"])), narrowed_down(&game));
+
+        // finding the word at the same time as narrowing it down isn't worth listing
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        game.guess("crane").unwrap();
+        assert_eq!(None, narrowed_down(&game));
+    }
+}
@@ -0,0 +1,168 @@
+//! Reading the player's input, with line editing when it comes from a terminal
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use rustyline::{
+    config::BellStyle, error::ReadlineError, history::MemHistory, CompletionType, Config,
+};
+
+use wordle::messages::Messages;
+
+use crate::complete::Completions;
+
+/// The number of lines kept in the history
+const HISTORY_SIZE: usize = 100;
+
+/// Why a line couldn't be read
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stop {
+    /// The input ended, e.g. because the player pressed Ctrl-D
+    End,
+    /// The player pressed Ctrl-C
+    Interrupted,
+}
+
+/// Somewhere to read the player's input from, a line at a time
+pub trait Prompt {
+    /// Show `text` and read a line, without its line ending
+    fn read(&mut self, text: &str) -> Result<String, Stop>;
+
+    /// Remember a line, so the player can bring it back later in the session
+    fn remember(&mut self, _line: &str) {}
+
+    /// Complete what the player types to one of these words when they press Tab
+    fn set_completions(&mut self, _completions: Completions) {}
+}
+
+/// A prompt with line editing, and a history of the lines entered that's
+/// only kept for the session, so it can't give away past answers.
+///
+/// Pressing Tab cycles through the completions of what's been typed, or rings
+/// the bell if there aren't any.
+#[derive(Debug)]
+pub struct Editor {
+    /// The line editor
+    editor: rustyline::Editor<Completions, MemHistory>,
+}
+
+impl Editor {
+    /// Start editing lines in the terminal, with an empty history
+    pub fn new() -> rustyline::Result<Self> {
+        let config = Config::builder()
+            .max_history_size(HISTORY_SIZE)?
+            .history_ignore_dups(true)?
+            .auto_add_history(false)
+            .completion_type(CompletionType::Circular)
+            .bell_style(BellStyle::Audible)
+            .build();
+        let history = MemHistory::with_config(&config);
+        Ok(Self {
+            editor: rustyline::Editor::with_history(config, history)?,
+        })
+    }
+}
+
+impl Prompt for Editor {
+    fn read(&mut self, text: &str) -> Result<String, Stop> {
+        match self.editor.readline(text) {
+            Ok(line) => Ok(line),
+            Err(ReadlineError::Interrupted) => Err(Stop::Interrupted),
+            Err(ReadlineError::Eof) => {
+                // end the prompt's line, so the shell prompt doesn't start after it
+                println!();
+                Err(Stop::End)
+            }
+            Err(e) => panic!("Failed to read line: {}", e),
+        }
+    }
+
+    fn remember(&mut self, line: &str) {
+        // the history is only in memory, so there's nothing to go wrong
+        let _ = self.editor.add_history_entry(line);
+    }
+
+    fn set_completions(&mut self, completions: Completions) {
+        self.editor.set_helper(Some(completions));
+    }
+}
+
+/// A prompt that reads plain lines, e.g. from a pipe or a script of input
+#[derive(Debug)]
+pub struct Lines<R, W> {
+    /// Where lines are read from
+    input: R,
+    /// Where prompts are shown
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Lines<R, W> {
+    /// Read lines from `input`, showing prompts in `output`
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+}
+
+impl<R: BufRead, W: Write> Prompt for Lines<R, W> {
+    fn read(&mut self, text: &str) -> Result<String, Stop> {
+        write!(self.output, "{}", text).expect("Could not write prompt");
+        self.output.flush().expect("Could not flush prompt");
+
+        let mut line = String::new();
+        let read = self
+            .input
+            .read_line(&mut line)
+            .expect("Failed to read line");
+        if read == 0 {
+            // end the prompt's line, so the shell prompt doesn't start after it
+            writeln!(self.output).expect("Could not write prompt");
+            return Err(Stop::End);
+        }
+        let end = line.trim_end_matches(['\r', '\n']).len();
+        line.truncate(end);
+        Ok(line)
+    }
+}
+
+/// The prompt for stdin: line editing if it's a terminal, or plain lines otherwise
+pub fn stdin(messages: &Messages) -> Box<dyn Prompt> {
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        match Editor::new() {
+            Ok(editor) => return Box::new(editor),
+            Err(e) => eprintln!(
+                "{}",
+                messages.format("warning-line-editing", &[("error", e.to_string().into())])
+            ),
+        }
+    }
+    Box::new(Lines::new(io::stdin().lock(), io::stdout()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut output = Vec::new();
+        let mut prompt = Lines::new("crane\r\n  slate \n".as_bytes(), &mut output);
+        assert_eq!(Ok("crane".to_owned()), prompt.read("1: "));
+        assert_eq!(Ok("  slate ".to_owned()), prompt.read("2: "));
+        assert_eq!(Err(Stop::End), prompt.read("3: "));
+        assert_eq!("1: 2: 3: \n", String::from_utf8(output).unwrap());
+    }
+}
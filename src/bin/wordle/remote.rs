@@ -0,0 +1,374 @@
+//! Sending daily results to a wordle-server leaderboard, with `--server`
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    error::Error,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ureq::{http::StatusCode, Agent};
+use wordle::transcript::Transcript;
+
+/// How long to wait for the server before giving up and sending the result later
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A result to put on the leaderboard, as the server's `POST /daily/result` takes it
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    /// The player's name, or `None` for the server to make one up
+    pub player: Option<String>,
+    /// The game, which the server plays again to check it
+    pub transcript: Transcript,
+}
+
+/// A result on the leaderboard
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Entry {
+    /// Where the result places, starting at 1
+    pub rank: usize,
+    /// The player's name, or the one the server made up
+    pub player: String,
+    /// The number of guesses it took to win
+    pub guesses: usize,
+    /// The time spent on the game, in milliseconds
+    pub time_ms: u64,
+    /// Whether the game was played in hard mode
+    pub hard_mode: bool,
+}
+
+/// The best results for a day's puzzle, as the server's `GET /daily/leaderboard` gives them
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Leaderboard {
+    /// The day of the puzzle
+    pub date: NaiveDate,
+    /// The number of the puzzle
+    pub puzzle: u32,
+    /// The results, best first
+    pub results: Vec<Entry>,
+}
+
+/// Today's puzzle, as the server's `GET /daily/meta` describes it
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Meta {
+    /// Today's date, in UTC
+    pub date: NaiveDate,
+    /// The number of today's puzzle
+    pub puzzle: u32,
+    /// A keyed hash of today's answer, if the server picks its answers with a
+    /// daily secret
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+}
+
+impl Meta {
+    /// Whether the server picks its own answers with a daily secret, rather
+    /// than going through the answer list in order as this game does; then
+    /// it refuses every daily result from this game
+    pub fn keyed(&self) -> bool {
+        self.fingerprint.is_some()
+    }
+}
+
+/// The body of an error response from the server
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    /// What went wrong
+    error: String,
+}
+
+/// Why the server didn't take a result, or couldn't be asked
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ApiError {
+    /// The server turned the request down, so sending it again won't help
+    Rejected(String),
+    /// The server couldn't be reached, or had trouble of its own, so the
+    /// request can be tried again later
+    Unavailable(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rejected(reason) => write!(f, "the server refused it: {}", reason),
+            Self::Unavailable(reason) => write!(f, "could not reach the server: {}", reason),
+        }
+    }
+}
+
+impl Error for ApiError {}
+
+/// The parts of the server's API the game uses; a trait so tests can stand
+/// in for the server
+pub trait ApiClient {
+    /// Put a result on the leaderboard, and get back where it placed
+    fn submit(&self, submission: &Submission) -> Result<Entry, ApiError>;
+
+    /// The leaderboard for today's puzzle
+    fn leaderboard(&self) -> Result<Leaderboard, ApiError>;
+
+    /// Today's puzzle, as the server has it
+    fn meta(&self) -> Result<Meta, ApiError>;
+}
+
+/// Talks to a wordle-server over HTTP
+#[derive(Debug)]
+pub struct HttpClient {
+    /// The server's base URL, without a trailing `/`
+    url: String,
+    /// Makes the requests
+    agent: Agent,
+}
+
+impl HttpClient {
+    /// Talk to the server at `url`, e.g. `https://wordle.example.com`
+    pub fn new(url: &str) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self {
+            url: url.trim_end_matches('/').to_owned(),
+            agent,
+        }
+    }
+}
+
+/// Read a response's body as `T`, or the error the server gave instead
+fn read<T: for<'de> Deserialize<'de>>(
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<T, ApiError> {
+    let mut response = response.map_err(|e| ApiError::Unavailable(e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .body_mut()
+            .read_json()
+            .map_err(|e| ApiError::Unavailable(e.to_string()));
+    }
+    let reason = response
+        .body_mut()
+        .read_json::<ErrorBody>()
+        .map_or_else(|_| status.to_string(), |body| body.error);
+    // being told to slow down or wait is worth trying again later; other
+    // client errors aren't
+    let retry = status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS;
+    if retry {
+        Err(ApiError::Unavailable(reason))
+    } else {
+        Err(ApiError::Rejected(reason))
+    }
+}
+
+impl ApiClient for HttpClient {
+    fn submit(&self, submission: &Submission) -> Result<Entry, ApiError> {
+        read(
+            self.agent
+                .post(format!("{}/daily/result", self.url))
+                .send_json(submission),
+        )
+    }
+
+    fn leaderboard(&self) -> Result<Leaderboard, ApiError> {
+        read(
+            self.agent
+                .get(format!("{}/daily/leaderboard", self.url))
+                .call(),
+        )
+    }
+
+    fn meta(&self) -> Result<Meta, ApiError> {
+        read(self.agent.get(format!("{}/daily/meta", self.url)).call())
+    }
+}
+
+/// Results that couldn't be sent yet, kept in a file until they can be
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Outbox {
+    /// The file, a JSON array of [`Submission`]s
+    path: PathBuf,
+}
+
+/// What [`Outbox::send`] did with the results waiting in it
+#[derive(Debug, Default)]
+pub struct Sent {
+    /// The results put on the leaderboard, and where they placed
+    pub accepted: Vec<Entry>,
+    /// The results the server turned down, and why; they've been dropped
+    pub rejected: Vec<String>,
+}
+
+impl Outbox {
+    /// Keep results waiting to be sent in the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file the results are kept in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The results waiting to be sent, oldest first
+    pub fn load(&self) -> io::Result<Vec<Submission>> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => {
+                serde_json::from_str(&text).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the results waiting to be sent; with none, the file is removed
+    fn save(&self, waiting: &[Submission]) -> io::Result<()> {
+        if waiting.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(waiting)?)
+    }
+
+    /// Add a result to send later
+    pub fn push(&self, submission: Submission) -> io::Result<()> {
+        let mut waiting = self.load()?;
+        waiting.push(submission);
+        self.save(&waiting)
+    }
+
+    /// Try to send every result waiting, oldest first, and keep the ones that
+    /// still can't be sent.
+    ///
+    /// Once the server can't be reached, the rest aren't tried.
+    pub fn send(&self, client: &dyn ApiClient) -> io::Result<Sent> {
+        let waiting = self.load()?;
+        let mut sent = Sent::default();
+        let mut unsent = Vec::new();
+        for submission in waiting {
+            if !unsent.is_empty() {
+                unsent.push(submission);
+                continue;
+            }
+            match client.submit(&submission) {
+                Ok(entry) => sent.accepted.push(entry),
+                Err(ApiError::Rejected(reason)) => sent.rejected.push(reason),
+                Err(ApiError::Unavailable(_)) => unsent.push(submission),
+            }
+        }
+        self.save(&unsent)?;
+        Ok(sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    use wordle::Wordle;
+
+    /// Stands in for a server, answering each submission with the next response
+    struct Fake {
+        /// The responses, in the order they're given
+        responses: RefCell<Vec<Result<Entry, ApiError>>>,
+    }
+
+    impl ApiClient for Fake {
+        fn submit(&self, _: &Submission) -> Result<Entry, ApiError> {
+            self.responses.borrow_mut().remove(0)
+        }
+
+        fn leaderboard(&self) -> Result<Leaderboard, ApiError> {
+            Err(ApiError::Unavailable(String::from("offline")))
+        }
+
+        fn meta(&self) -> Result<Meta, ApiError> {
+            Err(ApiError::Unavailable(String::from("offline")))
+        }
+    }
+
+    fn submission(player: &str) -> Submission {
+        let words = ["crane"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        game.guess("crane").unwrap();
+        Submission {
+            player: Some(player.to_owned()),
+            transcript: game.transcript(),
+        }
+    }
+
+    fn entry(rank: usize) -> Entry {
+        Entry {
+            rank,
+            player: String::from("alice"),
+            guesses: 1,
+            time_ms: 0,
+            hard_mode: false,
+        }
+    }
+
+    #[test]
+    fn test_outbox() {
+        let path =
+            std::env::temp_dir().join(format!("wordle-outbox-test-{}.json", std::process::id()));
+        let outbox = Outbox::new(&path);
+        assert_eq!(Vec::<Submission>::new(), outbox.load().unwrap());
+
+        for player in ["alice", "bob", "carol", "dave"] {
+            outbox.push(submission(player)).unwrap();
+        }
+        // once the server can't be reached, the rest wait their turn
+        let client = Fake {
+            responses: RefCell::new(vec![
+                Ok(entry(1)),
+                Err(ApiError::Rejected(String::from("too late"))),
+                Err(ApiError::Unavailable(String::from("offline"))),
+            ]),
+        };
+        let sent = outbox.send(&client).unwrap();
+        assert_eq!(vec![entry(1)], sent.accepted);
+        assert_eq!(vec!["too late"], sent.rejected);
+        let players: Vec<Option<String>> = outbox
+            .load()
+            .unwrap()
+            .into_iter()
+            .map(|submission| submission.player)
+            .collect();
+        assert_eq!(
+            vec![Some("carol".to_owned()), Some("dave".to_owned())],
+            players
+        );
+
+        let client = Fake {
+            responses: RefCell::new(vec![Ok(entry(2)), Ok(entry(3))]),
+        };
+        assert_eq!(2, outbox.send(&client).unwrap().accepted.len());
+        assert!(!path.exists(), "an empty outbox leaves no file behind");
+    }
+}
@@ -0,0 +1,1256 @@
+//! Drawing guesses in the terminal
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    iter,
+    time::Duration,
+};
+
+use owo_colors::{AnsiColors, DynColors, OwoColorize, Style as AnsiStyle, XtermColors};
+use ratatui::crossterm::terminal;
+use serde_json::json;
+use unicode_width::UnicodeWidthStr;
+use wordle::{
+    alphabet::Alphabet,
+    board::BoardStyle,
+    keyboard::KeyboardState,
+    messages::Messages,
+    share::TileSet,
+    solver::{Explanation, Suggestion},
+    speech,
+    state::Started,
+    stats::{render_histogram, Stats, BAR},
+    theme::{self, Color, Theme},
+    GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+use crate::{
+    big,
+    remote::{ApiError, Leaderboard},
+};
+
+/// How far each keyboard row is indented, in columns, like a real keyboard's stagger;
+/// any rows past these are indented like the last
+const ROW_OFFSETS: [usize; 3] = [0, 2, 6];
+
+/// Clears the terminal and moves the cursor to the top left
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// How everything is drawn, decided once at startup
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    /// Whether to use ANSI colors; without them, output is plain ASCII
+    color: bool,
+    /// The colors of the tiles
+    theme: &'static Theme,
+    /// The tiles for share grids
+    tiles: TileSet<'static>,
+    /// Whether to clear the screen before drawing the board
+    clear: bool,
+    /// Whether to describe guesses in sentences, for screen readers, rather than draw them
+    accessible: bool,
+    /// Whether to draw the board as big tiles, when the terminal is wide enough
+    big: bool,
+    /// The language guesses are described in, and rejections explained in
+    messages: &'static Messages,
+}
+
+impl Style {
+    /// Draw with or without colors; share grids use emoji with colors, or ASCII without
+    pub fn new(color: bool) -> Self {
+        let tiles = if color {
+            TileSet::classic()
+        } else {
+            TileSet::ascii()
+        };
+        Self {
+            color,
+            theme: &theme::CLASSIC,
+            tiles,
+            clear: false,
+            accessible: false,
+            big: false,
+            messages: Messages::english(),
+        }
+    }
+
+    /// Use colors unless `no_color` is set, the `NO_COLOR` environment variable
+    /// is set, or stdout isn't a terminal that supports them
+    pub fn detect(no_color: bool) -> Self {
+        let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+        // see https://no-color.org: any value but an empty one counts
+        let no_color = no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::new(io::stdout().is_terminal() && !dumb && !no_color)
+    }
+
+    /// Color the tiles with a different theme, e.g. the high contrast one for
+    /// players who find green and yellow hard to tell apart
+    pub fn with_theme(mut self, theme: &'static Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Describe guesses and explain rejections in another language
+    pub fn with_messages(mut self, messages: &'static Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Use different tiles for share grids
+    pub fn with_tiles(mut self, tiles: TileSet<'static>) -> Self {
+        self.tiles = tiles;
+        self
+    }
+
+    /// Clear the screen before drawing the board, which only makes sense in a terminal
+    pub fn with_clear(mut self, enabled: bool) -> Self {
+        self.clear = enabled;
+        self
+    }
+
+    /// Describe guesses and the keyboard in sentences a screen reader can read
+    /// out, rather than drawing them; this also turns off colors
+    pub fn with_accessible(mut self, enabled: bool) -> Self {
+        self.accessible = enabled;
+        self.color &= !enabled;
+        self
+    }
+
+    /// Draw the board as big tiles, three lines high, when the terminal is wide enough
+    pub fn with_big(mut self, enabled: bool) -> Self {
+        self.big = enabled;
+        self
+    }
+
+    /// Whether the screen is cleared before drawing the board
+    pub fn clear(&self) -> bool {
+        self.clear
+    }
+
+    /// Whether ANSI colors are used
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    /// The colors of the tiles
+    pub fn theme(&self) -> &'static Theme {
+        self.theme
+    }
+
+    /// The catalog the messages shown to the player come from
+    pub fn messages(&self) -> &'static Messages {
+        self.messages
+    }
+
+    /// The style of the dots on empty tiles
+    pub fn empty(&self) -> AnsiStyle {
+        match self.theme.empty {
+            Some(color) => AnsiStyle::new().color(dyn_color(color)),
+            None => AnsiStyle::new().dimmed(),
+        }
+    }
+
+    /// The tiles for share grids
+    pub fn tiles(&self) -> &TileSet<'static> {
+        &self.tiles
+    }
+
+    /// Draw a guess and its feedback.
+    ///
+    /// With colors, this is the guessed letters on colored tiles; without, the
+    /// letters are marked instead, e.g. `[C] (a) r a n` for correct letters,
+    /// letters in the word, and letters not in the word.
+    pub fn guess(&self, guess: &str, statuses: &[LetterStatus]) -> String {
+        if !self.color {
+            return guess
+                .chars()
+                .zip(statuses)
+                .map(|(letter, status)| match status {
+                    LetterStatus::Correct => format!("[{}]", letter.to_uppercase()),
+                    LetterStatus::InWord => format!("({})", letter),
+                    LetterStatus::NotInWord => letter.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(" ");
+        }
+
+        guess
+            .chars()
+            .zip(statuses)
+            .map(|(letter, status)| {
+                let tile = format!(" {} ", letter.to_uppercase());
+                tile.style(ansi(status, self.theme)).to_string()
+            })
+            .collect()
+    }
+
+    /// Draw the game's board: a row for each guess so far, then an empty row
+    /// for each guess left.
+    ///
+    /// Without colors, this is the board's [grid](BoardStyle::Grid); for screen
+    /// readers, it's every guess described in words, then the guesses left. In
+    /// big mode, it's [big tiles](big::board) if they fit in the terminal.
+    pub fn board<S: Started>(&self, game: &Wordle<'_, S>) -> String {
+        if self.accessible {
+            return game
+                .history()
+                .iter()
+                .enumerate()
+                .map(|(i, (guess, statuses))| spoken(self.messages, i + 1, guess, statuses))
+                .chain(guesses_left(self.messages, game))
+                .collect::<Vec<String>>()
+                .join("\n");
+        }
+        if self.big {
+            if let Some(lines) = big::board(self, game, terminal_width()) {
+                return lines.join("\n");
+            }
+        }
+        if !self.color {
+            return game.board(BoardStyle::Grid).to_string();
+        }
+
+        let empty = " · "
+            .repeat(game.word_length())
+            .style(self.empty())
+            .to_string();
+        let guesses = game.history().len();
+        game.history()
+            .iter()
+            .map(|(guess, statuses)| self.guess(guess, statuses))
+            .chain(iter::repeat_n(
+                empty,
+                game.max_guesses().saturating_sub(guesses),
+            ))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Draw the board after a guess: the whole board, or, for screen readers,
+    /// just the latest guess, so the earlier ones aren't read out again
+    pub fn turn<S: Started>(&self, game: &Wordle<'_, S>) -> String {
+        match game.history().last() {
+            Some((guess, statuses)) if self.accessible => {
+                iter::once(spoken(self.messages, game.history().len(), guess, statuses))
+                    .chain(guesses_left(self.messages, game))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+            _ => self.board(game),
+        }
+    }
+
+    /// Draw the alphabet's keyboard, with each letter colored by its best-known status.
+    ///
+    /// Without colors, this lists the letters known to be in the word and the
+    /// letters ruled out instead; for screen readers, it lists the letters with
+    /// each status in sentences.
+    pub fn keyboard(&self, state: &KeyboardState, alphabet: &Alphabet) -> String {
+        if self.accessible {
+            return speech::keyboard(self.messages, state);
+        }
+        if !self.color {
+            let mut in_word: Vec<char> = state
+                .letters_with(LetterStatus::Correct)
+                .chain(state.letters_with(LetterStatus::InWord))
+                .collect();
+            in_word.sort_unstable();
+            let eliminated: Vec<char> = state.letters_with(LetterStatus::NotInWord).collect();
+            return format!(
+                "{}\n{}",
+                self.messages
+                    .format("keyboard-in-word", &[("letters", list(&in_word).into())]),
+                self.messages.format(
+                    "keyboard-eliminated",
+                    &[("letters", list(&eliminated).into())]
+                )
+            );
+        }
+
+        let offsets = ROW_OFFSETS.into_iter().chain(iter::repeat(ROW_OFFSETS[2]));
+        alphabet
+            .keyboard()
+            .iter()
+            .zip(offsets)
+            .map(|(row, offset)| {
+                let keys: Vec<String> = row
+                    .chars()
+                    .map(|letter| {
+                        let key = format!(" {} ", letter.to_uppercase());
+                        match state.get(letter) {
+                            Some(status) => key.style(ansi(&status, self.theme)).to_string(),
+                            None => key,
+                        }
+                    })
+                    .collect();
+                format!("{}{}", " ".repeat(offset), keys.join(" "))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Summarize a player's statistics like the official game's statistics
+    /// popup: the [totals](stats_summary), then the guess distribution as a
+    /// [histogram](Style::histogram) that fits in `width` columns
+    pub fn stats(&self, stats: &Stats, width: usize) -> String {
+        format!(
+            "{}\n{}\n{}",
+            stats_summary(self.messages, stats),
+            self.messages.format("guess-distribution", &[]),
+            self.histogram(stats, width)
+        )
+    }
+
+    /// Draw the guess distribution with [`render_histogram`], in at most
+    /// `width` columns, or [`MAX_WIDTH`] on wide terminals.
+    ///
+    /// The most recent game's row is marked, if it was a win; with colors, its
+    /// bar is the color of a correct tile too. Without colors, the bars are drawn with
+    /// `#` to keep to plain ASCII.
+    pub fn histogram(&self, stats: &Stats, width: usize) -> String {
+        let last = match stats.last_game() {
+            Some(GameStatus::Won { guesses }) => Some(guesses - 1),
+            _ => None,
+        };
+        let highlight = AnsiStyle::new().color(dyn_color(self.theme.correct));
+        render_histogram(&stats.histogram(), width.min(MAX_WIDTH), last)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                if !self.color {
+                    return row.replace(BAR, "#");
+                }
+                let bar: String = row.chars().filter(|&c| c == BAR).collect();
+                if last != Some(i) || bar.is_empty() {
+                    return row;
+                }
+                row.replacen(&bar, &bar.style(highlight).to_string(), 1)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// The widest the guess distribution gets, however wide the terminal is
+const MAX_WIDTH: usize = 48;
+
+/// The width assumed when the terminal's can't be found, e.g. when output is piped
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// The width of the terminal in columns, or [`DEFAULT_WIDTH`] if stdout isn't one
+pub fn terminal_width() -> usize {
+    if !io::stdout().is_terminal() {
+        return DEFAULT_WIDTH;
+    }
+    terminal::size().map_or(DEFAULT_WIDTH, |(columns, _)| columns.into())
+}
+
+/// Summarize a player's statistics: games played, win %, streaks, and so on
+pub fn stats_summary(messages: &Messages, stats: &Stats) -> String {
+    let mut parts = vec![messages.format(
+        "stats-summary",
+        &[
+            ("played", stats.played().into()),
+            ("percentage", stats.win_percentage().into()),
+            ("current", stats.current_streak().into()),
+            ("max", stats.max_streak().into()),
+        ],
+    )];
+    if stats.assisted() > 0 {
+        parts.push(messages.format("stats-assisted", &[("count", stats.assisted().into())]));
+    }
+    if let Some(best) = stats.best_time() {
+        parts.push(messages.format("stats-best-time", &[("time", duration(best).into())]));
+    }
+    parts.join("  ")
+}
+
+/// A day's leaderboard from the server, a line for each result
+pub fn leaderboard(messages: &Messages, board: &Leaderboard) -> String {
+    let mut lines = vec![messages.format(
+        "leaderboard",
+        &[
+            ("puzzle", board.puzzle.into()),
+            ("date", board.date.to_string().into()),
+        ],
+    )];
+    if board.results.is_empty() {
+        lines.push(format!("  {}", messages.format("leaderboard-empty", &[])));
+    }
+    let hard_mode = format!("  ({})", messages.format("banner-hard-mode", &[]));
+    let name_width = board
+        .results
+        .iter()
+        .map(|entry| entry.player.width())
+        .max()
+        .unwrap_or(0);
+    for entry in &board.results {
+        let padding = " ".repeat(name_width - entry.player.width());
+        let result = messages.format(
+            "leaderboard-entry",
+            &[
+                ("guesses", entry.guesses.into()),
+                (
+                    "time",
+                    duration(Duration::from_millis(entry.time_ms)).into(),
+                ),
+            ],
+        );
+        lines.push(format!(
+            "{:>3}. {}{}  {}{}",
+            entry.rank,
+            entry.player,
+            padding,
+            result,
+            if entry.hard_mode {
+                hard_mode.as_str()
+            } else {
+                ""
+            }
+        ));
+    }
+    lines.join("\n")
+}
+
+/// A player's statistics as JSON, for scripts: the same as [`stats_summary`]
+/// and [`Style::histogram`] show, with a count for every number of guesses up
+/// to `max_guesses`
+pub fn stats_json(stats: &Stats, max_guesses: usize) -> serde_json::Value {
+    let mut distribution = stats.distribution().to_vec();
+    if distribution.len() < max_guesses {
+        distribution.resize(max_guesses, 0);
+    }
+    let last_game = stats.last_game().map(|status| match status {
+        GameStatus::Won { guesses } => json!({ "won": true, "guesses": guesses }),
+        _ => json!({ "won": false, "guesses": null }),
+    });
+    json!({
+        "played": stats.played(),
+        "wins": stats.wins(),
+        "win_percentage": stats.win_percentage(),
+        "current_streak": stats.current_streak(),
+        "max_streak": stats.max_streak(),
+        "assisted": stats.assisted(),
+        "best_time_secs": stats.best_time().map(|time| time.as_secs()),
+        "distribution": distribution,
+        "last_game": last_game,
+    })
+}
+
+/// Format a duration in whole seconds, e.g. `47s` or `1m 23s`
+pub fn duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+/// Format the time left until something, in hours, minutes, and seconds, e.g. `07:42:13`
+pub fn countdown(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// What assist mode shows before each guess: how many answers are still
+/// possible, and the best guesses to try with the information (in bits) they'd
+/// give; followed by the explanation of each guess, if they have them, with
+/// the colors drawn in `tiles`
+pub fn suggestions(
+    messages: &Messages,
+    remaining: usize,
+    suggestions: &[Suggestion],
+    tiles: &TileSet<'_>,
+) -> String {
+    let guesses: Vec<String> = suggestions
+        .iter()
+        .map(|s| {
+            messages.format(
+                "suggestion",
+                &[
+                    ("word", s.word.to_uppercase().into()),
+                    ("bits", format!("{:.2}", s.score).into()),
+                ],
+            )
+        })
+        .collect();
+    let mut text = messages.format(
+        "suggestions",
+        &[
+            ("count", remaining.into()),
+            ("words", guesses.join(", ").into()),
+        ],
+    );
+    for suggestion in suggestions {
+        if let Some(explanation) = &suggestion.explanation {
+            text.push('\n');
+            text.push_str(&explain(messages, &suggestion.word, explanation, tiles));
+        }
+    }
+    text
+}
+
+/// Explain a suggestion in an indented block, e.g.
+///
+/// ```text
+///   SLATE: could be the answer; leaves 2.17 answers on average, 5 at most
+///     likeliest colors: ⬛⬛🟩⬛🟩 5, ⬛⬛🟩🟩🟩 3, 🟩⬛🟩⬛🟩 2
+/// ```
+fn explain(
+    messages: &Messages,
+    word: &str,
+    explanation: &Explanation,
+    tiles: &TileSet<'_>,
+) -> String {
+    let patterns: Vec<String> = explanation
+        .patterns
+        .iter()
+        .map(|(pattern, n)| {
+            let squares: String = pattern.iter().map(|status| tiles.tile(status)).collect();
+            format!("{} {}", squares, n)
+        })
+        .collect();
+    let verdict = if explanation.possible_answers > 0 {
+        "could-be-answer"
+    } else {
+        "cannot-be-answer"
+    };
+    let summary = messages.format(
+        "explanation",
+        &[
+            ("word", word.to_uppercase().into()),
+            ("verdict", messages.format(verdict, &[]).into()),
+            (
+                "average",
+                format!("{:.2}", explanation.expected_remaining).into(),
+            ),
+            ("worst", explanation.worst_case.into()),
+        ],
+    );
+    let likeliest = messages.format(
+        "likeliest-colors",
+        &[("patterns", patterns.join(", ").into())],
+    );
+    format!("  {}\n    {}", summary, likeliest)
+}
+
+/// Why `guess` was rejected, as specifically as possible
+pub fn rejection(messages: &Messages, guess: &str, error: &GuessError) -> String {
+    let reason = match *error {
+        GuessError::WrongLength { expected, actual } => messages.format(
+            match actual < expected {
+                true => "rejection-too-short",
+                false => "rejection-too-long",
+            },
+            &[("expected", expected.into()), ("actual", actual.into())],
+        ),
+        GuessError::InvalidLetter { letter } => messages.format(
+            "rejection-invalid-letter",
+            &[("letter", letter.to_string().into())],
+        ),
+        GuessError::NotInWordList => messages.format("rejection-not-in-word-list", &[]),
+        GuessError::AlreadyGuessed { turn } => messages.format(
+            "rejection-already-guessed",
+            &[
+                ("guess", guess.to_uppercase().into()),
+                ("turn", turn.into()),
+            ],
+        ),
+        GuessError::HardMode(violation) => messages.violation(&violation),
+        GuessError::GameOver => messages.format("rejection-game-over", &[]),
+        GuessError::NoWord => messages.format("rejection-no-word", &[]),
+    };
+    messages.format(
+        "rejection",
+        &[("guess", guess.into()), ("reason", reason.into())],
+    )
+}
+
+/// Why the server didn't take a result, or couldn't be asked
+pub fn api_error(messages: &Messages, error: &ApiError) -> String {
+    match error {
+        ApiError::Rejected(reason) => {
+            messages.format("api-rejected", &[("reason", reason.as_str().into())])
+        }
+        ApiError::Unavailable(reason) => {
+            messages.format("api-unavailable", &[("reason", reason.as_str().into())])
+        }
+    }
+}
+
+/// How many answers are still possible after a guess, and how many were before it
+pub fn remaining(messages: &Messages, remaining: usize, before: usize) -> String {
+    messages.format(
+        "remaining",
+        &[
+            ("remaining", count(remaining).into()),
+            ("before", count(before).into()),
+        ],
+    )
+}
+
+/// The handful of answers that were still possible after a guess
+pub fn narrowed_down(messages: &Messages, guess: usize, answers: &[&str]) -> String {
+    let answers: Vec<String> = answers.iter().map(|word| word.to_uppercase()).collect();
+    messages.format(
+        "narrowed-down",
+        &[
+            ("turn", guess.into()),
+            ("answers", answers.join(", ").into()),
+        ],
+    )
+}
+
+/// Format a count with commas between each group of three digits, e.g. `12,972`
+pub fn count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Lay out blocks of text side by side, as many to a row as fit in `width`
+/// columns, with `gap` columns between them.
+///
+/// Each block is padded to its widest line, so the ones beside it line up;
+/// a block too wide to share a row gets one of its own. Rows are kept as even
+/// as they can be.
+pub fn side_by_side(blocks: &[String], width: usize, gap: usize) -> String {
+    let widths: Vec<usize> = blocks
+        .iter()
+        .map(|block| block.lines().map(visible_width).max().unwrap_or(0))
+        .collect();
+    let fits = |n: usize| {
+        widths
+            .chunks(n)
+            .all(|row| row.iter().sum::<usize>() + gap * (row.len() - 1) <= width)
+    };
+    let most = (1..=blocks.len()).rev().find(|&n| fits(n)).unwrap_or(1);
+    // spread the blocks evenly over the rows they need, e.g. 2 and 2 rather than 3 and 1
+    let needed = blocks.len().div_ceil(most);
+    let per_row = (1..most)
+        .find(|&n| blocks.len().div_ceil(n) == needed && fits(n))
+        .unwrap_or(most);
+
+    let mut rows = Vec::new();
+    for (row, row_widths) in blocks.chunks(per_row).zip(widths.chunks(per_row)) {
+        let lines: Vec<Vec<&str>> = row.iter().map(|block| block.lines().collect()).collect();
+        let height = lines.iter().map(Vec::len).max().unwrap_or(0);
+        for i in 0..height {
+            let line: Vec<String> = lines
+                .iter()
+                .zip(row_widths)
+                .map(|(block, &width)| {
+                    let line = block.get(i).copied().unwrap_or("");
+                    format!("{}{}", line, " ".repeat(width - visible_width(line)))
+                })
+                .collect();
+            rows.push(line.join(&" ".repeat(gap)).trim_end().to_owned());
+        }
+    }
+    rows.join("\n")
+}
+
+/// Remove ANSI color codes, leaving what's visible on screen
+fn visible(text: &str) -> String {
+    let mut visible = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            visible.push(c);
+        }
+    }
+    visible
+}
+
+/// The number of columns text takes up on screen, leaving out ANSI color codes
+fn visible_width(text: &str) -> usize {
+    visible(text).width()
+}
+
+/// List letters separated by commas
+fn list(letters: &[char]) -> String {
+    letters
+        .iter()
+        .map(char::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A guess described in words, e.g. `Guess 2, CRANE: C — correct position. [...]`
+fn spoken(messages: &Messages, number: usize, guess: &str, statuses: &[LetterStatus]) -> String {
+    messages.format(
+        "spoken-guess",
+        &[
+            ("number", number.into()),
+            (
+                "description",
+                speech::guess(messages, guess, statuses).into(),
+            ),
+        ],
+    )
+}
+
+/// How many guesses are left in a game, as a sentence, or `None` once it's over
+fn guesses_left<S: Started>(messages: &Messages, game: &Wordle<'_, S>) -> Option<String> {
+    if game.status() != GameStatus::InProgress {
+        return None;
+    }
+    let left = game.max_guesses() - game.history().len();
+    Some(messages.format("guesses-left", &[("count", left.into())]))
+}
+
+/// A theme's color as an ANSI color.
+///
+/// The first 16 colors of the palette are given as the basic colors, which
+/// more terminals support, and which they may have their own shades for.
+pub fn dyn_color(color: Color) -> DynColors {
+    const BASIC: [AnsiColors; 16] = [
+        AnsiColors::Black,
+        AnsiColors::Red,
+        AnsiColors::Green,
+        AnsiColors::Yellow,
+        AnsiColors::Blue,
+        AnsiColors::Magenta,
+        AnsiColors::Cyan,
+        AnsiColors::White,
+        AnsiColors::BrightBlack,
+        AnsiColors::BrightRed,
+        AnsiColors::BrightGreen,
+        AnsiColors::BrightYellow,
+        AnsiColors::BrightBlue,
+        AnsiColors::BrightMagenta,
+        AnsiColors::BrightCyan,
+        AnsiColors::BrightWhite,
+    ];
+    match color {
+        Color::Indexed(i @ 0..=15) => DynColors::Ansi(BASIC[usize::from(i)]),
+        Color::Indexed(i) => DynColors::Xterm(XtermColors::from(i)),
+        Color::Rgb(r, g, b) => DynColors::Rgb(r, g, b),
+    }
+}
+
+/// The colors of a tile for a [`LetterStatus`]: bold letters, black on
+/// correct and present tiles and white on absent ones
+pub fn ansi(status: &LetterStatus, theme: &Theme) -> AnsiStyle {
+    let style = AnsiStyle::new()
+        .bold()
+        .on_color(dyn_color(theme.color(status)));
+    match status {
+        LetterStatus::Correct | LetterStatus::InWord => style.black(),
+        LetterStatus::NotInWord => style.white(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::{score_guess, GameStatus};
+
+    /// The keyboard after guessing "trace" against "crane"
+    fn keyboard_state() -> KeyboardState {
+        let mut state = KeyboardState::new();
+        state.add("trace", &score_guess("trace", "crane"));
+        state
+    }
+
+    #[test]
+    fn test_color() {
+        let style = Style::new(true);
+        assert_eq!(
+            "\x1b[30;42;1m C \x1b[0m\x1b[30;43;1m R \x1b[0m\x1b[37;100;1m T \x1b[0m",
+            style.guess(
+                "crt",
+                &[
+                    LetterStatus::Correct,
+                    LetterStatus::InWord,
+                    LetterStatus::NotInWord
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_colorblind() {
+        let style = Style::new(true).with_theme(&theme::HIGH_CONTRAST);
+        assert_eq!(
+            "\x1b[30;48;5;208;1m C \x1b[0m\x1b[30;48;5;117;1m R \x1b[0m\x1b[37;100;1m T \x1b[0m",
+            style.guess(
+                "crt",
+                &[
+                    LetterStatus::Correct,
+                    LetterStatus::InWord,
+                    LetterStatus::NotInWord
+                ]
+            )
+        );
+        let keyboard = style.keyboard(&keyboard_state(), &Alphabet::english());
+        assert!(keyboard.contains("\x1b[30;48;5;208;1m E \x1b[0m"));
+        assert!(keyboard.contains("\x1b[30;48;5;117;1m C \x1b[0m"));
+
+        // the marks don't have colors to change
+        assert_eq!(
+            Style::new(false).guess("trace", &score_guess("trace", "crane")),
+            Style::new(false)
+                .with_theme(&theme::HIGH_CONTRAST)
+                .guess("trace", &score_guess("trace", "crane"))
+        );
+    }
+
+    #[test]
+    fn test_custom_theme() {
+        let theme = Theme::parse(
+            r##"
+            correct = "#859900"
+            present = 136
+            absent = "#586e75"
+            empty = 240
+            [tiles]
+            absent = "🟫"
+            "##,
+        )
+        .unwrap();
+        let style = Style::new(true).with_theme(Box::leak(Box::new(theme)));
+
+        // the tiles are drawn in the theme's colors, wherever they're drawn
+        let correct = "\x1b[30;48;2;133;153;0;1m";
+        let present = "\x1b[30;48;5;136;1m";
+        let absent = "\x1b[37;48;2;88;110;117;1m";
+        assert_eq!(
+            format!(
+                "{} C \x1b[0m{} R \x1b[0m{} T \x1b[0m",
+                correct, present, absent
+            ),
+            style.guess(
+                "crt",
+                &[
+                    LetterStatus::Correct,
+                    LetterStatus::InWord,
+                    LetterStatus::NotInWord
+                ]
+            )
+        );
+        let keyboard = style.keyboard(&keyboard_state(), &Alphabet::english());
+        assert!(keyboard.contains(&format!("{} E \x1b[0m", correct)));
+        assert!(keyboard.contains(&format!("{} C \x1b[0m", present)));
+        assert!(keyboard.contains(&format!("{} T \x1b[0m", absent)));
+
+        let board = style.board(&game(&["trace"]));
+        assert!(board.contains(&format!("{} R \x1b[0m", correct)));
+        assert!(board.ends_with("\x1b[38;5;240m ·  ·  ·  ·  · \x1b[0m"));
+        let big = style.with_big(true).board(&game(&["trace"]));
+        assert!(big.contains(&format!("{}  R  \x1b[0m", correct)));
+        assert!(big.contains("\x1b[38;5;240m  ·  \x1b[0m"));
+
+        let mut stats = Stats::new();
+        stats.record_status(GameStatus::Won { guesses: 1 });
+        assert!(style
+            .histogram(&stats, 12)
+            .starts_with("1: \x1b[38;2;133;153;0m"));
+
+        // the share tiles are left to the caller, but the theme has them
+        assert_eq!("🟫", style.theme().tile_set().not_in_word);
+    }
+
+    #[test]
+    fn test_plain() {
+        let style = Style::new(false);
+        assert_eq!(
+            "t [R] [A] (c) [E]",
+            style.guess("trace", &score_guess("trace", "crane"))
+        );
+
+        // share grids are ASCII without colors, too
+        assert_eq!(&TileSet::ascii(), style.tiles());
+        assert_eq!(&TileSet::classic(), Style::new(true).tiles());
+    }
+
+    #[test]
+    fn test_keyboard_color() {
+        let keyboard = Style::new(true).keyboard(&keyboard_state(), &Alphabet::english());
+        let rows: Vec<&str> = keyboard.lines().collect();
+        assert_eq!(3, rows.len());
+        assert!(rows[0].starts_with(" Q   W  \x1b[30;42;1m E \x1b[0m"));
+        assert!(rows[0].contains("\x1b[37;100;1m T \x1b[0m"));
+        assert!(rows[1].contains("\x1b[30;42;1m A \x1b[0m"));
+        assert!(rows[2].contains("\x1b[30;43;1m C \x1b[0m"));
+
+        // the escape codes take up no space, so the rows stay staggered
+        assert_eq!(
+            vec![
+                " Q   W   E   R   T   Y   U   I   O   P ",
+                "   A   S   D   F   G   H   J   K   L ",
+                "       Z   X   C   V   B   N   M ",
+            ],
+            keyboard.lines().map(visible).collect::<Vec<_>>()
+        );
+        let widths: Vec<usize> = keyboard.lines().map(|row| visible(row).width()).collect();
+        assert_eq!(vec![39, 37, 33], widths);
+    }
+
+    #[test]
+    fn test_keyboard_plain() {
+        assert_eq!(
+            "In word: a, c, e, r\nEliminated: t",
+            Style::new(false).keyboard(&keyboard_state(), &Alphabet::english())
+        );
+        assert_eq!(
+            "In word: \nEliminated: ",
+            Style::new(false).keyboard(&KeyboardState::new(), &Alphabet::english())
+        );
+    }
+
+    #[test]
+    fn test_stats_summary() {
+        let mut stats = Stats::new();
+        assert_eq!(
+            "Played: 0  Win %: 0  Current streak: 0  Max streak: 0",
+            stats_summary(Messages::english(), &stats)
+        );
+        for guesses in [3, 4, 4, 4, 4] {
+            stats.record_status(GameStatus::Won { guesses });
+        }
+        stats.record_assisted(GameStatus::Lost);
+        assert_eq!(
+            "Played: 6  Win %: 83  Current streak: 0  Max streak: 5  Assisted: 1",
+            stats_summary(Messages::english(), &stats)
+        );
+        stats.record_time(Duration::from_secs(47));
+        assert_eq!(
+            "Played: 6  Win %: 83  Current streak: 0  Max streak: 5  Assisted: 1  Personal best: 47s",
+            stats_summary(Messages::english(), &stats)
+        );
+        assert!(Style::new(false)
+            .stats(&stats, 80)
+            .starts_with("Played: 6  Win %: 83  Current streak: 0  Max streak: 5  Assisted: 1  Personal best: 47s\nGuess distribution:\n1: "));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let style = Style::new(false);
+        let mut stats = Stats::new();
+        for guesses in [3, 4, 4, 4, 4] {
+            stats.record_status(GameStatus::Won { guesses });
+        }
+        stats.record_status(GameStatus::Lost);
+        // a loss has no row to highlight
+        assert_eq!(
+            concat!(
+                "1:                     0\n",
+                "2:                     0\n",
+                "3: #####               1\n",
+                "4: ################### 4\n",
+                "5:                     0\n",
+                "6:                     0\n",
+                "X: #####               1",
+            ),
+            style.histogram(&stats, 24)
+        );
+
+        stats.record_status(GameStatus::Won { guesses: 3 });
+        assert_eq!(
+            concat!(
+                "1:                   0\n",
+                "2:                   0\n",
+                "3: #########         2 <\n",
+                "4: ################# 4\n",
+                "5:                   0\n",
+                "6:                   0\n",
+                "X: #####             1",
+            ),
+            style.histogram(&stats, 24)
+        );
+
+        // bars stop growing on wide terminals, and give way on tiny ones
+        let wide = style.histogram(&stats, 200);
+        assert_eq!(
+            Some(format!("4: {} 4", "#".repeat(MAX_WIDTH - 7))),
+            wide.lines().nth(3).map(str::to_owned)
+        );
+        assert_eq!(
+            "1:  0\n2:  0\n3:  2 <\n4:  4\n5:  0\n6:  0\nX:  1",
+            style.histogram(&stats, 3)
+        );
+    }
+
+    #[test]
+    fn test_histogram_color() {
+        let mut stats = Stats::new();
+        for guesses in [2, 2, 2, 2, 1] {
+            stats.record_status(GameStatus::Won { guesses });
+        }
+        let histogram = Style::new(true).histogram(&stats, 12);
+        assert_eq!(
+            "1: ██    1 <\n2: █████ 4\n3:       0\n4:       0\n5:       0\n6:       0\nX:       0",
+            visible(&histogram)
+        );
+        // the most recent game, won in one, is green, or orange in the high contrast theme
+        let green = "██".style(AnsiStyle::new().green()).to_string();
+        assert_eq!(
+            Some(format!("1: {}    1 <", green)),
+            histogram.lines().next().map(str::to_owned)
+        );
+        let orange = "██"
+            .style(AnsiStyle::new().color(XtermColors::from(208)))
+            .to_string();
+        let colorblind = Style::new(true).with_theme(&theme::HIGH_CONTRAST);
+        assert!(colorblind
+            .histogram(&stats, 12)
+            .starts_with(&format!("1: {}", orange)));
+        assert_eq!(Some("2: █████ 4"), histogram.lines().nth(1));
+    }
+
+    #[test]
+    fn test_stats_json() {
+        let mut stats = Stats::new();
+        assert_eq!(
+            json!({
+                "played": 0,
+                "wins": 0,
+                "win_percentage": 0,
+                "current_streak": 0,
+                "max_streak": 0,
+                "assisted": 0,
+                "best_time_secs": null,
+                "distribution": [0, 0, 0, 0, 0, 0],
+                "last_game": null,
+            }),
+            stats_json(&stats, 6)
+        );
+
+        stats.record_status(GameStatus::Won { guesses: 3 });
+        stats.record_time(Duration::from_secs(47));
+        let json = stats_json(&stats, 6);
+        assert_eq!(json!([0, 0, 1, 0, 0, 0]), json["distribution"]);
+        assert_eq!(json!({ "won": true, "guesses": 3 }), json["last_game"]);
+        assert_eq!(47, json["best_time_secs"]);
+        stats.record_status(GameStatus::Lost);
+        assert_eq!(
+            json!({ "won": false, "guesses": null }),
+            stats_json(&stats, 6)["last_game"]
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!("0s", duration(Duration::from_millis(999)));
+        assert_eq!("47s", duration(Duration::from_secs(47)));
+        assert_eq!("1m 03s", duration(Duration::from_secs(63)));
+        assert_eq!("61m 40s", duration(Duration::from_secs(3700)));
+    }
+
+    #[test]
+    fn test_countdown() {
+        assert_eq!("00:00:00", countdown(Duration::from_millis(999)));
+        assert_eq!("07:42:13", countdown(Duration::from_secs(27733)));
+        assert_eq!("23:59:59", countdown(Duration::from_secs(86399)));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let suggestion = |word: &str, score| Suggestion {
+            word: word.into(),
+            score,
+            explanation: None,
+        };
+        let tiles = TileSet::classic();
+        assert_eq!(
+            "12 possible answers left; try SLATE (2.91 bits), CRATE (2.50 bits)",
+            suggestions(
+                Messages::english(),
+                12,
+                &[suggestion("slate", 2.9134), suggestion("crate", 2.5)],
+                &tiles
+            )
+        );
+        assert_eq!(
+            "1 possible answer left; try CRANE (0.00 bits)",
+            suggestions(Messages::english(), 1, &[suggestion("crane", 0.0)], &tiles)
+        );
+
+        // explanations are drawn with the tiles
+        let mut explained = vec![suggestion("crane", 0.0), suggestion("xylyl", 0.0)];
+        wordle::solver::explain_all(&mut explained, &["crane"]);
+        assert_eq!(
+            "1 possible answer left; try CRANE (0.00 bits), XYLYL (0.00 bits)\n  \
+             CRANE: could be the answer; leaves 0.00 answers on average, 0 at most\n    \
+             likeliest colors: ##### 1\n  \
+             XYLYL: can't be the answer; leaves 1.00 answers on average, 1 at most\n    \
+             likeliest colors: ..... 1",
+            suggestions(Messages::english(), 1, &explained, &TileSet::ascii())
+        );
+    }
+
+    #[test]
+    fn test_remaining() {
+        assert_eq!(
+            "Possible answers remaining: 42 (was 1,208)",
+            remaining(Messages::english(), 42, 1208)
+        );
+        assert_eq!(
+            "Possible answers after guess 3: CRANE, CRATE",
+            narrowed_down(Messages::english(), 3, &["crane", "crate"])
+        );
+        assert_eq!("0", count(0));
+        assert_eq!("999", count(999));
+        assert_eq!("12,972", count(12972));
+        assert_eq!("1,000,000", count(1_000_000));
+    }
+
+    /// A game against "crane" with four guesses, after `guesses` of them
+    fn game(guesses: &[&str]) -> Wordle<'static> {
+        const WORDS: [&str; 4] = ["crane", "trace", "scare", "slate"];
+        let mut game = Wordle::builder()
+            .guesses(&WORDS)
+            .answers(&WORDS)
+            .max_guesses(4)
+            .build()
+            .unwrap()
+            .set_answer("crane")
+            .unwrap();
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_board_plain() {
+        let style = Style::new(false);
+        assert_eq!(
+            concat!(
+                " .   .   .   .   . \n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . ",
+            ),
+            style.board(&game(&[]))
+        );
+        assert_eq!(
+            concat!(
+                " s   l  [A]  t  [E]\n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . ",
+            ),
+            style.board(&game(&["slate"]))
+        );
+        assert_eq!(
+            concat!(
+                " s   l  [A]  t  [E]\n",
+                " s  (c) [A] (r) [E]\n",
+                "[C] [R] [A] [N] [E]\n",
+                " .   .   .   .   . ",
+            ),
+            style.board(&game(&["slate", "scare", "crane"]))
+        );
+    }
+
+    #[test]
+    fn test_board_accessible() {
+        let style = Style::new(true).with_accessible(true);
+        assert!(!style.color());
+        assert_eq!("4 guesses left.", style.board(&game(&[])));
+        let board = style.board(&game(&["slate", "scare"]));
+        assert_eq!(
+            concat!(
+                "Guess 1, SLATE: S — not in the word. L — not in the word. A — correct position. ",
+                "T — not in the word. E — correct position. 2 correct, 0 present, 3 absent.\n",
+                "Guess 2, SCARE: S — not in the word. C — in the word, wrong position. ",
+                "A — correct position. R — in the word, wrong position. E — correct position. ",
+                "2 correct, 2 present, 1 absent.\n",
+                "2 guesses left.",
+            ),
+            board
+        );
+        // only the latest guess is read out after each turn, and nothing is left once it's over
+        assert_eq!(
+            board.lines().skip(1).collect::<Vec<&str>>().join("\n"),
+            style.turn(&game(&["slate", "scare"]))
+        );
+        assert!(style
+            .turn(&game(&["crane"]))
+            .starts_with("Guess 1, CRANE: C — correct position."));
+        assert_eq!(1, style.turn(&game(&["crane"])).lines().count());
+    }
+
+    #[test]
+    fn test_board_color() {
+        let style = Style::new(true);
+        let empty = "\x1b[2m ·  ·  ·  ·  · \x1b[0m";
+        assert_eq!([empty; 4].join("\n"), style.board(&game(&[])));
+        assert_eq!(
+            format!(
+                "{}\n{}\n{}\n{}",
+                style.guess("trace", &score_guess("trace", "crane")),
+                style.guess("crane", &score_guess("crane", "crane")),
+                empty,
+                empty
+            ),
+            style.board(&game(&["trace", "crane"]))
+        );
+        // every row is the same width on screen
+        let board = style.board(&game(&["trace"]));
+        let widths: Vec<usize> = board.lines().map(|line| visible(line).width()).collect();
+        assert_eq!(vec![15; 4], widths);
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let blocks = vec![
+            String::from("ab\ncd"),
+            String::from("\x1b[1mef\x1b[0m"),
+            String::from("g\nhi\njk"),
+        ];
+        assert_eq!(
+            "ab  \x1b[1mef\x1b[0m  g\ncd      hi\n        jk",
+            side_by_side(&blocks, 10, 2)
+        );
+        // too narrow for all three, so the last goes underneath
+        assert_eq!(
+            "ab  \x1b[1mef\x1b[0m\ncd\ng\nhi\njk",
+            side_by_side(&blocks, 7, 2)
+        );
+        assert_eq!(
+            "ab\ncd\n\x1b[1mef\x1b[0m\ng\nhi\njk",
+            side_by_side(&blocks, 1, 2)
+        );
+        // room for three of four, so they're laid out two by two
+        let blocks = vec![
+            String::from("a"),
+            String::from("b"),
+            String::from("c"),
+            String::from("d"),
+        ];
+        assert_eq!("a b\nc d", side_by_side(&blocks, 5, 1));
+    }
+}
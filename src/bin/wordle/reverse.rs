@@ -0,0 +1,242 @@
+//! Playing a reverse puzzle, finding words that give the colors shown
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use wordle::{
+    messages::Messages,
+    reverse::{ReverseWordle, RowError},
+    share, LetterStatus,
+};
+
+use crate::{
+    command::{self, Command, Input},
+    complete::Completions,
+    confirm,
+    prompt::{Prompt, Stop},
+    render::{self, Style},
+    Flow,
+};
+
+/// Fill in the puzzle's rows from the top until they're all filled, or the
+/// player gives up on it.
+///
+/// Rows that are all green can only be the answer, so they're filled in to
+/// start with. A hint says how many words would fill the next row.
+///
+/// Returns [`Flow::Quit`] if the player quit or closed stdin partway through.
+pub fn play(puzzle: &mut ReverseWordle<'_>, prompt: &mut dyn Prompt, style: &Style) -> Flow {
+    let answer = puzzle.answer();
+    for row in 0..puzzle.target().len() {
+        if puzzle.target()[row]
+            .iter()
+            .all(|status| *status == LetterStatus::Correct)
+        {
+            puzzle
+                .try_row(row, answer)
+                .expect("The answer gives all greens");
+        }
+    }
+    prompt.set_completions(Completions::new(puzzle.guess_list().iter().copied()));
+    let messages = style.messages();
+
+    let mut drawn = None;
+    while let Some(row) = puzzle.rows().iter().position(Option::is_none) {
+        if drawn != Some(row) {
+            println!("{}", draw(puzzle, style));
+            drawn = Some(row);
+        }
+
+        let text = messages.format(
+            "prompt-row",
+            &[
+                ("row", (row + 1).into()),
+                ("rows", puzzle.rows().len().into()),
+            ],
+        );
+        let text = format!("{} ", text);
+        let line = match prompt.read(&text) {
+            Ok(line) => line,
+            Err(Stop::End) => return Flow::Quit,
+            Err(Stop::Interrupted) => return Flow::Interrupted,
+        };
+        match command::parse(&line) {
+            Input::Guess(word) => {
+                let word = word.to_lowercase();
+                if !word.is_empty() {
+                    prompt.remember(&word);
+                }
+                match puzzle.try_row(row, &word) {
+                    Ok(()) => {}
+                    Err(RowError::Invalid(e)) => {
+                        println!("{}", render::rejection(messages, &word, &e))
+                    }
+                    Err(RowError::Mismatch { actual }) => println!(
+                        "{}",
+                        messages.format(
+                            "reverse-mismatch",
+                            &[
+                                ("guess", word.to_uppercase().into()),
+                                (
+                                    "tiles",
+                                    share::share_grid(&[("", actual)], style.tiles()).into()
+                                ),
+                            ],
+                        )
+                    ),
+                    Err(RowError::NoSuchRow { rows }) => println!(
+                        "{}",
+                        messages.format("reverse-no-such-row", &[("rows", rows.into())])
+                    ),
+                }
+            }
+            Input::Command(Command::Quit) => return Flow::Quit,
+            Input::Command(Command::Skip) => break,
+            Input::Command(Command::GiveUp) => {
+                match confirm(prompt, &messages.format("prompt-give-up-reverse", &[])) {
+                    Ok(true) => break,
+                    Ok(false) | Err(Stop::End) => {}
+                    Err(Stop::Interrupted) => return Flow::Interrupted,
+                }
+            }
+            Input::Command(Command::Hint) => println!(
+                "{}",
+                messages.format(
+                    "reverse-hint",
+                    &[
+                        ("count", puzzle.solutions(row).len().into()),
+                        ("row", (row + 1).into()),
+                    ],
+                )
+            ),
+            Input::Command(Command::Copy) => {
+                println!("{}", messages.format("reverse-no-copy", &[]))
+            }
+            Input::Command(Command::Save(_)) => {
+                println!("{}", messages.format("reverse-no-save", &[]))
+            }
+            Input::Unknown(command) => println!(
+                "{}",
+                messages.format("unknown-command", &[("command", command.into())])
+            ),
+        }
+    }
+    Flow::Continue
+}
+
+/// The rows' colors, each beside the word filling it, or blanks if it hasn't
+/// been filled, under the answer they're played against
+pub fn draw(puzzle: &ReverseWordle<'_>, style: &Style) -> String {
+    let blank = "_".repeat(puzzle.answer().chars().count());
+    let mut text = style.messages().format(
+        "reverse-target",
+        &[("answer", puzzle.answer().to_uppercase().into())],
+    );
+    for (target, word) in puzzle.target().iter().zip(puzzle.rows()) {
+        let tiles = share::share_grid(&[("", target.clone())], style.tiles());
+        let word = word.map_or(blank.clone(), str::to_uppercase);
+        text.push_str(&format!("\n{}  {}", tiles, word));
+    }
+    text
+}
+
+/// Whether the puzzle was solved, and if not, a word that fits each empty row
+pub fn summary(messages: &Messages, puzzle: &ReverseWordle<'_>) -> String {
+    if puzzle.is_complete() {
+        return format!(
+            "{}\n{}",
+            words(puzzle),
+            messages.format("reverse-solved", &[])
+        );
+    }
+    let mut lines = vec![messages.format("reverse-would-fit", &[])];
+    for (row, word) in puzzle.rows().iter().enumerate() {
+        if word.is_none() {
+            let solutions = puzzle.solutions(row);
+            let first = solutions.first().expect("Every row can be solved");
+            lines.push(messages.format(
+                "reverse-fits",
+                &[
+                    ("row", (row + 1).into()),
+                    ("word", first.to_uppercase().into()),
+                    ("others", (solutions.len() - 1).into()),
+                ],
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// The words filling the rows, in order
+fn words(puzzle: &ReverseWordle<'_>) -> String {
+    let words: Vec<String> = puzzle
+        .rows()
+        .iter()
+        .flatten()
+        .map(|word| word.to_uppercase())
+        .collect();
+    words.join(" → ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle::{score_guess, share::TileSet, state::NoWord, Wordle};
+
+    const WORDS: [&str; 6] = ["crane", "slate", "trace", "irate", "share", "stare"];
+
+    fn puzzle(game: &Wordle<'static, NoWord>) -> ReverseWordle<'static> {
+        let target = vec![
+            score_guess("crane", "trace"),
+            score_guess("slate", "trace"),
+            vec![LetterStatus::Correct; 5],
+        ];
+        ReverseWordle::new(game, "trace", target).unwrap()
+    }
+
+    #[test]
+    fn test_play() {
+        let game = Wordle::new(&WORDS, &WORDS);
+        let mut puzzle = puzzle(&game);
+        let style = Style::new(false).with_tiles(TileSet::ascii());
+        assert_eq!(
+            "Find words that give these colors against TRACE:\n\
+             +##.#  _____\n\
+             ..#+#  _____\n\
+             #####  _____",
+            draw(&puzzle, &style)
+        );
+
+        let mut prompt =
+            crate::prompt::Lines::new("crane\nirate\n:hint\nshare\n".as_bytes(), Vec::new());
+        assert_eq!(Flow::Continue, play(&mut puzzle, &mut prompt, &style));
+        assert!(puzzle.is_complete());
+        assert_eq!(
+            "CRANE → SHARE → TRACE\nSolved!",
+            summary(Messages::english(), &puzzle)
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let game = Wordle::new(&WORDS, &WORDS);
+        let mut puzzle = puzzle(&game);
+        puzzle.try_row(1, "slate").unwrap();
+        assert_eq!(
+            "Words that would have fit:\nRow 1: CRANE\nRow 3: TRACE",
+            summary(Messages::english(), &puzzle)
+        );
+    }
+}
@@ -0,0 +1,687 @@
+//! A full-screen view of a game, with the guesses typed straight into the board
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    DefaultTerminal, Frame,
+};
+use wordle::{
+    keyboard::KeyboardState,
+    messages::Messages,
+    theme::{self, Theme},
+    GameStatus, LetterStatus, Wordle,
+};
+
+use crate::{
+    animate::{self, Reveal, Tile},
+    render, Flow,
+};
+
+/// Restores the terminal when dropped, even when unwinding from a panic
+#[derive(Debug)]
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+/// What the player is doing
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Mode {
+    /// Typing guesses
+    Playing,
+    /// Looking at the quit/give-up menu
+    Menu,
+    /// Looking at the finished board
+    Finished,
+}
+
+/// The state of the view; the game itself is only changed through the library
+#[derive(Debug)]
+struct App<'g, 'a> {
+    /// The game being played
+    game: &'g mut Wordle<'a>,
+    /// Whether this is the daily puzzle, which can't be given up before guessing
+    daily: bool,
+    /// The colors of the tiles
+    theme: &'static Theme,
+    /// The catalog the player's messages come from
+    messages: &'static Messages,
+    /// Whether to animate guesses being revealed
+    animate: bool,
+    /// The latest guess being revealed, while it's animated
+    reveal: Option<Reveal>,
+    /// The guess being typed
+    input: String,
+    /// A message for the player, e.g. why a guess was rejected
+    message: String,
+    /// What the player is doing
+    mode: Mode,
+    /// What to do once the view is closed, if it should be
+    done: Option<Flow>,
+}
+
+impl<'g, 'a> App<'g, 'a> {
+    /// Start showing a game
+    fn new(
+        game: &'g mut Wordle<'a>,
+        daily: bool,
+        theme: &'static Theme,
+        messages: &'static Messages,
+        animate: bool,
+    ) -> Self {
+        Self {
+            game,
+            daily,
+            theme,
+            messages,
+            animate,
+            reveal: None,
+            input: String::new(),
+            message: messages.format("tui-help", &[]),
+            mode: Mode::Playing,
+            done: None,
+        }
+    }
+
+    /// Update the state for a key press
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        // raw mode turns Ctrl-C into a key press, rather than a signal
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.done = Some(Flow::Interrupted);
+            return;
+        }
+        // a key press skips the rest of the reveal; if that ends the game, the
+        // key shouldn't also dismiss the result before it's been seen
+        if let Some(reveal) = &mut self.reveal {
+            reveal.finish();
+            self.tick(Duration::ZERO);
+            if self.mode == Mode::Finished {
+                return;
+            }
+        }
+
+        match self.mode {
+            Mode::Playing => self.handle_playing(key.code),
+            Mode::Menu => self.handle_menu(key.code),
+            Mode::Finished => self.done = Some(Flow::Continue),
+        }
+    }
+
+    /// Edit or submit the guess being typed
+    fn handle_playing(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c)
+                if c.is_alphabetic() && self.input.chars().count() < self.game.word_length() =>
+            {
+                self.input.extend(c.to_lowercase());
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter if self.input.chars().count() < self.game.word_length() => {
+                self.message = self.messages.format("tui-too-short", &[]);
+            }
+            KeyCode::Enter => match self.game.guess(&self.input) {
+                Ok(_) => {
+                    self.input.clear();
+                    self.message.clear();
+                    if self.animate {
+                        let won = matches!(self.game.status(), GameStatus::Won { .. });
+                        self.reveal = Some(Reveal::new(self.game.word_length(), won));
+                    } else {
+                        self.check_finished();
+                    }
+                }
+                Err(e) => self.message = render::rejection(self.messages, &self.input, &e),
+            },
+            KeyCode::Esc => {
+                self.mode = Mode::Menu;
+                self.message = self.messages.format("tui-menu", &[]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Pick an option from the quit/give-up menu
+    fn handle_menu(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.done = Some(Flow::Quit),
+            KeyCode::Char('g') if self.daily && self.game.history().is_empty() => {
+                self.mode = Mode::Playing;
+                self.message = self.messages.format("daily-give-up", &[]);
+            }
+            KeyCode::Char('g') => {
+                self.game.give_up();
+                self.check_finished();
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Playing;
+                self.message.clear();
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the reveal of the latest guess along by `elapsed`, and show the
+    /// result once it's over, if the game is too
+    fn tick(&mut self, elapsed: Duration) {
+        let Some(reveal) = &mut self.reveal else {
+            return;
+        };
+        reveal.tick(elapsed);
+        if reveal.done() {
+            self.reveal = None;
+            self.check_finished();
+        }
+    }
+
+    /// Show the result once the game is over
+    fn check_finished(&mut self) {
+        let word = self
+            .game
+            .transcript()
+            .answer
+            .unwrap_or_default()
+            .to_uppercase();
+        self.message = match self.game.status() {
+            GameStatus::InProgress => return,
+            GameStatus::Won { guesses } => self
+                .messages
+                .format("tui-won", &[("guesses", guesses.into())]),
+            GameStatus::Lost => self.messages.format("tui-lost", &[("answer", word.into())]),
+        };
+        self.mode = Mode::Finished;
+    }
+
+    /// Draw the whole view
+    fn draw(&self, frame: &mut Frame<'_>) {
+        // the board has a line to spare above it, for tiles to bounce into
+        let [title, board, counter, keyboard, message] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(self.game.max_guesses() as u16 + 2),
+            Constraint::Length(2),
+            Constraint::Length(self.game.alphabet().keyboard().len() as u16 + 1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let mode = match self.game.hard_mode() {
+            true => format!(" ({})", self.messages.format("banner-hard-mode", &[])),
+            false => String::new(),
+        };
+        frame.render_widget(
+            Line::from(format!("WORDLE{}", mode)).bold().centered(),
+            title,
+        );
+        frame.render_widget(Paragraph::new(self.board()).centered(), board);
+        frame.render_widget(
+            Line::from(self.messages.format(
+                "tui-guesses-left",
+                &[("count", self.game.guesses_left().into())],
+            ))
+            .centered(),
+            counter,
+        );
+        frame.render_widget(Paragraph::new(self.keyboard()).centered(), keyboard);
+        frame.render_widget(Line::from(self.message.as_str()).centered(), message);
+    }
+
+    /// A row of the board for each guess: the guesses made, then the one being
+    /// typed, after a blank row for tiles to bounce into
+    fn board(&self) -> Vec<Line<'static>> {
+        let length = self.game.word_length();
+        let history = self.game.history();
+        let mut rows = vec![tiles(std::iter::repeat_n((' ', None), length), self.theme)];
+        rows.extend(history.iter().enumerate().map(|(row, (guess, statuses))| {
+            let revealing = self.reveal.as_ref().filter(|_| row + 1 == history.len());
+            let letters = guess.chars().zip(statuses).enumerate();
+            tiles(
+                letters.map(|(i, (c, s))| match revealing.map(|reveal| reveal.tile(i)) {
+                    Some(Tile::Hidden) => (c, None),
+                    Some(Tile::Turning) => ('─', None),
+                    Some(Tile::Shown | Tile::Lifted) | None => (c, Some(*s)),
+                }),
+                self.theme,
+            )
+        }));
+
+        // lifted tiles jump into the row above, leaving a gap behind
+        if let Some(reveal) = &self.reveal {
+            let row = history.len();
+            for i in (0..length).filter(|&i| reveal.tile(i) == Tile::Lifted) {
+                let tile = std::mem::replace(&mut rows[row].spans[2 * i], Span::raw("   "));
+                rows[row - 1].spans[2 * i] = tile;
+            }
+        }
+
+        if self.game.status() == GameStatus::InProgress {
+            let typed = self.input.chars().map(Some).chain(std::iter::repeat(None));
+            let row = typed.take(length).map(|c| (c.unwrap_or('_'), None));
+            rows.push(tiles(row, self.theme));
+        }
+        while rows.len() <= self.game.max_guesses() {
+            let row = tiles(std::iter::repeat_n(('·', None), length), self.theme);
+            rows.push(match self.theme.empty {
+                Some(color) => row.patch_style(Style::new().fg(tui_color(color))),
+                None => row,
+            });
+        }
+        rows
+    }
+
+    /// The keyboard, with each letter colored by its best-known status; the
+    /// guess being revealed only counts once all its tiles have landed
+    fn keyboard(&self) -> Vec<Line<'static>> {
+        let state = match &self.reveal {
+            Some(reveal) if !reveal.landed() => {
+                let mut state = KeyboardState::new();
+                let history = self.game.history();
+                for (guess, statuses) in &history[..history.len() - 1] {
+                    state.add(guess, statuses);
+                }
+                state
+            }
+            _ => self.game.keyboard(),
+        };
+        self.game
+            .alphabet()
+            .keyboard()
+            .iter()
+            .map(|row| tiles(row.chars().map(|c| (c, state.get(c))), self.theme))
+            .collect()
+    }
+}
+
+/// Draw letters as tiles, colored by their status if known
+fn tiles(
+    letters: impl Iterator<Item = (char, Option<LetterStatus>)>,
+    theme: &Theme,
+) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (letter, status) in letters {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" "));
+        }
+        let style = match status {
+            Some(status) => tile_style(status, theme),
+            None => Style::new(),
+        };
+        spans.push(Span::styled(format!(" {} ", letter.to_uppercase()), style));
+    }
+    Line::from(spans)
+}
+
+/// A theme's color as a terminal color, giving the first 16 colors of the
+/// palette as the basic colors, like the rest of the game
+fn tui_color(color: theme::Color) -> Color {
+    const BASIC: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    match color {
+        theme::Color::Indexed(i @ 0..=15) => BASIC[usize::from(i)],
+        theme::Color::Indexed(i) => Color::Indexed(i),
+        theme::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+/// The colors of a tile for a [`LetterStatus`]
+fn tile_style(status: LetterStatus, theme: &Theme) -> Style {
+    let style = Style::new().bold().bg(tui_color(theme.color(&status)));
+    match status {
+        LetterStatus::Correct | LetterStatus::InWord => style.fg(Color::Black),
+        LetterStatus::NotInWord => style.fg(Color::White),
+    }
+}
+
+/// Play the current word full-screen, until it's over or the player quits.
+///
+/// The terminal is restored afterwards, including if anything panics.
+///
+/// Unless `animate` is off, each guess's tiles are flipped over one by one.
+pub fn play(
+    game: &mut Wordle<'_>,
+    daily: bool,
+    theme: &'static Theme,
+    messages: &'static Messages,
+    animate: bool,
+) -> io::Result<Flow> {
+    let mut terminal = ratatui::init();
+    let _guard = TerminalGuard;
+    run(
+        &mut terminal,
+        App::new(game, daily, theme, messages, animate),
+    )
+}
+
+/// Draw the view and handle input until the view is closed
+fn run(terminal: &mut DefaultTerminal, mut app: App<'_, '_>) -> io::Result<Flow> {
+    let mut last = Instant::now();
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        // while a guess is being revealed, wait for input only until the next
+        // frame is due; resizes just need a redraw, which happens either way
+        if app.reveal.is_none() || event::poll(animate::FRAME)? {
+            if let Event::Key(key) = event::read()? {
+                app.handle_key(key);
+            }
+        }
+        let now = Instant::now();
+        app.tick(now - last);
+        last = now;
+        if let Some(flow) = app.done {
+            return Ok(flow);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    const WORDS: [&str; 3] = ["crane", "trace", "slate"];
+
+    /// Render the app, and get back the text of each line of the screen
+    fn render(app: &App<'_, '_>, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn press(app: &mut App<'_, '_>, code: KeyCode) {
+        app.handle_key(KeyEvent::from(code));
+    }
+
+    fn type_word(app: &mut App<'_, '_>, word: &str) {
+        for c in word.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(
+            &mut game,
+            false,
+            &theme::CLASSIC,
+            Messages::english(),
+            false,
+        );
+        type_word(&mut app, "trace");
+        press(&mut app, KeyCode::Enter);
+        type_word(&mut app, "sl");
+
+        assert_eq!(
+            vec![
+                "                WORDLE",
+                "",
+                "           T   R   A   C   E",
+                "           S   L   _   _   _",
+                "           ·   ·   ·   ·   ·",
+                "           ·   ·   ·   ·   ·",
+                "           ·   ·   ·   ·   ·",
+                "           ·   ·   ·   ·   ·",
+                "",
+                "            Guesses left: 5",
+                "",
+                " Q   W   E   R   T   Y   U   I   O   P",
+                "   A   S   D   F   G   H   J   K   L",
+                "       Z   X   C   V   B   N   M",
+                "",
+                "",
+            ],
+            render(&app, 39, 16)
+        );
+
+        // the tiles are colored by their status
+        let mut terminal = Terminal::new(TestBackend::new(39, 16)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(Color::DarkGray, buffer[(10, 2)].bg, "T is gray");
+        assert_eq!(Color::Green, buffer[(14, 2)].bg, "R is green");
+        assert_eq!(Color::Yellow, buffer[(22, 2)].bg, "C is yellow");
+        assert_eq!(
+            Color::Reset,
+            buffer[(10, 3)].bg,
+            "the typed guess isn't colored"
+        );
+
+        // or by another theme's colors
+        app.theme = &theme::HIGH_CONTRAST;
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(Color::Indexed(208), buffer[(14, 2)].bg);
+        assert_eq!(Color::Indexed(117), buffer[(22, 2)].bg);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let app = App::new(
+            &mut game,
+            false,
+            &theme::CLASSIC,
+            Messages::english(),
+            false,
+        );
+
+        // the board stays centered at any width
+        let narrow = render(&app, 39, 16);
+        let wide = render(&app, 59, 16);
+        assert_eq!(format!("{}{}", " ".repeat(10), narrow[2]), wide[2]);
+    }
+
+    #[test]
+    fn test_editing() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(
+            &mut game,
+            false,
+            &theme::CLASSIC,
+            Messages::english(),
+            false,
+        );
+
+        type_word(&mut app, "cranes");
+        assert_eq!("crane", app.input, "extra letters are ignored");
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!("Not enough letters", app.message);
+        assert!(app.game.history().is_empty());
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!("'cranx' is not valid: not in word list", app.message);
+        assert_eq!("cranx", app.input);
+
+        press(&mut app, KeyCode::Backspace);
+        type_word(&mut app, "E");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(Mode::Finished, app.mode);
+        assert!(app.message.starts_with("You got it in 1!"));
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(Some(Flow::Continue), app.done);
+    }
+
+    #[test]
+    fn test_menu() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(&mut game, true, &theme::CLASSIC, Messages::english(), false);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(Mode::Menu, app.mode);
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(
+            Mode::Playing,
+            app.mode,
+            "the daily puzzle needs a guess first"
+        );
+
+        type_word(&mut app, "slate");
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(GameStatus::Lost, app.game.status());
+        assert_eq!("The word was CRANE. Press any key to continue", app.message);
+
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(
+            &mut game,
+            false,
+            &theme::CLASSIC,
+            Messages::english(),
+            false,
+        );
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char('q'));
+        assert_eq!(Some(Flow::Quit), app.done);
+
+        let mut app = App::new(
+            &mut game,
+            false,
+            &theme::CLASSIC,
+            Messages::english(),
+            false,
+        );
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(Some(Flow::Interrupted), app.done);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(&mut game, false, &theme::CLASSIC, Messages::english(), true);
+        type_word(&mut app, "trace");
+        press(&mut app, KeyCode::Enter);
+
+        let screen = |app: &App<'_, '_>| {
+            let mut terminal = Terminal::new(TestBackend::new(39, 16)).unwrap();
+            terminal.draw(|frame| app.draw(frame)).unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        // the first tile starts turning over straight away, and the rest wait
+        let buffer = screen(&app);
+        assert_eq!("─", buffer[(11, 2)].symbol(), "T is on its edge");
+        assert_eq!(Color::Reset, buffer[(10, 2)].bg);
+        assert_eq!("R", buffer[(15, 2)].symbol());
+        assert_eq!(Color::Reset, buffer[(14, 2)].bg);
+        assert_eq!(
+            Color::Reset,
+            buffer[(16, 11)].bg,
+            "T isn't on the keyboard yet"
+        );
+
+        // then they turn over one by one, left to right
+        app.tick(Duration::from_millis(500));
+        let buffer = screen(&app);
+        assert_eq!(Color::DarkGray, buffer[(10, 2)].bg);
+        assert_eq!(Color::Green, buffer[(14, 2)].bg);
+        assert_eq!(Color::Reset, buffer[(22, 2)].bg, "C hasn't turned yet");
+        assert_eq!(Color::Reset, buffer[(16, 11)].bg);
+
+        // the keyboard only catches up once the last tile has landed
+        app.tick(Duration::from_secs(2));
+        assert!(app.reveal.is_none());
+        let buffer = screen(&app);
+        assert_eq!(Color::Yellow, buffer[(22, 2)].bg);
+        assert_eq!(Color::DarkGray, buffer[(16, 11)].bg);
+        assert_eq!(Mode::Playing, app.mode);
+    }
+
+    #[test]
+    fn test_reveal_win() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(&mut game, false, &theme::CLASSIC, Messages::english(), true);
+        type_word(&mut app, "crane");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(Mode::Playing, app.mode, "the result waits for the reveal");
+
+        // once every tile has landed, the row bounces, a tile at a time
+        app.tick(Duration::from_millis(1300));
+        let bounced = render(&app, 39, 16);
+        assert_eq!("           C", bounced[1]);
+        assert_eq!("               R   A   N   E", bounced[2]);
+
+        app.tick(Duration::from_secs(2));
+        assert_eq!(Mode::Finished, app.mode);
+        assert!(app.message.starts_with("You got it in 1!"));
+    }
+
+    #[test]
+    fn test_reveal_skip() {
+        let mut game = Wordle::new(&WORDS, &WORDS[..1]).choose_word();
+        let mut app = App::new(&mut game, false, &theme::CLASSIC, Messages::english(), true);
+        type_word(&mut app, "trace");
+        press(&mut app, KeyCode::Enter);
+
+        // typing the next guess skips the reveal, without losing the letter
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.reveal.is_none());
+        assert_eq!("s", app.input);
+
+        // but a key that skips to the end of the game doesn't dismiss it too
+        press(&mut app, KeyCode::Backspace);
+        type_word(&mut app, "crane");
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(Mode::Finished, app.mode);
+        assert_eq!(None, app.done);
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(Some(Flow::Continue), app.done);
+    }
+}
@@ -0,0 +1,191 @@
+//! Rendering the guesses made in a game as text
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use crate::{codec, LetterStatus};
+
+/// How a [`Board`] is drawn
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BoardStyle {
+    /// Only the colored squares for each guess, e.g. `🟩🟨⬛⬛⬛`
+    Emoji,
+    /// The guessed letters with markers for their status, e.g. `[C] (a) r a n`
+    ///
+    /// Correct letters are uppercase in square brackets, letters in the word are
+    /// in parentheses, and letters not in the word are left bare.
+    Letters,
+    /// Like [`BoardStyle::Letters`], but every tile is the same width and the
+    /// unused turns are drawn as rows of empty (`.`) tiles.
+    Grid,
+}
+
+/// A view over the guesses made in a game that can be printed with [`fmt::Display`].
+///
+/// Rows are separated by newlines; there is no trailing newline.
+#[derive(Debug, Clone, Copy)]
+pub struct Board<'a> {
+    /// The guesses made so far, along with their feedback
+    rows: &'a [(&'a str, Vec<LetterStatus>)],
+    /// The number of letters in each word
+    word_length: usize,
+    /// The number of guesses a player gets to find the word
+    max_guesses: usize,
+    /// How the board is drawn
+    style: BoardStyle,
+}
+
+impl<'a> Board<'a> {
+    /// Create a view over the given guesses
+    pub fn new(
+        rows: &'a [(&'a str, Vec<LetterStatus>)],
+        word_length: usize,
+        max_guesses: usize,
+        style: BoardStyle,
+    ) -> Self {
+        Self {
+            rows,
+            word_length,
+            max_guesses,
+            style,
+        }
+    }
+
+    /// Draw the same board in a different style
+    pub fn with_style(self, style: BoardStyle) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Write a single guessed row
+    fn fmt_row(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        word: &str,
+        statuses: &[LetterStatus],
+    ) -> fmt::Result {
+        let tiles = word.chars().zip(statuses.iter());
+        match self.style {
+            BoardStyle::Emoji => {
+                let statuses: Vec<LetterStatus> = tiles.map(|(_, &status)| status).collect();
+                f.write_str(&codec::statuses_to_emoji(&statuses))
+            }
+            BoardStyle::Letters | BoardStyle::Grid => {
+                for (i, (letter, status)) in tiles.enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    match (status, self.style) {
+                        (LetterStatus::Correct, _) => write!(f, "[{}]", letter.to_uppercase())?,
+                        (LetterStatus::InWord, _) => write!(f, "({})", letter)?,
+                        (LetterStatus::NotInWord, BoardStyle::Grid) => write!(f, " {} ", letter)?,
+                        (LetterStatus::NotInWord, _) => write!(f, "{}", letter)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Write a row for a turn that hasn't been played yet
+    fn fmt_empty_row(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tiles = vec![" . "; self.word_length];
+        f.write_str(&tiles.join(" "))
+    }
+}
+
+impl fmt::Display for Board<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total_rows = match self.style {
+            BoardStyle::Grid => self.max_guesses.max(self.rows.len()),
+            _ => self.rows.len(),
+        };
+
+        for i in 0..total_rows {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            match self.rows.get(i) {
+                Some((word, statuses)) => self.fmt_row(f, word, &statuses[..self.word_length])?,
+                None => self.fmt_empty_row(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_guess;
+
+    /// A game against "crane" that took three guesses
+    fn rows() -> Vec<(&'static str, Vec<LetterStatus>)> {
+        ["scare", "trace", "crane"]
+            .into_iter()
+            .map(|guess| (guess, score_guess(guess, "crane")))
+            .collect()
+    }
+
+    #[test]
+    fn test_emoji() {
+        let rows = rows();
+        let board = Board::new(&rows, 5, 6, BoardStyle::Emoji);
+        assert_eq!("⬛🟨🟩🟨🟩\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩", board.to_string());
+    }
+
+    #[test]
+    fn test_letters() {
+        let rows = rows();
+        let board = Board::new(&rows, 5, 6, BoardStyle::Letters);
+        assert_eq!(
+            "s (c) [A] (r) [E]\nt [R] [A] (c) [E]\n[C] [R] [A] [N] [E]",
+            board.to_string()
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let rows = rows();
+        let board = Board::new(&rows, 5, 6, BoardStyle::Grid);
+        assert_eq!(
+            concat!(
+                " s  (c) [A] (r) [E]\n",
+                " t  [R] [A] (c) [E]\n",
+                "[C] [R] [A] [N] [E]\n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . \n",
+                " .   .   .   .   . ",
+            ),
+            board.to_string()
+        );
+
+        // every row of the grid is the same width
+        let board = board.to_string();
+        let widths: Vec<usize> = board.lines().map(|l| l.chars().count()).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]));
+    }
+
+    #[test]
+    fn test_empty_board() {
+        assert_eq!("", Board::new(&[], 5, 6, BoardStyle::Emoji).to_string());
+        assert_eq!("", Board::new(&[], 5, 6, BoardStyle::Letters).to_string());
+        assert_eq!(
+            " .   .   .   .   . \n .   .   .   .   . ",
+            Board::new(&[], 5, 2, BoardStyle::Grid).to_string()
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! Configuration for a [`Wordle`] game
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt, marker::PhantomData, time::Instant};
+
+use crate::{
+    alphabet::Alphabet, dictionary::Dictionary, state::NoWord, Wordle, MAX_GUESSES, MAX_HINTS,
+    WORD_LENGTH,
+};
+
+/// Why a [`WordleBuilder`] couldn't build a game
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuildError {
+    /// No list of acceptable guesses was given
+    MissingGuesses,
+    /// No list of answers was given
+    MissingAnswers,
+    /// The list of acceptable guesses is empty
+    EmptyGuesses,
+    /// The list of answers is empty
+    EmptyAnswers,
+    /// The word length is zero
+    InvalidWordLength,
+    /// The guess limit is zero
+    InvalidMaxGuesses,
+    /// A word in one of the lists doesn't have the configured number of letters
+    WrongLength {
+        /// The offending word
+        word: String,
+        /// The configured word length
+        expected: usize,
+    },
+    /// A word in one of the lists has a letter that isn't in the alphabet
+    InvalidLetter {
+        /// The offending word
+        word: String,
+        /// The first letter of `word` that isn't in the alphabet
+        letter: char,
+    },
+    /// An answer isn't in the list of acceptable guesses, so it could never be guessed
+    AnswerNotInGuesses {
+        /// The offending answer
+        word: String,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingGuesses => write!(f, "no guess list given"),
+            Self::MissingAnswers => write!(f, "no answer list given"),
+            Self::EmptyGuesses => write!(f, "the guess list is empty"),
+            Self::EmptyAnswers => write!(f, "the answer list is empty"),
+            Self::InvalidWordLength => write!(f, "the word length must be at least 1"),
+            Self::InvalidMaxGuesses => write!(f, "the guess limit must be at least 1"),
+            Self::WrongLength { word, expected } => write!(
+                f,
+                "'{}' has {} letters, expected {}",
+                word,
+                word.chars().count(),
+                expected
+            ),
+            Self::InvalidLetter { word, letter } => {
+                write!(
+                    f,
+                    "'{}' has the letter '{}', which isn't in the alphabet",
+                    word, letter
+                )
+            }
+            Self::AnswerNotInGuesses { word } => {
+                write!(f, "the answer '{}' is not in the guess list", word)
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Builder for a [`Wordle`] game.
+///
+/// All of the validation of the configuration happens in [`WordleBuilder::build`].
+///
+/// ```
+/// let guesses = ["crane", "slate", "trace"];
+/// let answers = ["crane"];
+/// let game = wordle::Wordle::builder()
+///     .guesses(&guesses)
+///     .answers(&answers)
+///     .hard_mode(true)
+///     .seed(42)
+///     .build()
+///     .unwrap();
+/// assert!(game.hard_mode());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordleBuilder<'a> {
+    /// Acceptable guesses
+    guesses: Option<&'a [&'a str]>,
+    /// An index to look up the guesses in, or `None` to hash them
+    dictionary: Option<Dictionary<'a>>,
+    /// Answer list
+    answers: Option<&'a [&'a str]>,
+    /// The number of letters in each word
+    word_length: usize,
+    /// The letters words can be made of
+    alphabet: Alphabet,
+    /// The number of guesses a player gets to find the word
+    max_guesses: usize,
+    /// Whether revealed hints must be used in subsequent guesses
+    hard_mode: bool,
+    /// Whether guessing a word again is rejected rather than using up a turn
+    reject_repeats: bool,
+    /// Seed for choosing words, or `None` to seed from the OS
+    seed: Option<u64>,
+    /// Whether to keep track of the answers still consistent with the guesses so far
+    track_candidates: bool,
+    /// Whether the remaining candidates themselves may be revealed
+    assist: bool,
+    /// The number of hints a player can ask for on each word
+    max_hints: usize,
+}
+
+impl Default for WordleBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            guesses: None,
+            dictionary: None,
+            answers: None,
+            word_length: WORD_LENGTH,
+            alphabet: Alphabet::default(),
+            max_guesses: MAX_GUESSES,
+            hard_mode: false,
+            reject_repeats: false,
+            seed: None,
+            track_candidates: false,
+            assist: false,
+            max_hints: MAX_HINTS,
+        }
+    }
+}
+
+impl<'a> WordleBuilder<'a> {
+    /// Start with the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the list of acceptable guesses; this must include every answer
+    pub fn guesses(mut self, guesses: &'a [&'a str]) -> Self {
+        self.guesses = Some(guesses);
+        self.dictionary = None;
+        self
+    }
+
+    /// Set the acceptable guesses from a [`Dictionary`], looking them up in its
+    /// index rather than hashing them; this must include every answer
+    pub fn dictionary(mut self, dictionary: Dictionary<'a>) -> Self {
+        self.guesses = Some(dictionary.list());
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Set the list of words to choose answers from
+    pub fn answers(mut self, answers: &'a [&'a str]) -> Self {
+        self.answers = Some(answers);
+        self
+    }
+
+    /// Set the number of letters in each word (default: 5)
+    pub fn word_length(mut self, word_length: usize) -> Self {
+        self.word_length = word_length;
+        self
+    }
+
+    /// Set the letters words can be made of (default: [`Alphabet::english`])
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Set the number of guesses a player gets to find the word (default: 6)
+    pub fn max_guesses(mut self, max_guesses: usize) -> Self {
+        self.max_guesses = max_guesses;
+        self
+    }
+
+    /// Require revealed hints to be used in subsequent guesses (default: off)
+    pub fn hard_mode(mut self, enabled: bool) -> Self {
+        self.hard_mode = enabled;
+        self
+    }
+
+    /// Reject a word that was already guessed against the current word, rather
+    /// than letting it use up a turn (default: `false`)
+    pub fn reject_repeats(mut self, enabled: bool) -> Self {
+        self.reject_repeats = enabled;
+        self
+    }
+
+    /// Seed the choice of answers, so the same seed plays the same words (default: random)
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// See [`Wordle::with_candidate_tracking`] (default: off)
+    pub fn track_candidates(mut self, enabled: bool) -> Self {
+        self.track_candidates = enabled;
+        self
+    }
+
+    /// See [`Wordle::with_assist`] (default: off)
+    pub fn assist(mut self, enabled: bool) -> Self {
+        self.assist = enabled;
+        self
+    }
+
+    /// Set the number of hints a player can ask for on each word (default: 2)
+    pub fn max_hints(mut self, max_hints: usize) -> Self {
+        self.max_hints = max_hints;
+        self
+    }
+
+    /// Validate the configuration and build the game
+    pub fn build(self) -> Result<Wordle<'a, NoWord>, BuildError> {
+        let guesses = self.guesses.ok_or(BuildError::MissingGuesses)?;
+        let answers = self.answers.ok_or(BuildError::MissingAnswers)?;
+
+        if guesses.is_empty() {
+            return Err(BuildError::EmptyGuesses);
+        }
+        if answers.is_empty() {
+            return Err(BuildError::EmptyAnswers);
+        }
+        if self.word_length == 0 {
+            return Err(BuildError::InvalidWordLength);
+        }
+        if self.max_guesses == 0 {
+            return Err(BuildError::InvalidMaxGuesses);
+        }
+
+        if let Some(word) = guesses
+            .iter()
+            .chain(answers)
+            .find(|word| word.chars().count() != self.word_length)
+        {
+            return Err(BuildError::WrongLength {
+                word: word.to_string(),
+                expected: self.word_length,
+            });
+        }
+
+        if let Some((word, letter)) = guesses
+            .iter()
+            .chain(answers)
+            .find_map(|word| Some((word, self.alphabet.invalid_letter(word)?)))
+        {
+            return Err(BuildError::InvalidLetter {
+                word: word.to_string(),
+                letter,
+            });
+        }
+
+        let guesses = self.dictionary.unwrap_or_else(|| Dictionary::new(guesses));
+        if let Some(word) = answers.iter().find(|word| !guesses.contains_guess(word)) {
+            return Err(BuildError::AnswerNotInGuesses {
+                word: word.to_string(),
+            });
+        }
+
+        Ok(Wordle {
+            seed: self.seed.unwrap_or_else(rand::random),
+            games: 0,
+            number: None,
+            guesses,
+            answers,
+            word: "",
+            word_length: self.word_length,
+            alphabet: self.alphabet,
+            max_guesses: self.max_guesses,
+            hard_mode: self.hard_mode,
+            reject_repeats: self.reject_repeats,
+            track_candidates: self.track_candidates || self.assist,
+            assist: self.assist,
+            candidates: None,
+            history: Vec::new(),
+            gave_up: false,
+            max_hints: self.max_hints,
+            hints: Vec::new(),
+            started: Instant::now(),
+            paused: None,
+            guess_times: Vec::new(),
+            ended: None,
+            state: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Transcript;
+
+    const GUESSES: [&str; 4] = ["crane", "slate", "trace", "toast"];
+    const ANSWERS: [&str; 2] = ["crane", "slate"];
+
+    #[test]
+    fn test_build() {
+        let game = WordleBuilder::new()
+            .guesses(&GUESSES)
+            .answers(&ANSWERS)
+            .max_guesses(4)
+            .hard_mode(true)
+            .build()
+            .unwrap();
+        assert_eq!(5, game.word_length());
+        assert_eq!(4, game.max_guesses());
+        assert!(game.hard_mode());
+    }
+
+    #[test]
+    fn test_validation() {
+        let builder = WordleBuilder::new();
+        assert_eq!(
+            BuildError::MissingGuesses,
+            builder.clone().answers(&ANSWERS).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::MissingAnswers,
+            builder.clone().guesses(&GUESSES).build().unwrap_err()
+        );
+
+        let builder = builder.guesses(&GUESSES).answers(&ANSWERS);
+        assert_eq!(
+            BuildError::EmptyGuesses,
+            builder.clone().guesses(&[]).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::EmptyAnswers,
+            builder.clone().answers(&[]).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::InvalidWordLength,
+            builder.clone().word_length(0).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::InvalidMaxGuesses,
+            builder.clone().max_guesses(0).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::WrongLength {
+                word: "crane".into(),
+                expected: 6
+            },
+            builder.clone().word_length(6).build().unwrap_err()
+        );
+        assert_eq!(
+            BuildError::WrongLength {
+                word: "cranes".into(),
+                expected: 5
+            },
+            builder
+                .clone()
+                .answers(&["crane", "cranes"])
+                .build()
+                .unwrap_err()
+        );
+        assert_eq!(
+            BuildError::InvalidLetter {
+                word: "niños".into(),
+                letter: 'ñ'
+            },
+            builder
+                .clone()
+                .guesses(&["crane", "slate", "niños"])
+                .build()
+                .unwrap_err()
+        );
+        assert_eq!(
+            BuildError::AnswerNotInGuesses {
+                word: "boats".into()
+            },
+            builder
+                .clone()
+                .answers(&["crane", "boats"])
+                .build()
+                .unwrap_err()
+        );
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_seed() {
+        let answers = ["aaaaa", "bbbbb", "ccccc", "ddddd", "eeeee", "fffff"];
+        let play = |seed| {
+            let mut game = WordleBuilder::new()
+                .guesses(&answers)
+                .answers(&answers)
+                .seed(seed)
+                .build()
+                .unwrap();
+            let mut words = Vec::new();
+            for _ in 0..20 {
+                let played = game.choose_word();
+                words.push(played.answer());
+                game = played.clear();
+            }
+            words
+        };
+        assert_eq!(play(42), play(42));
+        assert_ne!(play(42), play(1337));
+    }
+
+    #[test]
+    fn test_numbered_games() {
+        let answers = ["aaaaa", "bbbbb", "ccccc", "ddddd", "eeeee", "fffff"];
+        let builder = || {
+            WordleBuilder::new()
+                .guesses(&answers)
+                .answers(&answers)
+                .seed(42)
+        };
+
+        let mut played = builder().build().unwrap().choose_word();
+        let mut words = vec![played.answer()];
+        for _ in 1..7 {
+            played = played.clear().choose_word();
+            words.push(played.answer());
+        }
+        assert_eq!(Some(7), played.game_number());
+
+        // any game can be jumped to, and play carries on from there
+        let jumped = builder().build().unwrap().choose_game(5);
+        assert_eq!(words[4], jumped.answer());
+        assert_eq!(Some(5), jumped.game_number());
+        let jumped = jumped.clear().choose_word();
+        assert_eq!(words[5], jumped.answer());
+        assert_eq!(Some(6), jumped.game_number());
+
+        // a resumed game carries on its own session
+        let transcript = Transcript {
+            seed: Some(42),
+            ..jumped.transcript()
+        };
+        assert_eq!(Some(6), transcript.game);
+        let resumed = builder()
+            .seed(1)
+            .build()
+            .unwrap()
+            .resume(&transcript)
+            .unwrap();
+        assert_eq!(Some(6), resumed.game_number());
+        let resumed = resumed.clear().choose_word();
+        assert_eq!(words[6], resumed.answer());
+
+        let jumped = jumped.clear().set_answer("aaaaa").unwrap();
+        assert_eq!(None, jumped.game_number());
+        assert_eq!(42, jumped.seed());
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let words = ["niños", "señor", "playa"];
+        let game = WordleBuilder::new()
+            .guesses(&words)
+            .answers(&words)
+            .alphabet(Alphabet::spanish())
+            .build()
+            .unwrap();
+        assert_eq!(&Alphabet::spanish(), game.alphabet());
+    }
+
+    #[test]
+    fn test_other_word_lengths() {
+        let words = ["wordle", "quordl"];
+        let mut game = WordleBuilder::new()
+            .guesses(&words)
+            .answers(&words[..1])
+            .word_length(6)
+            .build()
+            .unwrap()
+            .choose_word();
+        assert_eq!(6, game.guess("quordl").unwrap().feedback().len());
+    }
+}
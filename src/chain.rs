@@ -0,0 +1,318 @@
+//! Chains of games where each starts with the last one's answer, like Hurdle
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    share::{share_grid, TileSet},
+    state::NoWord,
+    GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+/// Games played one after another, where each game after the first starts
+/// with the answer to the one before it already guessed.
+///
+/// The guess played for the player counts against each game's guesses, so
+/// they have one fewer of their own. The chain is won once every game is won,
+/// and lost as soon as any game is.
+#[derive(Debug)]
+pub struct ChainedWordle<'a> {
+    /// The settings and word lists each game is played with
+    template: Wordle<'a, NoWord>,
+    /// The answer to each game, in order
+    answers: Vec<&'a str>,
+    /// The games started so far; the last is the one being played
+    games: Vec<Wordle<'a>>,
+    /// The number of guesses in the final game, if it has fewer than the others
+    final_guesses: Option<usize>,
+}
+
+impl<'a> ChainedWordle<'a> {
+    /// Chain games against each of `answers` in turn, with the word lists and
+    /// settings of `game`, and start the first one
+    pub fn new(game: &Wordle<'a, NoWord>, answers: &[&str]) -> Result<Self, GuessError> {
+        let template = game.fresh_board();
+        let words = answers
+            .iter()
+            .map(|answer| template.check_answer(answer))
+            .collect::<Result<_, _>>()?;
+        let mut chain = Self {
+            template,
+            answers: words,
+            games: Vec::new(),
+            final_guesses: None,
+        };
+        chain.start_next();
+        Ok(chain)
+    }
+
+    /// Chain `length` games against different answers chosen at random
+    pub fn random<R: Rng + ?Sized>(game: &Wordle<'a, NoWord>, length: usize, rng: &mut R) -> Self {
+        let answers: Vec<&str> = game.answers.choose_multiple(rng, length).copied().collect();
+        Self::new(game, &answers).expect("Answers are acceptable guesses")
+    }
+
+    /// Give the final game `guesses` guesses, counting the one played for the
+    /// player, rather than as many as the others
+    pub fn with_final_guesses(mut self, guesses: usize) -> Self {
+        self.final_guesses = Some(guesses);
+        // a chain of one game has already started its final game, but nothing's been guessed yet
+        if self.games.len() == self.answers.len() {
+            if let Some(game) = self.games.last_mut() {
+                game.max_guesses = guesses;
+            }
+        }
+        self
+    }
+
+    /// Start the next game, with the last game's answer guessed for the player
+    fn start_next(&mut self) {
+        let Some(&answer) = self.answers.get(self.games.len()) else {
+            return;
+        };
+        let mut game = self.template.fresh_board().start(answer);
+        if self.games.len() + 1 == self.answers.len() {
+            if let Some(guesses) = self.final_guesses {
+                game.max_guesses = guesses;
+            }
+        }
+        if let Some(previous) = self.games.last().map(Wordle::answer) {
+            game.guess(previous)
+                .expect("Answers are acceptable guesses, and nothing's been guessed yet");
+        }
+        self.games.push(game);
+    }
+
+    /// The number of games in the chain
+    pub fn length(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// The answer to each game, in order, for showing once the chain is over
+    pub fn answers(&self) -> &[&'a str] {
+        &self.answers
+    }
+
+    /// The games started so far, in order
+    pub fn games(&self) -> &[Wordle<'a>] {
+        &self.games
+    }
+
+    /// The game being played, or the last one played once the chain is over
+    pub fn current(&self) -> &Wordle<'a> {
+        self.games
+            .last()
+            .expect("The first game starts with the chain")
+    }
+
+    /// The number of the game being played, counting from one
+    pub fn number(&self) -> usize {
+        self.games.len()
+    }
+
+    /// The word guessed for the player at the start of the game being played,
+    /// or `None` for the first game
+    pub fn carried_over(&self) -> Option<&'a str> {
+        self.games.iter().rev().nth(1).map(Wordle::answer)
+    }
+
+    /// Guess a word in the game being played, and get back its feedback.
+    ///
+    /// Once the game is won, the next one starts straight away.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<LetterStatus>, GuessError> {
+        if self.status() != GameStatus::InProgress {
+            return Err(GuessError::GameOver);
+        }
+        let game = self.games.last_mut().expect("The chain is in progress");
+        let feedback = game.guess(word)?.into_feedback();
+        if matches!(game.status(), GameStatus::Won { .. }) {
+            self.start_next();
+        }
+        Ok(feedback)
+    }
+
+    /// Give up on the game being played, which loses the chain
+    pub fn give_up(&mut self) {
+        if let Some(game) = self.games.last_mut() {
+            game.give_up();
+        }
+    }
+
+    /// The number of games won so far
+    pub fn solved(&self) -> usize {
+        self.games
+            .iter()
+            .filter(|game| matches!(game.status(), GameStatus::Won { .. }))
+            .count()
+    }
+
+    /// The number of guesses the player made themselves, leaving out the ones
+    /// played for them
+    pub fn guesses(&self) -> usize {
+        self.games
+            .iter()
+            .enumerate()
+            .map(|(i, game)| game.history().len() - usize::from(i > 0))
+            .sum()
+    }
+
+    /// Won once every game is won, with the number of [`guesses`](Self::guesses)
+    /// the player made, or lost once any game is
+    pub fn status(&self) -> GameStatus {
+        match self.current().status() {
+            GameStatus::Lost => GameStatus::Lost,
+            GameStatus::Won { .. } if self.games.len() == self.answers.len() => GameStatus::Won {
+                guesses: self.guesses(),
+            },
+            _ => GameStatus::InProgress,
+        }
+    }
+
+    /// The time spent on the chain: every game's time added up
+    pub fn elapsed(&self) -> Duration {
+        self.games.iter().map(Wordle::elapsed).sum()
+    }
+
+    /// The text to share for the chain: a header with how many games were
+    /// won, e.g. `Hurdle 3/5`, then each game's grid.
+    ///
+    /// The rows played for the player are left in, since they're part of the puzzle.
+    pub fn share_text(&self, tiles: &TileSet<'_>) -> String {
+        let mut text = format!("Hurdle {}/{}", self.solved(), self.length());
+        for game in &self.games {
+            text.push_str("\n\n");
+            text.push_str(&share_grid(game.history(), tiles));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 6] = ["crane", "slate", "trace", "irate", "share", "stare"];
+
+    fn chain(answers: &[&str]) -> ChainedWordle<'static> {
+        let template = Wordle::new(&WORDS, &WORDS);
+        ChainedWordle::new(&template, answers).unwrap()
+    }
+
+    #[test]
+    fn test_chain() {
+        let mut chain = chain(&["crane", "slate", "trace"]);
+        assert_eq!(3, chain.length());
+        assert_eq!(1, chain.number());
+        assert_eq!(None, chain.carried_over());
+        assert!(chain.current().history().is_empty());
+
+        chain.guess("irate").unwrap();
+        assert_eq!(1, chain.number());
+        assert_eq!(
+            vec![LetterStatus::Correct; 5],
+            chain.guess("crane").unwrap()
+        );
+
+        // the next game starts with the last answer, which uses up one of its guesses
+        assert_eq!(2, chain.number());
+        assert_eq!(Some("crane"), chain.carried_over());
+        assert_eq!(
+            &[("crane", crate::score_guess("crane", "slate"))][..],
+            chain.current().history()
+        );
+        assert_eq!(5, chain.current().guesses_left());
+        assert_eq!(GameStatus::InProgress, chain.status());
+
+        chain.guess("slate").unwrap();
+        chain.guess("trace").unwrap();
+        assert_eq!(GameStatus::Won { guesses: 4 }, chain.status());
+        assert_eq!(3, chain.solved());
+        assert_eq!(3, chain.games().len());
+        assert_eq!(Err(GuessError::GameOver), chain.guess("crane"));
+
+        // invalid guesses aren't played
+        let mut chain = self::chain(&["crane", "slate"]);
+        assert_eq!(Err(GuessError::NotInWordList), chain.guess("zzzzz"));
+        assert_eq!(0, chain.guesses());
+        assert!(ChainedWordle::new(&Wordle::new(&WORDS, &WORDS), &["zzzzz"]).is_err());
+    }
+
+    #[test]
+    fn test_loss() {
+        let mut chain = chain(&["crane", "slate", "trace"]);
+        chain.guess("crane").unwrap();
+        for _ in 0..5 {
+            chain.guess("irate").unwrap();
+        }
+        assert_eq!(GameStatus::Lost, chain.status());
+        assert_eq!(1, chain.solved());
+        assert_eq!(2, chain.games().len(), "the chain stops at the game lost");
+
+        let mut chain = self::chain(&["crane", "slate"]);
+        chain.give_up();
+        assert_eq!(GameStatus::Lost, chain.status());
+    }
+
+    #[test]
+    fn test_final_guesses() {
+        let mut chain = chain(&["crane", "slate", "trace"]).with_final_guesses(3);
+        chain.guess("crane").unwrap();
+        assert_eq!(6, chain.current().max_guesses());
+        chain.guess("slate").unwrap();
+        assert_eq!(3, chain.current().max_guesses());
+        assert_eq!(2, chain.current().guesses_left());
+
+        // a chain of one game is all final game
+        let chain = self::chain(&["crane"]).with_final_guesses(4);
+        assert_eq!(4, chain.current().max_guesses());
+    }
+
+    #[test]
+    fn test_random() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let chain = ChainedWordle::random(&template, 4, &mut rand::thread_rng());
+        let mut answers = chain.answers.clone();
+        answers.sort_unstable();
+        answers.dedup();
+        assert_eq!(4, answers.len(), "every game has its own word");
+    }
+
+    #[test]
+    fn test_share_text() {
+        let mut chain = chain(&["crane", "slate", "trace"]);
+        for guess in ["slate", "crane", "slate", "irate"] {
+            chain.guess(guess).unwrap();
+        }
+        assert_eq!(
+            concat!(
+                "Hurdle 2/3\n",
+                "\n",
+                "..#.#\n",
+                "#####\n",
+                "\n",
+                "..#.#\n",
+                "#####\n",
+                "\n",
+                "..#+#\n",
+                ".##+#",
+            ),
+            chain.share_text(&TileSet::ascii())
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Compact, reversible text encodings for the feedback from a guess
+//!
+//! There are three encodings of a sequence of [`LetterStatus`]es:
+//! - letters: one ASCII character per status, e.g. `"gy--g"`
+//! - emoji: one colored square per status, e.g. `"🟩🟨⬛⬛🟩"`
+//! - packed: a single base-3 number with the first status as the most significant digit
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt};
+
+use crate::LetterStatus;
+
+/// Letter is in word in the correct position
+pub const GREEN_SQ: &str = "🟩";
+/// Letter is in word, but has incorrect position
+pub const YELLOW_SQ: &str = "🟨";
+/// Letter is not in word
+pub const BLACK_SQ: &str = "⬛";
+/// Letter is not in word (light theme)
+pub const WHITE_SQ: &str = "⬜";
+
+/// An error decoding an encoded sequence of [`LetterStatus`]es
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseStatusError {
+    /// The character at the given (`char`, not byte) position doesn't stand for a status
+    InvalidChar {
+        /// The unrecognized character
+        ch: char,
+        /// The position of `ch` in the input
+        position: usize,
+    },
+    /// The packed value is too large for the given number of statuses
+    OutOfRange {
+        /// The packed value
+        value: u32,
+        /// The number of statuses it was supposed to hold
+        len: usize,
+    },
+}
+
+impl fmt::Display for ParseStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar { ch, position } => {
+                write!(f, "invalid status '{}' at position {}", ch, position + 1)
+            }
+            Self::OutOfRange { value, len } => {
+                write!(f, "{} is too large to hold {} statuses", value, len)
+            }
+        }
+    }
+}
+
+impl Error for ParseStatusError {}
+
+/// Get the character representing a [`LetterStatus`] in the letter encoding
+pub fn status_to_char(status: &LetterStatus) -> char {
+    match status {
+        LetterStatus::Correct => 'g',
+        LetterStatus::InWord => 'y',
+        LetterStatus::NotInWord => '-',
+    }
+}
+
+/// Get the colored square to represent a [`LetterStatus`]
+pub fn status_to_emoji(status: &LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => GREEN_SQ,
+        LetterStatus::InWord => YELLOW_SQ,
+        LetterStatus::NotInWord => BLACK_SQ,
+    }
+}
+
+/// Encode statuses as letters, e.g. `"gy--g"`
+pub fn statuses_to_string(statuses: &[LetterStatus]) -> String {
+    statuses.iter().map(status_to_char).collect()
+}
+
+/// Decode statuses from letters, e.g. `"gy--g"`. Letters are not case-sensitive.
+pub fn statuses_from_string(s: &str) -> Result<Vec<LetterStatus>, ParseStatusError> {
+    s.chars()
+        .enumerate()
+        .map(|(position, ch)| match ch.to_ascii_lowercase() {
+            'g' => Ok(LetterStatus::Correct),
+            'y' => Ok(LetterStatus::InWord),
+            '-' => Ok(LetterStatus::NotInWord),
+            _ => Err(ParseStatusError::InvalidChar { ch, position }),
+        })
+        .collect()
+}
+
+/// Encode statuses as colored squares, e.g. `"🟩🟨⬛⬛🟩"`
+pub fn statuses_to_emoji(statuses: &[LetterStatus]) -> String {
+    statuses.iter().map(status_to_emoji).collect()
+}
+
+/// Decode statuses from colored squares, e.g. `"🟩🟨⬛⬛🟩"`.
+///
+/// Both ⬛ and ⬜ are accepted for letters not in the word, and emoji variation
+/// selectors are ignored.
+pub fn statuses_from_emoji(s: &str) -> Result<Vec<LetterStatus>, ParseStatusError> {
+    s.chars()
+        .filter(|&ch| ch != '\u{fe0f}')
+        .enumerate()
+        .map(|(position, ch)| {
+            let mut buf = [0; 4];
+            match &*ch.encode_utf8(&mut buf) {
+                GREEN_SQ => Ok(LetterStatus::Correct),
+                YELLOW_SQ => Ok(LetterStatus::InWord),
+                BLACK_SQ | WHITE_SQ => Ok(LetterStatus::NotInWord),
+                _ => Err(ParseStatusError::InvalidChar { ch, position }),
+            }
+        })
+        .collect()
+}
+
+/// Decode statuses typed in by a person, which may be in either encoding.
+///
+/// As well as `g`, `y`, and `-`, a letter not in the word can be given as `b`
+/// (for black), `x`, `.`, or `_`, and the squares from the high-contrast share
+/// text (🟧 and 🟦) are understood too. Whitespace is ignored, so `"g y - - g"`
+/// and `"🟩 🟨⬛⬛🟩"` both work.
+pub fn parse_statuses(s: &str) -> Result<Vec<LetterStatus>, ParseStatusError> {
+    s.chars()
+        .filter(|&ch| !ch.is_whitespace() && ch != '\u{fe0f}')
+        .enumerate()
+        .map(|(position, ch)| match ch.to_ascii_lowercase() {
+            'g' | '🟩' | '🟧' => Ok(LetterStatus::Correct),
+            'y' | '🟨' | '🟦' => Ok(LetterStatus::InWord),
+            '-' | 'b' | 'x' | '.' | '_' | '⬛' | '⬜' => Ok(LetterStatus::NotInWord),
+            _ => Err(ParseStatusError::InvalidChar { ch, position }),
+        })
+        .collect()
+}
+
+/// Pack statuses into a single base-3 number, with the first status as the most
+/// significant digit.
+///
+/// Not in word is 0, in word is 1, and correct is 2; so `"gy--g"` packs to
+/// `2*81 + 1*27 + 0*9 + 0*3 + 2 = 191`. Up to 20 statuses fit in a `u32`.
+pub fn statuses_to_packed(statuses: &[LetterStatus]) -> u32 {
+    assert!(statuses.len() <= 20, "Too many statuses to pack");
+    statuses.iter().fold(0, |packed, status| {
+        packed * 3
+            + match status {
+                LetterStatus::NotInWord => 0,
+                LetterStatus::InWord => 1,
+                LetterStatus::Correct => 2,
+            }
+    })
+}
+
+/// Unpack `len` statuses from a base-3 number; see [`statuses_to_packed`].
+pub fn statuses_from_packed(value: u32, len: usize) -> Result<Vec<LetterStatus>, ParseStatusError> {
+    if len > 20 || value >= 3u32.pow(len as u32) {
+        return Err(ParseStatusError::OutOfRange { value, len });
+    }
+
+    let mut statuses = vec![LetterStatus::NotInWord; len];
+    let mut rest = value;
+    for status in statuses.iter_mut().rev() {
+        *status = match rest % 3 {
+            0 => LetterStatus::NotInWord,
+            1 => LetterStatus::InWord,
+            _ => LetterStatus::Correct,
+        };
+        rest /= 3;
+    }
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterStatus::*;
+
+    /// Every possible sequence of `len` statuses
+    fn all_patterns(len: usize) -> Vec<Vec<LetterStatus>> {
+        (0..len).fold(vec![vec![]], |patterns, _| {
+            patterns
+                .into_iter()
+                .flat_map(|p| {
+                    [Correct, InWord, NotInWord].into_iter().map(move |s| {
+                        let mut p = p.clone();
+                        p.push(s);
+                        p
+                    })
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_examples() {
+        let statuses = [Correct, InWord, NotInWord, NotInWord, Correct];
+        assert_eq!("gy--g", statuses_to_string(&statuses));
+        assert_eq!("🟩🟨⬛⬛🟩", statuses_to_emoji(&statuses));
+        assert_eq!(191, statuses_to_packed(&statuses));
+
+        assert_eq!(Ok(statuses.to_vec()), statuses_from_string("GY--g"));
+        assert_eq!(
+            Ok(statuses.to_vec()),
+            statuses_from_emoji("🟩🟨⬜⬛\u{fe0f}🟩")
+        );
+        assert_eq!(Ok(statuses.to_vec()), statuses_from_packed(191, 5));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..=7 {
+            let patterns = all_patterns(len);
+            assert_eq!(3usize.pow(len as u32), patterns.len());
+
+            for pattern in patterns {
+                assert_eq!(
+                    Ok(pattern.clone()),
+                    statuses_from_string(&statuses_to_string(&pattern))
+                );
+                assert_eq!(
+                    Ok(pattern.clone()),
+                    statuses_from_emoji(&statuses_to_emoji(&pattern))
+                );
+                assert_eq!(
+                    Ok(pattern.clone()),
+                    statuses_from_packed(statuses_to_packed(&pattern), len)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_statuses() {
+        let statuses = vec![Correct, InWord, NotInWord, NotInWord, Correct];
+        for input in [
+            "gy--g",
+            "GYbxg",
+            " g y . _ g\n",
+            "🟩🟨⬛⬜🟩",
+            "🟩 🟨 ⬛\u{fe0f} ⬛ 🟩",
+            "🟧🟦⬛⬛🟧",
+            "gy⬛-🟩",
+        ] {
+            assert_eq!(Ok(statuses.clone()), parse_statuses(input), "{:?}", input);
+        }
+        assert_eq!(Ok(vec![]), parse_statuses("  "));
+        assert_eq!(
+            Err(ParseStatusError::InvalidChar {
+                ch: 'r',
+                position: 2
+            }),
+            parse_statuses("g y r")
+        );
+    }
+
+    #[test]
+    fn test_packed_is_dense() {
+        // every value below 3^5 is a distinct pattern
+        let mut values: Vec<u32> = all_patterns(5)
+            .iter()
+            .map(|p| statuses_to_packed(p))
+            .collect();
+        values.sort_unstable();
+        assert_eq!((0..243).collect::<Vec<u32>>(), values);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(ParseStatusError::InvalidChar {
+                ch: 'x',
+                position: 2
+            }),
+            statuses_from_string("gyx-g")
+        );
+        assert_eq!(
+            Err(ParseStatusError::InvalidChar {
+                ch: 'g',
+                position: 1
+            }),
+            statuses_from_emoji("🟩g")
+        );
+        assert_eq!(
+            Err(ParseStatusError::OutOfRange { value: 243, len: 5 }),
+            statuses_from_packed(243, 5)
+        );
+        assert_eq!(
+            "invalid status 'x' at position 3",
+            statuses_from_string("gyx-g").unwrap_err().to_string()
+        );
+    }
+}
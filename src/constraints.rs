@@ -0,0 +1,343 @@
+//! A structured summary of what the guesses so far reveal about the answer
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::LetterStatus;
+
+/// Everything known about the answer from the feedback for a set of guesses.
+///
+/// For each letter, the feedback gives a lower bound on how many times it appears
+/// in the answer (the number of green and yellow tiles for it in a guess), and a
+/// gray tile for it makes that bound exact. Positions are zero-based.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ConstraintSet {
+    /// The letter known to be at each position (the greens)
+    fixed: Vec<Option<char>>,
+    /// Letters known not to be at each position (from yellows and grays)
+    excluded_at: Vec<BTreeSet<char>>,
+    /// The minimum number of times letters appear in the answer
+    min_counts: BTreeMap<char, usize>,
+    /// The exact number of times letters appear in the answer, where known
+    exact_counts: BTreeMap<char, usize>,
+}
+
+impl ConstraintSet {
+    /// Start with no constraints on words with `word_length` letters
+    pub fn new(word_length: usize) -> Self {
+        Self {
+            fixed: vec![None; word_length],
+            excluded_at: vec![BTreeSet::new(); word_length],
+            min_counts: BTreeMap::new(),
+            exact_counts: BTreeMap::new(),
+        }
+    }
+
+    /// Build the constraints for a sequence of guesses and their feedback
+    pub fn from_history<S: AsRef<str>>(
+        word_length: usize,
+        history: &[(S, Vec<LetterStatus>)],
+    ) -> Self {
+        let mut constraints = Self::new(word_length);
+        for (guess, statuses) in history {
+            constraints.add(guess.as_ref(), statuses);
+        }
+        constraints
+    }
+
+    /// Add what the feedback for a guess reveals
+    pub fn add(&mut self, guess: &str, statuses: &[LetterStatus]) {
+        assert_eq!(
+            guess.chars().count(),
+            self.fixed.len(),
+            "Guess has the wrong length"
+        );
+        assert_eq!(
+            statuses.len(),
+            self.fixed.len(),
+            "Feedback has the wrong length"
+        );
+
+        // the number of green/yellow tiles for each letter, and whether it had any gray tiles
+        let mut found: BTreeMap<char, (usize, bool)> = BTreeMap::new();
+
+        for (i, (letter, status)) in guess.chars().zip(statuses).enumerate() {
+            let (count, gray) = found.entry(letter).or_insert((0, false));
+            match status {
+                LetterStatus::Correct => {
+                    self.fixed[i] = Some(letter);
+                    *count += 1;
+                }
+                LetterStatus::InWord => {
+                    self.excluded_at[i].insert(letter);
+                    *count += 1;
+                }
+                LetterStatus::NotInWord => {
+                    // either the letter isn't in the word at all, or all of its
+                    // occurrences are accounted for by other tiles; either way it
+                    // isn't here (or this tile would be green)
+                    self.excluded_at[i].insert(letter);
+                    *gray = true;
+                }
+            }
+        }
+
+        for (letter, (count, gray)) in found {
+            let min = self.min_counts.entry(letter).or_insert(0);
+            *min = (*min).max(count);
+            if gray {
+                self.exact_counts.insert(letter, count);
+            }
+        }
+        self.min_counts.retain(|_, &mut min| min > 0);
+    }
+
+    /// Check whether a word satisfies every constraint, i.e. whether it could still be the answer
+    pub fn matches(&self, word: &str) -> bool {
+        let letters: Vec<char> = word.chars().collect();
+        if letters.len() != self.fixed.len() {
+            return false;
+        }
+
+        let positions_ok = letters.iter().enumerate().all(|(i, letter)| {
+            self.fixed[i].is_none_or(|fixed| fixed == *letter)
+                && !self.excluded_at[i].contains(letter)
+        });
+        if !positions_ok {
+            return false;
+        }
+
+        let count = |letter: char| letters.iter().filter(|&&l| l == letter).count();
+        self.min_counts
+            .iter()
+            .all(|(&letter, &min)| count(letter) >= min)
+            && self
+                .exact_counts
+                .iter()
+                .all(|(&letter, &exact)| count(letter) == exact)
+    }
+
+    /// The letter known to be at each position (the greens)
+    pub fn fixed(&self) -> &[Option<char>] {
+        &self.fixed
+    }
+
+    /// Letters known to be in the word, with the minimum number of times each appears
+    pub fn required(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.min_counts.iter().map(|(&letter, &min)| (letter, min))
+    }
+
+    /// Letters known not to be in the word at all
+    pub fn excluded(&self) -> impl Iterator<Item = char> + '_ {
+        self.exact_counts
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&letter, _)| letter)
+    }
+
+    /// Letters known to be in the word, but not at `position` (from yellows)
+    pub fn excluded_at(&self, position: usize) -> impl Iterator<Item = char> + '_ {
+        self.excluded_at[position]
+            .iter()
+            .copied()
+            .filter(move |letter| self.min_counts.contains_key(letter))
+    }
+
+    /// The exact number of times a letter appears in the word, if known
+    pub fn exact_count(&self, letter: char) -> Option<usize> {
+        self.exact_counts.get(&letter).copied()
+    }
+
+    /// Express the constraints as a regular expression matching the possible answers.
+    ///
+    /// Positional constraints become character classes; letter counts become
+    /// lookaheads (e.g. `(?=(?:.*e){2})`), so the pattern needs a regex engine
+    /// that supports them, like PCRE or `fancy-regex`.
+    pub fn to_regex(&self) -> String {
+        let mut regex = String::from("^");
+
+        for (letter, min) in self.required() {
+            // greens already guarantee some occurrences
+            let fixed = self.fixed.iter().filter(|&&f| f == Some(letter)).count();
+            if min > fixed {
+                regex.push_str(&lookahead('=', letter, min));
+            }
+            if let Some(exact) = self.exact_count(letter) {
+                regex.push_str(&lookahead('!', letter, exact + 1));
+            }
+        }
+
+        let excluded: BTreeSet<char> = self.excluded().collect();
+        for (fixed, excluded_at) in self.fixed.iter().zip(&self.excluded_at) {
+            match fixed {
+                Some(letter) => regex.push_str(&escape(*letter)),
+                None => {
+                    let class: String = excluded.union(excluded_at).map(|&l| escape(l)).collect();
+                    if class.is_empty() {
+                        regex.push('.');
+                    } else {
+                        regex.push_str(&format!("[^{}]", class));
+                    }
+                }
+            }
+        }
+
+        regex.push('$');
+        regex
+    }
+}
+
+/// A lookahead asserting (`=`) or denying (`!`) that `letter` appears at least `n` times
+fn lookahead(kind: char, letter: char, n: usize) -> String {
+    if n == 1 {
+        format!("(?{}.*{})", kind, escape(letter))
+    } else {
+        format!("(?{}(?:.*{}){{{}}})", kind, escape(letter), n)
+    }
+}
+
+/// Escape a letter for use in a regular expression
+fn escape(letter: char) -> String {
+    if letter.is_alphanumeric() {
+        letter.to_string()
+    } else {
+        format!("\\{}", letter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{filter, score_guess};
+
+    const WORDS: [&str; 12] = [
+        "crane", "trace", "react", "eerie", "geese", "hello", "lolly", "holly", "alley", "llama",
+        "speed", "abbey",
+    ];
+
+    /// Build the constraints for guesses against an answer
+    fn constraints(answer: &str, guesses: &[&str]) -> ConstraintSet {
+        let history: Vec<(&str, Vec<LetterStatus>)> = guesses
+            .iter()
+            .map(|&g| (g, score_guess(g, answer)))
+            .collect();
+        ConstraintSet::from_history(5, &history)
+    }
+
+    #[test]
+    fn test_summary() {
+        // t- r+ a+ c? e+
+        let c = constraints("crane", &["trace"]);
+        assert_eq!(&[None, Some('r'), Some('a'), None, Some('e')], c.fixed());
+        assert_eq!(
+            vec![('a', 1), ('c', 1), ('e', 1), ('r', 1)],
+            c.required().collect::<Vec<_>>()
+        );
+        assert_eq!(vec!['t'], c.excluded().collect::<Vec<_>>());
+        assert_eq!(vec!['c'], c.excluded_at(3).collect::<Vec<_>>());
+        assert_eq!(None, c.exact_count('c'));
+        assert_eq!(Some(0), c.exact_count('t'));
+    }
+
+    #[test]
+    fn test_duplicate_letter_counts() {
+        // one green 'l', one yellow 'l', one gray 'l': exactly two 'l's
+        let c = constraints("hello", &["lolly"]);
+        assert_eq!(Some(2), c.exact_count('l'));
+        assert!(c.matches("hello"));
+        assert!(
+            !c.matches("holly"),
+            "'o' must not be in the second position"
+        );
+        assert!(c.excluded().all(|l| l != 'l'), "'l' is not excluded");
+
+        // two yellow 'e's, no gray: at least two
+        let c = constraints("geese", &["eerie"]);
+        assert_eq!(None, c.exact_count('e'));
+        assert!(c.required().any(|r| r == ('e', 3)));
+        assert!(c.matches("geese"));
+
+        // a gray duplicate of a yellow letter caps the count at one
+        let c = constraints("abbey", &["eerie"]);
+        assert_eq!(Some(1), c.exact_count('e'));
+        assert!(!c.matches("geese"));
+        assert!(c.matches("abbey"));
+    }
+
+    #[test]
+    fn test_matches_agrees_with_filter() {
+        // the constraints must accept exactly the words the real scoring accepts
+        for answer in WORDS {
+            for first in WORDS {
+                for second in WORDS {
+                    let c = constraints(answer, &[first, second]);
+                    for word in WORDS {
+                        let consistent =
+                            filter::is_consistent(word, first, &score_guess(first, answer))
+                                && filter::is_consistent(
+                                    word,
+                                    second,
+                                    &score_guess(second, answer),
+                                );
+                        assert_eq!(
+                            consistent,
+                            c.matches(word),
+                            "answer {}, guesses {} & {}, word {}",
+                            answer,
+                            first,
+                            second,
+                            word
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_regex() {
+        assert_eq!("^.....$", ConstraintSet::new(5).to_regex());
+
+        let c = constraints("crane", &["trace"]);
+        assert_eq!("^(?=.*c)[^t]ra[^ct]e$", c.to_regex());
+
+        let c = constraints("hello", &["lolly"]);
+        assert_eq!("^(?!(?:.*l){3})(?=.*o)[^ly][^oy]ll[^y]$", c.to_regex());
+
+        let c = constraints("abbey", &["eerie"]);
+        assert_eq!(
+            "^(?=.*e)(?!(?:.*e){2})[^eir][^eir][^ir][^ir][^eir]$",
+            c.to_regex()
+        );
+
+        // the regex accepts exactly the words the constraints do
+        for answer in WORDS {
+            for guess in WORDS {
+                let c = constraints(answer, &[guess]);
+                let regex = fancy_regex::Regex::new(&c.to_regex()).unwrap();
+                for word in WORDS {
+                    assert_eq!(
+                        c.matches(word),
+                        regex.is_match(word).unwrap(),
+                        "{}",
+                        c.to_regex()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Daily puzzles: one answer per day, the same for everyone playing with the same answer list
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveTime, Offset, TimeZone};
+
+/// The date of puzzle number 0, the same day as the official game's first puzzle
+pub fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2021, 6, 19).expect("The epoch is a valid date")
+}
+
+/// The number of the puzzle for a date, or `None` for dates before the first puzzle
+pub fn puzzle_number(date: NaiveDate) -> Option<u32> {
+    let days = date.signed_duration_since(epoch()).num_days();
+    u32::try_from(days).ok()
+}
+
+/// The answer for a puzzle number.
+///
+/// Puzzles go through the answer list in order, starting over at the end, so
+/// the order of the list is the order of the puzzles (like the official list).
+pub fn answer<'a>(answers: &[&'a str], puzzle: u32) -> &'a str {
+    answers[index(puzzle, answers.len())]
+}
+
+/// Where a puzzle number's [answer] is in an answer list of `len` words
+pub fn index(puzzle: u32, len: usize) -> usize {
+    puzzle as usize % len
+}
+
+/// When the next puzzle comes out after `now`: the next midnight in `now`'s
+/// time zone.
+///
+/// Days with a daylight saving change aren't 24 hours long, so neither is the
+/// wait. If the clocks go forward at midnight, skipping it, the next puzzle
+/// comes out when they do.
+pub fn next_puzzle<Tz: TimeZone>(now: &DateTime<Tz>) -> DateTime<Tz> {
+    let timezone = now.timezone();
+    let midnight = now
+        .date_naive()
+        .succ_opt()
+        .expect("Dates this far in the future aren't supported")
+        .and_time(NaiveTime::MIN);
+    match timezone.from_local_datetime(&midnight) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => time,
+        // the clocks went forward when midnight came by the old offset
+        LocalResult::None => timezone.from_utc_datetime(&(midnight - now.offset().fix())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset, NaiveDateTime};
+
+    /// A time zone with daylight saving changes to test against, at UTC-3 until:
+    /// - 2018-11-04, when the clocks go forward from midnight to 01:00 (UTC-2)
+    /// - 2019-02-17, when they go back from midnight to 23:00 the day before (UTC-3)
+    /// - 2024-03-10, when they go forward from 02:00 to 03:00 (UTC-2)
+    #[derive(Debug, Clone, Copy)]
+    struct Zone;
+
+    fn hours(hours: i32) -> FixedOffset {
+        FixedOffset::east_opt(hours * 3600).unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, hour: u32) -> NaiveDateTime {
+        date(y, m, d).and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    /// The offset in effect at a time in UTC
+    fn offset_at(time: &NaiveDateTime) -> FixedOffset {
+        let changes = [
+            (utc(2018, 11, 4, 3), -2),
+            (utc(2019, 2, 17, 2), -3),
+            (utc(2024, 3, 10, 5), -2),
+        ];
+        let offset = changes
+            .iter()
+            .rfind(|(start, _)| start <= time)
+            .map_or(-3, |(_, offset)| *offset);
+        hours(offset)
+    }
+
+    impl TimeZone for Zone {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Zone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            // the earliest first, for when a time happens twice
+            let mut offsets = [hours(-2), hours(-3)]
+                .into_iter()
+                .filter(|offset| offset_at(&(*local - *offset)) == *offset);
+            match (offsets.next(), offsets.next()) {
+                (Some(earliest), Some(latest)) => LocalResult::Ambiguous(earliest, latest),
+                (Some(offset), None) => LocalResult::Single(offset),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            offset_at(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            offset_at(utc)
+        }
+    }
+
+    /// How long from a local time in [`Zone`] until the next puzzle
+    fn wait(y: i32, m: u32, d: u32, hour: u32) -> Duration {
+        let now = Zone.with_ymd_and_hms(y, m, d, hour, 0, 0).unwrap();
+        next_puzzle(&now) - now
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_puzzle_number() {
+        assert_eq!(None, puzzle_number(date(2021, 6, 18)));
+        assert_eq!(Some(0), puzzle_number(date(2021, 6, 19)));
+        assert_eq!(Some(1), puzzle_number(date(2021, 6, 20)));
+        // the official game's first puzzle of 2022 was also number 196
+        assert_eq!(Some(196), puzzle_number(date(2022, 1, 1)));
+    }
+
+    #[test]
+    fn test_answer() {
+        let answers = ["cigar", "rebut", "sissy"];
+        assert_eq!("cigar", answer(&answers, 0));
+        assert_eq!("sissy", answer(&answers, 2));
+        assert_eq!("rebut", answer(&answers, 4));
+        assert_eq!(1, index(4, answers.len()));
+    }
+
+    #[test]
+    fn test_next_puzzle() {
+        let now = chrono::Utc
+            .with_ymd_and_hms(2022, 1, 1, 16, 17, 47)
+            .unwrap();
+        let next = next_puzzle(&now);
+        assert_eq!(date(2022, 1, 2).and_time(NaiveTime::MIN), next.naive_utc());
+        assert_eq!(Duration::seconds(7 * 3600 + 42 * 60 + 13), next - now);
+
+        // an ordinary day in the test time zone
+        assert_eq!(Duration::hours(4), wait(2018, 10, 1, 20));
+        assert_eq!(
+            Zone.with_ymd_and_hms(2018, 10, 2, 0, 0, 0).unwrap(),
+            next_puzzle(&Zone.with_ymd_and_hms(2018, 10, 1, 20, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_next_puzzle_dst() {
+        // the clocks go forward at 02:00, so the day is an hour shorter
+        assert_eq!(Duration::hours(22), wait(2024, 3, 10, 1));
+        assert_eq!(Duration::hours(1), wait(2024, 3, 9, 23));
+        assert_eq!(Duration::hours(12), wait(2024, 3, 10, 12));
+
+        // the clocks go back at midnight, so 23:00 happens twice
+        assert_eq!(Duration::hours(5), wait(2019, 2, 16, 20));
+
+        // midnight never happens, so the next puzzle comes out at 01:00
+        let now = Zone.with_ymd_and_hms(2018, 11, 3, 20, 0, 0).unwrap();
+        let next = next_puzzle(&now);
+        assert_eq!(
+            date(2018, 11, 4).and_hms_opt(1, 0, 0).unwrap(),
+            next.naive_local()
+        );
+        assert_eq!(Duration::hours(4), next - now);
+    }
+}
@@ -0,0 +1,166 @@
+//! Short definitions of words, to show what the answer meant once a game is over
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use unicode_width::UnicodeWidthStr;
+
+/// The byte order mark some editors put at the start of UTF-8 files
+const BOM: char = '\u{feff}';
+
+/// The most columns a [line](Definitions::line) takes up, including the word
+const LINE_WIDTH: usize = 80;
+
+/// Definitions of words, read from text with a `word<TAB>definition` line for each.
+///
+/// Reading is forgiving, since definitions are only ever extra: lines without
+/// a tab or with nothing on either side of it are skipped, and so are words
+/// already defined on an earlier line.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Definitions {
+    /// The definition of each word, by its lowercase spelling
+    words: HashMap<String, String>,
+}
+
+impl Definitions {
+    /// Read definitions from text; a byte order mark and `\r\n` line endings are ignored
+    pub fn parse(text: &str) -> Self {
+        let mut words = HashMap::new();
+        for line in text.strip_prefix(BOM).unwrap_or(text).lines() {
+            let Some((word, definition)) = line.split_once('\t') else {
+                continue;
+            };
+            let (word, definition) = (word.trim(), definition.trim());
+            if !word.is_empty() && !definition.is_empty() {
+                words
+                    .entry(word.to_lowercase())
+                    .or_insert_with(|| definition.to_owned());
+            }
+        }
+        Self { words }
+    }
+
+    /// Read definitions from a file
+    pub fn load(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path).map(|text| Self::parse(&text))
+    }
+
+    /// The definition of a word, in any case, if there is one
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.words.get(&word.to_lowercase()).map(String::as_str)
+    }
+
+    /// A word and its definition on one short line, e.g. `CRANE: a large wading bird…`
+    ///
+    /// Definitions too long for the line are cut off at a space and end with `…`.
+    pub fn line(&self, word: &str) -> Option<String> {
+        let definition = self.get(word)?;
+        let word = word.to_uppercase();
+        let room = LINE_WIDTH.saturating_sub(word.width() + 2);
+        Some(format!("{}: {}", word, shorten(definition, room)))
+    }
+
+    /// The number of words with definitions
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether no words have definitions
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Cut text down to at most `width` columns, at a space if there is one, with `…` at the end
+fn shorten(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_owned();
+    }
+    let mut end = 0;
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        // leave a column for the ellipsis
+        used += c.to_string().width();
+        if used > width.saturating_sub(1) {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    let cut = &text[..end];
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut,
+    };
+    format!("{}…", cut.trim_end_matches([' ', ',', ';', '.']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let definitions = Definitions::parse(
+            "\u{feff}crane\ta large wading bird\r\n\
+             \n\
+             no tab here\n\
+             \tno word\n\
+             slate\t\n\
+             Trace\t a mark left behind \n\
+             crane\ta machine for lifting things\n",
+        );
+        assert_eq!(2, definitions.len());
+        assert_eq!(Some("a large wading bird"), definitions.get("crane"));
+        assert_eq!(Some("a mark left behind"), definitions.get("TRACE"));
+        assert_eq!(None, definitions.get("slate"));
+        assert!(Definitions::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_line() {
+        let definitions = Definitions::parse(
+            "crane\ta large wading bird\n\
+             irate\tfeeling or characterized by great anger, especially at something \
+             unfair, and not at all inclined to let it go\n",
+        );
+        assert_eq!(
+            Some(String::from("CRANE: a large wading bird")),
+            definitions.line("crane")
+        );
+        assert_eq!(
+            Some(String::from(
+                "IRATE: feeling or characterized by great anger, especially at something…"
+            )),
+            definitions.line("irate")
+        );
+        assert!(definitions.line("irate").unwrap().width() <= LINE_WIDTH);
+        assert_eq!(None, definitions.line("slate"));
+    }
+
+    #[test]
+    fn test_load() {
+        let path =
+            std::env::temp_dir().join(format!("wordle-defs-test-{}.tsv", std::process::id()));
+        fs::write(&path, "crane\ta large wading bird\n").unwrap();
+        assert_eq!(
+            Some("a large wading bird"),
+            Definitions::load(&path).unwrap().get("crane")
+        );
+        fs::remove_file(&path).unwrap();
+        assert!(Definitions::load(&path).is_err());
+    }
+}
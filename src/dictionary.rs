@@ -0,0 +1,307 @@
+//! Looking up acceptable guesses, in a hash set or (with the `fst` feature) a
+//! finite state transducer
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#[cfg(feature = "fst")]
+use std::{borrow::Cow, error::Error, fs, io, path::Path};
+
+#[cfg(feature = "mmap")]
+pub mod packed;
+use std::{collections::HashSet, fmt};
+
+/// A list of acceptable guesses, along with an index to look them up in.
+///
+/// By default the index is a hash set of the words. With the `fst` feature it
+/// can instead be a finite state transducer mapping each word to its place in
+/// the list, which takes a fraction of the memory for very large lists; see
+/// [`Dictionary::fst`].
+///
+/// ```
+/// use wordle::dictionary::Dictionary;
+///
+/// let guesses = ["crane", "slate", "trace"];
+/// let dictionary = Dictionary::new(&guesses);
+/// assert!(dictionary.contains_guess("slate"));
+/// assert!(!dictionary.contains_guess("sla"));
+/// ```
+#[derive(Clone)]
+pub struct Dictionary<'a> {
+    /// The words, in their original order
+    list: &'a [&'a str],
+    /// How the words are looked up
+    index: Index<'a>,
+}
+
+/// How a [`Dictionary`]'s words are looked up
+#[derive(Clone)]
+enum Index<'a> {
+    /// A hash set of the words
+    Hash(HashSet<&'a str>),
+    /// A transducer from each word to its place in the list
+    #[cfg(feature = "fst")]
+    Fst(fst::Map<Cow<'a, [u8]>>),
+}
+
+impl<'a> Dictionary<'a> {
+    /// Look up the words in `list` in a hash set
+    pub fn new(list: &'a [&'a str]) -> Self {
+        Self {
+            list,
+            index: Index::Hash(list.iter().copied().collect()),
+        }
+    }
+
+    /// Look up the words in `list` in a transducer built from them
+    #[cfg(feature = "fst")]
+    pub fn fst(list: &'a [&'a str]) -> Self {
+        let map =
+            fst::Map::new(Cow::Owned(build_fst(list))).expect("The transducer was just built");
+        Self {
+            list,
+            index: Index::Fst(map),
+        }
+    }
+
+    /// Look up the words in `list` in a transducer built from them earlier
+    /// with [`build_fst`].
+    ///
+    /// Every word must map to its place in `list`, so a transducer built from
+    /// another list (or an older version of this one) is rejected.
+    #[cfg(feature = "fst")]
+    pub fn from_fst(
+        list: &'a [&'a str],
+        bytes: impl Into<Cow<'a, [u8]>>,
+    ) -> Result<Self, FstError> {
+        let dictionary = Self::from_fst_unchecked(list, bytes)?;
+
+        // every word in the transducer is in the list, and every word in the
+        // list is found, so they hold the same words
+        let Index::Fst(map) = &dictionary.index else {
+            unreachable!()
+        };
+        let mut stream = map.stream();
+        while let Some((key, i)) = fst::Streamer::next(&mut stream) {
+            let word = usize::try_from(i).ok().and_then(|i| list.get(i));
+            if word.map(|word| word.as_bytes()) != Some(key) {
+                return Err(FstError::Mismatch {
+                    word: String::from_utf8_lossy(key).into_owned(),
+                });
+            }
+        }
+        if let Some(word) = list.iter().find(|word| !dictionary.contains_guess(word)) {
+            return Err(FstError::Mismatch {
+                word: word.to_string(),
+            });
+        }
+        Ok(dictionary)
+    }
+
+    /// Look up the words in `list` in a transducer that's known to have been
+    /// built from them
+    #[cfg(feature = "fst")]
+    pub(crate) fn from_fst_unchecked(
+        list: &'a [&'a str],
+        bytes: impl Into<Cow<'a, [u8]>>,
+    ) -> Result<Self, FstError> {
+        let map = fst::Map::new(bytes.into()).map_err(FstError::Invalid)?;
+        Ok(Self {
+            list,
+            index: Index::Fst(map),
+        })
+    }
+
+    /// Read a transducer written by `wordle-dict build-fst` for `list`; see
+    /// [`Dictionary::from_fst`]
+    #[cfg(feature = "fst")]
+    pub fn load_fst(list: &'a [&'a str], path: &Path) -> Result<Self, FstError> {
+        Self::from_fst(list, fs::read(path).map_err(FstError::Io)?)
+    }
+
+    /// The acceptable guess matching `word`, if it is one
+    pub fn get(&self, word: &str) -> Option<&'a str> {
+        match &self.index {
+            Index::Hash(words) => words.get(word).copied(),
+            #[cfg(feature = "fst")]
+            Index::Fst(map) => {
+                let word = self.list.get(usize::try_from(map.get(word)?).ok()?)?;
+                Some(word)
+            }
+        }
+    }
+
+    /// Whether `word` is an acceptable guess
+    pub fn contains_guess(&self, word: &str) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// The words, in their original order
+    pub fn list(&self) -> &'a [&'a str] {
+        self.list
+    }
+
+    /// The number of words in the list
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Whether the list has no words
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Roughly how many bytes the index takes up, not counting the words
+    /// themselves
+    pub fn index_size(&self) -> usize {
+        match &self.index {
+            // a pointer and length for each slot, plus a control byte
+            Index::Hash(words) => words.capacity() * (std::mem::size_of::<&str>() + 1),
+            #[cfg(feature = "fst")]
+            Index::Fst(map) => map.as_fst().size(),
+        }
+    }
+}
+
+impl fmt::Debug for Dictionary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the words themselves would be far too many to show
+        let index = match &self.index {
+            Index::Hash(_) => "hash",
+            #[cfg(feature = "fst")]
+            Index::Fst(_) => "fst",
+        };
+        f.debug_struct("Dictionary")
+            .field("words", &self.list.len())
+            .field("index", &index)
+            .finish()
+    }
+}
+
+/// Build a transducer mapping each word in `list` to its place in the list,
+/// to save and look words up in later with [`Dictionary::from_fst`].
+///
+/// A word listed more than once maps to its first place.
+#[cfg(feature = "fst")]
+pub fn build_fst(list: &[&str]) -> Vec<u8> {
+    // the words have to go in in order, and only once each
+    let mut words: Vec<(&str, usize)> = list.iter().copied().zip(0..).collect();
+    words.sort_unstable();
+    words.dedup_by_key(|(word, _)| *word);
+
+    let mut builder = fst::MapBuilder::memory();
+    for (word, i) in words {
+        builder
+            .insert(word, i as u64)
+            .expect("The words are sorted and unique");
+    }
+    builder.into_inner().expect("Building in memory can't fail")
+}
+
+/// Why a transducer couldn't be used for a [`Dictionary`]
+#[cfg(feature = "fst")]
+#[derive(Debug)]
+pub enum FstError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The bytes aren't a transducer
+    Invalid(fst::Error),
+    /// The transducer wasn't built from the word list
+    Mismatch {
+        /// A word in one but not the other
+        word: String,
+    },
+}
+
+#[cfg(feature = "fst")]
+impl fmt::Display for FstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Invalid(e) => write!(f, "not a valid transducer: {}", e),
+            Self::Mismatch { word } => write!(
+                f,
+                "the transducer wasn't built from this word list ('{}' is in only one of them)",
+                word
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fst")]
+impl Error for FstError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Invalid(e) => Some(e),
+            Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 4] = ["trace", "crane", "slate", "crane"];
+
+    /// Check that `dictionary` has just the words in `WORDS`
+    fn check(dictionary: &Dictionary<'_>) {
+        assert_eq!(4, dictionary.len());
+        for word in WORDS {
+            assert_eq!(Some(word), dictionary.get(word));
+            assert!(dictionary.contains_guess(word));
+        }
+        for word in ["", "cran", "cranes", "CRANE", "zzzzz"] {
+            assert_eq!(None, dictionary.get(word));
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        check(&Dictionary::new(&WORDS));
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn test_fst() {
+        let dictionary = Dictionary::fst(&WORDS);
+        check(&dictionary);
+        // the word comes back from the list, at its first place
+        assert!(std::ptr::eq(WORDS[1], dictionary.get("crane").unwrap()));
+        check(&dictionary.clone());
+
+        let bytes = build_fst(&WORDS);
+        check(&Dictionary::from_fst(&WORDS, bytes.as_slice()).unwrap());
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn test_fst_errors() {
+        assert!(matches!(
+            Dictionary::from_fst(&WORDS, &b"not a transducer"[..]),
+            Err(FstError::Invalid(_))
+        ));
+
+        // a transducer for another list
+        let bytes = build_fst(&["crane", "slate", "trace"]);
+        let error = Dictionary::from_fst(&WORDS, bytes.clone()).unwrap_err();
+        assert!(matches!(error, FstError::Mismatch { ref word } if word == "crane"));
+        let error = Dictionary::from_fst(&WORDS[..2], build_fst(&WORDS)).unwrap_err();
+        assert!(matches!(error, FstError::Mismatch { ref word } if word == "slate"));
+        let error = Dictionary::from_fst(&["crane", "slate", "trace", "spare"], bytes).unwrap_err();
+        assert!(matches!(error, FstError::Mismatch { ref word } if word == "spare"));
+    }
+}
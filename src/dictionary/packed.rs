@@ -0,0 +1,443 @@
+//! A word list packed into one file, which is mapped into memory to open it
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt, fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use super::Dictionary;
+#[cfg(feature = "fst")]
+use super::FstError;
+
+/// The bytes every packed file starts with
+const MAGIC: &[u8; 8] = b"WORDLEPK";
+/// The version of the format written by [`pack`]
+const VERSION: u32 = 1;
+/// The length of the header, before the words
+const HEADER: usize = 40;
+
+// The header is, with every number little-endian:
+//
+//   0  magic          8 bytes
+//   8  version        u32
+//  12  word length    u32, in letters
+//  16  slot width     u32, in bytes; shorter words are padded with zeroes
+//  20  checksum       u32, the CRC-32 of everything after the header
+//  24  word count     u64
+//  32  fst length     u64, or 0 if there's no transducer
+//
+// followed by a slot for each word, then the transducer, if there is one.
+
+/// Why a word list couldn't be packed, or a packed file couldn't be opened
+#[derive(Debug)]
+pub enum PackError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The file doesn't start with the magic number, so it isn't a packed word list
+    NotPacked,
+    /// The file was packed by a newer version of the format
+    UnsupportedVersion(u32),
+    /// The file is longer or shorter than its header says, e.g. because it was cut off
+    WrongSize {
+        /// The length the header gives
+        expected: u64,
+        /// The length of the file
+        actual: u64,
+    },
+    /// The words don't match the checksum, so the file was corrupted
+    ChecksumMismatch {
+        /// The checksum in the header
+        expected: u32,
+        /// The checksum of the words
+        actual: u32,
+    },
+    /// A word isn't valid UTF-8, or doesn't have the right number of letters
+    InvalidWord {
+        /// The place of the word in the list
+        index: usize,
+    },
+    /// The transducer in the file can't be used for the words
+    #[cfg(feature = "fst")]
+    Fst(FstError),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::NotPacked => write!(f, "not a packed word list (wrong magic number)"),
+            Self::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "packed with format version {}, which isn't supported",
+                    version
+                )
+            }
+            Self::WrongSize { expected, actual } => write!(
+                f,
+                "the file should be {} bytes long, but is {}; it may have been cut off",
+                expected, actual
+            ),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "the checksum is {:08x}, but the words add up to {:08x}; the file is corrupt",
+                expected, actual
+            ),
+            Self::InvalidWord { index } => write!(f, "word {} is invalid", index + 1),
+            #[cfg(feature = "fst")]
+            Self::Fst(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            #[cfg(feature = "fst")]
+            Self::Fst(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Pack a word list, for [`Dictionary::open_packed`] to open.
+///
+/// Every word must have `word_length` letters.
+pub fn pack(words: &[&str], word_length: usize) -> Result<Vec<u8>, PackError> {
+    write(words, word_length, &[])
+}
+
+/// Pack a word list along with a transducer to look its words up in, for
+/// [`Dictionary::open_packed`] to open.
+///
+/// Every word must have `word_length` letters.
+#[cfg(feature = "fst")]
+pub fn pack_with_fst(words: &[&str], word_length: usize) -> Result<Vec<u8>, PackError> {
+    write(words, word_length, &super::build_fst(words))
+}
+
+/// Pack a word list, followed by the bytes of a transducer (if any)
+fn write(words: &[&str], word_length: usize, fst: &[u8]) -> Result<Vec<u8>, PackError> {
+    if let Some(index) = words
+        .iter()
+        .position(|word| word.chars().count() != word_length || word.contains('\0'))
+    {
+        return Err(PackError::InvalidWord { index });
+    }
+    let width = words.iter().map(|word| word.len()).max().unwrap_or(0);
+
+    let mut bytes = Vec::with_capacity(HEADER + words.len() * width + fst.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(word_length as u32).to_le_bytes());
+    bytes.extend_from_slice(&(width as u32).to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&(words.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&(fst.len() as u64).to_le_bytes());
+    for word in words {
+        bytes.extend_from_slice(word.as_bytes());
+        bytes.resize(bytes.len() + width - word.len(), 0);
+    }
+    bytes.extend_from_slice(fst);
+
+    let checksum = crc32fast::hash(&bytes[HEADER..]);
+    bytes[20..24].copy_from_slice(&checksum.to_le_bytes());
+    Ok(bytes)
+}
+
+/// A packed word list, mapped into memory.
+///
+/// Get one from [`Dictionary::open_packed`]; the words are read straight out of
+/// the mapping, without copying them.
+#[derive(Debug)]
+pub struct Packed {
+    /// The whole file
+    map: Mmap,
+    /// The number of letters in each word
+    word_length: usize,
+    /// The number of bytes in each word's slot
+    width: usize,
+    /// The number of words
+    count: usize,
+    /// The number of bytes in the transducer, or 0 if there isn't one
+    fst_len: usize,
+}
+
+impl Dictionary<'_> {
+    /// Open a word list packed by [`pack`] (or `wordle-dict pack`), mapping it
+    /// into memory rather than reading it.
+    ///
+    /// The whole file is checked against its checksum, so a corrupt or
+    /// truncated file is an error rather than a list of garbage words.
+    ///
+    /// ```no_run
+    /// use wordle::{dictionary::Dictionary, Wordle};
+    ///
+    /// let packed = Dictionary::open_packed("guesses.pack".as_ref()).unwrap();
+    /// let words = packed.words();
+    /// let game = Wordle::builder()
+    ///     .dictionary(packed.dictionary(&words).unwrap())
+    ///     .answers(&["crane"])
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn open_packed(path: &Path) -> Result<Packed, PackError> {
+        let file = File::open(path).map_err(PackError::Io)?;
+        // SAFETY: the mapping is only ever read, and the file isn't expected
+        // to change while it's open; every word is checked before it's used
+        let map = unsafe { Mmap::map(&file) }.map_err(PackError::Io)?;
+        Packed::new(map)
+    }
+}
+
+impl Packed {
+    /// Check the header and contents of a mapped file
+    fn new(map: Mmap) -> Result<Self, PackError> {
+        if map.len() < HEADER || &map[..8] != MAGIC {
+            return Err(PackError::NotPacked);
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(map[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(map[at..at + 8].try_into().unwrap());
+        let version = u32_at(8);
+        if version != VERSION {
+            return Err(PackError::UnsupportedVersion(version));
+        }
+        let (word_length, width, checksum) = (u32_at(12), u32_at(16), u32_at(20));
+        let (count, fst_len) = (u64_at(24), u64_at(32));
+
+        // sizes that overflow can't match the file's anyway
+        let expected = count
+            .checked_mul(width.into())
+            .and_then(|words| words.checked_add(fst_len))
+            .and_then(|body| body.checked_add(HEADER as u64))
+            .unwrap_or(u64::MAX);
+        let actual = map.len() as u64;
+        if expected != actual {
+            return Err(PackError::WrongSize { expected, actual });
+        }
+        let actual = crc32fast::hash(&map[HEADER..]);
+        if actual != checksum {
+            return Err(PackError::ChecksumMismatch {
+                expected: checksum,
+                actual,
+            });
+        }
+
+        let packed = Self {
+            word_length: word_length as usize,
+            width: width as usize,
+            count: count as usize,
+            fst_len: fst_len as usize,
+            map,
+        };
+        if let Some(index) = (0..packed.count).find(|&i| packed.word(i).is_none()) {
+            return Err(PackError::InvalidWord { index });
+        }
+        Ok(packed)
+    }
+
+    /// The `i`th word, if it's valid
+    fn word(&self, i: usize) -> Option<&str> {
+        let start = HEADER + i * self.width;
+        let slot = &self.map[start..start + self.width];
+        let end = slot.iter().position(|&b| b == 0).unwrap_or(self.width);
+        if slot[end..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        let word = std::str::from_utf8(&slot[..end]).ok()?;
+        (word.chars().count() == self.word_length).then_some(word)
+    }
+
+    /// The number of letters in each word
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    /// The number of words
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether there are no words
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Whether the file has a transducer to look the words up in
+    pub fn has_fst(&self) -> bool {
+        self.fst_len > 0
+    }
+
+    /// The words, in their original order, borrowed from the mapping
+    pub fn words(&self) -> Vec<&str> {
+        (0..self.count)
+            .map(|i| self.word(i).expect("The words were checked when opened"))
+            .collect()
+    }
+
+    /// A dictionary of `words`, which should be this file's [`Packed::words`].
+    ///
+    /// It looks the words up in the file's transducer if it has one (and the
+    /// `fst` feature is on), or hashes them otherwise.
+    pub fn dictionary<'a>(&'a self, words: &'a [&'a str]) -> Result<Dictionary<'a>, PackError> {
+        #[cfg(feature = "fst")]
+        if self.has_fst() {
+            let fst = &self.map[self.map.len() - self.fst_len..];
+            // the checksum vouches for the transducer matching the file's own
+            // words, so it only needs checking against any others
+            let dictionary = if self.owns(words) {
+                Dictionary::from_fst_unchecked(words, fst)
+            } else {
+                Dictionary::from_fst(words, fst)
+            };
+            return dictionary.map_err(PackError::Fst);
+        }
+        Ok(Dictionary::new(words))
+    }
+
+    /// Whether `words` are the words in the mapping itself
+    #[cfg(feature = "fst")]
+    fn owns(&self, words: &[&str]) -> bool {
+        words.len() == self.count
+            && words
+                .iter()
+                .enumerate()
+                .all(|(i, word)| std::ptr::eq(word.as_ptr(), &self.map[HEADER + i * self.width]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::PathBuf};
+
+    const WORDS: [&str; 4] = ["crane", "slate", "trace", "ñandú"];
+
+    /// Write `bytes` to a file of their own for a test, and get back its path
+    fn file(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "wordle-packed-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    fn open(name: &str, bytes: &[u8]) -> Result<Packed, PackError> {
+        let path = file(name, bytes);
+        let packed = Dictionary::open_packed(&path);
+        fs::remove_file(path).unwrap();
+        packed
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let packed = open("round-trip", &pack(&WORDS, 5).unwrap()).unwrap();
+        assert_eq!(5, packed.word_length());
+        assert_eq!(4, packed.len());
+        assert!(!packed.has_fst());
+        let words = packed.words();
+        assert_eq!(WORDS.to_vec(), words);
+
+        let dictionary = packed.dictionary(&words).unwrap();
+        assert!(dictionary.contains_guess("ñandú"));
+        assert!(!dictionary.contains_guess("ñand"));
+
+        let packed = open("empty", &pack(&[], 5).unwrap()).unwrap();
+        assert!(packed.is_empty());
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn test_round_trip_fst() {
+        let packed = open("round-trip-fst", &pack_with_fst(&WORDS, 5).unwrap()).unwrap();
+        assert!(packed.has_fst());
+        let words = packed.words();
+        let dictionary = packed.dictionary(&words).unwrap();
+        assert_eq!(Some("trace"), dictionary.get("trace"));
+        assert!(matches!(
+            packed.dictionary(&words[1..]),
+            Err(PackError::Fst(FstError::Mismatch { .. }))
+        ));
+        // copies of the words aren't the file's own, so they're checked
+        let copies = WORDS.to_vec();
+        assert!(packed.dictionary(&copies).is_ok());
+        let copies = ["crane", "slate", "trace", "spare"];
+        assert!(packed.dictionary(&copies).is_err());
+    }
+
+    #[test]
+    fn test_pack_errors() {
+        assert!(matches!(
+            pack(&["crane", "cranes"], 5),
+            Err(PackError::InvalidWord { index: 1 })
+        ));
+        assert!(matches!(
+            pack(&["cr\0ne"], 5),
+            Err(PackError::InvalidWord { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let bytes = pack(&WORDS, 5).unwrap();
+
+        let error = open("text", b"crane\nslate\n").unwrap_err();
+        assert!(matches!(error, PackError::NotPacked));
+        let error = open("short", &bytes[..10]).unwrap_err();
+        assert!(matches!(error, PackError::NotPacked));
+
+        let mut newer = bytes.clone();
+        newer[8] = 2;
+        let error = open("newer", &newer).unwrap_err();
+        assert!(matches!(error, PackError::UnsupportedVersion(2)));
+
+        let error = open("truncated", &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(
+            "the file should be 68 bytes long, but is 67; it may have been cut off",
+            error.to_string()
+        );
+        let mut longer = bytes.clone();
+        longer.push(0);
+        let error = open("longer", &longer).unwrap_err();
+        assert!(matches!(
+            error,
+            PackError::WrongSize {
+                expected: 68,
+                actual: 69
+            }
+        ));
+
+        let mut flipped = bytes.clone();
+        flipped[HEADER + 1] ^= 1;
+        let error = open("flipped", &flipped).unwrap_err();
+        assert!(matches!(error, PackError::ChecksumMismatch { .. }));
+
+        // a file that was written wrongly, but checksummed afterwards
+        let mut invalid = bytes.clone();
+        invalid[HEADER + 8] = 0xff;
+        let checksum = crc32fast::hash(&invalid[HEADER..]);
+        invalid[20..24].copy_from_slice(&checksum.to_le_bytes());
+        let error = open("invalid", &invalid).unwrap_err();
+        assert!(matches!(error, PackError::InvalidWord { index: 1 }));
+
+        let error = Dictionary::open_packed(Path::new("no-such-file.pack")).unwrap_err();
+        assert!(matches!(error, PackError::Io(_)));
+    }
+}
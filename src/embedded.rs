@@ -0,0 +1,154 @@
+//! Word lists built into the crate, for when no word list files are available.
+//!
+//! The lists are kept gzip-compressed in the binary, and decompressed the first
+//! time they're needed: the guess list takes 4,815 bytes rather than 10,890,
+//! and the answer list 1,515 rather than 2,844, for a saving of about 7 KB.
+//! After changing a list in `wordlists/`, compress it again with
+//! `gzip -9 -n -k -f wordlists/<list>.txt`; a test checks they match.
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt, io::BufReader, sync::OnceLock};
+
+use flate2::read::GzDecoder;
+
+use crate::wordlist::{self, Limits, Words};
+
+/// The built-in acceptable guesses, one per line, gzip-compressed
+const GUESSES: &[u8] = include_bytes!("../wordlists/guesses.txt.gz");
+/// The built-in answers, one per line in daily puzzle order, gzip-compressed
+const ANSWERS: &[u8] = include_bytes!("../wordlists/answers.txt.gz");
+
+/// The lists, once they've been decompressed
+type Lists = (&'static [&'static str], &'static [&'static str]);
+
+/// Why the built-in word lists couldn't be decompressed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EmbeddedError {
+    /// Which list it was
+    list: &'static str,
+    /// What was wrong with it
+    reason: String,
+}
+
+impl fmt::Display for EmbeddedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the built-in {} list is corrupt: {}",
+            self.list, self.reason
+        )
+    }
+}
+
+impl Error for EmbeddedError {}
+
+/// Decompress one of the built-in lists
+fn decompress(list: &'static str, bytes: &[u8]) -> Result<Words, EmbeddedError> {
+    wordlist::read(BufReader::new(GzDecoder::new(bytes)), &Limits::default()).map_err(|e| {
+        EmbeddedError {
+            list,
+            reason: e.to_string(),
+        }
+    })
+}
+
+/// The built-in lists of acceptable guesses and answers, decompressed the
+/// first time they're asked for
+pub fn lists() -> Result<Lists, EmbeddedError> {
+    static LISTS: OnceLock<Result<Lists, EmbeddedError>> = OnceLock::new();
+    LISTS
+        .get_or_init(|| {
+            let guesses = decompress("guess", GUESSES)?;
+            let answers = decompress("answer", ANSWERS)?;
+            Ok((guesses.leak(), answers.leak()))
+        })
+        .clone()
+}
+
+/// The built-in list of acceptable guesses, all with [`WORD_LENGTH`](crate::WORD_LENGTH) letters
+///
+/// # Panics
+/// If the built-in lists are corrupt, which the tests rule out; see [`lists`]
+/// to handle that instead.
+pub fn guesses() -> Vec<&'static str> {
+    lists().unwrap_or_else(|e| panic!("{}", e)).0.to_vec()
+}
+
+/// The built-in list of answers, all of which are acceptable guesses
+///
+/// # Panics
+/// If the built-in lists are corrupt, which the tests rule out; see [`lists`]
+/// to handle that instead.
+pub fn answers() -> Vec<&'static str> {
+    lists().unwrap_or_else(|e| panic!("{}", e)).1.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wordlist::LoadError, Wordle};
+
+    #[test]
+    fn test_lists() {
+        let guesses = guesses();
+        let answers = answers();
+        assert!(answers.len() > 365, "at least a year of daily puzzles");
+        assert!(guesses.len() > answers.len());
+
+        // the builder checks the lengths, and that every answer can be guessed
+        Wordle::builder()
+            .guesses(&guesses)
+            .answers(&answers)
+            .build()
+            .unwrap();
+        assert!(guesses
+            .iter()
+            .all(|word| word.chars().all(|c| c.is_ascii_lowercase())));
+
+        let game = Wordle::with_default_lists().unwrap().choose_word();
+        assert!(answers.contains(&game.answer()));
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        // the compressed lists are the plain text ones, word for word
+        let (guesses, answers) = lists().unwrap();
+        let plain = include_str!("../wordlists/guesses.txt");
+        assert_eq!(plain.lines().collect::<Vec<_>>(), guesses);
+        let plain = include_str!("../wordlists/answers.txt");
+        assert_eq!(plain.lines().collect::<Vec<_>>(), answers);
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let e = decompress("guess", &GUESSES[..100]).unwrap_err();
+        assert!(
+            e.to_string()
+                .starts_with("the built-in guess list is corrupt: "),
+            "{}",
+            e
+        );
+        assert!(decompress("answer", b"crane\nslate\n").is_err());
+
+        // which is how loading the word lists reports it, rather than panicking
+        let e = LoadError::Embedded(e);
+        assert_eq!(None, e.path());
+        assert!(e
+            .to_string()
+            .starts_with("the built-in guess list is corrupt: "));
+    }
+}
@@ -0,0 +1,74 @@
+//! Narrowing down the possible answers using the feedback from guesses
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{score_guess, LetterStatus};
+
+/// Check whether `word` could be the answer, given that guessing `guess` produced `statuses`.
+///
+/// This re-scores `guess` as if `word` were the answer, so it agrees with
+/// [`Wordle::guess`](crate::Wordle::guess) on every duplicate-letter edge case.
+pub fn is_consistent(word: &str, guess: &str, statuses: &[LetterStatus]) -> bool {
+    score_guess(guess, word) == statuses
+}
+
+/// Keep only the candidates that are consistent with the feedback from a guess
+pub fn filter_candidates<'a>(
+    candidates: &[&'a str],
+    guess: &str,
+    statuses: &[LetterStatus],
+) -> Vec<&'a str> {
+    candidates
+        .iter()
+        .copied()
+        .filter(|word| is_consistent(word, guess, statuses))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterStatus::*;
+
+    #[test]
+    fn test_filter_candidates() {
+        let candidates = ["hello", "world", "jolly", "lolly", "holly"];
+
+        // all gray
+        assert_eq!(
+            vec!["hello", "jolly", "lolly", "holly"],
+            filter_candidates(&candidates, "twirp", &[NotInWord; 5])
+        );
+
+        // the answer itself is always consistent with its own all-green feedback
+        assert_eq!(
+            vec!["world"],
+            filter_candidates(&candidates, "world", &[Correct; 5])
+        );
+
+        // duplicate letters: one 'l' yellow, one green, one gray means exactly
+        // two 'l's in the answer
+        let statuses = score_guess("lolly", "hello");
+        assert_eq!(
+            vec![NotInWord, InWord, Correct, Correct, NotInWord],
+            statuses
+        );
+        assert_eq!(
+            vec!["hello"],
+            filter_candidates(&candidates, "lolly", &statuses)
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! The word lists, compiled in as `static` arrays by the build script.
+//!
+//! With the `codegen-wordlists` feature, the lists named by the
+//! `WORDLE_GUESSES` and `WORDLE_ANSWERS` environment variables at build time
+//! (or the built-in ones, if they aren't set) are checked the way
+//! `wordle-dict check` does, then turned into arrays of bytes, so there's
+//! nothing to parse when the program starts. A problem with a list fails
+//! the build, naming the file and line.
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::WORD_LENGTH;
+
+include!(concat!(env!("OUT_DIR"), "/words_gen.rs"));
+
+/// A generated word, as a string
+pub fn word(bytes: &[u8; WORD_LENGTH]) -> &str {
+    std::str::from_utf8(bytes).expect("The build script only allows ASCII")
+}
+
+/// A generated list, as strings to play with
+pub fn words(list: &[[u8; WORD_LENGTH]]) -> Vec<&str> {
+    list.iter().map(word).collect()
+}
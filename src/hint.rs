@@ -0,0 +1,138 @@
+//! Hints that reveal part of the answer, for when a player is stuck
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{constraints::ConstraintSet, messages::Messages};
+
+/// Something revealed about the answer
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    /// A letter that's in the word
+    Present {
+        /// The letter
+        letter: char,
+    },
+    /// The letter at a (zero-based) position
+    Position {
+        /// The letter
+        letter: char,
+        /// Where the letter is in the word
+        position: usize,
+    },
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Messages::english().hint(self))
+    }
+}
+
+/// Why [`Wordle::hint`](crate::Wordle::hint) couldn't give a hint
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HintError {
+    /// Every hint allowed for this word has been used
+    NoHintsLeft,
+    /// Everything about the word is already known
+    NothingToReveal,
+    /// The word has already been found, or there are no guesses left
+    GameOver,
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Messages::english().hint_error(self))
+    }
+}
+
+impl Error for HintError {}
+
+/// Find something about the answer that isn't known from the guesses or earlier hints.
+///
+/// Letters in the word that haven't been found yet are revealed first, in the
+/// order they appear in the word; once every letter is known, the positions
+/// that haven't been found are revealed, left to right.
+pub(crate) fn next_hint(answer: &str, known: &ConstraintSet, given: &[Hint]) -> Option<Hint> {
+    let letter_known = |letter: char| {
+        known.required().any(|(l, _)| l == letter)
+            || given.iter().any(|hint| match hint {
+                Hint::Present { letter: l } | Hint::Position { letter: l, .. } => *l == letter,
+            })
+    };
+    let position_known = |position: usize| {
+        known.fixed()[position].is_some()
+            || given
+                .iter()
+                .any(|hint| matches!(hint, Hint::Position { position: p, .. } if *p == position))
+    };
+
+    let letters: Vec<char> = answer.chars().collect();
+    if let Some(&letter) = letters.iter().find(|&&letter| !letter_known(letter)) {
+        return Some(Hint::Present { letter });
+    }
+    (0..letters.len())
+        .find(|&position| !position_known(position))
+        .map(|position| Hint::Position {
+            letter: letters[position],
+            position,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_guess;
+
+    #[test]
+    fn test_next_hint() {
+        let history = [("trace", score_guess("trace", "crane"))];
+        let known = ConstraintSet::from_history(5, &history);
+
+        // c, r, a, and e are known to be in the word, but n isn't
+        let hint = next_hint("crane", &known, &[]).unwrap();
+        assert_eq!(Hint::Present { letter: 'n' }, hint);
+        assert_eq!("The word contains N", hint.to_string());
+
+        // then the unknown positions: r, a, and e are already green
+        let hint = next_hint("crane", &known, &[hint]).unwrap();
+        assert_eq!(
+            Hint::Position {
+                letter: 'c',
+                position: 0
+            },
+            hint
+        );
+        assert_eq!("The 1st letter is C", hint.to_string());
+        let given = [Hint::Present { letter: 'n' }, hint];
+        let hint = next_hint("crane", &known, &given).unwrap();
+        assert_eq!(
+            Hint::Position {
+                letter: 'n',
+                position: 3
+            },
+            hint
+        );
+
+        let given = [given[0], given[1], hint];
+        assert_eq!(None, next_hint("crane", &known, &given));
+    }
+}
@@ -0,0 +1,113 @@
+//! The best-known status of each letter, like the keyboard under the official game's board
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use crate::LetterStatus;
+
+/// The rows of a QWERTY keyboard
+pub const QWERTY: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// The best status each letter has had in any guess.
+///
+/// A letter that was green anywhere is green; otherwise a letter that was yellow
+/// anywhere is yellow; a letter that was only ever gray is gray. Letters that
+/// haven't been guessed have no status.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct KeyboardState {
+    /// The best status of each guessed letter
+    letters: BTreeMap<char, LetterStatus>,
+}
+
+impl KeyboardState {
+    /// Start with no letters guessed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the state for a sequence of guesses and their feedback
+    pub fn from_history<S: AsRef<str>>(history: &[(S, Vec<LetterStatus>)]) -> Self {
+        let mut state = Self::new();
+        for (guess, statuses) in history {
+            state.add(guess.as_ref(), statuses);
+        }
+        state
+    }
+
+    /// Add the feedback for a guess
+    pub fn add(&mut self, guess: &str, statuses: &[LetterStatus]) {
+        for (letter, &status) in guess.chars().zip(statuses) {
+            let best = self.letters.entry(letter).or_insert(status);
+            if rank(status) > rank(*best) {
+                *best = status;
+            }
+        }
+    }
+
+    /// The best-known status of a letter, or `None` if it hasn't been guessed
+    pub fn get(&self, letter: char) -> Option<LetterStatus> {
+        self.letters.get(&letter).copied()
+    }
+
+    /// The guessed letters with a given status, in alphabetical order
+    pub fn letters_with(&self, status: LetterStatus) -> impl Iterator<Item = char> + '_ {
+        self.letters
+            .iter()
+            .filter(move |(_, &s)| s == status)
+            .map(|(&letter, _)| letter)
+    }
+}
+
+/// How much a status reveals; higher wins
+fn rank(status: LetterStatus) -> u8 {
+    match status {
+        LetterStatus::NotInWord => 0,
+        LetterStatus::InWord => 1,
+        LetterStatus::Correct => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_guess;
+    use LetterStatus::*;
+
+    #[test]
+    fn test_best_status() {
+        let history: Vec<(&str, Vec<LetterStatus>)> = ["lolly", "hello"]
+            .into_iter()
+            .map(|guess| (guess, score_guess(guess, "hello")))
+            .collect();
+
+        let state = KeyboardState::from_history(&history[..1]);
+        // one gray, one yellow and two green 'l's
+        assert_eq!(Some(Correct), state.get('l'));
+        assert_eq!(Some(InWord), state.get('o'));
+        assert_eq!(Some(NotInWord), state.get('y'));
+        assert_eq!(None, state.get('h'));
+
+        // a yellow letter turns green once it's found in place
+        let state = KeyboardState::from_history(&history);
+        assert_eq!(Some(Correct), state.get('o'));
+        assert_eq!(
+            vec!['e', 'h', 'l', 'o'],
+            state.letters_with(Correct).collect::<Vec<_>>()
+        );
+        assert_eq!(vec!['y'], state.letters_with(NotInWord).collect::<Vec<_>>());
+    }
+}
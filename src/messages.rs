@@ -0,0 +1,466 @@
+//! The text shown to players, in English or another language, from Fluent catalogs
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! Each message has an id, and any arguments it needs are passed by name, so
+//! a translation can put them wherever its grammar wants them:
+//!
+//! ```ftl
+//! rejection-already-guessed = vous avez déjà essayé { $guess } à l'essai { $turn }
+//! guesses-left = { $count ->
+//!         [one] Plus qu'un essai.
+//!        *[other] Plus que { $count } essais.
+//!     }
+//! ```
+//!
+//! Besides the [built-in locales](Messages::builtin), a catalog can be read
+//! from a `.ftl` file. Any message it leaves out is shown in English.
+
+use std::{error::Error, fmt, fs, io, path::Path, sync::OnceLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentValue;
+
+use crate::{
+    hint::{Hint, HintError},
+    HardModeViolation,
+};
+
+/// The English catalog, the built-in default
+const ENGLISH: &str = include_str!("../locales/en.ftl");
+/// The French catalog
+const FRENCH: &str = include_str!("../locales/fr.ftl");
+/// The German catalog
+const GERMAN: &str = include_str!("../locales/de.ftl");
+
+/// Why a catalog couldn't be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MessagesError {
+    /// The locale isn't a language identifier, like `fr` or `pt-BR`
+    InvalidLocale(String),
+    /// The catalog isn't valid Fluent
+    Syntax {
+        /// The line the problem is on, starting from 1
+        line: usize,
+        /// What was wrong
+        issue: String,
+    },
+    /// A message is given more than once
+    Duplicate(String),
+}
+
+impl fmt::Display for MessagesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLocale(locale) => write!(f, "'{}' is not a valid locale", locale),
+            Self::Syntax { line, issue } => write!(f, "line {}: {}", line, issue),
+            Self::Duplicate(id) => write!(f, "message '{}' is given more than once", id),
+        }
+    }
+}
+
+impl Error for MessagesError {}
+
+/// A catalog of the messages shown to players, in one locale.
+///
+/// Messages are looked up by id with [`Messages::format`]; one the catalog
+/// doesn't have comes from the English catalog instead, and one that neither
+/// has is shown as its id, so a gap in a translation is easy to spot.
+pub struct Messages {
+    /// The locale the messages are in, which decides plural forms
+    locale: LanguageIdentifier,
+    /// The messages themselves
+    bundle: FluentBundle<FluentResource>,
+    /// Where messages this catalog doesn't have come from
+    fallback: Option<&'static Messages>,
+}
+
+impl fmt::Debug for Messages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Messages")
+            .field("locale", &self.locale.to_string())
+            .field("fallback", &self.fallback.map(|m| m.locale.to_string()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Messages {
+    /// The locales with built-in catalogs, for [`Messages::builtin`]
+    pub const BUILTIN: [&'static str; 3] = ["en", "fr", "de"];
+
+    /// A built-in catalog by locale: `en`, `fr`, or `de`
+    pub fn builtin(locale: &str) -> Option<&'static Self> {
+        static FRENCH_MESSAGES: OnceLock<Messages> = OnceLock::new();
+        static GERMAN_MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+        let (cell, text) = match locale {
+            "en" => return Some(Self::english()),
+            "fr" => (&FRENCH_MESSAGES, FRENCH),
+            "de" => (&GERMAN_MESSAGES, GERMAN),
+            _ => return None,
+        };
+        Some(cell.get_or_init(|| {
+            Self::build(locale, text, Some(Self::english()))
+                .expect("The built-in catalogs are valid")
+        }))
+    }
+
+    /// The English catalog, the default
+    pub fn english() -> &'static Self {
+        static ENGLISH_MESSAGES: OnceLock<Messages> = OnceLock::new();
+        ENGLISH_MESSAGES
+            .get_or_init(|| Self::build("en", ENGLISH, None).expect("The English catalog is valid"))
+    }
+
+    /// Read a catalog in `locale` from Fluent; any message left out is shown in English
+    pub fn parse(locale: &str, text: &str) -> Result<Self, MessagesError> {
+        Self::build(locale, text, Some(Self::english()))
+    }
+
+    /// Read a catalog from a `.ftl` file named for its locale, e.g. `fr.ftl`.
+    ///
+    /// A file that isn't a valid catalog is an [`io::ErrorKind::InvalidData`] error.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let locale = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        Self::parse(locale, &text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Read a catalog, with messages it doesn't have coming from `fallback`
+    fn build(
+        locale: &str,
+        text: &str,
+        fallback: Option<&'static Self>,
+    ) -> Result<Self, MessagesError> {
+        let locale: LanguageIdentifier = locale
+            .parse()
+            .map_err(|_| MessagesError::InvalidLocale(locale.to_owned()))?;
+        let resource = FluentResource::try_new(text.to_owned()).map_err(|(_, errors)| {
+            let error = &errors[0];
+            MessagesError::Syntax {
+                line: text[..error.pos.start.min(text.len())]
+                    .lines()
+                    .count()
+                    .max(1),
+                issue: error.kind.to_string(),
+            }
+        })?;
+
+        let mut bundle = FluentBundle::new_concurrent(vec![locale.clone()]);
+        // the isolation marks around arguments are for mixing left-to-right
+        // and right-to-left text, and show up as junk in most terminals
+        bundle.set_use_isolating(false);
+        bundle
+            .add_builtins()
+            .expect("The built-in functions are only added once");
+        bundle.add_resource(resource).map_err(|errors| {
+            let id = errors
+                .into_iter()
+                .find_map(|error| match error {
+                    fluent_bundle::FluentError::Overriding { id, .. } => Some(id),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            MessagesError::Duplicate(id)
+        })?;
+        Ok(Self {
+            locale,
+            bundle,
+            fallback,
+        })
+    }
+
+    /// The locale the messages are in, e.g. `fr`
+    pub fn locale(&self) -> String {
+        self.locale.to_string()
+    }
+
+    /// Whether this catalog has its own message for `id`, rather than
+    /// falling back to English for it
+    pub fn has_message(&self, id: &str) -> bool {
+        self.bundle.has_message(id)
+    }
+
+    /// The message `id`, filled in with `args`.
+    ///
+    /// A message this catalog doesn't have comes from English instead, and
+    /// one that neither has is given as `id`.
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let pattern = match self.bundle.get_message(id).and_then(|m| m.value()) {
+            Some(pattern) => pattern,
+            None => {
+                return match self.fallback {
+                    Some(fallback) => fallback.format(id, args),
+                    None => id.to_owned(),
+                }
+            }
+        };
+        let mut fluent_args = FluentArgs::with_capacity(args.len());
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        // a mistake in a translation, like a missing argument, still leaves
+        // the rest of the message readable, so it's shown anyway
+        let mut errors = Vec::new();
+        self.bundle
+            .format_pattern(pattern, Some(&fluent_args), &mut errors)
+            .into_owned()
+    }
+
+    /// A hint a guess failed to use in hard mode, e.g. `2nd letter must be R`
+    pub fn violation(&self, violation: &HardModeViolation) -> String {
+        match *violation {
+            HardModeViolation::MissingCorrect { letter, position } => self.format(
+                "hard-mode-missing-correct",
+                &[
+                    ("position", (position + 1).into()),
+                    ("letter", letter.to_uppercase().to_string().into()),
+                ],
+            ),
+            HardModeViolation::MissingPresent { letter } => self.format(
+                "hard-mode-missing-present",
+                &[("letter", letter.to_uppercase().to_string().into())],
+            ),
+        }
+    }
+
+    /// Something revealed about the answer, e.g. `The 3rd letter is A`
+    pub fn hint(&self, hint: &Hint) -> String {
+        match *hint {
+            Hint::Present { letter } => self.format(
+                "hint-present",
+                &[("letter", letter.to_uppercase().to_string().into())],
+            ),
+            Hint::Position { letter, position } => self.format(
+                "hint-position",
+                &[
+                    ("position", (position + 1).into()),
+                    ("letter", letter.to_uppercase().to_string().into()),
+                ],
+            ),
+        }
+    }
+
+    /// Why a hint couldn't be given
+    pub fn hint_error(&self, error: &HintError) -> String {
+        let id = match error {
+            HintError::NoHintsLeft => "hint-no-hints-left",
+            HintError::NothingToReveal => "hint-nothing-to-reveal",
+            HintError::GameOver => "hint-game-over",
+        };
+        self.format(id, &[])
+    }
+}
+
+/// The language of a POSIX locale like `LANG`'s, e.g. `fr` for `fr_FR.UTF-8`.
+///
+/// The `C` and `POSIX` locales don't name a language, so they give `None`.
+pub fn language(locale: &str) -> Option<&str> {
+    let language = locale
+        .split(['_', '.', '@', '-'])
+        .next()
+        .unwrap_or_default();
+    match language {
+        "" | "C" | "POSIX" => None,
+        language => Some(language),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ids of every English message, which every other locale should translate
+    fn ids() -> Vec<&'static str> {
+        ENGLISH
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" = ").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_message() {
+        let args = FluentArgs::from_iter([
+            ("guess", FluentValue::from("CRANE")),
+            ("reason", "not in word list".into()),
+            ("expected", 5.into()),
+            ("actual", 4.into()),
+            ("letter", "R".into()),
+            ("turn", 2.into()),
+            ("position", 3.into()),
+            ("guesses", 4.into()),
+            ("answer", "CRANE".into()),
+            ("current", 2.into()),
+            ("max", 5.into()),
+            ("streak", 1.into()),
+            ("meaning", "correct position".into()),
+            ("letters", "C, R".into()),
+            ("correct", 1.into()),
+            ("present", 2.into()),
+            ("absent", 2.into()),
+            ("tally", "1 correct, 2 present, 2 absent".into()),
+            ("number", 2.into()),
+            ("description", "CRANE: ...".into()),
+            ("count", 1.into()),
+            ("remaining", "1,208".into()),
+            ("before", "12,972".into()),
+            ("answers", "CRANE, CRATE".into()),
+            ("seed", "42".into()),
+            ("boards", 4.into()),
+            ("games", 3.into()),
+            ("won", 1.into()),
+            ("played", 2.into()),
+            ("puzzle", 196.into()),
+            ("date", "2022-01-01".into()),
+            ("first", "2021-06-19".into()),
+            ("time", "1m 05s".into()),
+            ("splits", "5s, 10s".into()),
+            ("path", "/tmp/stats.json".into()),
+            ("backup", "/tmp/stats.json.bak".into()),
+            ("error", "permission denied".into()),
+            ("hint", "the word has an R".into()),
+            ("command", ":nope".into()),
+            ("added", 3.into()),
+            ("skipped", 1.into()),
+            ("name", "dark".into()),
+            ("names", "classic, dark".into()),
+            ("code", "fr".into()),
+            ("key", "colour".into()),
+            ("length", 6.into()),
+            ("rank", 1.into()),
+            ("player", "alice".into()),
+            ("solved", 1.into()),
+            ("row", 2.into()),
+            ("rows", 3.into()),
+            ("tiles", "🟩⬛🟨⬛⬛".into()),
+            ("others", 2.into()),
+            ("word", "CRANE".into()),
+            ("words", "SLATE (5.87 bits)".into()),
+            ("bits", "5.87".into()),
+            ("verdict", "could be the answer".into()),
+            ("average", "2.17".into()),
+            ("worst", 5.into()),
+            ("patterns", "⬛⬛🟩⬛🟩 5".into()),
+            ("percentage", 50.into()),
+        ]);
+        assert!(ids().len() > 20);
+        for locale in Messages::BUILTIN {
+            let messages = Messages::builtin(locale).unwrap();
+            for id in ids() {
+                assert!(messages.has_message(id), "{} has no '{}'", locale, id);
+                let pattern = messages.bundle.get_message(id).unwrap().value().unwrap();
+                let mut errors = Vec::new();
+                let text = messages
+                    .bundle
+                    .format_pattern(pattern, Some(&args), &mut errors);
+                assert!(errors.is_empty(), "{} '{}': {:?}", locale, id, errors);
+                assert!(!text.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_english() {
+        let messages = Messages::english();
+        let violation = |position| {
+            messages.violation(&HardModeViolation::MissingCorrect {
+                letter: 'r',
+                position,
+            })
+        };
+        assert_eq!("1st letter must be R", violation(0));
+        assert_eq!("2nd letter must be R", violation(1));
+        assert_eq!("3rd letter must be R", violation(2));
+        assert_eq!("11th letter must be R", violation(10));
+        assert_eq!("21st letter must be R", violation(20));
+        assert_eq!(
+            "Guess must contain C",
+            messages.violation(&HardModeViolation::MissingPresent { letter: 'c' })
+        );
+        let hint = |position| {
+            messages.hint(&Hint::Position {
+                letter: 'a',
+                position,
+            })
+        };
+        assert_eq!("The 3rd letter is A", hint(2));
+        assert_eq!("The 12th letter is A", hint(11));
+        assert_eq!(
+            "The word contains N",
+            messages.hint(&Hint::Present { letter: 'n' })
+        );
+        assert_eq!(
+            "No hints left",
+            messages.hint_error(&HintError::NoHintsLeft)
+        );
+        assert_eq!(
+            "1 guess left.",
+            messages.format("guesses-left", &[("count", 1.into())])
+        );
+        assert_eq!(
+            "3 guesses left.",
+            messages.format("guesses-left", &[("count", 3.into())])
+        );
+        assert_eq!("no-such-message", messages.format("no-such-message", &[]));
+    }
+
+    #[test]
+    fn test_parse() {
+        let messages = Messages::parse("fr", "rejection-game-over = la partie est finie").unwrap();
+        assert_eq!("fr", messages.locale());
+        assert_eq!(
+            "la partie est finie",
+            messages.format("rejection-game-over", &[])
+        );
+        // anything left out is in English
+        assert!(!messages.has_message("rejection-not-in-word-list"));
+        assert_eq!(
+            "not in word list",
+            messages.format("rejection-not-in-word-list", &[])
+        );
+
+        assert_eq!(
+            Some(&MessagesError::InvalidLocale("not a locale".into())),
+            Messages::parse("not a locale", "").err().as_ref()
+        );
+        assert!(matches!(
+            Messages::parse("fr", "a = b\nthis isn't fluent"),
+            Err(MessagesError::Syntax { line: 2, .. })
+        ));
+        assert_eq!(
+            Some(&MessagesError::Duplicate("a".into())),
+            Messages::parse("fr", "a = b\na = c").err().as_ref()
+        );
+        assert!(Messages::builtin("xx").is_none());
+    }
+
+    #[test]
+    fn test_language() {
+        assert_eq!(Some("fr"), language("fr_FR.UTF-8"));
+        assert_eq!(Some("de"), language("de_DE@euro"));
+        assert_eq!(Some("en"), language("en"));
+        assert_eq!(None, language("C.UTF-8"));
+        assert_eq!(None, language("POSIX"));
+        assert_eq!(None, language(""));
+    }
+}
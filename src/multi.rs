@@ -0,0 +1,308 @@
+//! Several boards played at once with the same guesses, each against its own word, like Quordle
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use rand::{seq::SliceRandom, Rng};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    share::{score_line, share_grid, TileSet},
+    state::NoWord,
+    GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+/// The number of guesses for `boards` boards: one more than a single board
+/// gets for each extra board, like Dordle's 7 and Quordle's 9
+pub fn guess_budget(max_guesses: usize, boards: usize) -> usize {
+    max_guesses + boards.saturating_sub(1)
+}
+
+/// Boards that all take the same guesses, each against its own word.
+///
+/// A board stops taking guesses once its word is found. The game is won once
+/// every word is found, and lost if the guesses run out first.
+///
+/// Hard mode doesn't apply; every board takes every acceptable guess.
+#[derive(Debug)]
+pub struct MultiGame<'a> {
+    /// Each board, in order
+    boards: Vec<Wordle<'a>>,
+}
+
+impl<'a> MultiGame<'a> {
+    /// Play a board against each of `answers`, with the word lists and
+    /// settings of `game` and the [guess budget](guess_budget) for that many boards
+    pub fn new(game: &Wordle<'a, NoWord>, answers: &[&str]) -> Result<Self, GuessError> {
+        let max_guesses = guess_budget(game.max_guesses, answers.len());
+        let mut boards = Vec::with_capacity(answers.len());
+        for answer in answers {
+            let mut board = game.fresh_board();
+            board.max_guesses = max_guesses;
+            board.hard_mode = false;
+            boards.push(board.set_answer(answer)?);
+        }
+        Ok(Self { boards })
+    }
+
+    /// Play `boards` boards against different answers chosen at random
+    pub fn random<R: Rng + ?Sized>(game: &Wordle<'a, NoWord>, boards: usize, rng: &mut R) -> Self {
+        let answers: Vec<&str> = game.answers.choose_multiple(rng, boards).copied().collect();
+        Self::new(game, &answers).expect("Answers are acceptable guesses")
+    }
+
+    /// The boards, in order
+    pub fn boards(&self) -> &[Wordle<'a>] {
+        &self.boards
+    }
+
+    /// The answer on each board, in order, for showing once the game is over
+    pub fn answers(&self) -> Vec<&'a str> {
+        self.boards.iter().map(Wordle::answer).collect()
+    }
+
+    /// The number of guesses there are to find every word
+    pub fn max_guesses(&self) -> usize {
+        self.boards.first().map_or(0, Wordle::max_guesses)
+    }
+
+    /// The number of guesses made so far
+    pub fn turns(&self) -> usize {
+        self.boards
+            .iter()
+            .map(|board| board.history().len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Guess a word on every board that hasn't been solved, and get back the
+    /// feedback for each board, or `None` for the ones already solved.
+    ///
+    /// A rejected guess isn't played on any board.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Option<Vec<LetterStatus>>>, GuessError> {
+        if self.status() != GameStatus::InProgress {
+            return Err(GuessError::GameOver);
+        }
+        let mut unsolved = self
+            .boards
+            .iter_mut()
+            .filter(|board| board.status() == GameStatus::InProgress);
+        // the boards share their word lists, so a guess one takes, they all take
+        let first = unsolved.next().expect("The game is in progress");
+        first.guess(word)?;
+        for board in unsolved {
+            board
+                .guess(word)
+                .expect("Every board takes the same guesses");
+        }
+
+        let turn = self.turns();
+        Ok(self
+            .boards
+            .iter()
+            .map(|board| {
+                board
+                    .history()
+                    .last()
+                    .filter(|_| board.history().len() == turn)
+                    .map(|(_, statuses)| statuses.clone())
+            })
+            .collect())
+    }
+
+    /// Give up on every board that hasn't been solved
+    pub fn give_up(&mut self) {
+        for board in &mut self.boards {
+            board.give_up();
+        }
+    }
+
+    /// Won once every board is solved, taking as many guesses as the last one
+    /// took, or lost once any board is
+    pub fn status(&self) -> GameStatus {
+        let statuses: Vec<GameStatus> = self.boards.iter().map(Wordle::status).collect();
+        if statuses.contains(&GameStatus::Lost) {
+            GameStatus::Lost
+        } else if statuses.contains(&GameStatus::InProgress) {
+            GameStatus::InProgress
+        } else {
+            GameStatus::Won {
+                guesses: self.turns(),
+            }
+        }
+    }
+
+    /// The turn each board was solved on, or `None` for a board that wasn't
+    pub fn solved_on(&self) -> Vec<Option<usize>> {
+        self.boards
+            .iter()
+            .map(|board| match board.status() {
+                GameStatus::Won { guesses } => Some(guesses),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The time spent on the game, until the last board finished or so far
+    pub fn elapsed(&self) -> Duration {
+        self.boards
+            .iter()
+            .map(Wordle::elapsed)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The text to share for the game: a header, e.g. `Wordle x4 8/9`, then
+    /// the boards' grids two to a row.
+    ///
+    /// A board solved early is padded with blank rows, so the grids beside it line up.
+    pub fn share_text(&self, tiles: &TileSet<'_>) -> String {
+        let mut text = format!(
+            "Wordle x{} {}",
+            self.boards.len(),
+            score_line(self.status(), self.max_guesses(), false)
+        );
+        for pair in self.boards.chunks(2) {
+            let grids: Vec<Vec<String>> = pair
+                .iter()
+                .map(|board| {
+                    share_grid(board.history(), tiles)
+                        .lines()
+                        .map(String::from)
+                        .collect()
+                })
+                .collect();
+            let width = grids
+                .iter()
+                .flatten()
+                .map(|row| row.width())
+                .max()
+                .unwrap_or(0);
+            let height = grids.iter().map(Vec::len).max().unwrap_or(0);
+            text.push('\n');
+            for i in 0..height {
+                let row: Vec<String> = grids
+                    .iter()
+                    .map(|grid| {
+                        let row = grid.get(i).map_or("", String::as_str);
+                        format!("{}{}", row, " ".repeat(width - row.width()))
+                    })
+                    .collect();
+                text.push('\n');
+                text.push_str(row.join(" ").trim_end());
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 6] = ["crane", "slate", "trace", "irate", "share", "stare"];
+
+    fn game(answers: &[&str]) -> MultiGame<'static> {
+        let template = Wordle::builder()
+            .guesses(&WORDS)
+            .answers(&WORDS)
+            .hard_mode(true)
+            .build()
+            .unwrap();
+        MultiGame::new(&template, answers).unwrap()
+    }
+
+    #[test]
+    fn test_guess_budget() {
+        assert_eq!(6, guess_budget(6, 1));
+        assert_eq!(7, guess_budget(6, 2));
+        assert_eq!(9, guess_budget(6, 4));
+        assert_eq!(9, game(&["crane", "slate", "trace", "irate"]).max_guesses());
+    }
+
+    #[test]
+    fn test_win() {
+        let mut game = game(&["crane", "slate"]);
+        let feedback = game.guess("slate").unwrap();
+        assert_eq!(
+            vec![
+                Some(crate::score_guess("slate", "crane")),
+                Some(vec![LetterStatus::Correct; 5])
+            ],
+            feedback
+        );
+        assert_eq!(GameStatus::InProgress, game.status());
+
+        // invalid guesses aren't played at all
+        assert_eq!(Err(GuessError::NotInWordList), game.guess("zzzzz"));
+        assert_eq!(1, game.turns());
+
+        // the solved board doesn't take any more guesses, and hard mode doesn't apply
+        let feedback = game.guess("crane").unwrap();
+        assert_eq!(vec![Some(vec![LetterStatus::Correct; 5]), None], feedback);
+        assert_eq!(GameStatus::Won { guesses: 2 }, game.status());
+        assert_eq!(vec![Some(2), Some(1)], game.solved_on());
+        assert_eq!(Err(GuessError::GameOver), game.guess("trace"));
+    }
+
+    #[test]
+    fn test_loss() {
+        let mut game = game(&["crane", "slate"]);
+        game.guess("crane").unwrap();
+        for _ in 0..6 {
+            game.guess("irate").unwrap();
+        }
+        assert_eq!(GameStatus::Lost, game.status());
+        assert_eq!(vec![Some(1), None], game.solved_on());
+
+        let mut game = self::game(&["crane", "slate"]);
+        game.give_up();
+        assert_eq!(GameStatus::Lost, game.status());
+    }
+
+    #[test]
+    fn test_random() {
+        let template = Wordle::new(&WORDS, &WORDS);
+        let game = MultiGame::random(&template, 4, &mut rand::thread_rng());
+        let mut answers: Vec<&str> = game.boards().iter().map(Wordle::answer).collect();
+        answers.sort_unstable();
+        answers.dedup();
+        assert_eq!(4, answers.len(), "every board has its own word");
+    }
+
+    #[test]
+    fn test_share_text() {
+        let mut game = game(&["crane", "slate", "trace", "share"]);
+        for guess in ["slate", "crane", "trace", "share"] {
+            game.guess(guess).unwrap();
+        }
+        assert_eq!(
+            concat!(
+                "Wordle x4 4/9\n",
+                "\n",
+                "..#.# #####\n",
+                "#####\n",
+                "\n",
+                "..#+# #.#.#\n",
+                "+##.# .+#.#\n",
+                "##### .+#.#\n",
+                "      #####",
+            ),
+            game.share_text(&TileSet::ascii())
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! A computer opponent that races the player to find the same word
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    share::{self, TileSet},
+    solver::{EntropyStrategy, RandomStrategy, Strategy},
+    LetterStatus, Wordle,
+};
+
+/// How well an [`Opponent`] plays
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Difficulty {
+    /// Guess random words that could still be the answer
+    Easy,
+    /// Guess the most informative word every turn
+    Hard,
+}
+
+/// A computer player with its own board, playing against the same word as the player.
+///
+/// The opponent's guesses are hidden; only the colors it got back are exposed,
+/// so the player can see whether they're ahead without being given hints.
+#[derive(Debug)]
+pub struct Opponent<'a> {
+    /// The opponent's own board
+    game: Wordle<'a>,
+    /// How the opponent chooses its guesses
+    strategy: Box<dyn Strategy + 'a>,
+    /// Whether the opponent has found the word
+    solved: bool,
+}
+
+impl<'a> Opponent<'a> {
+    /// Create an opponent playing against the same word, with the same settings, as `game`
+    pub fn new(game: &Wordle<'a>, strategy: Box<dyn Strategy + 'a>) -> Self {
+        Self {
+            game: game.fresh_board().start(game.word),
+            strategy,
+            solved: false,
+        }
+    }
+
+    /// Create an opponent using the strategy for a difficulty level
+    pub fn with_difficulty(game: &Wordle<'a>, difficulty: Difficulty) -> Self {
+        let strategy: Box<dyn Strategy> = match difficulty {
+            Difficulty::Easy => Box::new(RandomStrategy::new()),
+            Difficulty::Hard => Box::new(EntropyStrategy::new()),
+        };
+        Self::new(game, strategy)
+    }
+
+    /// Make the opponent's next guess.
+    ///
+    /// Returns the feedback the opponent got and whether it found the word; or
+    /// `None` if the opponent is done (it found the word, ran out of guesses,
+    /// or ran out of words to guess).
+    pub fn take_turn(&mut self) -> Option<(Vec<LetterStatus>, bool)> {
+        if self.is_done() {
+            return None;
+        }
+
+        let guess = self.strategy.next_guess(&self.game)?;
+        let statuses = self.game.guess(&guess).ok()?.into_feedback();
+        self.solved = statuses.iter().all(|s| *s == LetterStatus::Correct);
+        Some((statuses, self.solved))
+    }
+
+    /// Whether the opponent has found the word
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Whether the opponent has finished playing, by finding the word or running out of guesses
+    pub fn is_done(&self) -> bool {
+        self.solved || self.turns() >= self.game.max_guesses()
+    }
+
+    /// The number of guesses the opponent has made
+    pub fn turns(&self) -> usize {
+        self.game.history().len()
+    }
+
+    /// The opponent's board so far, as colored squares without any letters
+    pub fn progress(&self) -> String {
+        self.progress_with(&TileSet::classic())
+    }
+
+    /// Like [`Opponent::progress`], but with the squares in `tiles`
+    pub fn progress_with(&self, tiles: &TileSet<'_>) -> String {
+        share::share_grid(self.game.history(), tiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUESSES: [&str; 6] = ["crane", "trace", "brace", "grace", "slate", "toast"];
+    const ANSWERS: [&str; 4] = ["crane", "trace", "brace", "grace"];
+
+    #[test]
+    fn test_take_turn() {
+        for difficulty in [Difficulty::Easy, Difficulty::Hard] {
+            let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("grace").unwrap();
+            let mut opponent = Opponent::with_difficulty(&game, difficulty);
+
+            let mut turns = 0;
+            while let Some((statuses, solved)) = opponent.take_turn() {
+                turns += 1;
+                assert_eq!(solved, statuses == [LetterStatus::Correct; 5]);
+            }
+            assert!(
+                opponent.is_solved(),
+                "{:?} opponent didn't solve it",
+                difficulty
+            );
+            assert!(opponent.is_done());
+            assert_eq!(turns, opponent.turns());
+            assert_eq!(None, opponent.take_turn());
+
+            // the player's board is untouched
+            assert!(game.history().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        // after "latch", "whomp" splits the rest best, but can't be played in hard mode
+        let guesses = [
+            "batch", "catch", "hatch", "latch", "match", "patch", "watch", "whomp",
+        ];
+        let answers = ["batch", "catch", "hatch", "match", "patch", "watch"];
+        let game = Wordle::builder()
+            .guesses(&guesses)
+            .answers(&answers)
+            .hard_mode(true)
+            .build()
+            .unwrap()
+            .set_answer("hatch")
+            .unwrap();
+        let mut opponent = Opponent::new(&game, Box::new(EntropyStrategy::with_opener("latch")));
+
+        while opponent.take_turn().is_some() {}
+        assert!(opponent.is_done());
+        assert!(opponent.is_solved());
+    }
+
+    #[test]
+    fn test_progress_hides_letters() {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("crane").unwrap();
+        let mut opponent = Opponent::new(&game, Box::new(EntropyStrategy::with_opener("toast")));
+
+        assert_eq!("", opponent.progress());
+        use LetterStatus::*;
+        assert_eq!(
+            Some((
+                vec![NotInWord, NotInWord, Correct, NotInWord, NotInWord],
+                false
+            )),
+            opponent.take_turn()
+        );
+        assert_eq!("⬛⬛🟩⬛⬛", opponent.progress());
+        assert_eq!("..#..", opponent.progress_with(&TileSet::ascii()));
+        assert!(!opponent.progress().contains("toast"));
+    }
+}
@@ -0,0 +1,185 @@
+//! The messages of a race over a WebSocket, shared by the server and its clients
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{race::PlayerStatus, LetterStatus};
+
+/// A message from a player to the server; spectators can't send any
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Join the room; this must be the first message
+    Join {
+        /// The name to show the other players
+        name: String,
+        /// The token from an earlier [`ServerMessage::Welcome`], to take back
+        /// the same place after being disconnected
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// Make a guess
+    Guess {
+        /// The word to guess
+        word: String,
+    },
+}
+
+/// A message from the server to a player or a spectator.
+///
+/// Spectators get everything but [`ServerMessage::Welcome`] and
+/// [`ServerMessage::Scored`], so they never see a letter of anyone's guesses.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// The player has joined (or rejoined) the room
+    Welcome {
+        /// The player's number in the room
+        player: usize,
+        /// The token to rejoin with, keeping the same place
+        token: String,
+        /// The number of letters in the word
+        word_length: usize,
+        /// The number of guesses each player has
+        max_guesses: usize,
+        /// The player's own guesses so far, when rejoining
+        guesses: Vec<Scored>,
+        /// Everyone in the room, including the player
+        players: Vec<Opponent>,
+    },
+    /// The spectator is watching the room
+    Spectating {
+        /// The number of letters in the word
+        word_length: usize,
+        /// The number of guesses each player has
+        max_guesses: usize,
+        /// Everyone in the room
+        players: Vec<Opponent>,
+    },
+    /// Another player joined (or rejoined) the room
+    Joined {
+        /// Their number in the room
+        player: usize,
+        /// Their name
+        name: String,
+    },
+    /// Another player was disconnected; they may yet rejoin
+    Left {
+        /// Their number in the room
+        player: usize,
+    },
+    /// The feedback for the player's own guess
+    Scored(Scored),
+    /// Another player made a guess; only its pattern is given away
+    Progress {
+        /// Their number in the room
+        player: usize,
+        /// The feedback for their guess, as colored squares
+        pattern: String,
+        /// The number of guesses they've made
+        turn: usize,
+    },
+    /// The race is over
+    Finished {
+        /// The player who found the word first, if anyone did
+        winner: Option<usize>,
+        /// The word
+        answer: String,
+        /// How everyone did, the winner first
+        standings: Vec<Standing>,
+    },
+    /// A message couldn't be acted on
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// One of a player's own guesses, with its feedback
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub struct Scored {
+    /// The guessed word
+    pub word: String,
+    /// The feedback for each letter of the guess
+    pub statuses: Vec<LetterStatus>,
+    /// The number of guesses made so far, including this one
+    pub turn: usize,
+}
+
+/// Someone in a room, as the other players see them
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub struct Opponent {
+    /// Their number in the room
+    pub player: usize,
+    /// Their name
+    pub name: String,
+    /// Whether they're connected right now
+    pub connected: bool,
+    /// The patterns of their guesses so far, as colored squares
+    pub patterns: Vec<String>,
+}
+
+/// How a player did in a race
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub struct Standing {
+    /// Their number in the room
+    pub player: usize,
+    /// Their name
+    pub name: String,
+    /// The number of guesses they made
+    pub guesses: usize,
+    /// Whether they found the word, and when
+    pub status: PlayerStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tagged() {
+        let join: ClientMessage =
+            serde_json::from_str(r#"{"type": "join", "name": "alice"}"#).unwrap();
+        assert_eq!(
+            ClientMessage::Join {
+                name: "alice".to_owned(),
+                token: None
+            },
+            join
+        );
+
+        let scored = ServerMessage::Scored(Scored {
+            word: "crane".to_owned(),
+            statuses: vec![LetterStatus::Correct; 5],
+            turn: 2,
+        });
+        let json = serde_json::to_value(&scored).unwrap();
+        assert_eq!("scored", json["type"]);
+        assert_eq!("crane", json["word"]);
+        assert_eq!(scored, serde_json::from_value(json).unwrap());
+    }
+}
@@ -0,0 +1,284 @@
+//! Python bindings, for analyzing strategies from notebooks
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+
+use crate::{
+    embedded, filter, solver, try_score_guess, AnyWordle, GameStatus, LetterStatus, Wordle,
+};
+
+create_exception!(
+    wordle,
+    InvalidGuess,
+    PyValueError,
+    "A guess, or an answer, that can't be played"
+);
+
+/// The built-in word lists, shared by every game that doesn't bring its own
+fn builtin() -> (&'static [&'static str], &'static [&'static str]) {
+    static LISTS: OnceLock<(Vec<&str>, Vec<&str>)> = OnceLock::new();
+    let (guesses, answers) = LISTS.get_or_init(|| (embedded::guesses(), embedded::answers()));
+    (guesses, answers)
+}
+
+/// A word list from Python, kept for the life of the process (as games borrow
+/// their word lists), so each distinct list is only ever kept once
+fn intern(words: Vec<String>) -> &'static [&'static str] {
+    static LISTS: OnceLock<Mutex<HashMap<Vec<String>, &'static [&'static str]>>> = OnceLock::new();
+    let mut lists = LISTS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    lists.entry(words).or_insert_with_key(|words| {
+        words
+            .iter()
+            .map(|word| &*word.clone().leak())
+            .collect::<Vec<_>>()
+            .leak()
+    })
+}
+
+/// The name of a status in Python
+fn status_name(status: LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => "correct",
+        LetterStatus::InWord => "present",
+        LetterStatus::NotInWord => "absent",
+    }
+}
+
+/// The names of the statuses in feedback
+fn names(feedback: &[LetterStatus]) -> Vec<&'static str> {
+    feedback.iter().copied().map(status_name).collect()
+}
+
+/// Read feedback given as status names
+fn parse_feedback(feedback: &[String]) -> PyResult<Vec<LetterStatus>> {
+    feedback
+        .iter()
+        .map(|name| match name.as_str() {
+            "correct" => Ok(LetterStatus::Correct),
+            "present" => Ok(LetterStatus::InWord),
+            "absent" => Ok(LetterStatus::NotInWord),
+            _ => Err(PyValueError::new_err(format!(
+                "'{}' is not a status; expected 'correct', 'present' or 'absent'",
+                name
+            ))),
+        })
+        .collect()
+}
+
+/// Suggestions as `(word, bits)` pairs
+fn pairs(suggestions: Vec<solver::Suggestion>) -> Vec<(String, f64)> {
+    suggestions
+        .into_iter()
+        .map(|suggestion| (suggestion.word, suggestion.score))
+        .collect()
+}
+
+/// A game of Wordle, with the built-in word lists unless others are given
+#[pyclass(name = "Wordle")]
+#[derive(Debug)]
+struct Game {
+    /// The game, at whichever stage it's reached
+    game: AnyWordle<'static>,
+}
+
+#[pymethods]
+impl Game {
+    #[new]
+    #[pyo3(signature = (guesses=None, answers=None, *, hard=false, seed=None))]
+    fn new(
+        guesses: Option<Vec<String>>,
+        answers: Option<Vec<String>>,
+        hard: bool,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let (builtin_guesses, builtin_answers) = builtin();
+        let mut builder = Wordle::builder()
+            .guesses(guesses.map_or(builtin_guesses, intern))
+            .answers(answers.map_or(builtin_answers, intern))
+            .hard_mode(hard)
+            .assist(true);
+        if let Some(seed) = seed {
+            builder = builder.seed(seed);
+        }
+        let game = builder
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { game: game.into() })
+    }
+
+    /// Start a new game with an answer chosen at random
+    fn choose_word(&mut self) {
+        self.game.choose_word();
+    }
+
+    /// Start a new game with the given answer
+    fn set_answer(&mut self, word: &str) -> PyResult<()> {
+        self.game
+            .set_answer(word)
+            .map_err(|e| InvalidGuess::new_err(e.to_string()))
+    }
+
+    /// Guess a word, and get back a status for each letter: "correct",
+    /// "present" or "absent"
+    fn guess(&mut self, word: &str) -> PyResult<Vec<&'static str>> {
+        let feedback = self
+            .game
+            .guess(word)
+            .map_err(|e| InvalidGuess::new_err(e.to_string()))?;
+        Ok(names(feedback.feedback()))
+    }
+
+    /// "in_progress", "won" or "lost"
+    #[getter]
+    fn status(&self) -> &'static str {
+        match self.game.status().unwrap_or(GameStatus::InProgress) {
+            GameStatus::InProgress => "in_progress",
+            GameStatus::Won { .. } => "won",
+            GameStatus::Lost => "lost",
+        }
+    }
+
+    /// The answer, once the game is over
+    #[getter]
+    fn answer(&self) -> Option<&'static str> {
+        self.game.reveal()
+    }
+
+    /// The guesses so far, each with its statuses
+    #[getter]
+    fn history(&self) -> Vec<(&'static str, Vec<&'static str>)> {
+        self.game
+            .history()
+            .iter()
+            .map(|(word, feedback)| (*word, names(feedback)))
+            .collect()
+    }
+
+    /// The number of guesses left
+    #[getter]
+    fn guesses_left(&self) -> usize {
+        match &self.game {
+            AnyWordle::NoWord(game) => game.max_guesses(),
+            AnyWordle::InProgress(game) => game.guesses_left(),
+            AnyWordle::Finished(_) => 0,
+        }
+    }
+
+    /// The answers that are still possible
+    fn candidates(&self) -> Vec<&'static str> {
+        let candidates = match &self.game {
+            AnyWordle::NoWord(game) => Some(game.answers()),
+            AnyWordle::InProgress(game) => game.remaining_candidates(),
+            AnyWordle::Finished(game) => game.remaining_candidates(),
+        };
+        candidates.map(<[_]>::to_vec).unwrap_or_default()
+    }
+
+    /// The `k` best next guesses, as `(word, bits)` pairs, best first; none
+    /// unless the game is in progress
+    #[pyo3(signature = (k=5))]
+    fn suggest(&self, py: Python<'_>, k: usize) -> Vec<(String, f64)> {
+        let AnyWordle::InProgress(game) = &self.game else {
+            return Vec::new();
+        };
+        pairs(py.detach(|| solver::suggest_next(game, k, false)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Wordle(status='{}', guesses={}, guesses_left={})",
+            self.status(),
+            self.game.history().len(),
+            self.guesses_left()
+        )
+    }
+}
+
+/// Score a guess against an answer, as a status for each letter: "correct",
+/// "present" or "absent"
+#[pyfunction(name = "score_guess")]
+fn py_score_guess(guess: &str, answer: &str) -> PyResult<Vec<&'static str>> {
+    let feedback =
+        try_score_guess(guess, answer).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(names(&feedback))
+}
+
+/// Keep only the candidates that could be the answer, given the statuses a
+/// guess got
+#[pyfunction]
+fn filter_candidates(
+    candidates: Vec<String>,
+    guess: &str,
+    feedback: Vec<String>,
+) -> PyResult<Vec<String>> {
+    let feedback = parse_feedback(&feedback)?;
+    let length = guess.chars().count();
+    if feedback.len() != length {
+        return Err(PyValueError::new_err(format!(
+            "expected {} statuses, one for each letter of the guess, not {}",
+            length,
+            feedback.len()
+        )));
+    }
+    let candidates: Vec<&str> = candidates
+        .iter()
+        .map(String::as_str)
+        .filter(|word| word.chars().count() == length)
+        .collect();
+    Ok(filter::filter_candidates(&candidates, guess, &feedback)
+        .into_iter()
+        .map(str::to_owned)
+        .collect())
+}
+
+/// The `k` best guesses when the answer is one of `candidates`, as
+/// `(word, bits)` pairs, best first; the guesses are the candidates themselves
+/// unless others are given
+#[pyfunction]
+#[pyo3(signature = (candidates, guesses=None, k=5))]
+fn suggest(
+    py: Python<'_>,
+    candidates: Vec<String>,
+    guesses: Option<Vec<String>>,
+    k: usize,
+) -> Vec<(String, f64)> {
+    let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+    let guesses: Vec<&str> = guesses.as_ref().map_or_else(
+        || candidates.clone(),
+        |guesses| guesses.iter().map(String::as_str).collect(),
+    );
+    pairs(py.detach(|| solver::suggest(&candidates, &guesses, k, false)))
+}
+
+/// The `wordle` Python module
+#[pymodule]
+fn wordle(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Game>()?;
+    m.add("InvalidGuess", m.py().get_type::<InvalidGuess>())?;
+    m.add_function(wrap_pyfunction!(py_score_guess, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_candidates, m)?)?;
+    m.add_function(wrap_pyfunction!(suggest, m)?)?;
+    Ok(())
+}
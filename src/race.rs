@@ -0,0 +1,428 @@
+//! Several players racing to find the same word on their own boards
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    error::Error,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{state::State, GuessError, LetterStatus, Wordle};
+
+/// Where a player is in a [`Race`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub enum PlayerStatus {
+    /// The player is still guessing
+    Playing,
+    /// The player found the word
+    Solved {
+        /// The number of guesses it took
+        guesses: usize,
+        /// The time since the start of the race when the word was found
+        time: Duration,
+    },
+    /// The player ran out of guesses
+    Failed {
+        /// The time since the start of the race of the last guess
+        time: Duration,
+    },
+}
+
+/// The result of a [`Race`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RaceOutcome {
+    /// A player still playing could yet win
+    Undecided,
+    /// The player with this id found the word in the fewest guesses, fastest
+    Winner(usize),
+    /// These players found the word in the same number of guesses, at the same time
+    Tie(Vec<usize>),
+    /// Every player ran out of guesses
+    NoWinner,
+}
+
+/// Why a guess in a [`Race`] was rejected
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RaceError {
+    /// There is no player with this id
+    UnknownPlayer(usize),
+    /// The player has already found the word or run out of guesses
+    PlayerFinished(usize),
+    /// The guess itself was invalid
+    Guess(GuessError),
+}
+
+impl fmt::Display for RaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPlayer(id) => write!(f, "there is no player {}", id),
+            Self::PlayerFinished(id) => write!(f, "player {} has already finished", id),
+            Self::Guess(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for RaceError {}
+
+impl From<GuessError> for RaceError {
+    fn from(e: GuessError) -> Self {
+        Self::Guess(e)
+    }
+}
+
+/// Players on independent boards, all against the same word, with a shared timer.
+///
+/// Players are identified by their index, starting at 0. The winner is whoever
+/// finds the word in the fewest guesses; ties are broken by who found it first.
+#[derive(Debug)]
+pub struct Race<'a> {
+    /// The word being raced for
+    answer: &'a str,
+    /// Each player's board
+    boards: Vec<Wordle<'a>>,
+    /// Each player's progress
+    statuses: Vec<PlayerStatus>,
+    /// When the race started
+    started: Instant,
+}
+
+/// A saved [`Race`], including every player's board.
+///
+/// Times are relative to the start of the race, so a race can be saved and
+/// restored later without counting the time in between.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RaceSnapshot {
+    /// The word being raced for
+    pub answer: String,
+    /// How long the race had been running
+    pub elapsed: Duration,
+    /// Each player's board and progress
+    pub players: Vec<PlayerSnapshot>,
+}
+
+/// One player's part of a [`RaceSnapshot`]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlayerSnapshot {
+    /// The player's guesses and their feedback
+    pub guesses: Vec<(String, Vec<LetterStatus>)>,
+    /// The player's progress
+    pub status: PlayerStatus,
+}
+
+impl<'a> Race<'a> {
+    /// Start a race between `players` players against the same word, with the same settings, as `game`
+    pub fn new(game: &Wordle<'a>, players: usize) -> Self {
+        Self {
+            answer: game.word,
+            boards: (0..players)
+                .map(|_| game.fresh_board().start(game.word))
+                .collect(),
+            statuses: vec![PlayerStatus::Playing; players],
+            started: Instant::now(),
+        }
+    }
+
+    /// Restore a saved race, replaying each player's guesses with the word lists and settings of `game`
+    pub fn restore<S: State>(
+        game: &Wordle<'a, S>,
+        snapshot: &RaceSnapshot,
+    ) -> Result<Self, RaceError> {
+        let template = game.fresh_board();
+        let answer = template.check_answer(&snapshot.answer)?;
+
+        let mut boards = Vec::with_capacity(snapshot.players.len());
+        for player in &snapshot.players {
+            let mut board = template.fresh_board().start(answer);
+            for (guess, _) in &player.guesses {
+                board.guess(guess)?;
+            }
+            boards.push(board);
+        }
+
+        Ok(Self {
+            answer,
+            boards,
+            statuses: snapshot.players.iter().map(|p| p.status).collect(),
+            started: Instant::now()
+                .checked_sub(snapshot.elapsed)
+                .unwrap_or_else(Instant::now),
+        })
+    }
+
+    /// Save the race
+    pub fn snapshot(&self) -> RaceSnapshot {
+        RaceSnapshot {
+            answer: self.answer.to_string(),
+            elapsed: self.elapsed(),
+            players: self
+                .boards
+                .iter()
+                .zip(&self.statuses)
+                .map(|(board, status)| PlayerSnapshot {
+                    guesses: board
+                        .history()
+                        .iter()
+                        .map(|(guess, statuses)| (guess.to_string(), statuses.clone()))
+                        .collect(),
+                    status: *status,
+                })
+                .collect(),
+        }
+    }
+
+    /// The number of players in the race
+    pub fn players(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// The time since the race started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// A player's board
+    pub fn board(&self, player: usize) -> Option<&Wordle<'a>> {
+        self.boards.get(player)
+    }
+
+    /// A player's progress
+    pub fn status(&self, player: usize) -> Option<PlayerStatus> {
+        self.statuses.get(player).copied()
+    }
+
+    /// Make a guess for a player, timed by the race's clock
+    pub fn guess(&mut self, player: usize, word: &str) -> Result<Vec<LetterStatus>, RaceError> {
+        let time = self.elapsed();
+        self.guess_at(player, word, time)
+    }
+
+    /// Make a guess for a player, `time` after the start of the race.
+    ///
+    /// This is for callers keeping their own clock, e.g. a server timing
+    /// guesses as they arrive. Times are expected never to go backwards.
+    pub fn guess_at(
+        &mut self,
+        player: usize,
+        word: &str,
+        time: Duration,
+    ) -> Result<Vec<LetterStatus>, RaceError> {
+        let board = self
+            .boards
+            .get_mut(player)
+            .ok_or(RaceError::UnknownPlayer(player))?;
+        if self.statuses[player] != PlayerStatus::Playing {
+            return Err(RaceError::PlayerFinished(player));
+        }
+
+        let statuses = board.guess(word)?.into_feedback();
+        let guesses = board.history().len();
+        if statuses.iter().all(|s| *s == LetterStatus::Correct) {
+            self.statuses[player] = PlayerStatus::Solved { guesses, time };
+        } else if guesses >= board.max_guesses() {
+            self.statuses[player] = PlayerStatus::Failed { time };
+        }
+        Ok(statuses)
+    }
+
+    /// Whether every player has finished
+    pub fn is_over(&self) -> bool {
+        self.statuses.iter().all(|s| *s != PlayerStatus::Playing)
+    }
+
+    /// Determine the winner: the fewest guesses, then the fastest time.
+    ///
+    /// The result is decided as soon as no player still playing could beat
+    /// the best finish so far, even if they haven't finished yet.
+    pub fn winner(&self) -> RaceOutcome {
+        let solved: Vec<(usize, usize, Duration)> = self
+            .statuses
+            .iter()
+            .enumerate()
+            .filter_map(|(id, status)| match status {
+                PlayerStatus::Solved { guesses, time } => Some((id, *guesses, *time)),
+                _ => None,
+            })
+            .collect();
+
+        let best = match solved
+            .iter()
+            .map(|&(_, guesses, time)| (guesses, time))
+            .min()
+        {
+            Some(best) => best,
+            None if self.is_over() => return RaceOutcome::NoWinner,
+            None => return RaceOutcome::Undecided,
+        };
+
+        // anyone still playing finishes later than the best time so far, so
+        // they can only win with fewer guesses
+        let contender = self
+            .boards
+            .iter()
+            .zip(&self.statuses)
+            .any(|(board, status)| {
+                *status == PlayerStatus::Playing && board.history().len() + 1 < best.0
+            });
+        if contender {
+            return RaceOutcome::Undecided;
+        }
+
+        let winners: Vec<usize> = solved
+            .into_iter()
+            .filter(|&(_, guesses, time)| (guesses, time) == best)
+            .map(|(id, _, _)| id)
+            .collect();
+        match winners[..] {
+            [id] => RaceOutcome::Winner(id),
+            _ => RaceOutcome::Tie(winners),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUESSES: [&str; 6] = ["crane", "trace", "brace", "grace", "slate", "toast"];
+    const ANSWERS: [&str; 4] = ["crane", "trace", "brace", "grace"];
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn test_fewest_guesses_wins() {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("grace").unwrap();
+        let mut race = Race::new(&game, 2);
+
+        race.guess_at(0, "slate", secs(1)).unwrap();
+        race.guess_at(0, "grace", secs(2)).unwrap();
+        race.guess_at(1, "grace", secs(5)).unwrap();
+
+        assert_eq!(
+            Some(PlayerStatus::Solved {
+                guesses: 1,
+                time: secs(5)
+            }),
+            race.status(1)
+        );
+        assert_eq!(RaceOutcome::Winner(1), race.winner());
+        assert!(race.is_over());
+    }
+
+    #[test]
+    fn test_ties() {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("brace").unwrap();
+        let mut race = Race::new(&game, 3);
+
+        for player in 0..3 {
+            race.guess_at(player, "crane", secs(1)).unwrap();
+        }
+        // the same number of guesses, but faster
+        race.guess_at(1, "brace", secs(3)).unwrap();
+        race.guess_at(2, "brace", secs(4)).unwrap();
+        race.guess_at(0, "brace", secs(3)).unwrap();
+        assert_eq!(RaceOutcome::Tie(vec![0, 1]), race.winner());
+    }
+
+    #[test]
+    fn test_one_player_finishes_first() {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("trace").unwrap();
+        let mut race = Race::new(&game, 2);
+
+        race.guess_at(0, "crane", secs(1)).unwrap();
+        race.guess_at(0, "trace", secs(2)).unwrap();
+        assert_eq!(
+            Err(RaceError::PlayerFinished(0)),
+            race.guess_at(0, "trace", secs(3))
+        );
+
+        // player 1 could still win on their first guess
+        assert_eq!(RaceOutcome::Undecided, race.winner());
+        assert!(!race.is_over());
+
+        // ... but not after a miss, though they can keep playing
+        race.guess_at(1, "slate", secs(3)).unwrap();
+        assert_eq!(RaceOutcome::Winner(0), race.winner());
+        race.guess_at(1, "toast", secs(4)).unwrap();
+        assert_eq!(Some(PlayerStatus::Playing), race.status(1));
+        race.guess_at(1, "trace", secs(5)).unwrap();
+        assert_eq!(RaceOutcome::Winner(0), race.winner());
+        assert!(race.is_over());
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let game = Wordle::builder()
+            .guesses(&GUESSES)
+            .answers(&ANSWERS)
+            .max_guesses(1)
+            .build()
+            .unwrap()
+            .set_answer("crane")
+            .unwrap();
+        let mut race = Race::new(&game, 2);
+
+        race.guess_at(0, "toast", secs(1)).unwrap();
+        assert_eq!(Some(PlayerStatus::Failed { time: secs(1) }), race.status(0));
+        assert_eq!(RaceOutcome::Undecided, race.winner());
+        race.guess_at(1, "slate", secs(2)).unwrap();
+        assert_eq!(RaceOutcome::NoWinner, race.winner());
+
+        assert_eq!(
+            Err(RaceError::UnknownPlayer(2)),
+            race.guess_at(2, "crane", secs(3))
+        );
+        assert_eq!(
+            Err(RaceError::Guess(GuessError::NotInWordList)),
+            Race::new(&game, 1).guess(0, "zzzzz")
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer("crane").unwrap();
+        let mut race = Race::new(&game, 2);
+        race.guess_at(0, "trace", secs(1)).unwrap();
+        race.guess_at(1, "crane", secs(2)).unwrap();
+
+        let snapshot = race.snapshot();
+        assert_eq!("crane", snapshot.answer);
+        assert_eq!(
+            vec![("trace".to_string(), crate::score_guess("trace", "crane"))],
+            snapshot.players[0].guesses
+        );
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: RaceSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot, restored);
+
+        let race = Race::restore(&game, &restored).unwrap();
+        assert_eq!(RaceOutcome::Winner(1), race.winner());
+        assert_eq!(
+            crate::score_guess("trace", "crane"),
+            race.board(0).unwrap().history()[0].1
+        );
+        assert!(race.elapsed() >= snapshot.elapsed);
+    }
+}
@@ -0,0 +1,152 @@
+//! Drawing boards for places outside the terminal
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{theme::Color, LetterStatus};
+
+pub mod html;
+pub mod svg;
+
+/// The colors of the tiles for each [`LetterStatus`].
+///
+/// A [`Theme`](crate::theme::Theme) gives the palette to draw with, with
+/// [`Theme::palette`](crate::theme::Theme::palette).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Palette {
+    /// Fill for a letter in the correct position
+    pub correct: Color,
+    /// Fill for a letter in the word, but in the wrong position
+    pub in_word: Color,
+    /// Fill for a letter not in the word
+    pub not_in_word: Color,
+}
+
+impl Palette {
+    /// Green, yellow, and gray, like the official game's light theme
+    pub const fn classic() -> Self {
+        Self {
+            correct: Color::Rgb(0x6a, 0xaa, 0x64),
+            in_word: Color::Rgb(0xc9, 0xb4, 0x58),
+            not_in_word: Color::Rgb(0x78, 0x7c, 0x7e),
+        }
+    }
+
+    /// Green, yellow, and a darker gray, like the official game's dark theme
+    pub const fn dark() -> Self {
+        Self {
+            correct: Color::Rgb(0x53, 0x8d, 0x4e),
+            in_word: Color::Rgb(0xb5, 0x9f, 0x3b),
+            not_in_word: Color::Rgb(0x3a, 0x3a, 0x3c),
+        }
+    }
+
+    /// Orange and blue, like the official game's high contrast mode
+    pub const fn high_contrast() -> Self {
+        Self {
+            correct: Color::Rgb(0xf5, 0x79, 0x3a),
+            in_word: Color::Rgb(0x85, 0xc0, 0xf9),
+            not_in_word: Color::Rgb(0x78, 0x7c, 0x7e),
+        }
+    }
+
+    /// Get the fill for a [`LetterStatus`], as `#rrggbb`
+    pub fn fill(&self, status: &LetterStatus) -> String {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::InWord => self.in_word,
+            LetterStatus::NotInWord => self.not_in_word,
+        }
+        .hex()
+    }
+}
+
+/// The background a board is drawn on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Background {
+    /// A white background
+    #[default]
+    Light,
+    /// A near-black background
+    Dark,
+}
+
+impl Background {
+    /// The background color
+    fn color(self) -> &'static str {
+        match self {
+            Self::Light => "#ffffff",
+            Self::Dark => "#121213",
+        }
+    }
+
+    /// The tile colors that suit the background
+    pub fn palette(self) -> Palette {
+        match self {
+            Self::Light => Palette::classic(),
+            Self::Dark => Palette::dark(),
+        }
+    }
+}
+
+/// How a board is drawn
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BoardOptions {
+    /// The width and height of each tile, in pixels
+    pub tile_size: u32,
+    /// Whether to write the guessed letters on the tiles, or only color them
+    pub letters: bool,
+    /// The background to draw on
+    pub background: Background,
+    /// The tile colors, or `None` for the background's
+    pub palette: Option<Palette>,
+}
+
+impl Default for BoardOptions {
+    fn default() -> Self {
+        Self {
+            tile_size: 62,
+            letters: true,
+            background: Background::default(),
+            palette: None,
+        }
+    }
+}
+
+/// Escape text to go in an SVG or HTML element, or an attribute
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("a&amp;b&lt;c&gt;&quot;", escape("a&b<c>\""));
+        assert_eq!("É", escape("É"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Drawing a board as a fragment of HTML, e.g. to embed it in an email or a web page
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Write;
+
+use super::{escape, BoardOptions};
+use crate::{messages::Messages, speech, transcript::Transcript, LetterStatus};
+
+/// Draw the guesses made in a game as a `<div>` with a row of tiles for each.
+///
+/// Everything is styled inline, so the fragment can be dropped in anywhere
+/// without a stylesheet. Each tile is labelled for screen readers with the
+/// same phrasing as [`speech::letters`], e.g. `R, correct position`, or just
+/// `correct position` when the letters are left off.
+pub fn board_html(history: &[(&str, Vec<LetterStatus>)], options: &BoardOptions) -> String {
+    let tile = options.tile_size;
+    let gap = (tile / 12).max(1);
+    let radius = tile / 10;
+    let palette = options.palette.unwrap_or(options.background.palette());
+
+    // writing to a String can't fail
+    let mut html = String::new();
+    let _ = writeln!(
+        html,
+        r#"<div role="group" aria-label="Wordle board" style="display:inline-grid;gap:{g}px;padding:{g}px;background:{}">"#,
+        options.background.color(),
+        g = gap
+    );
+    for (row, (word, statuses)) in history.iter().enumerate() {
+        let _ = writeln!(
+            html,
+            r#"<div role="group" aria-label="Guess {}" style="display:grid;grid-template-columns:repeat({},{t}px);grid-auto-rows:{t}px;gap:{}px">"#,
+            row + 1,
+            statuses.len(),
+            gap,
+            t = tile
+        );
+        let meanings = speech::meanings(Messages::english(), word, statuses);
+        for ((letter, status), meaning) in word.chars().zip(statuses).zip(meanings) {
+            let letter = letter.to_uppercase().to_string();
+            let (label, text) = match options.letters {
+                true => (format!("{}, {}", letter, meaning), escape(&letter)),
+                false => (meaning, String::new()),
+            };
+            let _ = writeln!(
+                html,
+                r#"<div role="img" aria-label="{}" style="display:flex;align-items:center;justify-content:center;border-radius:{}px;background:{};color:#ffffff;font:bold {}px sans-serif">{}</div>"#,
+                escape(&label),
+                radius,
+                palette.fill(status),
+                tile / 2,
+                text
+            );
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+/// Draw the guesses made in a saved game, like [`board_html`]
+pub fn transcript_html(transcript: &Transcript, options: &BoardOptions) -> String {
+    let history: Vec<(&str, Vec<LetterStatus>)> = transcript
+        .guesses
+        .iter()
+        .map(|guess| (guess.word.as_str(), guess.feedback.clone()))
+        .collect();
+    board_html(&history, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_guess;
+
+    #[test]
+    fn test_labels() {
+        // "speed" against "abide": the gray E comes before the yellow one
+        let history = vec![("speed", score_guess("speed", "abide"))];
+        let html = board_html(&history, &BoardOptions::default());
+        for label in [
+            r#"aria-label="S, not in the word""#,
+            r#"aria-label="E, in the word, wrong position""#,
+            r#"aria-label="E, no more in the word""#,
+            r#"aria-label="D, in the word, wrong position""#,
+        ] {
+            assert!(html.contains(label), "{} missing from {}", label, html);
+        }
+
+        let options = BoardOptions {
+            letters: false,
+            ..BoardOptions::default()
+        };
+        let html = board_html(&history, &options);
+        assert!(html.contains(r#"aria-label="not in the word""#));
+        assert!(!html.contains("S,"), "letters given away: {}", html);
+        assert!(!html.contains(">S<"), "letters given away: {}", html);
+    }
+
+    #[test]
+    fn test_escape() {
+        let history = vec![("<&\">", vec![LetterStatus::NotInWord; 4])];
+        let html = board_html(&history, &BoardOptions::default());
+        assert!(html.contains(r#"aria-label="&lt;, not in the word""#));
+        assert!(html.contains(">&amp;</div>"));
+        assert!(html.contains(r#"aria-label="&quot;, not in the word""#));
+        assert!(html.contains(">&gt;</div>"));
+    }
+}
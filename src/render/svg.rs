@@ -0,0 +1,158 @@
+//! Drawing a board as an SVG image, e.g. to share it somewhere that shows images
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Write;
+
+use super::{escape, BoardOptions};
+use crate::{transcript::Transcript, LetterStatus};
+
+/// Draw the guesses made in a game, one row of tiles for each.
+///
+/// The image is only as big as the guesses need, so no empty rows are drawn
+/// for turns that weren't played.
+pub fn board_svg(history: &[(&str, Vec<LetterStatus>)], options: &BoardOptions) -> String {
+    let tile = options.tile_size;
+    let gap = (tile / 12).max(1);
+    let radius = tile / 10;
+    let palette = options.palette.unwrap_or(options.background.palette());
+
+    let columns = history
+        .iter()
+        .map(|(_, statuses)| statuses.len())
+        .max()
+        .unwrap_or(0) as u32;
+    let rows = history.len() as u32;
+    let width = gap + columns * (tile + gap);
+    let height = gap + rows * (tile + gap);
+
+    // writing to a String can't fail
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{}" height="{}" fill="{}"/>"#,
+        width,
+        height,
+        options.background.color()
+    );
+    for (row, (word, statuses)) in (0..).zip(history) {
+        let y = gap + row * (tile + gap);
+        for (column, status) in (0..).zip(statuses) {
+            let x = gap + column * (tile + gap);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{t}" height="{t}" rx="{}" fill="{}"/>"#,
+                x,
+                y,
+                radius,
+                palette.fill(status),
+                t = tile
+            );
+        }
+        if !options.letters {
+            continue;
+        }
+        for (column, letter) in (0..).zip(word.chars()).take(statuses.len()) {
+            let _ = writeln!(
+                svg,
+                r##"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" font-weight="bold" fill="#ffffff" text-anchor="middle" dominant-baseline="central">{}</text>"##,
+                gap + column * (tile + gap) + tile / 2,
+                y + tile / 2,
+                tile / 2,
+                escape(&letter.to_uppercase().to_string())
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draw the guesses made in a saved game, like [`board_svg`]
+pub fn transcript_svg(transcript: &Transcript, options: &BoardOptions) -> String {
+    let history: Vec<(&str, Vec<LetterStatus>)> = transcript
+        .guesses
+        .iter()
+        .map(|guess| (guess.word.as_str(), guess.feedback.clone()))
+        .collect();
+    board_svg(&history, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        render::{Background, Palette},
+        score_guess,
+    };
+
+    #[test]
+    fn test_size() {
+        let history = vec![("crane", score_guess("crane", "crane"))];
+        let options = BoardOptions {
+            tile_size: 24,
+            ..BoardOptions::default()
+        };
+        let svg = board_svg(&history, &options);
+        // five tiles of 24 with a gap of 2 around each
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="132" height="28" viewBox="0 0 132 28">"#
+        ));
+        assert_eq!(5, svg.matches("<text").count());
+        assert_eq!(5, svg.matches("fill=\"#6aaa64\"").count());
+
+        let options = BoardOptions {
+            letters: false,
+            ..options
+        };
+        assert_eq!(0, board_svg(&history, &options).matches("<text").count());
+    }
+
+    #[test]
+    fn test_palette() {
+        let history = vec![("scare", score_guess("scare", "crane"))];
+        let svg = board_svg(
+            &history,
+            &BoardOptions {
+                palette: Some(Palette::high_contrast()),
+                background: Background::Dark,
+                ..BoardOptions::default()
+            },
+        );
+        assert!(svg.contains("fill=\"#121213\""), "the dark background");
+        assert_eq!(2, svg.matches("fill=\"#f5793a\"").count());
+        assert_eq!(2, svg.matches("fill=\"#85c0f9\"").count());
+        assert!(!svg.contains("#538d4e"), "not the background's palette");
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="5" height="5" viewBox="0 0 5 5">"#,
+                "\n",
+                r##"<rect width="5" height="5" fill="#ffffff"/>"##,
+                "\n</svg>\n"
+            ),
+            board_svg(&[], &BoardOptions::default())
+        );
+    }
+}
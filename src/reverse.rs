@@ -0,0 +1,366 @@
+//! Reverse puzzles, where the colors are given and the guesses have to be found
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt};
+
+use rand::Rng;
+
+use crate::{
+    codec, filter, score_guess, state::NoWord, GameStatus, GuessError, LetterStatus, Wordle,
+};
+
+/// The fewest rows [`ReverseWordle::generate`] tries to give a puzzle
+pub const MIN_ROWS: usize = 3;
+
+/// The most games [`ReverseWordle::generate`] plays looking for a puzzle with
+/// at least [`MIN_ROWS`] rows
+const ATTEMPTS: usize = 100;
+
+/// Why a [`ReverseWordle`] couldn't be made
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReverseError {
+    /// The answer isn't an acceptable guess
+    Answer(GuessError),
+    /// There are no rows to fill in
+    NoRows,
+    /// A row doesn't have a color for each letter of the answer
+    WrongLength {
+        /// The row, counting from zero
+        row: usize,
+        /// The number of letters in the answer
+        expected: usize,
+        /// The number of colors in the row
+        actual: usize,
+    },
+    /// No acceptable guess gives a row's colors
+    Unsolvable {
+        /// The row, counting from zero
+        row: usize,
+    },
+}
+
+impl fmt::Display for ReverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Answer(error) => write!(f, "Invalid answer: {}", error),
+            Self::NoRows => write!(f, "There are no rows to fill in"),
+            Self::WrongLength {
+                row,
+                expected,
+                actual,
+            } => write!(f, "Row {} has {} colors, not {}", row + 1, actual, expected),
+            Self::Unsolvable { row } => write!(f, "No word gives the colors in row {}", row + 1),
+        }
+    }
+}
+
+impl Error for ReverseError {}
+
+/// Why a word couldn't fill a row of a [`ReverseWordle`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RowError {
+    /// There's no such row
+    NoSuchRow {
+        /// The number of rows there are
+        rows: usize,
+    },
+    /// The word isn't an acceptable guess
+    Invalid(GuessError),
+    /// The word gives other colors than the row's
+    Mismatch {
+        /// The colors the word gives
+        actual: Vec<LetterStatus>,
+    },
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchRow { rows } => write!(f, "There are only {} rows", rows),
+            Self::Invalid(error) => write!(f, "{}", error),
+            Self::Mismatch { actual } => {
+                write!(f, "That gives {} instead", codec::statuses_to_emoji(actual))
+            }
+        }
+    }
+}
+
+impl Error for RowError {}
+
+/// A puzzle where the answer and the colors of each guess are given, like a
+/// share grid, and the player has to find words that give those colors.
+///
+/// Rows can be filled in any order, and any acceptable guess that gives a
+/// row's colors fills it, not just the word originally played.
+#[derive(Debug)]
+pub struct ReverseWordle<'a> {
+    /// A game against the answer, to check words with
+    game: Wordle<'a>,
+    /// The colors each row has to give
+    target: Vec<Vec<LetterStatus>>,
+    /// The word filling each row, if it's been filled
+    rows: Vec<Option<&'a str>>,
+}
+
+impl<'a> ReverseWordle<'a> {
+    /// A puzzle against `answer`, with the word lists and settings of `game`,
+    /// where each row has to give the colors in `target`.
+    ///
+    /// Every row has to be solvable: some acceptable guess has to give its colors.
+    pub fn new(
+        game: &Wordle<'a, NoWord>,
+        answer: &str,
+        target: Vec<Vec<LetterStatus>>,
+    ) -> Result<Self, ReverseError> {
+        let mut board = game.fresh_board();
+        board.hard_mode = false;
+        board.reject_repeats = false;
+        let board = board.set_answer(answer).map_err(ReverseError::Answer)?;
+        if target.is_empty() {
+            return Err(ReverseError::NoRows);
+        }
+        let puzzle = Self {
+            rows: vec![None; target.len()],
+            game: board,
+            target,
+        };
+        for (row, colors) in puzzle.target.iter().enumerate() {
+            if colors.len() != puzzle.game.word_length() {
+                return Err(ReverseError::WrongLength {
+                    row,
+                    expected: puzzle.game.word_length(),
+                    actual: colors.len(),
+                });
+            }
+            if puzzle.solutions(row).is_empty() {
+                return Err(ReverseError::Unsolvable { row });
+            }
+        }
+        Ok(puzzle)
+    }
+
+    /// Make a puzzle against `answer` by playing random games against it, as
+    /// a player guessing words that could be the answer might, and taking the
+    /// colors of a game that was won.
+    ///
+    /// Games with at least [`MIN_ROWS`] guesses are preferred, so the puzzle
+    /// isn't over too quickly. Choices only depend on `rng`, so a seeded RNG
+    /// always gives the same puzzle.
+    pub fn generate<R: Rng + ?Sized>(
+        game: &Wordle<'a, NoWord>,
+        answer: &str,
+        rng: &mut R,
+    ) -> Result<Self, ReverseError> {
+        let mut template = game.fresh_board();
+        template.hard_mode = false;
+        let word = template
+            .check_answer(answer)
+            .map_err(ReverseError::Answer)?;
+        let mut best: Option<Vec<Vec<LetterStatus>>> = None;
+        for _ in 0..ATTEMPTS {
+            let mut board = template.fresh_board().start(word);
+            while board.status() == GameStatus::InProgress {
+                let Some(word) = board.random_consistent_guess(rng) else {
+                    break;
+                };
+                board.guess(&word).expect("Unplayed guesses are accepted");
+            }
+            if !matches!(board.status(), GameStatus::Won { .. }) {
+                continue;
+            }
+            let rows: Vec<Vec<LetterStatus>> = board
+                .history()
+                .iter()
+                .map(|(_, statuses)| statuses.clone())
+                .collect();
+            let enough = rows.len() >= MIN_ROWS;
+            if best.as_ref().is_none_or(|best| rows.len() > best.len()) {
+                best = Some(rows);
+            }
+            if enough {
+                break;
+            }
+        }
+        // in the unlikely case no game was won, the answer on its own is still a puzzle
+        let target =
+            best.unwrap_or_else(|| vec![vec![LetterStatus::Correct; template.word_length()]]);
+        Self::new(game, answer, target)
+    }
+
+    /// The answer the rows are played against
+    pub fn answer(&self) -> &'a str {
+        self.game.answer()
+    }
+
+    /// The acceptable guesses, which the rows are filled with
+    pub fn guess_list(&self) -> &'a [&'a str] {
+        self.game.guess_list()
+    }
+
+    /// The colors each row has to give
+    pub fn target(&self) -> &[Vec<LetterStatus>] {
+        &self.target
+    }
+
+    /// The word filling each row, or `None` for a row that hasn't been filled
+    pub fn rows(&self) -> &[Option<&'a str>] {
+        &self.rows
+    }
+
+    /// Fill row `row` (counting from zero) with `word`, if it's an acceptable
+    /// guess that gives the row's colors; a row already filled is refilled
+    pub fn try_row(&mut self, row: usize, word: &str) -> Result<(), RowError> {
+        let target = self.target.get(row).ok_or(RowError::NoSuchRow {
+            rows: self.target.len(),
+        })?;
+        let word = self.game.validate(word).map_err(RowError::Invalid)?;
+        let actual = score_guess(word, self.answer());
+        if actual != *target {
+            return Err(RowError::Mismatch { actual });
+        }
+        self.rows[row] = Some(word);
+        Ok(())
+    }
+
+    /// Empty row `row` again
+    pub fn clear_row(&mut self, row: usize) {
+        if let Some(word) = self.rows.get_mut(row) {
+            *word = None;
+        }
+    }
+
+    /// Whether every row has been filled
+    pub fn is_complete(&self) -> bool {
+        self.rows.iter().all(Option::is_some)
+    }
+
+    /// Every acceptable guess that gives row `row`'s colors, in the order of
+    /// the guess list; empty if there's no such row
+    pub fn solutions(&self, row: usize) -> Vec<&'a str> {
+        let Some(target) = self.target.get(row) else {
+            return Vec::new();
+        };
+        self.game
+            .guess_list()
+            .iter()
+            .copied()
+            .filter(|guess| filter::is_consistent(self.answer(), guess, target))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use LetterStatus::*;
+
+    const WORDS: [&str; 6] = ["crane", "slate", "trace", "irate", "share", "stare"];
+
+    fn template() -> Wordle<'static, NoWord> {
+        Wordle::new(&WORDS, &WORDS)
+    }
+
+    #[test]
+    fn test_new() {
+        let game = template();
+        let target = vec![score_guess("slate", "trace"), vec![Correct; 5]];
+        let puzzle = ReverseWordle::new(&game, "trace", target.clone()).unwrap();
+        assert_eq!("trace", puzzle.answer());
+        assert_eq!(&target[..], puzzle.target());
+        assert_eq!(&[None, None][..], puzzle.rows());
+
+        assert_eq!(
+            Err(ReverseError::Answer(GuessError::NotInWordList)),
+            ReverseWordle::new(&game, "zzzzz", target.clone()).map(|_| ())
+        );
+        assert_eq!(
+            Err(ReverseError::NoRows),
+            ReverseWordle::new(&game, "trace", Vec::new()).map(|_| ())
+        );
+        assert_eq!(
+            Err(ReverseError::WrongLength {
+                row: 1,
+                expected: 5,
+                actual: 4
+            }),
+            ReverseWordle::new(&game, "trace", vec![vec![Correct; 5], vec![Correct; 4]])
+                .map(|_| ())
+        );
+        // nothing in the word list is all yellow against "trace"
+        assert_eq!(
+            Err(ReverseError::Unsolvable { row: 0 }),
+            ReverseWordle::new(&game, "trace", vec![vec![InWord; 5]]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_try_row() {
+        let game = template();
+        let target = vec![score_guess("slate", "trace"), vec![Correct; 5]];
+        let mut puzzle = ReverseWordle::new(&game, "trace", target).unwrap();
+
+        assert_eq!(vec!["slate", "share"], puzzle.solutions(0));
+        assert_eq!(vec!["trace"], puzzle.solutions(1));
+        assert!(puzzle.solutions(2).is_empty());
+
+        assert_eq!(
+            Err(RowError::Mismatch {
+                actual: score_guess("crane", "trace")
+            }),
+            puzzle.try_row(0, "crane")
+        );
+        assert_eq!(
+            Err(RowError::Invalid(GuessError::NotInWordList)),
+            puzzle.try_row(0, "zzzzz")
+        );
+        assert_eq!(
+            Err(RowError::NoSuchRow { rows: 2 }),
+            puzzle.try_row(2, "trace")
+        );
+
+        // rows can be filled in any order, with any word that fits
+        puzzle.try_row(1, "trace").unwrap();
+        assert!(!puzzle.is_complete());
+        puzzle.try_row(0, "share").unwrap();
+        assert_eq!(&[Some("share"), Some("trace")][..], puzzle.rows());
+        assert!(puzzle.is_complete());
+
+        puzzle.clear_row(0);
+        assert_eq!(&[None, Some("trace")][..], puzzle.rows());
+    }
+
+    #[test]
+    fn test_generate() {
+        let game = template();
+        let generate = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            ReverseWordle::generate(&game, "share", &mut rng).unwrap()
+        };
+        let puzzle = generate(7);
+        assert_eq!("share", puzzle.answer());
+        assert_eq!(Some(&vec![Correct; 5]), puzzle.target().last());
+        assert!(puzzle.target().len() >= MIN_ROWS);
+        assert_eq!(puzzle.target(), generate(7).target());
+
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            Err(ReverseError::Answer(GuessError::NotInWordList)),
+            ReverseWordle::generate(&game, "zzzzz", &mut rng).map(|_| ())
+        );
+    }
+}
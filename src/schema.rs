@@ -0,0 +1,142 @@
+//! JSON Schemas for the types that are saved or sent over the network, for clients in other languages
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use schemars::{schema_for, Schema};
+
+use crate::{
+    protocol::{ClientMessage, ServerMessage},
+    race::RaceSnapshot,
+    sim::Summary,
+    stats::{GameRecord, Stats},
+    transcript::Transcript,
+};
+
+/// The schema for each type that's saved or sent, by a name for its file
+pub fn schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        ("transcript", schema_for!(Transcript)),
+        ("stats", schema_for!(Stats)),
+        ("game-record", schema_for!(GameRecord)),
+        ("race-snapshot", schema_for!(RaceSnapshot)),
+        ("sim-report", schema_for!(Summary)),
+        ("client-message", schema_for!(ClientMessage)),
+        ("server-message", schema_for!(ServerMessage)),
+    ]
+}
+
+/// Write each schema to `<name>.schema.json` in `dir`, creating it if needed,
+/// and get back the files written
+pub fn write(dir: &Path, schemas: &[(&str, Schema)]) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    schemas
+        .iter()
+        .map(|(name, schema)| {
+            let path = dir.join(format!("{}.schema.json", name));
+            fs::write(&path, serde_json::to_string_pretty(schema)?)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Wordle;
+    use serde_json::Value;
+
+    /// Check that a value serialized as JSON matches the schema with the given name
+    fn assert_valid(name: &str, value: Value) {
+        let schema = schemas()
+            .into_iter()
+            .find(|(schema, _)| *schema == name)
+            .map(|(_, schema)| schema)
+            .unwrap();
+        let validator = jsonschema::validator_for(schema.as_value()).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&value)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{} doesn't match: {:?}", name, errors);
+    }
+
+    #[test]
+    fn test_transcript() {
+        let words = ["crane", "slate", "trace"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        game.hint().unwrap();
+        game.guess("slate").unwrap();
+        let transcript = Transcript {
+            seed: Some(42),
+            ..game.transcript()
+        };
+        assert_valid("transcript", serde_json::to_value(&transcript).unwrap());
+        assert_valid(
+            "transcript",
+            serde_json::to_value(transcript.hide_answer()).unwrap(),
+        );
+
+        // a transcript missing what it needs doesn't
+        let schema = &schemas()[0].1;
+        let validator = jsonschema::validator_for(schema.as_value()).unwrap();
+        assert!(!validator.is_valid(&serde_json::json!({ "answer": "crane" })));
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::new();
+        stats.record_status(crate::GameStatus::Won { guesses: 3 });
+        stats.record_status(crate::GameStatus::Lost);
+        assert_valid("stats", serde_json::to_value(&stats).unwrap());
+    }
+
+    #[test]
+    fn test_sim_report() {
+        let words = ["crane", "slate", "trace"];
+        let game = Wordle::new(&words, &words);
+        let outcomes = crate::sim::simulate(&game, |_| crate::solver::EntropyStrategy::new(), 1);
+        let report = crate::sim::Report::new("entropy", &outcomes, 6, std::time::Duration::ZERO);
+        let summary = Summary {
+            version: crate::sim::REPORT_VERSION,
+            seed: 7,
+            guesses: 3,
+            answers: 3,
+            max_guesses: 6,
+            reports: vec![report.clone(), report],
+            head_to_head: vec![crate::sim::HeadToHead::new(
+                "entropy", &outcomes, "entropy", &outcomes, 6,
+            )],
+        };
+        assert_valid("sim-report", serde_json::to_value(&summary).unwrap());
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = std::env::temp_dir().join(format!("wordle-schema-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let paths = write(&dir, &schemas()).unwrap();
+        assert_eq!(schemas().len(), paths.len());
+        assert!(paths.contains(&dir.join("transcript.schema.json")));
+        let written: Value = serde_json::from_str(&fs::read_to_string(&paths[0]).unwrap()).unwrap();
+        assert_eq!(&written, schemas()[0].1.as_value());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,382 @@
+//! Spoiler-free share grids, like the ones posted after finishing a _Wordle_
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{codec, messages::Messages, state::Started, GameStatus, LetterStatus, Wordle};
+
+/// The tiles used to draw each [`LetterStatus`] in a share grid.
+///
+/// Tiles can be any string, e.g. an emoji with a variation selector or a
+/// `:custom_emoji:` shortcode.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TileSet<'a> {
+    /// Tile for a letter in the correct position
+    pub correct: &'a str,
+    /// Tile for a letter in the word, but in the wrong position
+    pub in_word: &'a str,
+    /// Tile for a letter not in the word
+    pub not_in_word: &'a str,
+}
+
+impl TileSet<'static> {
+    /// Green, yellow, and black squares, like the official game's dark theme
+    pub const fn classic() -> Self {
+        Self {
+            correct: codec::GREEN_SQ,
+            in_word: codec::YELLOW_SQ,
+            not_in_word: codec::BLACK_SQ,
+        }
+    }
+
+    /// Green, yellow, and white squares, like the official game's light theme
+    pub const fn light() -> Self {
+        Self {
+            not_in_word: codec::WHITE_SQ,
+            ..Self::classic()
+        }
+    }
+
+    /// Orange and blue squares, like the official game's high contrast mode
+    pub const fn high_contrast() -> Self {
+        Self {
+            correct: "🟧",
+            in_word: "🟦",
+            not_in_word: codec::BLACK_SQ,
+        }
+    }
+
+    /// Plain ASCII characters, for places without emoji support
+    pub const fn ascii() -> Self {
+        Self {
+            correct: "#",
+            in_word: "+",
+            not_in_word: ".",
+        }
+    }
+}
+
+impl Default for TileSet<'static> {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl<'a> TileSet<'a> {
+    /// Get the tile for a [`LetterStatus`]
+    pub fn tile(&self, status: &LetterStatus) -> &'a str {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::InWord => self.in_word,
+            LetterStatus::NotInWord => self.not_in_word,
+        }
+    }
+
+    /// The display width of the widest tile in the set
+    fn width(&self) -> usize {
+        [self.correct, self.in_word, self.not_in_word]
+            .iter()
+            .map(|tile| tile.width())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// The score at the top of a share grid, e.g. `3/6`, `X/6` for a loss, or `3/6*` in hard mode
+pub fn score_line(status: GameStatus, max_guesses: usize, hard_mode: bool) -> String {
+    let guesses = match status {
+        GameStatus::Won { guesses } => guesses.to_string(),
+        GameStatus::Lost | GameStatus::InProgress => "X".to_string(),
+    };
+    let hard = if hard_mode { "*" } else { "" };
+    format!("{}/{}{}", guesses, max_guesses, hard)
+}
+
+/// The first line of the share text, e.g. `Wordle #196 3/6*`.
+///
+/// The number is left out if there's no puzzle number, and games where hints
+/// were used or that were played with assistance say so in `messages`' language,
+/// e.g. `Wordle 4/6 (1 hint)` or `Wordle 3/6 (1 hint, assisted)`.
+pub fn share_header(
+    messages: &Messages,
+    puzzle: Option<u32>,
+    status: GameStatus,
+    max_guesses: usize,
+    hard_mode: bool,
+    hints: usize,
+    assisted: bool,
+) -> String {
+    let title = match puzzle {
+        Some(puzzle) => format!("Wordle #{}", puzzle),
+        None => String::from("Wordle"),
+    };
+    header(
+        messages,
+        title,
+        status,
+        max_guesses,
+        hard_mode,
+        hints,
+        assisted,
+    )
+}
+
+/// The first line of the share text, after the title
+fn header(
+    messages: &Messages,
+    title: String,
+    status: GameStatus,
+    max_guesses: usize,
+    hard_mode: bool,
+    hints: usize,
+    assisted: bool,
+) -> String {
+    let mut header = title;
+    header.push(' ');
+    header.push_str(&score_line(status, max_guesses, hard_mode));
+
+    let mut notes = Vec::new();
+    if hints > 0 {
+        notes.push(messages.format("share-hints", &[("count", hints.into())]));
+    }
+    if assisted {
+        notes.push(messages.format("share-assisted", &[]));
+    }
+    if !notes.is_empty() {
+        header.push_str(&format!(" ({})", notes.join(", ")));
+    }
+    header
+}
+
+/// The complete text to share for a game, like the official game's: a header,
+/// then a row of tiles for each guess.
+///
+/// A game that isn't a daily puzzle but has a [number](Wordle::game_number)
+/// gives its seed and number instead, e.g. `Wordle seed 42 game 7 3/6`, so
+/// anyone can play the same word.
+pub fn share_text<S: Started>(
+    messages: &Messages,
+    game: &Wordle<'_, S>,
+    puzzle: Option<u32>,
+    tiles: &TileSet<'_>,
+) -> String {
+    let title = match (puzzle, game.game_number()) {
+        (Some(puzzle), _) => format!("Wordle #{}", puzzle),
+        (None, Some(number)) => format!("Wordle seed {} game {}", game.seed(), number),
+        (None, None) => String::from("Wordle"),
+    };
+    format!(
+        "{}\n{}",
+        header(
+            messages,
+            title,
+            game.status(),
+            game.max_guesses(),
+            game.hard_mode(),
+            game.hints().len(),
+            game.assist()
+        ),
+        share_grid(game.history(), tiles)
+    )
+}
+
+/// Build the rows of a share grid from the feedback for each guess, without any letters.
+///
+/// Tiles narrower than the widest tile in `tiles` are padded with spaces, so
+/// the columns stay aligned regardless of how many codepoints each tile is.
+/// Rows are separated by newlines; there is no trailing newline.
+pub fn share_grid(history: &[(&str, Vec<LetterStatus>)], tiles: &TileSet<'_>) -> String {
+    let width = tiles.width();
+    history
+        .iter()
+        .map(|(_, statuses)| {
+            statuses
+                .iter()
+                .map(|status| {
+                    let tile = tiles.tile(status);
+                    format!("{}{}", tile, " ".repeat(width - tile.width()))
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_guess;
+
+    /// A game against "crane" that took three guesses
+    fn history() -> Vec<(&'static str, Vec<LetterStatus>)> {
+        ["scare", "trace", "crane"]
+            .into_iter()
+            .map(|guess| (guess, score_guess(guess, "crane")))
+            .collect()
+    }
+
+    #[test]
+    fn test_presets() {
+        let history = history();
+        assert_eq!(
+            "⬛🟨🟩🟨🟩\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩",
+            share_grid(&history, &TileSet::default())
+        );
+        assert_eq!(
+            "⬜🟨🟩🟨🟩\n⬜🟩🟩🟨🟩\n🟩🟩🟩🟩🟩",
+            share_grid(&history, &TileSet::light())
+        );
+        assert_eq!(
+            "⬛🟦🟧🟦🟧\n⬛🟧🟧🟦🟧\n🟧🟧🟧🟧🟧",
+            share_grid(&history, &TileSet::high_contrast())
+        );
+        assert_eq!(
+            ".+#+#\n.##+#\n#####",
+            share_grid(&history, &TileSet::ascii())
+        );
+        assert_eq!("", share_grid(&[], &TileSet::default()));
+    }
+
+    #[test]
+    fn test_matches_codec() {
+        let history = history();
+        let expected: Vec<String> = history
+            .iter()
+            .map(|(_, statuses)| codec::statuses_to_emoji(statuses))
+            .collect();
+        assert_eq!(
+            expected.join("\n"),
+            share_grid(&history, &TileSet::classic())
+        );
+    }
+
+    #[test]
+    fn test_score_line() {
+        assert_eq!("3/6", score_line(GameStatus::Won { guesses: 3 }, 6, false));
+        assert_eq!("3/6*", score_line(GameStatus::Won { guesses: 3 }, 6, true));
+        assert_eq!("X/6", score_line(GameStatus::Lost, 6, false));
+        assert_eq!("X/8*", score_line(GameStatus::Lost, 8, true));
+    }
+
+    #[test]
+    fn test_share_text() {
+        let english = Messages::english();
+        let guesses = ["scare", "trace", "crane", "slate"];
+        let mut game = Wordle::builder()
+            .guesses(&guesses)
+            .answers(&guesses[2..3])
+            .hard_mode(true)
+            .build()
+            .unwrap()
+            .choose_word();
+        for guess in ["scare", "trace", "crane"] {
+            game.guess(guess).unwrap();
+        }
+        assert_eq!(
+            "Wordle #196 3/6*\n⬛🟨🟩🟨🟩\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩",
+            share_text(english, &game, Some(196), &TileSet::classic())
+        );
+
+        let mut game = Wordle::builder()
+            .guesses(&guesses)
+            .answers(&guesses[2..3])
+            .max_guesses(2)
+            .seed(42)
+            .build()
+            .unwrap()
+            .choose_word();
+        game.guess("slate").unwrap();
+        game.guess("trace").unwrap();
+        assert_eq!(
+            "Wordle seed 42 game 1 X/2\n..#.#\n.##+#",
+            share_text(english, &game, None, &TileSet::ascii())
+        );
+
+        let mut game = game.clear().choose_word();
+        game.hint().unwrap();
+        game.guess("crane").unwrap();
+        assert_eq!(
+            "Wordle seed 42 game 2 1/2 (1 hint)\n#####",
+            share_text(english, &game, None, &TileSet::ascii())
+        );
+        let mut game = game.clear().set_answer("crane").unwrap();
+        game.guess("crane").unwrap();
+        assert_eq!(
+            "Wordle 1/2\n#####",
+            share_text(english, &game, None, &TileSet::ascii())
+        );
+        assert_eq!(
+            "Wordle #7 X/6* (2 hints)",
+            share_header(english, Some(7), GameStatus::Lost, 6, true, 2, false)
+        );
+        assert_eq!(
+            "Wordle 3/6 (assisted)",
+            share_header(
+                english,
+                None,
+                GameStatus::Won { guesses: 3 },
+                6,
+                false,
+                0,
+                true
+            )
+        );
+        assert_eq!(
+            "Wordle #7 4/6 (1 hint, assisted)",
+            share_header(
+                english,
+                Some(7),
+                GameStatus::Won { guesses: 4 },
+                6,
+                false,
+                1,
+                true
+            )
+        );
+        // the notes are in the player's language
+        assert_eq!(
+            "Wordle #7 X/6 (2 indices, assisté)",
+            share_header(
+                Messages::builtin("fr").unwrap(),
+                Some(7),
+                GameStatus::Lost,
+                6,
+                false,
+                2,
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_alignment() {
+        // a multi-codepoint tile (with a variation selector) next to single-codepoint ones
+        let tiles = TileSet {
+            correct: "G",
+            in_word: "⬛\u{fe0f}",
+            not_in_word: "-",
+        };
+        let grid = share_grid(&history(), &tiles);
+        assert_eq!(
+            "- ⬛\u{fe0f}G ⬛\u{fe0f}G \n- G G ⬛\u{fe0f}G \nG G G G G ",
+            grid
+        );
+
+        let widths: Vec<usize> = grid.lines().map(|l| l.width()).collect();
+        assert_eq!(vec![10, 10, 10], widths);
+    }
+}
@@ -0,0 +1,436 @@
+//! Playing strategies against every answer, to see how well they do
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    cmp,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{solver::Strategy, state::NoWord, transcript::Transcript, GameStatus, Wordle};
+
+/// The version of the [`Summary`] format; bump it if the format changes
+pub const REPORT_VERSION: u32 = 2;
+
+/// Games won in this many guesses or more are listed in [`Report::slow`]
+pub const SLOW_GUESSES: usize = 6;
+
+/// How a strategy did against one answer
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Outcome<'a> {
+    /// The answer played against
+    pub answer: &'a str,
+    /// The number of guesses it took to find the answer, or `None` if it wasn't found
+    pub guesses: Option<usize>,
+    /// How long the game took to play
+    pub time: Duration,
+    /// The whole game, to look over afterwards
+    pub transcript: Transcript,
+}
+
+impl Outcome<'_> {
+    /// The number of guesses the game took, counting a loss as one more than
+    /// the `max_guesses` allowed, so outcomes can be compared
+    pub fn cost(&self, max_guesses: usize) -> usize {
+        self.guesses.unwrap_or(max_guesses + 1)
+    }
+}
+
+/// Play a game to the end with `strategy`, and get back the number of guesses
+/// it took to win, or `None` if it was lost.
+///
+/// A strategy that runs out of words, or makes a guess that isn't allowed,
+/// loses the game.
+pub fn play(game: &mut Wordle<'_>, strategy: &mut dyn Strategy) -> Option<usize> {
+    while game.status() == GameStatus::InProgress {
+        let guess = strategy.next_guess(game)?;
+        game.guess(&guess).ok()?;
+    }
+    match game.status() {
+        GameStatus::Won { guesses } => Some(guesses),
+        _ => None,
+    }
+}
+
+/// Play against every answer in `game`'s answer list, with the same settings
+/// as `game`, and get back the outcomes in the order of the answer list.
+///
+/// The games are shared out between `threads` threads (at least one). Each
+/// game gets a new strategy from `new_strategy`, which is given the game's
+/// position in the answer list; so a seeded strategy can be given a seed of its
+/// own for each game, and make the same choices however the games are shared out.
+pub fn simulate<'a, S, F>(
+    game: &Wordle<'a, NoWord>,
+    new_strategy: F,
+    threads: usize,
+) -> Vec<Outcome<'a>>
+where
+    S: Strategy,
+    F: Fn(usize) -> S + Sync,
+{
+    let answers = game.answers();
+    let next = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Outcome<'a>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&answer) = answers.get(i) else {
+                            break;
+                        };
+                        let mut board = game.fresh_board().start(answer);
+                        let start = Instant::now();
+                        let guesses = play(&mut board, &mut new_strategy(i));
+                        let outcome = Outcome {
+                            answer,
+                            guesses,
+                            time: start.elapsed(),
+                            transcript: board.transcript(),
+                        };
+                        outcomes.push((i, outcome));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Simulation thread panicked"))
+            .collect()
+    });
+    outcomes.sort_unstable_by_key(|(i, _)| *i);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// A summary of how a strategy did against every answer
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Report {
+    /// The name of the strategy
+    pub strategy: String,
+    /// The number of games played
+    pub games: usize,
+    /// The number of games won
+    pub wins: usize,
+    /// The average number of guesses in the games won, or `None` if none were
+    pub average_guesses: Option<f64>,
+    /// The number of games won in one guess, two guesses, and so on, up to the
+    /// most guesses allowed
+    pub distribution: Vec<usize>,
+    /// The answers that weren't found, in the order of the answer list
+    pub failures: Vec<String>,
+    /// The answers only found in [`SLOW_GUESSES`] guesses or more, in the
+    /// order of the answer list
+    pub slow: Vec<String>,
+    /// The wall-clock time the games took, in seconds
+    pub seconds: f64,
+    /// The average time each game took to play, in seconds; unlike
+    /// [`seconds`](Self::seconds), this doesn't depend on how many games were
+    /// played at once
+    pub seconds_per_game: f64,
+}
+
+impl Report {
+    /// Summarize the outcomes of games with up to `max_guesses` guesses, which
+    /// took `elapsed` to play
+    pub fn new(
+        strategy: &str,
+        outcomes: &[Outcome<'_>],
+        max_guesses: usize,
+        elapsed: Duration,
+    ) -> Self {
+        let mut distribution = vec![0; max_guesses];
+        let mut failures = Vec::new();
+        let mut slow = Vec::new();
+        for outcome in outcomes {
+            match outcome.guesses {
+                Some(guesses) => {
+                    distribution[guesses - 1] += 1;
+                    if guesses >= SLOW_GUESSES {
+                        slow.push(outcome.answer.to_owned());
+                    }
+                }
+                None => failures.push(outcome.answer.to_owned()),
+            }
+        }
+
+        let wins = outcomes.len() - failures.len();
+        let total: usize = distribution
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (i + 1) * count)
+            .sum();
+        let time: Duration = outcomes.iter().map(|outcome| outcome.time).sum();
+        Self {
+            strategy: strategy.to_owned(),
+            games: outcomes.len(),
+            wins,
+            average_guesses: (wins > 0).then(|| total as f64 / wins as f64),
+            distribution,
+            failures,
+            slow,
+            seconds: elapsed.as_secs_f64(),
+            seconds_per_game: match outcomes.len() {
+                0 => 0.0,
+                games => time.as_secs_f64() / games as f64,
+            },
+        }
+    }
+}
+
+/// How two strategies did against each other, answer by answer
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HeadToHead {
+    /// The name of the first strategy
+    pub a: String,
+    /// The name of the second strategy
+    pub b: String,
+    /// The number of answers the first strategy found in fewer guesses
+    pub a_better: usize,
+    /// The number of answers the second strategy found in fewer guesses
+    pub b_better: usize,
+    /// The number of answers both found in the same number of guesses, or
+    /// both failed on
+    pub ties: usize,
+    /// How many more guesses the first strategy took on average, counting a
+    /// loss as one more guess than allowed; below zero if it took fewer
+    pub difference: f64,
+}
+
+impl HeadToHead {
+    /// Compare the outcomes of strategies `a` and `b` against the same
+    /// answers, in games with up to `max_guesses` guesses.
+    ///
+    /// # Panics
+    ///
+    /// If the strategies weren't played against the same answers in the same order.
+    pub fn new(
+        a: &str,
+        a_outcomes: &[Outcome<'_>],
+        b: &str,
+        b_outcomes: &[Outcome<'_>],
+        max_guesses: usize,
+    ) -> Self {
+        assert!(
+            a_outcomes.len() == b_outcomes.len()
+                && a_outcomes
+                    .iter()
+                    .zip(b_outcomes)
+                    .all(|(a, b)| a.answer == b.answer),
+            "Strategies must be played against the same answers"
+        );
+        let (mut a_better, mut b_better, mut ties) = (0, 0, 0);
+        let mut difference = 0;
+        for (a, b) in a_outcomes.iter().zip(b_outcomes) {
+            let (a, b) = (a.cost(max_guesses), b.cost(max_guesses));
+            match a.cmp(&b) {
+                cmp::Ordering::Less => a_better += 1,
+                cmp::Ordering::Greater => b_better += 1,
+                cmp::Ordering::Equal => ties += 1,
+            }
+            difference += a as isize - b as isize;
+        }
+        Self {
+            a: a.to_owned(),
+            b: b.to_owned(),
+            a_better,
+            b_better,
+            ties,
+            difference: match a_outcomes.len() {
+                0 => 0.0,
+                games => difference as f64 / games as f64,
+            },
+        }
+    }
+}
+
+/// Everything a simulation found, as written to a JSON report.
+///
+/// The format is versioned by [`REPORT_VERSION`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Summary {
+    /// The version of the report format
+    pub version: u32,
+    /// The seed seeded strategies were given
+    pub seed: u64,
+    /// The number of acceptable guesses
+    pub guesses: usize,
+    /// The number of answers, each played once by every strategy
+    pub answers: usize,
+    /// The number of guesses allowed in each game
+    pub max_guesses: usize,
+    /// How each strategy did, in the order given
+    pub reports: Vec<Report>,
+    /// How each pair of strategies did against each other, in the order given
+    pub head_to_head: Vec<HeadToHead>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{EntropyStrategy, RandomStrategy};
+
+    const GUESSES: [&str; 6] = ["crane", "trace", "brace", "grace", "slate", "toast"];
+    const ANSWERS: [&str; 4] = ["crane", "trace", "brace", "grace"];
+
+    /// A strategy that always guesses the same word
+    #[derive(Debug)]
+    struct Stubborn;
+
+    impl Strategy for Stubborn {
+        fn next_guess(&mut self, _game: &Wordle<'_>) -> Option<String> {
+            Some("toast".into())
+        }
+    }
+
+    #[test]
+    fn test_simulate() {
+        let game = Wordle::new(&GUESSES, &ANSWERS);
+        let outcomes = simulate(&game, |_| EntropyStrategy::new(), 3);
+        let answers: Vec<&str> = outcomes.iter().map(|o| o.answer).collect();
+        assert_eq!(ANSWERS.to_vec(), answers);
+        assert!(outcomes.iter().all(|o| o.guesses.is_some()));
+
+        // every game is kept, to look over afterwards
+        let words: Vec<&str> = outcomes[1]
+            .transcript
+            .guesses
+            .iter()
+            .map(|guess| guess.word.as_str())
+            .collect();
+        assert_eq!(Some("trace"), outcomes[1].transcript.answer.as_deref());
+        assert_eq!(outcomes[1].guesses, Some(words.len()));
+        assert_eq!(Some(&"trace"), words.last());
+
+        // seeded strategies play the same however many threads there are
+        let random = |threads| {
+            simulate(&game, |i| RandomStrategy::seeded(i as u64), threads)
+                .into_iter()
+                .map(|o| {
+                    let words: Vec<String> =
+                        o.transcript.guesses.into_iter().map(|g| g.word).collect();
+                    (o.answer, o.guesses, words)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(random(1), random(4));
+
+        let outcomes = simulate(&game, |_| Stubborn, 2);
+        assert!(outcomes.iter().all(|o| o.guesses.is_none()));
+    }
+
+    /// An outcome of a game that took `millis` milliseconds
+    fn outcome(answer: &str, guesses: Option<usize>, millis: u64) -> Outcome<'_> {
+        let game = Wordle::new(&GUESSES, &ANSWERS).set_answer(answer).unwrap();
+        Outcome {
+            answer,
+            guesses,
+            time: Duration::from_millis(millis),
+            transcript: game.transcript(),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let outcomes = [
+            outcome("crane", Some(2), 100),
+            outcome("trace", None, 300),
+            outcome("brace", Some(6), 200),
+            outcome("grace", Some(3), 200),
+        ];
+        let report = Report::new("test", &outcomes, 6, Duration::from_millis(1500));
+        assert_eq!(
+            Report {
+                strategy: "test".into(),
+                games: 4,
+                wins: 3,
+                average_guesses: Some(11.0 / 3.0),
+                distribution: vec![0, 1, 1, 0, 0, 1],
+                failures: vec!["trace".into()],
+                slow: vec!["brace".into()],
+                seconds: 1.5,
+                seconds_per_game: 0.2,
+            },
+            report
+        );
+
+        let report = Report::new("test", &outcomes[1..2], 6, Duration::ZERO);
+        assert_eq!(None, report.average_guesses);
+    }
+
+    #[test]
+    fn test_head_to_head() {
+        let a = [
+            outcome("crane", Some(2), 0),
+            outcome("trace", None, 0),
+            outcome("brace", Some(3), 0),
+            outcome("grace", Some(4), 0),
+        ];
+        let b = [
+            outcome("crane", Some(3), 0),
+            outcome("trace", Some(5), 0),
+            outcome("brace", Some(3), 0),
+            outcome("grace", None, 0),
+        ];
+        assert_eq!(
+            HeadToHead {
+                a: "a".into(),
+                b: "b".into(),
+                a_better: 2,
+                b_better: 1,
+                ties: 1,
+                // -1 for crane, +2 for trace, -3 for grace
+                difference: -0.5,
+            },
+            HeadToHead::new("a", &a, "b", &b, 6)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "same answers")]
+    fn test_head_to_head_mismatch() {
+        let a = [outcome("crane", Some(2), 0)];
+        let b = [outcome("trace", Some(2), 0)];
+        HeadToHead::new("a", &a, "b", &b, 6);
+    }
+
+    #[test]
+    fn test_summary_round_trip() {
+        let outcomes = [outcome("crane", Some(2), 250), outcome("trace", None, 500)];
+        let summary = Summary {
+            version: REPORT_VERSION,
+            seed: 7,
+            guesses: GUESSES.len(),
+            answers: 2,
+            max_guesses: 6,
+            reports: vec![
+                Report::new("a", &outcomes, 6, Duration::from_millis(750)),
+                Report::new("b", &outcomes[..1], 6, Duration::from_secs(1)),
+            ],
+            head_to_head: vec![HeadToHead::new("a", &outcomes, "b", &outcomes, 6)],
+        };
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(summary, serde_json::from_str::<Summary>(&json).unwrap());
+    }
+}
@@ -0,0 +1,1492 @@
+//! Guess suggestions for solving a _Wordle_
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use directories::ProjectDirs;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{codec, filter, score_guess, LetterStatus, Wordle};
+use cache::SolverCache;
+
+pub mod cache;
+
+/// First line of an openers cache file; bump the version if the format changes
+const CACHE_HEADER: &str = "wordle-openers v1";
+
+/// A suggested guess along with how good of a guess it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// The suggested word
+    pub word: String,
+    /// The expected information (in bits) gained by guessing `word`
+    pub score: f64,
+    /// Why `word` is a good guess, if that was asked for
+    pub explanation: Option<Explanation>,
+}
+
+/// What guessing a word would tell the player, to explain a [`Suggestion`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    /// The expected information (in bits) gained by the guess
+    pub bits: f64,
+    /// The number of candidates expected to be left after the guess, not
+    /// counting the guess itself if it's the answer
+    pub expected_remaining: f64,
+    /// The most candidates that could be left after the guess
+    pub worst_case: usize,
+    /// The number of candidates the guess is itself; 1 if it could be the
+    /// answer, and 0 otherwise
+    pub possible_answers: usize,
+    /// The likeliest feedback for the guess, with the number of candidates
+    /// that would give it, likeliest first; at most [`Explanation::PATTERNS`]
+    pub patterns: Vec<(Vec<LetterStatus>, usize)>,
+}
+
+impl Explanation {
+    /// The number of patterns given in an explanation
+    pub const PATTERNS: usize = 3;
+}
+
+/// Group the candidates by the (packed) feedback they would give for `guess`,
+/// and count each group
+fn buckets(guess: &str, candidates: &[&str]) -> HashMap<u32, usize> {
+    let mut buckets: HashMap<u32, usize> = HashMap::new();
+    for candidate in candidates {
+        let pattern = codec::statuses_to_packed(&score_guess(guess, candidate));
+        *buckets.entry(pattern).or_insert(0) += 1;
+    }
+    buckets
+}
+
+/// Compute the expected information (in bits) gained by guessing `guess` when
+/// the answer is one of `candidates`.
+pub fn entropy(guess: &str, candidates: &[&str]) -> f64 {
+    if candidates.is_empty() {
+        return 0.0;
+    }
+    bits(buckets(guess, candidates), candidates.len())
+}
+
+/// The expected information (in bits) from splitting `total` candidates into `buckets`
+fn bits(buckets: HashMap<u32, usize>, total: usize) -> f64 {
+    // add the buckets up in order of size, so guesses that split the
+    // candidates the same way score exactly the same
+    let mut sizes: Vec<usize> = buckets.into_values().collect();
+    sizes.sort_unstable();
+
+    let total = total as f64;
+    sizes
+        .into_iter()
+        .map(|n| {
+            let n = n as f64;
+            n / total * (total / n).log2()
+        })
+        .sum()
+}
+
+/// Explain what guessing `guess` would tell the player, when the answer is one
+/// of `candidates`
+pub fn explain(guess: &str, candidates: &[&str]) -> Explanation {
+    let buckets = buckets(guess, candidates);
+    let solved = codec::statuses_to_packed(&vec![LetterStatus::Correct; guess.chars().count()]);
+    let possible_answers = buckets.get(&solved).copied().unwrap_or(0);
+
+    // the likeliest patterns first, in the order of their packed values when they're as likely
+    let mut patterns: Vec<(u32, usize)> = buckets.iter().map(|(&p, &n)| (p, n)).collect();
+    patterns.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let left = patterns.iter().filter(|&&(pattern, _)| pattern != solved);
+    let expected_remaining = match candidates.len() {
+        0 => 0.0,
+        total => {
+            let squares: usize = left.clone().map(|&(_, n)| n * n).sum();
+            squares as f64 / total as f64
+        }
+    };
+    let worst_case = left.map(|&(_, n)| n).max().unwrap_or(0);
+    patterns.truncate(Explanation::PATTERNS);
+
+    Explanation {
+        bits: bits(buckets, candidates.len()),
+        expected_remaining,
+        worst_case,
+        possible_answers,
+        patterns: patterns
+            .into_iter()
+            .map(|(pattern, n)| {
+                let statuses = codec::statuses_from_packed(pattern, guess.chars().count())
+                    .expect("The pattern was packed from as many statuses");
+                (statuses, n)
+            })
+            .collect(),
+    }
+}
+
+/// Find the `k` best guesses when the answer is one of `candidates`.
+///
+/// Suggestions are ordered best-first. Ties are broken in favor of words that
+/// could be the answer themselves, then alphabetically.
+///
+/// With `explain`, each suggestion comes with an [`Explanation`]; these are only
+/// worked out for the `k` suggestions given back.
+pub fn suggest(candidates: &[&str], guesses: &[&str], k: usize, explain: bool) -> Vec<Suggestion> {
+    let possible: HashSet<&str> = candidates.iter().copied().collect();
+    let mut suggestions: Vec<Suggestion> = guesses
+        .iter()
+        .map(|&word| Suggestion {
+            word: word.into(),
+            score: entropy(word, candidates),
+            explanation: None,
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| {
+                let a_possible = possible.contains(a.word.as_str());
+                let b_possible = possible.contains(b.word.as_str());
+                b_possible.cmp(&a_possible)
+            })
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    suggestions.truncate(k);
+    if explain {
+        explain_all(&mut suggestions, candidates);
+    }
+    suggestions
+}
+
+/// Add an [`Explanation`] to each of `suggestions`, e.g. ones from
+/// [`best_openers_cached`], which doesn't keep them
+pub fn explain_all(suggestions: &mut [Suggestion], candidates: &[&str]) {
+    for suggestion in suggestions {
+        suggestion.explanation = Some(explain(&suggestion.word, candidates));
+    }
+}
+
+/// Find the `k` best first guesses against the given answer list.
+///
+/// Every word in `guesses` is scored against every word in `answers`, so this
+/// is expensive for the full dictionary; see [`best_openers_cached`] to only
+/// pay that cost once.
+/// Suggestions are ordered as in [`suggest`].
+pub fn best_openers(guesses: &[&str], answers: &[&str], k: usize) -> Vec<Suggestion> {
+    suggest(answers, guesses, k, false)
+}
+
+/// The result of [`best_openers_cached`], if it can be used without recomputing it
+pub fn cached_openers<P: AsRef<Path>>(
+    guesses: &[&str],
+    answers: &[&str],
+    k: usize,
+    cache: P,
+) -> Option<Vec<Suggestion>> {
+    let mut suggestions = read_cache(cache.as_ref(), word_lists_hash(guesses, answers))?;
+    // a cache holding fewer suggestions than asked for is only usable if
+    // it already covers every possible guess
+    if suggestions.len() >= k || suggestions.len() == guesses.len() {
+        suggestions.truncate(k);
+        Some(suggestions)
+    } else {
+        None
+    }
+}
+
+/// Like [`best_openers`], but reuse a previous result stored at `cache`.
+///
+/// The cache is keyed by a hash of both word lists, so it is recomputed
+/// whenever either list changes. A missing, unreadable, or corrupt cache file is
+/// treated the same as a stale one: the openers are recomputed and the file is
+/// rewritten. Failing to write the cache is not an error; the result is still
+/// returned.
+pub fn best_openers_cached<P: AsRef<Path>>(
+    guesses: &[&str],
+    answers: &[&str],
+    k: usize,
+    cache: P,
+) -> Vec<Suggestion> {
+    if let Some(suggestions) = cached_openers(guesses, answers, k, cache.as_ref()) {
+        return suggestions;
+    }
+
+    let suggestions = best_openers(guesses, answers, k);
+    let hash = word_lists_hash(guesses, answers);
+    let _ = write_cache(cache.as_ref(), hash, &suggestions);
+    suggestions
+}
+
+/// Where [`best_openers_default_cache`] keeps the best first guesses, e.g.
+/// `~/.cache/wordle/openers.txt` on Linux, or `None` if there's no cache
+/// directory
+pub fn default_openers_cache() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "wordle").map(|dirs| dirs.cache_dir().join("openers.txt"))
+}
+
+/// Like [`best_openers_cached`], with the cache kept at [`default_openers_cache`].
+///
+/// Working the openers out takes a while, so `computing` is called first
+/// whenever they have to be, e.g. to tell the player why they're waiting.
+/// Without a cache directory, they always are.
+pub fn best_openers_default_cache(
+    guesses: &[&str],
+    answers: &[&str],
+    k: usize,
+    computing: impl FnOnce(),
+) -> Vec<Suggestion> {
+    openers_in(
+        guesses,
+        answers,
+        k,
+        default_openers_cache().as_deref(),
+        computing,
+    )
+}
+
+/// The openers for [`best_openers_default_cache`], cached at `cache` if there is one
+fn openers_in(
+    guesses: &[&str],
+    answers: &[&str],
+    k: usize,
+    cache: Option<&Path>,
+    computing: impl FnOnce(),
+) -> Vec<Suggestion> {
+    let Some(cache) = cache else {
+        computing();
+        return best_openers(guesses, answers, k);
+    };
+    cached_openers(guesses, answers, k, cache).unwrap_or_else(|| {
+        computing();
+        best_openers_cached(guesses, answers, k, cache)
+    })
+}
+
+/// Find the `k` best next guesses in `game`, among the guesses that can still
+/// be played (so hard mode is respected), explained if `explain` is set.
+///
+/// This scores every playable guess, so before the first guess it's as
+/// expensive as [`best_openers`].
+pub fn suggest_next(game: &Wordle<'_>, k: usize, explain: bool) -> Vec<Suggestion> {
+    suggest(&candidates(game), &game.playable_guesses(), k, explain)
+}
+
+/// The guess picked by [`suggest_lookahead`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lookahead {
+    /// The word to guess
+    pub word: String,
+    /// The expected number of guesses to find the answer, counting this one;
+    /// or `None` if the search ran out of time, and `word` is the best guess
+    /// from [`suggest`] instead
+    pub expected_guesses: Option<f64>,
+}
+
+/// Find the guess that is expected to find the answer in the fewest guesses,
+/// when the answer is one of `candidates`.
+///
+/// Only the `k` best guesses by [`suggest`] are considered at each step. Each
+/// one is tried by splitting the candidates by the feedback they would give,
+/// and working out how many more guesses each group would take in the same way,
+/// `depth` guesses deep (at least one); past that, the number of guesses is
+/// estimated from the size of the group.
+/// Groups of candidates that come up more than once are only worked out once.
+///
+/// If the search takes longer than `budget`, it is abandoned, and the best
+/// guess by [`suggest`] is returned instead. This is `None` if there are no
+/// candidates or no guesses.
+pub fn suggest_lookahead(
+    candidates: &[&str],
+    guesses: &[&str],
+    k: usize,
+    depth: usize,
+    budget: Duration,
+) -> Option<Lookahead> {
+    lookahead(candidates, guesses, k, depth, budget, None)
+}
+
+/// Like [`suggest_lookahead`], but reuse the groups of candidates worked out
+/// before, by this or an earlier run, from `cache`; and add the ones worked out
+/// now to it.
+pub fn suggest_lookahead_cached(
+    candidates: &[&str],
+    guesses: &[&str],
+    k: usize,
+    depth: usize,
+    budget: Duration,
+    cache: &mut SolverCache,
+) -> Option<Lookahead> {
+    lookahead(candidates, guesses, k, depth, budget, Some(cache))
+}
+
+/// How long [`suggest_next_lookahead`] and [`Session::suggest_lookahead`]
+/// search before leaving the suggestions as they are
+pub const LOOKAHEAD_BUDGET: Duration = Duration::from_secs(1);
+
+/// Like [`suggest_next`], but with the suggestion that [`suggest_lookahead`]
+/// picks out of them first, looking [`LookaheadStrategy::DEFAULT_DEPTH`] guesses
+/// ahead.
+///
+/// The search is given up on after [`LOOKAHEAD_BUDGET`]. It uses `cache`, if
+/// there is one; see [`suggest_lookahead_cached`].
+pub fn suggest_next_lookahead(
+    game: &Wordle<'_>,
+    k: usize,
+    explain: bool,
+    cache: Option<&mut SolverCache>,
+) -> Vec<Suggestion> {
+    let candidates = candidates(game);
+    let guesses = game.playable_guesses();
+    lookahead_first(
+        suggest(&candidates, &guesses, k, explain),
+        &candidates,
+        &guesses,
+        cache,
+    )
+}
+
+/// Move the suggestion that [`suggest_lookahead`] picks out of `suggestions`
+/// (the best ones by [`suggest`]) to the front
+fn lookahead_first(
+    mut suggestions: Vec<Suggestion>,
+    candidates: &[&str],
+    guesses: &[&str],
+    cache: Option<&mut SolverCache>,
+) -> Vec<Suggestion> {
+    let depth = LookaheadStrategy::DEFAULT_DEPTH;
+    let best = lookahead(
+        candidates,
+        guesses,
+        suggestions.len(),
+        depth,
+        LOOKAHEAD_BUDGET,
+        cache,
+    );
+    if let Some(i) = best.and_then(|best| suggestions.iter().position(|s| s.word == best.word)) {
+        let best = suggestions.remove(i);
+        suggestions.insert(0, best);
+    }
+    suggestions
+}
+
+/// [`suggest_lookahead`], using `cache` if there is one
+fn lookahead(
+    candidates: &[&str],
+    guesses: &[&str],
+    k: usize,
+    depth: usize,
+    budget: Duration,
+    cache: Option<&mut SolverCache>,
+) -> Option<Lookahead> {
+    if candidates.is_empty() || guesses.is_empty() {
+        return None;
+    }
+
+    let k = k.max(1);
+    let mut settings = Fnv::new();
+    settings.feed(&word_lists_hash(guesses, &[]).to_le_bytes());
+    settings.feed(&(k as u64).to_le_bytes());
+    let mut search = Search {
+        guesses,
+        k,
+        deadline: Instant::now().checked_add(budget),
+        settings,
+        memo: HashMap::new(),
+        cache,
+    };
+    match search.best(candidates, depth.max(1)) {
+        Ok((word, expected)) => Some(Lookahead {
+            word,
+            expected_guesses: Some(expected),
+        }),
+        Err(OutOfTime) => suggest(candidates, guesses, 1, false)
+            .pop()
+            .map(|suggestion| Lookahead {
+                word: suggestion.word,
+                expected_guesses: None,
+            }),
+    }
+}
+
+/// The bits of information a guess is assumed to give, when estimating how
+/// many guesses a group of candidates will take without searching it
+const ESTIMATED_BITS_PER_GUESS: f64 = 3.0;
+
+/// A search for the best guess by [`suggest_lookahead`]
+struct Search<'g, 'c> {
+    /// The words that can be guessed
+    guesses: &'g [&'g str],
+    /// The number of guesses to try for each group of candidates
+    k: usize,
+    /// When to give up, or `None` to never give up
+    deadline: Option<Instant>,
+    /// The hash of the guesses and `k`, which every key starts from
+    settings: Fnv,
+    /// The best guess and its expected number of guesses, by [`Search::key`]
+    memo: HashMap<u64, (String, f64)>,
+    /// Where to remember the results between runs, if anywhere
+    cache: Option<&'c mut SolverCache>,
+}
+
+/// The search ran past its deadline
+#[derive(Debug)]
+struct OutOfTime;
+
+impl Search<'_, '_> {
+    /// A hash of the candidates (in any order) and the search settings, which
+    /// the best guess for them is kept under
+    fn key(&self, candidates: &[&str], depth: usize) -> u64 {
+        let mut sorted = candidates.to_vec();
+        sorted.sort_unstable();
+
+        let mut hash = self.settings;
+        hash.feed(&(depth as u64).to_le_bytes());
+        for word in sorted {
+            hash.feed(word.as_bytes());
+            hash.feed(b"\n");
+        }
+        hash.finish()
+    }
+
+    /// The best guess for `candidates` (which aren't empty), and the expected
+    /// number of guesses it takes to find the answer
+    fn best(&mut self, candidates: &[&str], depth: usize) -> Result<(String, f64), OutOfTime> {
+        // one candidate is guessed right away; with two, either one is
+        // guessed, and is right half the time
+        match candidates {
+            [answer] => return Ok((answer.to_string(), 1.0)),
+            [answer, _] => return Ok((answer.to_string(), 1.5)),
+            _ => {}
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(OutOfTime);
+        }
+
+        let key = self.key(candidates, depth);
+        if let Some(best) = self.memo.get(&key) {
+            return Ok(best.clone());
+        }
+        if let Some(best) = self.cache.as_mut().and_then(|cache| cache.get(key)) {
+            self.memo.insert(key, best.clone());
+            return Ok(best);
+        }
+
+        let total = candidates.len() as f64;
+        let mut best: Option<(String, f64)> = None;
+        for suggestion in suggest(candidates, self.guesses, self.k, false) {
+            // a BTreeMap, so the costs are always added up in the same order
+            let mut groups: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+            for &candidate in candidates {
+                let pattern = codec::statuses_to_packed(&score_guess(&suggestion.word, candidate));
+                groups.entry(pattern).or_default().push(candidate);
+            }
+
+            let mut expected = 1.0;
+            for group in groups.values() {
+                if group == &[suggestion.word.as_str()] {
+                    // the guess was the answer
+                    continue;
+                }
+                let rest = if depth > 1 {
+                    self.best(group, depth - 1)?.1
+                } else {
+                    estimate(group.len())
+                };
+                expected += group.len() as f64 / total * rest;
+            }
+
+            // ties go to the guess suggested first
+            if best.as_ref().is_none_or(|(_, cost)| expected < *cost) {
+                best = Some((suggestion.word, expected));
+            }
+        }
+
+        let best = best.expect("There is at least one guess");
+        if let Some(cache) = self.cache.as_mut() {
+            cache.insert(key, &best.0, best.1);
+        }
+        self.memo.insert(key, best.clone());
+        Ok(best)
+    }
+}
+
+/// Estimate the number of guesses it takes to find the answer among `n`
+/// candidates, without searching for them
+fn estimate(n: usize) -> f64 {
+    let n = n as f64;
+    // the candidates can't be found any faster than by guessing one that
+    // tells all of the others apart
+    1.0 + (n.log2() / ESTIMATED_BITS_PER_GUESS).max(1.0 - 1.0 / n)
+}
+
+/// The answers that are still possible in `game`
+fn candidates<'a>(game: &Wordle<'a>) -> Vec<&'a str> {
+    match game.remaining_candidates() {
+        Some(candidates) => candidates.to_vec(),
+        None => {
+            let constraints = game.constraints();
+            game.answers()
+                .iter()
+                .copied()
+                .filter(|word| constraints.matches(word))
+                .collect()
+        }
+    }
+}
+
+/// One guess made in a [`Session`], and what was learned from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    /// The guessed word
+    pub guess: String,
+    /// The feedback for the guess
+    pub feedback: Vec<LetterStatus>,
+    /// The number of possible answers before the guess
+    pub before: usize,
+    /// The number of possible answers left after the guess
+    pub after: usize,
+}
+
+/// Why [`Session::apply`] couldn't take a guess into account
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StepError {
+    /// The guess doesn't have the same number of letters as the answers
+    WrongLength {
+        /// The number of letters in the answers
+        expected: usize,
+        /// The number of letters in the guess
+        actual: usize,
+    },
+    /// The feedback doesn't have one status for each letter of the guess
+    WrongFeedbackLength {
+        /// The number of letters in the guess
+        expected: usize,
+        /// The number of statuses in the feedback
+        actual: usize,
+    },
+    /// The answer has already been found
+    Solved,
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, actual } => write!(
+                f,
+                "Guess must have exactly {} letters, not {}",
+                expected, actual
+            ),
+            Self::WrongFeedbackLength { expected, actual } => write!(
+                f,
+                "Feedback must have exactly {} colors, not {}",
+                expected, actual
+            ),
+            Self::Solved => write!(f, "The answer has already been found"),
+        }
+    }
+}
+
+impl Error for StepError {}
+
+/// Solve a game played somewhere else, like the official Wordle, by narrowing
+/// down the possible answers from the feedback given for each guess.
+///
+/// Unlike a [`Wordle`], the answer isn't known, so any word of the right length
+/// can be guessed and the feedback has to be taken on trust. Typos can be taken
+/// back with [`Session::undo`].
+#[derive(Debug, Clone)]
+pub struct Session<'a> {
+    /// The words that can be guessed
+    guesses: &'a [&'a str],
+    /// The number of letters in each word
+    word_length: usize,
+    /// The answers that are still possible
+    candidates: Vec<&'a str>,
+    /// The guesses so far, each with the candidates from before it
+    steps: Vec<(Deduction, Vec<&'a str>)>,
+}
+
+impl<'a> Session<'a> {
+    /// Start solving a game whose answer is one of the `word_length`-letter
+    /// words in `answers`
+    pub fn new(guesses: &'a [&'a str], answers: &[&'a str], word_length: usize) -> Self {
+        Self {
+            guesses,
+            word_length,
+            candidates: answers
+                .iter()
+                .copied()
+                .filter(|word| word.chars().count() == word_length)
+                .collect(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// The answers that are still possible, in the order of the answer list
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    /// The guesses so far, in order
+    pub fn deductions(&self) -> impl Iterator<Item = &Deduction> + '_ {
+        self.steps.iter().map(|(deduction, _)| deduction)
+    }
+
+    /// Whether the last guess was the answer
+    pub fn is_solved(&self) -> bool {
+        self.steps.last().is_some_and(|(deduction, _)| {
+            deduction
+                .feedback
+                .iter()
+                .all(|status| *status == LetterStatus::Correct)
+        })
+    }
+
+    /// Find the `k` best next guesses, as in [`suggest`].
+    ///
+    /// Before the first guess this is as expensive as [`best_openers`], which
+    /// can use a cache.
+    pub fn suggest(&self, k: usize, explain: bool) -> Vec<Suggestion> {
+        let guesses: Vec<&str> = self
+            .guesses
+            .iter()
+            .copied()
+            .filter(|word| word.chars().count() == self.word_length)
+            .collect();
+        suggest(&self.candidates, &guesses, k, explain)
+    }
+
+    /// Find the `k` best next guesses, with the one expected to find the
+    /// answer soonest first; see [`suggest_next_lookahead`].
+    pub fn suggest_lookahead(
+        &self,
+        k: usize,
+        explain: bool,
+        cache: Option<&mut SolverCache>,
+    ) -> Vec<Suggestion> {
+        let guesses: Vec<&str> = self
+            .guesses
+            .iter()
+            .copied()
+            .filter(|word| word.chars().count() == self.word_length)
+            .collect();
+        lookahead_first(
+            suggest(&self.candidates, &guesses, k, explain),
+            &self.candidates,
+            &guesses,
+            cache,
+        )
+    }
+
+    /// Narrow down the possible answers with the feedback for a guess, and get
+    /// back what was learned
+    pub fn apply(
+        &mut self,
+        guess: &str,
+        feedback: Vec<LetterStatus>,
+    ) -> Result<&Deduction, StepError> {
+        let length = guess.chars().count();
+        if length != self.word_length {
+            return Err(StepError::WrongLength {
+                expected: self.word_length,
+                actual: length,
+            });
+        }
+        if feedback.len() != length {
+            return Err(StepError::WrongFeedbackLength {
+                expected: length,
+                actual: feedback.len(),
+            });
+        }
+        if self.is_solved() {
+            return Err(StepError::Solved);
+        }
+
+        let guess = guess.to_lowercase();
+        let candidates = filter::filter_candidates(&self.candidates, &guess, &feedback);
+        let deduction = Deduction {
+            before: self.candidates.len(),
+            after: candidates.len(),
+            guess,
+            feedback,
+        };
+        let previous = std::mem::replace(&mut self.candidates, candidates);
+        self.steps.push((deduction, previous));
+        Ok(&self.steps[self.steps.len() - 1].0)
+    }
+
+    /// Take back the last guess, e.g. if its feedback was mistyped, and get it
+    /// back if there was one
+    pub fn undo(&mut self) -> Option<Deduction> {
+        let (deduction, previous) = self.steps.pop()?;
+        self.candidates = previous;
+        Some(deduction)
+    }
+}
+
+/// A way of choosing guesses to solve a game
+pub trait Strategy: fmt::Debug {
+    /// Choose the next word to guess in `game`, based on the guesses made so
+    /// far; or `None` if there is nothing left to guess.
+    fn next_guess(&mut self, game: &Wordle<'_>) -> Option<String>;
+}
+
+/// Guess a random word that could still be the answer.
+///
+/// See [`Wordle::random_consistent_guess`].
+#[derive(Debug, Clone)]
+pub struct RandomStrategy {
+    /// (Pseudo-) Random Number Generator
+    rand: StdRng,
+}
+
+impl RandomStrategy {
+    /// Create a strategy seeded from the OS
+    pub fn new() -> Self {
+        Self {
+            rand: StdRng::from_entropy(),
+        }
+    }
+
+    /// Create a strategy that makes the same choices for the same seed
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rand: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for RandomStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn next_guess(&mut self, game: &Wordle<'_>) -> Option<String> {
+        game.random_consistent_guess(&mut self.rand)
+    }
+}
+
+/// Guess the word that gives the most information about the remaining
+/// possible answers; see [`suggest`].
+#[derive(Debug, Clone, Default)]
+pub struct EntropyStrategy {
+    /// The first guess to make, to avoid scoring the whole dictionary
+    opener: Option<String>,
+}
+
+impl EntropyStrategy {
+    /// Create a strategy that works out its own first guess
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always open with `opener`, e.g. the result of [`best_openers_cached`]
+    pub fn with_opener<S: Into<String>>(opener: S) -> Self {
+        Self {
+            opener: Some(opener.into()),
+        }
+    }
+}
+
+impl Strategy for EntropyStrategy {
+    fn next_guess(&mut self, game: &Wordle<'_>) -> Option<String> {
+        if game.history().is_empty() {
+            if let Some(opener) = &self.opener {
+                return Some(opener.clone());
+            }
+        }
+
+        let constraints = game.constraints();
+        let candidates: Vec<&str> = game
+            .answers()
+            .iter()
+            .copied()
+            .filter(|word| constraints.matches(word))
+            .collect();
+        let playable = game.playable_guesses();
+
+        match candidates.len() {
+            // nothing can be learned by splitting one or two candidates further,
+            // so go for the win
+            1 | 2 => Some(candidates[0].to_string()),
+            _ => suggest(&candidates, &playable, 1, false)
+                .pop()
+                .map(|suggestion| suggestion.word),
+        }
+    }
+}
+
+/// Guess the word whose letters are the most common, in the same positions,
+/// among the remaining possible answers.
+///
+/// Each letter of a guess scores the number of possible answers with that
+/// letter in that position. For the first few guesses a letter only scores in
+/// full once, since repeating it finds out less about the rest of the word.
+/// Ties are broken alphabetically, so the same game always gets the same guesses.
+///
+/// This is much cheaper than [`EntropyStrategy`], though it usually needs more
+/// guesses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PositionalFrequencyStrategy;
+
+impl PositionalFrequencyStrategy {
+    /// The number of guesses for which repeated letters are penalized
+    pub const EARLY_GUESSES: usize = 2;
+
+    /// Create the strategy
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Score `guess` against the letter counts of the possible answers.
+    ///
+    /// Scores are doubled so that a repeated letter can count for half.
+    fn score(guess: &str, counts: &[HashMap<char, usize>], early: bool) -> usize {
+        let mut seen = HashSet::new();
+        guess
+            .chars()
+            .zip(counts)
+            .map(|(c, counts)| {
+                let count = counts.get(&c).copied().unwrap_or(0);
+                if seen.insert(c) || !early {
+                    count * 2
+                } else {
+                    count
+                }
+            })
+            .sum()
+    }
+}
+
+impl Strategy for PositionalFrequencyStrategy {
+    fn next_guess(&mut self, game: &Wordle<'_>) -> Option<String> {
+        let candidates = candidates(game);
+        if let [answer] = candidates[..] {
+            return Some(answer.to_string());
+        }
+
+        let mut counts = vec![HashMap::new(); game.word_length()];
+        for word in &candidates {
+            for (c, counts) in word.chars().zip(counts.iter_mut()) {
+                *counts.entry(c).or_insert(0) += 1;
+            }
+        }
+
+        let early = game.history().len() < Self::EARLY_GUESSES;
+        game.playable_guesses()
+            .into_iter()
+            .map(|word| (Self::score(word, &counts, early), word))
+            // the highest score, and the first word alphabetically among those
+            .max_by(|(a_score, a), (b_score, b)| a_score.cmp(b_score).then_with(|| b.cmp(a)))
+            .map(|(_, word)| word.to_string())
+    }
+}
+
+/// Guess the word expected to find the answer in the fewest guesses; see
+/// [`suggest_lookahead`].
+#[derive(Debug, Clone)]
+pub struct LookaheadStrategy {
+    /// The first guess to make, to avoid searching the whole dictionary
+    opener: Option<String>,
+    /// The number of guesses to try at each step
+    k: usize,
+    /// The number of guesses to look ahead
+    depth: usize,
+    /// How long to search before settling for the best guess by [`suggest`]
+    budget: Duration,
+}
+
+impl LookaheadStrategy {
+    /// The number of guesses tried at each step, by default
+    pub const DEFAULT_K: usize = 5;
+    /// The number of guesses looked ahead, by default
+    pub const DEFAULT_DEPTH: usize = 2;
+
+    /// Create a strategy that searches `k` guesses at each step, `depth`
+    /// guesses deep, taking as long as it needs
+    pub fn new(k: usize, depth: usize) -> Self {
+        Self {
+            opener: None,
+            k,
+            depth,
+            budget: Duration::MAX,
+        }
+    }
+
+    /// Always open with `opener`, e.g. the result of [`best_openers_cached`]
+    pub fn with_opener<S: Into<String>>(mut self, opener: S) -> Self {
+        self.opener = Some(opener.into());
+        self
+    }
+
+    /// Settle for the best guess by [`suggest`] if a search takes longer than `budget`
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+}
+
+impl Default for LookaheadStrategy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_K, Self::DEFAULT_DEPTH)
+    }
+}
+
+impl Strategy for LookaheadStrategy {
+    fn next_guess(&mut self, game: &Wordle<'_>) -> Option<String> {
+        if game.history().is_empty() {
+            if let Some(opener) = &self.opener {
+                return Some(opener.clone());
+            }
+        }
+
+        suggest_lookahead(
+            &candidates(game),
+            &game.playable_guesses(),
+            self.k,
+            self.depth,
+            self.budget,
+        )
+        .map(|lookahead| lookahead.word)
+    }
+}
+
+/// A 64-bit FNV-1a hash.
+///
+/// This needs to be stable across runs and Rust versions since it ends up on
+/// disk, so [`std::collections::hash_map::DefaultHasher`] won't do.
+#[derive(Debug, Clone, Copy)]
+struct Fnv(u64);
+
+impl Fnv {
+    /// Start a hash
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Add `bytes` to the hash
+    fn feed(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// The hash of everything fed in so far
+    fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+/// Hash a pair of word lists
+fn word_lists_hash(guesses: &[&str], answers: &[&str]) -> u64 {
+    let mut hash = Fnv::new();
+    for list in [guesses, answers] {
+        for word in list {
+            hash.feed(word.as_bytes());
+            hash.feed(b"\n");
+        }
+        // separate the lists so moving a word from one to the other changes the hash
+        hash.feed(b"\0");
+    }
+    hash.finish()
+}
+
+/// Read an openers cache file, returning `None` if it is missing, malformed,
+/// or was built from different word lists.
+fn read_cache(path: &Path, hash: u64) -> Option<Vec<Suggestion>> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut lines = contents.lines();
+
+    if lines.next()? != CACHE_HEADER {
+        return None;
+    }
+    if u64::from_str_radix(lines.next()?, 16).ok()? != hash {
+        return None;
+    }
+
+    lines
+        .map(|line| {
+            let (word, score) = line.split_once('\t')?;
+            Some(Suggestion {
+                word: word.into(),
+                score: score.parse().ok()?,
+                explanation: None,
+            })
+        })
+        .collect()
+}
+
+/// Write an openers cache file
+fn write_cache(path: &Path, hash: u64, suggestions: &[Suggestion]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "{}", CACHE_HEADER)?;
+    writeln!(file, "{:016x}", hash)?;
+    for suggestion in suggestions {
+        writeln!(file, "{}\t{}", suggestion.word, suggestion.score)?;
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LetterStatus;
+
+    const GUESSES: [&str; 6] = ["abcde", "fghij", "abfgh", "aaaaa", "edcba", "xyzzy"];
+    const ANSWERS: [&str; 4] = ["abcde", "fghij", "abfgh", "edcba"];
+
+    /// Get a path in the temp dir that is unique to a test
+    fn cache_path(test: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "wordle-solver-{}-{}.cache",
+            test,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_session() {
+        use LetterStatus::*;
+
+        let mut session = Session::new(&GUESSES, &ANSWERS, 5);
+        assert_eq!(&ANSWERS, session.candidates());
+        assert_eq!("abcde", session.suggest(1, false)[0].word);
+
+        // "abfgh" against "abcde"
+        let feedback = vec![Correct, Correct, NotInWord, NotInWord, NotInWord];
+        let deduction = session.apply("ABFGH", feedback.clone()).unwrap();
+        assert_eq!(
+            &Deduction {
+                guess: "abfgh".into(),
+                feedback,
+                before: 4,
+                after: 1,
+            },
+            deduction
+        );
+        assert_eq!(&["abcde"], session.candidates());
+        assert!(!session.is_solved());
+
+        assert_eq!(
+            Err(StepError::WrongLength {
+                expected: 5,
+                actual: 4
+            }),
+            session.apply("abcd", vec![Correct; 4])
+        );
+        assert_eq!(
+            Err(StepError::WrongFeedbackLength {
+                expected: 5,
+                actual: 3
+            }),
+            session.apply("abcde", vec![Correct; 3])
+        );
+
+        // a mistyped guess that rules everything out can be taken back
+        session.apply("fghij", vec![Correct; 5]).unwrap();
+        assert!(session.candidates().is_empty());
+        assert_eq!("fghij", session.undo().unwrap().guess);
+        assert_eq!(&["abcde"], session.candidates());
+
+        session.apply("abcde", vec![Correct; 5]).unwrap();
+        assert!(session.is_solved());
+        assert_eq!(
+            Err(StepError::Solved),
+            session.apply("abcde", vec![Correct; 5])
+        );
+        assert_eq!(2, session.deductions().count());
+
+        session.undo();
+        session.undo();
+        assert_eq!(None, session.undo());
+        assert_eq!(&ANSWERS, session.candidates());
+    }
+
+    #[test]
+    fn test_entropy() {
+        // a guess that can't tell any of the answers apart gives no information
+        assert_eq!(0.0, entropy("xyzzy", &ANSWERS));
+        // a guess that splits the answers into four buckets gives two bits
+        assert_eq!(2.0, entropy("abfgh", &ANSWERS));
+        assert_eq!(0.0, entropy("abcde", &[]));
+    }
+
+    #[test]
+    fn test_explain() {
+        use LetterStatus::*;
+
+        let candidates = ["abcde", "abcdf", "abcdg", "fghij"];
+        assert_eq!(
+            Explanation {
+                bits: 1.5,
+                expected_remaining: 1.25,
+                worst_case: 2,
+                possible_answers: 1,
+                patterns: vec![
+                    (vec![Correct, Correct, Correct, Correct, NotInWord], 2),
+                    (vec![NotInWord; 5], 1),
+                    (vec![Correct; 5], 1),
+                ],
+            },
+            explain("abcde", &candidates)
+        );
+
+        // a guess that tells nothing apart leaves every candidate
+        let explanation = explain("xyzzy", &candidates);
+        assert_eq!(0, explanation.possible_answers);
+        assert_eq!(
+            (4.0, 4),
+            (explanation.expected_remaining, explanation.worst_case)
+        );
+        assert_eq!(vec![(vec![NotInWord; 5], 4)], explanation.patterns);
+
+        // only the suggestions given back are explained
+        let suggestions = suggest(&candidates, &GUESSES, 2, true);
+        assert_eq!(2, suggestions.len());
+        for suggestion in &suggestions {
+            let explanation = suggestion.explanation.as_ref().unwrap();
+            assert_eq!(suggestion.score, explanation.bits);
+            assert_eq!(&explain(&suggestion.word, &candidates), explanation);
+        }
+        assert!(suggest(&candidates, &GUESSES, 2, false)
+            .iter()
+            .all(|s| s.explanation.is_none()));
+    }
+
+    #[test]
+    fn test_best_openers() {
+        let openers = best_openers(&GUESSES, &ANSWERS, 3);
+        let words: Vec<&str> = openers.iter().map(|s| s.word.as_str()).collect();
+        assert_eq!(vec!["abcde", "abfgh", "edcba"], words);
+        assert!(openers.windows(2).all(|w| w[0].score >= w[1].score));
+
+        // asking for more openers than there are guesses returns all of them
+        assert_eq!(GUESSES.len(), best_openers(&GUESSES, &ANSWERS, 100).len());
+    }
+
+    #[test]
+    fn test_best_openers_cached() {
+        let path = cache_path("cached");
+        let _ = fs::remove_file(&path);
+
+        let expected = best_openers(&GUESSES, &ANSWERS, 3);
+        assert_eq!(None, cached_openers(&GUESSES, &ANSWERS, 3, &path));
+        assert_eq!(expected, best_openers_cached(&GUESSES, &ANSWERS, 3, &path));
+        assert!(path.exists(), "Cache file not written");
+        assert_eq!(
+            Some(expected.clone()),
+            cached_openers(&GUESSES, &ANSWERS, 3, &path)
+        );
+
+        // the cached result should be reused as-is
+        let hash = word_lists_hash(&GUESSES, &ANSWERS);
+        assert_eq!(Some(expected.clone()), read_cache(&path, hash));
+        assert_eq!(expected, best_openers_cached(&GUESSES, &ANSWERS, 3, &path));
+        assert_eq!(
+            expected[..2],
+            best_openers_cached(&GUESSES, &ANSWERS, 2, &path)[..]
+        );
+
+        // asking for more than was cached recomputes
+        assert_eq!(5, best_openers_cached(&GUESSES, &ANSWERS, 5, &path).len());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_openers_in() {
+        let path = cache_path("openers-in");
+        let _ = fs::remove_file(&path);
+        let expected = best_openers(&GUESSES, &ANSWERS, 3);
+
+        // the caller is told only when the openers have to be worked out
+        let mut computed = 0;
+        let mut openers = |cache| openers_in(&GUESSES, &ANSWERS, 3, cache, || computed += 1);
+        assert_eq!(expected, openers(Some(&path)));
+        assert_eq!(expected, openers(Some(&path)));
+        assert_eq!(expected, openers(None));
+        assert_eq!(2, computed);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let path = cache_path("invalidation");
+        let _ = fs::remove_file(&path);
+
+        best_openers_cached(&GUESSES, &ANSWERS, 3, &path);
+
+        // changing the answer list must not reuse the old result
+        let answers = ["abcde", "fghij"];
+        assert_eq!(None, read_cache(&path, word_lists_hash(&GUESSES, &answers)));
+        assert_eq!(
+            best_openers(&GUESSES, &answers, 3),
+            best_openers_cached(&GUESSES, &answers, 3, &path)
+        );
+
+        // corrupt caches fall back to recomputing
+        let expected = best_openers(&GUESSES, &ANSWERS, 3);
+        for garbage in ["", "wordle-openers v1\nnot a hash\n", "\u{0}\u{1}\u{2}"] {
+            fs::write(&path, garbage).unwrap();
+            assert_eq!(expected, best_openers_cached(&GUESSES, &ANSWERS, 3, &path));
+        }
+        let hash = word_lists_hash(&GUESSES, &ANSWERS);
+        fs::write(
+            &path,
+            format!("{}\n{:016x}\nabcde\tNaN?\n", CACHE_HEADER, hash),
+        )
+        .unwrap();
+        assert_eq!(expected, best_openers_cached(&GUESSES, &ANSWERS, 3, &path));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_suggest() {
+        // every guess splits these candidates the same, so prefer possible answers
+        let candidates = ["abcde", "fghij"];
+        let suggestions = suggest(&candidates, &["aaaaa", "fghij", "abcde"], 3, false);
+        let words: Vec<&str> = suggestions.iter().map(|s| s.word.as_str()).collect();
+        assert_eq!(vec!["abcde", "fghij", "aaaaa"], words);
+    }
+
+    #[test]
+    fn test_suggest_next() {
+        let mut game = Wordle::new(&GUESSES, &ANSWERS).set_answer("abcde").unwrap();
+        game.guess("abfgh").unwrap();
+        let words = |suggestions: Vec<Suggestion>| -> Vec<String> {
+            suggestions.into_iter().map(|s| s.word).collect()
+        };
+        assert_eq!(vec!["abcde", "aaaaa"], words(suggest_next(&game, 2, false)));
+
+        // in hard mode, only guesses keeping the green "a" and "b" are suggested
+        let mut game = game.clear().set_answer("abcde").unwrap();
+        game.set_hard_mode(true).unwrap();
+        game.guess("abfgh").unwrap();
+        assert_eq!(vec!["abcde"], words(suggest_next(&game, 3, false)));
+    }
+
+    #[test]
+    fn test_strategies() {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(RandomStrategy::seeded(42)),
+            Box::new(EntropyStrategy::new()),
+            Box::new(EntropyStrategy::with_opener("xyzzy")),
+            Box::new(PositionalFrequencyStrategy::new()),
+            Box::new(LookaheadStrategy::default()),
+            Box::new(LookaheadStrategy::new(1, 3).with_opener("xyzzy")),
+        ];
+
+        for strategy in strategies.iter_mut() {
+            for answer in ANSWERS {
+                let mut game = Wordle::new(&GUESSES, &ANSWERS).set_answer(answer).unwrap();
+
+                let solved = (0..GUESSES.len()).any(|_| {
+                    let guess = strategy.next_guess(&game).unwrap();
+                    game.guess(&guess).unwrap().feedback() == [LetterStatus::Correct; 5]
+                });
+                assert!(solved, "{:?} did not solve {}", strategy, answer);
+            }
+        }
+    }
+
+    #[test]
+    fn test_positional_frequency() {
+        let guesses = ["sassy", "salty", "fussy", "tasty", "mossy", "lofty"];
+        let answers = ["salty", "fussy", "tasty", "mossy"];
+        let mut strategy = PositionalFrequencyStrategy::new();
+        let mut game = Wordle::new(&guesses, &answers).set_answer("mossy").unwrap();
+
+        // "sassy" and "tasty" would tie, but the repeated "s"s count for less
+        let mut played = Vec::new();
+        while game.status() == crate::GameStatus::InProgress {
+            let guess = strategy.next_guess(&game).unwrap();
+            game.guess(&guess).unwrap();
+            played.push(guess);
+        }
+        // "fussy" and "mossy" tie for the second guess, so the first alphabetically wins
+        assert_eq!(vec!["tasty", "fussy", "mossy"], played);
+
+        // later on, repeats count in full
+        let mut counts = vec![HashMap::new(); 5];
+        for word in answers {
+            for (c, counts) in word.chars().zip(counts.iter_mut()) {
+                *counts.entry(c).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(
+            19,
+            PositionalFrequencyStrategy::score("sassy", &counts, true)
+        );
+        assert_eq!(
+            24,
+            PositionalFrequencyStrategy::score("sassy", &counts, false)
+        );
+        assert_eq!(
+            24,
+            PositionalFrequencyStrategy::score("tasty", &counts, false)
+        );
+        assert_eq!(
+            0,
+            PositionalFrequencyStrategy::score("xxxxx", &counts, false)
+        );
+    }
+
+    #[test]
+    fn test_suggest_lookahead() {
+        let forever = Duration::MAX;
+        assert_eq!(None, suggest_lookahead(&[], &GUESSES, 5, 2, forever));
+        assert_eq!(None, suggest_lookahead(&ANSWERS, &[], 5, 2, forever));
+        assert_eq!(
+            Some(Lookahead {
+                word: "fghij".into(),
+                expected_guesses: Some(1.0),
+            }),
+            suggest_lookahead(&["fghij"], &GUESSES, 5, 2, forever)
+        );
+
+        // "abcde" tells the other three answers apart, so it takes one guess
+        // when it's right, and two otherwise
+        let expected = Some(Lookahead {
+            word: "abcde".into(),
+            expected_guesses: Some(1.75),
+        });
+        assert_eq!(
+            expected,
+            suggest_lookahead(&ANSWERS, &GUESSES, 5, 2, forever)
+        );
+        assert_eq!(
+            expected,
+            suggest_lookahead(&ANSWERS, &GUESSES, 1, 1, forever)
+        );
+
+        // out of time, the best guess by entropy is used instead
+        assert_eq!(
+            Some(Lookahead {
+                word: "abcde".into(),
+                expected_guesses: None,
+            }),
+            suggest_lookahead(&ANSWERS, &GUESSES, 5, 2, Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_lookahead_beats_greedy() {
+        let candidates = [
+            "abuse", "acute", "avoid", "being", "close", "enemy", "exact", "maker", "media",
+            "thick", "video",
+        ];
+        let mut guesses = candidates.to_vec();
+        guesses.extend(["dying", "exist", "worth"]);
+
+        // "exist" splits the candidates up the most, but can't be the answer,
+        // and what it leaves takes longer to sort out
+        assert_eq!("exist", suggest(&candidates, &guesses, 1, false)[0].word);
+        let lookahead = suggest_lookahead(&candidates, &guesses, 5, 2, Duration::MAX).unwrap();
+        assert_eq!("media", lookahead.word);
+        assert!((23.0 / 11.0 - lookahead.expected_guesses.unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_suggest_lookahead_cached() {
+        let path = cache_path("lookahead");
+        let _ = fs::remove_file(&path);
+        let candidates = [
+            "abuse", "acute", "avoid", "being", "close", "enemy", "exact", "maker", "media",
+            "thick", "video",
+        ];
+        let mut guesses = candidates.to_vec();
+        guesses.extend(["dying", "exist", "worth"]);
+        let expected = suggest_lookahead(&candidates, &guesses, 5, 2, Duration::MAX);
+
+        let mut cache = SolverCache::open(&path);
+        let lookahead = |cache: &mut SolverCache| {
+            suggest_lookahead_cached(&candidates, &guesses, 5, 2, Duration::MAX, cache)
+        };
+        assert_eq!(expected, lookahead(&mut cache));
+        let stats = cache.stats();
+        assert_eq!(0, stats.hits);
+        assert!(stats.entries > 1);
+        drop(cache);
+
+        // the next run finds the whole search in the cache
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(expected, lookahead(&mut cache));
+        assert_eq!((1, 0), (cache.stats().hits, cache.stats().misses));
+
+        // but searching with other settings doesn't use it
+        suggest_lookahead_cached(&candidates, &guesses, 4, 2, Duration::MAX, &mut cache);
+        assert_eq!(1, cache.stats().hits);
+        drop(cache);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_session_suggest_lookahead() {
+        let candidates = [
+            "abuse", "acute", "avoid", "being", "close", "enemy", "exact", "maker", "media",
+            "thick", "video",
+        ];
+        let mut guesses = candidates.to_vec();
+        guesses.extend(["dying", "exist", "worth"]);
+        let session = Session::new(&guesses, &candidates, 5);
+
+        // the best guess by entropy moves down for the one that finds the answer soonest
+        let words = |suggestions: Vec<Suggestion>| -> Vec<String> {
+            suggestions.into_iter().map(|s| s.word).collect()
+        };
+        assert_eq!(vec!["exist", "media"], words(session.suggest(2, false)));
+        assert_eq!(
+            vec!["media", "exist"],
+            words(session.suggest_lookahead(2, false, None))
+        );
+    }
+
+    #[test]
+    fn test_estimate() {
+        assert_eq!(1.0, estimate(1));
+        assert_eq!(1.5, estimate(2));
+        assert!((5.0 / 3.0 - estimate(3)).abs() < 1e-9);
+        assert!(estimate(100) > estimate(10));
+    }
+
+    #[test]
+    fn test_word_lists_hash() {
+        let a = word_lists_hash(&["abcde"], &["fghij"]);
+        assert_eq!(a, word_lists_hash(&["abcde"], &["fghij"]));
+        assert_ne!(a, word_lists_hash(&["abcde", "fghij"], &[]));
+        assert_ne!(a, word_lists_hash(&["fghij"], &["abcde"]));
+    }
+}
@@ -0,0 +1,328 @@
+//! A cache of the searches done by the solver, kept between runs
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use super::Fnv;
+
+/// First line of a cache file; bump the version if the format changes, or the
+/// search would find different results, so older entries aren't used
+const HEADER: &str = "wordle-solver-cache v1";
+
+// After the header, each line is an entry, added to the end as it's found:
+//
+//   key<TAB>word<TAB>expected guesses<TAB>checksum
+//
+// where the key and checksum are 16 hex digits, and the checksum is the hash of
+// the rest of the line. Lines that don't parse or match their checksum, e.g.
+// because a run was killed partway through writing one, are ignored.
+
+/// The best guesses for groups of candidates that
+/// [`suggest_lookahead_cached`](super::suggest_lookahead_cached) has searched,
+/// kept in a file so later runs don't have to search them again.
+///
+/// Anything wrong with the file, from it being missing to it being corrupt,
+/// only means the searches are done again; opening a cache never fails.
+/// New entries are written when the cache is [flushed](SolverCache::flush) or dropped.
+#[derive(Debug)]
+pub struct SolverCache {
+    /// The file the cache is kept in
+    path: PathBuf,
+    /// The best guess and the expected number of guesses, by the key of the search
+    entries: HashMap<u64, (String, f64)>,
+    /// The lines for the entries not yet written to the file
+    pending: String,
+    /// Whether the file has to be written from scratch, because it's missing,
+    /// or from another version
+    rewrite: bool,
+    /// Whether the file's last line was cut off, so the next one has to start
+    /// on a line of its own
+    torn: bool,
+    /// The size of the file, as of when it was last read or written
+    bytes: u64,
+    /// The number of searches found in the cache
+    hits: u64,
+    /// The number of searches that weren't in the cache
+    misses: u64,
+}
+
+/// How much use a [`SolverCache`] has been
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct CacheStats {
+    /// The number of searches found in the cache
+    pub hits: u64,
+    /// The number of searches that weren't in the cache, and had to be done
+    pub misses: u64,
+    /// The number of searches in the cache
+    pub entries: usize,
+    /// The size of the cache file, in bytes, as of when it was last read or written
+    pub bytes: u64,
+}
+
+impl SolverCache {
+    /// Open the cache kept at `path`, or start an empty one if there isn't one
+    /// there that can be used
+    pub fn open<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut cache = Self {
+            path,
+            entries: HashMap::new(),
+            pending: String::new(),
+            rewrite: true,
+            torn: false,
+            bytes: 0,
+            hits: 0,
+            misses: 0,
+        };
+
+        let contents = match fs::read(&cache.path) {
+            Ok(contents) => contents,
+            Err(_) => return cache,
+        };
+        let text = String::from_utf8_lossy(&contents);
+        let mut lines = text.split('\n');
+        if lines.next() != Some(HEADER) {
+            return cache;
+        }
+
+        cache.entries = lines.filter_map(parse_line).collect();
+        cache.rewrite = false;
+        cache.torn = !contents.ends_with(b"\n");
+        cache.bytes = contents.len() as u64;
+        cache
+    }
+
+    /// The file the cache is kept in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// How much use the cache has been since it was opened, and how big it is
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+            bytes: self.bytes,
+        }
+    }
+
+    /// The best guess for the search with `key`, and the expected number of guesses
+    pub(crate) fn get(&mut self, key: u64) -> Option<(String, f64)> {
+        let entry = self.entries.get(&key).cloned();
+        match entry {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        entry
+    }
+
+    /// Remember the best guess for the search with `key`
+    pub(crate) fn insert(&mut self, key: u64, word: &str, expected: f64) {
+        if self.entries.contains_key(&key) {
+            return;
+        }
+        self.pending.push_str(&line(key, word, expected));
+        self.entries.insert(key, (word.to_owned(), expected));
+    }
+
+    /// Write the new entries to the file.
+    ///
+    /// The file is only added to, unless it has to be started again, so a run
+    /// that's stopped partway through loses at most the entry being written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() && !self.rewrite {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        if self.rewrite {
+            let mut text = format!("{}\n", HEADER);
+            for (key, (word, expected)) in &self.entries {
+                text.push_str(&line(*key, word, *expected));
+            }
+            fs::write(&self.path, &text)?;
+            self.bytes = text.len() as u64;
+        } else {
+            let mut file = fs::OpenOptions::new().append(true).open(&self.path)?;
+            if self.torn {
+                file.write_all(b"\n")?;
+            }
+            file.write_all(self.pending.as_bytes())?;
+            self.bytes = file.metadata()?.len();
+        }
+
+        self.rewrite = false;
+        self.torn = false;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl Drop for SolverCache {
+    fn drop(&mut self) {
+        // the cache is only there to save time, so not being able to write it isn't an error
+        let _ = self.flush();
+    }
+}
+
+/// The line for an entry, with its newline
+fn line(key: u64, word: &str, expected: f64) -> String {
+    let entry = format!("{:016x}\t{}\t{}", key, word, expected);
+    format!("{}\t{:016x}\n", entry, checksum(&entry))
+}
+
+/// The checksum of the start of a line
+fn checksum(entry: &str) -> u64 {
+    let mut hash = Fnv::new();
+    hash.feed(entry.as_bytes());
+    hash.finish()
+}
+
+/// Read an entry from a line, or `None` if it's corrupt
+fn parse_line(line: &str) -> Option<(u64, (String, f64))> {
+    let (entry, check) = line.rsplit_once('\t')?;
+    if u64::from_str_radix(check, 16).ok()? != checksum(entry) {
+        return None;
+    }
+
+    let mut fields = entry.split('\t');
+    let key = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let word = fields.next().filter(|word| !word.is_empty())?;
+    let expected: f64 = fields.next()?.parse().ok()?;
+    if fields.next().is_some() || !expected.is_finite() {
+        return None;
+    }
+    Some((key, (word.to_owned(), expected)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get a path in the temp dir that is unique to a test
+    fn cache_path(test: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "wordle-solver-cache-{}-{}",
+            test,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = cache_path("round-trip");
+        let _ = fs::remove_file(&path);
+
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(CacheStats::default(), cache.stats());
+        assert_eq!(None, cache.get(1));
+        cache.insert(1, "crane", 2.5);
+        cache.insert(u64::MAX, "slate", 1.0 / 3.0);
+        assert_eq!(Some(("crane".to_owned(), 2.5)), cache.get(1));
+        assert_eq!(
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                entries: 2,
+                bytes: 0,
+            },
+            cache.stats()
+        );
+        drop(cache);
+
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(Some(("slate".to_owned(), 1.0 / 3.0)), cache.get(u64::MAX));
+        assert_eq!(fs::metadata(&path).unwrap().len(), cache.stats().bytes);
+
+        // new entries are added to the end
+        cache.insert(2, "trace", 3.0);
+        cache.flush().unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(4, text.lines().count());
+        assert!(text.ends_with(&line(2, "trace", 3.0)));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_corruption() {
+        let path = cache_path("corruption");
+
+        // another version's entries aren't used, and the file is started again
+        fs::write(
+            &path,
+            format!("wordle-solver-cache v0\n{}", line(1, "crane", 2.0)),
+        )
+        .unwrap();
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(0, cache.stats().entries);
+        cache.flush().unwrap();
+        assert_eq!(format!("{}\n", HEADER), fs::read_to_string(&path).unwrap());
+
+        // broken lines are skipped, and a cut off last line doesn't break the next one
+        let good = line(1, "crane", 2.0);
+        let flipped = line(2, "slate", 2.0).replace("slate", "slats");
+        let cut = line(3, "trace", 2.0);
+        fs::write(
+            &path,
+            format!(
+                "{}\n{}not an entry\n\u{0}\u{1}\n{}{}",
+                HEADER,
+                good,
+                flipped,
+                &cut[..10]
+            ),
+        )
+        .unwrap();
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(1, cache.stats().entries);
+        assert_eq!(Some(("crane".to_owned(), 2.0)), cache.get(1));
+        assert_eq!(None, cache.get(2));
+        cache.insert(3, "trace", 2.0);
+        drop(cache);
+
+        let mut cache = SolverCache::open(&path);
+        assert_eq!(2, cache.stats().entries);
+        assert_eq!(Some(("trace".to_owned(), 2.0)), cache.get(3));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Some((0xabc, ("crane".to_owned(), 2.25))),
+            parse_line(line(0xabc, "crane", 2.25).trim_end())
+        );
+        for bad in ["", "\t", "abc\tcrane\t2.25", "zz\tcrane\t2\t0"] {
+            assert_eq!(None, parse_line(bad));
+        }
+        let nan = format!("{:016x}\tcrane\tNaN", 1);
+        assert_eq!(
+            None,
+            parse_line(&format!("{}\t{:016x}", nan, checksum(&nan)))
+        );
+    }
+}
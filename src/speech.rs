@@ -0,0 +1,223 @@
+//! Feedback in plain sentences rather than colors, for screen readers
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{keyboard::KeyboardState, messages::Messages, LetterStatus};
+
+/// What each letter of a guess's feedback means, e.g. `correct position` or
+/// `in the word, wrong position`.
+///
+/// A letter that's gray after the same letter was green or yellow elsewhere in
+/// the guess is "no more in the word", since the word does have it, just not
+/// as many times as it was guessed.
+pub fn meanings(messages: &Messages, guess: &str, feedback: &[LetterStatus]) -> Vec<String> {
+    let found: Vec<char> = guess
+        .chars()
+        .zip(feedback)
+        .filter(|(_, &status)| status != LetterStatus::NotInWord)
+        .map(|(letter, _)| letter)
+        .collect();
+    guess
+        .chars()
+        .zip(feedback)
+        .map(|(letter, status)| match status {
+            LetterStatus::Correct => messages.format("letter-correct", &[]),
+            LetterStatus::InWord => messages.format("letter-present", &[]),
+            LetterStatus::NotInWord if found.contains(&letter) => {
+                messages.format("letter-no-more", &[])
+            }
+            LetterStatus::NotInWord => messages.format("letter-absent", &[]),
+        })
+        .collect()
+}
+
+/// Describe each letter of a guess in turn, e.g.
+/// `C — correct position. R — in the word, wrong position. A — not in the word.`
+///
+/// See [`meanings`] for how each letter is described.
+pub fn letters(messages: &Messages, guess: &str, feedback: &[LetterStatus]) -> String {
+    guess
+        .chars()
+        .zip(meanings(messages, guess, feedback))
+        .map(|(letter, meaning)| {
+            messages.format(
+                "letter-described",
+                &[
+                    ("letter", letter.to_uppercase().to_string().into()),
+                    ("meaning", meaning.into()),
+                ],
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Count the letters with each status, e.g. `2 correct, 1 present, 2 absent`
+pub fn tally(messages: &Messages, feedback: &[LetterStatus]) -> String {
+    let count = |status| feedback.iter().filter(|&&s| s == status).count();
+    messages.format(
+        "tally",
+        &[
+            ("correct", count(LetterStatus::Correct).into()),
+            ("present", count(LetterStatus::InWord).into()),
+            ("absent", count(LetterStatus::NotInWord).into()),
+        ],
+    )
+}
+
+/// Describe a guess letter by letter, then sum it up, e.g.
+/// `CRANE: C — correct position. [...] E — not in the word. 1 correct, 0 present, 4 absent.`
+pub fn guess(messages: &Messages, guess: &str, feedback: &[LetterStatus]) -> String {
+    messages.format(
+        "guess-described",
+        &[
+            ("guess", guess.to_uppercase().into()),
+            ("letters", letters(messages, guess, feedback).into()),
+            ("tally", tally(messages, feedback).into()),
+        ],
+    )
+}
+
+/// List the guessed letters grouped by their best-known status, e.g.
+/// `Correct: C. In the word: A, R. Not in the word: E, N.`
+///
+/// Groups with no letters are left out.
+pub fn keyboard(messages: &Messages, state: &KeyboardState) -> String {
+    let groups = [
+        ("keyboard-correct", LetterStatus::Correct),
+        ("keyboard-present", LetterStatus::InWord),
+        ("keyboard-absent", LetterStatus::NotInWord),
+    ];
+    let listed: Vec<String> = groups
+        .into_iter()
+        .filter_map(|(id, status)| {
+            let letters: Vec<String> = state
+                .letters_with(status)
+                .map(|letter| letter.to_uppercase().to_string())
+                .collect();
+            (!letters.is_empty())
+                .then(|| messages.format(id, &[("letters", letters.join(", ").into())]))
+        })
+        .collect();
+    if listed.is_empty() {
+        messages.format("keyboard-empty", &[])
+    } else {
+        listed.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LetterStatus::*;
+
+    #[test]
+    fn test_letters() {
+        assert_eq!(
+            "C — correct position. R — in the word, wrong position. A — not in the word. \
+             N — not in the word. E — correct position.",
+            letters(
+                Messages::english(),
+                "crane",
+                &[Correct, InWord, NotInWord, NotInWord, Correct]
+            )
+        );
+        assert_eq!(
+            "1 correct, 2 present, 2 absent",
+            tally(
+                Messages::english(),
+                &[Correct, InWord, NotInWord, InWord, NotInWord]
+            )
+        );
+        assert_eq!(
+            "0 correct, 0 present, 0 absent",
+            tally(Messages::english(), &[])
+        );
+    }
+
+    #[test]
+    fn test_duplicates() {
+        // "eerie" against "there": the first E is yellow, the second gray, and the last green
+        assert_eq!(
+            "E — in the word, wrong position. E — no more in the word. \
+             R — in the word, wrong position. I — not in the word. E — correct position.",
+            letters(
+                Messages::english(),
+                "eerie",
+                &[InWord, NotInWord, InWord, NotInWord, Correct]
+            )
+        );
+        // "llama" against "hello": both Ls are found
+        assert_eq!(
+            "L — in the word, wrong position. L — in the word, wrong position. \
+             A — not in the word. M — not in the word. A — not in the word.",
+            letters(
+                Messages::english(),
+                "llama",
+                &[InWord, InWord, NotInWord, NotInWord, NotInWord]
+            )
+        );
+        // "speed" against "abide": the gray E comes before the yellow one
+        assert_eq!(
+            "S — not in the word. P — not in the word. E — in the word, wrong position. \
+             E — no more in the word. D — in the word, wrong position.",
+            letters(
+                Messages::english(),
+                "speed",
+                &[NotInWord, NotInWord, InWord, NotInWord, InWord]
+            )
+        );
+    }
+
+    #[test]
+    fn test_guess() {
+        assert_eq!(
+            "SLATE: S — not in the word. L — not in the word. A — correct position. \
+             T — not in the word. E — correct position. 2 correct, 0 present, 3 absent.",
+            guess(
+                Messages::english(),
+                "slate",
+                &[NotInWord, NotInWord, Correct, NotInWord, Correct]
+            )
+        );
+    }
+
+    #[test]
+    fn test_keyboard() {
+        assert_eq!(
+            "No letters guessed yet.",
+            keyboard(Messages::english(), &KeyboardState::new())
+        );
+
+        let mut state = KeyboardState::new();
+        state.add("crane", &[Correct, InWord, NotInWord, NotInWord, InWord]);
+        assert_eq!(
+            "Correct: C. In the word: E, R. Not in the word: A, N.",
+            keyboard(Messages::english(), &state)
+        );
+        state.add("cream", &[Correct, Correct, Correct, NotInWord, NotInWord]);
+        assert_eq!(
+            "Correct: C, E, R. Not in the word: A, M, N.",
+            keyboard(Messages::english(), &state)
+        );
+
+        let french = Messages::builtin("fr").unwrap();
+        assert_eq!(
+            "Bien placées : C, E, R. Pas dans le mot : A, M, N.",
+            keyboard(french, &state)
+        );
+    }
+}
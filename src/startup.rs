@@ -0,0 +1,145 @@
+//! What the command-line programs share as they start: loading the word lists
+//! to play with, and explaining why they couldn't be
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+
+#[cfg(feature = "embedded-wordlists")]
+use crate::embedded;
+use crate::{
+    messages::Messages,
+    wordlist::{self, Limits, LoadError},
+};
+
+/// Load the word lists for the lifetime of the program, each file read within
+/// `limits`, or the built-in lists with `builtin` (the `--builtin-words` flag).
+///
+/// If either file can't be read, the built-in lists are used instead, when
+/// there are any, with a notice saying so; otherwise this exits with an error
+/// message. Both are in the language of `messages`.
+pub fn load_word_lists(
+    messages: &Messages,
+    guesses: &Path,
+    answers: &Path,
+    builtin: bool,
+    limits: &Limits,
+) -> (&'static [&'static str], &'static [&'static str]) {
+    let lists = match builtin {
+        #[cfg(feature = "embedded-wordlists")]
+        true => embedded::lists()
+            .map(|(guesses, answers)| wordlist::Lists {
+                guesses,
+                answers,
+                fallback: None,
+            })
+            .map_err(LoadError::Embedded),
+        #[cfg(not(feature = "embedded-wordlists"))]
+        true => {
+            eprintln!("{}", messages.format("error-no-builtin-word-lists", &[]));
+            process::exit(1);
+        }
+        false => wordlist::load_or_builtin_limited(guesses, answers, limits),
+    };
+    match lists {
+        Ok(lists) => {
+            if let Some(LoadError::Io { path, error }) = &lists.fallback {
+                eprintln!(
+                    "{}",
+                    messages.format(
+                        "notice-builtin-word-lists",
+                        &[
+                            ("path", resolve(path).display().to_string().into()),
+                            ("error", error.to_string().into()),
+                        ],
+                    )
+                );
+            }
+            (lists.guesses, lists.answers)
+        }
+        Err(e) => {
+            eprintln!("{}", word_list_error(messages, &e));
+            if let LoadError::Io { .. } = e {
+                eprintln!("{}", messages.format("word-list-help", &[]));
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Why a word list couldn't be loaded, naming the file in full
+pub fn word_list_error(messages: &Messages, e: &LoadError) -> String {
+    let (id, path, error) = match e {
+        LoadError::Io { path, error } => ("error-read-word-list", path, error.to_string()),
+        LoadError::Invalid { path, issue } => ("error-invalid-word-list", path, issue.to_string()),
+        #[cfg(feature = "embedded-wordlists")]
+        LoadError::Embedded(e) => {
+            return messages.format(
+                "error-builtin-word-lists",
+                &[("error", e.to_string().into())],
+            )
+        }
+    };
+    messages.format(
+        id,
+        &[
+            ("path", resolve(path).display().to_string().into()),
+            ("error", error.into()),
+        ],
+    )
+}
+
+/// Make a path absolute, so messages say exactly which file was meant
+pub fn resolve(path: &Path) -> PathBuf {
+    match env::current_dir() {
+        Ok(dir) => dir.join(path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn test_word_list_error() {
+        let e = LoadError::Io {
+            path: PathBuf::from("guesses.txt"),
+            error: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+        assert_eq!(
+            format!(
+                "Error: could not read word list '{}': not found",
+                resolve(Path::new("guesses.txt")).display()
+            ),
+            word_list_error(Messages::english(), &e)
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert!(resolve(Path::new("guesses.txt")).is_absolute());
+        assert_eq!(
+            Path::new("/tmp/guesses.txt"),
+            resolve(Path::new("/tmp/guesses.txt"))
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! The stages a game goes through, and a game at any of them
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, time::Instant};
+
+use crate::{
+    alphabet::Alphabet, dictionary::Dictionary, share::TileSet, transcript::Transcript, GameResult,
+    GameStatus, GuessError, GuessOutcome, LetterStatus, Wordle,
+};
+
+/// A stage of a [`Wordle`] game; see [`NoWord`], [`InProgress`] and [`Finished`]
+pub trait State: sealed::Sealed + std::fmt::Debug {}
+
+/// A stage where a word has been chosen: [`InProgress`] or [`Finished`]
+pub trait Started: State {}
+
+/// No word has been chosen yet; only the settings can be looked at and changed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NoWord;
+
+/// A word has been chosen, and guesses can be made against it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InProgress;
+
+/// The word has been found, or the guesses have run out, so it can be revealed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Finished;
+
+impl State for NoWord {}
+impl State for InProgress {}
+impl State for Finished {}
+impl Started for InProgress {}
+impl Started for Finished {}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::NoWord {}
+    impl Sealed for super::InProgress {}
+    impl Sealed for super::Finished {}
+}
+
+impl<'a, S: State> Wordle<'a, S> {
+    /// The same game at another stage
+    pub(crate) fn into_state<T: State>(self) -> Wordle<'a, T> {
+        Wordle {
+            seed: self.seed,
+            games: self.games,
+            number: self.number,
+            guesses: self.guesses,
+            answers: self.answers,
+            word: self.word,
+            word_length: self.word_length,
+            alphabet: self.alphabet,
+            max_guesses: self.max_guesses,
+            hard_mode: self.hard_mode,
+            reject_repeats: self.reject_repeats,
+            track_candidates: self.track_candidates,
+            assist: self.assist,
+            candidates: self.candidates,
+            history: self.history,
+            gave_up: self.gave_up,
+            max_hints: self.max_hints,
+            hints: self.hints,
+            started: self.started,
+            paused: self.paused,
+            guess_times: self.guess_times,
+            ended: self.ended,
+            state: PhantomData,
+        }
+    }
+}
+
+/// A game at whichever stage it's reached, for frontends that only find out
+/// which while they run, e.g. a server keeping many players' games.
+///
+/// Each method does what the game's own would at its stage, and what makes
+/// sense otherwise: e.g. a guess before a word is chosen is a
+/// [`GuessError::NoWord`], and a game moves on to [`Finished`] as soon as a
+/// guess ends it.
+#[derive(Debug)]
+pub enum AnyWordle<'a> {
+    /// No word has been chosen yet
+    NoWord(Wordle<'a, NoWord>),
+    /// Guesses are being made against the word
+    InProgress(Wordle<'a, InProgress>),
+    /// The game is over
+    Finished(Wordle<'a, Finished>),
+}
+
+impl<'a> From<Wordle<'a, NoWord>> for AnyWordle<'a> {
+    fn from(game: Wordle<'a, NoWord>) -> Self {
+        Self::NoWord(game)
+    }
+}
+
+impl<'a> From<Wordle<'a, InProgress>> for AnyWordle<'a> {
+    fn from(game: Wordle<'a, InProgress>) -> Self {
+        // a resumed game can be over already
+        match game.finish() {
+            Ok(game) => Self::Finished(game),
+            Err(game) => Self::InProgress(game),
+        }
+    }
+}
+
+impl<'a> From<Wordle<'a, Finished>> for AnyWordle<'a> {
+    fn from(game: Wordle<'a, Finished>) -> Self {
+        Self::Finished(game)
+    }
+}
+
+impl<'a> AnyWordle<'a> {
+    /// How the game stands, or `None` if no word has been chosen
+    pub fn status(&self) -> Option<GameStatus> {
+        match self {
+            Self::NoWord(_) => None,
+            Self::InProgress(game) => Some(game.status()),
+            Self::Finished(game) => Some(game.status()),
+        }
+    }
+
+    /// The guesses made against the word so far, along with their feedback
+    pub fn history(&self) -> &[(&'a str, Vec<LetterStatus>)] {
+        match self {
+            Self::NoWord(_) => &[],
+            Self::InProgress(game) => game.history(),
+            Self::Finished(game) => game.history(),
+        }
+    }
+
+    /// The answer, once the game is over
+    pub fn reveal(&self) -> Option<&'a str> {
+        match self {
+            Self::Finished(game) => Some(game.reveal()),
+            _ => None,
+        }
+    }
+
+    /// How the game went, once it's over
+    pub fn result(&self) -> Option<GameResult> {
+        match self {
+            Self::Finished(game) => Some(game.result()),
+            _ => None,
+        }
+    }
+
+    /// A record of the game so far, or `None` if no word has been chosen
+    pub fn transcript(&self) -> Option<Transcript> {
+        match self {
+            Self::NoWord(_) => None,
+            Self::InProgress(game) => Some(game.transcript()),
+            Self::Finished(game) => Some(game.transcript()),
+        }
+    }
+
+    /// The feedback for every guess, as squares to share, once the game is over
+    pub fn share_grid(&self, tiles: &TileSet<'_>) -> Option<String> {
+        match self {
+            Self::Finished(game) => Some(game.share_grid(tiles)),
+            _ => None,
+        }
+    }
+
+    /// Guess a word like [`Wordle::guess`], moving on to [`Finished`] if the
+    /// guess ends the game
+    pub fn guess(&mut self, word: &str) -> Result<GuessOutcome, GuessError> {
+        let outcome = match self {
+            Self::NoWord(_) => return Err(GuessError::NoWord),
+            Self::InProgress(game) => game.guess(word)?,
+            Self::Finished(_) => return Err(GuessError::GameOver),
+        };
+        self.advance();
+        Ok(outcome)
+    }
+
+    /// Stop guessing the word, counting it as a loss; nothing happens unless
+    /// the game is in progress
+    pub fn give_up(&mut self) {
+        if let Self::InProgress(game) = self {
+            game.give_up();
+        }
+        self.advance();
+    }
+
+    /// Choose the next word to play against, abandoning the current one.
+    ///
+    /// See [`Wordle::choose_word`].
+    pub fn choose_word(&mut self) {
+        let game = self.take().into_setup();
+        *self = Self::InProgress(game.choose_word());
+    }
+
+    /// Play against a specific word, abandoning the current one.
+    ///
+    /// See [`Wordle::set_answer`]; if the word can't be played, the game goes
+    /// back to having no word.
+    pub fn set_answer(&mut self, word: &str) -> Result<(), GuessError> {
+        let game = self.take().into_setup();
+        let (game, result) = match game.check_answer(word) {
+            Ok(word) => (Self::InProgress(game.start(word)), Ok(())),
+            Err(e) => (Self::NoWord(game), Err(e)),
+        };
+        *self = game;
+        result
+    }
+
+    /// The game with the current word put away, keeping its settings
+    fn into_setup(self) -> Wordle<'a, NoWord> {
+        match self {
+            Self::NoWord(game) => game,
+            Self::InProgress(game) => game.clear(),
+            Self::Finished(game) => game.clear(),
+        }
+    }
+
+    /// Move a game that's over on to [`Finished`]
+    fn advance(&mut self) {
+        if matches!(self, Self::InProgress(game) if game.status() != GameStatus::InProgress) {
+            *self = match self.take() {
+                Self::InProgress(game) => Self::Finished(game.into_state()),
+                other => other,
+            };
+        }
+    }
+
+    /// Take the game out, leaving an empty one in its place for a moment
+    fn take(&mut self) -> Self {
+        std::mem::replace(self, Self::NoWord(Wordle::empty()))
+    }
+}
+
+impl Wordle<'_, NoWord> {
+    /// A game with no words at all, to stand in for one being moved
+    fn empty() -> Self {
+        Wordle {
+            seed: 0,
+            games: 0,
+            number: None,
+            guesses: Dictionary::new(&[]),
+            answers: &[],
+            word: "",
+            word_length: 0,
+            alphabet: Alphabet::default(),
+            max_guesses: 0,
+            hard_mode: false,
+            reject_repeats: false,
+            track_candidates: false,
+            assist: false,
+            candidates: None,
+            history: Vec::new(),
+            gave_up: false,
+            max_hints: 0,
+            hints: Vec::new(),
+            started: Instant::now(),
+            paused: None,
+            guess_times: Vec::new(),
+            ended: None,
+            state: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 4] = ["crane", "slate", "trace", "brace"];
+
+    #[test]
+    fn test_any_wordle() {
+        let mut game = AnyWordle::from(Wordle::new(&WORDS, &WORDS));
+        assert_eq!(None, game.status());
+        assert_eq!(Err(GuessError::NoWord), game.guess("crane"));
+        assert_eq!(None, game.transcript());
+
+        game.set_answer("trace").unwrap();
+        game.guess("crane").unwrap();
+        assert!(matches!(game, AnyWordle::InProgress(_)));
+        assert_eq!(None, game.reveal());
+        assert_eq!(None, game.result());
+        assert_eq!(1, game.history().len());
+
+        let outcome = game.guess("trace").unwrap();
+        assert!(matches!(game, AnyWordle::Finished(_)));
+        assert_eq!(Some("trace"), game.reveal());
+        assert_eq!(outcome.result().cloned(), game.result());
+        assert_eq!(Some(GameStatus::Won { guesses: 2 }), game.status());
+        assert_eq!(Err(GuessError::GameOver), game.guess("slate"));
+
+        // the next word starts a fresh game
+        assert_eq!(Err(GuessError::NotInWordList), game.set_answer("zzzzz"));
+        assert!(matches!(game, AnyWordle::NoWord(_)));
+        game.choose_word();
+        assert_eq!(Some(GameStatus::InProgress), game.status());
+        game.give_up();
+        assert_eq!(Some(GameStatus::Lost), game.status());
+        assert!(game.reveal().is_some());
+    }
+}
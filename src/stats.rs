@@ -0,0 +1,1064 @@
+//! Player statistics across games, like the official game's statistics popup
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    iter,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{GameResult, GameStatus, LetterStatus};
+
+/// Totals over every game a player has finished
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Stats {
+    /// The number of games finished
+    played: u32,
+    /// The number of games won
+    wins: u32,
+    /// The number of games won in a row, up to the most recent one
+    current_streak: u32,
+    /// The longest run of games won in a row
+    max_streak: u32,
+    /// The number of games won in 1, 2, ... guesses
+    distribution: Vec<u32>,
+    /// The number of games finished with the help of hints or the solver
+    assisted: u32,
+    /// The most recent daily puzzle played
+    last_daily: Option<DailyResult>,
+    /// The fastest win in a timed game
+    best_time: Option<Duration>,
+    /// How the most recent game ended
+    last_game: Option<GameStatus>,
+    /// The games in the log, oldest first; they're kept in a log of their own
+    /// by a [`StatsStore`], not with the totals
+    #[serde(skip)]
+    games: Vec<GameRecord>,
+}
+
+/// How a daily puzzle went, so it isn't played twice
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DailyResult {
+    /// The puzzle number
+    pub puzzle: u32,
+    /// How the game ended
+    pub status: GameStatus,
+    /// The number of guesses allowed
+    pub max_guesses: usize,
+    /// Whether it was played in hard mode
+    pub hard_mode: bool,
+    /// The number of hints used
+    #[serde(default)]
+    pub hints: usize,
+    /// Whether it was played with help from the solver
+    #[serde(default)]
+    pub assisted: bool,
+    /// The feedback for each guess, without the letters
+    pub feedback: Vec<Vec<LetterStatus>>,
+    /// The word played automatically as the first guess, if there was one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opener: Option<String>,
+}
+
+/// A finished game, as kept in a [`StatsStore`]'s game log
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GameRecord {
+    /// The day the game was played
+    pub date: NaiveDate,
+    /// The number of the daily puzzle, if the game was one
+    pub puzzle: Option<u32>,
+    /// The seed the answer was chosen with, if it was chosen at random
+    pub seed: Option<u64>,
+    /// The game's number in its seeded session, if it had one
+    pub game: Option<u32>,
+    /// Whether the word was found
+    pub won: bool,
+    /// The number of guesses made
+    pub guesses: usize,
+    /// Whether it was played in hard mode
+    pub hard_mode: bool,
+    /// Whether it was played with the help of hints or the solver
+    pub assisted: bool,
+    /// The time spent on the game
+    pub duration: Duration,
+}
+
+/// What makes two [`GameRecord`]s the same game
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum GameKey {
+    /// A daily puzzle, played on a day
+    Daily(NaiveDate, u32),
+    /// A numbered game in a seeded session
+    Seeded(u64, u32),
+}
+
+impl GameRecord {
+    /// Log a game played on `date`, with no puzzle, seed or number of its own
+    pub fn new(result: &GameResult, date: NaiveDate) -> Self {
+        Self {
+            date,
+            puzzle: None,
+            seed: None,
+            game: None,
+            won: result.won,
+            guesses: result.turns_used,
+            hard_mode: result.hard_mode,
+            assisted: result.assisted,
+            duration: result.duration,
+        }
+    }
+
+    /// What makes the game the same as another, if anything does
+    fn key(&self) -> Option<GameKey> {
+        match (self.puzzle, self.seed, self.game) {
+            (Some(puzzle), _, _) => Some(GameKey::Daily(self.date, puzzle)),
+            (None, Some(seed), Some(game)) => Some(GameKey::Seeded(seed, game)),
+            _ => None,
+        }
+    }
+
+    /// Whether the game has the same result as another
+    fn same_result(&self, other: &Self) -> bool {
+        (self.won, self.guesses, self.hard_mode, self.assisted)
+            == (other.won, other.guesses, other.hard_mode, other.assisted)
+    }
+}
+
+/// Two different records of the same daily puzzle, found by [`Stats::merge`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Conflict {
+    /// The record that was kept: the one played first
+    pub kept: GameRecord,
+    /// The record that was dropped
+    pub dropped: GameRecord,
+}
+
+/// What [`Stats::merge`] did with the other stats' games
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MergeSummary {
+    /// The number of games added, including games played before there was a
+    /// game log, which are only in the totals; a game that took the place of a
+    /// [conflicting](Conflict) one isn't counted
+    pub added: u32,
+    /// The number of games skipped, as they were already recorded
+    pub duplicates: u32,
+    /// The daily puzzles recorded with different results, and which was kept
+    pub conflicts: Vec<Conflict>,
+}
+
+/// The columns of an exported game log, in order
+const COLUMNS: [&str; 9] = [
+    "date",
+    "puzzle",
+    "seed",
+    "game",
+    "won",
+    "guesses",
+    "hard_mode",
+    "assisted",
+    "seconds",
+];
+
+/// Write a game log as CSV, with a header row and then a row for each game.
+///
+/// Columns that don't apply to a game, like the puzzle number of a random
+/// word, are left empty, and the duration is in seconds.
+pub fn write_csv<W: Write>(mut out: W, games: &[GameRecord]) -> io::Result<()> {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    writeln!(out, "{}", COLUMNS.join(","))?;
+    for game in games {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{:.3}",
+            game.date,
+            optional(game.puzzle.map(|puzzle| puzzle.to_string())),
+            optional(game.seed.map(|seed| seed.to_string())),
+            optional(game.game.map(|number| number.to_string())),
+            game.won,
+            game.guesses,
+            game.hard_mode,
+            game.assisted,
+            game.duration.as_secs_f64()
+        )?;
+    }
+    out.flush()
+}
+
+/// A game as written by [`write_json`], with the duration in seconds
+#[derive(Serialize)]
+struct JsonRow {
+    /// The day the game was played
+    date: NaiveDate,
+    /// The number of the daily puzzle, if the game was one
+    puzzle: Option<u32>,
+    /// The seed the answer was chosen with, if it was chosen at random
+    seed: Option<u64>,
+    /// The game's number in its seeded session, if it had one
+    game: Option<u32>,
+    /// Whether the word was found
+    won: bool,
+    /// The number of guesses made
+    guesses: usize,
+    /// Whether it was played in hard mode
+    hard_mode: bool,
+    /// Whether it was played with help
+    assisted: bool,
+    /// The time spent on the game, in seconds
+    seconds: f64,
+}
+
+/// Write a game log as a JSON array, with an object for each game that has
+/// the same fields as the columns of [`write_csv`]
+pub fn write_json<W: Write>(mut out: W, games: &[GameRecord]) -> io::Result<()> {
+    let rows: Vec<JsonRow> = games
+        .iter()
+        .map(|game| JsonRow {
+            date: game.date,
+            puzzle: game.puzzle,
+            seed: game.seed,
+            game: game.game,
+            won: game.won,
+            guesses: game.guesses,
+            hard_mode: game.hard_mode,
+            assisted: game.assisted,
+            seconds: game.duration.as_secs_f64(),
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut out, &rows)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+/// What the bars of a [histogram](render_histogram) are drawn with
+pub const BAR: char = '█';
+
+/// Draw a guess distribution as text, a row for each number of guesses from
+/// one to six and then an `X` row for games lost, e.g. `3: ████████ 12`.
+///
+/// The bars are scaled so the longest fills the row, and every row is padded
+/// out so the counts line up on the right, within `width` columns. A row with no games
+/// has no bar; any other gets at least one block. The row at `highlight`, if
+/// any, is marked with `<`. The labels and counts are never cut short, so rows
+/// only fit in very narrow widths without their bars.
+pub fn render_histogram(dist: &[u32; 7], width: usize, highlight: Option<usize>) -> Vec<String> {
+    let most = dist.iter().copied().max().unwrap_or(0);
+    let count_width = most.to_string().len();
+    let marker_width = if highlight.is_some() { 2 } else { 0 };
+    // the label and ": " before the bar, and the count and marker after it
+    let longest = width.saturating_sub(4 + count_width + marker_width);
+    dist.iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let label = if i < 6 {
+                char::from(b'1' + i as u8)
+            } else {
+                'X'
+            };
+            let bar = (u64::from(count) * longest as u64).div_ceil(u64::from(most.max(1)));
+            let bar: String = iter::repeat_n(BAR, bar as usize).collect();
+            let marker = if highlight == Some(i) { " <" } else { "" };
+            format!(
+                "{}: {:<longest$} {:>count_width$}{}",
+                label, bar, count, marker
+            )
+        })
+        .collect()
+}
+
+impl Stats {
+    /// Start with no games played
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the result of a finished game, as an assisted one if hints or
+    /// the solver helped
+    pub fn record(&mut self, result: &GameResult) {
+        if result.assisted {
+            self.record_assisted(result.status());
+        } else {
+            self.record_status(result.status());
+        }
+    }
+
+    /// Record a finished game by how it ended alone; games still in progress are ignored
+    pub fn record_status(&mut self, status: GameStatus) {
+        match status {
+            GameStatus::InProgress => return,
+            GameStatus::Won { guesses } => {
+                self.wins += 1;
+                self.current_streak += 1;
+                self.max_streak = self.max_streak.max(self.current_streak);
+                if self.distribution.len() < guesses {
+                    self.distribution.resize(guesses, 0);
+                }
+                self.distribution[guesses - 1] += 1;
+            }
+            GameStatus::Lost => self.current_streak = 0,
+        }
+        self.played += 1;
+        self.last_game = Some(status);
+    }
+
+    /// Record the result of a finished game that was played with the help of
+    /// hints or the solver
+    pub fn record_assisted(&mut self, status: GameStatus) {
+        if status != GameStatus::InProgress {
+            self.assisted += 1;
+        }
+        self.record_status(status);
+    }
+
+    /// Record the result of a daily puzzle, like any other game, and remember it
+    pub fn record_daily(&mut self, result: DailyResult) {
+        if result.hints > 0 || result.assisted {
+            self.record_assisted(result.status);
+        } else {
+            self.record_status(result.status);
+        }
+        self.last_daily = Some(result);
+    }
+
+    /// Record the time taken to win a timed game, and get back whether it's a new personal best
+    pub fn record_time(&mut self, time: Duration) -> bool {
+        let best = self.best_time.is_none_or(|best| time < best);
+        if best {
+            self.best_time = Some(time);
+        }
+        best
+    }
+
+    /// The result of a daily puzzle, if it was the most recent one played
+    pub fn daily_result(&self, puzzle: u32) -> Option<&DailyResult> {
+        self.last_daily
+            .as_ref()
+            .filter(|result| result.puzzle == puzzle)
+    }
+
+    /// The number of games finished
+    pub fn played(&self) -> u32 {
+        self.played
+    }
+
+    /// The number of games won
+    pub fn wins(&self) -> u32 {
+        self.wins
+    }
+
+    /// The number of games finished with the help of hints or the solver
+    pub fn assisted(&self) -> u32 {
+        self.assisted
+    }
+
+    /// The percentage of games won, rounded to the nearest whole number
+    pub fn win_percentage(&self) -> u32 {
+        if self.played == 0 {
+            return 0;
+        }
+        (f64::from(self.wins) * 100.0 / f64::from(self.played)).round() as u32
+    }
+
+    /// The number of games won in a row, up to the most recent one
+    pub fn current_streak(&self) -> u32 {
+        self.current_streak
+    }
+
+    /// The longest run of games won in a row
+    pub fn max_streak(&self) -> u32 {
+        self.max_streak
+    }
+
+    /// The fastest win in a timed game, if any
+    pub fn best_time(&self) -> Option<Duration> {
+        self.best_time
+    }
+
+    /// How the most recent game ended, or `None` if none have been played
+    pub fn last_game(&self) -> Option<GameStatus> {
+        self.last_game
+    }
+
+    /// The games in the game log, oldest first; empty unless the stats were
+    /// loaded by a [`StatsStore`]
+    pub fn games(&self) -> &[GameRecord] {
+        &self.games
+    }
+
+    /// Fold in the stats from somewhere else, e.g. another computer.
+    ///
+    /// The totals are added up, less the games that are in both game logs: a
+    /// daily puzzle played on the same day, or a seeded game with the same
+    /// number. If the same daily puzzle was recorded with a different result,
+    /// the one played first is kept. The streaks are worked out again from the
+    /// merged game log, which is sorted by date.
+    pub fn merge(&mut self, other: &Stats) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+        let mut theirs = other.clone();
+        // games of theirs that took the place of ours, so weren't added
+        let mut replaced = 0;
+        for game in &other.games {
+            let key = game.key();
+            let same = self.games.iter().position(|ours| match game.puzzle {
+                Some(puzzle) => ours.puzzle == Some(puzzle),
+                None => key.is_some() && ours.key() == key,
+            });
+            let Some(same) = same else {
+                self.games.push(game.clone());
+                continue;
+            };
+
+            let ours = &self.games[same];
+            if ours.key() == key && ours.same_result(game) {
+                summary.duplicates += 1;
+                theirs.untally(game);
+            } else if ours.date <= game.date {
+                summary.conflicts.push(Conflict {
+                    kept: ours.clone(),
+                    dropped: game.clone(),
+                });
+                theirs.untally(game);
+            } else {
+                summary.conflicts.push(Conflict {
+                    kept: game.clone(),
+                    dropped: ours.clone(),
+                });
+                let dropped = std::mem::replace(&mut self.games[same], game.clone());
+                self.untally(&dropped);
+                replaced += 1;
+            }
+        }
+        summary.added = theirs.played.saturating_sub(replaced);
+
+        self.played += theirs.played;
+        self.wins += theirs.wins;
+        self.assisted += theirs.assisted;
+        if self.distribution.len() < theirs.distribution.len() {
+            self.distribution.resize(theirs.distribution.len(), 0);
+        }
+        for (ours, theirs) in self.distribution.iter_mut().zip(&theirs.distribution) {
+            *ours += theirs;
+        }
+        self.best_time = match (self.best_time, other.best_time) {
+            (Some(ours), Some(theirs)) => Some(ours.min(theirs)),
+            (ours, theirs) => ours.or(theirs),
+        };
+        if other.last_daily.as_ref().map(|result| result.puzzle)
+            > self.last_daily.as_ref().map(|result| result.puzzle)
+        {
+            self.last_daily = other.last_daily.clone();
+        }
+        self.last_game = self.last_game.or(other.last_game);
+
+        // the sort is stable, so games on the same day stay in the order they were played
+        self.games.sort_by_key(|game| game.date);
+        self.max_streak = self.max_streak.max(other.max_streak);
+        if !self.games.is_empty() {
+            let mut streak = 0;
+            for game in &self.games {
+                streak = if game.won { streak + 1 } else { 0 };
+                self.max_streak = self.max_streak.max(streak);
+            }
+            self.current_streak = streak;
+        }
+        summary
+    }
+
+    /// Take a game out of the totals, as if it had never been recorded
+    fn untally(&mut self, game: &GameRecord) {
+        self.played = self.played.saturating_sub(1);
+        if game.won {
+            self.wins = self.wins.saturating_sub(1);
+            if let Some(count) = game
+                .guesses
+                .checked_sub(1)
+                .and_then(|i| self.distribution.get_mut(i))
+            {
+                *count = count.saturating_sub(1);
+            }
+        }
+        if game.assisted {
+            self.assisted = self.assisted.saturating_sub(1);
+        }
+    }
+
+    /// The number of games won in each number of guesses, starting at one guess.
+    ///
+    /// This has an entry for every number of guesses up to the most any win took.
+    pub fn distribution(&self) -> &[u32] {
+        &self.distribution
+    }
+
+    /// The guess distribution as [`render_histogram`] draws it: the number of
+    /// games won in one to six guesses, then the number lost.
+    ///
+    /// Wins in more than six guesses, which only games with extra guesses
+    /// have, aren't counted.
+    pub fn histogram(&self) -> [u32; 7] {
+        let mut dist = [0; 7];
+        for (row, &count) in dist.iter_mut().zip(&self.distribution) {
+            *row = count;
+        }
+        dist[6] = self.played.saturating_sub(self.wins);
+        dist
+    }
+}
+
+/// Reads and writes [`Stats`] as JSON in a file, and keeps a log of every
+/// game next to it, one [`GameRecord`] per line
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StatsStore {
+    /// The stats file
+    path: PathBuf,
+}
+
+impl StatsStore {
+    /// Use the stats in a file; nothing is read or written until asked
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The stats file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the stats, along with the game log; if the file doesn't exist
+    /// yet, there are no games played.
+    ///
+    /// A file that isn't valid stats is an [`ErrorKind::InvalidData`] error;
+    /// see [`StatsStore::recover`].
+    pub fn load(&self) -> io::Result<Stats> {
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Stats::new()),
+            Err(e) => return Err(e),
+        };
+        let mut stats: Stats =
+            serde_json::from_str(&json).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        stats.games = self.games()?;
+        Ok(stats)
+    }
+
+    /// Write the stats, creating the file and its directory if needed.
+    ///
+    /// The file is replaced in one step, so an interrupted write can't corrupt it.
+    pub fn save(&self, stats: &Stats) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(stats)?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)
+    }
+
+    /// The game log: e.g. `stats.games.jsonl` next to `stats.json`
+    pub fn log_path(&self) -> PathBuf {
+        self.path.with_extension("games.jsonl")
+    }
+
+    /// Add a game to the end of the log, creating it and its directory if needed
+    pub fn append(&self, game: &GameRecord) -> io::Result<()> {
+        let path = self.log_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(game)?;
+        line.push('\n');
+        // one write, so games logged at the same time can't be interleaved
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Replace the game log with the stats' games, e.g. after [`Stats::merge`].
+    ///
+    /// Like the stats file, the log is replaced in one step.
+    pub fn save_games(&self, stats: &Stats) -> io::Result<()> {
+        let path = self.log_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut lines = String::new();
+        for game in &stats.games {
+            lines.push_str(&serde_json::to_string(game)?);
+            lines.push('\n');
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, lines)?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Read every game in the log, oldest first; if there's no log yet, no
+    /// games have been logged.
+    ///
+    /// A line that isn't a valid game is an [`ErrorKind::InvalidData`] error.
+    pub fn games(&self) -> io::Result<Vec<GameRecord>> {
+        let text = match fs::read_to_string(self.log_path()) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    io::Error::new(ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+                })
+            })
+            .collect()
+    }
+
+    /// Move an unreadable stats file out of the way, so the next save starts afresh.
+    ///
+    /// Returns where the old file was moved to.
+    pub fn recover(&self) -> io::Result<PathBuf> {
+        let backup = self.path.with_extension("json.bak");
+        fs::rename(&self.path, &backup)?;
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut stats = Stats::new();
+        assert_eq!(0, stats.win_percentage());
+        assert_eq!(None, stats.last_game());
+
+        for status in [
+            GameStatus::Won { guesses: 3 },
+            GameStatus::Won { guesses: 4 },
+            GameStatus::InProgress,
+            GameStatus::Lost,
+            GameStatus::Won { guesses: 3 },
+        ] {
+            stats.record_status(status);
+        }
+        assert_eq!(4, stats.played());
+        assert_eq!(3, stats.wins());
+        assert_eq!(75, stats.win_percentage());
+        assert_eq!(1, stats.current_streak());
+        assert_eq!(2, stats.max_streak());
+        assert_eq!(&[0, 0, 2, 1], stats.distribution());
+        assert_eq!(Some(GameStatus::Won { guesses: 3 }), stats.last_game());
+
+        stats.record_status(GameStatus::Won { guesses: 1 });
+        stats.record_status(GameStatus::Won { guesses: 1 });
+        assert_eq!(3, stats.current_streak());
+        assert_eq!(3, stats.max_streak());
+        assert_eq!(&[2, 0, 2, 1], stats.distribution());
+        assert_eq!(83, stats.win_percentage());
+    }
+
+    #[test]
+    fn test_record_result() {
+        let guesses = ["crane", "slate"];
+        let mut game = crate::Wordle::new(&guesses, &["crane"]).choose_word();
+        game.guess("slate").unwrap();
+        let outcome = game.guess("crane").unwrap();
+        let result = outcome.result().unwrap();
+
+        let mut stats = Stats::new();
+        stats.record(result);
+        assert_eq!(1, stats.wins());
+        assert_eq!(&[0, 1], stats.distribution());
+        assert_eq!(0, stats.assisted());
+        stats.record(&GameResult {
+            won: false,
+            assisted: true,
+            ..result.clone()
+        });
+        assert_eq!(2, stats.played());
+        assert_eq!(1, stats.assisted());
+        assert_eq!(0, stats.current_streak());
+
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let record = GameRecord::new(result, date);
+        assert_eq!((date, None), (record.date, record.puzzle));
+        assert!(record.won);
+        assert_eq!(2, record.guesses);
+        assert_eq!(result.duration, record.duration);
+    }
+
+    #[test]
+    fn test_record_time() {
+        let mut stats = Stats::new();
+        assert_eq!(None, stats.best_time());
+        assert!(stats.record_time(Duration::from_secs(60)));
+        assert!(stats.record_time(Duration::from_secs(47)));
+        assert!(!stats.record_time(Duration::from_secs(50)));
+        assert_eq!(Some(Duration::from_secs(47)), stats.best_time());
+    }
+
+    #[test]
+    fn test_record_daily() {
+        let mut stats = Stats::new();
+        let result = |puzzle| DailyResult {
+            puzzle,
+            status: GameStatus::Won { guesses: 2 },
+            max_guesses: 6,
+            hard_mode: false,
+            hints: 1,
+            assisted: false,
+            feedback: vec![
+                vec![LetterStatus::NotInWord; 5],
+                vec![LetterStatus::Correct; 5],
+            ],
+            opener: None,
+        };
+
+        stats.record_daily(result(100));
+        assert_eq!(1, stats.wins());
+        assert_eq!(Some(&result(100)), stats.daily_result(100));
+        stats.record_daily(result(101));
+        assert_eq!(None, stats.daily_result(100));
+        assert_eq!(2, stats.current_streak());
+        assert_eq!(2, stats.assisted());
+
+        // games played with the solver's help are assisted too
+        stats.record_daily(DailyResult {
+            hints: 0,
+            assisted: true,
+            ..result(102)
+        });
+        assert_eq!(3, stats.assisted());
+
+        stats.record_assisted(GameStatus::InProgress);
+        stats.record_status(GameStatus::Lost);
+        assert_eq!(3, stats.assisted());
+        assert_eq!(4, stats.played());
+    }
+
+    #[test]
+    fn test_store() {
+        let dir = std::env::temp_dir().join(format!("wordle-stats-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = StatsStore::new(dir.join("nested").join("stats.json"));
+
+        // the first run starts from nothing, and creates the file
+        assert_eq!(Stats::new(), store.load().unwrap());
+        let mut stats = Stats::new();
+        stats.record_status(GameStatus::Won { guesses: 2 });
+        store.save(&stats).unwrap();
+        assert_eq!(stats, store.load().unwrap());
+
+        // a corrupt file is an error, until it's moved aside
+        fs::write(store.path(), "{ not json").unwrap();
+        let e = store.load().unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, e.kind());
+        let backup = store.recover().unwrap();
+        assert_eq!("{ not json", fs::read_to_string(backup).unwrap());
+        assert_eq!(Stats::new(), store.load().unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Two games, one of each kind
+    fn games() -> Vec<GameRecord> {
+        vec![
+            GameRecord {
+                date: NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+                puzzle: Some(196),
+                seed: None,
+                game: None,
+                won: true,
+                guesses: 3,
+                hard_mode: true,
+                assisted: false,
+                duration: Duration::from_millis(95_250),
+            },
+            GameRecord {
+                date: NaiveDate::from_ymd_opt(2022, 1, 2).unwrap(),
+                puzzle: None,
+                seed: Some(42),
+                game: Some(7),
+                won: false,
+                guesses: 6,
+                hard_mode: false,
+                assisted: true,
+                duration: Duration::from_secs(300),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_game_log() {
+        let dir = std::env::temp_dir().join(format!("wordle-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = StatsStore::new(dir.join("stats.es.json"));
+        assert_eq!(dir.join("stats.es.games.jsonl"), store.log_path());
+
+        assert!(store.games().unwrap().is_empty());
+        for game in games() {
+            store.append(&game).unwrap();
+        }
+        assert_eq!(games(), store.games().unwrap());
+
+        // the log is kept apart from the summary, which doesn't touch it,
+        // but it's loaded along with it
+        store.save(&Stats::new()).unwrap();
+        assert_eq!(games(), store.load().unwrap().games());
+
+        let mut stats = Stats::new();
+        stats.games = games()[1..].to_vec();
+        store.save_games(&stats).unwrap();
+        assert_eq!(&games()[1..], store.games().unwrap());
+
+        fs::write(store.log_path(), "\n{ not json\n").unwrap();
+        let e = store.games().unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, e.kind());
+        assert!(e.to_string().starts_with("line 2: "));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_export() {
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &[]).unwrap();
+        assert_eq!(
+            "date,puzzle,seed,game,won,guesses,hard_mode,assisted,seconds\n",
+            String::from_utf8(csv).unwrap()
+        );
+
+        // what's written can be read back as the same games
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &games()).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(Some(COLUMNS.join(",").as_str()), lines.next());
+        let read: Vec<GameRecord> = lines
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                assert_eq!(COLUMNS.len(), fields.len());
+                GameRecord {
+                    date: fields[0].parse().unwrap(),
+                    puzzle: fields[1].parse().ok(),
+                    seed: fields[2].parse().ok(),
+                    game: fields[3].parse().ok(),
+                    won: fields[4].parse().unwrap(),
+                    guesses: fields[5].parse().unwrap(),
+                    hard_mode: fields[6].parse().unwrap(),
+                    assisted: fields[7].parse().unwrap(),
+                    duration: Duration::from_secs_f64(fields[8].parse().unwrap()),
+                }
+            })
+            .collect();
+        assert_eq!(games(), read);
+        assert!(csv.contains("\n2022-01-01,196,,,true,3,true,false,95.250\n"));
+
+        let mut json = Vec::new();
+        write_json(&mut json, &games()).unwrap();
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_slice(&json).unwrap();
+        let read: Vec<GameRecord> = rows
+            .into_iter()
+            .map(|mut row| {
+                assert_eq!(COLUMNS.len(), row.len());
+                let seconds = row.remove("seconds").unwrap().as_f64().unwrap();
+                row.insert(
+                    "duration".into(),
+                    serde_json::to_value(Duration::from_secs_f64(seconds)).unwrap(),
+                );
+                serde_json::from_value(row.into()).unwrap()
+            })
+            .collect();
+        assert_eq!(games(), read);
+
+        let mut json = Vec::new();
+        write_json(&mut json, &[]).unwrap();
+        assert_eq!("[]\n", String::from_utf8(json).unwrap());
+    }
+
+    #[test]
+    fn test_merge() {
+        let game = |day, puzzle, seed: Option<(u64, u32)>, guesses: Option<usize>| GameRecord {
+            date: NaiveDate::from_ymd_opt(2022, 1, day).unwrap(),
+            puzzle,
+            seed: seed.map(|(seed, _)| seed),
+            game: seed.map(|(_, game)| game),
+            won: guesses.is_some(),
+            guesses: guesses.unwrap_or(6),
+            hard_mode: false,
+            assisted: false,
+            duration: Duration::from_secs(60),
+        };
+        let played = |games: &[GameRecord]| {
+            let mut stats = Stats::new();
+            for game in games {
+                stats.record_status(match game.won {
+                    true => GameStatus::Won {
+                        guesses: game.guesses,
+                    },
+                    false => GameStatus::Lost,
+                });
+            }
+            stats.games = games.to_vec();
+            stats
+        };
+
+        let daily = game(1, Some(196), None, Some(3));
+        let seeded = game(1, None, Some((42, 1)), None);
+        let late = game(5, Some(199), None, Some(5));
+        let kept = game(6, Some(200), None, Some(4));
+        let mut laptop = played(&[daily.clone(), seeded.clone(), late.clone(), kept.clone()]);
+
+        let new = game(2, None, Some((42, 2)), Some(2));
+        let early = game(4, Some(199), None, Some(1));
+        let dropped = game(7, Some(200), None, None);
+        let desktop = played(&[
+            daily.clone(),
+            seeded.clone(),
+            new.clone(),
+            early.clone(),
+            dropped.clone(),
+        ]);
+
+        assert_eq!(
+            MergeSummary {
+                added: 1,
+                duplicates: 2,
+                conflicts: vec![
+                    Conflict {
+                        kept: early.clone(),
+                        dropped: late
+                    },
+                    Conflict {
+                        kept: kept.clone(),
+                        dropped
+                    },
+                ],
+            },
+            laptop.merge(&desktop)
+        );
+        assert_eq!(vec![daily, seeded, new, early, kept], laptop.games());
+        assert_eq!(5, laptop.played());
+        assert_eq!(4, laptop.wins());
+        assert_eq!(&[1, 1, 1, 1, 0], laptop.distribution());
+        assert_eq!(3, laptop.current_streak());
+        assert_eq!(3, laptop.max_streak());
+
+        // merging again changes nothing
+        let merged = laptop.clone();
+        let summary = laptop.merge(&desktop);
+        assert_eq!(0, summary.added);
+        assert_eq!(merged, laptop);
+
+        // without game logs, the totals are just added up
+        let mut ours = Stats::new();
+        ours.record_status(GameStatus::Won { guesses: 2 });
+        ours.record_time(Duration::from_secs(90));
+        let mut theirs = Stats::new();
+        theirs.record_status(GameStatus::Won { guesses: 4 });
+        theirs.record_status(GameStatus::Won { guesses: 4 });
+        theirs.record_time(Duration::from_secs(30));
+        assert_eq!(2, ours.merge(&theirs).added);
+        assert_eq!(3, ours.played());
+        assert_eq!(&[0, 1, 0, 2], ours.distribution());
+        assert_eq!(1, ours.current_streak());
+        assert_eq!(2, ours.max_streak());
+        assert_eq!(Some(Duration::from_secs(30)), ours.best_time());
+    }
+
+    #[test]
+    fn test_render_histogram() {
+        assert_eq!(
+            vec![
+                "1:                   0",
+                "2: ███               1",
+                "3: ███████           3",
+                "4: █████████████████ 8 <",
+                "5: █████████         4",
+                "6: ███               1",
+                "X: █████             2",
+            ],
+            render_histogram(&[0, 1, 3, 8, 4, 1, 2], 24, Some(3))
+        );
+
+        // before any games, there are no bars to draw
+        assert_eq!(
+            vec![
+                "1:         0",
+                "2:         0",
+                "3:         0",
+                "4:         0",
+                "5:         0",
+                "6:         0",
+                "X:         0",
+            ],
+            render_histogram(&[0; 7], 12, None)
+        );
+
+        // one huge bucket takes the whole row, and the counts make room for it
+        assert_eq!(
+            vec![
+                "1:                 0",
+                "2:                 0",
+                "3:                 0",
+                "4: █████████ 1000000",
+                "5:                 0",
+                "6:                 0",
+                "X:                 0",
+            ],
+            render_histogram(&[0, 0, 0, 1_000_000, 0, 0, 0], 20, None)
+        );
+
+        // the smallest count still gets a block, and nothing goes past the width
+        let wide = render_histogram(&[1, 2, 3, 4, 5, 6, 700], 80, Some(6));
+        assert_eq!(Some(&format!("1: █{}1", " ".repeat(73))), wide.first());
+        assert_eq!(Some(&format!("X: {} 700 <", "█".repeat(71))), wide.last());
+        assert!(wide.iter().all(|row| row.chars().count() <= 80));
+
+        // and when there's no room, the bars go before the labels and counts do
+        assert_eq!(
+            vec!["1:  5 <", "2:  0", "3:  0", "4:  0", "5:  0", "6:  0", "X:  0"],
+            render_histogram(&[5, 0, 0, 0, 0, 0, 0], 3, Some(0))
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut stats = Stats::new();
+        assert_eq!([0; 7], stats.histogram());
+        for status in [
+            GameStatus::Won { guesses: 3 },
+            GameStatus::Won { guesses: 3 },
+            GameStatus::Lost,
+            GameStatus::Won { guesses: 7 },
+        ] {
+            stats.record_status(status);
+        }
+        // a win in more than six guesses has no row of its own
+        assert_eq!([0, 0, 2, 0, 0, 0, 1], stats.histogram());
+    }
+}
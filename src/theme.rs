@@ -0,0 +1,444 @@
+//! Color themes: the colors of the tiles for each kind of feedback, and the share tiles to match
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! Besides the [built-in themes](Theme::builtin), a theme can be read from a
+//! small TOML file. Any key left out keeps the classic theme's value:
+//!
+//! ```toml
+//! # colors are "#rrggbb", or a number from the 256 color palette
+//! correct = "#f5793a"
+//! present = 117
+//! absent = 8
+//! # the dots on empty tiles; left out, they're dimmed instead
+//! empty = "#3a3a3c"
+//!
+//! # the tiles for share grids
+//! [tiles]
+//! correct = "🟧"
+//! present = "🟦"
+//! absent = "⬛"
+//! ```
+
+use std::{borrow::Cow, error::Error, fmt, fs, io, path::Path, str::FromStr};
+
+use crate::{
+    codec,
+    render::{Background, Palette},
+    share::TileSet,
+    LetterStatus,
+};
+
+/// The colors a theme file can have, besides its `[tiles]` table
+const COLOR_KEYS: [&str; 4] = ["correct", "present", "absent", "empty"];
+
+/// A color, as 24-bit RGB or an entry in the 256 color palette
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Color {
+    /// A color given by its red, green, and blue parts
+    Rgb(u8, u8, u8),
+    /// An entry in the 256 color palette; the first 16 are the terminal's
+    /// basic colors, which it may have its own shades for
+    Indexed(u8),
+}
+
+impl Color {
+    /// The red, green, and blue parts of the color.
+    ///
+    /// Colors in the palette are given xterm's default shades.
+    pub fn rgb(self) -> (u8, u8, u8) {
+        /// xterm's default shades of the basic colors
+        const BASIC: [(u8, u8, u8); 16] = [
+            (0, 0, 0),
+            (205, 0, 0),
+            (0, 205, 0),
+            (205, 205, 0),
+            (0, 0, 238),
+            (205, 0, 205),
+            (0, 205, 205),
+            (229, 229, 229),
+            (127, 127, 127),
+            (255, 0, 0),
+            (0, 255, 0),
+            (255, 255, 0),
+            (92, 92, 255),
+            (255, 0, 255),
+            (0, 255, 255),
+            (255, 255, 255),
+        ];
+        /// The levels of each part in the 6x6x6 color cube
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        match self {
+            Self::Rgb(r, g, b) => (r, g, b),
+            Self::Indexed(i @ 0..=15) => BASIC[usize::from(i)],
+            Self::Indexed(i @ 16..=231) => {
+                let i = usize::from(i - 16);
+                (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+            }
+            Self::Indexed(i) => {
+                let level = 8 + 10 * (i - 232);
+                (level, level, level)
+            }
+        }
+    }
+
+    /// The color as `#rrggbb`, e.g. for SVG or CSS
+    pub fn hex(self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Read `#rrggbb`, or a number from 0 to 255 for a palette entry
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(index) = s.parse() {
+            return Ok(Self::Indexed(index));
+        }
+        let expected = || format!("expected '#rrggbb' or a number from 0 to 255, not '{}'", s);
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(expected)?;
+        let part = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| expected());
+        Ok(Self::Rgb(part(0)?, part(2)?, part(4)?))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rgb(..) => f.write_str(&self.hex()),
+            Self::Indexed(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+/// Why a theme file couldn't be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ThemeError {
+    /// The file isn't valid TOML
+    Toml(String),
+    /// The file has a key that isn't part of a theme
+    UnknownKey(String),
+    /// A color isn't `#rrggbb` or a palette entry
+    InvalidColor {
+        /// The key the color was given for
+        key: String,
+        /// What was wrong with it
+        issue: String,
+    },
+    /// A share tile isn't a string
+    InvalidTile {
+        /// The key the tile was given for, e.g. `tiles.correct`
+        key: String,
+    },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(message) => f.write_str(message),
+            Self::UnknownKey(key) => write!(f, "unknown key '{}'", key),
+            Self::InvalidColor { key, issue } => {
+                write!(f, "invalid color for '{}': {}", key, issue)
+            }
+            Self::InvalidTile { key } => write!(f, "invalid tile for '{}': expected a string", key),
+        }
+    }
+}
+
+impl Error for ThemeError {}
+
+/// The colors of the tiles for each kind of feedback, and the share tiles to match.
+///
+/// Letters are drawn in black on correct and present tiles, and in white on
+/// absent ones.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Theme {
+    /// The color of a letter in the correct position
+    pub correct: Color,
+    /// The color of a letter in the word, but in the wrong position
+    pub present: Color,
+    /// The color of a letter not in the word
+    pub absent: Color,
+    /// The color of the dots on empty tiles, or `None` to dim them
+    pub empty: Option<Color>,
+    /// The share tile for a letter in the correct position
+    pub correct_tile: Cow<'static, str>,
+    /// The share tile for a letter in the word, but in the wrong position
+    pub present_tile: Cow<'static, str>,
+    /// The share tile for a letter not in the word
+    pub absent_tile: Cow<'static, str>,
+}
+
+/// The [classic](Theme::classic) theme, for when one is needed for the
+/// lifetime of the program
+pub static CLASSIC: Theme = Theme::classic();
+/// The [high contrast](Theme::high_contrast) theme, for when one is needed for
+/// the lifetime of the program
+pub static HIGH_CONTRAST: Theme = Theme::high_contrast();
+
+impl Theme {
+    /// The names of the built-in themes, for [`Theme::builtin`]
+    pub const BUILTIN: [&'static str; 2] = ["classic", "high-contrast"];
+
+    /// The terminal's own green and yellow, with gray, like the official game
+    pub const fn classic() -> Self {
+        Self {
+            correct: Color::Indexed(2),
+            present: Color::Indexed(3),
+            absent: Color::Indexed(8),
+            empty: None,
+            correct_tile: Cow::Borrowed(codec::GREEN_SQ),
+            present_tile: Cow::Borrowed(codec::YELLOW_SQ),
+            absent_tile: Cow::Borrowed(codec::BLACK_SQ),
+        }
+    }
+
+    /// Orange and blue instead of green and yellow, like the official game's
+    /// high contrast mode, for players who find green and yellow hard to tell apart
+    pub const fn high_contrast() -> Self {
+        Self {
+            correct: Color::Indexed(208),
+            present: Color::Indexed(117),
+            absent: Color::Indexed(8),
+            empty: None,
+            correct_tile: Cow::Borrowed("🟧"),
+            present_tile: Cow::Borrowed("🟦"),
+            absent_tile: Cow::Borrowed(codec::BLACK_SQ),
+        }
+    }
+
+    /// A built-in theme by name: `classic` or `high-contrast`
+    pub fn builtin(name: &str) -> Option<&'static Self> {
+        match name {
+            "classic" => Some(&CLASSIC),
+            "high-contrast" => Some(&HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+
+    /// Read a theme from TOML; any key left out keeps the classic theme's value
+    pub fn parse(text: &str) -> Result<Self, ThemeError> {
+        let table: toml::Table =
+            toml::from_str(text).map_err(|e| ThemeError::Toml(e.message().to_owned()))?;
+        let mut theme = Self::classic();
+        for (key, value) in &table {
+            if key == "tiles" {
+                let tiles = value.as_table().ok_or_else(|| ThemeError::InvalidTile {
+                    key: key.to_owned(),
+                })?;
+                for (key, value) in tiles {
+                    let tile = match value.as_str() {
+                        Some(tile) => Cow::Owned(tile.to_owned()),
+                        None => {
+                            return Err(ThemeError::InvalidTile {
+                                key: format!("tiles.{}", key),
+                            })
+                        }
+                    };
+                    match key.as_str() {
+                        "correct" => theme.correct_tile = tile,
+                        "present" => theme.present_tile = tile,
+                        "absent" => theme.absent_tile = tile,
+                        _ => return Err(ThemeError::UnknownKey(format!("tiles.{}", key))),
+                    }
+                }
+                continue;
+            }
+            if !COLOR_KEYS.contains(&key.as_str()) {
+                return Err(ThemeError::UnknownKey(key.to_owned()));
+            }
+
+            let invalid = |issue: String| ThemeError::InvalidColor {
+                key: key.to_owned(),
+                issue,
+            };
+            let color = match value {
+                toml::Value::String(s) => s.parse().map_err(invalid)?,
+                toml::Value::Integer(i) => u8::try_from(*i)
+                    .map(Color::Indexed)
+                    .map_err(|_| invalid(format!("{} is not from 0 to 255", i)))?,
+                _ => return Err(invalid(format!("expected a string, not {}", value))),
+            };
+            match key.as_str() {
+                "correct" => theme.correct = color,
+                "present" => theme.present = color,
+                "absent" => theme.absent = color,
+                _ => theme.empty = Some(color),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Read a theme from a file.
+    ///
+    /// A file that isn't a valid theme is an [`io::ErrorKind::InvalidData`] error.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// The color of the tile for a [`LetterStatus`]
+    pub fn color(&self, status: &LetterStatus) -> Color {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::InWord => self.present,
+            LetterStatus::NotInWord => self.absent,
+        }
+    }
+
+    /// The tiles for share grids
+    pub fn tile_set(&self) -> TileSet<'_> {
+        TileSet {
+            correct: &self.correct_tile,
+            in_word: &self.present_tile,
+            not_in_word: &self.absent_tile,
+        }
+    }
+
+    /// The tile colors for drawing boards as images.
+    ///
+    /// The built-in themes use the terminal's basic colors, which look dull as
+    /// images, so they get the official game's shades for `background` instead.
+    pub fn palette(&self, background: Background) -> Palette {
+        if self == &CLASSIC {
+            return background.palette();
+        }
+        if self == &HIGH_CONTRAST {
+            return Palette {
+                not_in_word: background.palette().not_in_word,
+                ..Palette::high_contrast()
+            };
+        }
+        Palette {
+            correct: self.correct,
+            in_word: self.present,
+            not_in_word: self.absent,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color() {
+        assert_eq!(Ok(Color::Rgb(0x6a, 0xaa, 0x64)), "#6aaa64".parse());
+        assert_eq!(Ok(Color::Rgb(255, 255, 255)), "#FFFFFF".parse());
+        assert_eq!(Ok(Color::Indexed(208)), "208".parse());
+        assert!("#6aaa6".parse::<Color>().is_err());
+        assert!("#6aaa6g".parse::<Color>().is_err());
+        assert!("256".parse::<Color>().is_err());
+        assert!("green".parse::<Color>().is_err());
+
+        assert_eq!("#6aaa64", Color::Rgb(0x6a, 0xaa, 0x64).hex());
+        assert_eq!("#7f7f7f", Color::Indexed(8).hex());
+        assert_eq!("#ff8700", Color::Indexed(208).hex());
+        assert_eq!("#87d7ff", Color::Indexed(117).hex());
+        assert_eq!("#eeeeee", Color::Indexed(255).hex());
+        assert_eq!("208", Color::Indexed(208).to_string());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Theme::classic()), Theme::parse(""));
+
+        let theme = Theme::parse(
+            r##"
+            correct = "#f5793a"
+            present = 117
+            empty = "#3a3a3c"
+
+            [tiles]
+            correct = ":orange:"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            Theme {
+                correct: Color::Rgb(0xf5, 0x79, 0x3a),
+                present: Color::Indexed(117),
+                empty: Some(Color::Rgb(0x3a, 0x3a, 0x3c)),
+                correct_tile: Cow::Borrowed(":orange:"),
+                ..Theme::classic()
+            },
+            theme
+        );
+        assert_eq!(":orange:", theme.tile_set().correct);
+        assert_eq!(codec::YELLOW_SQ, theme.tile_set().in_word);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text| Theme::parse(text).unwrap_err();
+        assert_eq!(
+            "invalid color for 'present': expected '#rrggbb' or a number from 0 to 255, not 'yellow'",
+            error("present = \"yellow\"").to_string()
+        );
+        assert_eq!(
+            "invalid color for 'absent': 300 is not from 0 to 255",
+            error("absent = 300").to_string()
+        );
+        assert_eq!(
+            "invalid color for 'empty': expected a string, not true",
+            error("empty = true").to_string()
+        );
+        assert_eq!(
+            ThemeError::UnknownKey("background".into()),
+            error("background = 0")
+        );
+        assert_eq!(
+            ThemeError::UnknownKey("tiles.empty".into()),
+            error("[tiles]\nempty = \"⬜\"")
+        );
+        assert_eq!(
+            ThemeError::InvalidTile {
+                key: "tiles.absent".into()
+            },
+            error("[tiles]\nabsent = 0")
+        );
+        assert!(matches!(error("not toml"), ThemeError::Toml(_)));
+    }
+
+    #[test]
+    fn test_builtin() {
+        assert_eq!(Some(&Theme::classic()), Theme::builtin("classic"));
+        assert_eq!(
+            Some(&Theme::high_contrast()),
+            Theme::builtin("high-contrast")
+        );
+        assert_eq!(None, Theme::builtin("neon"));
+        for name in Theme::BUILTIN {
+            assert!(Theme::builtin(name).is_some());
+        }
+
+        assert_eq!(TileSet::classic(), Theme::classic().tile_set());
+        assert_eq!(TileSet::high_contrast(), Theme::high_contrast().tile_set());
+    }
+}
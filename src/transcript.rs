@@ -0,0 +1,168 @@
+//! A record of a game, to save it and pick it up again later
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{hint::Hint, GameStatus, GuessError, LetterStatus};
+
+/// A guess made in a [`Transcript`]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub struct TranscriptGuess {
+    /// The guessed word
+    pub word: String,
+    /// The feedback for the guess
+    pub feedback: Vec<LetterStatus>,
+    /// How long the guess took, from when the word was chosen or the guess before it
+    pub time: Duration,
+}
+
+/// Everything needed to pick up a game where it was left off.
+///
+/// Get one from [`Wordle::transcript`](crate::Wordle::transcript), and continue
+/// the game with [`Wordle::resume`](crate::Wordle::resume).
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "binary-proto", derive(bincode::Encode, bincode::Decode))]
+pub struct Transcript {
+    /// The answer, or `None` if it's been hidden
+    pub answer: Option<String>,
+    /// The number of the daily puzzle, if the game was one
+    pub puzzle: Option<u32>,
+    /// The seed the answer was chosen with, if it was chosen at random
+    pub seed: Option<u64>,
+    /// The game's number in its seeded session, if its answer was chosen by number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game: Option<u32>,
+    /// Whether the game was just for practice, so it doesn't count towards stats
+    pub practice: bool,
+    /// Whether the game was played with help from the solver
+    #[serde(default)]
+    pub assist: bool,
+    /// The number of letters in the word
+    pub word_length: usize,
+    /// The number of guesses the player had to find the word
+    pub max_guesses: usize,
+    /// Whether revealed hints had to be used in subsequent guesses
+    pub hard_mode: bool,
+    /// The guesses made, in order
+    pub guesses: Vec<TranscriptGuess>,
+    /// The hints given, in order
+    pub hints: Vec<Hint>,
+    /// How the game stood
+    pub status: GameStatus,
+    /// The time spent on the game
+    pub elapsed: Duration,
+}
+
+impl Transcript {
+    /// Hide the answer, unless the game is over and it's been revealed anyway.
+    ///
+    /// A daily puzzle can still be resumed without it, since its number gives
+    /// the answer away.
+    pub fn hide_answer(mut self) -> Self {
+        if self.status == GameStatus::InProgress {
+            self.answer = None;
+        }
+        self
+    }
+}
+
+/// Why [`Wordle::resume`](crate::Wordle::resume) couldn't continue a game
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ResumeError {
+    /// The game was played with words of another length
+    WrongLength {
+        /// The number of letters in this game's words
+        expected: usize,
+        /// The number of letters in the saved game's words
+        actual: usize,
+    },
+    /// The game was played with a different number of guesses
+    WrongMaxGuesses {
+        /// The number of guesses in this game
+        expected: usize,
+        /// The number of guesses in the saved game
+        actual: usize,
+    },
+    /// The answer was hidden, and the game wasn't a daily puzzle
+    MissingAnswer,
+    /// The answer isn't in the list of acceptable guesses
+    UnknownAnswer {
+        /// The saved answer
+        word: String,
+    },
+    /// A saved guess isn't valid in this game
+    InvalidGuess {
+        /// The saved guess
+        word: String,
+        /// Why it isn't valid
+        error: GuessError,
+    },
+    /// A saved guess's feedback doesn't match the answer
+    FeedbackMismatch {
+        /// The saved guess
+        word: String,
+    },
+    /// More hints were given than this game allows
+    TooManyHints,
+    /// How the game stood doesn't match the guesses
+    StatusMismatch,
+    /// The guesses took longer, all together, than the whole game
+    TimeMismatch,
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, actual } => write!(
+                f,
+                "the saved game has {}-letter words, not {}",
+                actual, expected
+            ),
+            Self::WrongMaxGuesses { expected, actual } => write!(
+                f,
+                "the saved game allowed {} guesses, not {}",
+                actual, expected
+            ),
+            Self::MissingAnswer => write!(f, "the saved game's answer is hidden"),
+            Self::UnknownAnswer { word } => {
+                write!(f, "the answer '{}' is not in the guess list", word)
+            }
+            Self::InvalidGuess { word, error } => {
+                write!(f, "the guess '{}' is not valid: {}", word, error)
+            }
+            Self::FeedbackMismatch { word } => write!(
+                f,
+                "the feedback for the guess '{}' doesn't match the answer",
+                word
+            ),
+            Self::TooManyHints => write!(f, "the saved game used too many hints"),
+            Self::StatusMismatch => {
+                write!(f, "the saved game's result doesn't match its guesses")
+            }
+            Self::TimeMismatch => {
+                write!(f, "the saved game's guesses took longer than the game")
+            }
+        }
+    }
+}
+
+impl Error for ResumeError {}
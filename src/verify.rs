@@ -0,0 +1,456 @@
+//! Checking a game a client says it played, by playing it again
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt, time::Duration};
+
+use crate::{
+    share::{self, TileSet},
+    transcript::Transcript,
+    BuildError, GameStatus, GuessError, Wordle, MAX_GUESSES, MAX_HINTS,
+};
+
+/// A game that [`verify_transcript`] found to be genuine
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Verified {
+    /// How the game ended up, as played again
+    pub status: GameStatus,
+    /// The number of guesses made
+    pub guesses: usize,
+    /// The number of hints used
+    pub hints: usize,
+    /// Whether the game was played in hard mode
+    pub hard_mode: bool,
+    /// The time the client says the game took, which can't be checked
+    pub elapsed: Duration,
+}
+
+/// Why a transcript didn't check out
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VerifyError {
+    /// The transcript gives an answer, and it isn't the real one
+    WrongAnswer {
+        /// The answer the transcript gives
+        claimed: String,
+    },
+    /// The game was played with words of another length
+    WrongLength {
+        /// The number of letters in the real answer
+        expected: usize,
+        /// The number of letters in the transcript's words
+        actual: usize,
+    },
+    /// The game was played with another number of guesses
+    WrongMaxGuesses {
+        /// The number of guesses everyone gets
+        expected: usize,
+        /// The number of guesses the transcript says it had
+        actual: usize,
+    },
+    /// More hints were used than a game allows
+    TooManyHints,
+    /// A guess couldn't have been made: it isn't an acceptable guess, it
+    /// broke hard mode, or the game was already over
+    IllegalGuess {
+        /// The turn it was made on, starting from 1
+        turn: usize,
+        /// The guessed word
+        word: String,
+        /// Why it couldn't have been made
+        error: GuessError,
+    },
+    /// A guess's feedback isn't what the real answer gives
+    ForgedFeedback {
+        /// The turn it was made on, starting from 1
+        turn: usize,
+        /// The guessed word
+        word: String,
+    },
+    /// How the game ended doesn't match the guesses, e.g. a win is claimed
+    /// without the answer being guessed
+    StatusMismatch {
+        /// How the transcript says the game ended
+        claimed: GameStatus,
+        /// How it ended when played again
+        actual: GameStatus,
+    },
+    /// The answer or the list of guesses to check against isn't valid
+    InvalidWordList(BuildError),
+    /// A share grid has a row for each of a different number of guesses
+    GridLength {
+        /// The number of guesses in the transcript
+        expected: usize,
+        /// The number of rows in the grid
+        actual: usize,
+    },
+    /// A row of a share grid isn't the feedback its guess got
+    ForgedGrid {
+        /// The row, and the turn of its guess, starting from 1
+        row: usize,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongAnswer { claimed } => {
+                write!(f, "'{}' isn't the answer", claimed)
+            }
+            Self::WrongLength { expected, actual } => {
+                write!(f, "the game has {}-letter words, not {}", expected, actual)
+            }
+            Self::WrongMaxGuesses { expected, actual } => {
+                write!(f, "the game allows {} guesses, not {}", expected, actual)
+            }
+            Self::TooManyHints => write!(f, "more than {} hints were used", MAX_HINTS),
+            Self::IllegalGuess { turn, word, error } => {
+                write!(f, "guess {} ('{}') isn't allowed: {}", turn, word, error)
+            }
+            Self::ForgedFeedback { turn, word } => write!(
+                f,
+                "the feedback for guess {} ('{}') doesn't match the answer",
+                turn, word
+            ),
+            Self::StatusMismatch { claimed, actual } => write!(
+                f,
+                "the game is said to have ended {:?}, but the guesses say {:?}",
+                claimed, actual
+            ),
+            Self::InvalidWordList(e) => write!(f, "the word list is invalid: {}", e),
+            Self::GridLength { expected, actual } => write!(
+                f,
+                "the grid has {} rows, but {} guesses were made",
+                actual, expected
+            ),
+            Self::ForgedGrid { row } => write!(
+                f,
+                "row {} of the grid doesn't match the feedback for guess {}",
+                row, row
+            ),
+        }
+    }
+}
+
+impl Error for VerifyError {}
+
+/// Check a transcript by playing its guesses again against `answer`, with
+/// `guesses` as the acceptable guesses, and get back how the game really went.
+///
+/// Every guess must be acceptable (and follow hard mode, if the game was in
+/// it), its feedback must be what `answer` gives, and the game must have
+/// ended the way the transcript says. The transcript may leave the answer
+/// out, but if it gives one, it must be `answer`.
+pub fn verify_transcript(
+    transcript: &Transcript,
+    answer: &str,
+    guesses: &[&str],
+) -> Result<Verified, VerifyError> {
+    if let Some(claimed) = transcript
+        .answer
+        .as_deref()
+        .filter(|&claimed| claimed != answer)
+    {
+        return Err(VerifyError::WrongAnswer {
+            claimed: claimed.to_owned(),
+        });
+    }
+    let word_length = answer.chars().count();
+    if transcript.word_length != word_length {
+        return Err(VerifyError::WrongLength {
+            expected: word_length,
+            actual: transcript.word_length,
+        });
+    }
+    if transcript.max_guesses != MAX_GUESSES {
+        return Err(VerifyError::WrongMaxGuesses {
+            expected: MAX_GUESSES,
+            actual: transcript.max_guesses,
+        });
+    }
+    if transcript.hints.len() > MAX_HINTS {
+        return Err(VerifyError::TooManyHints);
+    }
+
+    let answers = [answer];
+    let mut game = Wordle::builder()
+        .guesses(guesses)
+        .answers(&answers)
+        .word_length(word_length)
+        .hard_mode(transcript.hard_mode)
+        .build()
+        .map_err(VerifyError::InvalidWordList)?
+        .set_answer(answer)
+        .map_err(|_| {
+            VerifyError::InvalidWordList(BuildError::AnswerNotInGuesses {
+                word: answer.to_owned(),
+            })
+        })?;
+    for (i, guess) in transcript.guesses.iter().enumerate() {
+        let turn = i + 1;
+        let feedback = game
+            .guess(&guess.word)
+            .map_err(|error| VerifyError::IllegalGuess {
+                turn,
+                word: guess.word.clone(),
+                error,
+            })?;
+        if feedback.feedback() != guess.feedback {
+            return Err(VerifyError::ForgedFeedback {
+                turn,
+                word: guess.word.clone(),
+            });
+        }
+    }
+
+    // a game that's given up on ends lost, with guesses to spare
+    if transcript.status == GameStatus::Lost && game.status() == GameStatus::InProgress {
+        game.give_up();
+    }
+    if game.status() != transcript.status {
+        return Err(VerifyError::StatusMismatch {
+            claimed: transcript.status,
+            actual: game.status(),
+        });
+    }
+    Ok(Verified {
+        status: game.status(),
+        guesses: game.history().len(),
+        hints: transcript.hints.len(),
+        hard_mode: transcript.hard_mode,
+        elapsed: transcript.elapsed,
+    })
+}
+
+/// Check a share grid drawn with `tiles` by verifying the transcript of the
+/// game it's from, as [`verify_transcript`] does, and then that each of its
+/// rows is the feedback the guess on that turn really got.
+///
+/// Padding after a row's tiles is ignored, as are blank lines around the grid.
+pub fn verify_share_grid(
+    grid: &str,
+    tiles: &TileSet<'_>,
+    transcript: &Transcript,
+    answer: &str,
+    guesses: &[&str],
+) -> Result<Verified, VerifyError> {
+    let verified = verify_transcript(transcript, answer, guesses)?;
+    // the transcript's feedback has just been checked against the answer
+    let history: Vec<_> = transcript
+        .guesses
+        .iter()
+        .map(|guess| (guess.word.as_str(), guess.feedback.clone()))
+        .collect();
+    let expected = share::share_grid(&history, tiles);
+    let claimed: Vec<&str> = grid.trim_matches('\n').lines().collect();
+    if claimed.len() != history.len() {
+        return Err(VerifyError::GridLength {
+            expected: history.len(),
+            actual: claimed.len(),
+        });
+    }
+    for (i, (claimed, expected)) in claimed.iter().zip(expected.lines()).enumerate() {
+        if claimed.trim_end() != expected.trim_end() {
+            return Err(VerifyError::ForgedGrid { row: i + 1 });
+        }
+    }
+    Ok(verified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LetterStatus;
+
+    const GUESSES: [&str; 5] = ["crane", "slate", "trace", "irate", "stare"];
+
+    /// A genuine transcript of a game against "crane", guessing `words`
+    fn played(words: &[&str]) -> Transcript {
+        let mut game = Wordle::new(&GUESSES, &GUESSES).set_answer("crane").unwrap();
+        for word in words {
+            game.guess(word).unwrap();
+        }
+        game.transcript()
+    }
+
+    #[test]
+    fn test_genuine() {
+        let verified = verify_transcript(&played(&["slate", "crane"]), "crane", &GUESSES).unwrap();
+        assert_eq!(GameStatus::Won { guesses: 2 }, verified.status);
+        assert_eq!(2, verified.guesses);
+        assert!(!verified.hard_mode);
+
+        // the answer can be left out
+        let hidden = Transcript {
+            answer: None,
+            ..played(&["trace", "crane"])
+        };
+        assert!(verify_transcript(&hidden, "crane", &GUESSES).is_ok());
+
+        // and a game that was given up on is a loss
+        let mut gave_up = played(&["slate"]);
+        gave_up.status = GameStatus::Lost;
+        assert_eq!(
+            Ok(GameStatus::Lost),
+            verify_transcript(&gave_up, "crane", &GUESSES).map(|verified| verified.status)
+        );
+    }
+
+    #[test]
+    fn test_forged_feedback() {
+        // claim an all-green first guess that wasn't the answer
+        let mut forged = played(&["slate"]);
+        forged.guesses[0].feedback = vec![LetterStatus::Correct; 5];
+        forged.status = GameStatus::Won { guesses: 1 };
+        assert_eq!(
+            Err(VerifyError::ForgedFeedback {
+                turn: 1,
+                word: "slate".to_owned()
+            }),
+            verify_transcript(&forged, "crane", &GUESSES)
+        );
+
+        // or claim a win without the feedback to back it up
+        let mut forged = played(&["slate", "trace"]);
+        forged.status = GameStatus::Won { guesses: 2 };
+        assert_eq!(
+            Err(VerifyError::StatusMismatch {
+                claimed: GameStatus::Won { guesses: 2 },
+                actual: GameStatus::InProgress,
+            }),
+            verify_transcript(&forged, "crane", &GUESSES)
+        );
+    }
+
+    #[test]
+    fn test_wrong_answer() {
+        let transcript = played(&["crane"]);
+        assert_eq!(
+            Err(VerifyError::WrongAnswer {
+                claimed: "crane".to_owned()
+            }),
+            verify_transcript(&transcript, "slate", &GUESSES)
+        );
+
+        // a game against another answer doesn't check out even with it hidden
+        let hidden = Transcript {
+            answer: None,
+            ..transcript
+        };
+        assert_eq!(
+            Err(VerifyError::ForgedFeedback {
+                turn: 1,
+                word: "crane".to_owned()
+            }),
+            verify_transcript(&hidden, "slate", &GUESSES)
+        );
+    }
+
+    #[test]
+    fn test_illegal_guess() {
+        let mut transcript = played(&["slate", "crane"]);
+        transcript.guesses[0].word = "zzzzz".to_owned();
+        assert_eq!(
+            Err(VerifyError::IllegalGuess {
+                turn: 1,
+                word: "zzzzz".to_owned(),
+                error: GuessError::NotInWordList
+            }),
+            verify_transcript(&transcript, "crane", &GUESSES)
+        );
+
+        // a word the client knows but the server doesn't is just as illegal
+        let transcript = played(&["stare", "crane"]);
+        assert!(matches!(
+            verify_transcript(&transcript, "crane", &GUESSES[..4]),
+            Err(VerifyError::IllegalGuess { turn: 1, .. })
+        ));
+
+        // as is a guess after the game was won
+        let mut transcript = played(&["crane"]);
+        transcript.guesses.push(transcript.guesses[0].clone());
+        transcript.status = GameStatus::Won { guesses: 2 };
+        assert!(matches!(
+            verify_transcript(&transcript, "crane", &GUESSES),
+            Err(VerifyError::IllegalGuess {
+                turn: 2,
+                error: GuessError::GameOver,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rules() {
+        let transcript = Transcript {
+            max_guesses: 10,
+            ..played(&["crane"])
+        };
+        assert_eq!(
+            Err(VerifyError::WrongMaxGuesses {
+                expected: 6,
+                actual: 10
+            }),
+            verify_transcript(&transcript, "crane", &GUESSES)
+        );
+        assert_eq!(
+            Err(VerifyError::InvalidWordList(
+                BuildError::AnswerNotInGuesses {
+                    word: "crane".to_owned()
+                }
+            )),
+            verify_transcript(&played(&["crane"]), "crane", &GUESSES[1..])
+        );
+    }
+
+    #[test]
+    fn test_share_grid() {
+        let mut game = Wordle::new(&GUESSES, &GUESSES).set_answer("crane").unwrap();
+        game.guess("slate").unwrap();
+        game.guess("crane").unwrap();
+        let game = game.finish().unwrap();
+        let transcript = game.transcript();
+        for tiles in [TileSet::classic(), TileSet::ascii()] {
+            let grid = game.share_grid(&tiles);
+            assert_eq!(
+                Ok(GameStatus::Won { guesses: 2 }),
+                verify_share_grid(&grid, &tiles, &transcript, "crane", &GUESSES)
+                    .map(|verified| verified.status)
+            );
+        }
+
+        // a grid that claims a better first guess than was made
+        let tiles = TileSet::ascii();
+        assert_eq!(
+            Err(VerifyError::ForgedGrid { row: 1 }),
+            verify_share_grid("#.#.#\n#####", &tiles, &transcript, "crane", &GUESSES).map(|_| ())
+        );
+        // or leaves a guess out
+        assert_eq!(
+            Err(VerifyError::GridLength {
+                expected: 2,
+                actual: 1
+            }),
+            verify_share_grid("#####", &tiles, &transcript, "crane", &GUESSES).map(|_| ())
+        );
+        // and the grid can't vouch for a transcript that doesn't check out
+        let mut forged = transcript.clone();
+        forged.guesses[0].feedback = vec![LetterStatus::Correct; 5];
+        assert!(matches!(
+            verify_share_grid(&game.share_grid(&tiles), &tiles, &forged, "crane", &GUESSES),
+            Err(VerifyError::ForgedFeedback { turn: 1, .. })
+        ));
+    }
+}
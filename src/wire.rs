@@ -0,0 +1,202 @@
+//! A compact binary encoding for transcripts, feedback, and race messages, for when JSON is too big
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{error::Error, fmt};
+
+use bincode::{config, Decode, Encode};
+
+/// The version of the encoding that [`encode`] writes.
+///
+/// It's the first byte of every payload, so when the encoding of a type
+/// changes, payloads written before can still be told apart and decoded.
+pub const VERSION: u8 = 1;
+
+/// Why a payload couldn't be decoded
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The payload is empty, so it doesn't even have a version
+    Empty,
+    /// The payload is from a version of the encoding that isn't known
+    UnknownVersion(u8),
+    /// The payload isn't a valid encoding of the type
+    Invalid(bincode::error::DecodeError),
+    /// There are bytes left over after the value
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the payload is empty"),
+            Self::UnknownVersion(version) => {
+                write!(f, "the payload is from unknown version {}", version)
+            }
+            Self::Invalid(e) => write!(f, "the payload is invalid: {}", e),
+            Self::TrailingBytes(count) => {
+                write!(f, "the payload has {} bytes left over", count)
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Encode a value, after a byte for the [version](VERSION) of the encoding
+pub fn encode<T: Encode>(value: &T) -> Vec<u8> {
+    let mut bytes = vec![VERSION];
+    bincode::encode_into_std_write(value, &mut bytes, config::standard())
+        .expect("Writing to a Vec can't fail");
+    bytes
+}
+
+/// Decode a value written by [`encode`], by this version or an earlier one
+pub fn decode<T: Decode<()>>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let (&version, payload) = bytes.split_first().ok_or(DecodeError::Empty)?;
+    match version {
+        // when a type's encoding changes, earlier versions are decoded here too
+        VERSION => {
+            let (value, read) = bincode::decode_from_slice(payload, config::standard())
+                .map_err(DecodeError::Invalid)?;
+            match payload.len() - read {
+                0 => Ok(value),
+                left => Err(DecodeError::TrailingBytes(left)),
+            }
+        }
+        _ => Err(DecodeError::UnknownVersion(version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{ClientMessage, Scored, ServerMessage, Standing},
+        race::PlayerStatus,
+        transcript::Transcript,
+        LetterStatus, Wordle,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::{fmt::Debug, time::Duration};
+
+    /// Check that a value survives both encodings, and print how big each is
+    fn round_trip<T>(name: &str, value: &T)
+    where
+        T: Encode + Decode<()> + Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let json = serde_json::to_vec(value).unwrap();
+        let binary = encode(value);
+        assert_eq!(value, &serde_json::from_slice::<T>(&json).unwrap());
+        assert_eq!(value, &decode::<T>(&binary).unwrap());
+        println!(
+            "{}: {} bytes as JSON, {} bytes as binary",
+            name,
+            json.len(),
+            binary.len()
+        );
+        assert!(binary.len() < json.len());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let words = ["crane", "slate", "trace"];
+        let mut game = Wordle::new(&words, &words).set_answer("crane").unwrap();
+        game.hint().unwrap();
+        for guess in ["slate", "trace", "crane"] {
+            game.guess(guess).unwrap();
+        }
+        let transcript = Transcript {
+            seed: Some(42),
+            ..game.transcript()
+        };
+        round_trip("transcript", &transcript);
+        round_trip("feedback", &game.history()[1].1);
+
+        round_trip(
+            "join",
+            &ClientMessage::Join {
+                name: "alice".to_owned(),
+                token: None,
+            },
+        );
+        round_trip(
+            "guess",
+            &ClientMessage::Guess {
+                word: "crane".to_owned(),
+            },
+        );
+        round_trip(
+            "scored",
+            &ServerMessage::Scored(Scored {
+                word: "slate".to_owned(),
+                statuses: vec![LetterStatus::NotInWord; 5],
+                turn: 1,
+            }),
+        );
+        round_trip(
+            "finished",
+            &ServerMessage::Finished {
+                winner: Some(0),
+                answer: "crane".to_owned(),
+                standings: vec![Standing {
+                    player: 0,
+                    name: "alice".to_owned(),
+                    guesses: 3,
+                    status: PlayerStatus::Solved {
+                        guesses: 3,
+                        time: Duration::from_millis(41_500),
+                    },
+                }],
+            },
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        let bytes = encode(&LetterStatus::Correct);
+        assert_eq!(VERSION, bytes[0]);
+
+        assert!(matches!(
+            decode::<LetterStatus>(&[]),
+            Err(DecodeError::Empty)
+        ));
+        let mut future = bytes.clone();
+        future[0] = VERSION + 1;
+        assert!(matches!(
+            decode::<LetterStatus>(&future),
+            Err(DecodeError::UnknownVersion(2))
+        ));
+
+        let feedback = encode(&vec![LetterStatus::InWord; 5]);
+        assert!(matches!(
+            decode::<Vec<LetterStatus>>(&feedback[..3]),
+            Err(DecodeError::Invalid(_))
+        ));
+        let mut padded = bytes;
+        padded.push(0);
+        assert!(matches!(
+            decode::<LetterStatus>(&padded),
+            Err(DecodeError::TrailingBytes(1))
+        ));
+    }
+}
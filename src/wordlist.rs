@@ -0,0 +1,1068 @@
+//! Reading and checking word lists, and preparing them from raw dictionaries
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// The byte order mark some editors put at the start of UTF-8 files
+const BOM: char = '\u{feff}';
+/// The longest line a word list can have, in bytes, unless [`Limits`] say otherwise
+pub const MAX_LINE: usize = 1024;
+
+/// A word in a word list, along with where it was found
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Entry<'a> {
+    /// The (one-based) line number of the word
+    pub line: usize,
+    /// The (one-based) column the word starts in, counting characters
+    pub column: usize,
+    /// The word, with surrounding whitespace removed
+    pub word: &'a str,
+}
+
+/// The character that starts a comment line in a word list
+const COMMENT: char = '#';
+
+/// Read the words in a word list, one per line.
+///
+/// A byte order mark at the start of the text is ignored, as are `\r\n` line
+/// endings, whitespace around each word, blank lines, and comment lines
+/// starting with `#`.
+pub fn entries(text: &str) -> Vec<Entry<'_>> {
+    text.strip_prefix(BOM)
+        .unwrap_or(text)
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| entry(i + 1, line))
+        .collect()
+}
+
+/// The word on a line of a word list (without its line ending), unless the
+/// line is blank or a comment
+fn entry(line: usize, text: &str) -> Option<Entry<'_>> {
+    let word = text.trim();
+    if word.is_empty() || word.starts_with(COMMENT) {
+        return None;
+    }
+    let indent = text.len() - text.trim_start().len();
+    Some(Entry {
+        line,
+        column: text[..indent].chars().count() + 1,
+        word,
+    })
+}
+
+/// The column of the first character in `entry`'s word that matches `pred`
+fn column_of(entry: &Entry<'_>, pred: impl Fn(char) -> bool) -> Option<usize> {
+    Some(entry.column + entry.word.chars().position(pred)?)
+}
+
+/// The words of a word list, packed one after another into one string rather
+/// than allocated one by one
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Words {
+    /// Every word, end to end
+    text: String,
+    /// Where each word ends in `text`
+    ends: Vec<usize>,
+}
+
+impl Words {
+    /// An empty list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a word to the end of the list
+    pub fn push(&mut self, word: &str) {
+        self.text.push_str(word);
+        self.ends.push(self.text.len());
+    }
+
+    /// The `i`th word
+    pub fn get(&self, i: usize) -> Option<&str> {
+        let end = *self.ends.get(i)?;
+        let start = i.checked_sub(1).map_or(0, |i| self.ends[i]);
+        Some(&self.text[start..end])
+    }
+
+    /// The number of words
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Whether there are no words
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// The words, in order
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len())
+            .map(|i| &self.text[i.checked_sub(1).map_or(0, |i| self.ends[i])..self.ends[i]])
+    }
+
+    /// The words as a list of string slices, e.g. to play with
+    pub fn to_vec(&self) -> Vec<&str> {
+        self.iter().collect()
+    }
+
+    /// Keep the words for the rest of the program, e.g. to play with for as
+    /// long as it runs
+    pub fn leak(self) -> &'static [&'static str] {
+        let words: &'static Self = Box::leak(Box::new(self));
+        words.to_vec().leak()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Words {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(words: I) -> Self {
+        let mut list = Self::new();
+        for word in words {
+            list.push(word);
+        }
+        list
+    }
+}
+
+/// How big a word list being read is allowed to get, so that a hostile one
+/// can't use up all the memory
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    /// The most words the list can have, or `None` for no limit
+    pub max_words: Option<usize>,
+    /// The longest a line can be, in bytes
+    pub max_line: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_words: None,
+            max_line: MAX_LINE,
+        }
+    }
+}
+
+/// Why a word list file couldn't be loaded
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file couldn't be read
+    Io {
+        /// The file
+        path: PathBuf,
+        /// Why it couldn't be read
+        error: io::Error,
+    },
+    /// A word in the file can't be played
+    Invalid {
+        /// The file
+        path: PathBuf,
+        /// The word, its line, and what's wrong with it
+        issue: Issue,
+    },
+    /// The built-in lists, fallen back on when a file couldn't be read, are
+    /// corrupt
+    #[cfg(feature = "embedded-wordlists")]
+    Embedded(crate::embedded::EmbeddedError),
+}
+
+impl LoadError {
+    /// The file that couldn't be loaded, or `None` if it was the built-in lists
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Io { path, .. } | Self::Invalid { path, .. } => Some(path),
+            #[cfg(feature = "embedded-wordlists")]
+            Self::Embedded(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(f, "could not read '{}': {}", path.display(), error)
+            }
+            Self::Invalid { path, issue } => write!(f, "'{}', {}", path.display(), issue),
+            #[cfg(feature = "embedded-wordlists")]
+            Self::Embedded(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Invalid { issue, .. } => Some(issue),
+            #[cfg(feature = "embedded-wordlists")]
+            Self::Embedded(e) => Some(e),
+        }
+    }
+}
+
+/// Why a word list couldn't be read by [`read`]
+#[derive(Debug)]
+pub enum ReadError {
+    /// The list couldn't be read
+    Io(io::Error),
+    /// A word in the list can't be played, or the list is too big
+    Invalid(Issue),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Invalid(issue) => write!(f, "{}", issue),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Invalid(issue) => Some(issue),
+        }
+    }
+}
+
+/// Load a word list file for playing, as read by [`read`]
+pub fn load(path: &Path) -> Result<Words, LoadError> {
+    load_limited(path, &Limits::default())
+}
+
+/// Load a word list file for playing, as read by [`read`] within `limits`
+pub fn load_limited(path: &Path, limits: &Limits) -> Result<Words, LoadError> {
+    let file = File::open(path).map_err(|error| LoadError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    read(BufReader::new(file), limits).map_err(|e| match e {
+        ReadError::Io(error) => LoadError::Io {
+            path: path.to_owned(),
+            error,
+        },
+        ReadError::Invalid(issue) => LoadError::Invalid {
+            path: path.to_owned(),
+            issue,
+        },
+    })
+}
+
+/// The lists of acceptable guesses and answers, kept for the rest of the
+/// program, as loaded by [`load_or_builtin`]
+#[derive(Debug)]
+pub struct Lists {
+    /// The acceptable guesses
+    pub guesses: &'static [&'static str],
+    /// The possible answers
+    pub answers: &'static [&'static str],
+    /// Why the files couldn't be read, if the built-in lists are used instead
+    pub fallback: Option<LoadError>,
+}
+
+/// Load the lists of acceptable guesses and answers for the rest of the
+/// program, or the built-in lists if either file can't be read.
+///
+/// A word that can't be played is an error even with the built-in lists to
+/// fall back on, so that the mistake gets noticed. Without the
+/// `embedded-wordlists` feature there's nothing to fall back on, so a file
+/// that can't be read is an error too, as are corrupt built-in lists.
+pub fn load_or_builtin(guesses: &Path, answers: &Path) -> Result<Lists, LoadError> {
+    load_or_builtin_limited(guesses, answers, &Limits::default())
+}
+
+/// Load the lists of acceptable guesses and answers like [`load_or_builtin`],
+/// with each file read within `limits`
+pub fn load_or_builtin_limited(
+    guesses: &Path,
+    answers: &Path,
+    limits: &Limits,
+) -> Result<Lists, LoadError> {
+    let lists = load_limited(guesses, limits)
+        .and_then(|guesses| Ok((guesses, load_limited(answers, limits)?)));
+    match lists {
+        Ok((guesses, answers)) => Ok(Lists {
+            guesses: guesses.leak(),
+            answers: answers.leak(),
+            fallback: None,
+        }),
+        #[cfg(feature = "embedded-wordlists")]
+        Err(e @ LoadError::Io { .. }) => {
+            let (guesses, answers) = crate::embedded::lists().map_err(LoadError::Embedded)?;
+            Ok(Lists {
+                guesses,
+                answers,
+                fallback: Some(e),
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Read a word list for playing a line at a time, the way [`entries`] does,
+/// with every word lowercased.
+///
+/// A word with anything but letters in it is an error, naming its line and
+/// column, since it could never be guessed. Whether the words have the right
+/// number of letters is left to the game.
+///
+/// Only a line is held in memory at a time, besides the words themselves, so
+/// a list within `limits` can't take up much more memory than its words.
+pub fn read<R: BufRead>(mut reader: R, limits: &Limits) -> Result<Words, ReadError> {
+    let mut words = Words::new();
+    let mut buf = Vec::new();
+    let mut line = 0;
+    loop {
+        line += 1;
+        buf.clear();
+        // room for a line of the longest length, and a `\r\n` after it
+        let read = (&mut reader)
+            .take(limits.max_line as u64 + 2)
+            .read_until(b'\n', &mut buf)
+            .map_err(ReadError::Io)?;
+        if read == 0 {
+            return Ok(words);
+        }
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+            if buf.last() == Some(&b'\r') {
+                buf.pop();
+            }
+        }
+
+        let invalid = |column, word: &str, problem| {
+            ReadError::Invalid(Issue {
+                line,
+                column,
+                word: word.to_owned(),
+                problem,
+            })
+        };
+        if buf.len() > limits.max_line {
+            let start = String::from_utf8_lossy(&buf[..limits.max_line]);
+            let problem = Problem::LineTooLong {
+                max: limits.max_line,
+            };
+            return Err(invalid(start.chars().count() + 1, "", problem));
+        }
+        let text = match std::str::from_utf8(&buf) {
+            Ok(text) => text,
+            Err(e) => {
+                let valid = std::str::from_utf8(&buf[..e.valid_up_to()]).unwrap();
+                let text = String::from_utf8_lossy(&buf);
+                return Err(invalid(
+                    valid.chars().count() + 1,
+                    text.trim(),
+                    Problem::InvalidUtf8,
+                ));
+            }
+        };
+        let text = match line {
+            1 => text.strip_prefix(BOM).unwrap_or(text),
+            _ => text,
+        };
+
+        let Some(entry) = entry(line, text) else {
+            continue;
+        };
+        if let Some(column) = column_of(&entry, |c| !c.is_alphabetic()) {
+            return Err(invalid(column, entry.word, Problem::NotAlphabetic));
+        }
+        if let Some(max) = limits.max_words.filter(|&max| words.len() == max) {
+            let problem = Problem::TooManyWords { max };
+            return Err(invalid(entry.column, entry.word, problem));
+        }
+        words.push(&entry.word.to_lowercase());
+    }
+}
+
+/// What's wrong with a word in a word list
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Problem {
+    /// The word was already in the list
+    Duplicate {
+        /// The line the word first appeared on
+        first_line: usize,
+    },
+    /// The word doesn't have the right number of letters
+    WrongLength {
+        /// The number of letters words should have
+        expected: usize,
+        /// The number of letters in the word
+        actual: usize,
+    },
+    /// The word has something other than letters in it
+    NotAlphabetic,
+    /// The word has capital letters, so it can never be guessed
+    Uppercase,
+    /// The answer isn't in the guess list, so it can never be guessed
+    NotInGuesses,
+    /// The line isn't valid UTF-8
+    InvalidUtf8,
+    /// The line is longer than the [`Limits`] allow
+    LineTooLong {
+        /// The longest a line can be, in bytes
+        max: usize,
+    },
+    /// The list has more words than the [`Limits`] allow
+    TooManyWords {
+        /// The most words the list can have
+        max: usize,
+    },
+}
+
+/// A problem with a word in a word list
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Issue {
+    /// The (one-based) line number of the word
+    pub line: usize,
+    /// The (one-based) column of the problem, counting characters: where the
+    /// word starts, or the first character that's wrong with it
+    pub column: usize,
+    /// The word
+    pub word: String,
+    /// What's wrong with it
+    pub problem: Problem,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match self.problem {
+            Problem::LineTooLong { max } => {
+                return write!(f, "the line is longer than {} bytes", max)
+            }
+            Problem::TooManyWords { max } => {
+                return write!(f, "the list has more than {} words", max)
+            }
+            _ => write!(f, "'{}' ", self.word)?,
+        }
+        match self.problem {
+            Problem::Duplicate { first_line } => {
+                write!(f, "is a duplicate of line {}", first_line)
+            }
+            Problem::WrongLength { expected, actual } => {
+                write!(f, "has {} letters, not {}", actual, expected)
+            }
+            Problem::NotAlphabetic => write!(f, "has characters that aren't letters"),
+            Problem::Uppercase => write!(f, "has capital letters"),
+            Problem::NotInGuesses => write!(f, "is not in the guess list"),
+            Problem::InvalidUtf8 => write!(f, "isn't valid UTF-8"),
+            Problem::LineTooLong { .. } | Problem::TooManyWords { .. } => unreachable!(),
+        }
+    }
+}
+
+impl Error for Issue {}
+
+/// Find the problems with the words in a list, in line order.
+///
+/// Duplicates, characters that aren't letters, and capital letters are always
+/// problems; the number of letters is only checked if `length` is given.
+pub fn check(entries: &[Entry<'_>], length: Option<usize>) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        let issue = |problem, column| Issue {
+            line: entry.line,
+            column,
+            word: entry.word.to_owned(),
+            problem,
+        };
+
+        if let Some(&first_line) = seen.get(entry.word) {
+            issues.push(issue(Problem::Duplicate { first_line }, entry.column));
+            continue;
+        }
+        seen.insert(entry.word, entry.line);
+
+        let actual = entry.word.chars().count();
+        match length {
+            Some(expected) if actual != expected => issues.push(issue(
+                Problem::WrongLength { expected, actual },
+                entry.column,
+            )),
+            _ => {}
+        }
+        if let Some(column) = column_of(entry, |c| !c.is_alphabetic()) {
+            issues.push(issue(Problem::NotAlphabetic, column));
+        } else if let Some(column) = column_of(entry, char::is_uppercase) {
+            issues.push(issue(Problem::Uppercase, column));
+        }
+    }
+    issues
+}
+
+/// Find the answers that aren't in the guess list, in line order
+pub fn check_subset(answers: &[Entry<'_>], guesses: &[Entry<'_>]) -> Vec<Issue> {
+    let guesses: HashSet<&str> = guesses.iter().map(|entry| entry.word).collect();
+    answers
+        .iter()
+        .filter(|entry| !guesses.contains(entry.word))
+        .map(|entry| Issue {
+            line: entry.line,
+            column: entry.column,
+            word: entry.word.to_owned(),
+            problem: Problem::NotInGuesses,
+        })
+        .collect()
+}
+
+/// How to order a normalized word list
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Order {
+    /// Alphabetical order
+    Sorted,
+    /// The order the words first appear in, e.g. for an answer list in daily puzzle order
+    Original,
+}
+
+/// Lowercase the words in a list and remove any duplicates
+pub fn normalize(entries: &[Entry<'_>], order: Order) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut words: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.word.to_lowercase())
+        .filter(|word| seen.insert(word.clone()))
+        .collect();
+    if order == Order::Sorted {
+        words.sort_unstable();
+    }
+    words
+}
+
+/// An error reading a frequency list
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseFrequencyError {
+    /// The (one-based) line number of the line that couldn't be read
+    pub line: usize,
+}
+
+impl fmt::Display for ParseFrequencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected a word and how often it's used, e.g. 'crane 1234'",
+            self.line
+        )
+    }
+}
+
+impl Error for ParseFrequencyError {}
+
+/// Read a frequency list: a word and how often it's used on each line,
+/// separated by whitespace.
+///
+/// Words are lowercased, and the frequencies of words that appear more than
+/// once are added up. Blank lines are ignored, as in [`entries`].
+pub fn frequencies(text: &str) -> Result<HashMap<String, f64>, ParseFrequencyError> {
+    let mut frequencies = HashMap::new();
+    for entry in entries(text) {
+        let mut fields = entry.word.split_whitespace();
+        let (Some(word), Some(frequency), None) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(ParseFrequencyError { line: entry.line });
+        };
+        let frequency: f64 = frequency
+            .parse()
+            .ok()
+            .filter(|frequency: &f64| frequency.is_finite())
+            .ok_or(ParseFrequencyError { line: entry.line })?;
+        *frequencies.entry(word.to_lowercase()).or_insert(0.0) += frequency;
+    }
+    Ok(frequencies)
+}
+
+/// Cut a raw list down to the playable words: those with `length` letters and
+/// nothing else, and (if `frequencies` are given) used at least `min_frequency` times.
+///
+/// The result is normalized and sorted, as by [`normalize`].
+pub fn filter(
+    entries: &[Entry<'_>],
+    length: usize,
+    frequencies: Option<&HashMap<String, f64>>,
+    min_frequency: f64,
+) -> Vec<String> {
+    let mut words = normalize(entries, Order::Sorted);
+    words.retain(|word| {
+        word.chars().count() == length
+            && word.chars().all(char::is_alphabetic)
+            && frequencies.is_none_or(|frequencies| {
+                frequencies
+                    .get(word)
+                    .is_some_and(|&frequency| frequency >= min_frequency)
+            })
+    });
+    words
+}
+
+/// What's wrong with a word list written as an array
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArrayProblem {
+    /// There's no `[` starting the array
+    MissingArray,
+    /// Something other than a quoted word was found where one should be
+    ExpectedWord,
+    /// A quoted word isn't closed before the end of the text
+    UnterminatedWord,
+    /// Something other than a `,` or `]` was found after a word
+    ExpectedComma,
+    /// There's something other than a `;` after the array
+    TrailingText,
+    /// A word in the array can't be played
+    Invalid {
+        /// The word
+        word: String,
+        /// What's wrong with it
+        problem: Problem,
+    },
+}
+
+/// An error reading a word list written as an array
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseArrayError {
+    /// The (zero-based) byte offset in the text where the error was found
+    pub offset: usize,
+    /// What's wrong
+    pub problem: ArrayProblem,
+}
+
+impl fmt::Display for ParseArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte {}: ", self.offset)?;
+        match &self.problem {
+            ArrayProblem::MissingArray => {
+                write!(f, "expected an array, e.g. [\"cigar\", \"rebut\"]")
+            }
+            ArrayProblem::ExpectedWord => write!(f, "expected a quoted word or ']'"),
+            ArrayProblem::UnterminatedWord => write!(f, "the quoted word is never closed"),
+            ArrayProblem::ExpectedComma => write!(f, "expected ',' or ']' after the word"),
+            ArrayProblem::TrailingText => write!(f, "unexpected text after the array"),
+            ArrayProblem::Invalid { word, problem } => {
+                write!(f, "'{}' ", word)?;
+                match problem {
+                    Problem::WrongLength { expected, actual } => {
+                        write!(f, "has {} letters, not {}", actual, expected)
+                    }
+                    _ => write!(f, "has characters that aren't letters"),
+                }
+            }
+        }
+    }
+}
+
+impl Error for ParseArrayError {}
+
+/// Read a word list written as a JavaScript or JSON array of strings, the way
+/// the official lists are usually passed around, e.g. `["cigar","rebut"]`.
+///
+/// Anything before the first `[` is skipped, so the array can be assigned to a
+/// variable, and it can be followed by a `;`. Words can be in single or double
+/// quotes, with any whitespace and a trailing comma. Words are lowercased, and a
+/// word with anything but letters in it (or without `length` letters, if given)
+/// is an error.
+pub fn parse_array(text: &str, length: Option<usize>) -> Result<Vec<String>, ParseArrayError> {
+    let error = |offset, problem| ParseArrayError { offset, problem };
+    let start = text
+        .find('[')
+        .ok_or_else(|| error(text.len(), ArrayProblem::MissingArray))?;
+    let mut chars = text[start + 1..]
+        .char_indices()
+        .map(|(i, c)| (start + 1 + i, c))
+        .peekable();
+    let skip_whitespace = |chars: &mut std::iter::Peekable<_>| {
+        while chars
+            .next_if(|&(_, c): &(usize, char)| c.is_whitespace())
+            .is_some()
+        {}
+    };
+
+    let mut words = Vec::new();
+    loop {
+        // the array can end here if it's empty, or after a trailing comma
+        skip_whitespace(&mut chars);
+        let (offset, quote) = match chars.next() {
+            Some((_, ']')) => break,
+            Some((offset, quote @ ('"' | '\''))) => (offset, quote),
+            Some((offset, _)) => return Err(error(offset, ArrayProblem::ExpectedWord)),
+            None => return Err(error(text.len(), ArrayProblem::ExpectedWord)),
+        };
+        let mut word = String::new();
+        loop {
+            match chars.next() {
+                Some((_, c)) if c == quote => break,
+                // a backslash escapes the character after it, e.g. a quote
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c)) => word.push(c),
+                    None => return Err(error(offset, ArrayProblem::UnterminatedWord)),
+                },
+                Some((_, c)) => word.push(c),
+                None => return Err(error(offset, ArrayProblem::UnterminatedWord)),
+            }
+        }
+
+        let actual = word.chars().count();
+        let problem = match length {
+            _ if !word.chars().all(char::is_alphabetic) => Some(Problem::NotAlphabetic),
+            Some(expected) if actual != expected => Some(Problem::WrongLength { expected, actual }),
+            _ => None,
+        };
+        if let Some(problem) = problem {
+            return Err(error(offset, ArrayProblem::Invalid { word, problem }));
+        }
+        words.push(word.to_lowercase());
+
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some((_, ',')) => {}
+            Some((_, ']')) => break,
+            Some((offset, _)) => return Err(error(offset, ArrayProblem::ExpectedComma)),
+            None => return Err(error(text.len(), ArrayProblem::ExpectedComma)),
+        }
+    }
+
+    skip_whitespace(&mut chars);
+    chars.next_if(|&(_, c)| c == ';');
+    skip_whitespace(&mut chars);
+    match chars.next() {
+        Some((offset, _)) => Err(error(offset, ArrayProblem::TrailingText)),
+        None => Ok(words),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_entries() {
+        let words: Vec<(usize, usize, &str)> = entries("\u{feff}crane\r\n\r\n  slate \nirate")
+            .into_iter()
+            .map(|entry| (entry.line, entry.column, entry.word))
+            .collect();
+        assert_eq!(
+            vec![(1, 1, "crane"), (3, 3, "slate"), (4, 1, "irate")],
+            words
+        );
+        assert!(entries("").is_empty());
+
+        let words: Vec<(usize, &str)> =
+            entries("# answers\ncrane\n  # not slate\n\nirate # late\n")
+                .into_iter()
+                .map(|entry| (entry.line, entry.word))
+                .collect();
+        assert_eq!(vec![(2, "crane"), (5, "irate # late")], words);
+    }
+
+    #[test]
+    fn test_load() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/lists");
+        for name in [
+            "crlf.txt",
+            "bom.txt",
+            "comments.txt",
+            "blank-lines.txt",
+            "mixed-case.txt",
+        ] {
+            assert_eq!(
+                vec!["crane", "slate", "irate"],
+                load(&fixtures.join(name)).unwrap().to_vec(),
+                "{}",
+                name
+            );
+        }
+
+        let path = fixtures.join("invalid.txt");
+        let e = load(&path).unwrap_err();
+        assert_eq!(Some(path.as_path()), e.path());
+        assert_eq!(
+            format!(
+                "'{}', line 3, column 3: 'cr4ne' has characters that aren't letters",
+                path.display()
+            ),
+            e.to_string()
+        );
+
+        let path = fixtures.join("missing.txt");
+        let e = load(&path).unwrap_err();
+        assert!(
+            matches!(&e, LoadError::Io { error, .. } if error.kind() == io::ErrorKind::NotFound)
+        );
+        assert_eq!(Some(path.as_path()), e.path());
+    }
+
+    #[test]
+    fn test_load_or_builtin() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/lists");
+        let list = fixtures.join("bom.txt");
+        let lists = load_or_builtin(&list, &list).unwrap();
+        assert_eq!(["crane", "slate", "irate"], lists.guesses);
+        assert_eq!(["crane", "slate", "irate"], lists.answers);
+        assert!(lists.fallback.is_none());
+
+        // a bad word is reported rather than falling back
+        let invalid = fixtures.join("invalid.txt");
+        let e = load_or_builtin(&list, &invalid).unwrap_err();
+        assert!(matches!(&e, LoadError::Invalid { .. }));
+        assert_eq!(Some(invalid.as_path()), e.path());
+
+        let limits = Limits {
+            max_words: Some(2),
+            ..Limits::default()
+        };
+        let e = load_or_builtin_limited(&list, &list, &limits).unwrap_err();
+        assert!(matches!(&e, LoadError::Invalid { .. }));
+
+        let missing = fixtures.join("missing.txt");
+        #[cfg(feature = "embedded-wordlists")]
+        {
+            let lists = load_or_builtin(&list, &missing).unwrap();
+            assert_eq!(crate::embedded::guesses(), lists.guesses);
+            assert_eq!(crate::embedded::answers(), lists.answers);
+            assert_eq!(
+                Some(missing.as_path()),
+                lists.fallback.as_ref().and_then(LoadError::path)
+            );
+        }
+        #[cfg(not(feature = "embedded-wordlists"))]
+        {
+            let e = load_or_builtin(&list, &missing).unwrap_err();
+            assert!(matches!(&e, LoadError::Io { .. }));
+            assert_eq!(Some(missing.as_path()), e.path());
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let read = |text: &str, limits: &Limits| read(text.as_bytes(), limits);
+        let words = read(
+            "\u{feff}Crane\r\n# slate\n\n  irate \ntrace",
+            &Limits::default(),
+        );
+        assert_eq!(vec!["crane", "irate", "trace"], words.unwrap().to_vec());
+        assert!(read("", &Limits::default()).unwrap().is_empty());
+
+        let issue = |text: &str, limits: &Limits| match read(text, limits) {
+            Err(ReadError::Invalid(issue)) => (issue.line, issue.column, issue.problem),
+            other => panic!("{:?}", other),
+        };
+        let limits = Limits::default();
+        assert_eq!(
+            (3, 4, Problem::NotAlphabetic),
+            issue("crane\nslate\n  x-ray\n", &limits)
+        );
+        assert_eq!(
+            (1, 2, Problem::NotAlphabetic),
+            issue("\u{feff}ñ-ndú", &limits),
+            "the byte order mark isn't a column, but ñ is just one"
+        );
+
+        let limits = Limits {
+            max_words: Some(2),
+            max_line: 8,
+        };
+        assert_eq!(2, read("crane\nslate\n# irate\n", &limits).unwrap().len());
+        assert_eq!(
+            (4, 2, Problem::TooManyWords { max: 2 }),
+            issue("crane\nslate\n\n irate\n", &limits)
+        );
+        // the line ending doesn't count towards the line's length
+        assert!(read("  crane \r\n", &limits).is_ok());
+        assert_eq!(
+            (2, 9, Problem::LineTooLong { max: 8 }),
+            issue("crane\n  cranes  \n", &limits)
+        );
+        assert_eq!(
+            (1, 9, Problem::LineTooLong { max: 8 }),
+            issue(&"#".repeat(100_000), &limits)
+        );
+
+        match super::read(&b"crane\nsl\xffte\n"[..], &Limits::default()) {
+            Err(ReadError::Invalid(issue)) => {
+                assert_eq!(
+                    (2, 3, Problem::InvalidUtf8),
+                    (issue.line, issue.column, issue.problem)
+                );
+                assert_eq!(
+                    "line 2, column 3: 'sl\u{fffd}te' isn't valid UTF-8",
+                    issue.to_string()
+                );
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_words() {
+        let mut words: Words = ["crane", "ñandú"].into_iter().collect();
+        words.push("slate");
+        assert_eq!(3, words.len());
+        assert_eq!(Some("ñandú"), words.get(1));
+        assert_eq!(None, words.get(3));
+        assert_eq!(vec!["crane", "ñandú", "slate"], words.to_vec());
+        assert!(Words::new().is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        let list = entries("crane\nslate\ncrane\ncranes\ncr4ne\nSlate\n");
+        assert_eq!(
+            vec![
+                (3, Problem::Duplicate { first_line: 1 }),
+                (
+                    4,
+                    Problem::WrongLength {
+                        expected: 5,
+                        actual: 6
+                    }
+                ),
+                (5, Problem::NotAlphabetic),
+                (6, Problem::Uppercase),
+            ],
+            check(&list, Some(5))
+                .into_iter()
+                .map(|issue| (issue.line, issue.problem))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, check(&list, None).len());
+        assert_eq!(
+            "line 4, column 1: 'cranes' has 6 letters, not 5",
+            check(&list, Some(5))[1].to_string()
+        );
+        assert_eq!(
+            "line 5, column 3: 'cr4ne' has characters that aren't letters",
+            check(&list, Some(5))[2].to_string()
+        );
+
+        let guesses = entries("crane\nslate\n");
+        let answers = entries("slate\nirate\n");
+        assert_eq!(
+            vec![Issue {
+                line: 2,
+                column: 1,
+                word: "irate".into(),
+                problem: Problem::NotInGuesses
+            }],
+            check_subset(&answers, &guesses)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let list = entries("\u{feff}Slate\r\ncrane\nslate\nIRATE\n");
+        assert_eq!(
+            vec!["crane", "irate", "slate"],
+            normalize(&list, Order::Sorted)
+        );
+        assert_eq!(
+            vec!["slate", "crane", "irate"],
+            normalize(&list, Order::Original)
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let list = entries("crane\nslate\ncranes\nirate\nx-ray\nSLATE\n");
+        assert_eq!(vec!["crane", "irate", "slate"], filter(&list, 5, None, 0.0));
+
+        let frequencies = frequencies("crane 100\nslate 20\nSlate\t15\ncranes 500\n").unwrap();
+        assert_eq!(Some(&35.0), frequencies.get("slate"));
+        assert_eq!(
+            vec!["crane", "slate"],
+            filter(&list, 5, Some(&frequencies), 30.0)
+        );
+        assert_eq!(vec!["crane"], filter(&list, 5, Some(&frequencies), 50.0));
+
+        assert_eq!(
+            Err(ParseFrequencyError { line: 2 }),
+            super::frequencies("crane 100\nslate lots\n")
+        );
+        assert_eq!(
+            Err(ParseFrequencyError { line: 1 }),
+            super::frequencies("crane\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_array() {
+        assert_eq!(
+            vec!["cigar", "rebut", "sissy"],
+            parse_array("[\"cigar\",\"rebut\",\"sissy\"]", Some(5)).unwrap()
+        );
+        assert_eq!(
+            vec!["cigar", "rebut"],
+            parse_array("const answers = [\n  'cigar',\n  \"REBUT\",\n];\n", None).unwrap()
+        );
+        assert!(parse_array(" [ ] ", None).unwrap().is_empty());
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/lists/nyt.js");
+        let words = parse_array(&fs::read_to_string(path).unwrap(), Some(5)).unwrap();
+        assert_eq!(34, words.len());
+        assert_eq!(Some(&"cigar".to_owned()), words.first());
+        assert_eq!(Some(&"croak".to_owned()), words.last());
+
+        let problem = |text: &str| {
+            let e = parse_array(text, Some(5)).unwrap_err();
+            (e.offset, e.problem)
+        };
+        assert_eq!((6, ArrayProblem::MissingArray), problem("cigar\n"));
+        assert_eq!((9, ArrayProblem::ExpectedWord), problem("[\"cigar\",,]"));
+        assert_eq!((9, ArrayProblem::ExpectedWord), problem("[\"cigar\","));
+        assert_eq!(
+            (9, ArrayProblem::UnterminatedWord),
+            problem("[\"cigar\",'rebut]")
+        );
+        assert_eq!(
+            (9, ArrayProblem::ExpectedComma),
+            problem("[\"cigar\" \"rebut\"]")
+        );
+        assert_eq!(
+            (11, ArrayProblem::TrailingText),
+            problem("[\"cigar\"]; [\"rebut\"]")
+        );
+        assert_eq!(
+            (
+                1,
+                ArrayProblem::Invalid {
+                    word: "cigars".into(),
+                    problem: Problem::WrongLength {
+                        expected: 5,
+                        actual: 6
+                    }
+                }
+            ),
+            problem("[\"cigars\"]")
+        );
+        assert_eq!(
+            "byte 9: 'x-ray' has characters that aren't letters",
+            parse_array("[\"cigar\",\"x-ray\"]", None)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
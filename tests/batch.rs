@@ -0,0 +1,231 @@
+//! End-to-end tests of the `wordle` binary, driven through batch mode
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use assert_cmd::Command;
+use predicates::str::contains;
+
+/// The `wordle` binary in batch mode, using the test word lists
+fn wordle(args: &[&str]) -> Command {
+    wordle_with("guesses.txt", "answers.txt", args)
+}
+
+/// The `wordle` binary in batch mode, using the named lists in `tests/data`
+fn wordle_with(guesses: &str, answers: &str, args: &[&str]) -> Command {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let mut cmd = Command::cargo_bin("wordle").unwrap();
+    cmd.arg("--batch")
+        .arg("--guesses")
+        .arg(format!("{}/{}", data, guesses))
+        .arg("--answers")
+        .arg(format!("{}/{}", data, answers))
+        .args(args);
+    cmd
+}
+
+#[test]
+fn test_win() {
+    wordle(&["--answer", "crane"])
+        .write_stdin("trace\nslate\ncrane\n")
+        .assert()
+        .code(0)
+        .stdout("trace -ggyg playing 1\nslate --g-g playing 2\ncrane ggggg win 3\n")
+        .stderr("");
+}
+
+#[test]
+fn test_theme() {
+    let themes = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/themes");
+    for theme in ["high-contrast", &format!("{}/solarized.toml", themes)] {
+        wordle(&["--answer", "crane", "--theme", theme])
+            .write_stdin("crane\n")
+            .assert()
+            .code(0)
+            .stdout("crane ggggg win 1\n");
+    }
+
+    // a bad theme is caught before the game starts, naming the bad color
+    wordle(&[
+        "--answer",
+        "crane",
+        "--theme",
+        &format!("{}/invalid.toml", themes),
+    ])
+    .write_stdin("crane\n")
+    .assert()
+    .code(1)
+    .stdout("")
+    .stderr(contains("invalid color for 'present'"));
+    wordle(&["--answer", "crane", "--theme", "neon"])
+        .assert()
+        .code(1)
+        .stderr(contains("The built-in themes are: classic, high-contrast"));
+}
+
+#[test]
+fn test_loss() {
+    wordle(&["--answer", "crane"])
+        .write_stdin("trace\nslate\nstare\nsnare\nshare\nirate\n")
+        .assert()
+        .code(1)
+        .stdout(contains("irate -gg-g loss 6\n"));
+}
+
+#[test]
+fn test_invalid_guesses() {
+    wordle(&["--answer", "crane"])
+        .write_stdin("cranes\nzzzzz\ncrane\n")
+        .assert()
+        .code(0)
+        .stdout("crane ggggg win 1\n")
+        .stderr(contains("'zzzzz' is not valid: not in word list"));
+
+    wordle(&["--answer", "crane", "--strict"])
+        .write_stdin("zzzzz\ncrane\n")
+        .assert()
+        .code(0)
+        .stdout("zzzzz invalid playing 1\ncrane ggggg win 2\n");
+}
+
+#[test]
+fn test_rejection_messages() {
+    let rejected = |args: &[&str], input: &str, message: &str| {
+        wordle(args)
+            .write_stdin(input)
+            .assert()
+            .code(0)
+            .stderr(format!("{}\n", message));
+    };
+    let answer = ["--answer", "crane"];
+
+    rejected(
+        &answer,
+        "zzzzz\ncrane\n",
+        "'zzzzz' is not valid: not in word list",
+    );
+    rejected(
+        &answer,
+        "cran\ncrane\n",
+        "'cran' is not valid: too short — need 5 letters, got 4",
+    );
+    rejected(
+        &answer,
+        "cranes\ncrane\n",
+        "'cranes' is not valid: too long — need 5 letters, got 6",
+    );
+    rejected(
+        &answer,
+        "cr4ne\ncrane\n",
+        "'cr4ne' is not valid: '4' isn't a letter",
+    );
+    rejected(
+        &answer,
+        "trace\nslate\nSLATE\ncrane\n",
+        "'slate' is not valid: you already tried SLATE on guess 2",
+    );
+    rejected(
+        &["--answer", "crane", "--hard"],
+        "trace\nslate\ncrane\n",
+        "'slate' is not valid: 2nd letter must be R",
+    );
+}
+
+#[test]
+fn test_repeated_guesses() {
+    // a repeat is rejected without using up a turn, whatever its case
+    wordle(&["--answer", "crane", "--hard"])
+        .write_stdin("trace\nTRACE\ncrane\n")
+        .assert()
+        .code(0)
+        .stdout("trace -ggyg playing 1\ncrane ggggg win 2\n")
+        .stderr("'trace' is not valid: you already tried TRACE on guess 1\n");
+
+    // unless repeats are allowed, like the official game
+    wordle(&["--answer", "crane", "--strict-repeats"])
+        .write_stdin("slate\nslate\ncrane\n")
+        .assert()
+        .code(0)
+        .stdout("slate --g-g playing 1\nslate --g-g playing 2\ncrane ggggg win 3\n")
+        .stderr("");
+}
+
+#[test]
+fn test_input_errors() {
+    // the input ends before the game does
+    wordle(&["--answer", "crane"])
+        .write_stdin("trace\n")
+        .assert()
+        .code(2)
+        .stderr(contains("the input ended before the game was over"));
+
+    wordle(&["--answer", "zzzzz"])
+        .write_stdin("crane\n")
+        .assert()
+        .code(2)
+        .stderr(contains("the answer can't be played: 'zzzzz' is not valid"));
+}
+
+#[test]
+fn test_random_answer() {
+    // every answer in the test list is one of these
+    wordle(&[])
+        .write_stdin("crane\nslate\nirate\n")
+        .assert()
+        .code(0)
+        .stdout(contains(" win "));
+}
+
+#[test]
+fn test_length() {
+    wordle_with(
+        "guesses6.txt",
+        "answers6.txt",
+        &["--length", "6", "--answer", "planet"],
+    )
+    .write_stdin("crane\nbright\nplates\nplanet\n")
+    .assert()
+    .code(0)
+    .stdout("bright -----g playing 1\nplates gggyg- playing 2\nplanet gggggg win 3\n")
+    .stderr(contains(
+        "'crane' is not valid: too short — need 6 letters, got 5",
+    ));
+
+    // the 5 letter lists don't match
+    wordle(&["--length", "6", "--answer", "planet"])
+        .write_stdin("planet\n")
+        .assert()
+        .code(1)
+        .stderr(contains(
+            "the word lists don't match --length 6: 'crane' has 5 letters, expected 6",
+        ));
+}
+
+#[test]
+fn test_any_answer() {
+    wordle(&["--answer", "xylyl"])
+        .write_stdin("xylyl\n")
+        .assert()
+        .code(2)
+        .stderr(contains(
+            "the answer can't be played: 'xylyl' is not valid: not in word list",
+        ));
+
+    wordle(&["--answer", "XYLYL", "--allow-any-answer"])
+        .write_stdin("crane\nxylyl\n")
+        .assert()
+        .code(0)
+        .stdout("crane ----- playing 1\nxylyl ggggg win 2\n");
+}
@@ -0,0 +1,51 @@
+//! Running the game in the command line tests, away from the user's own files
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// each test file uses only some of these
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use assert_cmd::Command;
+
+/// An empty directory of its own for a test, to keep stats and settings in
+pub fn dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run the game with the test word lists ending in `lists` (e.g. `6` for
+/// `guesses6.txt`), keeping its stats, settings and saves in `dir` rather
+/// than the user's own
+pub fn wordle(dir: &Path, lists: &str) -> Command {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let mut command = Command::cargo_bin("wordle").unwrap();
+    command
+        .env("HOME", dir)
+        .env("XDG_DATA_HOME", dir)
+        .env("XDG_CONFIG_HOME", dir)
+        .arg("--guesses")
+        .arg(format!("{}/guesses{}.txt", data, lists))
+        .arg("--answers")
+        .arg(format!("{}/answers{}.txt", data, lists));
+    command
+}
@@ -0,0 +1,6 @@
+// Scraped from the official site's main.js; the answers are in daily puzzle order
+var Ma = ["cigar","rebut","sissy","humph","awake","blush","focal","evade","naval","serve",
+  "heath","dwarf","model","karma","stink","grade","quiet","bench","abate","feign",
+  'major', 'death',   "fresh",
+	"crust","stool","colon","abase","marry","react","batty","pride","floss","helix","croak",
+];
@@ -0,0 +1,258 @@
+//! End-to-end tests of the `wordle-dict` binary
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::PathBuf};
+
+use assert_cmd::Command;
+use predicates::str::contains;
+
+/// Write a file for a test in a directory of its own, and get back its path
+fn fixture(test: &str, name: &str, contents: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("dict-{}", test));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// The `wordle-dict` binary
+fn wordle_dict() -> Command {
+    Command::cargo_bin("wordle-dict").unwrap()
+}
+
+#[test]
+fn test_check() {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    wordle_dict()
+        .arg("check")
+        .arg("--guesses")
+        .arg(format!("{}/guesses.txt", data))
+        .arg("--answers")
+        .arg(format!("{}/answers.txt", data))
+        .assert()
+        .code(0)
+        .stdout("7 guesses and 3 answers, no problems found\n");
+}
+
+#[test]
+fn test_check_problems() {
+    let guesses = fixture(
+        "check",
+        "guesses.txt",
+        "crane\nslate\ncrane\nx-ray\nirate\n",
+    );
+    let answers = fixture("check", "answers.txt", "slate\ntrace\ncranes\n");
+    wordle_dict()
+        .arg("check")
+        .arg("--guesses")
+        .arg(&guesses)
+        .arg("--answers")
+        .arg(&answers)
+        .assert()
+        .code(1)
+        .stdout(contains(format!(
+            "{}: line 3, column 1: 'crane' is a duplicate of line 1\n\
+             {}: line 4, column 2: 'x-ray' has characters that aren't letters\n\
+             {}: line 3, column 1: 'cranes' has 6 letters, not 5\n\
+             {}: line 2, column 1: 'trace' is not in the guess list\n\
+             {}: line 3, column 1: 'cranes' is not in the guess list\n\
+             5 guesses and 3 answers, 5 problems found\n",
+            guesses.display(),
+            guesses.display(),
+            answers.display(),
+            answers.display(),
+            answers.display(),
+        )));
+}
+
+#[test]
+fn test_normalize() {
+    let input = fixture(
+        "normalize",
+        "raw.txt",
+        "\u{feff}Slate\r\ncrane\r\n\r\nSLATE\r\n",
+    );
+    wordle_dict()
+        .arg("normalize")
+        .arg(&input)
+        .assert()
+        .code(0)
+        .stdout("crane\nslate\n");
+
+    let output = input.with_file_name("normalized.txt");
+    wordle_dict()
+        .arg("normalize")
+        .arg(&input)
+        .arg("--keep-order")
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(0)
+        .stderr(contains("Wrote 2 words"));
+    assert_eq!("slate\ncrane\n", fs::read_to_string(&output).unwrap());
+}
+
+#[test]
+fn test_filter() {
+    let input = fixture("filter", "raw.txt", "crane\ncranes\nslate\nirate\nx-ray\n");
+    let frequencies = fixture("filter", "frequencies.txt", "crane 90\nslate 40\nirate 5\n");
+    wordle_dict()
+        .arg("filter")
+        .arg(&input)
+        .assert()
+        .code(0)
+        .stdout("crane\nirate\nslate\n");
+    wordle_dict()
+        .arg("filter")
+        .arg(&input)
+        .arg("--frequencies")
+        .arg(&frequencies)
+        .arg("--min-frequency")
+        .arg("10")
+        .assert()
+        .code(0)
+        .stdout("crane\nslate\n");
+}
+
+#[test]
+fn test_import_js() {
+    let input = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/lists/nyt.js");
+    let output = fixture("import-js", "answers.txt", "");
+    wordle_dict()
+        .arg("import-js")
+        .arg(input)
+        .arg("--keep-order")
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(0)
+        .stderr(contains("Wrote 34 words"));
+    let answers = fs::read_to_string(&output).unwrap();
+    assert!(answers.starts_with("cigar\nrebut\nsissy\n"));
+    assert!(answers.ends_with("helix\ncroak\n"));
+
+    let input = fixture(
+        "import-js",
+        "guesses.json",
+        r#"["rebut", "Cigar", "rebut"]"#,
+    );
+    wordle_dict()
+        .arg("import-js")
+        .arg(&input)
+        .assert()
+        .code(0)
+        .stdout("cigar\nrebut\n");
+
+    let input = fixture("import-js", "broken.js", r#"["cigar" "rebut"]"#);
+    wordle_dict()
+        .arg("import-js")
+        .arg(&input)
+        .assert()
+        .code(2)
+        .stderr(contains("byte 9: expected ',' or ']' after the word"));
+}
+
+#[test]
+fn test_missing_file() {
+    wordle_dict()
+        .args(["normalize", "no-such-list.txt"])
+        .assert()
+        .code(2)
+        .stderr(contains("Error: could not read 'no-such-list.txt'"));
+}
+
+#[cfg(feature = "fst")]
+#[test]
+fn test_build_fst() {
+    use wordle::{dictionary::Dictionary, GuessError, Wordle};
+
+    let input = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/guesses.txt");
+    let output = fixture("build-fst", "guesses.fst", "");
+    wordle_dict()
+        .arg("build-fst")
+        .arg(input)
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .code(0)
+        .stderr(contains("Wrote 7 words to"));
+
+    // a game can look its guesses up in the transducer
+    let text = fs::read_to_string(input).unwrap();
+    let guesses: Vec<&str> = text.lines().collect();
+    let dictionary = Dictionary::load_fst(&guesses, &output).unwrap();
+    let mut game = Wordle::builder()
+        .dictionary(dictionary)
+        .answers(&["crane"])
+        .build()
+        .unwrap()
+        .choose_word();
+    assert_eq!(Err(GuessError::NotInWordList), game.guess("spare"));
+    game.guess("trace").unwrap();
+    game.guess("crane").unwrap();
+    assert_eq!(&guesses[..], game.guess_list());
+
+    // but not one built from another list
+    assert!(Dictionary::load_fst(&guesses[1..], &output).is_err());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_pack() {
+    use predicates::prelude::PredicateBooleanExt;
+    use wordle::{dictionary::Dictionary, GameStatus, Wordle};
+
+    let input = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/guesses.txt");
+    let output = fixture("pack", "guesses.pack", "");
+    let mut pack = wordle_dict();
+    pack.arg("pack").arg(input).arg("--output").arg(&output);
+    #[cfg(feature = "fst")]
+    pack.arg("--fst");
+    pack.assert().code(0).stderr(contains("Wrote 7 words to"));
+
+    // the packed list plays just like the text one
+    let packed = Dictionary::open_packed(&output).unwrap();
+    assert_eq!(cfg!(feature = "fst"), packed.has_fst());
+    let words = packed.words();
+    let text = fs::read_to_string(input).unwrap();
+    assert_eq!(text.lines().collect::<Vec<_>>(), words);
+    let mut game = Wordle::builder()
+        .dictionary(packed.dictionary(&words).unwrap())
+        .answers(&words[..1])
+        .build()
+        .unwrap()
+        .choose_word();
+    assert!(game.guess("spare").is_err());
+    game.guess("slate").unwrap();
+    game.guess("crane").unwrap();
+    assert_eq!(GameStatus::Won { guesses: 2 }, game.status());
+
+    // and a damaged one is turned away
+    let mut bytes = fs::read(&output).unwrap();
+    bytes.truncate(bytes.len() - 3);
+    let truncated = output.with_extension("truncated");
+    fs::write(&truncated, bytes).unwrap();
+    let error = Dictionary::open_packed(&truncated).unwrap_err();
+    assert!(error.to_string().contains("it may have been cut off"));
+
+    wordle_dict()
+        .args(["pack", "--length", "6", input, "--output"])
+        .arg(output.with_extension("wrong"))
+        .assert()
+        .code(2)
+        .stderr(contains("could not pack").and(contains("word 1 is invalid")));
+}
@@ -0,0 +1,92 @@
+# Tests of the Python bindings; see the README for how to run them
+
+import pytest
+
+import wordle
+
+
+def test_score_guess():
+    assert wordle.score_guess("crane", "crane") == ["correct"] * 5
+    assert wordle.score_guess("speed", "abide") == [
+        "absent",
+        "absent",
+        "present",
+        "absent",
+        "present",
+    ]
+    with pytest.raises(ValueError):
+        wordle.score_guess("crane", "cranes")
+
+
+def test_filter_candidates():
+    candidates = ["hello", "world", "jolly", "lolly", "holly"]
+    assert wordle.filter_candidates(candidates, "twirp", ["absent"] * 5) == [
+        "hello",
+        "jolly",
+        "lolly",
+        "holly",
+    ]
+    assert wordle.filter_candidates(candidates, "world", ["correct"] * 5) == ["world"]
+    with pytest.raises(ValueError):
+        wordle.filter_candidates(candidates, "world", ["green"] * 5)
+    with pytest.raises(ValueError):
+        wordle.filter_candidates(candidates, "world", ["correct"])
+
+
+def test_suggest():
+    suggestions = wordle.suggest(["crane", "slate", "irate"], k=2)
+    assert len(suggestions) == 2
+    word, bits = suggestions[0]
+    assert word in ["crane", "slate", "irate"]
+    assert bits > 0
+
+    guesses = ["crane", "slate", "irate", "trace"]
+    assert [word for word, _ in wordle.suggest(["crane"], guesses, k=1)] == ["crane"]
+
+
+def test_game():
+    game = wordle.Wordle(hard=True, seed=42)
+    game.set_answer("crane")
+    assert game.status == "in_progress"
+    assert game.answer is None
+
+    assert game.guess("slate") == ["absent", "absent", "correct", "absent", "correct"]
+    assert "crane" in game.candidates()
+    assert len(game.suggest(k=3)) == 3
+    assert game.guesses_left == 5
+
+    # hard mode: the A has to stay where it is
+    with pytest.raises(wordle.InvalidGuess):
+        game.guess("irons")
+    with pytest.raises(ValueError):
+        game.guess("zzzzz")
+
+    assert game.guess("crane") == ["correct"] * 5
+    assert game.status == "won"
+    assert game.answer == "crane"
+    assert game.history == [
+        ("slate", ["absent", "absent", "correct", "absent", "correct"]),
+        ("crane", ["correct"] * 5),
+    ]
+
+
+def test_seeded_games():
+    first, second = wordle.Wordle(seed=7), wordle.Wordle(seed=7)
+    first.choose_word()
+    second.choose_word()
+    for game in (first, second):
+        while game.status == "in_progress":
+            game.guess(game.suggest(k=1)[0][0])
+    assert first.answer == second.answer
+    assert first.history == second.history
+
+
+def test_word_lists():
+    game = wordle.Wordle(["crane", "slate", "irate"], ["crane"])
+    game.choose_word()
+    assert game.candidates() == ["crane"]
+    with pytest.raises(wordle.InvalidGuess):
+        game.guess("trace")
+
+    with pytest.raises(ValueError):
+        wordle.Wordle(["crane"], ["slate"])
@@ -0,0 +1,216 @@
+//! Tests of sending daily results to a server with `--server`, against a mock server
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{
+    net::TcpListener,
+    path::{Path, PathBuf},
+};
+
+use assert_cmd::Command;
+use chrono::Utc;
+use predicates::str::contains;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+use wordle::daily;
+
+const ANSWERS: [&str; 3] = ["crane", "slate", "irate"];
+
+/// A directory of its own for a test, to keep stats in
+fn dir(test: &str) -> PathBuf {
+    common::dir(&format!("remote-{}", test))
+}
+
+/// Play today's puzzle with the test word lists, sending the result to the
+/// server at `url` and keeping stats in `dir`; it's won in one guess
+fn wordle(dir: &Path, url: &str) -> Command {
+    let puzzle = daily::puzzle_number(Utc::now().date_naive()).unwrap();
+    let mut command = common::wordle(dir, "");
+    command
+        .args([
+            "--no-share",
+            "--no-color",
+            "--daily",
+            "--utc",
+            "--server",
+            url,
+        ])
+        .write_stdin(format!("{}\n", daily::answer(&ANSWERS, puzzle)));
+    command
+}
+
+/// Where results that couldn't be sent are kept
+fn outbox(dir: &Path) -> PathBuf {
+    dir.join("wordle").join("outbox.json")
+}
+
+/// The leaderboard the mock server gives back
+fn leaderboard() -> Value {
+    json!({
+        "date": "2022-01-01",
+        "puzzle": 196,
+        "results": [
+            { "rank": 1, "player": "anonymous-ab12", "guesses": 1, "time_ms": 5_000, "hard_mode": false },
+            { "rank": 2, "player": "bob", "guesses": 3, "time_ms": 83_000, "hard_mode": true },
+        ],
+    })
+}
+
+/// Answer submissions with `status` and `body`, and leaderboard requests with [`leaderboard`]
+async fn mock(status: u16, body: Value) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/daily/result"))
+        .respond_with(ResponseTemplate::new(status).set_body_json(body))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/daily/leaderboard"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(leaderboard()))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// The bodies of the results sent to the server
+async fn submitted(server: &MockServer) -> Vec<Value> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|request| request.url.path() == "/daily/result")
+        .map(|request| request.body_json().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_submit() {
+    let dir = dir("submit");
+    let server = mock(201, leaderboard()["results"][0].clone()).await;
+    wordle(&dir, &server.uri())
+        .assert()
+        .success()
+        .stdout(contains("You're #1 on the leaderboard, as anonymous-ab12"))
+        .stdout(contains(concat!(
+            "Leaderboard for Wordle #196 (2022-01-01):\n",
+            "  1. anonymous-ab12  1 in 5s\n",
+            "  2. bob             3 in 1m 23s  (hard mode)\n",
+        )));
+
+    // the server checks the game by playing it again, so it gets all of it
+    let submitted = submitted(&server).await;
+    assert_eq!(1, submitted.len());
+    assert_eq!(Value::Null, submitted[0]["player"]);
+    let transcript = &submitted[0]["transcript"];
+    assert_eq!(
+        json!(wordle::daily::puzzle_number(Utc::now().date_naive())),
+        transcript["puzzle"]
+    );
+    assert_eq!(1, transcript["guesses"].as_array().unwrap().len());
+    assert!(!outbox(&dir).exists());
+}
+
+#[tokio::test]
+async fn test_rejected() {
+    let dir = dir("rejected");
+    let server = mock(
+        422,
+        json!({ "error": "Games played with help from the solver don't count" }),
+    )
+    .await;
+    wordle(&dir, &server.uri())
+        .assert()
+        .success()
+        .stderr(contains(
+            "could not put the result on the leaderboard: the server refused it: Games played with help from the solver don't count",
+        ))
+        .stdout(contains("Leaderboard for Wordle #196"));
+    // sending it again wouldn't help, so it isn't kept
+    assert!(!outbox(&dir).exists());
+    // but it still counts towards the player's own stats
+    assert!(dir.join("wordle").join("stats.json").exists());
+}
+
+#[tokio::test]
+async fn test_offline() {
+    let dir = dir("offline");
+    // a port nothing is listening on
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let url = format!("http://127.0.0.1:{}", port);
+    wordle(&dir, &url)
+        .assert()
+        .success()
+        .stderr(contains("could not reach the server"))
+        .stderr(contains("the result will be sent next time"));
+    assert!(outbox(&dir).exists());
+    assert!(dir.join("wordle").join("stats.json").exists());
+
+    // a server having trouble of its own is worth trying again later too
+    let server = mock(503, json!({ "error": "The database is down" })).await;
+    wordle(&dir, &server.uri())
+        .assert()
+        .success()
+        .stdout(contains("You've already played"));
+    assert_eq!(1, submitted(&server).await.len());
+    assert!(outbox(&dir).exists());
+
+    // the next run sends the result it kept
+    let server = mock(201, leaderboard()["results"][0].clone()).await;
+    wordle(&dir, &server.uri())
+        .assert()
+        .success()
+        .stdout(contains(
+            "Sent 1 results kept from before to the leaderboard",
+        ))
+        .stdout(contains("You've already played"));
+    assert_eq!(1, submitted(&server).await.len());
+    assert!(!outbox(&dir).exists());
+}
+
+#[tokio::test]
+async fn test_keyed_server() {
+    let dir = dir("keyed");
+    let server = mock(201, leaderboard()["results"][0].clone()).await;
+    Mock::given(method("GET"))
+        .and(path("/daily/meta"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "date": "2022-01-01",
+            "puzzle": 196,
+            "rollover": "2022-01-02T00:00:00Z",
+            "fingerprint": "0123456789abcdef",
+        })))
+        .mount(&server)
+        .await;
+    wordle(&dir, &server.uri())
+        .assert()
+        .success()
+        .stderr(contains("picks its own daily answers"));
+
+    // the server would refuse it, so it isn't sent, or kept to send again
+    assert!(submitted(&server).await.is_empty());
+    assert!(!outbox(&dir).exists());
+    assert!(dir.join("wordle").join("stats.json").exists());
+}
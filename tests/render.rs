@@ -0,0 +1,196 @@
+//! Golden-file tests of the SVG boards
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{env, fs, path::PathBuf};
+
+use wordle::{
+    render::{
+        html::{board_html, transcript_html},
+        svg::{board_svg, transcript_svg},
+        Background, BoardOptions, Palette,
+    },
+    theme::Theme,
+    transcript::Transcript,
+    Wordle,
+};
+
+const WORDS: [&str; 5] = ["crane", "scare", "trace", "slate", "speed"];
+
+/// A game against "crane", after these guesses
+fn transcript(guesses: &[&str]) -> Transcript {
+    let mut game = Wordle::new(&WORDS, &WORDS).set_answer("crane").unwrap();
+    for guess in guesses {
+        game.guess(guess).unwrap();
+    }
+    game.transcript()
+}
+
+/// Check `svg` against the golden file `name` in `tests/data/render`.
+///
+/// Set `UPDATE_GOLDEN=1` to write the file instead, after checking by eye
+/// that the change is intended.
+fn check(name: &str, svg: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data/render")
+        .join(name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, svg).unwrap();
+    }
+    let golden = fs::read_to_string(&path).unwrap();
+    assert_eq!(golden, svg, "{} changed", name);
+}
+
+#[test]
+fn test_won() {
+    let transcript = transcript(&["speed", "scare", "trace", "crane"]);
+    check(
+        "won.svg",
+        &transcript_svg(&transcript, &BoardOptions::default()),
+    );
+}
+
+#[test]
+fn test_dark_without_letters() {
+    let transcript = transcript(&["slate", "trace"]);
+    let options = BoardOptions {
+        tile_size: 40,
+        letters: false,
+        background: Background::Dark,
+        palette: None,
+    };
+    check("dark.svg", &transcript_svg(&transcript, &options));
+}
+
+#[test]
+fn test_high_contrast() {
+    let history = vec![("scare", wordle::score_guess("scare", "crane"))];
+    let options = BoardOptions {
+        tile_size: 32,
+        palette: Some(Palette::high_contrast()),
+        ..BoardOptions::default()
+    };
+    check("high-contrast.svg", &board_svg(&history, &options));
+}
+
+#[test]
+fn test_custom_theme() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/themes/solarized.toml"
+    );
+    let theme = Theme::load(path.as_ref()).unwrap();
+    let transcript = transcript(&["slate", "scare", "crane"]);
+    let options = BoardOptions {
+        tile_size: 40,
+        palette: Some(theme.palette(Background::Dark)),
+        background: Background::Dark,
+        ..BoardOptions::default()
+    };
+    check("solarized.svg", &transcript_svg(&transcript, &options));
+    let html = transcript_html(&transcript, &options);
+    assert_well_formed(&html);
+    check("solarized.html", &html);
+
+    // the built-in themes keep the official game's shades
+    let classic = Theme::classic().palette(Background::Light);
+    assert_eq!(Background::Light.palette(), classic);
+    let high_contrast = Theme::high_contrast().palette(Background::Dark);
+    assert_eq!(Palette::high_contrast().correct, high_contrast.correct);
+    assert_eq!(
+        Background::Dark.palette().not_in_word,
+        high_contrast.not_in_word
+    );
+}
+
+/// Check that `html` is well formed: every tag is closed in order, attribute
+/// values are quoted, and `<`, `>`, `&` and `"` only appear escaped in text
+fn assert_well_formed(html: &str) {
+    let mut open = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        assert!(
+            !text.contains('>') && !text.contains('"'),
+            "unescaped text {:?}",
+            text
+        );
+        for (i, _) in text.match_indices('&') {
+            let entity = &text[i..text[i..].find(';').map_or(text.len(), |end| i + end + 1)];
+            assert!(
+                ["&amp;", "&lt;", "&gt;", "&quot;"].contains(&entity),
+                "unescaped & in {:?}",
+                text
+            );
+        }
+
+        let end = start + rest[start..].find('>').expect("unclosed tag");
+        let tag = &rest[start + 1..end];
+        assert!(!tag.contains('<'), "unclosed tag {:?}", tag);
+        assert_eq!(
+            0,
+            tag.matches('"').count() % 2,
+            "unbalanced quotes in {:?}",
+            tag
+        );
+        match tag.strip_prefix('/') {
+            Some(name) => assert_eq!(open.pop(), Some(name), "mismatched </{}>", name),
+            None => {
+                let name = tag.split_whitespace().next().unwrap();
+                let attributes = tag[name.len()..].split('"').step_by(2);
+                for attribute in attributes.map(str::trim).filter(|a| !a.is_empty()) {
+                    assert!(attribute.ends_with('='), "unquoted attribute in {:?}", tag);
+                }
+                open.push(name);
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    assert!(
+        rest.trim().is_empty(),
+        "text after the last tag: {:?}",
+        rest
+    );
+    assert!(open.is_empty(), "unclosed elements: {:?}", open);
+}
+
+#[test]
+fn test_html_won() {
+    let transcript = transcript(&["speed", "scare", "trace", "crane"]);
+    let html = transcript_html(&transcript, &BoardOptions::default());
+    assert_well_formed(&html);
+    check("won.html", &html);
+}
+
+#[test]
+fn test_html_dark_without_letters() {
+    let transcript = transcript(&["slate", "trace"]);
+    let options = BoardOptions {
+        tile_size: 40,
+        letters: false,
+        background: Background::Dark,
+        palette: None,
+    };
+    let html = transcript_html(&transcript, &options);
+    assert_well_formed(&html);
+    check("dark.html", &html);
+}
+
+#[test]
+fn test_html_escaped() {
+    let history = vec![("<a&\">", vec![wordle::LetterStatus::InWord; 5])];
+    assert_well_formed(&board_html(&history, &BoardOptions::default()));
+}
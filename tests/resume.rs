@@ -0,0 +1,127 @@
+//! End-to-end tests of picking up saved games with `--resume`
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use wordle::{transcript::Transcript, LetterStatus, Wordle};
+
+const WORDS: [&str; 7] = [
+    "crane", "trace", "slate", "stare", "snare", "share", "irate",
+];
+
+/// A practice game against "slate", after these guesses
+fn transcript(guesses: &[&str]) -> Transcript {
+    let mut game = Wordle::new(&WORDS, &WORDS).set_answer("slate").unwrap();
+    for guess in guesses {
+        game.guess(guess).unwrap();
+    }
+    Transcript {
+        practice: true,
+        ..game.transcript()
+    }
+}
+
+/// Save a transcript in a directory of its own, returning the directory and the save
+fn save(name: &str, transcript: &Transcript) -> (PathBuf, PathBuf) {
+    let dir = common::dir(name);
+    let path = dir.join("saved.json");
+    fs::write(&path, serde_json::to_string_pretty(transcript).unwrap()).unwrap();
+    (dir, path)
+}
+
+/// Run the game with the test word lists, keeping its stats in `dir`
+fn wordle(dir: &Path, lists: &str) -> Command {
+    let mut command = common::wordle(dir, lists);
+    command.arg("--no-share");
+    command
+}
+
+#[test]
+fn test_resume_path() {
+    let (dir, path) = save("resume-path", &transcript(&["crane"]));
+    wordle(&dir, "")
+        .arg("--resume")
+        .arg(&path)
+        .write_stdin("slate\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--- Practice game resumed ---"))
+        .stdout(predicate::str::contains("Guess 2/6"))
+        .stdout(predicate::str::contains("Magnificent!"));
+    // a save that isn't the Ctrl-C one is left where it was
+    assert!(path.exists());
+}
+
+#[test]
+fn test_resume_finished() {
+    let (dir, path) = save("resume-finished", &transcript(&["crane", "trace", "slate"]));
+    wordle(&dir, "")
+        .arg("--resume")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--- This game is over ---"))
+        .stdout(predicate::str::contains("Found SLATE in 3/6"))
+        .stdout(predicate::str::contains("Guess").not());
+}
+
+#[test]
+fn test_resume_tampered() {
+    let mut tampered = transcript(&["crane"]);
+    tampered.guesses[0].feedback = vec![LetterStatus::Correct; 5];
+    let (dir, path) = save("resume-tampered", &tampered);
+    wordle(&dir, "")
+        .arg("--resume")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the feedback for the guess 'crane' doesn't match the answer",
+        ));
+
+    // the saved game's words are 5 letters long, not 6 like these lists
+    let (dir, path) = save("resume-stale", &transcript(&["crane"]));
+    wordle(&dir, "6")
+        .arg("--length")
+        .arg("6")
+        .arg("--resume")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the saved game has 5-letter words, not 6",
+        ));
+}
+
+#[test]
+fn test_resume_missing() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("resume-missing");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    wordle(&dir, "")
+        .arg("--resume")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("there is no saved game to resume"));
+}
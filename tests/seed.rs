@@ -0,0 +1,59 @@
+//! End-to-end tests of reproducible sessions with `--seed`
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+/// Skip every word of a session, returning everything printed.
+///
+/// Each run keeps its stats and settings in its own empty directory, so what's
+/// printed after each game doesn't depend on earlier runs or the user's config.
+fn transcript(run: &str, args: &[&str]) -> String {
+    let output = common::wordle(&common::dir(run), "")
+        .args(args)
+        .write_stdin(":skip\ny\n".repeat(7))
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_same_seed() {
+    let first = transcript("seed-1", &["--seed", "1337", "--games", "4"]);
+    let second = transcript("seed-2", &["--seed", "1337", "--games", "4"]);
+    assert_eq!(first, second);
+    assert!(first.contains("--- Game 4 of seed 1337 started ---"));
+}
+
+/// The answer revealed at the end of each game
+fn answers(transcript: &str) -> Vec<&str> {
+    transcript
+        .lines()
+        .filter_map(|line| line.split("The word was ").nth(1))
+        .collect()
+}
+
+#[test]
+fn test_jump_to_game() {
+    let played = transcript("game-1", &["--seed", "42", "--games", "7"]);
+    let jumped = transcript("game-2", &["--seed", "42", "--game", "7", "--games", "1"]);
+    assert_eq!(7, answers(&played).len());
+    assert_eq!(answers(&played)[6..], answers(&jumped));
+    assert!(jumped.contains("--- Game 7 of seed 42 started ---"));
+    assert!(jumped.contains("Wordle seed 42 game 7 X/6"));
+}
@@ -0,0 +1,197 @@
+//! End-to-end tests of the `wordle-sim` binary
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use wordle::{transcript::Transcript, GameStatus};
+
+/// The `wordle-sim` binary, using the test word lists and a cache of its own
+fn wordle_sim(args: &[&str]) -> Command {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let mut cmd = Command::cargo_bin("wordle-sim").unwrap();
+    cmd.env("XDG_CACHE_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .arg("--guesses")
+        .arg(format!("{}/guesses.txt", data))
+        .arg("--answers")
+        .arg(format!("{}/answers.txt", data))
+        .args(args);
+    cmd
+}
+
+#[test]
+fn test_table() {
+    wordle_sim(&["--strategy", "entropy", "--strategy", "random", "--seed", "7"])
+        .assert()
+        .code(0)
+        .stdout(contains(
+            "3 answers, 7 guesses, seed 7\n\n\
+             strategy  games   wins  failures  average     1     2     3     4     5     6      time  per game\n\
+             entropy       3      3         0    1.667     1     2     0     0     0     0",
+        ))
+        .stdout(contains(
+            "\nrandom        3      3         0    1.667     1     2     0     0     0     0",
+        ));
+}
+
+#[test]
+fn test_json_report() {
+    let path = format!("{}/sim-report.json", env!("CARGO_TARGET_TMPDIR"));
+    wordle_sim(&["--seed", "7", "--threads", "2", "--out", &path])
+        .assert()
+        .code(0);
+
+    let mut report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    // the only things that change from run to run
+    for key in ["seconds", "seconds_per_game"] {
+        assert!(report["reports"][0][key].is_f64());
+        report["reports"][0][key] = 0.0.into();
+    }
+    assert_eq!(
+        serde_json::json!({
+            "version": 2,
+            "seed": 7,
+            "guesses": 7,
+            "answers": 3,
+            "max_guesses": 6,
+            "reports": [{
+                "strategy": "entropy",
+                "games": 3,
+                "wins": 3,
+                "average_guesses": 5.0 / 3.0,
+                "distribution": [1, 2, 0, 0, 0, 0],
+                "failures": [],
+                "slow": [],
+                "seconds": 0.0,
+                "seconds_per_game": 0.0,
+            }],
+            "head_to_head": [],
+        }),
+        report
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_deterministic() {
+    // only the random strategy needs a seed to be repeatable
+    wordle_sim(&[
+        "--strategy",
+        "frequency",
+        "--strategy",
+        "entropy",
+        "--strategy",
+        "lookahead",
+    ])
+    .assert()
+    .code(0)
+    .stdout(contains(
+        "strategy   games   wins  failures  average     1     2     3     4     5     6      time  per game\n\
+         frequency      3      3         0    1.667     1     2     0     0     0     0",
+    ))
+    .stdout(contains(
+        "\nentropy        3      3         0    1.667     1     2     0     0     0     0",
+    ))
+    .stdout(contains(
+        "\nlookahead      3      3         0    1.667     1     2     0     0     0     0",
+    ));
+}
+
+#[test]
+fn test_head_to_head() {
+    wordle_sim(&["--strategy", "entropy", "--strategy", "frequency"])
+        .assert()
+        .code(0)
+        .stdout(contains(
+            "head to head          better   worse   same  difference\n\
+             entropy vs frequency       1       1      1      +0.000\n",
+        ));
+}
+
+#[test]
+fn test_save_transcripts() {
+    let dir = format!("{}/sim-transcripts", env!("CARGO_TARGET_TMPDIR"));
+    let _ = fs::remove_dir_all(&dir);
+    wordle_sim(&["--strategy", "frequency", "--save-transcripts", &dir])
+        .assert()
+        .code(0);
+
+    let mut names: Vec<_> = fs::read_dir(format!("{}/frequency", dir))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(vec!["crane.json", "irate.json", "slate.json"], names);
+
+    let transcript: Transcript =
+        serde_json::from_str(&fs::read_to_string(format!("{}/frequency/slate.json", dir)).unwrap())
+            .unwrap();
+    assert_eq!(Some("slate"), transcript.answer.as_deref());
+    assert_eq!(GameStatus::Won { guesses: 2 }, transcript.status);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compare() {
+    // answers that only differ in their first letter, and guesses that can
+    // tell them apart; a strategy that only guesses possible answers does badly
+    let dir = format!("{}/sim-compare", env!("CARGO_TARGET_TMPDIR"));
+    fs::create_dir_all(&dir).unwrap();
+    let answers = "bight\ndight\nfight\nlight\nmight\nnight\nright\nsight\ntight\nwight\n";
+    fs::write(format!("{}/answers.txt", dir), answers).unwrap();
+    fs::write(
+        format!("{}/guesses.txt", dir),
+        format!("{}fldmb\nswart\n", answers),
+    )
+    .unwrap();
+    let compare = |a: &str, b: &str| {
+        let mut cmd = Command::cargo_bin("wordle-sim").unwrap();
+        cmd.env("XDG_CACHE_HOME", env!("CARGO_TARGET_TMPDIR"))
+            .args(["compare", "--a", a, "--b", b, "--seed", "3"])
+            .arg("--guesses")
+            .arg(format!("{}/guesses.txt", dir))
+            .arg("--answers")
+            .arg(format!("{}/answers.txt", dir));
+        cmd
+    };
+
+    compare("entropy", "random")
+        .assert()
+        .code(2)
+        .stdout(contains(
+            "random vs entropy: better on 1 of 10 answers, worse on 8, the same on 1\n\
+             Mean difference: +1.900 guesses (95% interval +0.700 to +3.100)\n\
+             Sign test: p = 0.039\n\
+             random is significantly worse than entropy\n",
+        ));
+    compare("random", "entropy")
+        .assert()
+        .code(0)
+        .stdout(contains("entropy is significantly better than random\n"));
+    compare("entropy", "entropy")
+        .assert()
+        .code(0)
+        .stdout(contains(
+            "entropy vs entropy: better on 0 of 10 answers, worse on 0, the same on 10\n",
+        ))
+        .stdout(contains(
+            "entropy is not significantly different from entropy\n",
+        ));
+    fs::remove_dir_all(&dir).unwrap();
+}
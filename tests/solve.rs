@@ -0,0 +1,123 @@
+//! End-to-end tests of the `wordle-solve` binary
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::{prelude::*, str::contains};
+
+/// The `wordle-solve` binary, using the test word lists and a cache of its own
+fn wordle_solve() -> Command {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let mut cmd = Command::cargo_bin("wordle-solve").unwrap();
+    cmd.env("XDG_CACHE_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .arg("--guesses")
+        .arg(format!("{}/guesses.txt", data))
+        .arg("--answers")
+        .arg(format!("{}/answers.txt", data));
+    cmd
+}
+
+#[test]
+fn test_solve() {
+    // the suggestion is CRANE; a typo is taken back, then SLATE is played instead
+    wordle_solve()
+        .write_stdin("gyx\nyg---\nundo\nslate b b g g g\nggggg\n")
+        .assert()
+        .code(0)
+        .stdout(contains("3 possible answers left; try CRANE (1.58 bits)"))
+        .stdout(contains("Feedback must have exactly 5 colors, not 3"))
+        .stdout(contains("Took back CRANE"))
+        .stdout(contains("The answer must be IRATE"))
+        .stdout(contains(
+            "Solved in 2 guesses!\n\nHow it went:\n\
+             1. SLATE ⬛⬛🟩🟩🟩  narrowed 3 possible answers to 1\n\
+             2. IRATE 🟩🟩🟩🟩🟩  solved\n",
+        ));
+}
+
+#[test]
+fn test_no_candidates() {
+    wordle_solve()
+        .write_stdin("ggggy\nggggg\nquit\n")
+        .assert()
+        .code(0)
+        .stdout(contains("No words fit that feedback"))
+        .stdout(contains("Type the word you played before its colors"))
+        .stdout(contains(
+            "1. CRANE 🟩🟩🟩🟩🟨  narrowed 3 possible answers to 0",
+        ));
+}
+
+#[test]
+fn test_cache() {
+    let dir = format!("{}/solve-cache", env!("CARGO_TARGET_TMPDIR"));
+    let _ = fs::remove_dir_all(&dir);
+    // with every guess as a possible answer, SHARE leaves CRANE, TRACE and
+    // IRATE, which takes a search to choose between
+    let guesses = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/guesses.txt");
+    let solve = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("wordle-solve").unwrap();
+        cmd.env("XDG_CACHE_HOME", env!("CARGO_TARGET_TMPDIR"))
+            .args(["--guesses", guesses, "--answers", guesses])
+            .args(args)
+            .write_stdin("share --gyg\nquit\n");
+        cmd
+    };
+
+    solve(&["--cache-dir", &dir, "--cache-stats"])
+        .assert()
+        .code(0)
+        .stdout(contains("Solver cache: 0 hits, 1 miss, 1 entry ("))
+        .stdout(contains(format!("in {}/solver.txt", dir)));
+    // the next run doesn't need to search again
+    solve(&["--cache-dir", &dir, "--cache-stats"])
+        .assert()
+        .code(0)
+        .stdout(contains("Solver cache: 1 hit, 0 misses, 1 entry ("));
+
+    // a corrupt cache is searched again
+    fs::write(format!("{}/solver.txt", dir), "\u{0}garbage").unwrap();
+    solve(&["--cache-dir", &dir, "--cache-stats"])
+        .assert()
+        .code(0)
+        .stdout(contains("Solver cache: 0 hits, 1 miss, 1 entry ("));
+
+    // and without the cache, the same guesses are suggested without saving anything
+    fs::remove_dir_all(&dir).unwrap();
+    solve(&["--no-cache"])
+        .assert()
+        .code(0)
+        .stdout(contains("3 possible answers left; try "))
+        .stdout(contains("Solver cache").not());
+    assert!(!std::path::Path::new(&dir).exists());
+}
+
+#[test]
+fn test_explain() {
+    wordle_solve()
+        .arg("--explain")
+        .write_stdin("quit\n")
+        .assert()
+        .code(0)
+        .stdout(contains(
+            "3 possible answers left; try CRANE (1.58 bits), IRATE (1.58 bits), SLATE (1.58 bits)\n  \
+             CRANE: could be the answer; leaves 0.67 answers on average, 1 at most\n    \
+             likeliest colors: ⬛⬛🟩⬛🟩 1, ⬛🟩🟩⬛🟩 1, 🟩🟩🟩🟩🟩 1\n  \
+             IRATE: could be the answer;",
+        ));
+}
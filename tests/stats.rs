@@ -0,0 +1,196 @@
+//! End-to-end tests of the game log and exporting it
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use assert_cmd::Command;
+use predicates::str::contains;
+
+/// A directory of its own for a test, to keep stats in
+fn dir(test: &str) -> PathBuf {
+    common::dir(&format!("stats-{}", test))
+}
+
+/// Run the game with the test word lists, keeping its stats in `dir`
+fn wordle(dir: &Path) -> Command {
+    let mut command = common::wordle(dir, "");
+    command.arg("--no-share").arg("--no-color");
+    command
+}
+
+#[test]
+fn test_export_empty() {
+    let dir = dir("empty");
+    let path = dir.join("games.csv");
+    wordle(&dir)
+        .arg("--export-stats")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(contains("Exported 0 games"));
+    assert_eq!(
+        "date,puzzle,seed,game,won,guesses,hard_mode,assisted,seconds\n",
+        fs::read_to_string(&path).unwrap()
+    );
+}
+
+#[test]
+fn test_export() {
+    let dir = dir("export");
+    // two games given up, a practice game, and one in hard mode
+    wordle(&dir)
+        .args(["--seed", "42", "--games", "2"])
+        .write_stdin(":skip\ny\n:skip\n")
+        .assert()
+        .success();
+    wordle(&dir)
+        .args(["--answer", "crane"])
+        .write_stdin("crane\n")
+        .assert()
+        .success();
+    wordle(&dir)
+        .args(["--seed", "7", "--game", "3", "--hard"])
+        .write_stdin("crane\nslate\nirate\n")
+        .assert()
+        .success();
+
+    let csv = dir.join("games.csv");
+    wordle(&dir)
+        .arg("--export-stats")
+        .arg(&csv)
+        .assert()
+        .success()
+        .stdout(contains("Exported 3 games"));
+    let csv = fs::read_to_string(csv).unwrap();
+    let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+    assert_eq!(
+        4,
+        rows.len(),
+        "a header and a row per game, but not practice"
+    );
+    assert_eq!(
+        vec![",42,1,false,0,false,false", ",42,2,false,0,false,false"],
+        rows[1..3]
+            .iter()
+            .map(|row| row[1..8].join(","))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["", "7", "3"], rows[3][1..4]);
+    assert_eq!("true", rows[3][6], "the hard mode game is exported too");
+
+    let json = dir.join("games.txt");
+    wordle(&dir)
+        .arg("--export-stats")
+        .arg(&json)
+        .assert()
+        .failure()
+        .stderr(contains("use --format csv or --format json"));
+    wordle(&dir)
+        .arg("--export-stats")
+        .arg(&json)
+        .args(["--format", "json"])
+        .assert()
+        .success();
+    let games: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(json).unwrap()).unwrap();
+    assert_eq!(3, games.len());
+    assert_eq!(Some(42), games[0]["seed"].as_u64());
+    assert_eq!(Some(true), games[2]["hard_mode"].as_bool());
+}
+
+#[test]
+fn test_merge() {
+    let laptop = dir("merge-laptop");
+    let desktop = dir("merge-desktop");
+    wordle(&laptop)
+        .args(["--seed", "42", "--games", "2"])
+        .write_stdin(":skip\ny\n:skip\n")
+        .assert()
+        .success();
+    // the first two games were already played on the laptop
+    wordle(&desktop)
+        .args(["--seed", "42", "--games", "3"])
+        .write_stdin(":skip\ny\n:skip\ny\n:skip\n")
+        .assert()
+        .success();
+
+    let stats = desktop.join("wordle").join("stats.json");
+    wordle(&laptop)
+        .arg("--merge-stats")
+        .arg(&stats)
+        .assert()
+        .success()
+        .stdout(contains("Added 1 games from"))
+        .stdout(contains("skipped 2 already recorded"));
+    let output = wordle(&laptop).arg("--stats-json").output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(Some(3), json["stats"]["played"].as_u64());
+
+    wordle(&laptop)
+        .arg("--merge-stats")
+        .arg(desktop.join("missing.json"))
+        .assert()
+        .failure()
+        .stderr(contains("could not read stats"));
+}
+
+#[test]
+fn test_merge_conflict() {
+    let laptop = dir("conflict-laptop");
+    let desktop = dir("conflict-desktop");
+    let record = |date: &str, won: bool| {
+        format!(
+            r#"{{"date":"{}","puzzle":196,"seed":null,"game":null,"won":{},"guesses":3,"hard_mode":false,"assisted":false,"duration":{{"secs":60,"nanos":0}}}}"#,
+            date, won
+        )
+    };
+    for (dir, date, won) in [
+        (&laptop, "2022-01-02", true),
+        (&desktop, "2022-01-01", false),
+    ] {
+        let data = dir.join("wordle");
+        fs::create_dir_all(&data).unwrap();
+        let stats = if won {
+            r#"{"played":1,"wins":1,"current_streak":1,"max_streak":1,"distribution":[0,0,1]}"#
+        } else {
+            r#"{"played":1,"wins":0,"current_streak":0,"max_streak":0,"distribution":[]}"#
+        };
+        fs::write(data.join("stats.json"), stats).unwrap();
+        fs::write(data.join("stats.games.jsonl"), record(date, won) + "\n").unwrap();
+    }
+
+    wordle(&laptop)
+        .arg("--merge-stats")
+        .arg(desktop.join("wordle").join("stats.json"))
+        .assert()
+        .success()
+        .stderr(contains(
+            "Warning: Wordle #196 was recorded differently in each; kept the earlier record, from 2022-01-01",
+        ))
+        .stdout(contains("Added 0 games"));
+    let output = wordle(&laptop).arg("--stats-json").output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(Some(1), json["stats"]["played"].as_u64());
+    assert_eq!(Some(0), json["stats"]["wins"].as_u64());
+    let log = fs::read_to_string(laptop.join("wordle").join("stats.games.jsonl")).unwrap();
+    assert_eq!(record("2022-01-01", false) + "\n", log);
+}
@@ -0,0 +1,109 @@
+//! Checks that reading a big word list only holds a line of it in memory at a time
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use wordle::wordlist::{self, LoadError, Problem};
+
+/// The system allocator, keeping track of the most memory in use at once
+struct Counting;
+
+/// The number of bytes allocated right now
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The most bytes allocated at once since the last [`reset_peak`]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Start measuring the most memory in use from now, relative to what's in use now
+fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(current, Ordering::SeqCst);
+    current
+}
+
+/// Write a big word list: mostly comments, with a word on every 1000th line,
+/// and `last` as its final line
+fn big_list(name: &str, last: &str) -> (PathBuf, u64) {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let mut file = BufWriter::new(fs::File::create(&path).unwrap());
+    for i in 0..300_000 {
+        match i % 1000 {
+            0 => writeln!(file, "  crane"),
+            _ => writeln!(file, "# {:>60}", i),
+        }
+        .unwrap();
+    }
+    writeln!(file, "{}", last).unwrap();
+    file.flush().unwrap();
+    drop(file);
+    let size = fs::metadata(&path).unwrap().len();
+    (path, size)
+}
+
+// one test, so nothing else is allocating while the memory is measured
+#[test]
+fn test_bounded_memory() {
+    let (path, size) = big_list("big-list.txt", "slate");
+    assert!(size > 15_000_000);
+
+    let before = reset_peak();
+    let words = wordlist::load(&path).unwrap();
+    let peak = PEAK.load(Ordering::SeqCst) - before;
+    assert_eq!(301, words.len());
+    assert_eq!(Some("slate"), words.get(300));
+    assert!(
+        peak < 64 * 1024,
+        "reading a {} byte list used {} bytes",
+        size,
+        peak
+    );
+    fs::remove_file(path).unwrap();
+
+    // and the problems in it are found exactly where they are
+    let (path, _) = big_list("big-list-invalid.txt", "  sl4te");
+    match wordlist::load(&path) {
+        Err(LoadError::Invalid { issue, .. }) => {
+            assert_eq!(
+                (300_001, 5, Problem::NotAlphabetic),
+                (issue.line, issue.column, issue.problem)
+            );
+        }
+        other => panic!("{:?}", other),
+    }
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,175 @@
+//! End-to-end tests of finding the word lists, with and without the built-in ones
+
+// Copyright (C) 2022 Charles German <5donuts@protonmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use assert_cmd::Command;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+/// An empty directory to run in, so there are no word list files
+fn empty_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The `wordle` binary in batch mode, in `dir`
+fn wordle(dir: &Path, args: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("wordle").unwrap();
+    cmd.current_dir(dir).arg("--batch").args(args);
+    cmd
+}
+
+#[cfg(feature = "embedded-wordlists")]
+#[test]
+fn test_missing_files() {
+    let dir = empty_dir("missing-files");
+    wordle(&dir, &["--answer", "crane"])
+        .write_stdin("crane\n")
+        .assert()
+        .code(0)
+        .stdout("crane ggggg win 1\n")
+        .stderr(contains("guesses.txt").and(contains("using the built-in word lists")));
+}
+
+#[cfg(not(feature = "embedded-wordlists"))]
+#[test]
+fn test_missing_files() {
+    let dir = empty_dir("missing-files");
+    wordle(&dir, &["--answer", "crane"])
+        .write_stdin("crane\n")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(
+            contains(format!(
+                "could not read word list '{}'",
+                dir.join("guesses.txt").display()
+            ))
+            .and(contains("Use --guesses and --answers")),
+        );
+}
+
+#[cfg(feature = "embedded-wordlists")]
+#[test]
+fn test_builtin_words() {
+    // "about" isn't in these lists, but it is in the built-in ones
+    let dir = empty_dir("builtin-words");
+    fs::write(dir.join("guesses.txt"), "crane\nslate\n").unwrap();
+    fs::write(dir.join("answers.txt"), "crane\n").unwrap();
+    wordle(&dir, &["--answer", "about"])
+        .write_stdin("about\n")
+        .assert()
+        .code(2);
+    wordle(&dir, &["--builtin-words", "--answer", "about"])
+        .write_stdin("about\n")
+        .assert()
+        .code(0)
+        .stdout("about ggggg win 1\n")
+        .stderr("");
+}
+
+#[cfg(not(feature = "embedded-wordlists"))]
+#[test]
+fn test_builtin_words() {
+    let dir = empty_dir("builtin-words");
+    wordle(&dir, &["--builtin-words", "--answer", "about"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(contains("no built-in word lists"));
+}
+
+#[test]
+fn test_lang() {
+    let dir = empty_dir("lang");
+    let dicts = dir.join("dicts");
+    fs::create_dir_all(&dicts).unwrap();
+    fs::write(dicts.join("guesses.es.txt"), "niños\nseñor\nplaya\n").unwrap();
+    fs::write(dicts.join("answers.es.txt"), "niños\n").unwrap();
+
+    wordle(&dir, &["--lang", "list", "--dict-dir", "dicts"])
+        .assert()
+        .code(0)
+        .stdout("es\n");
+    wordle(&dir, &["--lang", "es", "--dict-dir", "dicts"])
+        .write_stdin("señor\nniños\n")
+        .assert()
+        .code(0)
+        .stdout("señor y-gg- playing 1\nniños ggggg win 2\n");
+
+    // the error names the directory that was searched
+    wordle(&dir, &["--lang", "fr", "--dict-dir", "dicts"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(contains(format!(
+            "no word lists for language 'fr' in '{}'",
+            dicts.display()
+        )));
+}
+
+/// A word list in `tests/data/lists`
+fn fixture(name: &str) -> String {
+    format!("{}/tests/data/lists/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_list_formats() {
+    let dir = empty_dir("list-formats");
+    for name in [
+        "crlf.txt",
+        "bom.txt",
+        "comments.txt",
+        "blank-lines.txt",
+        "mixed-case.txt",
+    ] {
+        let list = fixture(name);
+        wordle(
+            &dir,
+            &["--guesses", &list, "--answers", &list, "--answer", "slate"],
+        )
+        .write_stdin("irate\nslate\n")
+        .assert()
+        .code(0)
+        .stdout("irate --ggg playing 1\nslate ggggg win 2\n")
+        .stderr("");
+    }
+}
+
+#[test]
+fn test_invalid_list() {
+    let dir = empty_dir("invalid-list");
+    let list = fixture("invalid.txt");
+    // the bad word is reported rather than used, even with the built-in lists to fall back on
+    wordle(
+        &dir,
+        &["--guesses", &list, "--answers", &fixture("bom.txt")],
+    )
+    .write_stdin("crane\n")
+    .assert()
+    .code(1)
+    .stdout("")
+    .stderr(format!(
+        "Error: invalid word list '{}': line 3, column 3: 'cr4ne' has characters that aren't letters\n",
+        list
+    ));
+}